@@ -0,0 +1,71 @@
+//! `cargo bench -p sudoku-benches`, or `-- solve` for one group. Reports
+//! land in `target/criterion/`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use sudoku_benches::{fixtures, technique_positions, SEED};
+use sudoku_core::{Difficulty, PuzzleId, Solver};
+
+fn solve(c: &mut Criterion) {
+    let solver = Solver::new();
+    let mut group = c.benchmark_group("solve");
+    for (difficulty, grid) in fixtures() {
+        group.bench_with_input(BenchmarkId::from_parameter(difficulty), &grid, |b, g| {
+            b.iter(|| solver.solve(black_box(g)))
+        });
+    }
+    group.finish();
+}
+
+fn count_solutions(c: &mut Criterion) {
+    let solver = Solver::new();
+    let mut group = c.benchmark_group("count_solutions");
+    for (difficulty, grid) in fixtures() {
+        group.bench_with_input(BenchmarkId::from_parameter(difficulty), &grid, |b, g| {
+            b.iter(|| solver.count_solutions(black_box(g), 2))
+        });
+    }
+    group.finish();
+}
+
+fn rate_se(c: &mut Criterion) {
+    let solver = Solver::new();
+    let mut group = c.benchmark_group("rate_se");
+    group.sample_size(10);
+    for (difficulty, grid) in fixtures() {
+        group.bench_with_input(BenchmarkId::from_parameter(difficulty), &grid, |b, g| {
+            b.iter(|| solver.rate_se(black_box(g)))
+        });
+    }
+    group.finish();
+}
+
+/// Latency of `get_hint` at a position whose next step is each technique
+fn hint(c: &mut Criterion) {
+    let solver = Solver::new();
+    let mut group = c.benchmark_group("hint");
+    for (technique, grid) in technique_positions() {
+        group.bench_with_input(BenchmarkId::from_parameter(technique), &grid, |b, g| {
+            b.iter(|| solver.get_hint(black_box(g)))
+        });
+    }
+    group.finish();
+}
+
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(10);
+    for &difficulty in Difficulty::standard_levels().iter() {
+        let id = PuzzleId {
+            difficulty,
+            seed: SEED,
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(difficulty), &id, |b, id| {
+            b.iter(|| black_box(id).generate())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, solve, count_solutions, rate_se, hint, generate);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+//! Fixed puzzles for the benchmarks. Every fixture comes from a constant
+//! seed, so runs on different machines and commits measure the same work.
+
+use std::collections::BTreeMap;
+use sudoku_core::{Difficulty, Grid, PuzzleId, Solver};
+use sudoku_test_utils::apply_hint;
+
+/// Seed of every fixture puzzle
+pub const SEED: u64 = 0x5EED_2024;
+
+/// Hints followed while collecting technique positions
+const MAX_STEPS: usize = 200;
+
+/// The fixture puzzle for a level
+pub fn puzzle(difficulty: Difficulty) -> Grid {
+    PuzzleId {
+        difficulty,
+        seed: SEED,
+    }
+    .generate()
+}
+
+/// One fixture per level, easiest first
+pub fn fixtures() -> Vec<(Difficulty, Grid)> {
+    Difficulty::all_levels()
+        .iter()
+        .map(|&d| (d, puzzle(d)))
+        .collect()
+}
+
+/// For each technique the solver uses on the fixtures, the first position
+/// where it is the next hint, with candidates filled in
+pub fn technique_positions() -> BTreeMap<String, Grid> {
+    let solver = Solver::new();
+    let mut positions = BTreeMap::new();
+    for (_, mut grid) in fixtures() {
+        grid.recalculate_candidates();
+        for _ in 0..MAX_STEPS {
+            if grid.is_complete() {
+                break;
+            }
+            let Some(hint) = solver.get_hint(&grid) else {
+                break;
+            };
+            positions
+                .entry(hint.technique.to_string())
+                .or_insert_with(|| grid.deep_clone());
+            if !apply_hint(&mut grid, &hint) {
+                break;
+            }
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_are_stable() {
+        let easy = puzzle(Difficulty::Easy);
+        assert_eq!(
+            easy.to_string_compact(),
+            puzzle(Difficulty::Easy).to_string_compact()
+        );
+        assert_eq!(Solver::new().count_solutions(&easy, 2), 1);
+    }
+}
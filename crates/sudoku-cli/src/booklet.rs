@@ -0,0 +1,362 @@
+//! Printable booklets: puzzles laid out several to a page in a PDF, with the
+//! solutions in an appendix. Grids come from `sudoku-render`; the PDF is
+//! written by hand, since pages only need its shapes and the standard
+//! Helvetica fonts, which readers supply themselves.
+
+use clap::ValueEnum;
+use sudoku_render::{Board, Color, Palette, Scene, Shape, Style};
+
+/// Page size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Paper {
+    A4,
+    Letter,
+}
+
+impl Paper {
+    /// Width and height in points
+    fn size(self) -> (f32, f32) {
+        match self {
+            Paper::A4 => (595.0, 842.0),
+            Paper::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+/// One puzzle to print
+pub struct Entry {
+    /// Shown above the grid, e.g. "No. 3 - Hard (SE 4.2)"
+    pub caption: String,
+    pub puzzle: String,
+    pub solution: String,
+}
+
+const MARGIN: f32 = 48.0;
+/// Room at the top of each page for its heading
+const HEADER: f32 = 32.0;
+/// Room above each grid for its caption
+const CAPTION: f32 = 16.0;
+/// Advance width of a digit in Helvetica, per point of font size
+const DIGIT_WIDTH: f32 = 0.556;
+/// Solutions are printed small, this many to a page
+const SOLUTIONS_PER_PAGE: usize = 12;
+
+/// Lay out `entries` `per_page` to a page, followed by their solutions,
+/// and return the PDF file
+pub fn booklet(title: &str, entries: &[Entry], per_page: usize, paper: Paper) -> Vec<u8> {
+    let per_page = per_page.max(1);
+    let mut pages = Vec::new();
+    for chunk in entries.chunks(per_page) {
+        let mut page = Page::new(paper);
+        page.header(title, pages.len() + 1);
+        let grids: Vec<_> = chunk
+            .iter()
+            .map(|e| (e.caption.as_str(), e.puzzle.as_str(), None))
+            .collect();
+        page.grids(&grids, per_page);
+        pages.push(page.content);
+    }
+    for chunk in entries.chunks(SOLUTIONS_PER_PAGE) {
+        let mut page = Page::new(paper);
+        page.header(&format!("{} - Solutions", title), pages.len() + 1);
+        let grids: Vec<_> = chunk
+            .iter()
+            .map(|e| {
+                (
+                    e.caption.as_str(),
+                    e.puzzle.as_str(),
+                    Some(e.solution.as_str()),
+                )
+            })
+            .collect();
+        page.grids(&grids, SOLUTIONS_PER_PAGE);
+        pages.push(page.content);
+    }
+    assemble(paper, &pages)
+}
+
+/// A page's content stream, drawn with y measured down from the top
+struct Page {
+    width: f32,
+    height: f32,
+    content: String,
+}
+
+impl Page {
+    fn new(paper: Paper) -> Self {
+        let (width, height) = paper.size();
+        Page {
+            width,
+            height,
+            content: String::new(),
+        }
+    }
+
+    /// `font` is "F1" (Helvetica) or "F2" (Helvetica-Bold)
+    fn text(&mut self, font: &str, size: f32, x: f32, y: f32, text: &str) {
+        self.content.push_str(&format!(
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font,
+            size,
+            x,
+            self.height - y,
+            escape(text)
+        ));
+    }
+
+    fn line(&mut self, width: f32, (x0, y0): (f32, f32), (x1, y1): (f32, f32)) {
+        self.content.push_str(&format!(
+            "{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n",
+            width,
+            x0,
+            self.height - y0,
+            x1,
+            self.height - y1
+        ));
+    }
+
+    /// Booklet title on the left, page number on the right
+    fn header(&mut self, title: &str, number: usize) {
+        self.text("F2", 14.0, MARGIN, MARGIN, title);
+        let number = number.to_string();
+        let x = self.width - MARGIN - number.len() as f32 * DIGIT_WIDTH * 10.0;
+        self.text("F1", 10.0, x, MARGIN, &number);
+    }
+
+    /// Captioned grids in `slots` equal slots below the header. With a
+    /// solution, the givens are bold and the rest regular.
+    fn grids(&mut self, grids: &[(&str, &str, Option<&str>)], slots: usize) {
+        let columns = match slots {
+            1 | 2 => 1,
+            3..=8 => 2,
+            _ => 3,
+        };
+        let rows = slots.div_ceil(columns);
+        let slot_w = (self.width - 2.0 * MARGIN) / columns as f32;
+        let slot_h = (self.height - 2.0 * MARGIN - HEADER) / rows as f32;
+        let size = (slot_w * 0.9).min(slot_h - CAPTION * 2.0);
+        for (i, &(caption, puzzle, solution)) in grids.iter().enumerate() {
+            let x = MARGIN + slot_w * (i % columns) as f32 + (slot_w - size) / 2.0;
+            let y = MARGIN + HEADER + slot_h * (i / columns) as f32 + CAPTION;
+            let caption_size = if solution.is_some() { 8.0 } else { 10.0 };
+            self.text("F1", caption_size, x, y - 5.0, caption);
+            self.grid(x, y, size, puzzle, solution);
+        }
+    }
+
+    fn grid(&mut self, x: f32, y: f32, size: f32, puzzle: &str, solution: Option<&str>) {
+        let Some(mut board) = Board::from_puzzle(puzzle) else {
+            return;
+        };
+        if let Some(solution) = solution {
+            board.fill(solution);
+        }
+        let style = Style {
+            cell: size / 9.0,
+            margin: 0.0,
+            candidates: false,
+            palette: Palette::print(),
+        };
+        self.scene(x, y, &sudoku_render::layout(&board, &style));
+    }
+
+    /// Draw a rendered board with its top left corner at `x`, `y`
+    fn scene(&mut self, x: f32, y: f32, scene: &Scene) {
+        for shape in &scene.shapes {
+            match *shape {
+                Shape::Rect {
+                    x: left,
+                    y: top,
+                    width,
+                    height,
+                    fill,
+                } => self.content.push_str(&format!(
+                    "{} rg {:.2} {:.2} {:.2} {:.2} re f\n",
+                    rgb(fill),
+                    x + left,
+                    self.height - y - top - height,
+                    width,
+                    height
+                )),
+                Shape::Line {
+                    from,
+                    to,
+                    width,
+                    color,
+                    dashed,
+                } => {
+                    self.content.push_str(&format!("{} RG ", rgb(color)));
+                    if dashed {
+                        self.content.push_str(&format!(
+                            "[{:.2} {:.2}] 0 d ",
+                            width * 3.0,
+                            width * 2.0
+                        ));
+                    }
+                    self.line(width, (x + from.0, y + from.1), (x + to.0, y + to.1));
+                    if dashed {
+                        self.content.push_str("[] 0 d\n");
+                    }
+                }
+                Shape::Circle {
+                    center,
+                    radius,
+                    width,
+                    color,
+                } => {
+                    // Four Bézier quarter arcs, counterclockwise from the right
+                    let (cx, cy) = (x + center.0, self.height - y - center.1);
+                    let (r, k) = (radius, radius * 0.5523);
+                    let mut path =
+                        format!("{} RG {:.2} w {:.2} {:.2} m", rgb(color), width, cx + r, cy);
+                    for (cos, sin) in [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)] {
+                        let (ex, ey) = (cx - r * sin, cy + r * cos);
+                        path.push_str(&format!(
+                            " {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c",
+                            cx + r * cos - k * sin,
+                            cy + r * sin + k * cos,
+                            ex + k * cos,
+                            ey + k * sin,
+                            ex,
+                            ey
+                        ));
+                    }
+                    self.content.push_str(&path);
+                    self.content.push_str(" S\n");
+                }
+                Shape::Digit {
+                    center,
+                    size,
+                    digit,
+                    bold,
+                    color,
+                } => {
+                    self.content.push_str(&format!("{} rg ", rgb(color)));
+                    self.text(
+                        if bold { "F2" } else { "F1" },
+                        size,
+                        x + center.0 - size * DIGIT_WIDTH / 2.0,
+                        y + center.1 + size * 0.36,
+                        &digit.to_string(),
+                    );
+                }
+            }
+        }
+        self.content.push_str("0 g 0 G\n");
+    }
+}
+
+/// A color as PDF RGB operands
+fn rgb(color: Color) -> String {
+    format!(
+        "{:.3} {:.3} {:.3}",
+        f32::from(color.0) / 255.0,
+        f32::from(color.1) / 255.0,
+        f32::from(color.2) / 255.0
+    )
+}
+
+/// Escape text for a PDF string in WinAnsi encoding; characters it can't
+/// show become "?"
+fn escape(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            '\u{a0}'..='\u{ff}' => out.push_str(&format!("\\{:03o}", c as u32)),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+/// Wrap content streams into a PDF file
+fn assemble(paper: Paper, pages: &[String]) -> Vec<u8> {
+    let (width, height) = paper.size();
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", 5 + 2 * i))
+        .collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (i, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            width,
+            height,
+            6 + 2 * i
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).bytes());
+    }
+    let xref = out.len();
+    out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+    for offset in offsets {
+        out.extend(format!("{:010} 00000 n \n", offset).bytes());
+    }
+    out.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .bytes(),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_booklet_pages() {
+        let entries: Vec<Entry> = (0..5)
+            .map(|_| Entry {
+                caption: "No. 1 (easy)".to_string(),
+                puzzle: PUZZLE.to_string(),
+                solution: solution(Tier::Singles),
+            })
+            .collect();
+        let pdf = booklet("Café", &entries, 4, Paper::A4);
+        let text = String::from_utf8(pdf).unwrap();
+
+        // Two pages of puzzles and one of solutions
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Count 3 >>"));
+        assert!(text.contains("(No. 1 \\(easy\\)) Tj"));
+        assert!(text.contains("(Caf\\351) Tj"));
+
+        // The cross-reference table is where the trailer says
+        let start: usize = text.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert!(text[start..].starts_with("xref\n"));
+        assert!(text.ends_with("%%EOF\n"));
+    }
+}
@@ -0,0 +1,210 @@
+//! Removing givens while the solution stays unique: minimizing a puzzle,
+//! digging symmetric puzzles out of a solved grid, and fitting givens to a
+//! fixed pattern.
+
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use sudoku_core::{Difficulty, Grid, PuzzleId, Solver};
+
+/// Pattern the givens keep when cells are removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Symmetry {
+    None,
+    /// 180° rotation about the center
+    Rotational,
+    /// Left-right mirror
+    Mirror,
+    /// Reflection in the main diagonal
+    Diagonal,
+}
+
+impl Symmetry {
+    /// Cells that must be cleared together with cell `i`
+    pub fn orbit(self, i: usize) -> Vec<usize> {
+        let (row, col) = (i / 9, i % 9);
+        let partner = match self {
+            Symmetry::None => i,
+            Symmetry::Rotational => 80 - i,
+            Symmetry::Mirror => row * 9 + (8 - col),
+            Symmetry::Diagonal => col * 9 + row,
+        };
+        if partner == i {
+            vec![i]
+        } else {
+            vec![i, partner]
+        }
+    }
+
+    /// Whether the givens of `puzzle` follow this pattern
+    pub fn matches(self, puzzle: &str) -> bool {
+        let cells = puzzle.as_bytes();
+        (0..81).all(|i| {
+            self.orbit(i)
+                .iter()
+                .all(|&j| (cells[i] == b'.') == (cells[j] == b'.'))
+        })
+    }
+}
+
+fn is_unique(puzzle: &str) -> bool {
+    Grid::from_string(puzzle).is_some_and(|grid| Solver::new().count_solutions(&grid, 2) == 1)
+}
+
+/// Clear cells in `order` (whole orbits at a time) wherever the puzzle stays
+/// unique, so no remaining given can be removed
+fn dig(puzzle: &str, symmetry: Symmetry, order: &[usize]) -> String {
+    let mut cells: Vec<u8> = puzzle.bytes().collect();
+    for &i in order {
+        let orbit = symmetry.orbit(i);
+        if orbit.iter().any(|&j| cells[j] == b'.') {
+            continue;
+        }
+        let saved: Vec<u8> = orbit.iter().map(|&j| cells[j]).collect();
+        for &j in &orbit {
+            cells[j] = b'.';
+        }
+        if !is_unique(std::str::from_utf8(&cells).unwrap_or_default()) {
+            for (&j, &value) in orbit.iter().zip(&saved) {
+                cells[j] = value;
+            }
+        }
+    }
+    String::from_utf8(cells).unwrap_or_default()
+}
+
+/// Remove every given that isn't needed for a unique solution, trying cells
+/// in reading order so the result is repeatable
+pub fn minimize(puzzle: &str) -> Result<String, String> {
+    if !is_unique(puzzle) {
+        return Err("puzzle must have exactly one solution".to_string());
+    }
+    let order: Vec<usize> = (0..81).collect();
+    Ok(dig(puzzle, Symmetry::None, &order))
+}
+
+/// Generate a puzzle whose givens follow `symmetry`, retrying up to
+/// `attempts` times for one the solver rates as `difficulty`
+pub fn generate_symmetric(
+    difficulty: Difficulty,
+    symmetry: Symmetry,
+    attempts: usize,
+) -> Option<String> {
+    let solver = Solver::new();
+    let mut rng = rand::thread_rng();
+    for _ in 0..attempts {
+        let seed = PuzzleId::random(difficulty).generate();
+        let Some(solution) = solver.solve(&seed) else {
+            continue;
+        };
+        let solution = solution.to_string_compact();
+        let mut order: Vec<usize> = (0..81).collect();
+        order.shuffle(&mut rng);
+        let puzzle = dig(&solution, symmetry, &order);
+        let grid = Grid::from_string(&puzzle)?;
+        if solver.rate_difficulty(&grid) == difficulty {
+            return Some(puzzle);
+        }
+    }
+    None
+}
+
+/// Where the givens go, from a grid marking them with digits or `x`, `X`,
+/// `#` or `*`, and empty cells with `.`, `0` or `_`; other characters are
+/// ignored
+pub fn parse_pattern(text: &str) -> Option<[bool; 81]> {
+    let cells: Vec<bool> = text
+        .chars()
+        .filter_map(|c| match c {
+            '1'..='9' | 'x' | 'X' | '#' | '*' => Some(true),
+            '.' | '0' | '_' => Some(false),
+            _ => None,
+        })
+        .collect();
+    cells.try_into().ok()
+}
+
+/// Generate a puzzle whose givens are exactly the cells set in `mask`,
+/// trying up to `attempts` shuffles of a solved grid for one with a unique
+/// solution that the solver rates as `difficulty`
+pub fn generate_with_pattern(
+    mask: &[bool; 81],
+    difficulty: Difficulty,
+    attempts: usize,
+) -> Option<String> {
+    let solver = Solver::new();
+    let mut rng = rand::thread_rng();
+    let seed = PuzzleId::random(difficulty).generate();
+    let solution = solver.solve(&seed)?.to_string_compact();
+    for _ in 0..attempts {
+        let grid = shuffled(solution.as_bytes(), &mut rng);
+        let puzzle: String = grid
+            .iter()
+            .zip(mask)
+            .map(|(&c, &given)| if given { c as char } else { '.' })
+            .collect();
+        if !is_unique(&puzzle) {
+            continue;
+        }
+        let rated = Grid::from_string(&puzzle).map(|grid| solver.rate_difficulty(&grid));
+        if rated == Some(difficulty) {
+            return Some(puzzle);
+        }
+    }
+    None
+}
+
+/// An equivalent grid: bands, stacks, and the rows and columns within
+/// them, reordered at random
+fn shuffled(solution: &[u8], rng: &mut impl Rng) -> Vec<u8> {
+    let rows = lines(rng);
+    let cols = lines(rng);
+    (0..81)
+        .map(|i| solution[rows[i / 9] * 9 + cols[i % 9]])
+        .collect()
+}
+
+/// A random order of the nine rows (or columns) keeping each band of three
+/// together
+fn lines(rng: &mut impl Rng) -> Vec<usize> {
+    let mut bands = [0, 1, 2];
+    bands.shuffle(rng);
+    let mut order = Vec::with_capacity(9);
+    for band in bands {
+        let mut within = [0, 1, 2];
+        within.shuffle(rng);
+        order.extend(within.map(|line| band * 3 + line));
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    #[test]
+    fn test_symmetry_and_minimize() {
+        assert_eq!(Symmetry::Rotational.orbit(0), vec![0, 80]);
+        assert_eq!(Symmetry::Rotational.orbit(40), vec![40]);
+        assert_eq!(Symmetry::Mirror.orbit(9), vec![9, 17]);
+        assert_eq!(Symmetry::Diagonal.orbit(1), vec![1, 9]);
+
+        let puzzle = solution(Tier::Singles);
+        let minimal = minimize(&puzzle).unwrap();
+        assert!(is_unique(&minimal));
+        assert!(minimal.matches(|c| c == '.').count() > 50);
+        assert!(Symmetry::None.matches(&minimal));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let mask = parse_pattern(&format!("x#{}", ".".repeat(79))).unwrap();
+        assert!(mask[0] && mask[1] && !mask[2]);
+        assert!(parse_pattern("x.x").is_none());
+
+        let solution = solution(Tier::Singles);
+        let grid = shuffled(solution.as_bytes(), &mut rand::thread_rng());
+        assert!(is_unique(std::str::from_utf8(&grid).unwrap()));
+    }
+}
@@ -0,0 +1,132 @@
+//! Reading puzzles from arguments, files and stdin, and writing them back out
+//! in the formats the frontends understand.
+
+use clap::ValueEnum;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use sudoku_render::Board;
+use sudoku_session::parse_puzzle_text;
+
+/// How a puzzle is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// 81 characters on one line, "." for empty cells
+    Line,
+    /// Nine lines of nine characters (.sdk)
+    Sdk,
+    /// A drawn grid with box borders
+    Grid,
+    /// One Braille cell per square, for refreshable displays
+    Braille,
+    /// Digits three lines tall in block characters
+    Large,
+}
+
+impl Format {
+    /// Write an 81-character puzzle string in this format
+    pub fn render(self, puzzle: &str) -> String {
+        let cells: Vec<char> = puzzle.chars().collect();
+        match self {
+            Format::Line => puzzle.to_string(),
+            Format::Sdk => cells
+                .chunks(9)
+                .map(|row| row.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Format::Grid => {
+                let separator = "------+-------+------";
+                let mut lines = Vec::new();
+                for (r, row) in cells.chunks(9).enumerate() {
+                    if r > 0 && r % 3 == 0 {
+                        lines.push(separator.to_string());
+                    }
+                    let boxes: Vec<String> = row
+                        .chunks(3)
+                        .map(|chunk| {
+                            chunk
+                                .iter()
+                                .map(|c| c.to_string())
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        })
+                        .collect();
+                    lines.push(boxes.join(" | "));
+                }
+                lines.join("\n")
+            }
+            Format::Braille | Format::Large => {
+                let Some(board) = Board::from_puzzle(puzzle) else {
+                    return puzzle.to_string();
+                };
+                let lines = if self == Format::Braille {
+                    sudoku_render::braille(&board)
+                } else {
+                    sudoku_render::large_digits(&board)
+                };
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+/// Every puzzle in `text`: one per line if each non-empty line holds a whole
+/// puzzle (as `generate` prints them), otherwise the text as a single grid
+pub fn parse_puzzles(text: &str) -> Result<Vec<String>, String> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(['#', '[']))
+        .collect();
+    let per_line: Option<Vec<String>> = lines.iter().map(|l| parse_puzzle_text(l)).collect();
+    match per_line {
+        Some(puzzles) if !puzzles.is_empty() => Ok(puzzles),
+        _ => parse_puzzle_text(text)
+            .map(|puzzle| vec![puzzle])
+            .ok_or_else(|| "expected 81 cells (digits, '.' or '0')".to_string()),
+    }
+}
+
+/// Resolve a puzzle argument: a literal puzzle, a file, or stdin when it is
+/// absent or "-"
+pub fn read_puzzles(arg: Option<&str>) -> Result<Vec<String>, String> {
+    parse_puzzles(&read_text(arg)?)
+}
+
+/// The text of a PUZZLE argument: the argument itself, a file's contents,
+/// or stdin for "-" and no argument
+pub fn read_text(arg: Option<&str>) -> Result<String, String> {
+    Ok(match arg {
+        None | Some("-") => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("stdin: {}", e))?;
+            text
+        }
+        Some(arg) if Path::new(arg).is_file() => {
+            fs::read_to_string(arg).map_err(|e| format!("{}: {}", arg, e))?
+        }
+        Some(arg) => arg.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_round_trip() {
+        for format in [Format::Line, Format::Sdk, Format::Grid] {
+            let text = format.render(PUZZLE);
+            assert_eq!(parse_puzzles(&text), Ok(vec![PUZZLE.to_string()]));
+        }
+        let two = format!("{}\n# comment\n{}\n", PUZZLE, PUZZLE.replace('.', "0"));
+        assert_eq!(parse_puzzles(&two).unwrap().len(), 2);
+        assert!(parse_puzzles("123").is_err());
+        assert_eq!(Format::Braille.render(PUZZLE).lines().count(), 9);
+    }
+}
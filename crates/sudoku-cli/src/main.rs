@@ -0,0 +1,624 @@
+mod booklet;
+mod dig;
+mod format;
+mod variant;
+
+use booklet::Paper;
+use clap::{Parser, Subcommand};
+use dig::Symmetry;
+use format::Format;
+use serde::{Deserialize, Serialize};
+use sudoku_core::{Difficulty, Generator, Grid, HintType, PuzzleId, Solver};
+use sudoku_db::PuzzleRegistry;
+use variant::Variant;
+
+#[derive(Parser)]
+#[command(name = "sudoku-cli", about = "Generate, solve and rate Sudoku puzzles")]
+struct Cli {
+    /// Print results as JSON, one object per puzzle and line
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// PUZZLE arguments take an 81-character string, a file, or "-"; without
+/// one, puzzles are read from stdin, one per line or as a single grid
+#[derive(Subcommand)]
+enum Command {
+    /// Generate new puzzles
+    Generate {
+        /// Difficulty level, by name
+        #[arg(short, long, value_parser = parse_difficulty, default_value = "medium")]
+        difficulty: Difficulty,
+        /// Target SE rating instead of a difficulty level
+        #[arg(long, conflicts_with_all = ["difficulty", "symmetry"])]
+        se: Option<f32>,
+        /// Pattern of the givens
+        #[arg(long, value_enum, default_value_t = Symmetry::None)]
+        symmetry: Symmetry,
+        /// Exact cells for the givens, for shape and letter art: a grid or
+        /// file marking them with digits or `x` and empty cells with `.`
+        #[arg(long, conflicts_with_all = ["se", "symmetry"])]
+        pattern: Option<String>,
+        /// How many puzzles to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+        #[arg(short, long, value_enum, default_value_t = Format::Line)]
+        format: Format,
+        /// File of puzzles already issued: new puzzles equivalent to one of
+        /// them are generated again, and the rest are added
+        #[arg(long)]
+        registry: Option<std::path::PathBuf>,
+    },
+    /// Print the solution of each puzzle
+    Solve {
+        puzzle: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = Format::Line)]
+        format: Format,
+        #[arg(long, value_enum, default_value_t = Variant::Classic)]
+        variant: Variant,
+    },
+    /// Rate each puzzle: difficulty level, SE rating and clue count
+    Rate { puzzle: Option<String> },
+    /// Show the next logical step for each puzzle
+    Hint { puzzle: Option<String> },
+    /// Check that each puzzle has exactly one solution; exits with 1 if any
+    /// doesn't
+    Validate {
+        puzzle: Option<String>,
+        #[arg(long, value_enum, default_value_t = Variant::Classic)]
+        variant: Variant,
+        /// List up to N solutions of puzzles that have more than one
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        solutions: usize,
+    },
+    /// Rewrite puzzles in another format
+    Convert {
+        puzzle: Option<String>,
+        #[arg(short, long, value_enum)]
+        to: Format,
+    },
+    /// Print puzzles for reading: large block digits, or `--format braille`
+    /// for a Braille display
+    Show {
+        puzzle: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = Format::Large)]
+        format: Format,
+    },
+    /// Remove every given that isn't needed for a unique solution
+    Minimize {
+        puzzle: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = Format::Line)]
+        format: Format,
+    },
+    /// Rate puzzles and write them as a puzzle pack the TUI can load
+    Pack {
+        /// Pack name
+        name: String,
+        puzzle: Option<String>,
+        #[arg(long)]
+        author: Option<String>,
+        /// File of puzzles already issued: equivalent puzzles are left out
+        /// of the pack, and the rest are added
+        #[arg(long)]
+        registry: Option<std::path::PathBuf>,
+    },
+    /// Write a pack per difficulty of puzzles that practise each technique,
+    /// for the TUI's pack browser
+    Tutorials {
+        /// Directory to write the packs to
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+        /// Puzzles per technique
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=5))]
+        per_technique: u8,
+    },
+    /// Lay puzzles out in a printable PDF, with the solutions at the back
+    Booklet {
+        /// A pack as written by `pack`, or puzzles in any format
+        puzzle: Option<String>,
+        /// Where to write the PDF
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+        /// Heading on each page; defaults to the pack's name
+        #[arg(long)]
+        title: Option<String>,
+        /// Puzzles per page
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=12))]
+        per_page: u8,
+        #[arg(long, value_enum, default_value_t = Paper::A4)]
+        paper: Paper,
+    },
+    /// Read a puzzle from a photo or scan of a printed grid; exits with 1 if
+    /// any cell needs checking
+    #[cfg(feature = "ocr")]
+    Scan {
+        /// PNG or JPEG image, cropped roughly to the grid
+        image: std::path::PathBuf,
+        /// Cells read with less confidence than this (0-1) are listed for
+        /// checking
+        #[arg(long, default_value_t = 0.6)]
+        min_confidence: f32,
+        #[arg(short, long, value_enum, default_value_t = Format::Line)]
+        format: Format,
+    },
+}
+
+/// Symmetric puzzles are dug at random; give up after this many tries at
+/// the requested difficulty
+const SYMMETRY_ATTEMPTS: usize = 50;
+
+/// Shuffled grids tried for one that fits a `--pattern` uniquely at the
+/// requested difficulty
+const PATTERN_ATTEMPTS: usize = 500;
+
+/// Generated puzzles in a row that may turn out to be already issued
+const REGISTRY_ATTEMPTS: usize = 20;
+
+fn main() {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Run a command; Ok(false) when some puzzle failed (no solution, not
+/// unique) but the input itself was fine
+fn run(cli: Cli) -> Result<bool, String> {
+    let json = cli.json;
+    match cli.command {
+        Command::Generate {
+            difficulty,
+            se,
+            symmetry,
+            pattern,
+            count,
+            format,
+            registry: registry_path,
+        } => {
+            let mut registry = load_registry(registry_path.as_deref())?;
+            let pattern = match pattern {
+                Some(arg) => Some(
+                    dig::parse_pattern(&format::read_text(Some(&arg))?)
+                        .ok_or("pattern must have 81 cells (`x` or digits, and `.`)")?,
+                ),
+                None => None,
+            };
+            let generate = || match (&pattern, se, symmetry) {
+                (Some(mask), _, _) => {
+                    dig::generate_with_pattern(mask, difficulty, PATTERN_ATTEMPTS)
+                }
+                (None, Some(target), _) => {
+                    Some(Generator::new().generate_for_se(target).to_string_compact())
+                }
+                (None, None, Symmetry::None) => {
+                    Some(PuzzleId::random(difficulty).generate().to_string_compact())
+                }
+                (None, None, symmetry) => {
+                    dig::generate_symmetric(difficulty, symmetry, SYMMETRY_ATTEMPTS)
+                }
+            };
+            let mut result = Ok(true);
+            for i in 0..count {
+                let puzzle = match registry.as_mut() {
+                    Some(registry) => registry.issue(REGISTRY_ATTEMPTS, generate),
+                    None => generate(),
+                };
+                let Some(puzzle) = puzzle else {
+                    result = Err(if registry.is_some() {
+                        format!(
+                            "no puzzle not already issued in {} tries",
+                            REGISTRY_ATTEMPTS
+                        )
+                    } else if pattern.is_some() {
+                        format!("no {} puzzle fits the pattern", difficulty)
+                    } else {
+                        format!("no {:?} {} puzzle found", symmetry, difficulty)
+                    });
+                    break;
+                };
+                let puzzle = normalize(&puzzle);
+                if json {
+                    print_json(&rating(&puzzle)?);
+                } else {
+                    print_puzzle(format, &puzzle, i);
+                }
+            }
+            save_registry(registry.as_ref(), registry_path.as_deref())?;
+            result
+        }
+        Command::Solve {
+            puzzle,
+            format,
+            variant,
+        } => {
+            let mut ok = true;
+            for (i, puzzle) in format::read_puzzles(puzzle.as_deref())?.iter().enumerate() {
+                match variant.solve(puzzle) {
+                    Some(solution) => {
+                        let solution = normalize(&solution);
+                        if json {
+                            print_json(&serde_json::json!({
+                                "puzzle": puzzle,
+                                "solution": solution,
+                            }));
+                        } else {
+                            print_puzzle(format, &solution, i);
+                        }
+                    }
+                    None => {
+                        eprintln!("{}: no solution", puzzle);
+                        ok = false;
+                    }
+                }
+            }
+            Ok(ok)
+        }
+        Command::Rate { puzzle } => {
+            for puzzle in format::read_puzzles(puzzle.as_deref())? {
+                let rating = rating(&puzzle)?;
+                if json {
+                    print_json(&rating);
+                } else {
+                    println!(
+                        "{}  {:<12} SE {:>4.1}  {} clues",
+                        rating.puzzle, rating.difficulty, rating.se_rating, rating.clues
+                    );
+                }
+            }
+            Ok(true)
+        }
+        Command::Hint { puzzle } => {
+            let mut ok = true;
+            for puzzle in format::read_puzzles(puzzle.as_deref())? {
+                let Some(mut grid) = Grid::from_string(&puzzle) else {
+                    return Err(format!("{}: could not be read", puzzle));
+                };
+                grid.recalculate_candidates();
+                let Some(hint) = Solver::new().get_hint(&grid) else {
+                    eprintln!("{}: no logical step found", puzzle);
+                    ok = false;
+                    continue;
+                };
+                let step = match &hint.hint_type {
+                    HintType::SetValue { pos, value } => {
+                        format!("r{}c{}={}", pos.row + 1, pos.col + 1, value)
+                    }
+                    HintType::EliminateCandidates { pos, values } => {
+                        let digits: String = values.iter().map(|v| v.to_string()).collect();
+                        format!("r{}c{}<>{}", pos.row + 1, pos.col + 1, digits)
+                    }
+                };
+                if json {
+                    print_json(&serde_json::json!({
+                        "puzzle": puzzle,
+                        "technique": hint.technique.to_string(),
+                        "se_rating": hint.technique.se_rating(),
+                        "step": step,
+                        "explanation": hint.explanation,
+                    }));
+                } else {
+                    println!("{}: {}", hint.technique, step);
+                    println!("  {}", hint.explanation);
+                }
+            }
+            Ok(ok)
+        }
+        Command::Validate {
+            puzzle,
+            variant,
+            solutions: list,
+        } => {
+            let mut ok = true;
+            for puzzle in format::read_puzzles(puzzle.as_deref())? {
+                let solutions = variant.count_solutions(&puzzle, 2);
+                let verdict = match solutions {
+                    0 => "no solution",
+                    1 => "unique",
+                    _ => "multiple solutions",
+                };
+                ok &= solutions == 1;
+                let listed = if solutions > 1 {
+                    variant.solutions(&puzzle, list)
+                } else {
+                    Vec::new()
+                };
+                if json {
+                    print_json(&serde_json::json!({
+                        "puzzle": puzzle,
+                        "valid": solutions == 1,
+                        "result": verdict,
+                        "solutions": listed,
+                    }));
+                } else {
+                    println!("{}  {}", puzzle, verdict);
+                    for solution in &listed {
+                        println!("  {}", solution);
+                    }
+                }
+            }
+            Ok(ok)
+        }
+        Command::Convert { puzzle, to } | Command::Show { puzzle, format: to } => {
+            for (i, puzzle) in format::read_puzzles(puzzle.as_deref())?.iter().enumerate() {
+                print_puzzle(to, puzzle, i);
+            }
+            Ok(true)
+        }
+        Command::Minimize { puzzle, format } => {
+            for (i, puzzle) in format::read_puzzles(puzzle.as_deref())?.iter().enumerate() {
+                let minimal = dig::minimize(puzzle).map_err(|e| format!("{}: {}", puzzle, e))?;
+                if json {
+                    print_json(&rating(&minimal)?);
+                } else {
+                    print_puzzle(format, &minimal, i);
+                }
+            }
+            Ok(true)
+        }
+        Command::Pack {
+            name,
+            puzzle,
+            author,
+            registry: registry_path,
+        } => {
+            let mut registry = load_registry(registry_path.as_deref())?;
+            let mut ok = true;
+            let mut puzzles = Vec::new();
+            for puzzle in format::read_puzzles(puzzle.as_deref())? {
+                let rating = rating(&puzzle)?;
+                if let Some(registry) = registry.as_mut() {
+                    if !registry.insert(&puzzle) {
+                        eprintln!("{}: already issued, left out", puzzle);
+                        ok = false;
+                        continue;
+                    }
+                }
+                puzzles.push(PackPuzzle {
+                    puzzle: rating.puzzle,
+                    title: Some(format!("No. {}", puzzles.len() + 1)),
+                    rating: Some(rating.se_rating),
+                });
+            }
+            let pack = Pack {
+                name,
+                author,
+                puzzles,
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&pack).map_err(|e| e.to_string())?
+            );
+            save_registry(registry.as_ref(), registry_path.as_deref())?;
+            Ok(ok)
+        }
+        Command::Tutorials {
+            output,
+            per_technique,
+        } => {
+            std::fs::create_dir_all(&output).map_err(|e| e.to_string())?;
+            let solver = Solver::new();
+            for difficulty in Difficulty::all_levels() {
+                let mut puzzles = Vec::new();
+                for entry in sudoku_glossary::ENTRIES
+                    .iter()
+                    .filter(|e| e.difficulty == *difficulty)
+                {
+                    let found = sudoku_glossary::tutorials(entry, per_technique.into());
+                    if found.is_empty() {
+                        eprintln!("{}: no puzzle found", entry.name);
+                    }
+                    for (i, puzzle) in found.into_iter().enumerate() {
+                        let grid =
+                            Grid::from_string(&puzzle).ok_or("generated an unreadable puzzle")?;
+                        puzzles.push(PackPuzzle {
+                            title: Some(format!("{} {}", entry.name, i + 1)),
+                            rating: Some(solver.rate_se(&grid)),
+                            puzzle,
+                        });
+                    }
+                }
+                if puzzles.is_empty() {
+                    continue;
+                }
+                let pack = Pack {
+                    name: format!("Tutorial: {}", difficulty),
+                    author: None,
+                    puzzles,
+                };
+                let path = output.join(format!(
+                    "tutorial-{}.json",
+                    difficulty.to_string().to_lowercase()
+                ));
+                let json = serde_json::to_string_pretty(&pack).map_err(|e| e.to_string())?;
+                std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))?;
+                println!("{}", path.display());
+            }
+            Ok(true)
+        }
+        Command::Booklet {
+            puzzle,
+            output,
+            title,
+            per_page,
+            paper,
+        } => {
+            let text = format::read_text(puzzle.as_deref())?;
+            let pack = match serde_json::from_str::<Pack>(&text) {
+                Ok(pack) => pack,
+                Err(_) => Pack {
+                    name: "Sudoku".to_string(),
+                    author: None,
+                    puzzles: format::parse_puzzles(&text)?
+                        .into_iter()
+                        .map(|puzzle| PackPuzzle {
+                            puzzle,
+                            title: None,
+                            rating: None,
+                        })
+                        .collect(),
+                },
+            };
+            let solver = Solver::new();
+            let entries = pack
+                .puzzles
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let puzzle = normalize(&p.puzzle);
+                    let grid = Grid::from_string(&puzzle)
+                        .ok_or_else(|| format!("{}: could not be read", puzzle))?;
+                    let solution = solver
+                        .solve(&grid)
+                        .ok_or_else(|| format!("{}: no solution", puzzle))?;
+                    let mut caption = format!(
+                        "{} - {}",
+                        p.title.clone().unwrap_or_else(|| format!("No. {}", i + 1)),
+                        solver.rate_difficulty(&grid)
+                    );
+                    if let Some(rating) = p.rating {
+                        caption.push_str(&format!(" (SE {:.1})", rating));
+                    }
+                    Ok(booklet::Entry {
+                        caption,
+                        puzzle,
+                        solution: solution.to_string_compact(),
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            let pdf = booklet::booklet(
+                title.as_deref().unwrap_or(&pack.name),
+                &entries,
+                per_page.into(),
+                paper,
+            );
+            std::fs::write(&output, pdf).map_err(|e| format!("{}: {}", output.display(), e))?;
+            Ok(true)
+        }
+        #[cfg(feature = "ocr")]
+        Command::Scan {
+            image,
+            min_confidence,
+            format,
+        } => {
+            let scan = sudoku_ocr::scan_file(&image).map_err(|e| e.to_string())?;
+            let puzzle = scan.puzzle();
+            let uncertain = scan.uncertain(min_confidence);
+            if json {
+                let confidence: Vec<f32> = scan.cells.iter().map(|c| c.confidence).collect();
+                print_json(&serde_json::json!({
+                    "puzzle": puzzle,
+                    "confidence": confidence,
+                    "uncertain": uncertain,
+                }));
+            } else {
+                print_puzzle(format, &puzzle, 0);
+                for i in &uncertain {
+                    let cell = scan.cells[*i];
+                    eprintln!(
+                        "check r{}c{}: read as {} ({:.0}% sure)",
+                        i / 9 + 1,
+                        i % 9 + 1,
+                        cell.digit.map_or('.', |d| (b'0' + d) as char),
+                        cell.confidence * 100.0
+                    );
+                }
+            }
+            Ok(uncertain.is_empty())
+        }
+    }
+}
+
+/// Match a difficulty by name, ignoring case
+fn parse_difficulty(name: &str) -> Result<Difficulty, String> {
+    Difficulty::all_levels()
+        .iter()
+        .copied()
+        .find(|d| d.to_string().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("unknown difficulty \"{}\"", name))
+}
+
+/// The registry at `path`, if one was given
+fn load_registry(path: Option<&std::path::Path>) -> Result<Option<PuzzleRegistry>, String> {
+    path.map(|path| PuzzleRegistry::load(path).map_err(|e| format!("{}: {}", path.display(), e)))
+        .transpose()
+}
+
+fn save_registry(
+    registry: Option<&PuzzleRegistry>,
+    path: Option<&std::path::Path>,
+) -> Result<(), String> {
+    match (registry, path) {
+        (Some(registry), Some(path)) => registry
+            .save(path)
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        _ => Ok(()),
+    }
+}
+
+/// Use "." for empty cells, as every format here does
+fn normalize(puzzle: &str) -> String {
+    puzzle.replace('0', ".")
+}
+
+/// What `rate` reports for one puzzle
+#[derive(Serialize)]
+struct Rating {
+    puzzle: String,
+    difficulty: String,
+    se_rating: f32,
+    clues: usize,
+}
+
+/// Rate a puzzle, which must have exactly one solution
+fn rating(puzzle: &str) -> Result<Rating, String> {
+    let grid = Grid::from_string(puzzle).ok_or_else(|| format!("{}: could not be read", puzzle))?;
+    let solver = Solver::new();
+    if solver.count_solutions(&grid, 2) != 1 {
+        return Err(format!("{}: not a unique puzzle", puzzle));
+    }
+    Ok(Rating {
+        puzzle: puzzle.to_string(),
+        difficulty: solver.rate_difficulty(&grid).to_string(),
+        se_rating: solver.rate_se(&grid),
+        clues: puzzle
+            .chars()
+            .filter(|c| c.is_ascii_digit() && *c != '0')
+            .count(),
+    })
+}
+
+/// Same layout as the TUI's JSON packs
+#[derive(Serialize, Deserialize)]
+struct Pack {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    puzzles: Vec<PackPuzzle>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PackPuzzle {
+    puzzle: String,
+    title: Option<String>,
+    rating: Option<f32>,
+}
+
+/// Print one puzzle; multi-line formats get a blank line between puzzles
+fn print_puzzle(format: Format, puzzle: &str, index: usize) {
+    if index > 0 && format != Format::Line {
+        println!();
+    }
+    println!("{}", format.render(puzzle));
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    println!("{}", serde_json::to_string(value).unwrap_or_default());
+}
@@ -0,0 +1,59 @@
+//! Rule variants for `solve` and `validate`. Classic puzzles go to the core
+//! solver; variants with extra units or constraints use sudoku-variants.
+
+use clap::ValueEnum;
+use std::sync::Arc;
+use sudoku_core::{Grid, Solver};
+use sudoku_variants::{AntiKnight, NonConsecutive, Puzzle, Sectors};
+
+/// The rules a puzzle is solved under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Variant {
+    Classic,
+    /// Four extra 3x3 windows must also hold each digit once
+    Windoku,
+    /// Cells a knight's move apart can't hold the same digit
+    AntiKnight,
+    /// Side-by-side cells can't hold consecutive digits
+    NonConsecutive,
+}
+
+impl Variant {
+    /// The first solution, as 81 characters
+    pub fn solve(self, puzzle: &str) -> Option<String> {
+        match self {
+            Variant::Classic => Grid::from_string(puzzle)
+                .and_then(|g| Solver::new().solve(&g))
+                .map(|solution| solution.to_string_compact()),
+            _ => self.parse(puzzle)?.solve().map(|s| s.to_line()),
+        }
+    }
+
+    /// Solutions found, stopping at `limit`; 0 for a puzzle that can't be read
+    pub fn count_solutions(self, puzzle: &str, limit: usize) -> usize {
+        match self {
+            Variant::Classic => {
+                Grid::from_string(puzzle).map_or(0, |g| Solver::new().count_solutions(&g, limit))
+            }
+            _ => self.parse(puzzle).map_or(0, |p| p.count_solutions(limit)),
+        }
+    }
+
+    /// Up to `limit` solutions, found lazily so a puzzle with thousands
+    /// stops at the cap
+    pub fn solutions(self, puzzle: &str, limit: usize) -> Vec<String> {
+        self.parse(puzzle).map_or_else(Vec::new, |p| {
+            p.solutions().take(limit).map(|s| s.to_line()).collect()
+        })
+    }
+
+    fn parse(self, puzzle: &str) -> Option<Puzzle> {
+        let sectors = match self {
+            Variant::Classic => Sectors::classic(),
+            Variant::Windoku => Sectors::windoku(),
+            Variant::AntiKnight => Sectors::classic().with_constraint(AntiKnight),
+            Variant::NonConsecutive => Sectors::classic().with_constraint(NonConsecutive),
+        };
+        Puzzle::parse(Arc::new(sectors), puzzle)
+    }
+}
@@ -0,0 +1,149 @@
+//! Daily puzzle: one seeded puzzle per UTC day, the same on every frontend.
+//!
+//! The day number (days since 1970-01-01 UTC) is mixed with splitmix64 into
+//! a `PuzzleId` seed, and the difficulty follows the weekday (gentle on
+//! Monday, hardest on Sunday), so the TUI, the server, the WASI component
+//! and the bindings all give the same grid on the same day.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use sudoku_core::{Difficulty, PuzzleId};
+
+/// Difficulty by weekday, Monday first
+pub const WEEKDAY_DIFFICULTY: [Difficulty; 7] = [
+    Difficulty::Easy,
+    Difficulty::Medium,
+    Difficulty::Medium,
+    Difficulty::Intermediate,
+    Difficulty::Hard,
+    Difficulty::Hard,
+    Difficulty::Expert,
+];
+
+/// Short weekday names, Monday first
+pub const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Today's day number (UTC). Not available on wasm32-unknown-unknown, where
+/// the caller passes the date in.
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() / 86_400)
+}
+
+/// Day number of a Unix timestamp
+pub fn day_of(timestamp: u64) -> u64 {
+    timestamp / 86_400
+}
+
+/// Weekday of a day number, 0 = Monday (1970-01-01 was a Thursday)
+pub fn weekday(day: u64) -> usize {
+    ((day + 3) % 7) as usize
+}
+
+/// Short weekday name
+pub fn weekday_name(day: u64) -> &'static str {
+    WEEKDAY_NAMES[weekday(day)]
+}
+
+/// Difficulty of the daily puzzle for `day`
+pub fn difficulty(day: u64) -> Difficulty {
+    WEEKDAY_DIFFICULTY[weekday(day)]
+}
+
+/// Puzzle id of the daily puzzle for `day`
+pub fn puzzle_id(day: u64) -> PuzzleId {
+    PuzzleId {
+        difficulty: difficulty(day),
+        seed: seed(day),
+    }
+}
+
+/// splitmix64 of the day number, kept to 32 bits so it fits in a short code
+fn seed(day: u64) -> u64 {
+    let mut z = day.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (z ^ (z >> 31)) & 0xFFFF_FFFF
+}
+
+/// Day number of a "YYYY-MM-DD" date; None before 1970-01-01 or for a date
+/// that doesn't exist
+pub fn parse_date(date: &str) -> Option<u64> {
+    let mut parts = date.splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let d: i64 = parts.next()?.parse().ok()?;
+    let leap = y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
+    let days_in_month = match m {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if d < 1 || d > days_in_month {
+        return None;
+    }
+    // Days-from-civil (Howard Hinnant's algorithm)
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    u64::try_from(era * 146_097 + doe - 719_468).ok()
+}
+
+/// Year, month and day of a day number (Howard Hinnant's civil-from-days)
+pub fn civil(day: u64) -> (i64, i64, i64) {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(m <= 2), m, d)
+}
+
+/// "YYYY-MM-DD" of a day number; the inverse of [`parse_date`]
+pub fn format_date(day: u64) -> String {
+    let (y, m, d) = civil(day);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_derivation() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(19_723), "2024-01-01");
+        assert_eq!(format_date(19_782), "2024-02-29");
+        assert_eq!(weekday_name(0), "Thu");
+        assert_eq!(weekday_name(19_723), "Mon");
+        assert_eq!(day_of(19_723 * 86_400 + 3_600), 19_723);
+
+        let id = puzzle_id(19_723);
+        assert_eq!(id.difficulty, Difficulty::Easy);
+        assert_eq!(id.seed, puzzle_id(19_723).seed);
+        assert_ne!(id.seed, puzzle_id(19_724).seed);
+        assert!(id.seed <= 0xFFFF_FFFF);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2024-01-01"), Some(19_723));
+        assert_eq!(parse_date("2024-02-29"), Some(19_782));
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("1969-12-31"), None);
+        assert_eq!(parse_date("today"), None);
+        for date in ["1970-01-01", "2000-02-29", "2024-12-31"] {
+            assert_eq!(format_date(parse_date(date).unwrap()), date);
+        }
+    }
+}
@@ -0,0 +1,507 @@
+//! Puzzle library in SQLite: puzzles with their ratings, the techniques a
+//! solve needs, tags, and the player's progress.
+//!
+//! Puzzles are stored once per minlex form, so the same puzzle rotated,
+//! reflected or relabeled is recognised as a duplicate.
+//!
+//! ```no_run
+//! use sudoku_db::{PuzzleDb, Query};
+//! let db = PuzzleDb::open("puzzles.db")?;
+//! db.add("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79", None)?;
+//! let hard_xwings = db.query(&Query {
+//!     technique: Some("X-Wing".into()),
+//!     ..Query::default()
+//! })?;
+//! # Ok::<(), sudoku_db::Error>(())
+//! ```
+
+pub mod minlex;
+pub mod registry;
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sudoku_core::{canonical_puzzle_hash_str, Difficulty, Grid, HintType, Solver};
+
+pub use registry::PuzzleRegistry;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS puzzles (
+    id          INTEGER PRIMARY KEY,
+    puzzle      TEXT NOT NULL,
+    canonical   TEXT NOT NULL UNIQUE,
+    hash        TEXT NOT NULL,
+    clues       INTEGER NOT NULL,
+    difficulty  TEXT NOT NULL,
+    se_rating   REAL NOT NULL,
+    source      TEXT,
+    added_at    INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS puzzles_difficulty ON puzzles (difficulty);
+CREATE INDEX IF NOT EXISTS puzzles_clues ON puzzles (clues);
+CREATE TABLE IF NOT EXISTS techniques (
+    puzzle_id   INTEGER NOT NULL REFERENCES puzzles (id) ON DELETE CASCADE,
+    technique   TEXT NOT NULL,
+    PRIMARY KEY (puzzle_id, technique)
+);
+CREATE INDEX IF NOT EXISTS techniques_name ON techniques (technique);
+CREATE TABLE IF NOT EXISTS tags (
+    puzzle_id   INTEGER NOT NULL REFERENCES puzzles (id) ON DELETE CASCADE,
+    tag         TEXT NOT NULL,
+    PRIMARY KEY (puzzle_id, tag)
+);
+CREATE TABLE IF NOT EXISTS progress (
+    puzzle_id       INTEGER PRIMARY KEY REFERENCES puzzles (id) ON DELETE CASCADE,
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    solved          INTEGER NOT NULL DEFAULT 0,
+    best_time_secs  INTEGER,
+    updated_at      INTEGER NOT NULL
+);
+";
+
+/// Most solver steps taken when collecting techniques
+const MAX_SOLVE_STEPS: usize = 81 * 9 + 81;
+
+#[derive(Debug)]
+pub enum Error {
+    Sqlite(rusqlite::Error),
+    /// Unreadable, unsolvable or not unique
+    InvalidPuzzle(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Sqlite(e) => write!(f, "database error: {}", e),
+            Error::InvalidPuzzle(why) => write!(f, "invalid puzzle: {}", why),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Sqlite(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Outcome of adding a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Added {
+    New(i64),
+    /// Already stored, possibly in another disguise; the existing id
+    Duplicate(i64),
+}
+
+impl Added {
+    pub fn id(self) -> i64 {
+        match self {
+            Added::New(id) | Added::Duplicate(id) => id,
+        }
+    }
+}
+
+/// A stored puzzle
+#[derive(Debug, Clone, PartialEq)]
+pub struct PuzzleRecord {
+    pub id: i64,
+    /// As it was added, "." for empty cells
+    pub puzzle: String,
+    /// Minlex form
+    pub canonical: String,
+    /// SHA-256 of the minlex form
+    pub hash: String,
+    pub clues: usize,
+    pub difficulty: String,
+    pub se_rating: f32,
+    pub source: Option<String>,
+    /// Unix seconds
+    pub added_at: i64,
+}
+
+impl PuzzleRecord {
+    const COLUMNS: &'static str =
+        "p.id, p.puzzle, p.canonical, p.hash, p.clues, p.difficulty, p.se_rating, p.source, p.added_at";
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            puzzle: row.get(1)?,
+            canonical: row.get(2)?,
+            hash: row.get(3)?,
+            clues: row.get(4)?,
+            difficulty: row.get(5)?,
+            se_rating: row.get(6)?,
+            source: row.get(7)?,
+            added_at: row.get(8)?,
+        })
+    }
+}
+
+/// The player's record on one puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Progress {
+    pub attempts: u32,
+    pub solved: bool,
+    pub best_time_secs: Option<u64>,
+}
+
+/// Filters for `PuzzleDb::query`; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    pub difficulty: Option<Difficulty>,
+    /// Needs this technique somewhere in its solve, by name
+    pub technique: Option<String>,
+    pub min_clues: Option<usize>,
+    pub max_clues: Option<usize>,
+    pub tag: Option<String>,
+    /// Only puzzles the player hasn't solved
+    pub unsolved: bool,
+    /// At most this many, oldest first
+    pub limit: Option<usize>,
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Techniques the solver uses on its way through the puzzle
+fn techniques(grid: &Grid) -> BTreeSet<String> {
+    let solver = Solver::new();
+    let mut grid = grid.deep_clone();
+    grid.recalculate_candidates();
+    let mut used = BTreeSet::new();
+    for _ in 0..MAX_SOLVE_STEPS {
+        if grid.is_complete() {
+            break;
+        }
+        let Some(hint) = solver.get_hint(&grid) else {
+            break;
+        };
+        used.insert(hint.technique.to_string());
+        match &hint.hint_type {
+            HintType::SetValue { pos, value } => {
+                grid.set_cell_unchecked(*pos, Some(*value));
+                grid.update_candidates_after_move(*pos, *value);
+            }
+            HintType::EliminateCandidates { pos, values } => {
+                let before = grid.cell(*pos).candidates().as_raw();
+                for &v in values {
+                    grid.cell_mut(*pos).remove_candidate(v);
+                }
+                if grid.cell(*pos).candidates().as_raw() == before {
+                    break;
+                }
+            }
+        }
+    }
+    used
+}
+
+/// An open puzzle library
+pub struct PuzzleDb {
+    conn: Connection,
+}
+
+impl PuzzleDb {
+    /// Open (creating if needed) a library file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// A library that lives only as long as the value
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Rate and store a puzzle with exactly one solution, unless an
+    /// equivalent one is already stored
+    pub fn add(&self, puzzle: &str, source: Option<&str>) -> Result<Added> {
+        let puzzle = puzzle.trim().replace('0', ".");
+        let canonical = minlex::minlex_str(&puzzle)
+            .ok_or_else(|| Error::InvalidPuzzle("expected 81 cells".into()))?;
+        if let Some(id) = self.find_canonical(&canonical)? {
+            return Ok(Added::Duplicate(id));
+        }
+
+        let grid = Grid::from_string(&puzzle)
+            .ok_or_else(|| Error::InvalidPuzzle("could not be read".into()))?;
+        let solver = Solver::new();
+        match solver.count_solutions(&grid, 2) {
+            1 => {}
+            0 => return Err(Error::InvalidPuzzle("no solution".into())),
+            _ => return Err(Error::InvalidPuzzle("more than one solution".into())),
+        }
+        let clues = puzzle.bytes().filter(u8::is_ascii_digit).count();
+
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO puzzles (puzzle, canonical, hash, clues, difficulty, se_rating, source, added_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                puzzle,
+                canonical,
+                canonical_puzzle_hash_str(&canonical),
+                clues,
+                solver.rate_difficulty(&grid).to_string(),
+                solver.rate_se(&grid),
+                source,
+                now(),
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        for technique in techniques(&grid) {
+            tx.execute(
+                "INSERT INTO techniques (puzzle_id, technique) VALUES (?1, ?2)",
+                params![id, technique],
+            )?;
+        }
+        tx.commit()?;
+        Ok(Added::New(id))
+    }
+
+    /// Id of the stored puzzle equivalent to `puzzle`, if any
+    pub fn find(&self, puzzle: &str) -> Result<Option<i64>> {
+        match minlex::minlex_str(&puzzle.trim().replace('0', ".")) {
+            Some(canonical) => self.find_canonical(&canonical),
+            None => Ok(None),
+        }
+    }
+
+    fn find_canonical(&self, canonical: &str) -> Result<Option<i64>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT id FROM puzzles WHERE canonical = ?1",
+                [canonical],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn get(&self, id: i64) -> Result<Option<PuzzleRecord>> {
+        let sql = format!(
+            "SELECT {} FROM puzzles p WHERE p.id = ?1",
+            PuzzleRecord::COLUMNS
+        );
+        Ok(self
+            .conn
+            .query_row(&sql, [id], PuzzleRecord::from_row)
+            .optional()?)
+    }
+
+    /// Remove a puzzle with its tags and progress
+    pub fn remove(&self, id: i64) -> Result<bool> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM puzzles WHERE id = ?1", [id])?
+            > 0)
+    }
+
+    /// Every stored puzzle as issued, to check new ones against
+    pub fn registry(&self) -> Result<PuzzleRegistry> {
+        let mut stmt = self.conn.prepare("SELECT hash FROM puzzles")?;
+        let hashes = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(PuzzleRegistry::from_hashes(hashes))
+    }
+
+    /// Number of stored puzzles
+    pub fn len(&self) -> Result<usize> {
+        Ok(self
+            .conn
+            .query_row("SELECT COUNT(*) FROM puzzles", [], |row| row.get(0))?)
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Techniques a puzzle's solve needs, by name
+    pub fn techniques(&self, id: i64) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT technique FROM techniques WHERE puzzle_id = ?1 ORDER BY technique")?;
+        let rows = stmt.query_map([id], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn tag(&self, id: i64, tag: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO tags (puzzle_id, tag) VALUES (?1, ?2)",
+            params![id, tag],
+        )?;
+        Ok(())
+    }
+
+    pub fn untag(&self, id: i64, tag: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM tags WHERE puzzle_id = ?1 AND tag = ?2",
+            params![id, tag],
+        )?;
+        Ok(())
+    }
+
+    pub fn tags(&self, id: i64) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tag FROM tags WHERE puzzle_id = ?1 ORDER BY tag")?;
+        let rows = stmt.query_map([id], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Count an attempt, and a solve in `secs` if `solved_in` is set
+    pub fn record_attempt(&self, id: i64, solved_in: Option<u64>) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO progress (puzzle_id, attempts, solved, best_time_secs, updated_at)
+             VALUES (?1, 1, ?2, ?3, ?4)
+             ON CONFLICT (puzzle_id) DO UPDATE SET
+                 attempts = attempts + 1,
+                 solved = solved OR excluded.solved,
+                 best_time_secs = CASE
+                     WHEN excluded.best_time_secs IS NULL THEN best_time_secs
+                     WHEN best_time_secs IS NULL THEN excluded.best_time_secs
+                     ELSE MIN(best_time_secs, excluded.best_time_secs) END,
+                 updated_at = excluded.updated_at",
+            params![id, solved_in.is_some(), solved_in, now()],
+        )?;
+        Ok(())
+    }
+
+    pub fn progress(&self, id: i64) -> Result<Progress> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT attempts, solved, best_time_secs FROM progress WHERE puzzle_id = ?1",
+                [id],
+                |row| {
+                    Ok(Progress {
+                        attempts: row.get(0)?,
+                        solved: row.get(1)?,
+                        best_time_secs: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?
+            .unwrap_or_default())
+    }
+
+    /// Puzzles matching every set filter
+    pub fn query(&self, query: &Query) -> Result<Vec<PuzzleRecord>> {
+        let mut sql = format!("SELECT {} FROM puzzles p WHERE 1", PuzzleRecord::COLUMNS);
+        let mut args: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        if let Some(difficulty) = query.difficulty {
+            args.push(Box::new(difficulty.to_string()));
+            sql += &format!(" AND p.difficulty = ?{}", args.len());
+        }
+        if let Some(technique) = &query.technique {
+            args.push(Box::new(technique.clone()));
+            sql += &format!(
+                " AND EXISTS (SELECT 1 FROM techniques t WHERE t.puzzle_id = p.id AND t.technique = ?{} COLLATE NOCASE)",
+                args.len()
+            );
+        }
+        if let Some(min) = query.min_clues {
+            args.push(Box::new(min));
+            sql += &format!(" AND p.clues >= ?{}", args.len());
+        }
+        if let Some(max) = query.max_clues {
+            args.push(Box::new(max));
+            sql += &format!(" AND p.clues <= ?{}", args.len());
+        }
+        if let Some(tag) = &query.tag {
+            args.push(Box::new(tag.clone()));
+            sql += &format!(
+                " AND EXISTS (SELECT 1 FROM tags g WHERE g.puzzle_id = p.id AND g.tag = ?{})",
+                args.len()
+            );
+        }
+        if query.unsolved {
+            sql +=
+                " AND NOT EXISTS (SELECT 1 FROM progress r WHERE r.puzzle_id = p.id AND r.solved)";
+        }
+        sql += " ORDER BY p.id";
+        if let Some(limit) = query.limit {
+            args.push(Box::new(limit));
+            sql += &format!(" LIMIT ?{}", args.len());
+        }
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(args.iter().map(|a| a.as_ref())),
+            PuzzleRecord::from_row,
+        )?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_add_dedup_and_query() {
+        let db = PuzzleDb::open_in_memory().unwrap();
+        let id = match db.add(PUZZLE, Some("test")).unwrap() {
+            Added::New(id) => id,
+            Added::Duplicate(_) => panic!("empty library"),
+        };
+        // The same puzzle mirrored left-right is a duplicate
+        let mirrored: String = PUZZLE
+            .as_bytes()
+            .chunks(9)
+            .flat_map(|row| row.iter().rev().map(|&b| b as char))
+            .collect();
+        assert_eq!(db.add(&mirrored, None).unwrap(), Added::Duplicate(id));
+        assert_eq!(db.len().unwrap(), 1);
+        assert!(db.registry().unwrap().contains(&mirrored));
+
+        let record = db.get(id).unwrap().unwrap();
+        assert_eq!(record.clues, 30);
+        assert!(!db.techniques(id).unwrap().is_empty());
+
+        db.tag(id, "classic").unwrap();
+        let tagged = Query {
+            tag: Some("classic".into()),
+            min_clues: Some(25),
+            ..Query::default()
+        };
+        assert_eq!(db.query(&tagged).unwrap().len(), 1);
+
+        db.record_attempt(id, None).unwrap();
+        db.record_attempt(id, Some(300)).unwrap();
+        db.record_attempt(id, Some(200)).unwrap();
+        let progress = db.progress(id).unwrap();
+        assert_eq!(progress.attempts, 3);
+        assert!(progress.solved);
+        assert_eq!(progress.best_time_secs, Some(200));
+        let unsolved = Query {
+            unsolved: true,
+            ..Query::default()
+        };
+        assert!(db.query(&unsolved).unwrap().is_empty());
+
+        assert!(matches!(
+            db.add(&".".repeat(81), None),
+            Err(Error::InvalidPuzzle(_))
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+//! Minlex canonical form: the lexicographically smallest string among every
+//! equivalent grid (transposition, band/stack and row/column swaps within
+//! them, digit relabeling), with empty cells as 0. Two puzzles are the same
+//! puzzle in disguise exactly when their minlex forms match.
+
+/// Row (or column) orders reachable by permuting bands and rows in bands
+fn line_orders() -> Vec<[usize; 9]> {
+    const PERMS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut orders = Vec::with_capacity(6 * 6 * 6 * 6);
+    for bands in PERMS {
+        for a in PERMS {
+            for b in PERMS {
+                for c in PERMS {
+                    let mut order = [0; 9];
+                    for (slot, inner) in [a, b, c].iter().enumerate() {
+                        for (k, &line) in inner.iter().enumerate() {
+                            order[slot * 3 + k] = bands[slot] * 3 + line;
+                        }
+                    }
+                    orders.push(order);
+                }
+            }
+        }
+    }
+    orders
+}
+
+/// Minlex form of a grid of cells (0 = empty, 1-9 = digit), row-major
+pub fn minlex(cells: &[u8; 81]) -> [u8; 81] {
+    let orders = line_orders();
+    let mut transposed = [0; 81];
+    for (i, cell) in transposed.iter_mut().enumerate() {
+        *cell = cells[(i % 9) * 9 + i / 9];
+    }
+
+    let mut best = [u8::MAX; 81];
+    let mut candidate = [0; 81];
+    for grid in [cells, &transposed] {
+        for rows in &orders {
+            for cols in &orders {
+                // Relabel digits in order of first appearance, bailing out as
+                // soon as this arrangement can't beat the best so far
+                let mut labels = [0u8; 10];
+                let mut next = 1;
+                let mut smaller = false;
+                let mut worse = false;
+                for i in 0..81 {
+                    let v = grid[rows[i / 9] * 9 + cols[i % 9]] as usize;
+                    let mapped = if v == 0 {
+                        0
+                    } else {
+                        if labels[v] == 0 {
+                            labels[v] = next;
+                            next += 1;
+                        }
+                        labels[v]
+                    };
+                    if !smaller {
+                        if mapped > best[i] {
+                            worse = true;
+                            break;
+                        }
+                        smaller = mapped < best[i];
+                    }
+                    candidate[i] = mapped;
+                }
+                if !worse && smaller {
+                    best = candidate;
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Minlex form of an 81-character puzzle string, with "." for empty cells
+pub fn minlex_str(puzzle: &str) -> Option<String> {
+    let bytes = puzzle.as_bytes();
+    if bytes.len() != 81 {
+        return None;
+    }
+    let mut cells = [0; 81];
+    for (cell, &b) in cells.iter_mut().zip(bytes) {
+        *cell = match b {
+            b'1'..=b'9' => b - b'0',
+            b'.' | b'0' => 0,
+            _ => return None,
+        };
+    }
+    Some(
+        minlex(&cells)
+            .iter()
+            .map(|&v| if v == 0 { '.' } else { (b'0' + v) as char })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_minlex_equivalence() {
+        let puzzle = Tier::Singles.puzzle();
+        // Transpose and swap digits 1 and 2: same puzzle in disguise
+        let bytes = puzzle.as_bytes();
+        let disguised: String = (0..81)
+            .map(|i| match bytes[(i % 9) * 9 + i / 9] {
+                b'1' => '2',
+                b'2' => '1',
+                c => c as char,
+            })
+            .collect();
+        let canonical = minlex_str(puzzle).unwrap();
+        assert_eq!(minlex_str(&disguised).unwrap(), canonical);
+        assert_eq!(minlex_str(&canonical).unwrap(), canonical);
+        assert!(canonical.starts_with('.'));
+        assert!(minlex_str("123").is_none());
+    }
+}
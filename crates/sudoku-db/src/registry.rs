@@ -0,0 +1,119 @@
+//! Puzzles already issued, by the hash of their minlex form, so a generator
+//! can turn down one that is only a rotation, reflection or relabeling of
+//! an earlier daily or pack puzzle.
+//!
+//! Saved as a text file of hashes, one per line, sorted so it diffs well
+//! under version control.
+
+use crate::minlex::minlex_str;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use sudoku_core::canonical_puzzle_hash_str;
+
+/// Hash of a puzzle's minlex form, as in `PuzzleRecord::hash`. None if the
+/// puzzle isn't 81 cells.
+pub fn canonical_hash(puzzle: &str) -> Option<String> {
+    minlex_str(&puzzle.trim().replace('0', ".")).map(|c| canonical_puzzle_hash_str(&c))
+}
+
+/// Canonical hashes of every puzzle issued so far
+#[derive(Debug, Clone, Default)]
+pub struct PuzzleRegistry {
+    hashes: HashSet<String>,
+}
+
+impl PuzzleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn from_hashes(hashes: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            hashes: hashes.into_iter().collect(),
+        }
+    }
+
+    /// Read a registry file; a file that doesn't exist yet is an empty
+    /// registry
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Self::from_hashes(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string),
+            )),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut hashes: Vec<&str> = self.hashes.iter().map(String::as_str).collect();
+        hashes.sort_unstable();
+        let mut text = hashes.join("\n");
+        text.push('\n');
+        fs::write(path, text)
+    }
+
+    /// Whether `puzzle`, in any disguise, was already issued
+    pub fn contains(&self, puzzle: &str) -> bool {
+        canonical_hash(puzzle).is_some_and(|hash| self.hashes.contains(&hash))
+    }
+
+    /// Record a puzzle as issued. False if it or an equivalent already was,
+    /// or it can't be read.
+    pub fn insert(&mut self, puzzle: &str) -> bool {
+        canonical_hash(puzzle).is_some_and(|hash| self.hashes.insert(hash))
+    }
+
+    /// Call `generate` until it returns a puzzle not issued before, and
+    /// record it. None if `attempts` tries in a row were duplicates.
+    pub fn issue(
+        &mut self,
+        attempts: usize,
+        mut generate: impl FnMut() -> Option<String>,
+    ) -> Option<String> {
+        (0..attempts)
+            .filter_map(|_| generate())
+            .find(|puzzle| self.insert(puzzle))
+    }
+
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_registry_rejects_disguises() {
+        let puzzle = Tier::Singles.puzzle();
+        let upside_down: String = puzzle.chars().rev().collect();
+        let mut registry = PuzzleRegistry::new();
+        assert!(registry.insert(puzzle));
+        assert!(registry.contains(&upside_down));
+        assert!(!registry.insert(&upside_down));
+        assert!(!registry.insert("123"));
+
+        let mut candidates = vec![upside_down, puzzle.replace('5', "0")].into_iter();
+        let issued = registry.issue(3, || candidates.next()).unwrap();
+        assert_eq!(issued, puzzle.replace('5', "0"));
+        assert_eq!(registry.len(), 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("registry.txt");
+        registry.save(&path).unwrap();
+        let loaded = PuzzleRegistry::load(&path).unwrap();
+        assert!(loaded.contains(puzzle) && loaded.len() == 2);
+    }
+}
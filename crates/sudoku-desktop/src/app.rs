@@ -0,0 +1,422 @@
+//! The window: menu bar, board and status line, with the session, stats and
+//! theme kept in eframe's storage between runs
+
+use crate::session::{Placement, Session};
+use crate::stats::Stats;
+use eframe::egui::{self, Align2, Color32, FontId, Key, Pos2, Rect, Sense, Stroke, Vec2};
+use sudoku_core::{Difficulty, Hint};
+use sudoku_render::{Palette, Scene, Shape, Style};
+
+const SESSION_KEY: &str = "session";
+const STATS_KEY: &str = "stats";
+const DARK_KEY: &str = "dark";
+
+const DIGIT_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
+
+/// Key presses gathered for one frame
+#[derive(Default)]
+struct Keys {
+    digit: Option<u8>,
+    /// Row and column steps
+    step: Option<(i32, i32)>,
+    clear: bool,
+    notes: bool,
+    hint: bool,
+    undo: bool,
+    redo: bool,
+}
+
+pub struct App {
+    session: Session,
+    stats: Stats,
+    selected: usize,
+    /// Digits toggle pencil marks instead of placing values
+    notes_mode: bool,
+    hint: Option<Hint>,
+    dark: bool,
+    message: String,
+    /// Text of the open-puzzle box while it is shown
+    open: Option<String>,
+    show_stats: bool,
+}
+
+fn format_time(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn color(c: sudoku_render::Color) -> Color32 {
+    Color32::from_rgb(c.0, c.1, c.2)
+}
+
+/// Paint a rendered board with its top left corner at `origin`
+fn paint(painter: &egui::Painter, origin: Pos2, scene: &Scene) {
+    let at = |(x, y): (f32, f32)| origin + Vec2::new(x, y);
+    painter.rect_filled(
+        Rect::from_min_size(origin, Vec2::new(scene.width, scene.height)),
+        0.0,
+        color(scene.background),
+    );
+    for shape in &scene.shapes {
+        match *shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                fill,
+            } => {
+                let rect = Rect::from_min_size(at((x, y)), Vec2::new(width, height));
+                painter.rect_filled(rect, 0.0, color(fill));
+            }
+            Shape::Line {
+                from,
+                to,
+                width,
+                color: c,
+                dashed,
+            } => {
+                let stroke = Stroke::new(width, color(c));
+                if dashed {
+                    painter.extend(egui::Shape::dashed_line(
+                        &[at(from), at(to)],
+                        stroke,
+                        width * 3.0,
+                        width * 2.0,
+                    ));
+                } else {
+                    painter.line_segment([at(from), at(to)], stroke);
+                }
+            }
+            Shape::Circle {
+                center,
+                radius,
+                width,
+                color: c,
+            } => {
+                painter.circle_stroke(at(center), radius, Stroke::new(width, color(c)));
+            }
+            Shape::Digit {
+                center,
+                size,
+                digit,
+                color: c,
+                ..
+            } => {
+                painter.text(
+                    at(center),
+                    Align2::CENTER_CENTER,
+                    digit,
+                    FontId::proportional(size),
+                    color(c),
+                );
+            }
+        }
+    }
+}
+
+impl App {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let storage = cc.storage;
+        let session = storage
+            .and_then(|s| eframe::get_value::<Session>(s, SESSION_KEY))
+            .filter(|s| !s.is_solved())
+            .unwrap_or_else(|| Session::generate(Difficulty::Medium));
+        App {
+            session,
+            stats: storage
+                .and_then(|s| eframe::get_value(s, STATS_KEY))
+                .unwrap_or_default(),
+            selected: 40,
+            notes_mode: false,
+            hint: None,
+            dark: storage
+                .and_then(|s| eframe::get_value(s, DARK_KEY))
+                .unwrap_or(true),
+            message: String::new(),
+            open: None,
+            show_stats: false,
+        }
+    }
+
+    /// Switch to another game; one left unfinished counts as played
+    fn start(&mut self, session: Session) {
+        if self.session.is_started() && !self.session.is_solved() {
+            self.stats.record_abandoned(self.session.difficulty);
+        }
+        self.message = format!("New {} puzzle", session.difficulty);
+        self.session = session;
+        self.hint = None;
+        self.selected = 40;
+    }
+
+    fn input(&mut self, value: u8) {
+        self.hint = None;
+        if self.session.is_solved() {
+            return;
+        }
+        if self.notes_mode {
+            self.session.toggle_note(self.selected, value);
+            return;
+        }
+        self.message = match self.session.place(self.selected, value) {
+            Placement::Placed => String::new(),
+            Placement::Given => "That cell is a given".to_string(),
+            Placement::Mistake => format!("{} doesn't go there", value),
+            Placement::Solved => {
+                let secs = self.session.elapsed_secs as u64;
+                self.stats.record_win(self.session.difficulty, secs);
+                format!("Solved in {}!", format_time(secs))
+            }
+        };
+    }
+
+    fn show_hint(&mut self) {
+        match self.session.hint() {
+            Some(hint) => {
+                self.message = format!("{}: {}", hint.technique, hint.explanation);
+                self.hint = Some(hint);
+            }
+            None => self.message = "No hint found".to_string(),
+        }
+    }
+
+    fn keys(&mut self, ctx: &egui::Context) {
+        if self.open.is_some() {
+            return;
+        }
+        let keys = ctx.input(|i| {
+            let command = i.modifiers.command;
+            Keys {
+                digit: (1..=9).find(|&d| i.key_pressed(DIGIT_KEYS[usize::from(d - 1)])),
+                step: [
+                    (Key::ArrowUp, (-1, 0)),
+                    (Key::ArrowDown, (1, 0)),
+                    (Key::ArrowLeft, (0, -1)),
+                    (Key::ArrowRight, (0, 1)),
+                ]
+                .into_iter()
+                .find(|(key, _)| i.key_pressed(*key))
+                .map(|(_, step)| step),
+                clear: i.key_pressed(Key::Backspace) || i.key_pressed(Key::Delete),
+                notes: !command && i.key_pressed(Key::N),
+                hint: !command && i.key_pressed(Key::H),
+                undo: command && !i.modifiers.shift && i.key_pressed(Key::Z),
+                redo: command
+                    && (i.key_pressed(Key::Y) || (i.modifiers.shift && i.key_pressed(Key::Z))),
+            }
+        });
+
+        if let Some(digit) = keys.digit {
+            self.input(digit);
+        }
+        if let Some((dr, dc)) = keys.step {
+            let row = (self.selected as i32 / 9 + dr).rem_euclid(9);
+            let col = (self.selected as i32 % 9 + dc).rem_euclid(9);
+            self.selected = (row * 9 + col) as usize;
+        }
+        if keys.clear {
+            self.session.clear(self.selected);
+        }
+        if keys.notes {
+            self.notes_mode = !self.notes_mode;
+        }
+        if keys.hint {
+            self.show_hint();
+        }
+        if keys.undo {
+            self.hint = None;
+            self.session.undo();
+        }
+        if keys.redo {
+            self.hint = None;
+            self.session.redo();
+        }
+    }
+
+    fn menu(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.menu_button("New", |ui| {
+                for &difficulty in Difficulty::all_levels() {
+                    if ui.button(difficulty.to_string()).clicked() {
+                        self.start(Session::generate(difficulty));
+                        ui.close();
+                    }
+                }
+                ui.separator();
+                if ui.button("Open puzzle or code…").clicked() {
+                    self.open = Some(String::new());
+                    ui.close();
+                }
+            });
+            if ui.button("Undo").clicked() {
+                self.hint = None;
+                self.session.undo();
+            }
+            if ui.button("Redo").clicked() {
+                self.hint = None;
+                self.session.redo();
+            }
+            ui.toggle_value(&mut self.notes_mode, "Notes (N)");
+            if ui.button("Fill notes").clicked() {
+                self.session.fill_notes();
+            }
+            if ui.button("Hint (H)").clicked() {
+                self.show_hint();
+            }
+            ui.toggle_value(&mut self.show_stats, "Stats");
+            let theme = if self.dark { "Light" } else { "Dark" };
+            if ui.button(theme).clicked() {
+                self.dark = !self.dark;
+            }
+        });
+    }
+
+    fn status(&self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let session = &self.session;
+            ui.label(format!(
+                "{}  ·  {}  ·  mistakes {}  ·  hints {}",
+                session.difficulty,
+                format_time(session.elapsed_secs as u64),
+                session.mistakes,
+                session.hints
+            ));
+            if let Some(code) = &session.short_code {
+                ui.label(format!("·  {}", code));
+            }
+        });
+        if !self.message.is_empty() {
+            ui.label(&self.message);
+        }
+    }
+
+    fn board(&mut self, ui: &mut egui::Ui) {
+        let side = ui.available_size().min_elem();
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(side), Sense::click());
+        let cell = side / 9.5;
+        let style = Style {
+            cell,
+            margin: cell / 4.0,
+            candidates: true,
+            palette: if self.dark {
+                Palette::dark()
+            } else {
+                Palette::light()
+            },
+        };
+
+        let mut board = self.session.board();
+        board.select(self.selected);
+        if let Some(hint) = &self.hint {
+            board.show_hint(hint);
+        }
+        paint(
+            ui.painter(),
+            rect.min,
+            &sudoku_render::layout(&board, &style),
+        );
+
+        if let Some(pos) = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked())
+        {
+            let local = pos - rect.min - Vec2::splat(style.margin);
+            let (row, col) = ((local.y / cell).floor(), (local.x / cell).floor());
+            if (0.0..9.0).contains(&row) && (0.0..9.0).contains(&col) {
+                self.selected = row as usize * 9 + col as usize;
+            }
+        }
+    }
+
+    fn open_window(&mut self, ctx: &egui::Context) {
+        let Some(text) = &mut self.open else {
+            return;
+        };
+        let (mut load, mut cancel) = (false, false);
+        egui::Window::new("Open puzzle")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("81-character puzzle string or short code");
+                ui.text_edit_singleline(text).request_focus();
+                ui.horizontal(|ui| {
+                    load = ui.button("Open").clicked() || ui.input(|i| i.key_pressed(Key::Enter));
+                    cancel =
+                        ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(Key::Escape));
+                });
+            });
+        if load {
+            match Session::load(text) {
+                Some(session) => {
+                    self.open = None;
+                    self.start(session);
+                }
+                None => self.message = "Not a puzzle with one solution".to_string(),
+            }
+        } else if cancel {
+            self.open = None;
+        }
+    }
+
+    fn stats_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Stats")
+            .open(&mut self.show_stats)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("stats").striped(true).show(ui, |ui| {
+                    for heading in ["Level", "Played", "Won", "Best", "Average"] {
+                        ui.strong(heading);
+                    }
+                    ui.end_row();
+                    for &difficulty in Difficulty::all_levels() {
+                        let level = self.stats.level(difficulty);
+                        ui.label(difficulty.to_string());
+                        ui.label(level.played.to_string());
+                        ui.label(level.won.to_string());
+                        ui.label(level.best_secs.map_or("-".into(), format_time));
+                        ui.label(level.average_secs().map_or("-".into(), format_time));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        ctx.set_visuals(if self.dark {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        });
+        if !self.session.is_solved() {
+            self.session.elapsed_secs += f64::from(ctx.input(|i| i.stable_dt));
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+        self.keys(ctx);
+
+        egui::TopBottomPanel::top("menu").show(ctx, |ui| self.menu(ui));
+        egui::TopBottomPanel::bottom("status").show(ctx, |ui| self.status(ui));
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.centered_and_justified(|ui| self.board(ui));
+        });
+        self.open_window(ctx);
+        self.stats_window(ctx);
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, SESSION_KEY, &self.session);
+        eframe::set_value(storage, STATS_KEY, &self.stats);
+        eframe::set_value(storage, DARK_KEY, &self.dark);
+    }
+}
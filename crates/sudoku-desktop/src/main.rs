@@ -0,0 +1,23 @@
+//! Native desktop Sudoku for Windows, macOS and Linux, drawn with egui. The
+//! board is laid out by `sudoku-render`, like the server's images.
+
+mod app;
+mod session;
+mod stats;
+
+use eframe::egui;
+
+fn main() -> eframe::Result {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_title("Sudoku")
+            .with_inner_size([520.0, 640.0])
+            .with_min_inner_size([320.0, 400.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Sudoku",
+        options,
+        Box::new(|cc| Ok(Box::new(app::App::new(cc)))),
+    )
+}
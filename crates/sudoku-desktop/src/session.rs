@@ -0,0 +1,275 @@
+//! A game in progress: the board, notes, undo history and counters. Kept in
+//! plain vectors so eframe's storage can save it between runs.
+
+use serde::{Deserialize, Serialize};
+use sudoku_core::{Difficulty, Grid, Hint, PuzzleId, Solver};
+use sudoku_render::{Board, Highlight};
+
+/// What a placement did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    Placed,
+    /// Not the solution's digit; counted as a mistake
+    Mistake,
+    /// The cell is a given and can't change
+    Given,
+    Solved,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Snapshot {
+    values: Vec<u8>,
+    notes: Vec<u16>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// 81 characters, "." for empty cells
+    pub puzzle: String,
+    solution: Vec<u8>,
+    /// 1-9, or 0 for empty
+    pub values: Vec<u8>,
+    /// Pencil marks as bits: bit 1 set means 1, and so on
+    pub notes: Vec<u16>,
+    pub difficulty: Difficulty,
+    /// Code the other apps can load, for generated puzzles
+    pub short_code: Option<String>,
+    pub mistakes: usize,
+    pub hints: usize,
+    pub elapsed_secs: f64,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+}
+
+fn digits(puzzle: &str) -> Vec<u8> {
+    puzzle
+        .bytes()
+        .map(|b| if b.is_ascii_digit() { b - b'0' } else { 0 })
+        .collect()
+}
+
+/// Cells sharing a row, column or box with `cell`, not counting itself
+fn peers(cell: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (cell / 9, cell % 9);
+    (0..81).filter(move |&i| {
+        let (r, c) = (i / 9, i % 9);
+        i != cell && (r == row || c == col || (r / 3 == row / 3 && c / 3 == col / 3))
+    })
+}
+
+impl Session {
+    /// A new random puzzle at `difficulty`
+    pub fn generate(difficulty: Difficulty) -> Self {
+        let id = PuzzleId::random(difficulty);
+        let mut session =
+            Self::from_grid(&id.generate(), difficulty).expect("generated puzzles are unique");
+        session.short_code = Some(id.to_short_code());
+        session
+    }
+
+    /// A game from a puzzle string or short code; None unless it has exactly
+    /// one solution
+    pub fn load(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some(id) = PuzzleId::from_short_code(text) {
+            let mut session = Self::from_grid(&id.generate(), id.difficulty)?;
+            session.short_code = Some(id.to_short_code());
+            return Some(session);
+        }
+        let grid = Grid::from_string(text)?;
+        Self::from_grid(&grid, Solver::new().rate_difficulty(&grid))
+    }
+
+    fn from_grid(grid: &Grid, difficulty: Difficulty) -> Option<Self> {
+        let solver = Solver::new();
+        if solver.count_solutions(grid, 2) != 1 {
+            return None;
+        }
+        let solution = solver.solve(grid)?;
+        let puzzle = grid.to_string_compact().replace('0', ".");
+        Some(Session {
+            values: digits(&puzzle),
+            puzzle,
+            solution: digits(&solution.to_string_compact()),
+            notes: vec![0; 81],
+            difficulty,
+            short_code: None,
+            mistakes: 0,
+            hints: 0,
+            elapsed_secs: 0.0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        })
+    }
+
+    pub fn is_given(&self, cell: usize) -> bool {
+        self.puzzle.as_bytes()[cell] != b'.'
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.values == self.solution
+    }
+
+    /// Whether any move has been made
+    pub fn is_started(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    fn checkpoint(&mut self) {
+        self.undo.push(Snapshot {
+            values: self.values.clone(),
+            notes: self.notes.clone(),
+        });
+        self.redo.clear();
+    }
+
+    /// Put `value` (1-9) in `cell`. A correct digit also clears it from
+    /// the notes of the cell's row, column and box.
+    pub fn place(&mut self, cell: usize, value: u8) -> Placement {
+        if self.is_given(cell) {
+            return Placement::Given;
+        }
+        if self.values[cell] == value {
+            return Placement::Placed;
+        }
+        self.checkpoint();
+        self.values[cell] = value;
+        self.notes[cell] = 0;
+        if value != self.solution[cell] {
+            self.mistakes += 1;
+            return Placement::Mistake;
+        }
+        for peer in peers(cell) {
+            self.notes[peer] &= !(1 << value);
+        }
+        if self.is_solved() {
+            Placement::Solved
+        } else {
+            Placement::Placed
+        }
+    }
+
+    /// Add or remove a pencil mark in an empty cell
+    pub fn toggle_note(&mut self, cell: usize, value: u8) {
+        if self.values[cell] != 0 {
+            return;
+        }
+        self.checkpoint();
+        self.notes[cell] ^= 1 << value;
+    }
+
+    /// Empty a cell of its value and notes
+    pub fn clear(&mut self, cell: usize) {
+        if self.is_given(cell) || (self.values[cell] == 0 && self.notes[cell] == 0) {
+            return;
+        }
+        self.checkpoint();
+        self.values[cell] = 0;
+        self.notes[cell] = 0;
+    }
+
+    /// Fill every empty cell's notes with its remaining candidates
+    pub fn fill_notes(&mut self) {
+        self.checkpoint();
+        for cell in (0..81).filter(|&c| self.values[c] == 0) {
+            let taken = peers(cell).fold(0u16, |acc, p| acc | (1 << self.values[p]));
+            self.notes[cell] = 0b11_1111_1110 & !taken;
+        }
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.step(true)
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.step(false)
+    }
+
+    fn step(&mut self, back: bool) -> bool {
+        let (from, to) = if back {
+            (&mut self.undo, &mut self.redo)
+        } else {
+            (&mut self.redo, &mut self.undo)
+        };
+        let Some(snapshot) = from.pop() else {
+            return false;
+        };
+        to.push(Snapshot {
+            values: std::mem::replace(&mut self.values, snapshot.values),
+            notes: std::mem::replace(&mut self.notes, snapshot.notes),
+        });
+        true
+    }
+
+    /// Cells holding a digit that isn't the solution's
+    pub fn wrong_cells(&self) -> Vec<usize> {
+        (0..81)
+            .filter(|&c| self.values[c] != 0 && self.values[c] != self.solution[c])
+            .collect()
+    }
+
+    /// The next logical step, counted as a hint. Worked out from the correct
+    /// digits only, so a mistake on the board can't mislead the solver.
+    pub fn hint(&mut self) -> Option<Hint> {
+        let current: String = (0..81)
+            .map(|c| match self.values[c] {
+                v if v != 0 && v == self.solution[c] => (b'0' + v) as char,
+                _ => '.',
+            })
+            .collect();
+        let mut grid = Grid::from_string(&current)?;
+        grid.recalculate_candidates();
+        let hint = Solver::new().get_hint(&grid)?;
+        self.hints += 1;
+        Some(hint)
+    }
+
+    /// The board to draw, with wrong digits marked
+    pub fn board(&self) -> Board {
+        let mut board = Board::default();
+        for cell in 0..81 {
+            board.values[cell] = self.values[cell];
+            board.givens[cell] = self.is_given(cell);
+            board.candidates[cell] = self.notes[cell];
+        }
+        for cell in self.wrong_cells() {
+            board.highlights[cell] = Highlight::Off;
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_session_moves() {
+        let mut session = Session::load(PUZZLE).unwrap();
+        assert_eq!(session.place(0, 1), Placement::Given);
+
+        session.toggle_note(2, 4);
+        session.toggle_note(3, 4);
+        assert_eq!(session.place(2, 1), Placement::Mistake);
+        assert_eq!((session.mistakes, session.wrong_cells()), (1, vec![2]));
+        assert!(session.undo());
+        assert_eq!(session.values[2], 0);
+        assert_eq!(session.notes[2], 1 << 4);
+
+        // A correct 4 clears the 4 from its row's notes
+        assert_eq!(session.place(2, 4), Placement::Placed);
+        assert_eq!(session.notes[3], 0);
+        assert!(session.hint().is_some());
+
+        for (cell, b) in solution(Tier::Singles).bytes().enumerate() {
+            if session.values[cell] == 0 {
+                session.place(cell, b - b'0');
+            }
+        }
+        assert!(session.is_solved());
+        assert!(Session::load("123").is_none());
+    }
+}
@@ -0,0 +1,51 @@
+//! Results per difficulty, saved alongside the session
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sudoku_core::Difficulty;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LevelStats {
+    /// Games finished or abandoned after the first move
+    pub played: u32,
+    pub won: u32,
+    pub best_secs: Option<u64>,
+    /// Sum of winning times, for the average
+    pub total_secs: u64,
+}
+
+impl LevelStats {
+    pub fn average_secs(&self) -> Option<u64> {
+        (self.won > 0).then(|| self.total_secs / u64::from(self.won))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    /// Keyed by difficulty name
+    levels: BTreeMap<String, LevelStats>,
+}
+
+impl Stats {
+    pub fn level(&self, difficulty: Difficulty) -> LevelStats {
+        self.levels
+            .get(&difficulty.to_string())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn record_win(&mut self, difficulty: Difficulty, secs: u64) {
+        let level = self.levels.entry(difficulty.to_string()).or_default();
+        level.played += 1;
+        level.won += 1;
+        level.total_secs += secs;
+        level.best_secs = Some(level.best_secs.map_or(secs, |best| best.min(secs)));
+    }
+
+    pub fn record_abandoned(&mut self, difficulty: Difficulty) {
+        self.levels
+            .entry(difficulty.to_string())
+            .or_default()
+            .played += 1;
+    }
+}
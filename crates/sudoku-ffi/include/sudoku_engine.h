@@ -0,0 +1,142 @@
+#ifndef SUDOKU_ENGINE_H
+#define SUDOKU_ENGINE_H
+
+/* Generated by cbindgen from src/capi.rs; do not edit. */
+
+#include <stdbool.h>
+#include <stdint.h>
+
+// Bytes reserved for the technique name in `SudokuHint`, including the NUL
+#define SUDOKU_TECHNIQUE_LEN 64
+
+// Bytes reserved for the explanation in `SudokuHint`, including the NUL
+#define SUDOKU_EXPLANATION_LEN 512
+
+// Result of `sudoku_game_make_move` and `sudoku_game_clear_cell`
+typedef enum SudokuMoveResult {
+  SUDOKU_MOVE_RESULT_SUCCESS = 0,
+  SUDOKU_MOVE_RESULT_COMPLETE = 1,
+  SUDOKU_MOVE_RESULT_CONFLICT = 2,
+  SUDOKU_MOVE_RESULT_CANNOT_MODIFY_GIVEN = 3,
+  SUDOKU_MOVE_RESULT_INVALID_VALUE = 4,
+  // Null game pointer or row/col outside 0-8
+  SUDOKU_MOVE_RESULT_INVALID_ARGUMENT = 5,
+} SudokuMoveResult;
+
+// The main Sudoku game interface for mobile platforms
+typedef struct SudokuGame SudokuGame;
+
+// The whole board, row-major
+typedef struct SudokuBoard {
+  // 1-9, or 0 for an empty cell
+  uint8_t values[81];
+  // 1 if the cell is a given
+  uint8_t givens[81];
+  // Pencil marks as bits: bit 1 set means candidate 1, and so on
+  uint16_t candidates[81];
+  // 1 if the cell's value clashes with another in its row, column or box
+  uint8_t conflicts[81];
+} SudokuBoard;
+
+// A hint; `value` is 0 for an elimination
+typedef struct SudokuHint {
+  uint8_t row;
+  uint8_t col;
+  // Value to place, or 0
+  uint8_t value;
+  // Candidates to remove from the cell, as bits like `SudokuBoard`
+  uint16_t eliminate;
+  float se_rating;
+  // NUL-terminated UTF-8, truncated to fit
+  char technique[SUDOKU_TECHNIQUE_LEN];
+  // NUL-terminated UTF-8, truncated to fit
+  char explanation[SUDOKU_EXPLANATION_LEN];
+} SudokuHint;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a game; `difficulty` is 0 (Beginner) through 7 (Extreme).
+// Returns null for any other value.
+SudokuGame *sudoku_game_new(uint8_t difficulty);
+
+// Create a game from an 81-character puzzle string ("0" or "." for empty
+// cells). Returns null if the string is unreadable or has no solution.
+//
+// # Safety
+// `puzzle` must be null or a valid NUL-terminated string.
+SudokuGame *sudoku_game_from_string(const char *puzzle);
+
+// Release a game. Null is ignored.
+//
+// # Safety
+// `game` must be null or a pointer from this module not yet freed.
+void sudoku_game_free(SudokuGame *game);
+
+// Place `value` (1-9) at `row`, `col` (0-8)
+//
+// # Safety
+// `game` must be null or a live pointer from this module.
+SudokuMoveResult sudoku_game_make_move(const SudokuGame *game,
+                                       uint8_t row,
+                                       uint8_t col,
+                                       uint8_t value);
+
+// Clear the value at `row`, `col` (0-8)
+//
+// # Safety
+// `game` must be null or a live pointer from this module.
+SudokuMoveResult sudoku_game_clear_cell(const SudokuGame *game, uint8_t row, uint8_t col);
+
+// Undo the last move; false if there was nothing to undo
+//
+// # Safety
+// `game` must be null or a live pointer from this module.
+bool sudoku_game_undo(const SudokuGame *game);
+
+// Redo the last undone move; false if there was nothing to redo
+//
+// # Safety
+// `game` must be null or a live pointer from this module.
+bool sudoku_game_redo(const SudokuGame *game);
+
+// Fill `out` with the current board; false on a null argument
+//
+// # Safety
+// `game` must be null or a live pointer from this module, and `out` null
+// or valid for writes.
+bool sudoku_game_get_board(const SudokuGame *game, SudokuBoard *out);
+
+// Fill `out` with the next logical step. False if no technique applies
+// (or on a null argument); counts as a hint used otherwise.
+//
+// # Safety
+// `game` must be null or a live pointer from this module, and `out` null
+// or valid for writes.
+bool sudoku_game_get_hint(const SudokuGame *game, SudokuHint *out);
+
+// Whether the board is filled in with no conflicts
+//
+// # Safety
+// `game` must be null or a live pointer from this module.
+bool sudoku_game_is_complete(const SudokuGame *game);
+
+// The game as JSON (the same save format as the uniffi `serialize`), or
+// null on a null argument. Release with `sudoku_string_free`.
+//
+// # Safety
+// `game` must be null or a live pointer from this module.
+char *sudoku_game_serialize(const SudokuGame *game);
+
+// Release a string returned by this library. Null is ignored.
+//
+// # Safety
+// `text` must be null or a string from this module not yet freed.
+void sudoku_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SUDOKU_ENGINE_H */
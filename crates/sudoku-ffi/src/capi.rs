@@ -0,0 +1,328 @@
+//! Plain C ABI over `SudokuGame`, for C, C++ and Unity consumers that don't
+//! use uniffi. The header, `include/sudoku_engine.h`, is checked in;
+//! regenerate it with `just ffi-header` (cbindgen) after changing this
+//! module. CI fails if it is out of date.
+//!
+//! Games are opaque pointers from `sudoku_game_new` or
+//! `sudoku_game_from_string` and must be released with `sudoku_game_free`.
+//! Boards and hints are written into caller-owned structs, so no other
+//! allocation crosses the boundary except `sudoku_game_serialize`, whose
+//! string is released with `sudoku_string_free`.
+
+use crate::{game_from_string, GameDifficulty, MoveResult, SudokuGame};
+use std::ffi::{c_char, CStr, CString};
+use std::sync::Arc;
+
+/// Bytes reserved for the technique name in `SudokuHint`, including the NUL
+pub const SUDOKU_TECHNIQUE_LEN: usize = 64;
+/// Bytes reserved for the explanation in `SudokuHint`, including the NUL
+pub const SUDOKU_EXPLANATION_LEN: usize = 512;
+
+/// Result of `sudoku_game_make_move` and `sudoku_game_clear_cell`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SudokuMoveResult {
+    Success = 0,
+    Complete = 1,
+    Conflict = 2,
+    CannotModifyGiven = 3,
+    InvalidValue = 4,
+    /// Null game pointer or row/col outside 0-8
+    InvalidArgument = 5,
+}
+
+impl From<MoveResult> for SudokuMoveResult {
+    fn from(result: MoveResult) -> Self {
+        match result {
+            MoveResult::Success => Self::Success,
+            MoveResult::Complete => Self::Complete,
+            MoveResult::Conflict => Self::Conflict,
+            MoveResult::CannotModifyGiven => Self::CannotModifyGiven,
+            MoveResult::InvalidValue => Self::InvalidValue,
+        }
+    }
+}
+
+/// The whole board, row-major
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SudokuBoard {
+    /// 1-9, or 0 for an empty cell
+    pub values: [u8; 81],
+    /// 1 if the cell is a given
+    pub givens: [u8; 81],
+    /// Pencil marks as bits: bit 1 set means candidate 1, and so on
+    pub candidates: [u16; 81],
+    /// 1 if the cell's value clashes with another in its row, column or box
+    pub conflicts: [u8; 81],
+}
+
+/// A hint; `value` is 0 for an elimination
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SudokuHint {
+    pub row: u8,
+    pub col: u8,
+    /// Value to place, or 0
+    pub value: u8,
+    /// Candidates to remove from the cell, as bits like `SudokuBoard`
+    pub eliminate: u16,
+    pub se_rating: f32,
+    /// NUL-terminated UTF-8, truncated to fit
+    pub technique: [c_char; SUDOKU_TECHNIQUE_LEN],
+    /// NUL-terminated UTF-8, truncated to fit
+    pub explanation: [c_char; SUDOKU_EXPLANATION_LEN],
+}
+
+fn bits(values: &[u8]) -> u16 {
+    values.iter().fold(0, |acc, &v| acc | (1 << v))
+}
+
+/// Copy `text` into `out` as a NUL-terminated string, cutting at a char
+/// boundary if it doesn't fit
+fn copy_str(text: &str, out: &mut [c_char]) {
+    let mut len = text.len().min(out.len() - 1);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    for (dst, &src) in out.iter_mut().zip(&text.as_bytes()[..len]) {
+        *dst = src as c_char;
+    }
+    out[len] = 0;
+}
+
+/// Borrow the game behind a handle
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+unsafe fn game_ref<'a>(game: *const SudokuGame) -> Option<&'a SudokuGame> {
+    game.as_ref()
+}
+
+/// Create a game; `difficulty` is 0 (Beginner) through 7 (Extreme).
+/// Returns null for any other value.
+#[no_mangle]
+pub extern "C" fn sudoku_game_new(difficulty: u8) -> *mut SudokuGame {
+    let difficulty = match difficulty {
+        0 => GameDifficulty::Beginner,
+        1 => GameDifficulty::Easy,
+        2 => GameDifficulty::Medium,
+        3 => GameDifficulty::Intermediate,
+        4 => GameDifficulty::Hard,
+        5 => GameDifficulty::Expert,
+        6 => GameDifficulty::Master,
+        7 => GameDifficulty::Extreme,
+        _ => return std::ptr::null_mut(),
+    };
+    Arc::into_raw(SudokuGame::new_classic(difficulty)).cast_mut()
+}
+
+/// Create a game from an 81-character puzzle string ("0" or "." for empty
+/// cells). Returns null if the string is unreadable or has no solution.
+///
+/// # Safety
+/// `puzzle` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_from_string(puzzle: *const c_char) -> *mut SudokuGame {
+    if puzzle.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Ok(puzzle) = CStr::from_ptr(puzzle).to_str() else {
+        return std::ptr::null_mut();
+    };
+    match game_from_string(puzzle.to_string()) {
+        Some(game) => Arc::into_raw(game).cast_mut(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Release a game. Null is ignored.
+///
+/// # Safety
+/// `game` must be null or a pointer from this module not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_free(game: *mut SudokuGame) {
+    if !game.is_null() {
+        drop(Arc::from_raw(game));
+    }
+}
+
+/// Place `value` (1-9) at `row`, `col` (0-8)
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_make_move(
+    game: *const SudokuGame,
+    row: u8,
+    col: u8,
+    value: u8,
+) -> SudokuMoveResult {
+    match game_ref(game) {
+        Some(game) if row < 9 && col < 9 => game.make_move(row, col, value).into(),
+        _ => SudokuMoveResult::InvalidArgument,
+    }
+}
+
+/// Clear the value at `row`, `col` (0-8)
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_clear_cell(
+    game: *const SudokuGame,
+    row: u8,
+    col: u8,
+) -> SudokuMoveResult {
+    match game_ref(game) {
+        Some(game) if row < 9 && col < 9 => game.clear_cell(row, col).into(),
+        _ => SudokuMoveResult::InvalidArgument,
+    }
+}
+
+/// Undo the last move; false if there was nothing to undo
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_undo(game: *const SudokuGame) -> bool {
+    game_ref(game).is_some_and(SudokuGame::undo)
+}
+
+/// Redo the last undone move; false if there was nothing to redo
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_redo(game: *const SudokuGame) -> bool {
+    game_ref(game).is_some_and(SudokuGame::redo)
+}
+
+/// Fill `out` with the current board; false on a null argument
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module, and `out` null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_get_board(
+    game: *const SudokuGame,
+    out: *mut SudokuBoard,
+) -> bool {
+    let (Some(game), Some(out)) = (game_ref(game), out.as_mut()) else {
+        return false;
+    };
+    for (i, cell) in game.get_all_cells().iter().enumerate() {
+        out.values[i] = cell.value;
+        out.givens[i] = u8::from(cell.is_given);
+        out.candidates[i] = bits(&cell.candidates);
+        out.conflicts[i] = u8::from(cell.has_conflict);
+    }
+    true
+}
+
+/// Fill `out` with the next logical step. False if no technique applies
+/// (or on a null argument); counts as a hint used otherwise.
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module, and `out` null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_get_hint(
+    game: *const SudokuGame,
+    out: *mut SudokuHint,
+) -> bool {
+    let (Some(game), Some(out)) = (game_ref(game), out.as_mut()) else {
+        return false;
+    };
+    let Some(hint) = game.get_hint() else {
+        return false;
+    };
+    out.row = hint.row;
+    out.col = hint.col;
+    out.value = hint.value.unwrap_or(0);
+    out.eliminate = bits(&hint.eliminate);
+    out.se_rating = hint.se_rating;
+    copy_str(&hint.technique, &mut out.technique);
+    copy_str(&hint.explanation, &mut out.explanation);
+    true
+}
+
+/// Whether the board is filled in with no conflicts
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_is_complete(game: *const SudokuGame) -> bool {
+    game_ref(game).is_some_and(SudokuGame::is_complete)
+}
+
+/// The game as JSON (the same save format as the uniffi `serialize`), or
+/// null on a null argument. Release with `sudoku_string_free`.
+///
+/// # Safety
+/// `game` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_game_serialize(game: *const SudokuGame) -> *mut c_char {
+    game_ref(game)
+        .and_then(|game| CString::new(game.serialize()).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Release a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `text` must be null or a string from this module not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{fixture, solution_digits, Tier};
+
+    #[test]
+    fn test_c_api_round_trip() {
+        // Zeros for empty cells, as C callers often write them
+        let puzzle = CString::new(Tier::Singles.puzzle().replace('.', "0")).unwrap();
+        let solution = solution_digits(&fixture(Tier::Singles));
+        unsafe {
+            let game = sudoku_game_from_string(puzzle.as_ptr());
+            assert!(!game.is_null());
+
+            let mut board: SudokuBoard = std::mem::zeroed();
+            assert!(sudoku_game_get_board(game, &mut board));
+            assert_eq!(board.values[0], 5);
+            assert_eq!(board.givens[0], 1);
+            assert_eq!(
+                sudoku_game_make_move(game, 0, 0, 1),
+                SudokuMoveResult::CannotModifyGiven
+            );
+            assert_eq!(
+                sudoku_game_make_move(game, 9, 0, 1),
+                SudokuMoveResult::InvalidArgument
+            );
+
+            let mut hint: SudokuHint = std::mem::zeroed();
+            assert!(sudoku_game_get_hint(game, &mut hint));
+            assert_ne!(hint.technique[0], 0);
+            let answer = solution[usize::from(hint.row) * 9 + usize::from(hint.col)];
+            if hint.value != 0 {
+                assert_eq!(hint.value, answer);
+            } else {
+                assert_eq!(hint.eliminate & bits(&[answer]), 0);
+            }
+
+            sudoku_game_free(game);
+        }
+        assert!(sudoku_game_new(8).is_null());
+
+        // Cut before the two-byte 'é' rather than through it
+        let mut out = [1 as c_char; 3];
+        copy_str("héllo", &mut out);
+        assert_eq!(out, [b'h' as c_char, 0, 1]);
+    }
+}
@@ -1,11 +1,18 @@
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 use sudoku_core::{
-    canonical_puzzle_hash_str, BitSet, Difficulty, Generator, Grid, Hint, HintType, Polarity,
-    Position, ProofCertificate, PuzzleId, Solver,
+    canonical_puzzle_hash_str, Difficulty, Generator, Grid, Hint, HintType, Polarity, Position,
+    ProofCertificate, PuzzleId, Solver,
+};
+use sudoku_session::{
+    ConflictKind, GameMode, GameSession, GameSettings, MinuteMetrics, MistakePolicy, Outcome,
+    SearchProgress,
 };
 
 uniffi::setup_scaffolding!();
 
+pub mod capi;
+
 /// A move result returned from making a move
 #[derive(Debug, Clone, uniffi::Enum)]
 pub enum MoveResult {
@@ -71,6 +78,32 @@ pub struct HintCell {
     pub col: u8,
 }
 
+/// A technique a move made available, for learners
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct MoveFeedback {
+    pub technique: String,
+    /// E.g. "New after this move: Naked Pair in box 7."
+    pub message: String,
+    pub cells: Vec<HintCell>,
+}
+
+impl From<sudoku_glossary::Feedback> for MoveFeedback {
+    fn from(feedback: sudoku_glossary::Feedback) -> Self {
+        MoveFeedback {
+            technique: feedback.technique,
+            message: feedback.message,
+            cells: feedback
+                .cells
+                .iter()
+                .map(|p| HintCell {
+                    row: p.row as u8,
+                    col: p.col as u8,
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Role of a cell in hint visualization
 #[derive(Debug, Clone, Copy, uniffi::Enum)]
 pub enum HintCellRole {
@@ -105,6 +138,88 @@ impl HintCellRole {
     }
 }
 
+/// A row, column or box of a hint's pattern, counting from 0
+#[derive(Debug, Clone, Copy, uniffi::Enum)]
+pub enum HintUnit {
+    Row { index: u8 },
+    Column { index: u8 },
+    Box { index: u8 },
+}
+
+/// A hint's explanation as data, to word in the app's language with
+/// `format_hint_explanation` or the app's own strings
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct HintExplanation {
+    /// Glossary key of the technique, as in `TechniqueInfo.key`
+    pub technique: String,
+    /// Cells of the pattern
+    pub cells: Vec<HintCell>,
+    /// Units every pattern cell lies in, box first
+    pub units: Vec<HintUnit>,
+    /// True if the hint places a digit, false if it removes candidates
+    pub places: bool,
+    /// The cell the hint changes
+    pub target: HintCell,
+    /// The digit placed, or the candidates removed
+    pub digits: Vec<u8>,
+}
+
+fn hint_cell(pos: &Position) -> HintCell {
+    HintCell {
+        row: pos.row as u8,
+        col: pos.col as u8,
+    }
+}
+
+impl From<&sudoku_glossary::ExplanationTemplate> for HintExplanation {
+    fn from(template: &sudoku_glossary::ExplanationTemplate) -> Self {
+        use sudoku_glossary::Unit;
+        HintExplanation {
+            technique: template.technique.clone(),
+            cells: template.cells.iter().map(hint_cell).collect(),
+            units: template
+                .units
+                .iter()
+                .map(|unit| match *unit {
+                    Unit::Row(n) => HintUnit::Row { index: n as u8 },
+                    Unit::Column(n) => HintUnit::Column { index: n as u8 },
+                    Unit::Box(n) => HintUnit::Box { index: n as u8 },
+                })
+                .collect(),
+            places: template.action == sudoku_glossary::Action::Place,
+            target: hint_cell(&template.target),
+            digits: template.digits.clone(),
+        }
+    }
+}
+
+impl From<HintExplanation> for sudoku_glossary::ExplanationTemplate {
+    fn from(explanation: HintExplanation) -> Self {
+        use sudoku_glossary::{Action, Unit};
+        let position = |cell: &HintCell| Position::new(cell.row as usize, cell.col as usize);
+        sudoku_glossary::ExplanationTemplate {
+            technique: explanation.technique,
+            cells: explanation.cells.iter().map(position).collect(),
+            units: explanation
+                .units
+                .iter()
+                .map(|unit| match *unit {
+                    HintUnit::Row { index } => Unit::Row(index as usize),
+                    HintUnit::Column { index } => Unit::Column(index as usize),
+                    HintUnit::Box { index } => Unit::Box(index as usize),
+                })
+                .collect(),
+            action: if explanation.places {
+                Action::Place
+            } else {
+                Action::Eliminate
+            },
+            target: position(&explanation.target),
+            digits: explanation.digits,
+        }
+    }
+}
+
 /// A hint for the player
 #[derive(Debug, Clone, uniffi::Record)]
 pub struct GameHint {
@@ -118,6 +233,8 @@ pub struct GameHint {
     pub eliminate: Vec<u8>,
     /// Human-readable explanation
     pub explanation: String,
+    /// The explanation as data, for other languages
+    pub template: HintExplanation,
     /// The technique name
     pub technique: String,
     /// Sudoku Explainer (SE) difficulty rating for this technique
@@ -128,6 +245,7 @@ pub struct GameHint {
 
 impl From<Hint> for GameHint {
     fn from(hint: Hint) -> Self {
+        let template = (&sudoku_glossary::ExplanationTemplate::from_hint(&hint)).into();
         let (row, col, value, eliminate) = match hint.hint_type {
             HintType::SetValue { pos, value } => {
                 (pos.row as u8, pos.col as u8, Some(value), vec![])
@@ -153,6 +271,7 @@ impl From<Hint> for GameHint {
             value,
             eliminate,
             explanation: hint.explanation,
+            template,
             technique: hint.technique.to_string(),
             se_rating,
             involved_cells,
@@ -160,6 +279,160 @@ impl From<Hint> for GameHint {
     }
 }
 
+/// What happens when a digit doesn't match the solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum MistakeRule {
+    /// Not checked against the solution
+    Ignore,
+    /// Counted, never game over
+    Count,
+    /// Counted; the game is lost at `max`. A `max` of 0 is read as `Count`
+    Limit { max: u32 },
+}
+
+/// Assists and mistake rules for a game, saved with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct AssistSettings {
+    /// Refill every empty cell's notes after each placement
+    pub auto_candidates: bool,
+    pub mistakes: MistakeRule,
+    /// Remove a placed digit from its peers' notes
+    pub smart_notes: bool,
+    pub highlight_same_digit: bool,
+    pub show_timer: bool,
+    /// No mistake limit
+    pub zen: bool,
+}
+
+impl From<GameSettings> for AssistSettings {
+    fn from(settings: GameSettings) -> Self {
+        AssistSettings {
+            auto_candidates: settings.auto_candidates,
+            mistakes: match settings.mistakes {
+                MistakePolicy::Ignore => MistakeRule::Ignore,
+                MistakePolicy::Count => MistakeRule::Count,
+                MistakePolicy::Limit(0) => MistakeRule::Count,
+                MistakePolicy::Limit(max) => MistakeRule::Limit { max },
+            },
+            smart_notes: settings.smart_notes,
+            highlight_same_digit: settings.highlight_same_digit,
+            show_timer: settings.show_timer,
+            zen: settings.zen,
+        }
+    }
+}
+
+impl From<AssistSettings> for GameSettings {
+    fn from(settings: AssistSettings) -> Self {
+        GameSettings {
+            auto_candidates: settings.auto_candidates,
+            mistakes: match settings.mistakes {
+                MistakeRule::Ignore => MistakePolicy::Ignore,
+                MistakeRule::Count => MistakePolicy::Count,
+                MistakeRule::Limit { max } => MistakePolicy::limit(max),
+            },
+            smart_notes: settings.smart_notes,
+            highlight_same_digit: settings.highlight_same_digit,
+            show_timer: settings.show_timer,
+            zen: settings.zen,
+        }
+    }
+}
+
+/// The assists new games start with
+#[uniffi::export]
+pub fn default_assist_settings() -> AssistSettings {
+    GameSettings::default().into()
+}
+
+/// What the player did during one minute of play, for a post-game sparkline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct MinuteActivity {
+    /// Minutes of play before this one, from 0
+    pub minute: u32,
+    /// Digits placed, right or wrong
+    pub placements: u32,
+    pub candidate_edits: u32,
+    pub mistakes: u32,
+}
+
+impl From<MinuteMetrics> for MinuteActivity {
+    fn from(metrics: MinuteMetrics) -> Self {
+        MinuteActivity {
+            minute: metrics.minute,
+            placements: metrics.placements,
+            candidate_edits: metrics.candidate_edits,
+            mistakes: metrics.mistakes,
+        }
+    }
+}
+
+/// How a game is played and judged; each mode has its own leaderboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum PlayMode {
+    Classic,
+    /// Lost if not solved within `target_secs`
+    TimeAttack {
+        target_secs: u64,
+    },
+    /// At most `max_hints` hints
+    HintChallenge {
+        max_hints: u32,
+    },
+    /// No notes
+    Hardcore,
+}
+
+impl From<GameMode> for PlayMode {
+    fn from(mode: GameMode) -> Self {
+        match mode {
+            GameMode::Classic => PlayMode::Classic,
+            GameMode::TimeAttack { target_secs } => PlayMode::TimeAttack { target_secs },
+            GameMode::HintChallenge { max_hints } => PlayMode::HintChallenge { max_hints },
+            GameMode::Hardcore => PlayMode::Hardcore,
+        }
+    }
+}
+
+impl From<PlayMode> for GameMode {
+    fn from(mode: PlayMode) -> Self {
+        match mode {
+            PlayMode::Classic => GameMode::Classic,
+            PlayMode::TimeAttack { target_secs } => GameMode::TimeAttack { target_secs },
+            PlayMode::HintChallenge { max_hints } => GameMode::HintChallenge { max_hints },
+            PlayMode::Hardcore => GameMode::Hardcore,
+        }
+    }
+}
+
+/// A mode by name ("classic", "time-attack", "hints", "hardcore") with its
+/// default target or hint allowance
+#[uniffi::export]
+pub fn play_mode_from_name(name: String) -> Option<PlayMode> {
+    GameMode::from_name(&name).map(PlayMode::from)
+}
+
+/// What's wrong with a cell, most severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum CellConflict {
+    /// The digit repeats in its row, column or box
+    RuleConflict,
+    /// The digit breaks no rule but isn't the solution's
+    Incorrect,
+    /// An empty cell none of whose notes, or no digit at all, can fit
+    CandidateContradiction,
+}
+
+impl From<ConflictKind> for CellConflict {
+    fn from(kind: ConflictKind) -> Self {
+        match kind {
+            ConflictKind::RuleConflict => CellConflict::RuleConflict,
+            ConflictKind::Incorrect => CellConflict::Incorrect,
+            ConflictKind::CandidateContradiction => CellConflict::CandidateContradiction,
+        }
+    }
+}
+
 /// Cell state for UI rendering
 #[derive(Debug, Clone, uniffi::Record)]
 pub struct CellState {
@@ -175,24 +448,40 @@ pub struct CellState {
     pub candidates: Vec<u8>,
     /// Whether this cell has a conflict
     pub has_conflict: bool,
+    /// The conflict to show, if any; zen shows only rule conflicts
+    pub conflict: Option<CellConflict>,
+}
+
+/// Wall-clock milliseconds, for the session clock
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
 }
 
-type UndoEntry = (usize, usize, Option<u8>, BitSet);
+/// A session on a new board with its clock running
+fn start_session(grid: Grid, solution: Grid, difficulty: Difficulty) -> GameSession {
+    let mut session = GameSession::new(grid, solution);
+    session.set_difficulty(difficulty);
+    session.start_clock(now_ms());
+    session
+}
 
 /// The main Sudoku game interface for mobile platforms
 #[derive(uniffi::Object)]
 pub struct SudokuGame {
-    grid: Mutex<Grid>,
-    solution: Mutex<Grid>,
+    /// Board, solution, undo history, counts, clock and assists
+    session: Mutex<GameSession>,
     difficulty: Mutex<Difficulty>,
     rated_difficulty: Mutex<Difficulty>,
-    undo_stack: Mutex<Vec<UndoEntry>>,
-    redo_stack: Mutex<Vec<UndoEntry>>,
-    hints_used: Mutex<usize>,
-    mistakes: Mutex<usize>,
     seed: Mutex<Option<u64>>,
     last_hint: Mutex<Option<Hint>>,
     se_rating_cache: Mutex<Option<f32>>,
+    /// Difficulty of what's left, with the board it was rated for
+    remaining_cache: Mutex<Option<(String, Difficulty)>>,
+    /// Locale for move feedback; None when it's off
+    feedback_locale: Mutex<Option<String>>,
+    last_feedback: Mutex<Vec<MoveFeedback>>,
 }
 
 #[uniffi::export]
@@ -211,17 +500,15 @@ impl SudokuGame {
             .expect("Generated puzzle should be solvable");
 
         Arc::new(Self {
-            grid: Mutex::new(grid),
-            solution: Mutex::new(solution),
+            session: Mutex::new(start_session(grid, solution, diff)),
             difficulty: Mutex::new(diff),
             rated_difficulty: Mutex::new(rated),
-            undo_stack: Mutex::new(Vec::new()),
-            redo_stack: Mutex::new(Vec::new()),
-            hints_used: Mutex::new(0),
-            mistakes: Mutex::new(0),
             seed: Mutex::new(Some(puzzle_id.seed)),
             last_hint: Mutex::new(None),
             se_rating_cache: Mutex::new(None),
+            remaining_cache: Mutex::new(None),
+            feedback_locale: Mutex::new(None),
+            last_feedback: Mutex::new(Vec::new()),
         })
     }
 
@@ -238,17 +525,15 @@ impl SudokuGame {
             .expect("Generated puzzle should be solvable");
 
         Arc::new(Self {
-            grid: Mutex::new(grid),
-            solution: Mutex::new(solution),
+            session: Mutex::new(start_session(grid, solution, rated)),
             difficulty: Mutex::new(rated),
             rated_difficulty: Mutex::new(rated),
-            undo_stack: Mutex::new(Vec::new()),
-            redo_stack: Mutex::new(Vec::new()),
-            hints_used: Mutex::new(0),
-            mistakes: Mutex::new(0),
             seed: Mutex::new(None),
             last_hint: Mutex::new(None),
             se_rating_cache: Mutex::new(None),
+            remaining_cache: Mutex::new(None),
+            feedback_locale: Mutex::new(None),
+            last_feedback: Mutex::new(Vec::new()),
         })
     }
 
@@ -259,74 +544,71 @@ impl SudokuGame {
         }
 
         let pos = Position::new(row as usize, col as usize);
-        let mut grid = self.grid.lock().unwrap();
-        let solution = self.solution.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
 
-        if grid.cell(pos).is_given() {
+        if session.grid().cell(pos).is_given() {
             return MoveResult::CannotModifyGiven;
         }
 
-        // Save for undo (including current candidates so they can be restored)
-        let old_value = grid.get(pos);
-        let old_candidates = grid.cell(pos).candidates();
-        self.undo_stack.lock().unwrap().push((
-            row as usize,
-            col as usize,
-            old_value,
-            old_candidates,
-        ));
-        self.redo_stack.lock().unwrap().clear();
-
-        // Check if correct
-        let is_correct = solution.get(pos) == Some(value);
-        if !is_correct {
-            *self.mistakes.lock().unwrap() += 1;
+        let is_correct = session.solution().get(pos) == Some(value);
+        let mut feedback = self.last_feedback.lock().unwrap();
+        feedback.clear();
+        if let Some(locale) = &*self.feedback_locale.lock().unwrap() {
+            if is_correct {
+                feedback.extend(
+                    sudoku_glossary::after_placement(session.grid(), pos, value, locale)
+                        .into_iter()
+                        .map(MoveFeedback::from),
+                );
+            }
         }
 
-        // Set the value and remove it from peer candidates
-        grid.set_cell_unchecked(pos, Some(value));
-        grid.update_candidates_after_move(pos, value);
+        // The session records the undo step, counts the mistake and updates
+        // notes as the settings ask
+        session.sync_clock(now_ms());
+        session.place(pos, value);
+        if session.outcome(now_ms()) != Outcome::Playing {
+            session.pause_clock(now_ms());
+        }
 
         // Check for conflicts
-        let values = grid.values();
+        let values = session.grid().values();
         let has_conflict = Self::check_conflict(&values, pos, value);
 
         if has_conflict {
             return MoveResult::Conflict;
         }
 
-        if grid.is_complete() && grid.validate().is_valid {
+        if session.is_complete() {
             return MoveResult::Complete;
         }
 
         MoveResult::Success
     }
 
+    /// Turn per-move technique feedback on, worded in `locale`, or off with
+    /// None. Off by default, since each correct move then runs the solver
+    /// twice.
+    pub fn set_move_feedback(&self, locale: Option<String>) {
+        *self.feedback_locale.lock().unwrap() = locale;
+        self.last_feedback.lock().unwrap().clear();
+    }
+
+    /// Techniques the last correct move made available, when feedback is on
+    pub fn get_move_feedback(&self) -> Vec<MoveFeedback> {
+        self.last_feedback.lock().unwrap().clone()
+    }
+
     /// Clear a cell
     pub fn clear_cell(&self, row: u8, col: u8) -> MoveResult {
         let pos = Position::new(row as usize, col as usize);
-        let mut grid = self.grid.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
 
-        if grid.cell(pos).is_given() {
+        if session.grid().cell(pos).is_given() {
             return MoveResult::CannotModifyGiven;
         }
 
-        let old_value = grid.get(pos);
-        if old_value.is_none() {
-            return MoveResult::Success;
-        }
-
-        let old_candidates = grid.cell(pos).candidates();
-        self.undo_stack.lock().unwrap().push((
-            row as usize,
-            col as usize,
-            old_value,
-            old_candidates,
-        ));
-        self.redo_stack.lock().unwrap().clear();
-
-        grid.set_cell_unchecked(pos, None);
-
+        session.clear(pos);
         MoveResult::Success
     }
 
@@ -337,7 +619,11 @@ impl SudokuGame {
         }
 
         let pos = Position::new(row as usize, col as usize);
-        let mut grid = self.grid.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
+        if !session.mode().allows_notes() {
+            return false;
+        }
+        let grid = session.grid_mut();
 
         let cell = grid.cell(pos);
         if cell.is_given() || cell.is_filled() {
@@ -345,74 +631,54 @@ impl SudokuGame {
         }
 
         grid.cell_mut(pos).toggle_candidate(value);
+        Self::count_candidate_edit(&mut session);
         true
     }
 
     /// Undo the last move
     pub fn undo(&self) -> bool {
-        let mut undo_stack = self.undo_stack.lock().unwrap();
-        if let Some((row, col, old_value, old_candidates)) = undo_stack.pop() {
-            let pos = Position::new(row, col);
-            let mut grid = self.grid.lock().unwrap();
-            let current_value = grid.get(pos);
-            let current_candidates = grid.cell(pos).candidates();
-
-            self.redo_stack
-                .lock()
-                .unwrap()
-                .push((row, col, current_value, current_candidates));
-
-            grid.set_cell_unchecked(pos, old_value);
-            // Restore the cell's own candidates from before the move
-            if old_value.is_none() {
-                grid.cell_mut(pos).set_candidates(old_candidates);
-            }
-            // If restoring a value, remove it from peer candidates
-            if let Some(v) = old_value {
-                grid.update_candidates_after_move(pos, v);
-            }
-            true
-        } else {
-            false
-        }
+        self.session.lock().unwrap().undo().is_some()
     }
 
     /// Redo the last undone move
     pub fn redo(&self) -> bool {
-        let mut redo_stack = self.redo_stack.lock().unwrap();
-        if let Some((row, col, value, saved_candidates)) = redo_stack.pop() {
-            let pos = Position::new(row, col);
-            let mut grid = self.grid.lock().unwrap();
-            let current_value = grid.get(pos);
-            let current_candidates = grid.cell(pos).candidates();
-
-            self.undo_stack
-                .lock()
-                .unwrap()
-                .push((row, col, current_value, current_candidates));
-
-            grid.set_cell_unchecked(pos, value);
-            // Restore the cell's candidates from the redo snapshot
-            if value.is_none() {
-                grid.cell_mut(pos).set_candidates(saved_candidates);
-            }
-            // If placing a value, remove it from peer candidates
-            if let Some(v) = value {
-                grid.update_candidates_after_move(pos, v);
-            }
-            true
-        } else {
-            false
-        }
+        self.session.lock().unwrap().redo().is_some()
+    }
+
+    /// Save the board and notes under `name`, replacing a bookmark of that name
+    pub fn save_bookmark(&self, name: String) {
+        self.session.lock().unwrap().save_bookmark(&name);
+    }
+
+    /// Go back to a saved bookmark as one undoable move
+    pub fn restore_bookmark(&self, name: String) -> bool {
+        self.session
+            .lock()
+            .unwrap()
+            .restore_bookmark(&name)
+            .is_some()
+    }
+
+    pub fn delete_bookmark(&self, name: String) -> bool {
+        self.session.lock().unwrap().remove_bookmark(&name)
+    }
+
+    /// Names of the saved bookmarks, oldest first
+    pub fn bookmark_names(&self) -> Vec<String> {
+        let session = self.session.lock().unwrap();
+        session.bookmarks().iter().map(|b| b.name.clone()).collect()
     }
 
-    /// Get a hint
+    /// Get a hint; None when there's none or the mode has no hints left
     pub fn get_hint(&self) -> Option<GameHint> {
-        let grid = self.grid.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
+        if !session.can_hint() {
+            return None;
+        }
         let solver = Solver::new();
 
-        if let Some(hint) = solver.get_hint(&grid) {
-            *self.hints_used.lock().unwrap() += 1;
+        if let Some(hint) = solver.get_hint(session.grid()) {
+            session.record_hint();
             let game_hint: GameHint = hint.clone().into();
             *self.last_hint.lock().unwrap() = Some(hint);
             Some(game_hint)
@@ -421,6 +687,27 @@ impl SudokuGame {
         }
     }
 
+    /// Like `get_hint`, but only with a technique no harder than `max_level`;
+    /// None if every step left needs more
+    pub fn get_hint_within(&self, max_level: GameDifficulty) -> Option<GameHint> {
+        let mut session = self.session.lock().unwrap();
+        if !session.can_hint() {
+            return None;
+        }
+        let hint = sudoku_session::hint_within(session.grid(), max_level.into())?;
+        session.record_hint();
+        let game_hint: GameHint = hint.clone().into();
+        *self.last_hint.lock().unwrap() = Some(hint);
+        Some(game_hint)
+    }
+
+    /// Whether the board as it stands can be finished with techniques no
+    /// harder than `max_level`
+    pub fn solvable_within(&self, max_level: GameDifficulty) -> bool {
+        let session = self.session.lock().unwrap();
+        sudoku_session::solve_path_within(session.grid(), max_level.into()).solved
+    }
+
     /// Get cell roles for hint visualization.
     /// Returns 81 u8 values (one per cell), encoding HintCellRole.
     /// detail_level: 0 = Summary (target + involved), 1 = ProofDetail (proof-specific roles).
@@ -440,30 +727,46 @@ impl SudokuGame {
         *self.last_hint.lock().unwrap() = None;
     }
 
+    /// Every step from the board as it stands to the solution, for a
+    /// walkthrough; stops early if no technique applies. Doesn't change the
+    /// board or count as hints.
+    pub fn solve_path(&self) -> Vec<GameHint> {
+        let session = self.session.lock().unwrap();
+        sudoku_session::solve_path(session.grid())
+            .steps
+            .into_iter()
+            .map(GameHint::from)
+            .collect()
+    }
+
     /// Get the current value at a position (0 if empty)
     pub fn get_value(&self, row: u8, col: u8) -> u8 {
         let pos = Position::new(row as usize, col as usize);
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         grid.get(pos).unwrap_or(0)
     }
 
     /// Get candidates at a position
     pub fn get_candidates(&self, row: u8, col: u8) -> Vec<u8> {
         let pos = Position::new(row as usize, col as usize);
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         grid.get_candidates(pos).to_vec()
     }
 
     /// Check if a cell is given
     pub fn is_given(&self, row: u8, col: u8) -> bool {
         let pos = Position::new(row as usize, col as usize);
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         grid.cell(pos).is_given()
     }
 
     /// Get all cell states (for efficient bulk rendering)
     pub fn get_all_cells(&self) -> Vec<CellState> {
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         let values = grid.values();
 
         let mut cells = Vec::with_capacity(81);
@@ -485,6 +788,7 @@ impl SudokuGame {
                     is_given: cell.is_given(),
                     candidates: cell.candidates().to_vec(),
                     has_conflict,
+                    conflict: session.conflict(pos).map(CellConflict::from),
                 });
             }
         }
@@ -507,43 +811,153 @@ impl SudokuGame {
         if let Some(cached) = *self.se_rating_cache.lock().unwrap() {
             return cached;
         }
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
         let solver = Solver::new();
-        let rating = solver.rate_se(&grid);
+        let rating = solver.rate_se(session.grid());
         *self.se_rating_cache.lock().unwrap() = Some(rating);
         rating
     }
 
+    /// Difficulty of what's left to solve, rated from the correct digits on
+    /// the board, so it falls (say from Expert to Easy) as the solve
+    /// progresses. Rated again only after the board changes.
+    pub fn remaining_difficulty(&self) -> GameDifficulty {
+        let current = {
+            let session = self.session.lock().unwrap();
+            let (grid, solution) = (session.grid(), session.solution());
+            (0..81)
+                .map(|i| {
+                    let pos = Position::new(i / 9, i % 9);
+                    match grid.cell(pos).value() {
+                        Some(v) if solution.cell(pos).value() == Some(v) => char::from(b'0' + v),
+                        _ => '.',
+                    }
+                })
+                .collect::<String>()
+        };
+        let mut cache = self.remaining_cache.lock().unwrap();
+        if let Some((board, difficulty)) = &*cache {
+            if *board == current {
+                return (*difficulty).into();
+            }
+        }
+        let difficulty = Grid::from_string(&current)
+            .map_or(*self.rated_difficulty.lock().unwrap(), |grid| {
+                Solver::new().rate_difficulty(&grid)
+            });
+        *cache = Some((current, difficulty));
+        difficulty.into()
+    }
+
     /// Get the number of hints used
     pub fn get_hints_used(&self) -> u32 {
-        *self.hints_used.lock().unwrap() as u32
+        self.session.lock().unwrap().hints_used() as u32
     }
 
     /// Get the number of mistakes made
     pub fn get_mistakes(&self) -> u32 {
-        *self.mistakes.lock().unwrap() as u32
+        self.session.lock().unwrap().mistakes() as u32
+    }
+
+    /// Mistakes left before the game is lost; None without a limit
+    pub fn mistakes_left(&self) -> Option<u32> {
+        let left = self.session.lock().unwrap().mistakes_left();
+        left.map(|n| n as u32)
+    }
+
+    /// Points so far, scored the same way on every platform; a finished
+    /// puzzle includes its time bonus
+    pub fn current_score(&self) -> u32 {
+        self.session.lock().unwrap().current_score(now_ms())
+    }
+
+    /// Whether the mistake limit has been reached or a timed mode's clock
+    /// has run out
+    pub fn is_game_over(&self) -> bool {
+        self.session.lock().unwrap().outcome(now_ms()) == Outcome::Lost
+    }
+
+    pub fn game_mode(&self) -> PlayMode {
+        self.session.lock().unwrap().mode().into()
+    }
+
+    /// Play this game under `mode`; Hardcore clears every note and turns
+    /// auto-candidates off
+    pub fn set_game_mode(&self, mode: PlayMode) {
+        self.session.lock().unwrap().set_mode(mode.into());
+    }
+
+    /// Leaderboard a win counts for, e.g. "time_attack"
+    pub fn leaderboard_category(&self) -> String {
+        let mode = self.session.lock().unwrap().mode();
+        mode.leaderboard_category().to_string()
+    }
+
+    /// Whether the mode leaves a hint to use
+    pub fn can_hint(&self) -> bool {
+        self.session.lock().unwrap().can_hint()
+    }
+
+    /// Placements, note edits and mistakes per minute of play so far
+    pub fn metrics_timeline(&self) -> Vec<MinuteActivity> {
+        let mut session = self.session.lock().unwrap();
+        session.sync_clock(now_ms());
+        session
+            .metrics_timeline()
+            .into_iter()
+            .map(MinuteActivity::from)
+            .collect()
+    }
+
+    pub fn assist_settings(&self) -> AssistSettings {
+        (*self.session.lock().unwrap().settings()).into()
+    }
+
+    /// Change the assists mid-game. Turning auto-candidates on fills every
+    /// empty cell's notes.
+    pub fn set_assist_settings(&self, settings: AssistSettings) {
+        self.session.lock().unwrap().set_settings(settings.into());
+    }
+
+    /// Time played, not counting pauses
+    pub fn get_elapsed_secs(&self) -> u64 {
+        self.session.lock().unwrap().elapsed_ms(now_ms()) / 1000
+    }
+
+    /// Stop the clock, e.g. when the app goes to the background
+    pub fn pause_timer(&self) {
+        self.session.lock().unwrap().pause_clock(now_ms());
+    }
+
+    /// Restart the clock after [`pause_timer`](Self::pause_timer)
+    pub fn resume_timer(&self) {
+        self.session.lock().unwrap().start_clock(now_ms());
     }
 
     /// Check if the puzzle is complete
     pub fn is_complete(&self) -> bool {
-        let grid = self.grid.lock().unwrap();
-        grid.is_complete() && grid.validate().is_valid
+        self.session.lock().unwrap().is_complete()
     }
 
     /// Serialize the game state for saving
     pub fn serialize(&self) -> String {
-        let grid = self.grid.lock().unwrap();
-        let solution = self.solution.lock().unwrap();
+        let session = self.session.lock().unwrap();
         let difficulty = self.difficulty.lock().unwrap();
         let rated_difficulty = self.rated_difficulty.lock().unwrap();
 
         serde_json::json!({
-            "puzzle": grid.to_string_compact(),
-            "solution": solution.to_string_compact(),
+            "puzzle": session.grid().to_string_compact(),
+            "solution": session.solution().to_string_compact(),
             "difficulty": format!("{:?}", *difficulty),
             "rated_difficulty": format!("{:?}", *rated_difficulty),
-            "hints_used": *self.hints_used.lock().unwrap(),
-            "mistakes": *self.mistakes.lock().unwrap(),
+            "hints_used": session.hints_used(),
+            "mistakes": session.mistakes(),
+            "settings": session.settings(),
+            "elapsed_secs": session.elapsed_ms(now_ms()) / 1000,
+            "score": session.score(),
+            "mode": session.mode(),
+            "bookmarks": session.bookmarks(),
+            "timeline": session.metrics_timeline(),
         })
         .to_string()
     }
@@ -551,14 +965,16 @@ impl SudokuGame {
     /// Get valid candidates for a cell (for ghost hints feature)
     pub fn get_valid_candidates(&self, row: u8, col: u8) -> Vec<u8> {
         let pos = Position::new(row as usize, col as usize);
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         grid.compute_candidates(pos).iter().collect()
     }
 
     /// Check if a cell is a naked single (only one valid candidate)
     pub fn is_naked_single(&self, row: u8, col: u8) -> bool {
         let pos = Position::new(row as usize, col as usize);
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         let cell = grid.cell(pos);
         if cell.is_given() || cell.is_filled() {
             return false;
@@ -569,46 +985,59 @@ impl SudokuGame {
     /// Fill candidates for a single cell with valid values
     pub fn fill_cell_candidates(&self, row: u8, col: u8) -> bool {
         let pos = Position::new(row as usize, col as usize);
-        let mut grid = self.grid.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
+        if !session.mode().allows_notes() {
+            return false;
+        }
+        let grid = session.grid_mut();
         let cell = grid.cell(pos);
         if cell.is_given() || cell.is_filled() {
             return false;
         }
         let valid = grid.compute_candidates(pos);
         grid.cell_mut(pos).set_candidates(valid);
+        Self::count_candidate_edit(&mut session);
         true
     }
 
-    /// Fill all empty cells with their valid candidates
+    /// Fill all empty cells with their valid candidates, unless the mode
+    /// forbids notes
     pub fn fill_all_candidates(&self) {
-        let mut grid = self.grid.lock().unwrap();
-        grid.recalculate_candidates();
+        let mut session = self.session.lock().unwrap();
+        if session.mode().allows_notes() {
+            session.grid_mut().recalculate_candidates();
+            Self::count_candidate_edit(&mut session);
+        }
     }
 
     /// Clear candidates from a single cell
     pub fn clear_cell_candidates(&self, row: u8, col: u8) -> bool {
         let pos = Position::new(row as usize, col as usize);
-        let mut grid = self.grid.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
+        let grid = session.grid_mut();
         let cell = grid.cell(pos);
         if cell.is_given() || cell.is_filled() {
             return false;
         }
         grid.cell_mut(pos)
             .set_candidates(sudoku_core::BitSet::empty());
+        Self::count_candidate_edit(&mut session);
         true
     }
 
     /// Clear all candidates from all cells
     pub fn clear_all_candidates(&self) {
-        let mut grid = self.grid.lock().unwrap();
-        grid.clear_all_candidates();
+        let mut session = self.session.lock().unwrap();
+        session.grid_mut().clear_all_candidates();
+        Self::count_candidate_edit(&mut session);
     }
 
     /// Remove invalid candidates - keep only candidates that match the solution
     /// This is the "Check Notes" feature - removes wrong pencil marks
     pub fn remove_invalid_candidates(&self) {
-        let mut grid = self.grid.lock().unwrap();
-        let solution = self.solution.lock().unwrap();
+        let mut session = self.session.lock().unwrap();
+        let solution = session.solution().clone();
+        let grid = session.grid_mut();
 
         for row in 0..9 {
             for col in 0..9 {
@@ -637,21 +1066,21 @@ impl SudokuGame {
     /// Get the correct value for a cell (from solution)
     pub fn get_solution_value(&self, row: u8, col: u8) -> u8 {
         let pos = Position::new(row as usize, col as usize);
-        let solution = self.solution.lock().unwrap();
-        solution.get(pos).unwrap_or(0)
+        let session = self.session.lock().unwrap();
+        session.solution().get(pos).unwrap_or(0)
     }
 
     /// Check if the current value at a position is correct
     pub fn is_value_correct(&self, row: u8, col: u8) -> bool {
         let pos = Position::new(row as usize, col as usize);
-        let grid = self.grid.lock().unwrap();
-        let solution = self.solution.lock().unwrap();
-        grid.get(pos) == solution.get(pos)
+        let session = self.session.lock().unwrap();
+        session.grid().get(pos) == session.solution().get(pos)
     }
 
     /// Get count of remaining empty cells
     pub fn get_empty_count(&self) -> u32 {
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         let mut count = 0u32;
         for row in 0..9 {
             for col in 0..9 {
@@ -665,7 +1094,8 @@ impl SudokuGame {
 
     /// Get count of each number placed (for number completion indicator)
     pub fn get_number_counts(&self) -> Vec<u8> {
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         let values = grid.values();
         let mut counts = [0u8; 9];
         for row in &values {
@@ -680,17 +1110,18 @@ impl SudokuGame {
 
     /// Check if can undo
     pub fn can_undo(&self) -> bool {
-        !self.undo_stack.lock().unwrap().is_empty()
+        self.session.lock().unwrap().can_undo()
     }
 
     /// Check if can redo
     pub fn can_redo(&self) -> bool {
-        !self.redo_stack.lock().unwrap().is_empty()
+        self.session.lock().unwrap().can_redo()
     }
 
     /// Get the puzzle as an 81-character string (givens as digits, empty as '.')
     pub fn get_puzzle_string(&self) -> String {
-        let grid = self.grid.lock().unwrap();
+        let session = self.session.lock().unwrap();
+        let grid = session.grid();
         // Return only givens: non-given cells become '.'
         let mut result = String::with_capacity(81);
         for row in 0..9 {
@@ -713,33 +1144,25 @@ impl SudokuGame {
     /// Apply a hint automatically (verified against backtracking solution)
     pub fn apply_hint(&self) -> Option<GameHint> {
         let solver = Solver::new();
-        let hint = {
-            let grid = self.grid.lock().unwrap();
-            solver.get_next_placement(&grid)?
-        };
+        let mut session = self.session.lock().unwrap();
+        if !session.can_hint() {
+            return None;
+        }
+        let hint = solver.get_next_placement(session.grid())?;
 
-        *self.hints_used.lock().unwrap() += 1;
+        session.record_hint();
         // Clear stale display hint (it came from unverified get_hint)
         *self.last_hint.lock().unwrap() = None;
 
         match &hint.hint_type {
             HintType::SetValue { pos, value } => {
-                let mut grid = self.grid.lock().unwrap();
-                let solution = self.solution.lock().unwrap();
                 // Trust the stored solution for the correct value.
                 // get_next_placement() solves the *current* grid (which may contain
                 // player mistakes), so its placement can disagree with the original
-                // solution. Always trust self.solution to avoid false "mistake" counts.
-                let correct_value = solution.get(*pos).unwrap_or(*value);
-                let old_value = grid.get(*pos);
-                let old_candidates = grid.cell(*pos).candidates();
-                self.undo_stack
-                    .lock()
-                    .unwrap()
-                    .push((pos.row, pos.col, old_value, old_candidates));
-                self.redo_stack.lock().unwrap().clear();
-                grid.set_cell_unchecked(*pos, Some(correct_value));
-                grid.update_candidates_after_move(*pos, correct_value);
+                // solution. Always trust the session's solution to avoid false
+                // "mistake" counts.
+                let correct_value = session.solution().get(*pos).unwrap_or(*value);
+                session.place(*pos, correct_value);
             }
             HintType::EliminateCandidates { .. } => {
                 // get_next_placement should always return SetValue, but
@@ -765,6 +1188,12 @@ impl SudokuGame {
 }
 
 impl SudokuGame {
+    /// Count a notes change in the activity timeline, dated now
+    fn count_candidate_edit(session: &mut GameSession) {
+        session.sync_clock(now_ms());
+        session.record_candidate_edit();
+    }
+
     /// Return cells belonging to a sector index.
     /// Convention: 0..8=rows, 9..17=cols, 18..26=boxes.
     fn sector_cells(sector: usize) -> Vec<usize> {
@@ -972,17 +1401,60 @@ pub fn game_from_string(puzzle: String) -> Option<Arc<SudokuGame>> {
     let difficulty = solver.rate_difficulty(&grid);
 
     Some(Arc::new(SudokuGame {
-        grid: Mutex::new(grid.clone()),
-        solution: Mutex::new(solution),
+        session: Mutex::new(start_session(grid, solution, difficulty)),
         difficulty: Mutex::new(difficulty),
-        rated_difficulty: Mutex::new(solver.rate_difficulty(&grid)),
-        undo_stack: Mutex::new(Vec::new()),
-        redo_stack: Mutex::new(Vec::new()),
-        hints_used: Mutex::new(0),
-        mistakes: Mutex::new(0),
+        rated_difficulty: Mutex::new(difficulty),
         seed: Mutex::new(None),
         last_hint: Mutex::new(None),
         se_rating_cache: Mutex::new(None),
+        remaining_cache: Mutex::new(None),
+        feedback_locale: Mutex::new(None),
+        last_feedback: Mutex::new(Vec::new()),
+    }))
+}
+
+/// Told about each puzzle tried while searching for an SE rating, so the
+/// app can show progress and let the player cancel
+#[uniffi::export(callback_interface)]
+pub trait GenerationObserver: Send + Sync {
+    /// `best_se` is the rating of the closest puzzle so far; return false
+    /// to cancel the search
+    fn on_progress(&self, attempts: u32, best_se: Option<f32>) -> bool;
+}
+
+/// Create a game close to `target_se`, trying at most `max_attempts`
+/// puzzles and reporting each to `observer`. Returns the closest puzzle
+/// found, or None if the observer cancelled. Call it off the main thread.
+///
+/// Cancelling is only noticed between attempts, when `on_progress` is
+/// called. Each attempt generates and rates a whole puzzle, which can take
+/// seconds at high targets, so a cancel the player makes during one takes
+/// effect when it finishes.
+#[uniffi::export]
+pub fn game_for_se_rating(
+    target_se: f32,
+    max_attempts: u32,
+    observer: Box<dyn GenerationObserver>,
+) -> Option<Arc<SudokuGame>> {
+    let (grid, se) = sudoku_session::generate_for_se(
+        target_se,
+        max_attempts,
+        |SearchProgress { attempts, best_se }| observer.on_progress(attempts, best_se),
+    )?;
+    let solver = Solver::new();
+    let solution = solver.solve(&grid)?;
+    let rated = solver.rate_difficulty(&grid);
+
+    Some(Arc::new(SudokuGame {
+        session: Mutex::new(start_session(grid, solution, rated)),
+        difficulty: Mutex::new(rated),
+        rated_difficulty: Mutex::new(rated),
+        seed: Mutex::new(None),
+        last_hint: Mutex::new(None),
+        se_rating_cache: Mutex::new(Some(se)),
+        remaining_cache: Mutex::new(None),
+        feedback_locale: Mutex::new(None),
+        last_feedback: Mutex::new(Vec::new()),
     }))
 }
 
@@ -996,17 +1468,15 @@ pub fn game_from_short_code(code: String) -> Option<Arc<SudokuGame>> {
     let rated = solver.rate_difficulty(&grid);
 
     Some(Arc::new(SudokuGame {
-        grid: Mutex::new(grid),
-        solution: Mutex::new(solution),
+        session: Mutex::new(start_session(grid, solution, puzzle_id.difficulty)),
         difficulty: Mutex::new(puzzle_id.difficulty),
         rated_difficulty: Mutex::new(rated),
-        undo_stack: Mutex::new(Vec::new()),
-        redo_stack: Mutex::new(Vec::new()),
-        hints_used: Mutex::new(0),
-        mistakes: Mutex::new(0),
         seed: Mutex::new(Some(puzzle_id.seed)),
         last_hint: Mutex::new(None),
         se_rating_cache: Mutex::new(None),
+        remaining_cache: Mutex::new(None),
+        feedback_locale: Mutex::new(None),
+        last_feedback: Mutex::new(Vec::new()),
     }))
 }
 
@@ -1039,17 +1509,15 @@ pub fn game_from_pregenerated(
     let diff = parse_difficulty(&difficulty);
 
     Some(Arc::new(SudokuGame {
-        grid: Mutex::new(grid),
-        solution: Mutex::new(solution),
+        session: Mutex::new(start_session(grid, solution, diff)),
         difficulty: Mutex::new(diff),
         rated_difficulty: Mutex::new(diff),
-        undo_stack: Mutex::new(Vec::new()),
-        redo_stack: Mutex::new(Vec::new()),
-        hints_used: Mutex::new(0),
-        mistakes: Mutex::new(0),
         seed: Mutex::new(None),
         last_hint: Mutex::new(None),
         se_rating_cache: Mutex::new(Some(se_rating)),
+        remaining_cache: Mutex::new(None),
+        feedback_locale: Mutex::new(None),
+        last_feedback: Mutex::new(Vec::new()),
     }))
 }
 
@@ -1084,18 +1552,276 @@ pub fn game_deserialize(json: String) -> Option<Arc<SudokuGame>> {
 
     let hints_used = data["hints_used"].as_u64().unwrap_or(0) as usize;
     let mistakes = data["mistakes"].as_u64().unwrap_or(0) as usize;
+    let settings: GameSettings =
+        serde_json::from_value(data["settings"].clone()).unwrap_or_default();
+    let elapsed_secs = data["elapsed_secs"].as_u64().unwrap_or(0);
+
+    let mut session = start_session(grid, solution, difficulty);
+    session.set_mode(serde_json::from_value(data["mode"].clone()).unwrap_or_default());
+    session.set_settings(settings);
+    session.set_bookmarks(serde_json::from_value(data["bookmarks"].clone()).unwrap_or_default());
+    session.restore_timeline(serde_json::from_value(data["timeline"].clone()).unwrap_or_default());
+    session.restore_counts(mistakes, hints_used);
+    session.restore_score(serde_json::from_value(data["score"].clone()).unwrap_or_default());
+    session.set_elapsed_ms(elapsed_secs * 1000, now_ms());
 
     Some(Arc::new(SudokuGame {
-        grid: Mutex::new(grid),
-        solution: Mutex::new(solution),
+        session: Mutex::new(session),
         difficulty: Mutex::new(difficulty),
         rated_difficulty: Mutex::new(rated_difficulty),
-        undo_stack: Mutex::new(Vec::new()),
-        redo_stack: Mutex::new(Vec::new()),
-        hints_used: Mutex::new(hints_used),
-        mistakes: Mutex::new(mistakes),
         seed: Mutex::new(None),
         last_hint: Mutex::new(None),
         se_rating_cache: Mutex::new(None),
+        remaining_cache: Mutex::new(None),
+        feedback_locale: Mutex::new(None),
+        last_feedback: Mutex::new(Vec::new()),
     }))
 }
+
+// MARK: - Technique Glossary
+
+/// What a solving technique is, for "What is an X-Wing?" in the hint sheet
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TechniqueInfo {
+    pub name: String,
+    /// Stable id for help links and translations
+    pub key: String,
+    /// Easiest level that calls for it
+    pub difficulty: String,
+    pub summary: String,
+    pub description: String,
+}
+
+/// Glossary entry for a technique name (as in `GameHint.technique`) or key
+#[uniffi::export]
+pub fn technique_info(name: String) -> Option<TechniqueInfo> {
+    technique_info_in(name, sudoku_glossary::i18n::DEFAULT_LOCALE.to_string())
+}
+
+/// Glossary entry with the name, summary and description in `locale`
+/// ("es", "de-CH"), falling back to English for anything not translated
+#[uniffi::export]
+pub fn technique_info_in(name: String, locale: String) -> Option<TechniqueInfo> {
+    sudoku_glossary::lookup(&name).map(|entry| TechniqueInfo {
+        name: entry.name_in(&locale),
+        key: entry.key.to_string(),
+        difficulty: format!("{:?}", entry.difficulty),
+        summary: entry.summary_in(&locale),
+        description: entry.description_in(&locale),
+    })
+}
+
+/// A hint's explanation worded in `locale`, falling back to English for
+/// anything not translated
+#[uniffi::export]
+pub fn format_hint_explanation(template: HintExplanation, locale: String) -> String {
+    sudoku_glossary::ExplanationTemplate::from(template).format_in(&locale)
+}
+
+/// Register translations for a locale, as `id = text` lines: ids are
+/// `technique-<key>`, `technique-<key>-summary`, `technique-<key>-description`
+/// and the `step-*`, `explain-*` and `unit-*` wording. Returns the numbers of lines that couldn't be
+/// read.
+#[uniffi::export]
+pub fn register_locale(tag: String, source: String) -> Vec<u32> {
+    sudoku_glossary::i18n::register_locale(&tag, &source)
+        .into_iter()
+        .map(|line| line as u32)
+        .collect()
+}
+
+// MARK: - Technique Profile
+
+/// A digit the player placed, as the app logged it
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SolvePlacement {
+    /// Game time in milliseconds
+    pub at_ms: u64,
+    pub row: u8,
+    pub col: u8,
+    pub value: u8,
+}
+
+/// How often one technique justified the player's placements
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TechniqueCount {
+    pub technique: String,
+    pub placements: u32,
+}
+
+/// Placements whose hardest technique was at one level
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TierTiming {
+    pub difficulty: GameDifficulty,
+    pub placements: u32,
+    /// Time since the previous placement, averaged
+    pub average_ms: u64,
+}
+
+/// Which techniques a finished game shows the player using
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct TechniqueProfile {
+    /// Most used first
+    pub techniques: Vec<TechniqueCount>,
+    /// Easiest level first
+    pub tiers: Vec<TierTiming>,
+    /// Wrong digits, and right ones no logical step led to
+    pub guesses: u32,
+    pub placements: u32,
+}
+
+/// Profile a game from its puzzle and the player's placements in order
+/// (leave out digits placed by hints). None if the puzzle can't be solved.
+#[uniffi::export]
+pub fn profile_solve(puzzle: String, placements: Vec<SolvePlacement>) -> Option<TechniqueProfile> {
+    let placements: Vec<sudoku_glossary::Placement> = placements
+        .iter()
+        .filter(|p| p.row < 9 && p.col < 9)
+        .map(|p| sudoku_glossary::Placement {
+            at_ms: p.at_ms,
+            cell: p.row as usize * 9 + p.col as usize,
+            value: p.value,
+        })
+        .collect();
+    let profile = sudoku_glossary::profile(&puzzle, &placements)?;
+    Some(TechniqueProfile {
+        techniques: profile
+            .techniques
+            .into_iter()
+            .map(|(technique, placements)| TechniqueCount {
+                technique,
+                placements,
+            })
+            .collect(),
+        tiers: profile
+            .tiers
+            .iter()
+            .map(|tier| TierTiming {
+                difficulty: tier.difficulty.into(),
+                placements: tier.moves,
+                average_ms: tier.average_ms(),
+            })
+            .collect(),
+        guesses: profile.guesses,
+        placements: profile.placements,
+    })
+}
+
+// MARK: - Daily Puzzle
+
+/// Today's day number (days since 1970-01-01 UTC)
+#[uniffi::export]
+pub fn daily_today() -> u64 {
+    sudoku_daily::today()
+}
+
+/// Short code of the daily puzzle for `day`, the same grid every other
+/// frontend serves that day; open it with `game_from_short_code`
+#[uniffi::export]
+pub fn daily_short_code(day: u64) -> String {
+    sudoku_daily::puzzle_id(day).to_short_code()
+}
+
+/// Day number of a "YYYY-MM-DD" date; None if it can't be read
+#[uniffi::export]
+pub fn daily_parse_date(date: String) -> Option<u64> {
+    sudoku_daily::parse_date(&date)
+}
+
+// MARK: - Daily Streak
+
+/// A day on the streak calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum StreakDay {
+    Missed,
+    Completed,
+    /// Missed, with a freeze spent to keep the streak
+    Frozen,
+}
+
+/// Daily-puzzle streaks with the same rules as the other frontends. Days
+/// are numbered from 1970-01-01 UTC; keep `to_json()` with the player's
+/// stats.
+#[derive(uniffi::Object)]
+pub struct StreakTracker {
+    tracker: Mutex<sudoku_streak::StreakTracker>,
+}
+
+#[uniffi::export]
+impl StreakTracker {
+    /// A tracker from saved JSON; empty when there is none or it can't be
+    /// read
+    #[uniffi::constructor]
+    pub fn new(saved: Option<String>) -> Arc<Self> {
+        let tracker = saved
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Arc::new(Self {
+            tracker: Mutex::new(tracker),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&*self.tracker.lock().unwrap()).unwrap_or_default()
+    }
+
+    /// Record the puzzle of `day` as solved, spending freezes on the days
+    /// missed if enough are held. False if the day was already solved.
+    pub fn complete(&self, day: u64, time_secs: u64) -> bool {
+        self.tracker.lock().unwrap().complete(day, time_secs)
+    }
+
+    pub fn current_streak(&self, today: u64) -> u32 {
+        self.tracker.lock().unwrap().current(today)
+    }
+
+    pub fn best_streak(&self) -> u32 {
+        self.tracker.lock().unwrap().best()
+    }
+
+    pub fn freezes(&self) -> u32 {
+        self.tracker.lock().unwrap().freezes()
+    }
+
+    /// `count` days from `first`, for a calendar
+    pub fn calendar(&self, first: u64, count: u32) -> Vec<StreakDay> {
+        let tracker = self.tracker.lock().unwrap();
+        (first..first + u64::from(count))
+            .map(|day| match tracker.day(day) {
+                Some(sudoku_streak::Day::Completed { .. }) => StreakDay::Completed,
+                Some(sudoku_streak::Day::Frozen) => StreakDay::Frozen,
+                None => StreakDay::Missed,
+            })
+            .collect()
+    }
+}
+
+// MARK: - Photo Import
+
+/// A puzzle read from a photo of a printed grid
+#[cfg(feature = "ocr")]
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ScannedPuzzle {
+    /// 81 characters, '.' for empty cells
+    pub puzzle: String,
+    /// Per cell, 0-1: how sure the reading is. Show low values for checking
+    pub confidence: Vec<f32>,
+}
+
+/// Read a puzzle from 8-bit grayscale pixels (row-major, 0 = black) of a
+/// photo cropped roughly to the grid. Returns None if the pixel count
+/// doesn't match or the image is too small.
+#[cfg(feature = "ocr")]
+#[uniffi::export]
+pub fn scan_puzzle_image(width: u32, height: u32, pixels: Vec<u8>) -> Option<ScannedPuzzle> {
+    let scan = sudoku_ocr::scan(&sudoku_ocr::GrayImage {
+        width: width as usize,
+        height: height as usize,
+        pixels: &pixels,
+    })
+    .ok()?;
+    Some(ScannedPuzzle {
+        puzzle: scan.puzzle(),
+        confidence: scan.cells.iter().map(|c| c.confidence).collect(),
+    })
+}
@@ -0,0 +1,162 @@
+//! Hint text at three lengths: terse notation for experienced players, the
+//! solver's own sentence, and a step-by-step version for beginners.
+
+use crate::i18n::{message, DEFAULT_LOCALE};
+use crate::lookup;
+use sudoku_core::{Hint, HintType, Position};
+
+/// How much a hint explains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// One line of notation, e.g. "X-Wing r25c37 ⇒ -5 r8c3"
+    Brief,
+    /// The solver's explanation
+    #[default]
+    Standard,
+    /// What the technique is, which cells to look at, and what follows
+    StepByStep,
+}
+
+impl Verbosity {
+    /// Parse a name from a config file or binding ("brief", "standard",
+    /// "step-by-step")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "brief" => Some(Self::Brief),
+            "standard" => Some(Self::Standard),
+            "step-by-step" | "detailed" => Some(Self::StepByStep),
+            _ => None,
+        }
+    }
+}
+
+/// Cells in row/column notation, rows sharing the same columns merged:
+/// r2c3, r5c3, r2c7 and r5c7 become "r25c37"
+pub fn cells(positions: &[Position]) -> String {
+    // Columns per row as a bit set
+    let mut rows = [0u16; 9];
+    for pos in positions {
+        rows[pos.row] |= 1 << pos.col;
+    }
+    let mut groups: Vec<(u16, u16)> = Vec::new();
+    for (row, &cols) in rows.iter().enumerate().filter(|(_, c)| **c != 0) {
+        match groups.iter_mut().find(|(_, c)| *c == cols) {
+            Some((group_rows, _)) => *group_rows |= 1 << row,
+            None => groups.push((1 << row, cols)),
+        }
+    }
+    let digits = |bits: u16| -> String {
+        (0..9)
+            .filter(|i| bits & (1 << i) != 0)
+            .map(|i| char::from(b'1' + i as u8))
+            .collect()
+    };
+    groups
+        .iter()
+        .map(|&(rows, cols)| format!("r{}c{}", digits(rows), digits(cols)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn values(values: &[u8]) -> String {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+fn step(locale: &str, id: &str, args: &[(&str, &str)]) -> String {
+    message(locale, id, args).unwrap_or_default()
+}
+
+/// The hint's text at `verbosity`, in English
+pub fn explain(hint: &Hint, verbosity: Verbosity) -> String {
+    explain_in(hint, verbosity, DEFAULT_LOCALE)
+}
+
+/// The hint's text at `verbosity`, with the technique and step wording in
+/// `locale`. The solver's own explanation stays in English.
+pub fn explain_in(hint: &Hint, verbosity: Verbosity, locale: &str) -> String {
+    let entry = lookup(&hint.technique.to_string());
+    let technique = entry.map_or_else(|| hint.technique.to_string(), |e| e.name_in(locale));
+    match verbosity {
+        Verbosity::Brief => {
+            let result = match &hint.hint_type {
+                HintType::SetValue { pos, value } => format!("{}={}", cells(&[*pos]), value),
+                HintType::EliminateCandidates { pos, values: v } => {
+                    format!("-{} {}", values(v), cells(&[*pos]))
+                }
+            };
+            if hint.involved_cells.is_empty() {
+                format!("{} ⇒ {}", technique, result)
+            } else {
+                format!("{} {} ⇒ {}", technique, cells(&hint.involved_cells), result)
+            }
+        }
+        Verbosity::Standard => hint.explanation.clone(),
+        Verbosity::StepByStep => {
+            let mut steps = Vec::new();
+            if let Some(entry) = entry {
+                steps.push(format!("{}: {}", technique, entry.summary_in(locale)));
+            }
+            if !hint.involved_cells.is_empty() {
+                let names: Vec<String> = hint.involved_cells.iter().map(|p| cells(&[*p])).collect();
+                steps.push(step(
+                    locale,
+                    "step-look",
+                    &[("cells", names.join(", ").as_str())],
+                ));
+            }
+            steps.push(hint.explanation.clone());
+            steps.push(match &hint.hint_type {
+                HintType::SetValue { pos, value } => {
+                    let (cell, value) = (cells(&[*pos]), value.to_string());
+                    step(
+                        locale,
+                        "step-place",
+                        &[("cell", cell.as_str()), ("value", value.as_str())],
+                    )
+                }
+                HintType::EliminateCandidates { pos, values: v } => {
+                    let digits: Vec<String> = v.iter().map(|d| d.to_string()).collect();
+                    let and = format!(" {} ", step(locale, "step-and", &[]));
+                    let (digits, cell) = (digits.join(&and), cells(&[*pos]));
+                    step(
+                        locale,
+                        "step-eliminate",
+                        &[("digits", digits.as_str()), ("cell", cell.as_str())],
+                    )
+                }
+            });
+            steps
+                .iter()
+                .enumerate()
+                .map(|(i, step)| format!("{}. {}", i + 1, step))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_core::Solver;
+    use sudoku_test_utils::{assert_hint_sound, fixture, solution_digits, Tier};
+
+    #[test]
+    fn test_explain() {
+        let corners = [(1, 2), (4, 2), (1, 6), (4, 6)].map(|(r, c)| Position::new(r, c));
+        assert_eq!(cells(&corners), "r25c37");
+        assert_eq!(cells(&[Position::new(7, 2)]), "r8c3");
+
+        let grid = fixture(Tier::Singles);
+        let hint = Solver::new().get_hint(&grid).unwrap();
+        assert_hint_sound(&solution_digits(&grid), &hint);
+        assert!(explain(&hint, Verbosity::Brief).starts_with(&hint.technique.to_string()));
+        assert_eq!(explain(&hint, Verbosity::Standard), hint.explanation);
+        let steps = explain(&hint, Verbosity::StepByStep);
+        assert!(steps.starts_with("1. ") && steps.contains(&hint.explanation));
+        assert_eq!(
+            Verbosity::from_name("Step-by-Step"),
+            Some(Verbosity::StepByStep)
+        );
+    }
+}
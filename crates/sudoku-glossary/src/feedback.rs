@@ -0,0 +1,139 @@
+//! Cause and effect for learners: the patterns a placement just made
+//! available, e.g. "New after this move: Naked Pair in box 7".
+
+use crate::i18n::message;
+use crate::{cells, lookup};
+use sudoku_core::{Grid, Hint, HintType, Position, Solver};
+
+/// A technique that applies after a move and didn't before
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feedback {
+    /// As the solver names it
+    pub technique: String,
+    /// Cells of the pattern
+    pub cells: Vec<Position>,
+    /// One sentence for the player
+    pub message: String,
+}
+
+/// Singles follow from almost every placement, so they aren't reported
+fn is_single(hint: &Hint) -> bool {
+    lookup(&hint.technique.to_string())
+        .is_some_and(|e| e.key == "naked-single" || e.key == "hidden-single")
+}
+
+/// What identifies a finding across two boards
+fn signature(hint: &Hint) -> (String, Vec<(usize, usize)>) {
+    let mut cells: Vec<_> = hint.involved_cells.iter().map(|p| (p.row, p.col)).collect();
+    cells.sort_unstable();
+    (hint.technique.to_string(), cells)
+}
+
+/// The smallest unit holding every cell: "box 7", "row 2", "column 5", or
+/// the cells themselves
+fn unit(positions: &[Position], locale: &str) -> String {
+    let Some(first) = positions.first() else {
+        return String::new();
+    };
+    let same = |f: fn(&Position) -> usize| positions.iter().all(|p| f(p) == f(first));
+    let (id, n) = if same(|p| p.row / 3 * 3 + p.col / 3) {
+        ("unit-box", first.row / 3 * 3 + first.col / 3)
+    } else if same(|p| p.row) {
+        ("unit-row", first.row)
+    } else if same(|p| p.col) {
+        ("unit-column", first.col)
+    } else {
+        return cells(positions);
+    };
+    message(locale, id, &[("n", &(n + 1).to_string())]).unwrap_or_default()
+}
+
+/// Findings near `pos` that placing `value` there creates on `before`,
+/// worded in `locale`. Candidates are recomputed on both boards, so the
+/// player's notes don't matter.
+pub fn after_placement(before: &Grid, pos: Position, value: u8, locale: &str) -> Vec<Feedback> {
+    let solver = Solver::new();
+    let mut grid = before.deep_clone();
+    grid.recalculate_candidates();
+    let existing: Vec<_> = solver.find_all(&grid).iter().map(signature).collect();
+
+    grid.set_cell_unchecked(pos, Some(value));
+    grid.recalculate_candidates();
+    let near = |p: &Position| {
+        p.row == pos.row || p.col == pos.col || (p.row / 3, p.col / 3) == (pos.row / 3, pos.col / 3)
+    };
+    let mut found: Vec<Feedback> = Vec::new();
+    for hint in solver.find_all(&grid) {
+        if is_single(&hint)
+            || !hint.involved_cells.iter().any(near)
+            || existing.contains(&signature(&hint))
+        {
+            continue;
+        }
+        let technique = hint.technique.to_string();
+        let name = lookup(&technique).map_or_else(|| technique.clone(), |e| e.name_in(locale));
+        let text = message(
+            locale,
+            "feedback-created",
+            &[
+                ("technique", name.as_str()),
+                ("unit", unit(&hint.involved_cells, locale).as_str()),
+            ],
+        )
+        .unwrap_or_default();
+        if found.iter().all(|f| f.message != text) {
+            found.push(Feedback {
+                technique,
+                cells: hint.involved_cells.clone(),
+                message: text,
+            });
+        }
+    }
+    found
+}
+
+/// The simplest technique the solver finds on `grid` that places `value`
+/// at `pos`, to tag a move with what justified it. None for a guess.
+pub fn justifying_technique(grid: &Grid, pos: Position, value: u8) -> Option<String> {
+    let placement = |hint: &Hint| match hint.hint_type {
+        HintType::SetValue { pos, value } => Some((pos, value)),
+        HintType::EliminateCandidates { .. } => None,
+    };
+    let mut grid = grid.deep_clone();
+    grid.recalculate_candidates();
+    Solver::new()
+        .find_all(&grid)
+        .into_iter()
+        .filter(|hint| placement(hint) == Some((pos, value)))
+        .min_by(|a, b| a.technique.se_rating().total_cmp(&b.technique.se_rating()))
+        .map(|hint| hint.technique.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_unit() {
+        let in_box = [Position::new(6, 0), Position::new(7, 2)];
+        assert_eq!(unit(&in_box, "en"), "box 7");
+        let in_row = [Position::new(1, 0), Position::new(1, 8)];
+        assert_eq!(unit(&in_row, "en"), "row 2");
+        let scattered = [Position::new(0, 0), Position::new(8, 8)];
+        assert_eq!(unit(&scattered, "en"), "r1c1 r9c9");
+
+        let grid = Grid::from_string(Tier::Singles.puzzle()).unwrap();
+        for feedback in after_placement(&grid, Position::new(0, 2), 4, "en") {
+            assert!(feedback.message.starts_with("New after this move: "));
+        }
+
+        let mut candidates = grid.deep_clone();
+        candidates.recalculate_candidates();
+        let hint = Solver::new().get_next_placement(&candidates).unwrap();
+        if let HintType::SetValue { pos, value } = hint.hint_type {
+            assert!(justifying_technique(&grid, pos, value).is_some());
+            assert!(justifying_technique(&grid, pos, value % 9 + 1).is_none());
+        }
+    }
+}
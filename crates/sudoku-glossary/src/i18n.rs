@@ -0,0 +1,166 @@
+//! Translations of the glossary and hint wording, as Fluent-style catalogs:
+//!
+//! ```ftl
+//! # Spanish
+//! technique-x-wing = Ala X
+//! technique-x-wing-summary = Un dígito en dos filas ...
+//! step-look = Mira { $cells }.
+//! ```
+//!
+//! Only the subset the glossary needs is read: one `id = text` message per
+//! line, `#` comments and `{ $name }` placeables. English is built in from
+//! `ENTRIES`; other locales are registered at run time, and any message a
+//! locale lacks falls back to English.
+
+use crate::ENTRIES;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Locale every other one falls back to
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Wording of the step-by-step explanation, hint templates and move
+/// feedback
+const STEPS: &[(&str, &str)] = &[
+    ("step-look", "Look at { $cells }."),
+    ("step-place", "So { $cell } is { $value }."),
+    (
+        "step-eliminate",
+        "So { $digits } can be removed from { $cell }.",
+    ),
+    ("step-and", "and"),
+    (
+        "explain-place",
+        "{ $technique } in { $where }: { $cell } is { $digits }.",
+    ),
+    (
+        "explain-eliminate",
+        "{ $technique } in { $where }: { $digits } can be removed from { $cell }.",
+    ),
+    (
+        "feedback-created",
+        "New after this move: { $technique } in { $unit }.",
+    ),
+    ("unit-box", "box { $n }"),
+    ("unit-row", "row { $n }"),
+    ("unit-column", "column { $n }"),
+];
+
+type Messages = HashMap<String, String>;
+
+fn english() -> Messages {
+    let mut messages: Messages = STEPS
+        .iter()
+        .map(|(id, text)| (id.to_string(), text.to_string()))
+        .collect();
+    for entry in ENTRIES {
+        let id = format!("technique-{}", entry.key);
+        messages.insert(format!("{}-summary", id), entry.summary.to_string());
+        messages.insert(format!("{}-description", id), entry.description.to_string());
+        messages.insert(id, entry.name.to_string());
+    }
+    messages
+}
+
+fn catalog() -> &'static Mutex<HashMap<String, Messages>> {
+    static CATALOG: OnceLock<Mutex<HashMap<String, Messages>>> = OnceLock::new();
+    CATALOG.get_or_init(|| Mutex::new(HashMap::from([(DEFAULT_LOCALE.to_string(), english())])))
+}
+
+/// "es-MX" and "es_mx" are the same locale
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+fn is_id(id: &str) -> bool {
+    id.starts_with(|c: char| c.is_ascii_alphabetic())
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Read catalog text into messages, with the 1-based numbers of lines that
+/// weren't a message, comment or blank
+fn parse(source: &str) -> (Messages, Vec<usize>) {
+    let mut messages = Messages::new();
+    let mut skipped = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((id, text)) if is_id(id.trim()) && !text.trim().is_empty() => {
+                messages.insert(id.trim().to_string(), text.trim().to_string());
+            }
+            _ => skipped.push(i + 1),
+        }
+    }
+    (messages, skipped)
+}
+
+/// Add or extend a locale from catalog text. Returns the numbers of lines
+/// that couldn't be read; the rest are registered.
+pub fn register_locale(tag: &str, source: &str) -> Vec<usize> {
+    let (messages, skipped) = parse(source);
+    catalog()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(normalize_tag(tag))
+        .or_default()
+        .extend(messages);
+    skipped
+}
+
+/// Locales with a catalog, sorted
+pub fn locales() -> Vec<String> {
+    let catalog = catalog().lock().unwrap_or_else(|e| e.into_inner());
+    let mut tags: Vec<String> = catalog.keys().cloned().collect();
+    tags.sort();
+    tags
+}
+
+/// A message in `locale`, with `{ $name }` placeables filled from `args`.
+/// Tries the locale, then its language ("pt" for "pt-BR"), then English;
+/// None if even English has no such message.
+pub fn message(locale: &str, id: &str, args: &[(&str, &str)]) -> Option<String> {
+    let tag = normalize_tag(locale);
+    let language = tag.split('-').next().unwrap_or_default().to_string();
+    let catalog = catalog().lock().unwrap_or_else(|e| e.into_inner());
+    let text = [tag.as_str(), language.as_str(), DEFAULT_LOCALE]
+        .iter()
+        .find_map(|tag| catalog.get(*tag)?.get(id))?;
+    let mut text = text.clone();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${} }}", name), value);
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locales() {
+        let skipped = register_locale(
+            "es_ES",
+            "# Spanish\ntechnique-x-wing = Ala X\n\nnot a message\nstep-look = Mira { $cells }.\n",
+        );
+        assert_eq!(skipped, vec![4]);
+        assert!(locales().contains(&"es-es".to_string()));
+
+        assert_eq!(message("es-ES", "technique-x-wing", &[]).unwrap(), "Ala X");
+        assert_eq!(
+            message("es-es", "step-look", &[("cells", "r1c1")]).unwrap(),
+            "Mira r1c1."
+        );
+        // Missing in Spanish, so English
+        assert_eq!(
+            message("es-ES", "technique-swordfish", &[]).unwrap(),
+            "Swordfish"
+        );
+        assert_eq!(message("de", "technique-x-wing", &[]).unwrap(), "X-Wing");
+        assert!(message("en", "no-such-message", &[]).is_none());
+    }
+}
@@ -0,0 +1,385 @@
+//! What each solving technique means, for hint dialogs ("What is an
+//! X-Wing?"). One copy of the prose for every frontend. Hints can also be
+//! taken apart into an [`ExplanationTemplate`] for wording in any locale.
+
+mod explain;
+mod feedback;
+pub mod i18n;
+mod profile;
+mod template;
+
+pub use explain::{cells, explain, explain_in, Verbosity};
+pub use feedback::{after_placement, justifying_technique, Feedback};
+pub use profile::{profile, Placement, Profile, TierTime};
+pub use template::{Action, ExplanationTemplate, Unit};
+
+use sudoku_core::{Difficulty, Grid, Hint, PuzzleId, Solver};
+use sudoku_session::apply_hint;
+
+/// A technique explained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// As the solver names it
+    pub name: &'static str,
+    /// Stable id for "learn more" links and translations
+    pub key: &'static str,
+    /// Easiest level whose puzzles call for it
+    pub difficulty: Difficulty,
+    /// One line
+    pub summary: &'static str,
+    /// A short paragraph on how it works
+    pub description: &'static str,
+}
+
+impl Entry {
+    /// The name in `locale`, or English
+    pub fn name_in(&self, locale: &str) -> String {
+        self.translated(locale, "", self.name)
+    }
+
+    pub fn summary_in(&self, locale: &str) -> String {
+        self.translated(locale, "-summary", self.summary)
+    }
+
+    pub fn description_in(&self, locale: &str) -> String {
+        self.translated(locale, "-description", self.description)
+    }
+
+    fn translated(&self, locale: &str, suffix: &str, english: &str) -> String {
+        i18n::message(locale, &format!("technique-{}{}", self.key, suffix), &[])
+            .unwrap_or_else(|| english.to_string())
+    }
+}
+
+macro_rules! entry {
+    ($name:literal, $key:literal, $difficulty:ident, $summary:literal, $description:literal) => {
+        Entry {
+            name: $name,
+            key: $key,
+            difficulty: Difficulty::$difficulty,
+            summary: $summary,
+            description: $description,
+        }
+    };
+}
+
+/// Every technique with an entry, roughly easiest first
+pub const ENTRIES: &[Entry] = &[
+    entry!(
+        "Naked Single",
+        "naked-single",
+        Beginner,
+        "A cell with only one candidate left.",
+        "Every other digit already appears in the cell's row, column or box, so the one that remains must go there."
+    ),
+    entry!(
+        "Hidden Single",
+        "hidden-single",
+        Beginner,
+        "The only cell in a row, column or box that can hold a digit.",
+        "Pick a unit and a digit. If just one cell in the unit can still take the digit, it goes there, even if that cell has other candidates."
+    ),
+    entry!(
+        "Naked Pair",
+        "naked-pair",
+        Medium,
+        "Two cells in a unit with the same two candidates.",
+        "Those two digits must fill those two cells, so neither can go anywhere else in the unit. Remove them from the other cells."
+    ),
+    entry!(
+        "Pointing Pair",
+        "pointing-pair",
+        Medium,
+        "Inside a box, a digit confined to one row or column.",
+        "The box's copy of the digit must sit on that line, so the rest of the line outside the box can't hold it. Three cells work the same way."
+    ),
+    entry!(
+        "Hidden Pair",
+        "hidden-pair",
+        Intermediate,
+        "Two digits that fit only in the same two cells of a unit.",
+        "Those cells must take the two digits, so any other candidates in them can be removed."
+    ),
+    entry!(
+        "Box/Line Reduction",
+        "box-line-reduction",
+        Intermediate,
+        "In a row or column, a digit confined to one box.",
+        "The line's copy of the digit must be in that box, so the box's other cells can't hold it."
+    ),
+    entry!(
+        "Naked Triple",
+        "naked-triple",
+        Hard,
+        "Three cells in a unit whose candidates come from the same three digits.",
+        "Each cell needn't hold all three, but together they use them up. Remove the three digits from the rest of the unit."
+    ),
+    entry!(
+        "Hidden Triple",
+        "hidden-triple",
+        Hard,
+        "Three digits that fit only in the same three cells of a unit.",
+        "Those cells must take the three digits, so their other candidates can be removed."
+    ),
+    entry!(
+        "X-Wing",
+        "x-wing",
+        Hard,
+        "A digit with two spots in each of two rows, lined up in the same two columns.",
+        "However the rows resolve, the digit lands once in each of those columns, so it can be removed from the rest of both columns. Swap rows and columns for the other orientation."
+    ),
+    entry!(
+        "Naked Quad",
+        "naked-quad",
+        Expert,
+        "Four cells in a unit whose candidates come from the same four digits.",
+        "Together they use up those digits, which can be removed from the rest of the unit."
+    ),
+    entry!(
+        "Hidden Quad",
+        "hidden-quad",
+        Expert,
+        "Four digits that fit only in the same four cells of a unit.",
+        "Those cells must take the four digits, so their other candidates can be removed."
+    ),
+    entry!(
+        "Swordfish",
+        "swordfish",
+        Expert,
+        "An X-Wing over three rows and three columns.",
+        "If a digit's spots in three rows all fall within the same three columns, those columns are covered by the rows, and the digit can be removed from the rest of them."
+    ),
+    entry!(
+        "XY-Wing",
+        "xy-wing",
+        Expert,
+        "A pivot cell XY seeing two cells XZ and YZ.",
+        "Whichever digit the pivot takes, one of the two pincers becomes Z. Any cell that sees both pincers can't be Z."
+    ),
+    entry!(
+        "XYZ-Wing",
+        "xyz-wing",
+        Expert,
+        "An XY-Wing whose pivot also holds Z.",
+        "One of the three cells must be Z, so Z can be removed from cells that see all three."
+    ),
+    entry!(
+        "W-Wing",
+        "w-wing",
+        Expert,
+        "Two cells with the same two candidates, joined by a strong link on one of them.",
+        "The link forces one of the two cells to take the other digit, so cells seeing both can't hold it."
+    ),
+    entry!(
+        "Skyscraper",
+        "skyscraper",
+        Expert,
+        "Two lines where a digit has two spots each, sharing one end.",
+        "One of the two free ends must hold the digit, so any cell that sees both ends loses it."
+    ),
+    entry!(
+        "Two-String Kite",
+        "two-string-kite",
+        Expert,
+        "A row pair and a column pair of one digit, joined inside a box.",
+        "One of the two far ends must hold the digit, so a cell seeing both of them can't."
+    ),
+    entry!(
+        "Empty Rectangle",
+        "empty-rectangle",
+        Expert,
+        "A box where a digit's spots form a cross, combined with a pair on a line.",
+        "The box forces the digit onto its row or its column; together with the pair this rules out one cell."
+    ),
+    entry!(
+        "Simple Coloring",
+        "simple-coloring",
+        Expert,
+        "Two alternating colors along a digit's strong links.",
+        "One color is true and the other false. A cell seeing both colors loses the digit, and a color that sees itself is false."
+    ),
+    entry!(
+        "Unique Rectangle",
+        "unique-rectangle",
+        Expert,
+        "Avoids four cells that would make a second solution.",
+        "Four cells in two rows, two columns and two boxes can't end with the same two digits, or they could be swapped. If three of them are limited to the pair, the fourth can't take both."
+    ),
+    entry!(
+        "BUG+1",
+        "bug-plus-one",
+        Expert,
+        "Every unsolved cell but one has two candidates.",
+        "A grid where every cell had two candidates would have more than one solution. The one cell with three must take the digit that appears three times in its units."
+    ),
+    entry!(
+        "Jellyfish",
+        "jellyfish",
+        Master,
+        "An X-Wing over four rows and four columns.",
+        "A digit's spots in four rows fall within four columns, so the rest of those columns lose it."
+    ),
+    entry!(
+        "Finned X-Wing",
+        "finned-x-wing",
+        Master,
+        "An X-Wing with extra spots, the fins, in one box.",
+        "Either the X-Wing holds or a fin is true. Cells that lose the digit both ways, in the fin's box, can be cleared."
+    ),
+    entry!(
+        "X-Chain",
+        "x-chain",
+        Master,
+        "A chain of one digit alternating strong and weak links.",
+        "If the chain starts and ends with strong links, one end is true, so cells seeing both ends lose the digit."
+    ),
+    entry!(
+        "XY-Chain",
+        "xy-chain",
+        Master,
+        "A chain of two-candidate cells, each sharing a digit with the next.",
+        "If the chain starts and ends with the same digit, one end is that digit, so cells seeing both ends lose it."
+    ),
+    entry!(
+        "AIC",
+        "aic",
+        Master,
+        "An alternating inference chain over any candidates.",
+        "Strong links (one must be true) alternate with weak links (both can't be). The two ends can't both be false, which rules out candidates that see both."
+    ),
+    entry!(
+        "ALS-XZ",
+        "als-xz",
+        Extreme,
+        "Two almost locked sets sharing a restricted digit.",
+        "An almost locked set is N cells with N+1 candidates. If two share a digit X that can only be in one of them, a common digit Z must be in one set, so cells seeing every Z in both lose it."
+    ),
+    entry!(
+        "Sue de Coq",
+        "sue-de-coq",
+        Extreme,
+        "Cells where a box and a line cross, split between the two.",
+        "The crossing cells' candidates are divided into a set locked with the rest of the box and a set locked with the rest of the line, clearing both."
+    ),
+    entry!(
+        "Death Blossom",
+        "death-blossom",
+        Extreme,
+        "A stem cell whose every candidate locks a different almost locked set.",
+        "Whatever the stem is, one of the sets is locked; a digit common to all of them can be removed from cells seeing every copy."
+    ),
+    entry!(
+        "Forcing Chain",
+        "forcing-chain",
+        Extreme,
+        "Every choice for a cell leads to the same conclusion.",
+        "Follow each candidate of a cell, or each cell for a digit in a unit. If all of them place or remove the same candidate, that result holds."
+    ),
+    entry!(
+        "Backtracking",
+        "backtracking",
+        Extreme,
+        "Trial and error.",
+        "No logical technique applies, so the solver guesses and undoes guesses that lead to a contradiction."
+    ),
+];
+
+/// Lowercase letters and digits only, so "Box/Line Reduction" and
+/// "box line reduction" match
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// The entry for a technique name (as in `hint.technique.to_string()`) or a
+/// key
+pub fn lookup(name: &str) -> Option<&'static Entry> {
+    let name = normalize(name);
+    ENTRIES
+        .iter()
+        .find(|e| normalize(e.name) == name || normalize(e.key) == name)
+}
+
+/// Seeds tried when looking for an example
+const EXAMPLE_SEEDS: u64 = 8;
+/// Seeds tried when collecting tutorial puzzles
+const TUTORIAL_SEEDS: u64 = 64;
+/// Solver steps walked through each seed
+const MAX_STEPS: usize = 150;
+
+/// Walk the solver's steps through `grid` until the technique comes up
+fn first_use(mut grid: Grid, wanted: &str) -> Option<(Grid, Hint)> {
+    let solver = Solver::new();
+    grid.recalculate_candidates();
+    for _ in 0..MAX_STEPS {
+        if grid.is_complete() {
+            break;
+        }
+        let hint = solver.get_hint(&grid)?;
+        if normalize(&hint.technique.to_string()) == wanted {
+            return Some((grid, hint));
+        }
+        if !apply_hint(&mut grid, &hint) {
+            break;
+        }
+    }
+    None
+}
+
+fn seeded(entry: &Entry, seed: u64) -> Grid {
+    PuzzleId {
+        difficulty: entry.difficulty,
+        seed,
+    }
+    .generate()
+}
+
+/// A position where the technique is the next step, and the hint there.
+/// The search uses fixed seeds, so it finds the same example every time.
+pub fn example(entry: &Entry) -> Option<(Grid, Hint)> {
+    let wanted = normalize(entry.name);
+    (1..=EXAMPLE_SEEDS).find_map(|seed| first_use(seeded(entry, seed), &wanted))
+}
+
+/// Up to `count` puzzles at the entry's level whose solve path uses the
+/// technique, as 81-character strings with "." for empty cells. Like
+/// `example`, the same puzzles come back every time.
+pub fn tutorials(entry: &Entry, count: usize) -> Vec<String> {
+    let wanted = normalize(entry.name);
+    (1..=TUTORIAL_SEEDS)
+        .map(|seed| seeded(entry, seed))
+        .filter(|grid| first_use(grid.deep_clone(), &wanted).is_some())
+        .map(|grid| grid.to_string_compact().replace('0', "."))
+        .take(count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{assert_hint_sound, solution_digits};
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(
+            lookup("Box/Line Reduction").unwrap().key,
+            "box-line-reduction"
+        );
+        assert_eq!(lookup("x-wing").unwrap().name, "X-Wing");
+        assert!(lookup("Telepathy").is_none());
+        for entry in ENTRIES {
+            assert_eq!(lookup(entry.key), Some(entry));
+        }
+
+        let (grid, hint) = example(lookup("Naked Single").unwrap()).unwrap();
+        assert_hint_sound(&solution_digits(&grid), &hint);
+        assert_eq!(hint.technique.to_string(), "Naked Single");
+
+        let x_wing = lookup("X-Wing").unwrap();
+        let puzzles = tutorials(x_wing, 2);
+        assert!(!puzzles.is_empty() && puzzles.len() <= 2);
+        assert_eq!(puzzles, tutorials(x_wing, 2));
+    }
+}
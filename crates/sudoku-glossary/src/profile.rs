@@ -0,0 +1,181 @@
+//! What a finished game says about the player: which techniques their
+//! placements needed, how long each level of technique took them, and how
+//! often they guessed.
+
+use crate::lookup;
+use sudoku_core::{Difficulty, Grid, HintType, Position, Solver};
+
+/// Solver steps tried to justify one placement
+const MAX_STEPS: usize = 100;
+
+/// A digit the player placed, from a move log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    /// Game time in milliseconds
+    pub at_ms: u64,
+    /// 0-80, row-major
+    pub cell: usize,
+    pub value: u8,
+}
+
+/// Placements whose hardest technique was at one level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TierTime {
+    pub difficulty: Difficulty,
+    pub moves: u32,
+    /// Time since the previous placement, summed
+    pub total_ms: u64,
+}
+
+impl TierTime {
+    pub fn average_ms(&self) -> u64 {
+        self.total_ms / u64::from(self.moves.max(1))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    /// Placements per technique, most first
+    pub techniques: Vec<(String, u32)>,
+    /// Easiest level first
+    pub tiers: Vec<TierTime>,
+    /// Wrong digits, and right ones no logical step led to
+    pub guesses: u32,
+    pub placements: u32,
+}
+
+impl Profile {
+    /// Share of placements that were guesses, 0-1
+    pub fn guess_rate(&self) -> f32 {
+        self.guesses as f32 / self.placements.max(1) as f32
+    }
+}
+
+/// The hardest technique the solver needs before it can place `value` at
+/// `pos`, or None if it gets there only by trial and error
+fn justify(grid: &Grid, pos: Position, value: u8) -> Option<String> {
+    let solver = Solver::new();
+    let mut grid = grid.deep_clone();
+    grid.recalculate_candidates();
+    let mut hardest: Option<(f32, String)> = None;
+    for _ in 0..MAX_STEPS {
+        let hint = solver.get_hint(&grid)?;
+        let rating = hint.technique.se_rating();
+        if !matches!(&hardest, Some((r, _)) if *r >= rating) {
+            hardest = Some((rating, hint.technique.to_string()));
+        }
+        match &hint.hint_type {
+            HintType::SetValue { pos: p, value: v } => {
+                if (*p, *v) == (pos, value) {
+                    break;
+                }
+                grid.set_cell_unchecked(*p, Some(*v));
+                grid.update_candidates_after_move(*p, *v);
+            }
+            HintType::EliminateCandidates { pos: p, values } => {
+                for &v in values {
+                    grid.cell_mut(*p).remove_candidate(v);
+                }
+            }
+        }
+    }
+    let (_, name) = hardest?;
+    (lookup(&name).map(|e| e.key) != Some("backtracking")).then_some(name)
+}
+
+/// Profile one game from its puzzle and the player's placements in order
+/// (leave out digits placed by hints). None if the puzzle can't be read or
+/// solved.
+pub fn profile(puzzle: &str, placements: &[Placement]) -> Option<Profile> {
+    let mut grid = Grid::from_string(puzzle)?;
+    let solution = Solver::new().solve(&grid)?;
+    let mut profile = Profile::default();
+    let mut last_ms = 0;
+    for placement in placements.iter().filter(|p| p.cell < 81) {
+        let pos = Position::new(placement.cell / 9, placement.cell % 9);
+        let elapsed = placement.at_ms.saturating_sub(last_ms);
+        last_ms = placement.at_ms;
+        if grid.cell(pos).value() == solution.cell(pos).value() {
+            continue;
+        }
+        profile.placements += 1;
+        let technique = (solution.cell(pos).value() == Some(placement.value))
+            .then(|| justify(&grid, pos, placement.value))
+            .flatten();
+        let Some(technique) = technique else {
+            profile.guesses += 1;
+            continue;
+        };
+        grid.set_cell_unchecked(pos, Some(placement.value));
+
+        match profile.techniques.iter_mut().find(|(t, _)| *t == technique) {
+            Some((_, count)) => *count += 1,
+            None => profile.techniques.push((technique.clone(), 1)),
+        }
+        let difficulty = lookup(&technique).map_or(Difficulty::Extreme, |e| e.difficulty);
+        match profile
+            .tiers
+            .iter_mut()
+            .find(|t| t.difficulty == difficulty)
+        {
+            Some(tier) => {
+                tier.moves += 1;
+                tier.total_ms += elapsed;
+            }
+            None => profile.tiers.push(TierTime {
+                difficulty,
+                moves: 1,
+                total_ms: elapsed,
+            }),
+        }
+    }
+    profile
+        .techniques
+        .sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    profile.tiers.sort_by_key(|t| {
+        Difficulty::all_levels()
+            .iter()
+            .position(|&d| d == t.difficulty)
+    });
+    Some(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    #[test]
+    fn test_profile() {
+        let puzzle = Tier::Singles.puzzle();
+        let solution = solution(Tier::Singles);
+        let mut placements: Vec<Placement> = puzzle
+            .bytes()
+            .zip(solution.bytes())
+            .enumerate()
+            .filter(|(_, (p, _))| *p == b'.')
+            .map(|(cell, (_, s))| Placement {
+                at_ms: cell as u64 * 1000,
+                cell,
+                value: s - b'0',
+            })
+            .collect();
+        // A wrong digit first
+        placements.insert(
+            0,
+            Placement {
+                at_ms: 0,
+                cell: 2,
+                value: 1,
+            },
+        );
+
+        let profile = profile(puzzle, &placements).unwrap();
+        assert_eq!(profile.placements, 52);
+        assert_eq!(profile.guesses, 1);
+        let counted: u32 = profile.techniques.iter().map(|(_, n)| n).sum();
+        assert_eq!(counted, 51);
+        assert_eq!(profile.tiers.iter().map(|t| t.moves).sum::<u32>(), 51);
+        assert!(profile.guess_rate() > 0.0);
+    }
+}
@@ -0,0 +1,159 @@
+//! Hint explanations as data rather than English sentences: the technique,
+//! the cells of its pattern, the units they share and the digits placed or
+//! removed. Bindings can word them in any locale, with the glossary's
+//! catalogs or their own.
+
+use crate::i18n::{message, DEFAULT_LOCALE};
+use crate::{cells, lookup};
+use sudoku_core::{Hint, HintType, Position};
+
+/// A row, column or box, counting from 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    Box(usize),
+}
+
+impl Unit {
+    /// E.g. "box 7", counting from 1, in `locale`
+    pub fn name_in(self, locale: &str) -> String {
+        let (id, n) = match self {
+            Unit::Row(n) => ("unit-row", n),
+            Unit::Column(n) => ("unit-column", n),
+            Unit::Box(n) => ("unit-box", n),
+        };
+        message(locale, id, &[("n", &(n + 1).to_string())]).unwrap_or_default()
+    }
+}
+
+/// Whether a hint places a digit or removes candidates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Place,
+    Eliminate,
+}
+
+/// What a hint found, for wording in any language
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplanationTemplate {
+    /// Glossary key ("x-wing"), or the solver's name for a technique
+    /// without an entry
+    pub technique: String,
+    /// Cells of the pattern
+    pub cells: Vec<Position>,
+    /// Units every pattern cell lies in, box first; empty for a pattern
+    /// that spans several
+    pub units: Vec<Unit>,
+    pub action: Action,
+    /// The cell the hint changes
+    pub target: Position,
+    /// The digit placed, or the candidates removed
+    pub digits: Vec<u8>,
+}
+
+impl ExplanationTemplate {
+    pub fn from_hint(hint: &Hint) -> Self {
+        let name = hint.technique.to_string();
+        let (action, target, digits) = match &hint.hint_type {
+            HintType::SetValue { pos, value } => (Action::Place, *pos, vec![*value]),
+            HintType::EliminateCandidates { pos, values } => {
+                (Action::Eliminate, *pos, values.clone())
+            }
+        };
+        Self {
+            technique: lookup(&name).map_or(name, |e| e.key.to_string()),
+            cells: hint.involved_cells.clone(),
+            units: shared_units(&hint.involved_cells),
+            action,
+            target,
+            digits,
+        }
+    }
+
+    /// One sentence in `locale`, falling back to English for any wording
+    /// it lacks
+    pub fn format_in(&self, locale: &str) -> String {
+        let technique =
+            lookup(&self.technique).map_or_else(|| self.technique.clone(), |e| e.name_in(locale));
+        let place = match self.units.first() {
+            Some(unit) => unit.name_in(locale),
+            None if self.cells.is_empty() => cells(&[self.target]),
+            None => cells(&self.cells),
+        };
+        let cell = cells(&[self.target]);
+        let and = format!(" {} ", message(locale, "step-and", &[]).unwrap_or_default());
+        let digits: Vec<String> = self.digits.iter().map(|d| d.to_string()).collect();
+        let digits = digits.join(&and);
+        let id = match self.action {
+            Action::Place => "explain-place",
+            Action::Eliminate => "explain-eliminate",
+        };
+        let args = [
+            ("technique", technique.as_str()),
+            ("where", place.as_str()),
+            ("cell", cell.as_str()),
+            ("digits", digits.as_str()),
+        ];
+        message(locale, id, &args).unwrap_or_default()
+    }
+
+    pub fn to_english(&self) -> String {
+        self.format_in(DEFAULT_LOCALE)
+    }
+}
+
+/// Units holding every one of `positions`, box then row then column
+fn shared_units(positions: &[Position]) -> Vec<Unit> {
+    let Some(first) = positions.first() else {
+        return Vec::new();
+    };
+    let same = |f: fn(&Position) -> usize| positions.iter().all(|p| f(p) == f(first));
+    let mut units = Vec::new();
+    if same(|p| p.row / 3 * 3 + p.col / 3) {
+        units.push(Unit::Box(first.row / 3 * 3 + first.col / 3));
+    }
+    if same(|p| p.row) {
+        units.push(Unit::Row(first.row));
+    }
+    if same(|p| p.col) {
+        units.push(Unit::Column(first.col));
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_core::Solver;
+    use sudoku_test_utils::{assert_hint_sound, fixture, solution_digits, Tier};
+
+    #[test]
+    fn test_template() {
+        let pair = [Position::new(1, 0), Position::new(1, 2)];
+        assert_eq!(shared_units(&pair), [Unit::Box(0), Unit::Row(1)]);
+        assert!(shared_units(&[Position::new(0, 0), Position::new(8, 8)]).is_empty());
+
+        let template = ExplanationTemplate {
+            technique: "x-wing".to_string(),
+            cells: [(1, 2), (4, 2), (1, 6), (4, 6)]
+                .map(|(r, c)| Position::new(r, c))
+                .to_vec(),
+            units: Vec::new(),
+            action: Action::Eliminate,
+            target: Position::new(7, 2),
+            digits: vec![5, 7],
+        };
+        assert_eq!(
+            template.to_english(),
+            "X-Wing in r25c37: 5 and 7 can be removed from r8c3."
+        );
+
+        let grid = fixture(Tier::Singles);
+        let hint = Solver::new().get_hint(&grid).unwrap();
+        assert_hint_sound(&solution_digits(&grid), &hint);
+        let template = ExplanationTemplate::from_hint(&hint);
+        assert_eq!(template.cells, hint.involved_cells);
+        assert!(!template.to_english().is_empty());
+    }
+}
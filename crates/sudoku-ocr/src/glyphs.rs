@@ -0,0 +1,78 @@
+//! Digit classifier: a cell's ink, resampled onto a 5×7 raster, compared
+//! with a small built-in font by correlation. Printed puzzles use plain
+//! upright digits, which this separates well without a trained model.
+
+/// Raster size the glyphs are compared at
+pub const GLYPH_W: usize = 5;
+pub const GLYPH_H: usize = 7;
+
+/// Digits 1-9, `#` for ink
+#[rustfmt::skip]
+const FONT: [[&str; GLYPH_H]; 9] = [
+    ["..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###."],
+    [".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####"],
+    ["#####", "...#.", "..#..", "...#.", "....#", "#...#", ".###."],
+    ["...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#."],
+    ["#####", "#....", "####.", "....#", "....#", "#...#", ".###."],
+    ["..##.", ".#...", "#....", "####.", "#...#", "#...#", ".###."],
+    ["#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#..."],
+    [".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###."],
+    [".###.", "#...#", "#...#", ".####", "....#", "...#.", ".##.."],
+];
+
+/// Ink of `digit` (1-9) at glyph position `x`, `y`
+pub fn ink(digit: u8, x: usize, y: usize) -> bool {
+    FONT[digit as usize - 1][y].as_bytes()[x] == b'#'
+}
+
+fn template(digit: u8) -> [f32; GLYPH_W * GLYPH_H] {
+    let mut t = [0.0; GLYPH_W * GLYPH_H];
+    for (i, v) in t.iter_mut().enumerate() {
+        *v = f32::from(u8::from(ink(digit, i % GLYPH_W, i / GLYPH_W)));
+    }
+    t
+}
+
+/// Pearson correlation of two rasters, 0 if either is flat
+fn correlation(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len() as f32;
+    let (ma, mb) = (a.iter().sum::<f32>() / n, b.iter().sum::<f32>() / n);
+    let (mut cov, mut va, mut vb) = (0.0, 0.0, 0.0);
+    for (&x, &y) in a.iter().zip(b) {
+        cov += (x - ma) * (y - mb);
+        va += (x - ma) * (x - ma);
+        vb += (y - mb) * (y - mb);
+    }
+    if va == 0.0 || vb == 0.0 {
+        0.0
+    } else {
+        cov / (va * vb).sqrt()
+    }
+}
+
+/// Best digit for a 5×7 raster of ink coverage (0-1), with a confidence in
+/// 0-1 that drops when the match is weak or the runner-up is close
+pub fn classify(raster: &[f32; GLYPH_W * GLYPH_H]) -> (u8, f32) {
+    let mut scores: Vec<(u8, f32)> = (1..=9)
+        .map(|d| (d, correlation(raster, &template(d))))
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let (digit, best) = scores[0];
+    let margin = best - scores[1].1;
+    let confidence = best.clamp(0.0, 1.0) * (margin * 4.0).clamp(0.0, 1.0);
+    (digit, confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_classifies_itself() {
+        for digit in 1..=9 {
+            let (found, confidence) = classify(&template(digit));
+            assert_eq!(found, digit);
+            assert!(confidence > 0.5, "{} scored {}", digit, confidence);
+        }
+    }
+}
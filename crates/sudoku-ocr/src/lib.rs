@@ -0,0 +1,315 @@
+//! Reading a printed Sudoku from a grayscale image.
+//!
+//! The image should show the grid straight on, roughly cropped to it, as a
+//! phone scan of a newspaper page does. The outer border is found from the
+//! darkest rows and columns, the grid is split into 81 cells, and each cell
+//! is either empty or classified as a digit with a confidence. Low
+//! confidence cells are for the player to check, not errors.
+
+mod glyphs;
+
+use glyphs::{GLYPH_H, GLYPH_W};
+use std::fmt;
+
+/// Share of a cell's inner area that must be ink for it to hold a digit
+const MIN_INK: f32 = 0.02;
+/// Share of each side of a cell ignored, so grid lines aren't read as ink
+const CELL_MARGIN: f32 = 0.15;
+/// A row or column darker than this share is taken as a grid line
+const LINE_DENSITY: f32 = 0.5;
+
+/// An 8-bit grayscale image, row-major, 0 = black
+pub struct GrayImage<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: &'a [u8],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanError {
+    /// `pixels` doesn't hold `width * height` bytes
+    BadDimensions,
+    /// Too small to hold a readable grid
+    TooSmall,
+    #[cfg(feature = "image")]
+    Decode(String),
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::BadDimensions => write!(f, "pixel data doesn't match the image size"),
+            ScanError::TooSmall => write!(f, "image is too small to hold a grid"),
+            #[cfg(feature = "image")]
+            ScanError::Decode(e) => write!(f, "could not read image: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// What was read in one cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellGuess {
+    pub digit: Option<u8>,
+    /// 0-1: how sure the reading is
+    pub confidence: f32,
+}
+
+/// A scanned grid, row-major
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scan {
+    pub cells: Vec<CellGuess>,
+}
+
+impl Scan {
+    /// The reading as an 81-character puzzle string, "." for empty cells
+    pub fn puzzle(&self) -> String {
+        self.cells
+            .iter()
+            .map(|c| c.digit.map_or('.', |d| (b'0' + d) as char))
+            .collect()
+    }
+
+    /// Indices of cells read with less than `min_confidence`
+    pub fn uncertain(&self, min_confidence: f32) -> Vec<usize> {
+        (0..self.cells.len())
+            .filter(|&i| self.cells[i].confidence < min_confidence)
+            .collect()
+    }
+}
+
+/// Otsu's threshold: the gray level best separating ink from paper
+fn otsu(pixels: &[u8]) -> u8 {
+    let mut histogram = [0usize; 256];
+    for &p in pixels {
+        histogram[p as usize] += 1;
+    }
+    let total = pixels.len() as f64;
+    let sum: f64 = (0..256).map(|i| i as f64 * histogram[i] as f64).sum();
+    let (mut sum_bg, mut weight_bg) = (0.0, 0.0);
+    let (mut best, mut best_var) = (127, 0.0);
+    for (level, &count) in histogram.iter().enumerate() {
+        weight_bg += count as f64;
+        if weight_bg == 0.0 || weight_bg == total {
+            continue;
+        }
+        sum_bg += level as f64 * count as f64;
+        let mean_bg = sum_bg / weight_bg;
+        let mean_fg = (sum - sum_bg) / (total - weight_bg);
+        let var = weight_bg * (total - weight_bg) * (mean_bg - mean_fg).powi(2);
+        if var > best_var {
+            best_var = var;
+            best = level as u8;
+        }
+    }
+    best
+}
+
+/// Ink mask of the image
+struct Mask {
+    width: usize,
+    height: usize,
+    ink: Vec<bool>,
+}
+
+impl Mask {
+    fn at(&self, x: usize, y: usize) -> bool {
+        self.ink[y * self.width + x]
+    }
+
+    /// First and last index along one axis whose line is dense with ink,
+    /// or the whole span if there is none
+    fn border(
+        &self,
+        len: usize,
+        span: usize,
+        ink_at: impl Fn(usize, usize) -> bool,
+    ) -> (usize, usize) {
+        let dense: Vec<usize> = (0..len)
+            .filter(|&i| {
+                (0..span).filter(|&j| ink_at(i, j)).count() as f32 > span as f32 * LINE_DENSITY
+            })
+            .collect();
+        match (dense.first(), dense.last()) {
+            (Some(&first), Some(&last)) if last - first >= len / 2 => (first, last),
+            _ => (0, len - 1),
+        }
+    }
+
+    /// Ink coverage of the inner part of a cell, resampled to the glyph raster
+    fn read_cell(&self, x0: f32, y0: f32, size_w: f32, size_h: f32) -> CellGuess {
+        let left = (x0 + size_w * CELL_MARGIN) as usize;
+        let right = (x0 + size_w * (1.0 - CELL_MARGIN)) as usize;
+        let top = (y0 + size_h * CELL_MARGIN) as usize;
+        let bottom = (y0 + size_h * (1.0 - CELL_MARGIN)) as usize;
+        let (right, bottom) = (right.min(self.width - 1), bottom.min(self.height - 1));
+
+        // Bounding box of the ink in the cell
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (usize::MAX, 0, usize::MAX, 0);
+        let mut ink = 0;
+        for y in top..=bottom {
+            for x in left..=right {
+                if self.at(x, y) {
+                    ink += 1;
+                    min_x = min_x.min(x);
+                    max_x = max_x.max(x);
+                    min_y = min_y.min(y);
+                    max_y = max_y.max(y);
+                }
+            }
+        }
+        let area = ((right - left + 1) * (bottom - top + 1)) as f32;
+        let coverage = ink as f32 / area;
+        if coverage < MIN_INK {
+            return CellGuess {
+                digit: None,
+                confidence: 1.0 - coverage / MIN_INK,
+            };
+        }
+
+        // Widen narrow glyphs (a "1") to the font's 5:7 shape before
+        // resampling, so they aren't stretched across the raster
+        let glyph_h = (max_y - min_y + 1) as f32;
+        let mut glyph_w = (max_x - min_x + 1) as f32;
+        let mut gx = min_x as f32;
+        let wanted = glyph_h * GLYPH_W as f32 / GLYPH_H as f32;
+        if glyph_w < wanted {
+            gx -= (wanted - glyph_w) / 2.0;
+            glyph_w = wanted;
+        }
+
+        let mut raster = [0.0; GLYPH_W * GLYPH_H];
+        for (i, value) in raster.iter_mut().enumerate() {
+            let (cx, cy) = (i % GLYPH_W, i / GLYPH_W);
+            let sx0 = gx + glyph_w * cx as f32 / GLYPH_W as f32;
+            let sx1 = gx + glyph_w * (cx + 1) as f32 / GLYPH_W as f32;
+            let sy0 = min_y as f32 + glyph_h * cy as f32 / GLYPH_H as f32;
+            let sy1 = min_y as f32 + glyph_h * (cy + 1) as f32 / GLYPH_H as f32;
+            let (mut hits, mut total) = (0, 0);
+            let mut y = sy0.floor().max(0.0) as usize;
+            while (y as f32) < sy1 && y < self.height {
+                let mut x = sx0.floor().max(0.0) as usize;
+                while (x as f32) < sx1 && x < self.width {
+                    total += 1;
+                    hits += usize::from(self.at(x, y));
+                    x += 1;
+                }
+                y += 1;
+            }
+            *value = if total == 0 {
+                0.0
+            } else {
+                hits as f32 / total as f32
+            };
+        }
+        let (digit, confidence) = glyphs::classify(&raster);
+        CellGuess {
+            digit: Some(digit),
+            confidence,
+        }
+    }
+}
+
+/// Read the 81 cells of the grid in `image`
+pub fn scan(image: &GrayImage) -> Result<Scan, ScanError> {
+    if image.pixels.len() != image.width * image.height {
+        return Err(ScanError::BadDimensions);
+    }
+    if image.width < 9 * 8 || image.height < 9 * 8 {
+        return Err(ScanError::TooSmall);
+    }
+    let threshold = otsu(image.pixels);
+    let mask = Mask {
+        width: image.width,
+        height: image.height,
+        ink: image.pixels.iter().map(|&p| p <= threshold).collect(),
+    };
+
+    let (top, bottom) = mask.border(mask.height, mask.width, |y, x| mask.at(x, y));
+    let (left, right) = mask.border(mask.width, mask.height, |x, y| mask.at(x, y));
+    let cell_w = (right - left) as f32 / 9.0;
+    let cell_h = (bottom - top) as f32 / 9.0;
+
+    let cells = (0..81)
+        .map(|i| {
+            let x0 = left as f32 + cell_w * (i % 9) as f32;
+            let y0 = top as f32 + cell_h * (i / 9) as f32;
+            mask.read_cell(x0, y0, cell_w, cell_h)
+        })
+        .collect();
+    Ok(Scan { cells })
+}
+
+/// Decode an image file (PNG, JPEG, ...) and scan it
+#[cfg(feature = "image")]
+pub fn scan_file(path: impl AsRef<std::path::Path>) -> Result<Scan, ScanError> {
+    let image = image::open(path)
+        .map_err(|e| ScanError::Decode(e.to_string()))?
+        .to_luma8();
+    scan(&GrayImage {
+        width: image.width() as usize,
+        height: image.height() as usize,
+        pixels: image.as_raw(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draw a grid with `puzzle`'s digits in the built-in font
+    fn render(puzzle: &str) -> (usize, Vec<u8>) {
+        const CELL: usize = 40;
+        const SCALE: usize = 3;
+        let size = CELL * 9 + 4;
+        let mut pixels = vec![255u8; size * size];
+        for k in 0..=9 {
+            let thickness = if k % 3 == 0 { 4 } else { 2 };
+            for t in 0..thickness {
+                let line = (k * CELL + t).min(size - 1);
+                for i in 0..size {
+                    pixels[line * size + i] = 0;
+                    pixels[i * size + line] = 0;
+                }
+            }
+        }
+        for (i, c) in puzzle.bytes().enumerate() {
+            if !c.is_ascii_digit() || c == b'0' {
+                continue;
+            }
+            let x0 = (i % 9) * CELL + (CELL - GLYPH_W * SCALE) / 2 + 2;
+            let y0 = (i / 9) * CELL + (CELL - GLYPH_H * SCALE) / 2 + 2;
+            for gy in 0..GLYPH_H * SCALE {
+                for gx in 0..GLYPH_W * SCALE {
+                    if glyphs::ink(c - b'0', gx / SCALE, gy / SCALE) {
+                        pixels[(y0 + gy) * size + x0 + gx] = 20;
+                    }
+                }
+            }
+        }
+        (size, pixels)
+    }
+
+    #[test]
+    fn test_scan_rendered_grid() {
+        let puzzle =
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+        let (size, pixels) = render(puzzle);
+        let scan = scan(&GrayImage {
+            width: size,
+            height: size,
+            pixels: &pixels,
+        })
+        .unwrap();
+        assert_eq!(scan.puzzle(), puzzle);
+        assert!(scan.uncertain(0.5).is_empty());
+
+        let bad = GrayImage {
+            width: size,
+            height: size,
+            pixels: &pixels[1..],
+        };
+        assert_eq!(super::scan(&bad), Err(ScanError::BadDimensions));
+    }
+}
@@ -0,0 +1,382 @@
+//! Python bindings: `import sudoku`.
+//!
+//! ```python
+//! import sudoku
+//! puzzle = sudoku.Generator().generate("hard")
+//! rating = sudoku.Solver().rate(puzzle)
+//! print(rating.difficulty, rating.se_rating)
+//! for step in sudoku.Solver().solve_path(puzzle):
+//!     print(step.technique, step.row, step.col, step.value)
+//! ```
+//!
+//! Anywhere a puzzle is expected, either a `Grid` or an 81-character string
+//! ("." or "0" for empty cells) is accepted. Difficulties are level names
+//! such as "hard", matched ignoring case. Rows and columns are zero-based.
+
+// pyo3 0.22's #[pymethods] expansion converts PyErr into itself
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use sudoku_core::{Difficulty, Generator, Grid, Hint, HintType, Position, PuzzleId, Solver};
+use sudoku_session::apply_hint;
+
+/// Most steps a solve path can take: every candidate eliminated, then
+/// every cell placed
+const MAX_PATH_STEPS: usize = 81 * 9 + 81;
+
+/// A puzzle argument: a `Grid` or a puzzle string
+#[derive(FromPyObject)]
+enum PuzzleArg<'py> {
+    Grid(PyRef<'py, PyGrid>),
+    Text(String),
+}
+
+impl PuzzleArg<'_> {
+    fn grid(&self) -> PyResult<Grid> {
+        match self {
+            PuzzleArg::Grid(grid) => Ok(grid.inner.deep_clone()),
+            PuzzleArg::Text(text) => parse(text),
+        }
+    }
+}
+
+fn parse(puzzle: &str) -> PyResult<Grid> {
+    Grid::from_string(puzzle.trim())
+        .ok_or_else(|| PyValueError::new_err("puzzle must be 81 cells (digits, '.' or '0')"))
+}
+
+fn parse_difficulty(name: &str) -> PyResult<Difficulty> {
+    Difficulty::all_levels()
+        .iter()
+        .copied()
+        .find(|d| d.to_string().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| PyValueError::new_err(format!("unknown difficulty \"{}\"", name)))
+}
+
+fn position(row: usize, col: usize) -> PyResult<Position> {
+    if row >= 9 || col >= 9 {
+        return Err(PyIndexError::new_err("row and col must be 0-8"));
+    }
+    Ok(Position::new(row, col))
+}
+
+/// A Sudoku grid: givens, placed values and candidates
+#[pyclass(name = "Grid")]
+#[derive(Clone)]
+pub struct PyGrid {
+    inner: Grid,
+}
+
+#[pymethods]
+impl PyGrid {
+    /// Read a puzzle string; candidates are filled in
+    #[new]
+    fn new(puzzle: &str) -> PyResult<Self> {
+        let mut inner = parse(puzzle)?;
+        inner.recalculate_candidates();
+        Ok(Self { inner })
+    }
+
+    /// Values as nine rows of nine, 0 for empty cells
+    fn values(&self) -> Vec<Vec<u8>> {
+        self.inner
+            .values()
+            .iter()
+            .map(|row| row.iter().map(|v| v.unwrap_or(0)).collect())
+            .collect()
+    }
+
+    /// Value of a cell, or None if it is empty
+    fn get(&self, row: usize, col: usize) -> PyResult<Option<u8>> {
+        Ok(self.inner.get(position(row, col)?))
+    }
+
+    /// Place a value (1-9), or clear the cell with None. Givens can't change.
+    #[pyo3(signature = (row, col, value))]
+    fn set(&mut self, row: usize, col: usize, value: Option<u8>) -> PyResult<()> {
+        let pos = position(row, col)?;
+        if self.inner.cell(pos).is_given() {
+            return Err(PyValueError::new_err("cannot change a given"));
+        }
+        if value.is_some_and(|v| !(1..=9).contains(&v)) {
+            return Err(PyValueError::new_err("value must be 1-9"));
+        }
+        self.inner.set_cell_unchecked(pos, value);
+        self.inner.recalculate_candidates();
+        Ok(())
+    }
+
+    /// Candidates of a cell, ascending
+    fn candidates(&self, row: usize, col: usize) -> PyResult<Vec<u8>> {
+        Ok(self.inner.get_candidates(position(row, col)?).to_vec())
+    }
+
+    fn is_given(&self, row: usize, col: usize) -> PyResult<bool> {
+        Ok(self.inner.cell(position(row, col)?).is_given())
+    }
+
+    /// Every cell filled with no conflicts
+    fn is_solved(&self) -> bool {
+        self.inner.is_complete() && self.inner.validate().is_valid
+    }
+
+    /// Number of givens
+    fn clues(&self) -> usize {
+        clues(&self.inner)
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string_compact()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Grid('{}')", self.inner.to_string_compact())
+    }
+}
+
+fn clues(grid: &Grid) -> usize {
+    (0..81)
+        .filter(|i| grid.cell(Position::new(i / 9, i % 9)).is_given())
+        .count()
+}
+
+/// Difficulty of a puzzle
+#[pyclass(get_all, frozen)]
+pub struct Rating {
+    /// Level name, e.g. "Hard"
+    pub difficulty: String,
+    /// Sudoku Explainer rating of the hardest step
+    pub se_rating: f32,
+    pub clues: usize,
+}
+
+#[pymethods]
+impl Rating {
+    fn __repr__(&self) -> String {
+        format!(
+            "Rating(difficulty='{}', se_rating={:.1}, clues={})",
+            self.difficulty, self.se_rating, self.clues
+        )
+    }
+}
+
+/// One logical step: a placement or a set of eliminations in one cell
+#[pyclass(get_all, frozen)]
+pub struct Step {
+    pub technique: String,
+    pub se_rating: f32,
+    pub explanation: String,
+    pub row: usize,
+    pub col: usize,
+    /// Value placed, for placements
+    pub value: Option<u8>,
+    /// Candidates removed, for eliminations
+    pub eliminate: Vec<u8>,
+    /// (row, col) of the cells the reasoning uses
+    pub involved_cells: Vec<(usize, usize)>,
+}
+
+#[pymethods]
+impl Step {
+    fn __repr__(&self) -> String {
+        match self.value {
+            Some(value) => format!(
+                "Step({}: r{}c{}={})",
+                self.technique,
+                self.row + 1,
+                self.col + 1,
+                value
+            ),
+            None => format!(
+                "Step({}: r{}c{}<>{:?})",
+                self.technique,
+                self.row + 1,
+                self.col + 1,
+                self.eliminate
+            ),
+        }
+    }
+}
+
+impl From<&Hint> for Step {
+    fn from(hint: &Hint) -> Self {
+        let (pos, value, eliminate) = match &hint.hint_type {
+            HintType::SetValue { pos, value } => (*pos, Some(*value), vec![]),
+            HintType::EliminateCandidates { pos, values } => (*pos, None, values.clone()),
+        };
+        Step {
+            technique: hint.technique.to_string(),
+            se_rating: hint.technique.se_rating(),
+            explanation: hint.explanation.clone(),
+            row: pos.row,
+            col: pos.col,
+            value,
+            eliminate,
+            involved_cells: hint.involved_cells.iter().map(|p| (p.row, p.col)).collect(),
+        }
+    }
+}
+
+/// Solving, counting, rating and hints. Long-running calls release the GIL.
+#[pyclass(name = "Solver", frozen)]
+pub struct PySolver;
+
+#[pymethods]
+impl PySolver {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+
+    /// The solution as a puzzle string, or None if there is none
+    fn solve(&self, py: Python<'_>, puzzle: PuzzleArg) -> PyResult<Option<String>> {
+        let grid = puzzle.grid()?;
+        Ok(py.allow_threads(|| Solver::new().solve(&grid).map(|g| g.to_string_compact())))
+    }
+
+    /// Number of solutions, counting up to `limit`
+    #[pyo3(signature = (puzzle, limit = 2))]
+    fn count_solutions(&self, py: Python<'_>, puzzle: PuzzleArg, limit: usize) -> PyResult<usize> {
+        let grid = puzzle.grid()?;
+        Ok(py.allow_threads(|| Solver::new().count_solutions(&grid, limit)))
+    }
+
+    /// Level and SE rating of a puzzle with exactly one solution
+    fn rate(&self, py: Python<'_>, puzzle: PuzzleArg) -> PyResult<Rating> {
+        let grid = puzzle.grid()?;
+        py.allow_threads(|| {
+            let solver = Solver::new();
+            if solver.count_solutions(&grid, 2) != 1 {
+                return Err(PyValueError::new_err(
+                    "puzzle must have exactly one solution",
+                ));
+            }
+            Ok(Rating {
+                difficulty: solver.rate_difficulty(&grid).to_string(),
+                se_rating: solver.rate_se(&grid),
+                clues: clues(&grid),
+            })
+        })
+    }
+
+    /// The next logical step, or None if no technique applies. A string
+    /// puzzle gets full candidates; a Grid's own candidates are used.
+    fn hint(&self, puzzle: PuzzleArg) -> PyResult<Option<Step>> {
+        let mut grid = puzzle.grid()?;
+        if matches!(puzzle, PuzzleArg::Text(_)) {
+            grid.recalculate_candidates();
+        }
+        Ok(Solver::new().get_hint(&grid).as_ref().map(Step::from))
+    }
+
+    /// Every step the solver takes from the puzzle to the solution, easiest
+    /// technique first. Stops early if no technique applies.
+    fn solve_path(&self, py: Python<'_>, puzzle: PuzzleArg) -> PyResult<Vec<Step>> {
+        let mut grid = puzzle.grid()?;
+        grid.recalculate_candidates();
+        Ok(py.allow_threads(|| {
+            let solver = Solver::new();
+            let mut path = Vec::new();
+            while !grid.is_complete() && path.len() < MAX_PATH_STEPS {
+                let Some(hint) = solver.get_hint(&grid) else {
+                    break;
+                };
+                if !apply_hint(&mut grid, &hint) {
+                    break;
+                }
+                path.push(Step::from(&hint));
+            }
+            path
+        }))
+    }
+}
+
+/// Puzzle generation
+#[pyclass(name = "Generator", frozen)]
+pub struct PyGenerator;
+
+#[pymethods]
+impl PyGenerator {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+
+    /// A new puzzle string at a difficulty level
+    #[pyo3(signature = (difficulty = "medium"))]
+    fn generate(&self, py: Python<'_>, difficulty: &str) -> PyResult<String> {
+        let difficulty = parse_difficulty(difficulty)?;
+        Ok(py.allow_threads(|| PuzzleId::random(difficulty).generate().to_string_compact()))
+    }
+
+    /// A new puzzle string close to a target SE rating
+    fn generate_for_se(&self, py: Python<'_>, se: f32) -> String {
+        py.allow_threads(|| Generator::new().generate_for_se(se).to_string_compact())
+    }
+
+    /// The puzzle an 8-character short code stands for
+    #[staticmethod]
+    fn from_short_code(code: &str) -> PyResult<String> {
+        PuzzleId::from_short_code(code)
+            .map(|id| id.generate().to_string_compact())
+            .ok_or_else(|| PyValueError::new_err("invalid short code"))
+    }
+}
+
+/// Names of every difficulty level, easiest first
+#[pyfunction]
+fn levels() -> Vec<String> {
+    Difficulty::all_levels()
+        .iter()
+        .map(|d| d.to_string())
+        .collect()
+}
+
+#[pymodule]
+fn sudoku(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyGrid>()?;
+    m.add_class::<PySolver>()?;
+    m.add_class::<PyGenerator>()?;
+    m.add_class::<Rating>()?;
+    m.add_class::<Step>()?;
+    m.add_function(wrap_pyfunction!(levels, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    fn text(puzzle: &str) -> PuzzleArg<'static> {
+        PuzzleArg::Text(puzzle.to_string())
+    }
+
+    #[test]
+    fn test_wrapped_functions() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            assert!(parse("123").is_err());
+            assert!(parse_difficulty(" HARD ").is_ok());
+            assert!(parse_difficulty("impossible").is_err());
+            assert!(position(9, 0).is_err());
+
+            let solver = PySolver::new();
+            let solved = solver.solve(py, text(PUZZLE)).unwrap();
+            assert_eq!(solved, Some(solution(Tier::Singles)));
+            assert_eq!(solver.count_solutions(py, text(PUZZLE), 2).unwrap(), 1);
+            assert!(solver.rate(py, text(&".".repeat(81))).is_err());
+            let path = solver.solve_path(py, text(PUZZLE)).unwrap();
+            assert!(path.iter().any(|step| step.value.is_some()));
+
+            let mut grid = PyGrid::new(PUZZLE).unwrap();
+            assert_eq!(grid.clues(), 30);
+            assert!(grid.set(0, 0, Some(1)).is_err());
+            grid.set(0, 2, Some(4)).unwrap();
+            assert_eq!(grid.get(0, 2).unwrap(), Some(4));
+        });
+    }
+}
@@ -0,0 +1,31 @@
+"""Smoke tests of the installed wheel, run by CI after building it."""
+
+import pytest
+import sudoku
+
+PUZZLE = "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"
+SOLUTION = "534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+
+
+def test_solve_and_count():
+    solver = sudoku.Solver()
+    assert solver.solve(PUZZLE) == SOLUTION
+    assert solver.count_solutions(PUZZLE) == 1
+
+
+def test_grid():
+    grid = sudoku.Grid(PUZZLE)
+    assert grid.clues() == 30
+    assert grid.get(0, 0) == 5
+    with pytest.raises(ValueError):
+        grid.set(0, 0, 1)
+    with pytest.raises(IndexError):
+        grid.get(9, 0)
+
+
+def test_generate_and_rate():
+    puzzle = sudoku.Generator().generate("easy")
+    rating = sudoku.Solver().rate(puzzle)
+    assert rating.difficulty in sudoku.levels()
+    path = sudoku.Solver().solve_path(puzzle)
+    assert path and all(step.value is not None or step.eliminate for step in path)
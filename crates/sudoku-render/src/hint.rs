@@ -0,0 +1,235 @@
+//! Boards from core grids and hints, and the role each cell plays in a
+//! hint for frontends that color the grid themselves
+
+use crate::{Board, Highlight, Link, Mark, MarkKind};
+use sudoku_core::{Grid, Hint, HintType, Polarity, Position, ProofCertificate};
+
+/// Role of a cell in a hint. The proof certificate refines the target and
+/// pattern cells into chain on/off, fish base/cover/fin, unique rectangle
+/// floor/roof and ALS groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintRole {
+    None,
+    Target,
+    Involved,
+    ChainOn,
+    ChainOff,
+    FishBase,
+    FishCover,
+    FishFin,
+    UrFloor,
+    UrRoof,
+    AlsGroup,
+}
+
+impl HintRole {
+    /// Legend label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Target => "target",
+            Self::Involved => "involved",
+            Self::ChainOn => "chain on",
+            Self::ChainOff => "chain off",
+            Self::FishBase => "base",
+            Self::FishCover => "cover",
+            Self::FishFin => "fin",
+            Self::UrFloor => "floor",
+            Self::UrRoof => "roof",
+            Self::AlsGroup => "ALS",
+        }
+    }
+
+    /// The background a [`Board`] draws for it
+    pub fn highlight(self) -> Highlight {
+        match self {
+            Self::None => Highlight::None,
+            Self::Target => Highlight::Target,
+            Self::Involved => Highlight::Involved,
+            Self::ChainOn => Highlight::On,
+            Self::ChainOff => Highlight::Off,
+            Self::FishBase => Highlight::Base,
+            Self::FishCover => Highlight::Cover,
+            Self::FishFin => Highlight::Fin,
+            Self::UrFloor | Self::UrRoof | Self::AlsGroup => Highlight::Group,
+        }
+    }
+}
+
+/// Roles for all 81 cells, row-major
+pub fn hint_roles(hint: &Hint) -> [HintRole; 81] {
+    let mut roles = [HintRole::None; 81];
+    for pos in &hint.involved_cells {
+        roles[pos.row * 9 + pos.col] = HintRole::Involved;
+    }
+
+    match &hint.proof {
+        Some(ProofCertificate::Fish {
+            base_sectors,
+            cover_sectors,
+            fins,
+            ..
+        }) => {
+            for (sectors, role) in [
+                (base_sectors, HintRole::FishBase),
+                (cover_sectors, HintRole::FishCover),
+            ] {
+                for &sector in sectors {
+                    for idx in sector_cells(sector) {
+                        if roles[idx] == HintRole::Involved {
+                            roles[idx] = role;
+                        }
+                    }
+                }
+            }
+            for &idx in fins.iter().filter(|&&i| i < 81) {
+                roles[idx] = HintRole::FishFin;
+            }
+        }
+        Some(ProofCertificate::Aic { chain, .. }) => {
+            for &(idx, _digit, polarity) in chain.iter().filter(|node| node.0 < 81) {
+                roles[idx] = match polarity {
+                    Polarity::On => HintRole::ChainOn,
+                    Polarity::Off => HintRole::ChainOff,
+                };
+            }
+        }
+        Some(ProofCertificate::Uniqueness {
+            floor_cells,
+            roof_cells,
+            ..
+        }) => {
+            for &idx in floor_cells.iter().filter(|&&i| i < 81) {
+                roles[idx] = HintRole::UrFloor;
+            }
+            for &idx in roof_cells.iter().filter(|&&i| i < 81) {
+                roles[idx] = HintRole::UrRoof;
+            }
+        }
+        Some(ProofCertificate::Als { als_chain, .. }) => {
+            for als in als_chain {
+                for &idx in als.cells.iter().filter(|&&i| i < 81) {
+                    roles[idx] = HintRole::AlsGroup;
+                }
+            }
+        }
+        Some(
+            ProofCertificate::Basic { .. }
+            | ProofCertificate::Forcing { .. }
+            | ProofCertificate::Backtracking,
+        )
+        | None => {}
+    }
+
+    let target = match &hint.hint_type {
+        HintType::SetValue { pos, .. } | HintType::EliminateCandidates { pos, .. } => pos,
+    };
+    roles[target.row * 9 + target.col] = HintRole::Target;
+    roles
+}
+
+/// Cells of a sector: 0..8 rows, 9..17 columns, 18..26 boxes
+fn sector_cells(sector: usize) -> Vec<usize> {
+    match sector {
+        0..=8 => (0..9).map(|col| sector * 9 + col).collect(),
+        9..=17 => (0..9).map(|row| row * 9 + sector - 9).collect(),
+        _ => {
+            let (br, bc) = ((sector - 18) / 3 * 3, (sector - 18) % 3 * 3);
+            (0..9).map(|i| (br + i / 3) * 9 + bc + i % 3).collect()
+        }
+    }
+}
+
+impl Board {
+    /// Values, givens and candidates of `grid`
+    pub fn from_grid(grid: &Grid) -> Self {
+        let mut board = Board::default();
+        for i in 0..81 {
+            let cell = grid.cell(Position::new(i / 9, i % 9));
+            board.values[i] = cell.value().unwrap_or(0);
+            board.givens[i] = cell.is_given();
+            board.candidates[i] = cell
+                .candidates()
+                .to_vec()
+                .iter()
+                .fold(0, |acc, &d| acc | (1 << d));
+        }
+        board
+    }
+
+    /// Highlight `cell` and its row, column and box
+    pub fn select(&mut self, cell: usize) {
+        let (row, col) = (cell / 9, cell % 9);
+        for i in 0..81 {
+            let (r, c) = (i / 9, i % 9);
+            if (r == row || c == col || (r / 3 == row / 3 && c / 3 == col / 3))
+                && self.highlights[i] == Highlight::None
+            {
+                self.highlights[i] = Highlight::Peer;
+            }
+        }
+        self.highlights[cell] = Highlight::Selected;
+    }
+
+    /// Overlay a hint: its target and pattern cells, the roles its proof
+    /// gives them, the value placed or candidates removed, and chain links
+    pub fn show_hint(&mut self, hint: &Hint) {
+        for (cell, role) in hint_roles(hint).into_iter().enumerate() {
+            if role != HintRole::None {
+                self.highlights[cell] = role.highlight();
+            }
+        }
+
+        if let Some(ProofCertificate::Aic { chain, .. }) = &hint.proof {
+            let chain: Vec<_> = chain.iter().filter(|node| node.0 < 81).collect();
+            for &&(idx, digit, _) in &chain {
+                self.marks.push(Mark {
+                    cell: idx,
+                    digit,
+                    kind: MarkKind::Node,
+                });
+            }
+            // Off to On is a strong inference, On to Off a weak one
+            for pair in chain.windows(2) {
+                self.links.push(Link {
+                    from: (pair[0].0, pair[0].1),
+                    to: (pair[1].0, pair[1].1),
+                    strong: matches!(pair[0].2, Polarity::Off),
+                });
+            }
+        }
+
+        match &hint.hint_type {
+            HintType::SetValue { pos, value } => {
+                let cell = pos.row * 9 + pos.col;
+                self.marks.push(Mark {
+                    cell,
+                    digit: *value,
+                    kind: MarkKind::Place,
+                });
+            }
+            HintType::EliminateCandidates { pos, values } => {
+                let cell = pos.row * 9 + pos.col;
+                for &digit in values {
+                    self.marks.push(Mark {
+                        cell,
+                        digit,
+                        kind: MarkKind::Eliminate,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sector_cells() {
+        assert_eq!(sector_cells(1), (9..18).collect::<Vec<_>>());
+        assert_eq!(sector_cells(10), vec![1, 10, 19, 28, 37, 46, 55, 64, 73]);
+        assert_eq!(sector_cells(22), vec![30, 31, 32, 39, 40, 41, 48, 49, 50]);
+    }
+}
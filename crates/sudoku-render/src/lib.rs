@@ -0,0 +1,470 @@
+//! Platform-independent board rendering.
+//!
+//! A [`Board`] (values, candidates, highlights and proof overlays) is laid out
+//! by [`layout`] into a [`Scene`]: a flat list of rectangles, lines, circles
+//! and digits in pixel coordinates. Backends turn the scene into a file:
+//! [`to_svg`], and [`to_png`] with the `png` feature. Anything else that
+//! can draw those four shapes (the CLI's PDF booklets) can use the scene
+//! directly. [`braille`] and [`large_digits`] write the values as text
+//! instead, for screen readers and low vision. [`hint_roles`] gives the
+//! role each cell plays in a hint, for frontends that draw the grid
+//! themselves.
+
+mod hint;
+#[cfg(feature = "png")]
+mod png;
+mod svg;
+mod text;
+
+pub use hint::{hint_roles, HintRole};
+#[cfg(feature = "png")]
+pub use png::to_png;
+pub use svg::to_svg;
+pub use text::{braille, large_digits};
+
+/// An sRGB color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+
+    /// "#rrggbb"
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Background of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Highlight {
+    #[default]
+    None,
+    Selected,
+    /// Shares a row, column or box with the selection
+    Peer,
+    /// The cell a hint changes
+    Target,
+    /// Part of a hint's pattern
+    Involved,
+    /// Fish base and cover sectors, and fins
+    Base,
+    Cover,
+    Fin,
+    /// Chain nodes that are true and false
+    On,
+    Off,
+    /// Cells of an almost locked set, or a unique rectangle
+    Group,
+}
+
+/// What a circled candidate means
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkKind {
+    /// The value a hint places
+    Place,
+    /// A candidate a hint removes
+    Eliminate,
+    /// A chain node
+    Node,
+}
+
+/// A circled candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    pub cell: usize,
+    pub digit: u8,
+    pub kind: MarkKind,
+}
+
+/// A line between two candidates, as drawn for chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Link {
+    /// (cell, digit)
+    pub from: (usize, u8),
+    pub to: (usize, u8),
+    /// Strong links are solid, weak ones dashed
+    pub strong: bool,
+}
+
+/// What to draw, cells row-major
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board {
+    /// 1-9, or 0 for an empty cell
+    pub values: [u8; 81],
+    pub givens: [bool; 81],
+    /// Pencil marks as bits: bit 1 set means candidate 1, and so on
+    pub candidates: [u16; 81],
+    pub highlights: [Highlight; 81],
+    pub marks: Vec<Mark>,
+    pub links: Vec<Link>,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            values: [0; 81],
+            givens: [false; 81],
+            candidates: [0; 81],
+            highlights: [Highlight::None; 81],
+            marks: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+}
+
+impl Board {
+    /// A board of givens from an 81-character puzzle string ("." or "0" for
+    /// empty cells)
+    pub fn from_puzzle(puzzle: &str) -> Option<Self> {
+        let bytes = puzzle.as_bytes();
+        if bytes.len() != 81 {
+            return None;
+        }
+        let mut board = Board::default();
+        for (i, &b) in bytes.iter().enumerate() {
+            match b {
+                b'1'..=b'9' => {
+                    board.values[i] = b - b'0';
+                    board.givens[i] = true;
+                }
+                b'.' | b'0' => {}
+                _ => return None,
+            }
+        }
+        Some(board)
+    }
+
+    /// Fill the empty cells from an 81-character solution string
+    pub fn fill(&mut self, solution: &str) {
+        for (i, b) in solution.bytes().take(81).enumerate() {
+            if self.values[i] == 0 && (b'1'..=b'9').contains(&b) {
+                self.values[i] = b - b'0';
+            }
+        }
+    }
+}
+
+/// Colors used by [`layout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub background: Color,
+    pub line: Color,
+    pub given: Color,
+    /// Digits the player placed
+    pub placed: Color,
+    pub candidate: Color,
+    pub selected: Color,
+    pub peer: Color,
+    pub target: Color,
+    pub involved: Color,
+    pub base: Color,
+    pub cover: Color,
+    pub fin: Color,
+    pub on: Color,
+    pub off: Color,
+    pub group: Color,
+    pub place_mark: Color,
+    pub eliminate_mark: Color,
+    pub link: Color,
+}
+
+impl Palette {
+    /// Dark digits on white, with soft highlight colors
+    pub fn light() -> Self {
+        Palette {
+            background: Color::WHITE,
+            line: Color(0x22, 0x22, 0x22),
+            given: Color(0x11, 0x11, 0x11),
+            placed: Color(0x1d, 0x4e, 0xd8),
+            candidate: Color(0x55, 0x55, 0x55),
+            selected: Color(0xbf, 0xdb, 0xfe),
+            peer: Color(0xee, 0xf2, 0xf7),
+            target: Color(0xbb, 0xf7, 0xd0),
+            involved: Color(0xfe, 0xf0, 0x8a),
+            base: Color(0xbf, 0xdb, 0xfe),
+            cover: Color(0xfb, 0xcf, 0xe8),
+            fin: Color(0xfe, 0xd7, 0xaa),
+            on: Color(0xbb, 0xf7, 0xd0),
+            off: Color(0xfe, 0xca, 0xca),
+            group: Color(0xdd, 0xd6, 0xfe),
+            place_mark: Color(0x16, 0xa3, 0x4a),
+            eliminate_mark: Color(0xdc, 0x26, 0x26),
+            link: Color(0x7c, 0x3a, 0xed),
+        }
+    }
+
+    /// Light digits on a dark background
+    pub fn dark() -> Self {
+        Palette {
+            background: Color(0x1b, 0x1d, 0x23),
+            line: Color(0xa0, 0xa4, 0xad),
+            given: Color(0xf1, 0xf2, 0xf4),
+            placed: Color(0x7d, 0xb4, 0xff),
+            candidate: Color(0x9a, 0x9e, 0xa8),
+            selected: Color(0x2f, 0x4a, 0x7a),
+            peer: Color(0x26, 0x29, 0x31),
+            target: Color(0x1f, 0x5c, 0x3a),
+            involved: Color(0x5c, 0x52, 0x1f),
+            base: Color(0x2f, 0x4a, 0x7a),
+            cover: Color(0x6b, 0x2d, 0x55),
+            fin: Color(0x6e, 0x45, 0x1c),
+            on: Color(0x1f, 0x5c, 0x3a),
+            off: Color(0x6e, 0x26, 0x26),
+            group: Color(0x45, 0x3a, 0x73),
+            place_mark: Color(0x4a, 0xde, 0x80),
+            eliminate_mark: Color(0xf8, 0x71, 0x71),
+            link: Color(0xc0, 0x84, 0xfc),
+        }
+    }
+
+    /// Black on white only, for print
+    pub fn print() -> Self {
+        let gray = Color(0xdd, 0xdd, 0xdd);
+        Palette {
+            background: Color::WHITE,
+            line: Color::BLACK,
+            given: Color::BLACK,
+            placed: Color::BLACK,
+            candidate: Color(0x44, 0x44, 0x44),
+            selected: gray,
+            peer: Color::WHITE,
+            target: gray,
+            involved: gray,
+            base: gray,
+            cover: gray,
+            fin: gray,
+            on: gray,
+            off: gray,
+            group: gray,
+            place_mark: Color::BLACK,
+            eliminate_mark: Color::BLACK,
+            link: Color::BLACK,
+        }
+    }
+
+    fn highlight(&self, highlight: Highlight) -> Option<Color> {
+        Some(match highlight {
+            Highlight::None => return None,
+            Highlight::Selected => self.selected,
+            Highlight::Peer => self.peer,
+            Highlight::Target => self.target,
+            Highlight::Involved => self.involved,
+            Highlight::Base => self.base,
+            Highlight::Cover => self.cover,
+            Highlight::Fin => self.fin,
+            Highlight::On => self.on,
+            Highlight::Off => self.off,
+            Highlight::Group => self.group,
+        })
+    }
+}
+
+/// Sizes and colors for [`layout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    /// Side of one cell in pixels
+    pub cell: f32,
+    /// Space around the grid in pixels
+    pub margin: f32,
+    /// Draw pencil marks in empty cells
+    pub candidates: bool,
+    pub palette: Palette,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            cell: 48.0,
+            margin: 12.0,
+            candidates: true,
+            palette: Palette::light(),
+        }
+    }
+}
+
+/// One drawing primitive, in pixels with y pointing down
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        fill: Color,
+    },
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        width: f32,
+        color: Color,
+        dashed: bool,
+    },
+    /// An outlined circle
+    Circle {
+        center: (f32, f32),
+        radius: f32,
+        width: f32,
+        color: Color,
+    },
+    /// A digit centered on `center`, `size` pixels tall (font size)
+    Digit {
+        center: (f32, f32),
+        size: f32,
+        digit: u8,
+        bold: bool,
+        color: Color,
+    },
+}
+
+/// A laid out board, drawn in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    pub width: f32,
+    pub height: f32,
+    pub background: Color,
+    pub shapes: Vec<Shape>,
+}
+
+/// Center of `digit`'s pencil mark slot in `cell`
+fn candidate_center(style: &Style, cell: usize, digit: u8) -> (f32, f32) {
+    let slot = style.cell / 3.0;
+    let d = usize::from(digit - 1);
+    (
+        style.margin + style.cell * (cell % 9) as f32 + slot * ((d % 3) as f32 + 0.5),
+        style.margin + style.cell * (cell / 9) as f32 + slot * ((d / 3) as f32 + 0.5),
+    )
+}
+
+/// Lay out `board`: highlights, grid lines, digits and candidates, then
+/// marks and links on top
+pub fn layout(board: &Board, style: &Style) -> Scene {
+    let palette = &style.palette;
+    let cell = style.cell;
+    let size = cell * 9.0;
+    let origin = style.margin;
+    let mut shapes = Vec::new();
+
+    for (i, &highlight) in board.highlights.iter().enumerate() {
+        if let Some(fill) = palette.highlight(highlight) {
+            shapes.push(Shape::Rect {
+                x: origin + cell * (i % 9) as f32,
+                y: origin + cell * (i / 9) as f32,
+                width: cell,
+                height: cell,
+                fill,
+            });
+        }
+    }
+
+    let heavy = (cell / 20.0).max(1.0);
+    for k in 0..=9 {
+        let width = if k % 3 == 0 { heavy } else { heavy / 3.0 };
+        let at = origin + cell * k as f32;
+        for (from, to) in [
+            ((at, origin), (at, origin + size)),
+            ((origin, at), (origin + size, at)),
+        ] {
+            shapes.push(Shape::Line {
+                from,
+                to,
+                width,
+                color: palette.line,
+                dashed: false,
+            });
+        }
+    }
+
+    let mark_radius = cell / 7.0;
+    for mark in board.marks.iter().filter(|m| m.cell < 81) {
+        let color = match mark.kind {
+            MarkKind::Place => palette.place_mark,
+            MarkKind::Eliminate => palette.eliminate_mark,
+            MarkKind::Node => palette.link,
+        };
+        shapes.push(Shape::Circle {
+            center: candidate_center(style, mark.cell, mark.digit),
+            radius: mark_radius,
+            width: (cell / 40.0).max(0.75),
+            color,
+        });
+    }
+
+    for i in 0..81 {
+        let (x, y) = (
+            origin + cell * (i % 9) as f32,
+            origin + cell * (i / 9) as f32,
+        );
+        let value = board.values[i];
+        if (1..=9).contains(&value) {
+            shapes.push(Shape::Digit {
+                center: (x + cell / 2.0, y + cell / 2.0),
+                size: cell * 0.62,
+                digit: value,
+                bold: board.givens[i],
+                color: if board.givens[i] {
+                    palette.given
+                } else {
+                    palette.placed
+                },
+            });
+        } else if style.candidates {
+            for digit in (1..=9).filter(|d| board.candidates[i] & (1 << d) != 0) {
+                shapes.push(Shape::Digit {
+                    center: candidate_center(style, i, digit),
+                    size: cell * 0.26,
+                    digit,
+                    bold: false,
+                    color: palette.candidate,
+                });
+            }
+        }
+    }
+
+    for link in board.links.iter().filter(|l| l.from.0 < 81 && l.to.0 < 81) {
+        shapes.push(Shape::Line {
+            from: candidate_center(style, link.from.0, link.from.1),
+            to: candidate_center(style, link.to.0, link.to.1),
+            width: (cell / 30.0).max(1.0),
+            color: palette.link,
+            dashed: !link.strong,
+        });
+    }
+
+    Scene {
+        width: size + 2.0 * origin,
+        height: size + 2.0 * origin,
+        background: palette.background,
+        shapes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_layout() {
+        let mut board = Board::from_puzzle(Tier::Singles.puzzle()).unwrap();
+        board.candidates[2] = (1 << 1) | (1 << 2) | (1 << 4);
+        board.highlights[2] = Highlight::Target;
+        board.marks.push(Mark {
+            cell: 2,
+            digit: 4,
+            kind: MarkKind::Place,
+        });
+        let scene = layout(&board, &Style::default());
+
+        let count = |f: fn(&Shape) -> bool| scene.shapes.iter().filter(|s| f(s)).count();
+        assert_eq!(count(|s| matches!(s, Shape::Rect { .. })), 1);
+        assert_eq!(count(|s| matches!(s, Shape::Line { .. })), 20);
+        assert_eq!(count(|s| matches!(s, Shape::Circle { .. })), 1);
+        // 30 givens and 3 candidates
+        assert_eq!(count(|s| matches!(s, Shape::Digit { .. })), 33);
+        assert_eq!(scene.width, 48.0 * 9.0 + 24.0);
+
+        assert!(Board::from_puzzle("12").is_none());
+    }
+}
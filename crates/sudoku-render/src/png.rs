@@ -0,0 +1,128 @@
+//! PNG output via tiny-skia. Digits are drawn as strokes from a small
+//! built-in outline, so no font needs to be installed.
+
+use crate::{Color, Scene, Shape};
+use tiny_skia::{
+    LineCap, LineJoin, Paint, PathBuilder, Pixmap, Rect, Stroke, StrokeDash, Transform,
+};
+
+/// Strokes of each digit 1-9 as polylines in a unit box, y pointing down
+#[rustfmt::skip]
+const GLYPHS: [&[&[(f32, f32)]]; 9] = [
+    &[&[(0.25, 0.2), (0.55, 0.0), (0.55, 1.0)], &[(0.25, 1.0), (0.85, 1.0)]],
+    &[&[(0.1, 0.2), (0.3, 0.0), (0.7, 0.0), (0.9, 0.2), (0.9, 0.4), (0.1, 1.0), (0.9, 1.0)]],
+    &[&[(0.1, 0.0), (0.9, 0.0), (0.45, 0.42), (0.75, 0.45), (0.9, 0.65), (0.85, 0.9), (0.65, 1.0), (0.3, 1.0), (0.1, 0.85)]],
+    &[&[(0.7, 1.0), (0.7, 0.0), (0.05, 0.7), (0.95, 0.7)]],
+    &[&[(0.9, 0.0), (0.15, 0.0), (0.1, 0.45), (0.6, 0.4), (0.85, 0.55), (0.9, 0.8), (0.7, 1.0), (0.3, 1.0), (0.1, 0.85)]],
+    &[&[(0.8, 0.05), (0.5, 0.0), (0.2, 0.15), (0.1, 0.5), (0.1, 0.8), (0.3, 1.0), (0.7, 1.0), (0.9, 0.8), (0.9, 0.6), (0.7, 0.45), (0.3, 0.45), (0.1, 0.6)]],
+    &[&[(0.1, 0.0), (0.9, 0.0), (0.4, 1.0)]],
+    &[&[(0.5, 0.45), (0.2, 0.35), (0.15, 0.15), (0.35, 0.0), (0.65, 0.0), (0.85, 0.15), (0.8, 0.35), (0.5, 0.45), (0.15, 0.6), (0.1, 0.8), (0.3, 1.0), (0.7, 1.0), (0.9, 0.8), (0.85, 0.6), (0.5, 0.45)]],
+    &[&[(0.9, 0.4), (0.7, 0.55), (0.3, 0.55), (0.1, 0.4), (0.1, 0.2), (0.3, 0.0), (0.7, 0.0), (0.9, 0.2), (0.9, 0.5), (0.8, 0.85), (0.5, 1.0), (0.2, 0.95)]],
+];
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.0, color.1, color.2, 255);
+    paint
+}
+
+/// The scene as a PNG file
+pub fn to_png(scene: &Scene) -> Result<Vec<u8>, String> {
+    let mut pixmap = Pixmap::new(scene.width.ceil() as u32, scene.height.ceil() as u32)
+        .ok_or("scene has no area")?;
+    let background = scene.background;
+    pixmap.fill(tiny_skia::Color::from_rgba8(
+        background.0,
+        background.1,
+        background.2,
+        255,
+    ));
+    let transform = Transform::identity();
+
+    for shape in &scene.shapes {
+        match *shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                fill,
+            } => {
+                if let Some(rect) = Rect::from_xywh(x, y, width, height) {
+                    pixmap.fill_rect(rect, &paint(fill), transform, None);
+                }
+            }
+            Shape::Line {
+                from,
+                to,
+                width,
+                color,
+                dashed,
+            } => {
+                let mut path = PathBuilder::new();
+                path.move_to(from.0, from.1);
+                path.line_to(to.0, to.1);
+                let mut stroke = Stroke {
+                    width,
+                    line_cap: LineCap::Square,
+                    ..Stroke::default()
+                };
+                if dashed {
+                    stroke.dash = StrokeDash::new(vec![width * 3.0, width * 2.0], 0.0);
+                }
+                if let Some(path) = path.finish() {
+                    pixmap.stroke_path(&path, &paint(color), &stroke, transform, None);
+                }
+            }
+            Shape::Circle {
+                center,
+                radius,
+                width,
+                color,
+            } => {
+                let stroke = Stroke {
+                    width,
+                    ..Stroke::default()
+                };
+                if let Some(path) = PathBuilder::from_circle(center.0, center.1, radius) {
+                    pixmap.stroke_path(&path, &paint(color), &stroke, transform, None);
+                }
+            }
+            Shape::Digit {
+                center,
+                size,
+                digit,
+                bold,
+                color,
+            } => {
+                if !(1..=9).contains(&digit) {
+                    continue;
+                }
+                // Cap height and width of a sans-serif digit at this size
+                let (h, w) = (size * 0.72, size * 0.43);
+                let (left, top) = (center.0 - w / 2.0, center.1 - h / 2.0);
+                let stroke = Stroke {
+                    width: size * if bold { 0.12 } else { 0.085 },
+                    line_cap: LineCap::Round,
+                    line_join: LineJoin::Round,
+                    ..Stroke::default()
+                };
+                let mut path = PathBuilder::new();
+                for line in GLYPHS[usize::from(digit - 1)] {
+                    for (k, &(x, y)) in line.iter().enumerate() {
+                        let (x, y) = (left + x * w, top + y * h);
+                        if k == 0 {
+                            path.move_to(x, y);
+                        } else {
+                            path.line_to(x, y);
+                        }
+                    }
+                }
+                if let Some(path) = path.finish() {
+                    pixmap.stroke_path(&path, &paint(color), &stroke, transform, None);
+                }
+            }
+        }
+    }
+    pixmap.encode_png().map_err(|e| e.to_string())
+}
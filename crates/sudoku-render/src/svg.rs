@@ -0,0 +1,148 @@
+//! SVG output
+
+use crate::{Scene, Shape};
+use std::fmt::Write;
+
+const FONT_FAMILY: &str = "Helvetica, Arial, sans-serif";
+
+/// A number with at most two decimals, without trailing zeros
+fn num(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// The scene as a standalone SVG document
+pub fn to_svg(scene: &Scene) -> String {
+    let (width, height) = (num(scene.width), num(scene.height));
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    );
+    let _ = write!(
+        out,
+        r#"<rect width="{}" height="{}" fill="{}"/><g font-family="{}" text-anchor="middle">"#,
+        width,
+        height,
+        scene.background.hex(),
+        FONT_FAMILY
+    );
+    for shape in &scene.shapes {
+        let _ = match *shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                fill,
+            } => write!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                num(x),
+                num(y),
+                num(width),
+                num(height),
+                fill.hex()
+            ),
+            Shape::Line {
+                from,
+                to,
+                width,
+                color,
+                dashed,
+            } => {
+                let dash = if dashed {
+                    format!(
+                        r#" stroke-dasharray="{} {}""#,
+                        num(width * 3.0),
+                        num(width * 2.0)
+                    )
+                } else {
+                    String::new()
+                };
+                write!(
+                    out,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" stroke-linecap="square"{}/>"#,
+                    num(from.0),
+                    num(from.1),
+                    num(to.0),
+                    num(to.1),
+                    color.hex(),
+                    num(width),
+                    dash
+                )
+            }
+            Shape::Circle {
+                center,
+                radius,
+                width,
+                color,
+            } => write!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                num(center.0),
+                num(center.1),
+                num(radius),
+                color.hex(),
+                num(width)
+            ),
+            Shape::Digit {
+                center,
+                size,
+                digit,
+                bold,
+                color,
+            } => write!(
+                out,
+                r#"<text x="{}" y="{}" dy="0.35em" font-size="{}"{} fill="{}">{}</text>"#,
+                num(center.0),
+                num(center.1),
+                num(size),
+                if bold { r#" font-weight="bold""# } else { "" },
+                color.hex(),
+                digit
+            ),
+        };
+    }
+    out.push_str("</g></svg>");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn test_svg() {
+        let scene = Scene {
+            width: 100.0,
+            height: 100.5,
+            background: Color::WHITE,
+            shapes: vec![
+                Shape::Line {
+                    from: (0.0, 0.0),
+                    to: (10.0, 10.0),
+                    width: 1.0,
+                    color: Color::BLACK,
+                    dashed: true,
+                },
+                Shape::Digit {
+                    center: (5.0, 5.0),
+                    size: 12.25,
+                    digit: 7,
+                    bold: true,
+                    color: Color(0x1d, 0x4e, 0xd8),
+                },
+            ],
+        };
+        let svg = to_svg(&scene);
+        assert!(svg
+            .starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100.5""#));
+        assert!(svg.contains(r#"stroke-dasharray="3 2""#));
+        assert!(svg.contains(r##"font-size="12.25" font-weight="bold" fill="#1d4ed8">7</text>"##));
+        assert!(svg.ends_with("</g></svg>"));
+    }
+}
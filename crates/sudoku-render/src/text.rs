@@ -0,0 +1,111 @@
+//! Boards as text for readers who can't use the drawn grid: one Braille
+//! cell per square for refreshable displays, and digits drawn three lines
+//! tall in block characters for low vision.
+
+use crate::Board;
+
+/// Digits 1-9 as Braille letters a-i, the way numbers are written after a
+/// number sign
+const BRAILLE_DIGITS: [char; 9] = ['⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊'];
+/// Dots 3 and 6, so an empty square still takes a cell on the display
+const BRAILLE_EMPTY: char = '⠤';
+
+/// Nine lines of eleven cells: three squares per box, a blank cell between
+/// boxes. Fits a 20-cell display.
+pub fn braille(board: &Board) -> Vec<String> {
+    board
+        .values
+        .chunks(9)
+        .map(|row| {
+            row.chunks(3)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .map(|&v| match v {
+                            1..=9 => BRAILLE_DIGITS[usize::from(v - 1)],
+                            _ => BRAILLE_EMPTY,
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("⠀")
+        })
+        .collect()
+}
+
+/// 3x5 pixel digits, "#" for ink; index 0 is an empty square's dot
+const FONT: [[&str; 5]; 10] = [
+    ["...", "...", ".#.", "...", "..."],
+    [".#.", "##.", ".#.", ".#.", "###"],
+    ["###", "..#", "###", "#..", "###"],
+    ["###", "..#", "###", "..#", "###"],
+    ["#.#", "#.#", "###", "..#", "..#"],
+    ["###", "#..", "###", "..#", "###"],
+    ["###", "#..", "###", "#.#", "###"],
+    ["###", "..#", "..#", "..#", "..#"],
+    ["###", "#.#", "###", "#.#", "###"],
+    ["###", "#.#", "###", "..#", "###"],
+];
+
+/// Two pixel rows of a glyph as one line of half blocks
+fn half_blocks(top: &str, bottom: &str) -> String {
+    top.bytes()
+        .zip(bottom.bytes())
+        .map(|pair| match pair {
+            (b'#', b'#') => '█',
+            (b'#', _) => '▀',
+            (_, b'#') => '▄',
+            _ => ' ',
+        })
+        .collect()
+}
+
+/// Each square three lines tall and three columns wide, with a wider gap
+/// between boxes and a blank line between bands of boxes. 39 columns by 29
+/// lines.
+pub fn large_digits(board: &Board) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (r, row) in board.values.chunks(9).enumerate() {
+        if r > 0 && r % 3 == 0 {
+            lines.push(String::new());
+        }
+        for pair in 0..3 {
+            let line: Vec<String> = row
+                .chunks(3)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .map(|&v| {
+                            let glyph = FONT[usize::from(v.min(9))];
+                            let bottom = glyph.get(pair * 2 + 1).copied().unwrap_or("...");
+                            half_blocks(glyph[pair * 2], bottom)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect();
+            lines.push(line.join("   ").trim_end().to_string());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_text_boards() {
+        let board = Board::from_puzzle(Tier::Singles.puzzle()).unwrap();
+        let dots = braille(&board);
+        assert_eq!(dots.len(), 9);
+        assert_eq!(dots[0], "⠑⠉⠤⠀⠤⠛⠤⠀⠤⠤⠤");
+
+        let large = large_digits(&board);
+        assert_eq!(large.len(), 9 * 3 + 2);
+        // Top of the 5 and the 3
+        assert!(large[0].starts_with("█▀▀ ▀▀█"));
+        assert!(large.iter().all(|line| line.chars().count() <= 39));
+    }
+}
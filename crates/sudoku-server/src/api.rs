@@ -0,0 +1,524 @@
+//! Request handlers and their JSON bodies.
+//!
+//! Puzzles are 81-character strings with "." or "0" for empty cells, and
+//! difficulties are level names such as "Hard", as in the other frontends.
+
+use crate::feed;
+use axum::extract::{Path, Query};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sudoku_core::{Difficulty, Grid, HintType, PuzzleId, Solver};
+use sudoku_daily as daily;
+use sudoku_render::{Board, Style};
+
+/// An error answered as `{"error": "..."}`
+pub struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self(StatusCode::BAD_REQUEST, message.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// Run solver work off the async runtime
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, ApiError> + Send + 'static,
+) -> ApiResult<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+}
+
+fn parse_grid(puzzle: &str) -> Result<Grid, ApiError> {
+    Grid::from_string(puzzle.trim())
+        .ok_or_else(|| ApiError::bad_request("puzzle must be 81 cells (digits, '.' or '0')"))
+}
+
+/// A grid with exactly one solution
+fn parse_unique(puzzle: &str) -> Result<Grid, ApiError> {
+    let grid = parse_grid(puzzle)?;
+    match Solver::new().count_solutions(&grid, 2) {
+        1 => Ok(grid),
+        0 => Err(ApiError::bad_request("puzzle has no solution")),
+        _ => Err(ApiError::bad_request("puzzle has more than one solution")),
+    }
+}
+
+fn parse_difficulty(name: &str) -> Result<Difficulty, ApiError> {
+    Difficulty::all_levels()
+        .iter()
+        .copied()
+        .find(|d| d.to_string().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| ApiError::bad_request(format!("unknown difficulty \"{}\"", name)))
+}
+
+/// A target SE rating within the levels' ranges; NaN is out of range too
+fn parse_se(target: f32) -> Result<f32, ApiError> {
+    let levels = Difficulty::all_levels();
+    let min = levels[0].se_range().0;
+    let max = levels[levels.len() - 1].se_range().1;
+    if (min..=max).contains(&target) {
+        Ok(target)
+    } else {
+        Err(ApiError::bad_request(format!("se must be {}-{}", min, max)))
+    }
+}
+
+/// Body of requests that take a single puzzle
+#[derive(Deserialize)]
+pub struct PuzzleRequest {
+    pub puzzle: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct GenerateRequest {
+    /// Level name; Medium if neither this nor `se` is given
+    pub difficulty: Option<String>,
+    /// Target SE rating instead of a level
+    pub se: Option<f32>,
+}
+
+/// Puzzles a `se` request generates before settling for the closest
+const SE_ATTEMPTS: u32 = 50;
+
+/// A generated puzzle
+#[derive(Serialize)]
+pub struct PuzzleResponse {
+    pub puzzle: String,
+    pub solution: String,
+    pub difficulty: String,
+    pub se_rating: f32,
+    /// 8-character code the apps can load, for seeded puzzles
+    pub short_code: Option<String>,
+    /// "YYYY-MM-DD", for the daily puzzle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+fn describe(grid: &Grid, short_code: Option<String>) -> Result<PuzzleResponse, ApiError> {
+    let solver = Solver::new();
+    let solution = solver
+        .solve(grid)
+        .ok_or_else(|| ApiError(StatusCode::INTERNAL_SERVER_ERROR, "unsolvable".into()))?;
+    Ok(PuzzleResponse {
+        puzzle: grid.to_string_compact(),
+        solution: solution.to_string_compact(),
+        difficulty: solver.rate_difficulty(grid).to_string(),
+        se_rating: solver.rate_se(grid),
+        short_code,
+        date: None,
+    })
+}
+
+/// `POST /generate`
+pub async fn generate(body: Option<Json<GenerateRequest>>) -> ApiResult<PuzzleResponse> {
+    let request = body.map(|Json(r)| r).unwrap_or_default();
+    let difficulty = match &request.difficulty {
+        Some(name) => parse_difficulty(name)?,
+        None => Difficulty::Medium,
+    };
+    let se = request.se.map(parse_se).transpose()?;
+    blocking(move || match se {
+        Some(target) => {
+            let (grid, _) = sudoku_session::generate_for_se(target, SE_ATTEMPTS, |_| true)
+                .ok_or_else(|| {
+                    ApiError(StatusCode::INTERNAL_SERVER_ERROR, "no puzzle found".into())
+                })?;
+            describe(&grid, None)
+        }
+        None => {
+            let id = PuzzleId::random(difficulty);
+            describe(&id.generate(), Some(id.to_short_code()))
+        }
+    })
+    .await
+}
+
+/// `GET /daily/{date}`
+pub async fn daily(Path(date): Path<String>) -> ApiResult<PuzzleResponse> {
+    let day = daily::parse_date(&date)
+        .ok_or_else(|| ApiError::bad_request("date must be YYYY-MM-DD, from 1970-01-01"))?;
+    blocking(move || {
+        let id = daily::puzzle_id(day);
+        let mut response = describe(&id.generate(), Some(id.to_short_code()))?;
+        response.date = Some(date);
+        Ok(response)
+    })
+    .await
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct FeedQuery {
+    /// First date, "YYYY-MM-DD"; today (UTC) if missing
+    pub from: Option<String>,
+    /// Number of days, 1-31; 7 if missing
+    pub days: Option<u64>,
+    /// "json" (the default) or "rss"
+    pub format: Option<String>,
+}
+
+/// `GET /daily/feed`: upcoming daily puzzles with their short codes
+pub async fn daily_feed(Query(query): Query<FeedQuery>) -> Result<Response, ApiError> {
+    let from = match &query.from {
+        Some(date) => daily::parse_date(date)
+            .ok_or_else(|| ApiError::bad_request("from must be YYYY-MM-DD, from 1970-01-01"))?,
+        None => daily::today(),
+    };
+    let days = query.days.unwrap_or(7);
+    if !(1..=feed::MAX_DAYS).contains(&days) {
+        return Err(ApiError::bad_request(format!(
+            "days must be 1-{}",
+            feed::MAX_DAYS
+        )));
+    }
+    let rss = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("rss") => true,
+        Some(other) => {
+            return Err(ApiError::bad_request(format!(
+                "unknown feed format \"{}\"",
+                other
+            )))
+        }
+    };
+    let Json(entries) = blocking(move || Ok(feed::entries(from, days))).await?;
+    if rss {
+        let headers = [(header::CONTENT_TYPE, "application/rss+xml")];
+        Ok((headers, feed::rss(&entries)).into_response())
+    } else {
+        Ok(Json(entries).into_response())
+    }
+}
+
+#[derive(Serialize)]
+pub struct SolveResponse {
+    pub solution: String,
+}
+
+/// `POST /solve`
+pub async fn solve(Json(request): Json<PuzzleRequest>) -> ApiResult<SolveResponse> {
+    blocking(move || {
+        let grid = parse_unique(&request.puzzle)?;
+        let solution = Solver::new()
+            .solve(&grid)
+            .ok_or_else(|| ApiError::bad_request("puzzle has no solution"))?;
+        Ok(SolveResponse {
+            solution: solution.to_string_compact(),
+        })
+    })
+    .await
+}
+
+#[derive(Serialize)]
+pub struct RateResponse {
+    pub difficulty: String,
+    pub se_rating: f32,
+    pub clues: usize,
+}
+
+/// `POST /rate`
+pub async fn rate(Json(request): Json<PuzzleRequest>) -> ApiResult<RateResponse> {
+    blocking(move || {
+        let grid = parse_unique(&request.puzzle)?;
+        let solver = Solver::new();
+        Ok(RateResponse {
+            difficulty: solver.rate_difficulty(&grid).to_string(),
+            se_rating: solver.rate_se(&grid),
+            clues: grid.values().iter().flatten().flatten().count(),
+        })
+    })
+    .await
+}
+
+/// A cell, zero-based
+#[derive(Serialize)]
+pub struct Cell {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// The next logical step, shaped like the FFI's `GameHint`
+#[derive(Serialize)]
+pub struct HintResponse {
+    pub technique: String,
+    pub se_rating: f32,
+    pub explanation: String,
+    pub cell: Cell,
+    /// Value to place, for placements
+    pub value: Option<u8>,
+    /// Candidates to remove from `cell`, for eliminations
+    pub eliminate: Vec<u8>,
+    pub involved_cells: Vec<Cell>,
+}
+
+/// `POST /hint`: candidates are computed from the givens and values in the
+/// puzzle string
+pub async fn hint(Json(request): Json<PuzzleRequest>) -> ApiResult<HintResponse> {
+    blocking(move || {
+        let mut grid = parse_grid(&request.puzzle)?;
+        grid.recalculate_candidates();
+        let hint = Solver::new()
+            .get_hint(&grid)
+            .ok_or_else(|| ApiError(StatusCode::UNPROCESSABLE_ENTITY, "no hint found".into()))?;
+        let (pos, value, eliminate) = match hint.hint_type {
+            HintType::SetValue { pos, value } => (pos, Some(value), vec![]),
+            HintType::EliminateCandidates { pos, values } => (pos, None, values),
+        };
+        Ok(HintResponse {
+            technique: hint.technique.to_string(),
+            se_rating: hint.technique.se_rating(),
+            explanation: hint.explanation,
+            cell: Cell {
+                row: pos.row,
+                col: pos.col,
+            },
+            value,
+            eliminate,
+            involved_cells: hint
+                .involved_cells
+                .iter()
+                .map(|p| Cell {
+                    row: p.row,
+                    col: p.col,
+                })
+                .collect(),
+        })
+    })
+    .await
+}
+
+#[derive(Serialize)]
+pub struct ValidateResponse {
+    /// Exactly one solution
+    pub valid: bool,
+    /// 0, 1 or 2 (meaning two or more)
+    pub solutions: usize,
+}
+
+/// `POST /validate`: an unreadable string is invalid rather than an error
+pub async fn validate(Json(request): Json<PuzzleRequest>) -> ApiResult<ValidateResponse> {
+    blocking(move || {
+        let solutions = Grid::from_string(request.puzzle.trim())
+            .map_or(0, |grid| Solver::new().count_solutions(&grid, 2));
+        Ok(ValidateResponse {
+            valid: solutions == 1,
+            solutions,
+        })
+    })
+    .await
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct ImageQuery {
+    /// "svg" (the default) or "png"
+    pub format: Option<String>,
+    /// Side of a cell in pixels, 16-128
+    pub cell: Option<f32>,
+    /// Draw pencil marks computed from the givens and values
+    pub candidates: bool,
+    /// Overlay the next logical step; implies `candidates`
+    pub hint: bool,
+}
+
+/// `GET /image/{puzzle}`: the board as an SVG or PNG image
+pub async fn image(
+    Path(puzzle): Path<String>,
+    Query(query): Query<ImageQuery>,
+) -> Result<Response, ApiError> {
+    let png = match query.format.as_deref() {
+        None | Some("svg") => false,
+        Some("png") => true,
+        Some(other) => {
+            return Err(ApiError::bad_request(format!(
+                "unknown image format \"{}\"",
+                other
+            )))
+        }
+    };
+    let Json((content_type, body)) = blocking(move || {
+        let mut grid = parse_grid(&puzzle)?;
+        grid.recalculate_candidates();
+        let mut board = Board::from_grid(&grid);
+        if query.hint {
+            if let Some(hint) = Solver::new().get_hint(&grid) {
+                board.show_hint(&hint);
+            }
+        }
+        let style = Style {
+            cell: query.cell.unwrap_or(48.0).clamp(16.0, 128.0),
+            candidates: query.candidates || query.hint,
+            ..Style::default()
+        };
+        let scene = sudoku_render::layout(&board, &style);
+        if png {
+            let bytes = sudoku_render::to_png(&scene)
+                .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            Ok(("image/png", bytes))
+        } else {
+            Ok(("image/svg+xml", sudoku_render::to_svg(&scene).into_bytes()))
+        }
+    })
+    .await?;
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{self, Body};
+    use axum::http::Request;
+    use serde_json::{json, Value};
+    use sudoku_test_utils::{solution, Tier};
+    use tower::ServiceExt;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    fn post(uri: &str, body: Value) -> Request<Body> {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    /// Send one request through the router: the status, content type and body
+    async fn send(request: Request<Body>) -> (StatusCode, String, Vec<u8>) {
+        let response = crate::router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or("", |value| value.to_str().unwrap())
+            .to_string();
+        let bytes = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, content_type, bytes.to_vec())
+    }
+
+    async fn send_json(request: Request<Body>) -> (StatusCode, Value) {
+        let (status, _, bytes) = send(request).await;
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_generate() {
+        let (status, body) = send_json(post("/generate", json!({ "difficulty": "easy" }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["puzzle"].as_str().unwrap().len(), 81);
+        assert_eq!(body["short_code"].as_str().unwrap().len(), 8);
+
+        let (status, body) = send_json(post("/generate", json!({ "se": 1.5 }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["short_code"].is_null());
+
+        let (status, body) = send_json(post("/generate", json!({ "se": 99.0 }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().starts_with("se must be"));
+        assert!(parse_se(f32::NAN).is_err());
+
+        let (status, _) = send_json(post("/generate", json!({ "difficulty": "Wobbly" }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_solve_and_rate() {
+        let (status, body) = send_json(post("/solve", json!({ "puzzle": PUZZLE }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["solution"], solution(Tier::Singles));
+
+        let (status, body) = send_json(post("/rate", json!({ "puzzle": PUZZLE }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["clues"], 30);
+
+        let empty = ".".repeat(81);
+        for uri in ["/solve", "/rate"] {
+            let (status, _) = send_json(post(uri, json!({ "puzzle": "123" }))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            let (status, body) = send_json(post(uri, json!({ "puzzle": empty }))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error"], "puzzle has more than one solution");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hint_and_validate() {
+        let (status, body) = send_json(post("/hint", json!({ "puzzle": PUZZLE }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!body["technique"].as_str().unwrap().is_empty());
+
+        let solved = solution(Tier::Singles);
+        let (status, _) = send_json(post("/hint", json!({ "puzzle": solved }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, _) = send_json(post("/hint", json!({ "puzzle": "123" }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        for (puzzle, solutions) in [
+            (PUZZLE.to_string(), 1),
+            ("123".into(), 0),
+            (".".repeat(81), 2),
+        ] {
+            let (status, body) = send_json(post("/validate", json!({ "puzzle": puzzle }))).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["solutions"], solutions);
+            assert_eq!(body["valid"], solutions == 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_daily() {
+        let (status, body) = send_json(get("/daily/2024-01-01")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["date"], "2024-01-01");
+        assert_eq!(body["short_code"].as_str().unwrap().len(), 8);
+        let (status, _) = send_json(get("/daily/yesterday")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = send_json(get("/daily/feed?from=2024-01-01&days=2")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 2);
+        let (status, content_type, _) = send(get("/daily/feed?days=1&format=rss")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "application/rss+xml");
+        for query in ["days=0", "days=32", "format=atom", "from=tomorrow"] {
+            let (status, _) = send_json(get(&format!("/daily/feed?{}", query))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_image() {
+        let (status, content_type, bytes) =
+            send(get(&format!("/image/{}?hint=true", PUZZLE))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "image/svg+xml");
+        assert!(bytes.starts_with(b"<svg"));
+
+        let (status, content_type, bytes) =
+            send(get(&format!("/image/{}?format=png", PUZZLE))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type, "image/png");
+        assert!(bytes.starts_with(b"\x89PNG"));
+
+        let (status, _) = send_json(get(&format!("/image/{}?format=gif", PUZZLE))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send_json(get("/image/123")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,108 @@
+//! Upcoming daily puzzles as JSON or RSS, for apps and newsletters that
+//! show them ahead of time. Entries come from the same seeds as `/daily`.
+
+use serde::Serialize;
+use sudoku_core::Solver;
+use sudoku_daily as daily;
+
+/// Most days one feed covers
+pub const MAX_DAYS: u64 = 31;
+
+/// Where feed items link to; the web app loads `?s=` short codes
+const PLAY_URL: &str = "https://ukodus.now/play/";
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Serialize)]
+pub struct FeedEntry {
+    /// "YYYY-MM-DD"
+    pub date: String,
+    #[serde(skip)]
+    pub day: u64,
+    pub short_code: String,
+    pub difficulty: String,
+    pub se_rating: f32,
+}
+
+/// The daily puzzles of `days` days starting at day number `from`
+pub fn entries(from: u64, days: u64) -> Vec<FeedEntry> {
+    let solver = Solver::new();
+    (from..from + days)
+        .map(|day| {
+            let id = daily::puzzle_id(day);
+            let grid = id.generate();
+            FeedEntry {
+                date: daily::format_date(day),
+                day,
+                short_code: id.to_short_code(),
+                difficulty: solver.rate_difficulty(&grid).to_string(),
+                se_rating: solver.rate_se(&grid),
+            }
+        })
+        .collect()
+}
+
+/// RFC 822 date at midnight UTC, as RSS wants
+fn rfc822(day: u64) -> String {
+    let (y, m, d) = daily::civil(day);
+    format!(
+        "{}, {:02} {} {} 00:00:00 GMT",
+        daily::weekday_name(day),
+        d,
+        MONTHS[(m - 1) as usize],
+        y
+    )
+}
+
+/// An RSS 2.0 channel with one item per entry
+pub fn rss(entries: &[FeedEntry]) -> String {
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<rss version=\"2.0\"><channel>\n",
+        "<title>Ukodus daily Sudoku</title>\n",
+        "<link>https://ukodus.now/play/</link>\n",
+        "<description>Upcoming daily puzzles</description>\n",
+    ));
+    for entry in entries {
+        let link = format!("{}?s={}", PLAY_URL, entry.short_code);
+        xml.push_str(&format!(
+            concat!(
+                "<item><title>Daily Sudoku {} ({})</title>",
+                "<link>{}</link><guid>{}</guid><pubDate>{}</pubDate>",
+                "<description>{}, SE {:.1}, code {}</description></item>\n"
+            ),
+            entry.date,
+            entry.difficulty,
+            link,
+            link,
+            rfc822(entry.day),
+            entry.difficulty,
+            entry.se_rating,
+            entry.short_code,
+        ));
+    }
+    xml.push_str("</channel></rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rss() {
+        let entry = FeedEntry {
+            date: "2024-01-01".into(),
+            day: daily::parse_date("2024-01-01").unwrap(),
+            short_code: "ABCD1234".into(),
+            difficulty: "Easy".into(),
+            se_rating: 2.0,
+        };
+        let xml = rss(&[entry]);
+        assert!(xml.contains("<pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>"));
+        assert!(xml.contains("<link>https://ukodus.now/play/?s=ABCD1234</link>"));
+        assert_eq!(xml.matches("<item>").count(), 1);
+    }
+}
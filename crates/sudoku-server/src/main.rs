@@ -0,0 +1,31 @@
+mod api;
+mod feed;
+
+use axum::routing::{get, post};
+use axum::Router;
+
+/// Port used when `PORT` isn't set
+const DEFAULT_PORT: u16 = 8080;
+
+fn router() -> Router {
+    Router::new()
+        .route("/generate", post(api::generate))
+        .route("/solve", post(api::solve))
+        .route("/rate", post(api::rate))
+        .route("/hint", post(api::hint))
+        .route("/validate", post(api::validate))
+        .route("/daily/feed", get(api::daily_feed))
+        .route("/daily/{date}", get(api::daily))
+        .route("/image/{puzzle}", get(api::image))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let port = std::env::var("PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    eprintln!("sudoku-server listening on {}", listener.local_addr()?);
+    axum::serve(listener, router()).await
+}
@@ -0,0 +1,81 @@
+//! Named snapshots of the board and its notes, kept apart from undo, so a
+//! player can try one branch of a guess and come back to where they were.
+
+use serde::{Deserialize, Serialize};
+use sudoku_core::{BitSet, Grid, Position};
+
+/// The values and notes of every cell, saved under a name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    /// Digit per cell in reading order, 0 for empty
+    values: Vec<u8>,
+    /// Raw note bits per cell in reading order
+    candidates: Vec<u16>,
+}
+
+impl Bookmark {
+    /// Snapshot `grid` as `name`
+    pub fn capture(name: &str, grid: &Grid) -> Self {
+        let cells = (0..81).map(|i| grid.cell(Position::new(i / 9, i % 9)));
+        Self {
+            name: name.to_string(),
+            values: cells
+                .clone()
+                .map(|cell| cell.value().unwrap_or(0))
+                .collect(),
+            candidates: cells.map(|cell| cell.candidates().as_raw()).collect(),
+        }
+    }
+
+    /// The saved digit at `pos`
+    pub fn value(&self, pos: Position) -> Option<u8> {
+        self.values
+            .get(pos.row * 9 + pos.col)
+            .copied()
+            .filter(|v| (1..=9).contains(v))
+    }
+
+    /// The saved notes at `pos`
+    pub fn candidates(&self, pos: Position) -> BitSet {
+        let raw = self.candidates.get(pos.row * 9 + pos.col).copied();
+        BitSet::from_raw(raw.unwrap_or(0))
+    }
+
+    /// Put the saved values and notes back on `grid`, leaving givens alone
+    pub fn apply(&self, grid: &mut Grid) {
+        for pos in (0..81).map(|i| Position::new(i / 9, i % 9)) {
+            if !grid.cell(pos).is_given() {
+                grid.set_cell_unchecked(pos, self.value(pos));
+                grid.cell_mut(pos).set_candidates(self.candidates(pos));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_bookmark() {
+        let mut grid = Grid::from_string(Tier::Singles.puzzle()).unwrap();
+        let (pos, noted) = (Position::new(0, 2), Position::new(0, 3));
+        grid.set_cell_unchecked(pos, Some(4));
+        grid.cell_mut(noted).set_candidates(BitSet::single(6));
+        let bookmark = Bookmark::capture("try 4", &grid);
+
+        grid.set_cell_unchecked(pos, Some(1));
+        grid.cell_mut(noted).set_candidates(BitSet::empty());
+        bookmark.apply(&mut grid);
+        assert_eq!(grid.get(pos), Some(4));
+        assert_eq!(
+            grid.cell(noted).candidates().as_raw(),
+            BitSet::single(6).as_raw()
+        );
+
+        let saved = serde_json::to_string(&bookmark).unwrap();
+        assert_eq!(serde_json::from_str::<Bookmark>(&saved).unwrap(), bookmark);
+    }
+}
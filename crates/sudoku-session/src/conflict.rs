@@ -0,0 +1,94 @@
+//! Conflicts on the board, graded by how serious they are, so frontends
+//! can style each kind differently and zen mode can keep to rule breaks.
+
+use serde::{Deserialize, Serialize};
+use sudoku_core::{Grid, Position};
+
+/// What's wrong with a cell, most severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// The digit repeats in its row, column or box
+    RuleConflict,
+    /// The digit breaks no rule but isn't the solution's
+    Incorrect,
+    /// An empty cell no digit can fill, or whose notes all clash with
+    /// digits already in its units
+    CandidateContradiction,
+}
+
+/// The conflict at `pos`, if any. Without a solution only the board's own
+/// rules are checked.
+pub fn conflict_at(grid: &Grid, solution: Option<&Grid>, pos: Position) -> Option<ConflictKind> {
+    match grid.get(pos) {
+        Some(value) if repeats(grid, pos, value) => Some(ConflictKind::RuleConflict),
+        Some(value) => solution
+            .and_then(|solution| solution.get(pos))
+            .filter(|&answer| answer != value)
+            .map(|_| ConflictKind::Incorrect),
+        None => {
+            let possible = grid.compute_candidates(pos).as_raw();
+            let notes = grid.cell(pos).candidates().as_raw();
+            (possible == 0 || (notes != 0 && notes & possible == 0))
+                .then_some(ConflictKind::CandidateContradiction)
+        }
+    }
+}
+
+/// Every conflict on the board, in reading order
+pub fn conflicts(grid: &Grid, solution: Option<&Grid>) -> Vec<(Position, ConflictKind)> {
+    (0..81)
+        .map(|i| Position::new(i / 9, i % 9))
+        .filter_map(|pos| conflict_at(grid, solution, pos).map(|kind| (pos, kind)))
+        .collect()
+}
+
+/// Whether `value` at `pos` appears again in the same row, column or box
+fn repeats(grid: &Grid, pos: Position, value: u8) -> bool {
+    let (box_row, box_col) = (pos.row / 3 * 3, pos.col / 3 * 3);
+    (0..9)
+        .flat_map(|i| {
+            [
+                Position::new(pos.row, i),
+                Position::new(i, pos.col),
+                Position::new(box_row + i / 3, box_col + i % 3),
+            ]
+        })
+        .any(|peer| (peer.row, peer.col) != (pos.row, pos.col) && grid.get(peer) == Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_core::BitSet;
+    use sudoku_test_utils::{solution, Tier};
+
+    #[test]
+    fn test_conflict_kinds() {
+        let solution = Grid::from_string(&solution(Tier::Singles)).unwrap();
+        let mut grid = Grid::from_string(Tier::Singles.puzzle()).unwrap();
+
+        grid.set_cell_unchecked(Position::new(0, 2), Some(5));
+        grid.set_cell_unchecked(Position::new(0, 3), Some(2));
+        assert_eq!(
+            conflict_at(&grid, Some(&solution), Position::new(0, 2)),
+            Some(ConflictKind::RuleConflict)
+        );
+        assert_eq!(
+            conflict_at(&grid, Some(&solution), Position::new(0, 3)),
+            Some(ConflictKind::Incorrect)
+        );
+        assert_eq!(conflict_at(&grid, None, Position::new(0, 3)), None);
+
+        // r1c7 can still take 1, 4, 8 or 9, but its only note is 3
+        let pos = Position::new(0, 6);
+        grid.cell_mut(pos).set_candidates(BitSet::single(3));
+        assert_eq!(
+            conflict_at(&grid, None, pos),
+            Some(ConflictKind::CandidateContradiction)
+        );
+        assert!(conflicts(&grid, None)
+            .iter()
+            .all(|&(_, kind)| kind != ConflictKind::Incorrect));
+    }
+}
@@ -0,0 +1,48 @@
+//! Puzzles pasted or saved as text, read the same way by the CLI and the
+//! TUI.
+
+/// Extract a puzzle from free-form text: a bare 81-character line, an `.sdk`
+/// file, or a drawn grid. Lines starting with `#` or `[` are metadata;
+/// `.`, `0` and `_` mark empty cells; other characters are ignored.
+pub fn parse_puzzle_text(text: &str) -> Option<String> {
+    let cells: String = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with(['#', '[']))
+        .flat_map(|line| line.chars())
+        .filter_map(|c| match c {
+            '1'..='9' => Some(c),
+            '.' | '0' | '_' => Some('.'),
+            _ => None,
+        })
+        .take(81)
+        .collect();
+    (cells.len() == 81).then_some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_parse_puzzle_text() {
+        let line = Tier::Singles.puzzle();
+        assert_eq!(parse_puzzle_text(line).as_deref(), Some(line));
+        let zeros = line.replace('.', "0");
+        assert_eq!(parse_puzzle_text(&zeros).as_deref(), Some(line));
+
+        // A drawn grid with metadata, borders and separators
+        let mut drawn = String::from("# from a book\n[Puzzle]\n");
+        for (r, row) in line.as_bytes().chunks(9).enumerate() {
+            if r > 0 && r % 3 == 0 {
+                drawn.push_str("------+-------+------\n");
+            }
+            let row = std::str::from_utf8(row).unwrap().replace('.', "_");
+            drawn.push_str(&format!("{} | {} | {}\n", &row[..3], &row[3..6], &row[6..]));
+        }
+        assert_eq!(parse_puzzle_text(&drawn).as_deref(), Some(line));
+
+        assert_eq!(parse_puzzle_text(&line[..80]), None);
+        assert_eq!(parse_puzzle_text(""), None);
+    }
+}
@@ -0,0 +1,45 @@
+//! Game rules shared by the frontends, so web, mobile and terminal play
+//! the same way. [`GameSettings`] bundles the assists a player can turn on
+//! and how mistakes are treated; frontends save it with the game.
+//! [`GameSession`] keeps a game's board, undo history, [`Bookmark`]s,
+//! counts, clock and [`MinuteMetrics`] for a post-game timeline.
+//! [`ConflictKind`] grades what's wrong with a cell, and [`Score`] keeps
+//! points comparable across platforms. [`GameMode`] sets how a game is won
+//! and which leaderboard it counts for. [`SeSearch`] looks for a puzzle
+//! near a target SE rating with progress and cancellation, and
+//! [`solve_path`] lists every step from a board to its solution;
+//! [`rate_se_by_path`] rates along it up to a number of steps.
+//! [`find_technique`] finds trainer positions for a technique, and
+//! [`parse_puzzle_text`] reads a puzzle out of pasted or saved text.
+
+mod bookmark;
+mod conflict;
+mod import;
+mod metrics;
+mod mode;
+mod path;
+mod scoring;
+mod search;
+mod session;
+mod settings;
+mod trainer;
+
+pub use bookmark::Bookmark;
+pub use conflict::{conflict_at, conflicts, ConflictKind};
+pub use import::parse_puzzle_text;
+pub use metrics::MinuteMetrics;
+pub use mode::{GameMode, Outcome, CHALLENGE_HINTS, DEFAULT_TARGET_SECS};
+pub use path::{
+    apply_hint, hint_within, is_within, rate_se_by_path, solve_path, solve_path_limited,
+    solve_path_within, RatingResult, SolvePath,
+};
+pub use scoring::{
+    difficulty_multiplier, par_secs, time_bonus, Score, BASE_POINTS, COMBO_STEP, HINT_PENALTY,
+    MAX_COMBO_MULTIPLIER, MISTAKE_PENALTY,
+};
+pub use search::{generate_for_se, SeSearch, SearchProgress, SE_TOLERANCE};
+pub use session::{GameSession, ValueChange};
+pub use settings::{GameSettings, MistakePolicy, DEFAULT_MISTAKE_LIMIT};
+pub use trainer::{
+    find_technique, technique_in, technique_matches, TRAINER_PUZZLES, TRAINER_STEPS,
+};
@@ -0,0 +1,75 @@
+//! Activity per minute of play, so a frontend can draw a post-game
+//! sparkline ("you stalled between minutes 12 and 19") without replaying
+//! its own move log.
+
+use serde::{Deserialize, Serialize};
+
+/// Something the player did that the timeline counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activity {
+    /// A digit placed, right or wrong
+    Placement,
+    /// Notes changed without placing a digit
+    CandidateEdit,
+    /// A wrong digit counted as a mistake
+    Mistake,
+}
+
+/// What happened during one minute of play
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinuteMetrics {
+    /// Minutes of play before this one, from 0
+    pub minute: u32,
+    pub placements: u32,
+    pub candidate_edits: u32,
+    pub mistakes: u32,
+}
+
+impl MinuteMetrics {
+    /// Whether the player did nothing this minute
+    pub fn is_idle(&self) -> bool {
+        self.placements == 0 && self.candidate_edits == 0 && self.mistakes == 0
+    }
+}
+
+/// Count `activity` in the minute containing `at_ms`, adding empty minutes
+/// up to it
+pub(crate) fn record(timeline: &mut Vec<MinuteMetrics>, at_ms: u64, activity: Activity) {
+    let minute = (at_ms / 60_000) as usize;
+    pad(timeline, minute);
+    let bucket = &mut timeline[minute];
+    match activity {
+        Activity::Placement => bucket.placements += 1,
+        Activity::CandidateEdit => bucket.candidate_edits += 1,
+        Activity::Mistake => bucket.mistakes += 1,
+    }
+}
+
+/// Add empty minutes so `timeline` reaches `minute`
+pub(crate) fn pad(timeline: &mut Vec<MinuteMetrics>, minute: usize) {
+    while timeline.len() <= minute {
+        timeline.push(MinuteMetrics {
+            minute: timeline.len() as u32,
+            ..MinuteMetrics::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut timeline = Vec::new();
+        record(&mut timeline, 5_000, Activity::Placement);
+        record(&mut timeline, 150_000, Activity::Mistake);
+        record(&mut timeline, 179_999, Activity::CandidateEdit);
+
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[0].placements, 1);
+        assert!(timeline[1].is_idle());
+        assert_eq!(timeline[2].minute, 2);
+        assert_eq!((timeline[2].mistakes, timeline[2].candidate_edits), (1, 1));
+    }
+}
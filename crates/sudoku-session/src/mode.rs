@@ -0,0 +1,149 @@
+//! Game modes: the rules a game is won or lost by, beyond the puzzle
+//! itself. Each mode has its own leaderboard category so a time-attack
+//! score never ranks against a classic one.
+
+use serde::{Deserialize, Serialize};
+
+/// Time-attack target unless the player picks another
+pub const DEFAULT_TARGET_SECS: u64 = 10 * 60;
+/// Hints allowed in the hint challenge
+pub const CHALLENGE_HINTS: u32 = 3;
+
+/// How a game is played and judged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// Solve at any pace with every assist
+    #[default]
+    Classic,
+    /// Solve before the clock reaches `target_secs`, or lose
+    TimeAttack { target_secs: u64 },
+    /// Solve with at most `max_hints` hints
+    HintChallenge { max_hints: u32 },
+    /// No notes at all
+    Hardcore,
+}
+
+/// Where a game stands under its mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Playing,
+    Won,
+    Lost,
+}
+
+impl GameMode {
+    /// Parse a mode name from a config file or host ("classic",
+    /// "time-attack", "hints" or "hardcore"), with the default target and
+    /// hint allowance
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "classic" => Some(Self::Classic),
+            "time_attack" => Some(Self::TimeAttack {
+                target_secs: DEFAULT_TARGET_SECS,
+            }),
+            "hints" | "hint_challenge" => Some(Self::HintChallenge {
+                max_hints: CHALLENGE_HINTS,
+            }),
+            "hardcore" => Some(Self::Hardcore),
+            _ => None,
+        }
+    }
+
+    /// The leaderboard a win in this mode is ranked on
+    pub fn leaderboard_category(self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::TimeAttack { .. } => "time_attack",
+            Self::HintChallenge { .. } => "hint_challenge",
+            Self::Hardcore => "hardcore",
+        }
+    }
+
+    /// Menu label
+    pub fn label(self) -> String {
+        match self {
+            Self::Classic => "Classic".to_string(),
+            Self::TimeAttack { target_secs } => {
+                format!("Time Attack {}:{:02}", target_secs / 60, target_secs % 60)
+            }
+            Self::HintChallenge { max_hints } => format!("{}-Hint Challenge", max_hints),
+            Self::Hardcore => "Hardcore".to_string(),
+        }
+    }
+
+    /// Short label for leaderboard rows; empty for classic
+    pub fn short_label(self) -> &'static str {
+        match self {
+            Self::Classic => "",
+            Self::TimeAttack { .. } => "Attack",
+            Self::HintChallenge { .. } => "Hints",
+            Self::Hardcore => "Hard",
+        }
+    }
+
+    /// The next mode in a menu's cycle
+    pub fn next(self) -> Self {
+        match self {
+            Self::Classic => Self::TimeAttack {
+                target_secs: DEFAULT_TARGET_SECS,
+            },
+            Self::TimeAttack { .. } => Self::HintChallenge {
+                max_hints: CHALLENGE_HINTS,
+            },
+            Self::HintChallenge { .. } => Self::Hardcore,
+            Self::Hardcore => Self::Classic,
+        }
+    }
+
+    /// Whether the player may write notes
+    pub fn allows_notes(self) -> bool {
+        self != Self::Hardcore
+    }
+
+    /// Hints allowed in one game, if limited
+    pub fn hint_limit(self) -> Option<u32> {
+        match self {
+            Self::HintChallenge { max_hints } => Some(max_hints),
+            _ => None,
+        }
+    }
+
+    /// Play time after which the game is lost, if any
+    pub fn time_limit_ms(self) -> Option<u64> {
+        match self {
+            Self::TimeAttack { target_secs } => Some(target_secs * 1000),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modes() {
+        assert_eq!(
+            GameMode::from_name("Time-Attack").unwrap().time_limit_ms(),
+            Some(600_000)
+        );
+        assert_eq!(GameMode::from_name("hints").unwrap().hint_limit(), Some(3));
+        assert!(!GameMode::Hardcore.allows_notes());
+        assert_eq!(GameMode::from_name("zen"), None);
+
+        let mut mode = GameMode::Classic;
+        let mut categories = Vec::new();
+        for _ in 0..4 {
+            categories.push(mode.leaderboard_category());
+            mode = mode.next();
+        }
+        assert_eq!(mode, GameMode::Classic);
+        categories.dedup();
+        assert_eq!(categories.len(), 4);
+
+        let saved = serde_json::to_string(&GameMode::TimeAttack { target_secs: 300 }).unwrap();
+        assert_eq!(saved, r#"{"time_attack":{"target_secs":300}}"#);
+    }
+}
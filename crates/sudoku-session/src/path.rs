@@ -0,0 +1,239 @@
+//! The solver's whole route through a puzzle, for walkthroughs that show
+//! every step rather than asking for one hint at a time. Hints and paths
+//! can be capped at a difficulty level, to ask whether a puzzle needs
+//! anything harder. Paths and ratings can also be cut off after a number of
+//! steps. That caps how many times the solver is asked for a hint, not how
+//! long one hint takes: a forcing-chain search inside sudoku-core can still
+//! run long (see docs/core-requests.md).
+
+use sudoku_core::{Difficulty, Grid, Hint, HintType, Solver};
+
+/// Every step the solver takes, easiest technique first. Each [`Hint`]
+/// carries its technique, placement or eliminations, explanation and
+/// proof.
+#[derive(Debug, Clone)]
+pub struct SolvePath {
+    pub steps: Vec<Hint>,
+    /// False if the path stops because no (allowed) technique applies
+    pub solved: bool,
+}
+
+/// A rating that may have stopped short of the solution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RatingResult {
+    /// SE rating of the hardest step on the way to the solution
+    Rated(f32),
+    /// The step cap was reached, or no technique applied, after `steps`
+    /// steps; `hardest_se` is the hardest of those
+    Incomplete { steps: usize, hardest_se: f32 },
+}
+
+/// The path from `grid` to the solution, starting from full candidates
+pub fn solve_path(grid: &Grid) -> SolvePath {
+    path_while(grid, usize::MAX, |solver, grid| solver.get_hint(grid))
+}
+
+/// The path, stopping after at most `max_steps` steps
+pub fn solve_path_limited(grid: &Grid, max_steps: usize) -> SolvePath {
+    path_while(grid, max_steps, |solver, grid| solver.get_hint(grid))
+}
+
+/// Rate `grid` by the hardest step of its solve path, walking at most
+/// `max_steps` steps. Each step is one unbounded `Solver::get_hint` call,
+/// so this limits the number of calls rather than the time taken.
+pub fn rate_se_by_path(grid: &Grid, max_steps: usize) -> RatingResult {
+    let path = solve_path_limited(grid, max_steps);
+    let hardest_se = path
+        .steps
+        .iter()
+        .map(|hint| hint.technique.se_rating())
+        .fold(0.0, f32::max);
+    if path.solved {
+        RatingResult::Rated(hardest_se)
+    } else {
+        RatingResult::Incomplete {
+            steps: path.steps.len(),
+            hardest_se,
+        }
+    }
+}
+
+/// The path using only techniques no harder than `max_level`; it stops
+/// where every step left needs more, so `solved` says whether the puzzle
+/// can be solved at that level
+pub fn solve_path_within(grid: &Grid, max_level: Difficulty) -> SolvePath {
+    path_while(grid, usize::MAX, |solver, grid| {
+        easiest_within(solver, grid, max_level)
+    })
+}
+
+/// The easiest hint for `grid` as it stands whose technique is no harder
+/// than `max_level`; None if every step left needs a harder technique (or
+/// none applies)
+pub fn hint_within(grid: &Grid, max_level: Difficulty) -> Option<Hint> {
+    easiest_within(&Solver::new(), grid, max_level)
+}
+
+/// sudoku-core can't be told to stop at a level (see docs/core-requests.md),
+/// so a step above it is checked against every finding rather than taken to
+/// mean nothing easier exists
+fn easiest_within(solver: &Solver, grid: &Grid, max_level: Difficulty) -> Option<Hint> {
+    let hint = solver.get_hint(grid)?;
+    if is_within(&hint, max_level) {
+        return Some(hint);
+    }
+    solver
+        .find_all(grid)
+        .into_iter()
+        .filter(|hint| is_within(hint, max_level))
+        .min_by(|a, b| a.technique.se_rating().total_cmp(&b.technique.se_rating()))
+}
+
+/// Whether the hint's technique rates inside `level`'s SE range or below
+pub fn is_within(hint: &Hint, level: Difficulty) -> bool {
+    hint.technique.se_rating() <= level.se_range().1
+}
+
+fn path_while(
+    grid: &Grid,
+    max_steps: usize,
+    next: impl Fn(&Solver, &Grid) -> Option<Hint>,
+) -> SolvePath {
+    let solver = Solver::new();
+    let mut grid = grid.deep_clone();
+    grid.recalculate_candidates();
+    let mut steps = Vec::new();
+    while !grid.is_complete() && steps.len() < max_steps {
+        let Some(hint) = next(&solver, &grid) else {
+            break;
+        };
+        // A step that changes nothing would repeat forever
+        if !apply_hint(&mut grid, &hint) {
+            break;
+        }
+        steps.push(hint);
+    }
+    SolvePath {
+        solved: grid.is_complete(),
+        steps,
+    }
+}
+
+/// Carry out a hint on `grid`; false if it changed nothing
+pub fn apply_hint(grid: &mut Grid, hint: &Hint) -> bool {
+    match &hint.hint_type {
+        HintType::SetValue { pos, value } => {
+            grid.set_cell_unchecked(*pos, Some(*value));
+            grid.update_candidates_after_move(*pos, *value);
+            true
+        }
+        HintType::EliminateCandidates { pos, values } => {
+            let before = grid.cell(*pos).candidates().as_raw();
+            for &v in values {
+                grid.cell_mut(*pos).remove_candidate(v);
+            }
+            grid.cell(*pos).candidates().as_raw() != before
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use sudoku_core::Position;
+    use sudoku_test_utils::strategy::{eliminations, puzzles};
+    use sudoku_test_utils::{
+        assert_hint_sound, assert_solve_path_sound, fixture, solution, solution_digits, Tier,
+    };
+
+    #[test]
+    fn test_solve_path() {
+        let solution = solution(Tier::Singles);
+        let puzzle = format!("...{}", &solution[3..]);
+        let path = solve_path(&Grid::from_string(&puzzle).unwrap());
+        assert!(path.solved);
+        assert_eq!(path.steps.len(), 3);
+        assert!(path
+            .steps
+            .iter()
+            .all(|step| matches!(step.hint_type, HintType::SetValue { .. })));
+
+        let empty = solve_path(&Grid::from_string(&".".repeat(81)).unwrap());
+        assert!(!empty.solved);
+    }
+
+    #[test]
+    fn test_rate_se_by_path() {
+        let solution = solution(Tier::Singles);
+        let grid = Grid::from_string(&format!("...{}", &solution[3..])).unwrap();
+        assert!(matches!(rate_se_by_path(&grid, 10), RatingResult::Rated(_)));
+        let cut = solve_path_limited(&grid, 1);
+        assert_eq!(cut.steps.len(), 1);
+        assert!(!cut.solved);
+        assert!(matches!(
+            rate_se_by_path(&grid, 1),
+            RatingResult::Incomplete { steps: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_solve_path_within() {
+        // Singles, intersections and subsets get stuck; it needs an X-Wing
+        let grid = fixture(Tier::Fish);
+        let full = solve_path(&grid);
+        assert!(full.solved);
+        let hardest = full
+            .steps
+            .iter()
+            .map(|step| step.technique.se_rating())
+            .fold(0.0, f32::max);
+        let (below, at_or_above): (Vec<Difficulty>, Vec<Difficulty>) = Difficulty::all_levels()
+            .iter()
+            .copied()
+            .partition(|level| level.se_range().1 < hardest);
+        assert!(!below.is_empty());
+
+        for level in below {
+            let capped = solve_path_within(&grid, level);
+            assert!(!capped.solved);
+            assert!(capped.steps.iter().all(|step| is_within(step, level)));
+            // Where it stopped, every step left is too hard for the level
+            let mut stuck = grid.deep_clone();
+            stuck.recalculate_candidates();
+            for step in &capped.steps {
+                apply_hint(&mut stuck, step);
+            }
+            assert!(hint_within(&stuck, level).is_none());
+        }
+        for level in at_or_above {
+            assert!(solve_path_within(&grid, level).solved);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        /// Whatever wrong candidates are struck first, every step is sound
+        #[test]
+        fn prop_steps_sound(
+            grid in puzzles(&[Difficulty::Beginner, Difficulty::Easy]),
+            struck in eliminations(40),
+        ) {
+            let solution = solution_digits(&grid);
+            for step in solve_path(&grid).steps {
+                assert_hint_sound(&solution, &step);
+            }
+
+            let mut grid = grid.deep_clone();
+            grid.recalculate_candidates();
+            for (cell, digit) in struck {
+                let pos = Position::new(cell / 9, cell % 9);
+                if grid.get(pos).is_none() && digit != solution[cell] {
+                    grid.cell_mut(pos).remove_candidate(digit);
+                }
+            }
+            assert_solve_path_sound(&grid, 60);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Points for a game, worked out the same way on every frontend so scores
+//! compare across platforms. Each cell's correct digit earns base points,
+//! scaled by the puzzle's difficulty and by the combo: the run of correct
+//! digits before it in this game (nothing to do with daily-puzzle streaks).
+//! Hints and mistakes cost points and break the combo, and a finished
+//! puzzle adds a bonus for every second under par.
+
+use serde::{Deserialize, Serialize};
+use sudoku_core::Difficulty;
+
+/// Points for a correct digit before multipliers
+pub const BASE_POINTS: u32 = 10;
+pub const HINT_PENALTY: u32 = 50;
+pub const MISTAKE_PENALTY: u32 = 25;
+/// Correct digits in a row needed to raise the combo multiplier by one
+pub const COMBO_STEP: u32 = 5;
+pub const MAX_COMBO_MULTIPLIER: u32 = 4;
+
+/// How much a puzzle's difficulty scales its points
+pub fn difficulty_multiplier(difficulty: Difficulty) -> u32 {
+    match difficulty {
+        Difficulty::Beginner => 1,
+        Difficulty::Easy => 2,
+        Difficulty::Medium => 3,
+        Difficulty::Intermediate => 4,
+        Difficulty::Hard => 5,
+        Difficulty::Expert => 6,
+        Difficulty::Master => 8,
+        Difficulty::Extreme => 10,
+    }
+}
+
+/// Time within which a finished puzzle earns a bonus
+pub fn par_secs(difficulty: Difficulty) -> u64 {
+    match difficulty {
+        Difficulty::Beginner => 5 * 60,
+        Difficulty::Easy => 8 * 60,
+        Difficulty::Medium => 12 * 60,
+        Difficulty::Intermediate => 15 * 60,
+        Difficulty::Hard => 20 * 60,
+        Difficulty::Expert => 30 * 60,
+        Difficulty::Master => 40 * 60,
+        Difficulty::Extreme => 60 * 60,
+    }
+}
+
+/// Bonus for finishing in `elapsed_secs`: a point per second under par,
+/// scaled by difficulty
+pub fn time_bonus(difficulty: Difficulty, elapsed_secs: u64) -> u32 {
+    let under_par = par_secs(difficulty).saturating_sub(elapsed_secs) as u32;
+    under_par * difficulty_multiplier(difficulty)
+}
+
+/// Points earned and lost so far in one game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Score {
+    /// From correct digits, multipliers included
+    pub placement_points: u32,
+    /// Correct digits in a row since the last hint or mistake. Saves from
+    /// before the rename call it `streak`.
+    #[serde(alias = "streak")]
+    pub combo: u32,
+    pub hint_penalty: u32,
+    pub mistake_penalty: u32,
+    /// Cells whose digit has earned its points, one bit each in reading
+    /// order, so clearing and placing again earns nothing
+    scored_cells: u128,
+}
+
+impl Score {
+    /// Multiplier the current combo gives the next correct digit
+    pub fn combo_multiplier(&self) -> u32 {
+        (1 + self.combo / COMBO_STEP).min(MAX_COMBO_MULTIPLIER)
+    }
+
+    /// A correct digit in cell `index` (0-80, reading order). Only a cell's
+    /// first correct digit scores and extends the combo.
+    pub fn record_correct(&mut self, index: usize, difficulty: Difficulty) {
+        let bit = 1u128 << index;
+        if self.scored_cells & bit != 0 {
+            return;
+        }
+        self.scored_cells |= bit;
+        self.placement_points +=
+            BASE_POINTS * difficulty_multiplier(difficulty) * self.combo_multiplier();
+        self.combo += 1;
+    }
+
+    pub fn record_mistake(&mut self) {
+        self.mistake_penalty += MISTAKE_PENALTY;
+        self.combo = 0;
+    }
+
+    pub fn record_hint(&mut self) {
+        self.hint_penalty += HINT_PENALTY;
+        self.combo = 0;
+    }
+
+    /// The score with `bonus` added, never below zero
+    pub fn total(&self, bonus: u32) -> u32 {
+        (self.placement_points + bonus).saturating_sub(self.hint_penalty + self.mistake_penalty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let mut score = Score::default();
+        for index in 0..6 {
+            score.record_correct(index, Difficulty::Easy);
+        }
+        // Five digits at 20 points, then the combo doubles the sixth
+        assert_eq!(score.placement_points, 5 * 20 + 40);
+
+        score.record_correct(0, Difficulty::Easy);
+        assert_eq!((score.placement_points, score.combo), (140, 6));
+
+        score.record_mistake();
+        score.record_hint();
+        assert_eq!((score.combo, score.total(0)), (0, 65));
+        assert_eq!(time_bonus(Difficulty::Easy, 8 * 60 - 10), 20);
+
+        let saved = serde_json::to_string(&score).unwrap();
+        assert_eq!(serde_json::from_str::<Score>(&saved).unwrap(), score);
+        let old: Score = serde_json::from_str(r#"{"streak":3}"#).unwrap();
+        assert_eq!(old.combo, 3);
+    }
+}
@@ -0,0 +1,149 @@
+//! Searching for a puzzle near a target SE rating. High targets can take
+//! many seconds, so the search goes one puzzle at a time: frontends show
+//! progress between attempts and stop whenever the player cancels.
+
+use sudoku_core::{Difficulty, Generator, Grid, Solver};
+
+/// A puzzle within this much of the target ends the search
+pub const SE_TOLERANCE: f32 = 0.2;
+
+/// How far a search has got
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchProgress {
+    /// Puzzles generated and rated so far
+    pub attempts: u32,
+    /// SE rating of the closest puzzle so far
+    pub best_se: Option<f32>,
+}
+
+/// A search for a puzzle rated close to `target`, run by [`SeSearch::step`]
+pub struct SeSearch {
+    target: f32,
+    /// Level whose SE range holds the target, to generate at
+    difficulty: Difficulty,
+    generator: Generator,
+    solver: Solver,
+    attempts: u32,
+    best: Option<(Grid, f32)>,
+}
+
+impl SeSearch {
+    pub fn new(target: f32) -> Self {
+        let levels = Difficulty::all_levels();
+        let difficulty = levels
+            .iter()
+            .copied()
+            .find(|level| target <= level.se_range().1)
+            .unwrap_or(levels[levels.len() - 1]);
+        Self {
+            target,
+            difficulty,
+            generator: Generator::new(),
+            solver: Solver::new(),
+            attempts: 0,
+            best: None,
+        }
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Generate and rate one more puzzle, keeping it if it's the closest
+    /// yet; returns whether the search is done
+    pub fn step(&mut self) -> bool {
+        if self.is_done() {
+            return true;
+        }
+        let grid = self.generator.generate(self.difficulty);
+        let se = self.solver.rate_se(&grid);
+        self.attempts += 1;
+        if self
+            .best_se()
+            .is_none_or(|best| self.distance(se) < self.distance(best))
+        {
+            self.best = Some((grid, se));
+        }
+        self.is_done()
+    }
+
+    /// Whether the closest puzzle is within [`SE_TOLERANCE`] of the target
+    pub fn is_done(&self) -> bool {
+        self.best_se()
+            .is_some_and(|se| self.distance(se) <= SE_TOLERANCE)
+    }
+
+    pub fn progress(&self) -> SearchProgress {
+        SearchProgress {
+            attempts: self.attempts,
+            best_se: self.best_se(),
+        }
+    }
+
+    /// The closest puzzle so far and its SE rating
+    pub fn best(&self) -> Option<(&Grid, f32)> {
+        self.best.as_ref().map(|(grid, se)| (grid, *se))
+    }
+
+    pub fn into_best(self) -> Option<(Grid, f32)> {
+        self.best
+    }
+
+    fn best_se(&self) -> Option<f32> {
+        self.best.as_ref().map(|&(_, se)| se)
+    }
+
+    fn distance(&self, se: f32) -> f32 {
+        (se - self.target).abs()
+    }
+}
+
+/// Search for up to `max_attempts` puzzles, calling `on_progress` after
+/// each; it returns false to cancel. The closest puzzle found and its SE
+/// rating, or None if cancelled.
+pub fn generate_for_se(
+    target: f32,
+    max_attempts: u32,
+    mut on_progress: impl FnMut(SearchProgress) -> bool,
+) -> Option<(Grid, f32)> {
+    let mut search = SeSearch::new(target);
+    while search.progress().attempts < max_attempts {
+        let done = search.step();
+        if !on_progress(search.progress()) {
+            return None;
+        }
+        if done {
+            break;
+        }
+    }
+    search.into_best()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_progress_and_cancel() {
+        let mut reports = Vec::new();
+        let found = generate_for_se(2.0, 3, |progress| {
+            reports.push(progress);
+            true
+        });
+        let (_, se) = found.unwrap();
+        assert!(!reports.is_empty() && reports.len() <= 3);
+        assert!(reports
+            .iter()
+            .enumerate()
+            .all(|(i, progress)| progress.attempts == i as u32 + 1));
+        assert_eq!(reports.last().unwrap().best_se, Some(se));
+
+        let mut calls = 0;
+        let cancelled = generate_for_se(99.0, 10, |_| {
+            calls += 1;
+            false
+        });
+        assert!(cancelled.is_none());
+        assert_eq!(calls, 1);
+    }
+}
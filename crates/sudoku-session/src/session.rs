@@ -0,0 +1,546 @@
+//! One game's board and bookkeeping: undo and redo, bookmarks, mistakes,
+//! hints used, the clock, activity per minute and completion. Frontends
+//! keep their own input and drawing and hand every change to the board to
+//! a [`GameSession`].
+
+use crate::metrics::{self, Activity, MinuteMetrics};
+use crate::scoring::{self, Score};
+use crate::{conflict, Bookmark, ConflictKind, GameMode, GameSettings, Outcome};
+use sudoku_core::{BitSet, Difficulty, Grid, Position};
+
+/// A cell as it was before an edit
+type Snapshot = (Position, Option<u8>, BitSet);
+
+/// A cell's value changed by an undo or redo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueChange {
+    pub pos: Position,
+    pub from: Option<u8>,
+    pub to: Option<u8>,
+}
+
+/// Time played, in milliseconds from whatever clock the frontend has
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Clock {
+    banked_ms: u64,
+    running_since: Option<u64>,
+    /// Latest time the frontend passed in, to date activity by
+    now_ms: u64,
+}
+
+/// A game in progress
+#[derive(Debug, Clone)]
+pub struct GameSession {
+    grid: Grid,
+    solution: Grid,
+    settings: GameSettings,
+    /// Each step restores one or more cells together
+    undo_stack: Vec<Vec<Snapshot>>,
+    redo_stack: Vec<Vec<Snapshot>>,
+    mistakes: usize,
+    hints_used: usize,
+    clock: Clock,
+    difficulty: Difficulty,
+    score: Score,
+    mode: GameMode,
+    bookmarks: Vec<Bookmark>,
+    /// Activity per minute of play, oldest first
+    timeline: Vec<MinuteMetrics>,
+}
+
+fn all_cells() -> impl Iterator<Item = Position> {
+    (0..81).map(|i| Position::new(i / 9, i % 9))
+}
+
+impl GameSession {
+    /// A game on `grid`, whose givens are marked, with its solution. The
+    /// clock is stopped until [`start_clock`](Self::start_clock).
+    pub fn new(grid: Grid, solution: Grid) -> Self {
+        Self {
+            grid,
+            solution,
+            settings: GameSettings::default(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            mistakes: 0,
+            hints_used: 0,
+            clock: Clock::default(),
+            difficulty: Difficulty::Medium,
+            score: Score::default(),
+            mode: GameMode::default(),
+            bookmarks: Vec::new(),
+            timeline: Vec::new(),
+        }
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// The difficulty points are scaled by; Medium unless set
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// The board for changes that aren't undo steps, such as filling every
+    /// cell's notes at once
+    pub fn grid_mut(&mut self) -> &mut Grid {
+        &mut self.grid
+    }
+
+    pub fn solution(&self) -> &Grid {
+        &self.solution
+    }
+
+    pub fn settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    /// Change the assists. Turning auto-candidates on fills every empty
+    /// cell's notes, unless the mode forbids notes.
+    pub fn set_settings(&mut self, mut settings: GameSettings) {
+        settings.auto_candidates &= self.mode.allows_notes();
+        if settings.auto_candidates && !self.settings.auto_candidates {
+            self.grid.recalculate_candidates();
+        }
+        self.settings = settings;
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Play under `mode`. A mode without notes wipes any on the board and
+    /// turns auto-candidates off.
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.mode = mode;
+        if !mode.allows_notes() {
+            self.settings.auto_candidates = false;
+            self.grid.clear_all_candidates();
+        }
+    }
+
+    /// Whether the mode leaves a hint to use
+    pub fn can_hint(&self) -> bool {
+        self.mode
+            .hint_limit()
+            .is_none_or(|limit| self.hints_used < limit as usize)
+    }
+
+    /// Whether a timed mode's clock has run out as of `now_ms`
+    pub fn is_out_of_time(&self, now_ms: u64) -> bool {
+        self.mode
+            .time_limit_ms()
+            .is_some_and(|limit| self.elapsed_ms(now_ms) >= limit)
+    }
+
+    /// Won once the board is complete; lost on too many mistakes or, in a
+    /// timed mode, when the clock runs out first
+    pub fn outcome(&self, now_ms: u64) -> Outcome {
+        if self.is_complete() {
+            Outcome::Won
+        } else if self.is_lost() || self.is_out_of_time(now_ms) {
+            Outcome::Lost
+        } else {
+            Outcome::Playing
+        }
+    }
+
+    pub fn mistakes(&self) -> usize {
+        self.mistakes
+    }
+
+    pub fn hints_used(&self) -> usize {
+        self.hints_used
+    }
+
+    /// Carry over the counts of a saved game
+    pub fn restore_counts(&mut self, mistakes: usize, hints_used: usize) {
+        self.mistakes = mistakes;
+        self.hints_used = hints_used;
+    }
+
+    /// Count a hint the player asked for
+    pub fn record_hint(&mut self) {
+        self.hints_used += 1;
+        self.score.record_hint();
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    /// Carry over the score of a saved game
+    pub fn restore_score(&mut self, score: Score) {
+        self.score = score;
+    }
+
+    /// Points so far; a finished puzzle adds its time bonus as of `now_ms`
+    pub fn current_score(&self, now_ms: u64) -> u32 {
+        let bonus = if self.is_complete() {
+            scoring::time_bonus(self.difficulty, self.elapsed_ms(now_ms) / 1000)
+        } else {
+            0
+        };
+        self.score.total(bonus)
+    }
+
+    /// Mistakes left before the game is lost; None without a limit
+    pub fn mistakes_left(&self) -> Option<usize> {
+        self.settings.mistakes_left(self.mistakes)
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.settings.is_lost(self.mistakes)
+    }
+
+    /// Every cell filled with no conflicts
+    pub fn is_complete(&self) -> bool {
+        self.grid.is_complete() && self.grid.validate().is_valid
+    }
+
+    /// Whether a placed digit disagrees with the solution
+    pub fn is_wrong(&self, pos: Position) -> bool {
+        self.grid
+            .get(pos)
+            .is_some_and(|v| self.solution.get(pos) != Some(v))
+    }
+
+    /// The conflict at `pos`, if the settings show its kind
+    pub fn conflict(&self, pos: Position) -> Option<ConflictKind> {
+        conflict::conflict_at(&self.grid, Some(&self.solution), pos)
+            .filter(|&kind| self.settings.shows(kind))
+    }
+
+    /// Every conflict the settings show, in reading order
+    pub fn conflicts(&self) -> Vec<(Position, ConflictKind)> {
+        conflict::conflicts(&self.grid, Some(&self.solution))
+            .into_iter()
+            .filter(|&(_, kind)| self.settings.shows(kind))
+            .collect()
+    }
+
+    /// Place `value` at `pos` as one undo step, scoring it, or counting a
+    /// mistake if it's wrong and the settings check. Notes are refilled
+    /// (auto-candidates) or pruned from the cell's peers (smart notes).
+    /// Returns whether the digit is right, or None for a given or a cell
+    /// that already holds `value`; neither changes the board, the undo
+    /// history, the score or the mistake count.
+    pub fn place(&mut self, pos: Position, value: u8) -> Option<bool> {
+        let cell = self.grid.cell(pos);
+        if cell.is_given() || cell.value() == Some(value) {
+            return None;
+        }
+        let correct = self.solution.get(pos) == Some(value);
+        if correct {
+            self.score
+                .record_correct(pos.row * 9 + pos.col, self.difficulty);
+        } else if self.settings.checks_mistakes() {
+            self.mistakes += 1;
+            self.score.record_mistake();
+            self.record_activity(Activity::Mistake);
+        }
+        self.record_activity(Activity::Placement);
+        let settings = self.settings;
+        self.edit(|grid| {
+            grid.set_cell_unchecked(pos, Some(value));
+            if settings.auto_candidates {
+                grid.recalculate_candidates();
+            } else if settings.smart_notes {
+                grid.update_candidates_after_move(pos, value);
+            }
+        });
+        Some(correct)
+    }
+
+    /// Empty a cell as one undo step. Returns the digit removed, if any.
+    pub fn clear(&mut self, pos: Position) -> Option<u8> {
+        let cell = self.grid.cell(pos);
+        let old = cell.value().filter(|_| !cell.is_given())?;
+        let auto_candidates = self.settings.auto_candidates;
+        self.edit(|grid| {
+            grid.set_cell_unchecked(pos, None);
+            if auto_candidates {
+                grid.recalculate_candidates();
+            }
+        });
+        Some(old)
+    }
+
+    /// Make any change to the board as one undo step; every cell it touches
+    /// is restored together. Returns whether anything changed.
+    pub fn edit(&mut self, change: impl FnOnce(&mut Grid)) -> bool {
+        let before: Vec<Snapshot> = all_cells()
+            .map(|pos| {
+                let cell = self.grid.cell(pos);
+                (pos, cell.value(), cell.candidates())
+            })
+            .collect();
+        change(&mut self.grid);
+        let step: Vec<Snapshot> = before
+            .into_iter()
+            .filter(|&(pos, value, candidates)| {
+                let cell = self.grid.cell(pos);
+                cell.value() != value || cell.candidates().as_raw() != candidates.as_raw()
+            })
+            .collect();
+        if step.is_empty() {
+            return false;
+        }
+        if step
+            .iter()
+            .all(|&(pos, value, _)| self.grid.get(pos) == value)
+        {
+            self.record_activity(Activity::CandidateEdit);
+        }
+        self.undo_stack.push(step);
+        self.redo_stack.clear();
+        true
+    }
+
+    /// Activity per minute of play up to the time last passed in, quiet
+    /// minutes included, for a post-game sparkline
+    pub fn metrics_timeline(&self) -> Vec<MinuteMetrics> {
+        let mut timeline = self.timeline.clone();
+        metrics::pad(&mut timeline, (self.played_ms() / 60_000) as usize);
+        timeline
+    }
+
+    /// Count a notes change made through [`grid_mut`](Self::grid_mut)
+    pub fn record_candidate_edit(&mut self) {
+        self.record_activity(Activity::CandidateEdit);
+    }
+
+    /// Carry over the timeline of a saved game
+    pub fn restore_timeline(&mut self, timeline: Vec<MinuteMetrics>) {
+        self.timeline = timeline;
+    }
+
+    fn record_activity(&mut self, activity: Activity) {
+        let at_ms = self.played_ms();
+        metrics::record(&mut self.timeline, at_ms, activity);
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Save the board and notes as `name`, replacing a bookmark of that name
+    pub fn save_bookmark(&mut self, name: &str) {
+        let bookmark = Bookmark::capture(name, &self.grid);
+        match self.bookmarks.iter_mut().find(|b| b.name == name) {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+    }
+
+    /// Put the board back as bookmark `name` left it, as one undo step.
+    /// Digits it brings back aren't scored or counted as mistakes again.
+    /// Returns the values it changed, or None without such a bookmark.
+    pub fn restore_bookmark(&mut self, name: &str) -> Option<Vec<ValueChange>> {
+        let bookmark = self.bookmarks.iter().find(|b| b.name == name)?.clone();
+        let changes = all_cells()
+            .filter(|&pos| !self.grid.cell(pos).is_given())
+            .map(|pos| ValueChange {
+                pos,
+                from: self.grid.get(pos),
+                to: bookmark.value(pos),
+            })
+            .filter(|change| change.from != change.to)
+            .collect();
+        self.edit(|grid| bookmark.apply(grid));
+        Some(changes)
+    }
+
+    pub fn remove_bookmark(&mut self, name: &str) -> bool {
+        let count = self.bookmarks.len();
+        self.bookmarks.retain(|b| b.name != name);
+        self.bookmarks.len() < count
+    }
+
+    /// Carry over the bookmarks of a saved game
+    pub fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+        self.bookmarks = bookmarks;
+    }
+
+    /// Undo steps recorded so far, for [`merge_steps_since`](Self::merge_steps_since)
+    pub fn undo_depth(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Collapse the steps recorded since the undo stack was `depth` deep
+    /// into one, e.g. for an edit applied to several selected cells
+    pub fn merge_steps_since(&mut self, depth: usize) {
+        if self.undo_stack.len() > depth + 1 {
+            let merged: Vec<Snapshot> = self.undo_stack.drain(depth..).flatten().collect();
+            self.undo_stack.push(merged);
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Undo the last step. Returns the values it changed (empty for a notes
+    /// edit), or None with nothing to undo.
+    pub fn undo(&mut self) -> Option<Vec<ValueChange>> {
+        let step = self.undo_stack.pop()?;
+        let (redo, changes) = self.restore(step);
+        self.redo_stack.push(redo);
+        Some(changes)
+    }
+
+    /// Redo the last undone step, as [`undo`](Self::undo)
+    pub fn redo(&mut self) -> Option<Vec<ValueChange>> {
+        let step = self.redo_stack.pop()?;
+        let (undo, changes) = self.restore(step);
+        self.undo_stack.push(undo);
+        Some(changes)
+    }
+
+    /// Put the cells of `step` back, returning the step that reverses it
+    /// and the values changed
+    fn restore(&mut self, step: Vec<Snapshot>) -> (Vec<Snapshot>, Vec<ValueChange>) {
+        let mut reverse = Vec::with_capacity(step.len());
+        let mut changes = Vec::new();
+        for (pos, value, candidates) in step.into_iter().rev() {
+            let cell = self.grid.cell(pos);
+            reverse.push((pos, cell.value(), cell.candidates()));
+            if cell.value() != value {
+                changes.push(ValueChange {
+                    pos,
+                    from: cell.value(),
+                    to: value,
+                });
+            }
+            self.grid.set_cell_unchecked(pos, value);
+            self.grid.cell_mut(pos).set_candidates(candidates);
+        }
+        changes.reverse();
+        (reverse, changes)
+    }
+
+    /// Start or resume the clock at `now_ms`
+    pub fn start_clock(&mut self, now_ms: u64) {
+        self.clock.now_ms = now_ms;
+        if self.clock.running_since.is_none() {
+            self.clock.running_since = Some(now_ms);
+        }
+    }
+
+    /// Stop the clock at `now_ms`, keeping the time played
+    pub fn pause_clock(&mut self, now_ms: u64) {
+        self.clock.now_ms = now_ms;
+        if let Some(since) = self.clock.running_since.take() {
+            self.clock.banked_ms += now_ms.saturating_sub(since);
+        }
+    }
+
+    pub fn is_clock_running(&self) -> bool {
+        self.clock.running_since.is_some()
+    }
+
+    /// Tell the session the time, so the moves that follow are counted in
+    /// the right minute of [`metrics_timeline`](Self::metrics_timeline)
+    pub fn sync_clock(&mut self, now_ms: u64) {
+        self.clock.now_ms = self.clock.now_ms.max(now_ms);
+    }
+
+    /// Time played as of the latest time passed in
+    fn played_ms(&self) -> u64 {
+        self.elapsed_ms(self.clock.now_ms)
+    }
+
+    /// Time played as of `now_ms`
+    pub fn elapsed_ms(&self, now_ms: u64) -> u64 {
+        let running = self
+            .clock
+            .running_since
+            .map_or(0, |since| now_ms.saturating_sub(since));
+        self.clock.banked_ms + running
+    }
+
+    /// Set the time played, e.g. from a save; a running clock restarts from
+    /// `now_ms`
+    pub fn set_elapsed_ms(&mut self, elapsed_ms: u64, now_ms: u64) {
+        self.clock.now_ms = now_ms;
+        self.clock.banked_ms = elapsed_ms;
+        if self.clock.running_since.is_some() {
+            self.clock.running_since = Some(now_ms);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{fixture, solution, Tier};
+
+    #[test]
+    fn test_session() {
+        let solution = Grid::from_string(&solution(Tier::Singles)).unwrap();
+        let mut session = GameSession::new(fixture(Tier::Singles), solution);
+        let (pos, peer) = (Position::new(0, 2), Position::new(0, 3));
+        let peer_notes = session.grid().cell(peer).candidates().as_raw();
+
+        assert_eq!(session.place(Position::new(0, 0), 1), None);
+        assert_eq!(session.place(pos, 2), Some(false));
+        assert_eq!(session.place(pos, 4), Some(true));
+        assert_eq!(session.mistakes(), 1);
+
+        // The digit already there again is no move at all
+        let (depth, score) = (session.undo_depth(), *session.score());
+        assert_eq!(session.place(pos, 4), None);
+        assert_eq!((session.undo_depth(), *session.score()), (depth, score));
+        assert_eq!(session.mistakes(), 1);
+
+        // Undo puts back the peers' notes too
+        let changes = session.undo().unwrap();
+        assert_eq!(changes[0].to, Some(2));
+        let changes = session.undo().unwrap();
+        assert_eq!((changes[0].from, changes[0].to), (Some(2), None));
+        assert_eq!(session.grid().cell(peer).candidates().as_raw(), peer_notes);
+        assert!(session.redo().is_some() && session.grid().get(pos) == Some(2));
+
+        session.start_clock(1_000);
+        session.pause_clock(4_000);
+        session.start_clock(10_000);
+        assert_eq!(session.elapsed_ms(11_000), 4_000);
+
+        // The redone mistake isn't counted again; the right digit scores once
+        session.undo();
+        session.place(pos, 4);
+        assert_eq!(session.current_score(11_000), 30 - scoring::MISTAKE_PENALTY);
+
+        session.set_mode(GameMode::TimeAttack { target_secs: 5 });
+        assert_eq!(session.outcome(11_000), Outcome::Playing);
+        assert_eq!(session.outcome(12_000), Outcome::Lost);
+        session.set_mode(GameMode::HintChallenge { max_hints: 1 });
+        session.record_hint();
+        assert!(!session.can_hint());
+
+        // A bookmark survives undo and comes back as one step
+        session.save_bookmark("before guess");
+        session.place(peer, 6);
+        session.undo();
+        session.undo();
+        assert_eq!(session.restore_bookmark("before guess").unwrap().len(), 1);
+        assert_eq!(session.grid().get(pos), Some(4));
+        assert!(session.undo().is_some() && session.grid().get(pos).is_none());
+
+        // Moves land in the minute of play they were made in
+        session.sync_clock(70_000);
+        session.place(peer, 6);
+        let timeline = session.metrics_timeline();
+        assert_eq!((timeline[0].placements, timeline[0].mistakes), (4, 1));
+        assert_eq!(timeline[1].placements, 1);
+    }
+}
@@ -0,0 +1,150 @@
+//! Assist settings: how forgiving and how helpful a game is. One bundle,
+//! saved with the game, so a puzzle continued on another device plays the
+//! same way.
+
+use crate::ConflictKind;
+use serde::{Deserialize, Serialize};
+
+/// Mistakes allowed before the game is lost, unless a policy says otherwise
+pub const DEFAULT_MISTAKE_LIMIT: u32 = 3;
+
+/// What happens when a digit doesn't match the solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MistakePolicy {
+    /// Not checked against the solution; only rule conflicts show
+    Ignore,
+    /// Marked and counted, never game over
+    Count,
+    /// Marked and counted; the game is lost at this many. A limit of 0,
+    /// say from an older save or a host, counts as no limit, like `Count`
+    Limit(u32),
+}
+
+impl Default for MistakePolicy {
+    fn default() -> Self {
+        Self::Limit(DEFAULT_MISTAKE_LIMIT)
+    }
+}
+
+impl MistakePolicy {
+    /// A limit of `max` mistakes, or `Count` for 0
+    pub fn limit(max: u32) -> Self {
+        match max {
+            0 => Self::Count,
+            max => Self::Limit(max),
+        }
+    }
+
+    /// Parse a name from a config file or host ("off", "count", or a
+    /// limit such as "3")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "off" | "ignore" => Some(Self::Ignore),
+            "count" | "unlimited" => Some(Self::Count),
+            limit => limit.parse().ok().filter(|&n| n > 0).map(Self::Limit),
+        }
+    }
+}
+
+/// Assists and rules for one game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    /// Keep every empty cell's notes filled with its valid candidates
+    pub auto_candidates: bool,
+    pub mistakes: MistakePolicy,
+    /// Remove a placed digit from the notes of its row, column and box
+    pub smart_notes: bool,
+    /// Shade cells holding the digit under the cursor
+    pub highlight_same_digit: bool,
+    pub show_timer: bool,
+    /// Keep playing with no mistake limit
+    pub zen: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            auto_candidates: false,
+            mistakes: MistakePolicy::default(),
+            smart_notes: true,
+            highlight_same_digit: true,
+            show_timer: true,
+            zen: false,
+        }
+    }
+}
+
+impl GameSettings {
+    /// Mistakes that end the game, if any do
+    pub fn mistake_limit(&self) -> Option<u32> {
+        match self.mistakes {
+            MistakePolicy::Limit(limit) if limit > 0 && !self.zen => Some(limit),
+            _ => None,
+        }
+    }
+
+    /// Whether `mistakes` mistakes have lost the game
+    pub fn is_lost(&self, mistakes: usize) -> bool {
+        self.mistake_limit()
+            .is_some_and(|limit| mistakes >= limit as usize)
+    }
+
+    /// Mistakes left before the game is lost; None without a limit
+    pub fn mistakes_left(&self, mistakes: usize) -> Option<usize> {
+        self.mistake_limit()
+            .map(|limit| (limit as usize).saturating_sub(mistakes))
+    }
+
+    /// Whether wrong digits are marked and counted
+    pub fn checks_mistakes(&self) -> bool {
+        self.mistakes != MistakePolicy::Ignore
+    }
+
+    /// Whether conflicts of this kind are shown; zen shows only rule
+    /// conflicts
+    pub fn shows(&self, kind: ConflictKind) -> bool {
+        match kind {
+            ConflictKind::RuleConflict => true,
+            ConflictKind::Incorrect => self.checks_mistakes() && !self.zen,
+            ConflictKind::CandidateContradiction => !self.zen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mistake_rules() {
+        let settings = GameSettings::default();
+        assert_eq!(settings.mistakes_left(1), Some(2));
+        assert!(!settings.is_lost(2) && settings.is_lost(3));
+
+        let zen = GameSettings {
+            zen: true,
+            ..settings
+        };
+        assert!(!zen.is_lost(10) && zen.mistakes_left(1).is_none());
+        assert!(zen.shows(ConflictKind::RuleConflict) && !zen.shows(ConflictKind::Incorrect));
+
+        assert_eq!(MistakePolicy::from_name("5"), Some(MistakePolicy::Limit(5)));
+        assert_eq!(MistakePolicy::from_name("Off"), Some(MistakePolicy::Ignore));
+        assert_eq!(MistakePolicy::from_name("0"), None);
+
+        // A zero limit never ends the game, however it arrives
+        assert_eq!(MistakePolicy::limit(0), MistakePolicy::Count);
+        assert_eq!(MistakePolicy::limit(4), MistakePolicy::Limit(4));
+        let saved: GameSettings = serde_json::from_str(r#"{"mistakes":{"limit":0}}"#).unwrap();
+        assert_eq!(saved.mistakes, MistakePolicy::Limit(0));
+        assert!(!saved.is_lost(0) && !saved.is_lost(10));
+        assert_eq!(saved.mistake_limit(), None);
+        assert!(saved.shows(ConflictKind::Incorrect));
+
+        // Fields missing from older saves take their defaults
+        let saved: GameSettings = serde_json::from_str(r#"{"zen":true}"#).unwrap();
+        assert_eq!(saved, zen);
+    }
+}
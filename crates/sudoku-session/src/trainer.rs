@@ -0,0 +1,86 @@
+//! Positions for a technique trainer: walk puzzles with the solver until
+//! its next move uses the technique being practised.
+
+use crate::path::apply_hint;
+use sudoku_core::{Difficulty, Generator, Grid, Hint, Solver};
+
+/// Puzzles to try before giving up on finding the technique
+pub const TRAINER_PUZZLES: usize = 6;
+/// Solver steps to walk through each puzzle
+pub const TRAINER_STEPS: usize = 120;
+
+/// Compare technique names loosely ("X-Wing", "x wing" and "xwing" all match)
+pub fn technique_matches(hint: &Hint, name: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    normalize(&hint.technique.to_string()) == normalize(name)
+}
+
+/// Walk `grid` step by step, at most `max_steps`, until the solver's next
+/// move is the named technique; returns that position (with full
+/// candidates) and the finding
+pub fn technique_in(grid: &Grid, name: &str, max_steps: usize) -> Option<(Grid, Hint)> {
+    let solver = Solver::new();
+    let mut grid = grid.deep_clone();
+    grid.recalculate_candidates();
+    for _ in 0..max_steps {
+        let hint = solver.get_hint(&grid)?;
+        if technique_matches(&hint, name) {
+            return Some((grid, hint));
+        }
+        if !apply_hint(&mut grid, &hint) {
+            return None;
+        }
+    }
+    None
+}
+
+/// A position from a fresh puzzle at `difficulty` where the solver's next
+/// move is the named technique; None if [`TRAINER_PUZZLES`] puzzles turn
+/// none up
+pub fn find_technique(name: &str, difficulty: Difficulty) -> Option<(Grid, Hint)> {
+    let mut generator = Generator::new();
+    (0..TRAINER_PUZZLES)
+        .find_map(|_| technique_in(&generator.generate(difficulty), name, TRAINER_STEPS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_core::Position;
+    use sudoku_test_utils::{fixture, Tier};
+
+    #[test]
+    fn test_technique_in() {
+        // Singles run out here and an X-Wing is needed
+        let grid = fixture(Tier::Fish);
+        let (position, hint) = technique_in(&grid, "x wing", TRAINER_STEPS).unwrap();
+        assert!(technique_matches(&hint, "X-Wing") && technique_matches(&hint, "XWING"));
+        assert!(!technique_matches(&hint, "XY-Wing"));
+        // The position carries on from the puzzle, where the technique applies
+        for i in 0..81 {
+            let pos = Position::new(i / 9, i % 9);
+            if let Some(value) = grid.get(pos) {
+                assert_eq!(position.get(pos), Some(value));
+            }
+        }
+        assert!(technique_matches(
+            &Solver::new().get_hint(&position).unwrap(),
+            "X-Wing"
+        ));
+
+        // Cut short before it gets there, or never there at all
+        let before = crate::solve_path(&grid)
+            .steps
+            .iter()
+            .position(|step| technique_matches(step, "X-Wing"))
+            .unwrap();
+        assert!(technique_in(&grid, "X-Wing", before).is_none());
+        assert!(technique_in(&grid, "X-Wing", before + 1).is_some());
+        assert!(technique_in(&grid, "Not A Technique", TRAINER_STEPS).is_none());
+    }
+}
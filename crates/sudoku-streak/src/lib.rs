@@ -0,0 +1,168 @@
+//! Daily-puzzle streaks with the same rules on every frontend. A
+//! [`StreakTracker`] records which days' puzzles were solved and turns that
+//! into the current and best streak; frontends keep it with their player
+//! stats.
+//!
+//! Days are numbered from 1970-01-01 UTC. A streak survives until a full
+//! day passes without a solve. Every [`FREEZE_EVERY`] days of a streak earn
+//! a freeze, and held freezes cover missed days so the streak carries on
+//! at the next solve.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Days of a streak that earn a freeze
+pub const FREEZE_EVERY: u32 = 7;
+/// Freezes held at once
+pub const MAX_FREEZES: u32 = 2;
+
+/// What happened on a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Day {
+    /// The day's puzzle was solved
+    Completed { time_secs: u64 },
+    /// Missed, with a freeze spent to keep the streak
+    Frozen,
+}
+
+/// Solved and frozen days, and the freezes held
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreakTracker {
+    days: BTreeMap<u64, Day>,
+    freezes: u32,
+}
+
+impl StreakTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the puzzle of `day` as solved. Missed days since the last
+    /// recorded one are frozen if enough freezes are held. False if the
+    /// day was already solved.
+    pub fn complete(&mut self, day: u64, time_secs: u64) -> bool {
+        if self.is_completed(day) {
+            return false;
+        }
+        if let Some((&last, _)) = self.days.range(..day).next_back() {
+            let missed = day - last - 1;
+            if missed > 0 && missed <= u64::from(self.freezes) {
+                for frozen in last + 1..day {
+                    self.days.insert(frozen, Day::Frozen);
+                }
+                self.freezes -= missed as u32;
+            }
+        }
+        self.days.insert(day, Day::Completed { time_secs });
+        if self.run_ending(day).is_multiple_of(FREEZE_EVERY) {
+            self.freezes = (self.freezes + 1).min(MAX_FREEZES);
+        }
+        true
+    }
+
+    /// Solved days in the unbroken run of recorded days ending at `day`
+    fn run_ending(&self, day: u64) -> u32 {
+        let mut run = 0;
+        let mut expected = day;
+        for (&d, entry) in self.days.range(..=day).rev() {
+            if d != expected {
+                break;
+            }
+            if matches!(entry, Day::Completed { .. }) {
+                run += 1;
+            }
+            let Some(previous) = d.checked_sub(1) else {
+                break;
+            };
+            expected = previous;
+        }
+        run
+    }
+
+    /// The streak as of `today`: still alive if yesterday was solved, or the
+    /// days missed since can be covered by freezes
+    pub fn current(&self, today: u64) -> u32 {
+        let Some((&last, _)) = self.days.range(..=today).next_back() else {
+            return 0;
+        };
+        let missed = today.saturating_sub(1).saturating_sub(last);
+        if missed <= u64::from(self.freezes) {
+            self.run_ending(last)
+        } else {
+            0
+        }
+    }
+
+    /// Longest streak ever
+    pub fn best(&self) -> u32 {
+        let mut best = 0;
+        let mut run = 0;
+        let mut previous: Option<u64> = None;
+        for (&day, entry) in &self.days {
+            if !matches!(previous, Some(p) if p + 1 == day) {
+                run = 0;
+            }
+            if matches!(entry, Day::Completed { .. }) {
+                run += 1;
+                best = best.max(run);
+            }
+            previous = Some(day);
+        }
+        best
+    }
+
+    /// Freezes held, at most [`MAX_FREEZES`]
+    pub fn freezes(&self) -> u32 {
+        self.freezes
+    }
+
+    pub fn day(&self, day: u64) -> Option<Day> {
+        self.days.get(&day).copied()
+    }
+
+    pub fn is_completed(&self, day: u64) -> bool {
+        matches!(self.day(day), Some(Day::Completed { .. }))
+    }
+
+    /// Every recorded day, oldest first
+    pub fn days(&self) -> impl Iterator<Item = (u64, Day)> + '_ {
+        self.days.iter().map(|(&day, &entry)| (day, entry))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_with_freeze() {
+        let mut tracker = StreakTracker::new();
+        for day in 1..=7 {
+            assert!(tracker.complete(day, 300));
+        }
+        assert!(!tracker.complete(7, 200));
+        assert_eq!((tracker.current(8), tracker.freezes()), (7, 1));
+
+        // Day 8 missed: the freeze keeps the streak through day 9
+        assert_eq!(tracker.current(9), 7);
+        tracker.complete(9, 300);
+        assert_eq!(tracker.day(8), Some(Day::Frozen));
+        assert_eq!((tracker.current(9), tracker.freezes()), (8, 0));
+
+        // Two days missed with no freezes left
+        assert_eq!(tracker.current(12), 0);
+        tracker.complete(12, 300);
+        assert_eq!((tracker.current(12), tracker.best()), (1, 8));
+
+        let json = serde_json::to_string(&tracker).unwrap();
+        assert_eq!(
+            serde_json::from_str::<StreakTracker>(&json).unwrap(),
+            tracker
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! [`SaveBackend`] over HTTP:
+//!
+//! - `GET {base}/saves` lists [`SaveSummary`]s
+//! - `GET {base}/saves/{id}` returns a [`SaveBlob`], or 404
+//! - `PUT {base}/saves/{id}` stores one if `If-Match` holds the stored
+//!   version, and answers 409 with the stored blob otherwise
+
+use crate::{SaveBackend, SaveBlob, SaveSummary, SyncError};
+use std::time::Duration;
+
+pub struct HttpBackend {
+    base: String,
+    /// Sent as a bearer token, to keep each player's saves apart
+    token: String,
+    timeout: Duration,
+}
+
+impl HttpBackend {
+    pub fn new(base: &str, token: &str) -> Self {
+        HttpBackend {
+            base: base.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    fn url(&self, game_id: &str) -> String {
+        format!("{}/saves/{}", self.base, urlencode(game_id))
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .timeout(self.timeout)
+    }
+}
+
+/// Percent-encode everything but unreserved characters
+fn urlencode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn read<T: serde::de::DeserializeOwned>(response: ureq::Response) -> Result<T, SyncError> {
+    let body = response
+        .into_string()
+        .map_err(|e| SyncError::Backend(e.to_string()))?;
+    serde_json::from_str(&body).map_err(|e| SyncError::Format(e.to_string()))
+}
+
+impl SaveBackend for HttpBackend {
+    fn put(&mut self, blob: &SaveBlob) -> Result<SaveBlob, SyncError> {
+        let body = serde_json::to_string(blob).map_err(|e| SyncError::Format(e.to_string()))?;
+        let response = self
+            .request("PUT", &self.url(&blob.game_id))
+            .set("Content-Type", "application/json")
+            .set("If-Match", &blob.version.to_string())
+            .send_string(&body);
+        match response {
+            Ok(response) => read(response),
+            Err(ureq::Error::Status(409, response)) => {
+                Err(SyncError::Conflict(Box::new(read(response)?)))
+            }
+            Err(e) => Err(SyncError::Backend(e.to_string())),
+        }
+    }
+
+    fn get(&self, game_id: &str) -> Result<Option<SaveBlob>, SyncError> {
+        match self.request("GET", &self.url(game_id)).call() {
+            Ok(response) => read(response).map(Some),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(SyncError::Backend(e.to_string())),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<SaveSummary>, SyncError> {
+        let url = format!("{}/saves", self.base);
+        match self.request("GET", &url).call() {
+            Ok(response) => read(response),
+            Err(e) => Err(SyncError::Backend(e.to_string())),
+        }
+    }
+}
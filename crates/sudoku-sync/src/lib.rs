@@ -0,0 +1,326 @@
+//! Save sync between devices. A [`SaveBackend`] stores one versioned
+//! [`SaveBlob`] per game; [`sync`] uploads a local save and, when another
+//! device got there first, merges the two by replaying both move logs.
+//!
+//! Blobs carry the frontend's own save format in `data` untouched. Only the
+//! puzzle and the move log are understood here, which is enough to merge.
+
+#[cfg(feature = "http")]
+mod http;
+
+#[cfg(feature = "http")]
+pub use http::HttpBackend;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A cell edit, in the order the player made it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedMove {
+    /// 0-80, row-major
+    pub cell: u8,
+    /// 1-9, or 0 to clear the cell
+    pub value: u8,
+    /// Unix time in milliseconds
+    pub at_ms: u64,
+}
+
+/// One game's save as stored by a backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveBlob {
+    pub game_id: String,
+    /// Bumped by the backend on every accepted put; 0 for a save never
+    /// uploaded
+    pub version: u64,
+    /// Unix time in milliseconds of the last change
+    pub updated_at: u64,
+    /// Name of the device that made the last change
+    pub device: String,
+    /// The starting grid, 81 characters
+    pub puzzle: String,
+    pub moves: Vec<SyncedMove>,
+    /// The frontend's own save, e.g. `SudokuGame::serialize` output
+    pub data: String,
+}
+
+/// What `list` returns per game, without the save itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveSummary {
+    pub game_id: String,
+    pub version: u64,
+    pub updated_at: u64,
+    pub device: String,
+}
+
+impl SaveBlob {
+    pub fn summary(&self) -> SaveSummary {
+        SaveSummary {
+            game_id: self.game_id.clone(),
+            version: self.version,
+            updated_at: self.updated_at,
+            device: self.device.clone(),
+        }
+    }
+
+    /// Values on the board after replaying the moves over the puzzle, 0 for
+    /// empty. Moves on givens are skipped.
+    pub fn replay(&self) -> Vec<u8> {
+        let mut values: Vec<u8> = self
+            .puzzle
+            .bytes()
+            .map(|b| if b.is_ascii_digit() { b - b'0' } else { 0 })
+            .collect();
+        let givens: Vec<bool> = values.iter().map(|&v| v != 0).collect();
+        for m in &self.moves {
+            let cell = usize::from(m.cell);
+            if cell < values.len() && !givens[cell] && m.value <= 9 {
+                values[cell] = m.value;
+            }
+        }
+        values
+    }
+}
+
+#[derive(Debug)]
+pub enum SyncError {
+    /// The stored save has moved past the version the put was based on
+    Conflict(Box<SaveBlob>),
+    /// The backend couldn't be reached or failed
+    Backend(String),
+    /// The backend answered with something that isn't a save
+    Format(String),
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncError::Conflict(remote) => write!(
+                f,
+                "save was changed on {} (version {})",
+                remote.device, remote.version
+            ),
+            SyncError::Backend(e) => write!(f, "save backend error: {}", e),
+            SyncError::Format(e) => write!(f, "unreadable save: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SyncError {}
+
+/// Versioned storage of save blobs
+pub trait SaveBackend {
+    /// Store `blob` if the stored version still equals `blob.version`, and
+    /// return it with the new version. Otherwise fail with
+    /// [`SyncError::Conflict`] holding the stored save.
+    fn put(&mut self, blob: &SaveBlob) -> Result<SaveBlob, SyncError>;
+
+    fn get(&self, game_id: &str) -> Result<Option<SaveBlob>, SyncError>;
+
+    /// Every stored game, most recently changed first
+    fn list(&self) -> Result<Vec<SaveSummary>, SyncError>;
+}
+
+/// Combine two diverged saves of the same game. Moves from both are
+/// replayed in time order, so edits made on either device survive; the
+/// frontend state in `data` comes from the newer side. Saves of different
+/// puzzles can't be merged, and the newer one wins outright.
+pub fn merge(local: &SaveBlob, remote: &SaveBlob) -> SaveBlob {
+    let (newer, older) = if local.updated_at >= remote.updated_at {
+        (local, remote)
+    } else {
+        (remote, local)
+    };
+    let mut merged = SaveBlob {
+        version: remote.version,
+        ..newer.clone()
+    };
+    if local.puzzle != remote.puzzle {
+        return merged;
+    }
+    // Keyed by time then cell, so a move both sides already share counts once
+    let mut moves: BTreeMap<(u64, u8), SyncedMove> = BTreeMap::new();
+    for m in older.moves.iter().chain(&newer.moves) {
+        moves.insert((m.at_ms, m.cell), *m);
+    }
+    merged.moves = moves.into_values().collect();
+    merged
+}
+
+/// Most conflicts a single [`sync`] call resolves before giving up
+const MAX_ATTEMPTS: usize = 3;
+
+/// Upload `local`, merging with whatever another device stored first.
+/// Returns the save as stored; frontends should load it when its moves
+/// differ from `local`'s.
+pub fn sync(backend: &mut dyn SaveBackend, local: &SaveBlob) -> Result<SaveBlob, SyncError> {
+    let mut blob = local.clone();
+    for _ in 0..MAX_ATTEMPTS {
+        match backend.put(&blob) {
+            Err(SyncError::Conflict(remote)) => blob = merge(&blob, &remote),
+            result => return result,
+        }
+    }
+    Err(SyncError::Backend("save kept changing during sync".into()))
+}
+
+/// Saves held in memory, for tests and offline play
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    saves: BTreeMap<String, SaveBlob>,
+}
+
+impl SaveBackend for MemoryBackend {
+    fn put(&mut self, blob: &SaveBlob) -> Result<SaveBlob, SyncError> {
+        // A save that is gone (deleted, or synced to another backend) counts
+        // as never uploaded, so the put recreates it
+        let stored_version = match self.saves.get(&blob.game_id) {
+            Some(stored) if stored.version != blob.version => {
+                return Err(SyncError::Conflict(Box::new(stored.clone())));
+            }
+            Some(stored) => stored.version,
+            None => 0,
+        };
+        let stored = SaveBlob {
+            version: stored_version + 1,
+            ..blob.clone()
+        };
+        self.saves.insert(blob.game_id.clone(), stored.clone());
+        Ok(stored)
+    }
+
+    fn get(&self, game_id: &str) -> Result<Option<SaveBlob>, SyncError> {
+        Ok(self.saves.get(game_id).cloned())
+    }
+
+    fn list(&self) -> Result<Vec<SaveSummary>, SyncError> {
+        let mut list: Vec<SaveSummary> = self.saves.values().map(SaveBlob::summary).collect();
+        list.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+        Ok(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    fn save(device: &str, updated_at: u64, moves: &[(u8, u8, u64)]) -> SaveBlob {
+        SaveBlob {
+            game_id: "daily-2024-01-01".into(),
+            version: 0,
+            updated_at,
+            device: device.into(),
+            puzzle: Tier::Singles.puzzle().into(),
+            moves: moves
+                .iter()
+                .map(|&(cell, value, at_ms)| SyncedMove { cell, value, at_ms })
+                .collect(),
+            data: format!("{{\"from\":\"{}\"}}", device),
+        }
+    }
+
+    #[test]
+    fn test_sync_merges_both_devices() {
+        let mut backend = MemoryBackend::default();
+        let phone = save("phone", 100, &[(2, 4, 10), (3, 6, 20)]);
+        let stored = sync(&mut backend, &phone).unwrap();
+        assert_eq!(stored.version, 1);
+
+        // The laptop started from the same first move, then went its own way
+        let laptop = save("laptop", 200, &[(2, 4, 10), (5, 8, 30), (0, 1, 40)]);
+        let merged = sync(&mut backend, &laptop).unwrap();
+        assert_eq!(merged.version, 2);
+        assert_eq!(merged.moves.len(), 4);
+        assert_eq!(merged.data, laptop.data);
+
+        let values = merged.replay();
+        assert_eq!((values[0], values[2], values[3], values[5]), (5, 4, 6, 8));
+        assert_eq!(backend.list().unwrap()[0].device, "laptop");
+
+        let other = SaveBlob {
+            puzzle: ".".repeat(81),
+            updated_at: 50,
+            ..phone
+        };
+        assert_eq!(merge(&other, &merged).moves, merged.moves);
+    }
+
+    #[test]
+    fn test_put_conflict_and_missing_save() {
+        let mut backend = MemoryBackend::default();
+        let phone = save("phone", 100, &[(2, 4, 10)]);
+        backend.put(&phone).unwrap();
+
+        // A put based on an old version gets the stored save back, and sync
+        // merges it in
+        let laptop = save("laptop", 200, &[(3, 6, 20)]);
+        match backend.put(&laptop) {
+            Err(SyncError::Conflict(remote)) => assert_eq!(remote.device, "phone"),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        let merged = sync(&mut backend, &laptop).unwrap();
+        assert_eq!((merged.version, merged.moves.len()), (2, 2));
+
+        // A save this backend never saw is taken as new
+        let elsewhere = SaveBlob {
+            game_id: "synced-elsewhere".into(),
+            version: 7,
+            ..phone
+        };
+        assert_eq!(backend.put(&elsewhere).unwrap().version, 1);
+    }
+
+    /// Another device that saves again before every put
+    struct Racing(MemoryBackend);
+
+    impl SaveBackend for Racing {
+        fn put(&mut self, blob: &SaveBlob) -> Result<SaveBlob, SyncError> {
+            let current = self.0.get(&blob.game_id)?.map_or(0, |s| s.version);
+            let other = SaveBlob {
+                version: current,
+                device: "tablet".into(),
+                ..blob.clone()
+            };
+            self.0.put(&other)?;
+            self.0.put(blob)
+        }
+
+        fn get(&self, game_id: &str) -> Result<Option<SaveBlob>, SyncError> {
+            self.0.get(game_id)
+        }
+
+        fn list(&self) -> Result<Vec<SaveSummary>, SyncError> {
+            self.0.list()
+        }
+    }
+
+    #[test]
+    fn test_sync_gives_up() {
+        let mut backend = Racing(MemoryBackend::default());
+        let result = sync(&mut backend, &save("phone", 100, &[(2, 4, 10)]));
+        assert!(matches!(result, Err(SyncError::Backend(_))));
+        assert_eq!(backend.list().unwrap()[0].version, MAX_ATTEMPTS as u64);
+    }
+
+    #[test]
+    fn test_replay_skips_givens() {
+        // The given in r1c1 keeps its 5, the later clear empties r1c4, and
+        // a cell off the board or a digit over 9 is dropped
+        let blob = save(
+            "phone",
+            100,
+            &[
+                (0, 9, 10),
+                (2, 4, 20),
+                (3, 6, 30),
+                (3, 0, 40),
+                (81, 1, 50),
+                (5, 12, 60),
+            ],
+        );
+        let values = blob.replay();
+        assert_eq!(values.len(), 81);
+        assert_eq!((values[0], values[2], values[3], values[5]), (5, 4, 0, 0));
+    }
+}
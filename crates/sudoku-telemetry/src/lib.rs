@@ -0,0 +1,196 @@
+//! Opt-in anonymous analytics. Frontends report [`Event`]s to a
+//! [`Telemetry`] sink; [`sink`] hands out [`Noop`] unless the player has
+//! agreed, so nothing leaves the device by default.
+//!
+//! Events carry no player id. A batch is tagged with the platform, the app
+//! version and a random id for the current run only.
+
+use serde::Serialize;
+
+/// How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Won,
+    Lost,
+    Abandoned,
+}
+
+/// Something a player did. Puzzles are identified by their canonical hash.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    GameStarted {
+        puzzle_hash: String,
+        difficulty: String,
+    },
+    GameFinished {
+        puzzle_hash: String,
+        outcome: Outcome,
+        time_secs: u64,
+        hints_used: usize,
+        mistakes: usize,
+    },
+    HintUsed {
+        technique: String,
+    },
+    /// A technique the position called for, whether or not a hint showed it
+    TechniqueEncountered {
+        technique: String,
+    },
+}
+
+/// Where events go
+pub trait Telemetry: Send {
+    fn record(&mut self, event: Event);
+
+    /// Send anything buffered. Sinks also flush on their own after a game
+    /// finishes.
+    fn flush(&mut self) {}
+}
+
+/// Drops every event; the default until the player opts in
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Noop;
+
+impl Telemetry for Noop {
+    fn record(&mut self, _event: Event) {}
+}
+
+/// Body of one upload
+#[derive(Debug, Serialize)]
+pub struct Batch<'a> {
+    pub platform: &'a str,
+    pub app_version: &'a str,
+    /// Random per run, so one session's events can be grouped
+    pub session: &'a str,
+    pub events: &'a [Event],
+}
+
+/// A random id for this run, not stored anywhere
+#[cfg(any(feature = "http", test))]
+fn session_id() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    format!("{:016x}", hasher.finish())
+}
+
+/// The sink to use: an [`HttpSink`] posting to `endpoint` if the player
+/// consented (and the `http` feature is on), otherwise [`Noop`]
+pub fn sink(
+    consent: bool,
+    endpoint: &str,
+    platform: &str,
+    app_version: &str,
+) -> Box<dyn Telemetry> {
+    #[cfg(feature = "http")]
+    if consent {
+        return Box::new(HttpSink::new(endpoint, platform, app_version));
+    }
+    let _ = (consent, endpoint, platform, app_version);
+    Box::new(Noop)
+}
+
+/// Events sent in the background, one batch per finished game or per
+/// [`HttpSink::BATCH_SIZE`] events. Failures are dropped silently.
+#[cfg(feature = "http")]
+pub struct HttpSink {
+    endpoint: String,
+    platform: String,
+    app_version: String,
+    session: String,
+    pending: Vec<Event>,
+}
+
+#[cfg(feature = "http")]
+impl HttpSink {
+    pub const BATCH_SIZE: usize = 50;
+
+    pub fn new(endpoint: &str, platform: &str, app_version: &str) -> Self {
+        HttpSink {
+            endpoint: endpoint.to_string(),
+            platform: platform.to_string(),
+            app_version: app_version.to_string(),
+            session: session_id(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl Telemetry for HttpSink {
+    fn record(&mut self, event: Event) {
+        let finished = matches!(event, Event::GameFinished { .. });
+        self.pending.push(event);
+        if finished || self.pending.len() >= Self::BATCH_SIZE {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let events = std::mem::take(&mut self.pending);
+        let body = serde_json::to_string(&Batch {
+            platform: &self.platform,
+            app_version: &self.app_version,
+            session: &self.session,
+            events: &events,
+        })
+        .expect("events serialize");
+        let endpoint = self.endpoint.clone();
+        std::thread::spawn(move || {
+            let _ = ureq::post(&endpoint)
+                .set("Content-Type", "application/json")
+                .timeout(std::time::Duration::from_secs(10))
+                .send_string(&body);
+        });
+    }
+}
+
+/// Sends whatever is still pending, e.g. events of a game left unfinished
+#[cfg(feature = "http")]
+impl Drop for HttpSink {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_json() {
+        let events = [
+            Event::HintUsed {
+                technique: "X-Wing".into(),
+            },
+            Event::GameFinished {
+                puzzle_hash: "abc".into(),
+                outcome: Outcome::Won,
+                time_secs: 300,
+                hints_used: 1,
+                mistakes: 0,
+            },
+        ];
+        let session = session_id();
+        let batch = Batch {
+            platform: "tui",
+            app_version: "0.1.0",
+            session: &session,
+            events: &events,
+        };
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!(json["events"][0]["event"], "hint_used");
+        assert_eq!(json["events"][1]["outcome"], "won");
+        assert_eq!(session.len(), 16);
+
+        let mut noop = sink(false, "http://localhost", "tui", "0.1.0");
+        noop.record(events[0].clone());
+        noop.flush();
+    }
+}
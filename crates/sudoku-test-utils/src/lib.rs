@@ -0,0 +1,184 @@
+//! Fixtures and invariants for testing anything built on sudoku-core: the
+//! bindings, the frontends, the fuzz targets and the benchmarks.
+//!
+//! Use it as a dev-dependency. The `proptest` feature adds strategies.
+
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
+use sudoku_core::{Grid, Hint, HintType, Position, Solver};
+
+pub use sudoku_session::apply_hint;
+
+/// Techniques grouped by how hard they are, easiest first. Each tier has a
+/// fixture whose solve needs it: stuck with only the tiers below, solved
+/// once it is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Tier {
+    /// Naked and hidden singles
+    Singles,
+    /// Pointing and box/line reduction
+    Intersections,
+    /// Naked and hidden pairs, triples and quads
+    Subsets,
+    /// X-Wing, Swordfish, Jellyfish
+    Fish,
+    /// XY-Wing, XYZ-Wing
+    Wings,
+    /// Chains and anything harder
+    Chains,
+}
+
+impl Tier {
+    pub const ALL: [Tier; 6] = [
+        Tier::Singles,
+        Tier::Intersections,
+        Tier::Subsets,
+        Tier::Fish,
+        Tier::Wings,
+        Tier::Chains,
+    ];
+
+    /// Lowest Sudoku Explainer rating among the tier's techniques, so the
+    /// hardest step of the fixture's solve rates at least this
+    pub fn min_se(self) -> f32 {
+        match self {
+            Tier::Singles => 1.0,
+            Tier::Intersections => 2.6,
+            Tier::Subsets => 3.0,
+            Tier::Fish => 3.2,
+            Tier::Wings => 4.0,
+            Tier::Chains => 5.0,
+        }
+    }
+
+    /// The fixture puzzle, 81 characters with "." for empty cells
+    pub const fn puzzle(self) -> &'static str {
+        match self {
+            // Wikipedia's example
+            Tier::Singles => {
+                "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"
+            }
+            Tier::Intersections => {
+                "4.....938.32.941...953..24.37.6.9..4529..16736.47.3.9.957..83....39..4..24..3.7.9"
+            }
+            // Needs a hidden pair
+            Tier::Subsets => {
+                "....3..86....2..4..9..7852.3718562949..1423754..3976182..7.3859.392.54677..9.4132"
+            }
+            // Needs an X-Wing
+            Tier::Fish => {
+                ".....94...2...17..4.865...23.5.4.6.7.7.9....3..9.......9.....2..6752......4......"
+            }
+            // Needs an XY-Wing
+            Tier::Wings => {
+                "9...4.......6...31.2.....9....7...2...29356...7...2....6.....7351...9.......8...9"
+            }
+            // AI Escargot
+            Tier::Chains => {
+                "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3.."
+            }
+        }
+    }
+}
+
+/// A tier's fixture, with candidates filled in
+pub fn fixture(tier: Tier) -> Grid {
+    let mut grid = Grid::from_string(tier.puzzle()).expect("fixtures parse");
+    grid.recalculate_candidates();
+    grid
+}
+
+/// The fixture's solution, 81 digits
+pub fn solution(tier: Tier) -> String {
+    Solver::new()
+        .solve(&fixture(tier))
+        .expect("fixtures have a solution")
+        .to_string_compact()
+}
+
+/// The solution's digit for each cell, row-major
+pub fn solution_digits(grid: &Grid) -> Vec<u8> {
+    Solver::new()
+        .solve(grid)
+        .expect("puzzle has a solution")
+        .to_string_compact()
+        .bytes()
+        .map(|b| b - b'0')
+        .collect()
+}
+
+fn index(pos: Position) -> usize {
+    pos.row * 9 + pos.col
+}
+
+/// Panic unless `hint` agrees with `solution`: a placement must put the
+/// solution's digit, and an elimination must leave it.
+pub fn assert_hint_sound(solution: &[u8], hint: &Hint) {
+    match &hint.hint_type {
+        HintType::SetValue { pos, value } => assert_eq!(
+            *value,
+            solution[index(*pos)],
+            "{} placed a wrong digit at r{}c{}",
+            hint.technique,
+            pos.row + 1,
+            pos.col + 1
+        ),
+        HintType::EliminateCandidates { pos, values } => assert!(
+            !values.contains(&solution[index(*pos)]),
+            "{} eliminated the solution from r{}c{}",
+            hint.technique,
+            pos.row + 1,
+            pos.col + 1
+        ),
+    }
+}
+
+/// Follow hints from `grid` for at most `max_steps`, checking each against
+/// the solution. Returns the hints taken, in order.
+pub fn assert_solve_path_sound(grid: &Grid, max_steps: usize) -> Vec<Hint> {
+    let solver = Solver::new();
+    let solution = solution_digits(grid);
+    let mut grid = grid.deep_clone();
+    let mut used = Vec::new();
+    for _ in 0..max_steps {
+        if grid.is_complete() {
+            break;
+        }
+        let Some(hint) = solver.get_hint(&grid) else {
+            break;
+        };
+        assert_hint_sound(&solution, &hint);
+        let progressed = apply_hint(&mut grid, &hint);
+        used.push(hint);
+        if !progressed {
+            break;
+        }
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_tiers() {
+        assert_eq!(&solution(Tier::Singles)[..9], "534678912");
+        for tier in Tier::ALL {
+            let hardest = assert_solve_path_sound(&fixture(tier), 400)
+                .iter()
+                .map(|hint| hint.technique.se_rating())
+                .fold(0.0, f32::max);
+            assert!(
+                hardest >= tier.min_se(),
+                "{:?} needed only SE {}",
+                tier,
+                hardest
+            );
+            if tier == Tier::Singles {
+                assert!(hardest < Tier::Intersections.min_se());
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! proptest strategies
+
+use proptest::prelude::*;
+use sudoku_core::{Difficulty, Grid, PuzzleId};
+
+/// Generated puzzles at the given levels, from any 32-bit seed. Keep to the
+/// easier levels for many cases; hard ones take a while to generate.
+pub fn puzzles(levels: &'static [Difficulty]) -> impl Strategy<Value = Grid> {
+    (prop::sample::select(levels), any::<u32>()).prop_map(|(difficulty, seed)| {
+        PuzzleId {
+            difficulty,
+            seed: u64::from(seed),
+        }
+        .generate()
+    })
+}
+
+/// Up to `max` (cell, digit) pairs: cells 0-80, digits 1-9. Pair with
+/// `solution_digits` to drop only wrong candidates.
+pub fn eliminations(max: usize) -> impl Strategy<Value = Vec<(usize, u8)>> {
+    prop::collection::vec((0..81usize, 1..=9u8), 0..=max)
+}
@@ -0,0 +1,146 @@
+//! Screen-reader line mode (`--a11y`): the board as plain text lines and a
+//! spoken-style description of every action, with no box drawing or color
+//! needed to follow the game.
+
+use crate::game::Game;
+use sudoku_core::Position;
+use sudoku_render::Board;
+
+/// How the board is written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoardText {
+    /// "Row 1: 5 3 .  . 7 ." lines for speech
+    #[default]
+    Lines,
+    /// One Braille cell per square
+    Braille,
+    /// Block digits three lines tall
+    Large,
+}
+
+impl BoardText {
+    /// Parse a name from the config ("lines", "braille", "large")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "lines" => Some(Self::Lines),
+            "braille" => Some(Self::Braille),
+            "large" => Some(Self::Large),
+            _ => None,
+        }
+    }
+}
+
+/// Cell name as read out, e.g. "R4C7"
+pub fn cell_name(pos: Position) -> String {
+    format!("R{}C{}", pos.row + 1, pos.col + 1)
+}
+
+/// One line per row, digits separated by spaces and "." for empty cells,
+/// with a space between boxes: "Row 1: 5 3 .  . 7 .  . . ."
+pub fn board_lines(game: &Game) -> Vec<String> {
+    let values = game.grid().values();
+    values
+        .iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            let boxes: Vec<String> = cells
+                .chunks(3)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .map(|v| v.map_or(".".to_string(), |v| v.to_string()))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect();
+            format!("Row {}: {}", row + 1, boxes.join("  "))
+        })
+        .collect()
+}
+
+/// The board in the chosen style
+pub fn board_text(game: &Game, style: BoardText) -> Vec<String> {
+    let mut board = Board::default();
+    for (i, value) in game.grid().values().iter().flatten().enumerate() {
+        board.values[i] = value.unwrap_or(0);
+    }
+    match style {
+        BoardText::Lines => board_lines(game),
+        BoardText::Braille => sudoku_render::braille(&board),
+        BoardText::Large => sudoku_render::large_digits(&board),
+    }
+}
+
+/// Units where `value` at `pos` clashes with another cell
+pub fn conflicts(values: &[[Option<u8>; 9]; 9], pos: Position, value: u8) -> Vec<&'static str> {
+    let (row, col) = (pos.row, pos.col);
+    let mut units = Vec::new();
+    if (0..9).any(|c| c != col && values[row][c] == Some(value)) {
+        units.push("row");
+    }
+    if (0..9).any(|r| r != row && values[r][col] == Some(value)) {
+        units.push("column");
+    }
+    let (br, bc) = (row / 3 * 3, col / 3 * 3);
+    let in_box = (br..br + 3)
+        .flat_map(|r| (bc..bc + 3).map(move |c| (r, c)))
+        .any(|(r, c)| (r, c) != (row, col) && values[r][c] == Some(value));
+    if in_box {
+        units.push("box");
+    }
+    units
+}
+
+/// What is in a cell: "R4C7 given 5", "R4C7 5", "R4C7 empty, notes 1 5 9"
+pub fn describe_cell(game: &Game, pos: Position) -> String {
+    let cell = game.grid().cell(pos);
+    let name = cell_name(pos);
+    match cell.value() {
+        Some(v) if cell.is_given() => format!("{} given {}", name, v),
+        Some(v) => format!("{} {}", name, v),
+        None => {
+            let notes: Vec<String> = cell.candidates().iter().map(|v| v.to_string()).collect();
+            let corner: Vec<String> = game
+                .corner_marks(pos)
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            let mut text = format!("{} empty", name);
+            if !notes.is_empty() {
+                text += &format!(", notes {}", notes.join(" "));
+            }
+            if !corner.is_empty() {
+                text += &format!(", corner {}", corner.join(" "));
+            }
+            text
+        }
+    }
+}
+
+/// A placement as read out: "R4C7 set to 5, conflict in column"
+pub fn describe_placement(game: &Game, pos: Position, value: u8) -> String {
+    let units = conflicts(&game.grid().values(), pos, value);
+    let mut text = format!("{} set to {}", cell_name(pos), value);
+    if !units.is_empty() {
+        text += &format!(", conflict in {}", units.join(" and "));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflicts() {
+        let mut values = [[None; 9]; 9];
+        values[3][0] = Some(5);
+        values[8][6] = Some(5);
+        values[4][8] = Some(5);
+        let pos = Position::new(3, 6);
+        values[3][6] = Some(5);
+        assert_eq!(conflicts(&values, pos, 5), vec!["row", "column", "box"]);
+        assert!(conflicts(&values, pos, 7).is_empty());
+        assert_eq!(cell_name(pos), "R4C7");
+    }
+}
@@ -0,0 +1,98 @@
+//! Achievements, with the same ids as the iOS app's Game Center set so
+//! progress means the same thing on every frontend.
+//!
+//! Progress is derived from the stats; only the set of unlocked ids is
+//! stored, so an unlock is announced once.
+
+use crate::stats::{GameResult, StatsManager, SPEED_DEMON_TIME};
+use sudoku_core::Difficulty;
+
+/// An achievement and the count needed to unlock it
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub target: u32,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "first_win",
+        name: "First Win",
+        description: "Solve a puzzle",
+        target: 1,
+    },
+    Achievement {
+        id: "beginner_master",
+        name: "Beginner Master",
+        description: "Win 10 Beginner games",
+        target: 10,
+    },
+    Achievement {
+        id: "no_mistakes",
+        name: "Flawless",
+        description: "Win without a mistake",
+        target: 1,
+    },
+    Achievement {
+        id: "speed_demon",
+        name: "Speed Demon",
+        description: "Win in under 5 minutes",
+        target: 1,
+    },
+    Achievement {
+        id: "expert_solver",
+        name: "Expert Solver",
+        description: "Win an Expert game",
+        target: 1,
+    },
+    Achievement {
+        id: "streak_5",
+        name: "On a Roll",
+        description: "Win 5 games in a row",
+        target: 5,
+    },
+    Achievement {
+        id: "streak_10",
+        name: "Unstoppable",
+        description: "Win 10 games in a row",
+        target: 10,
+    },
+    Achievement {
+        id: "konami",
+        name: "Nostalgia Master",
+        description: "Enter the Konami code",
+        target: 1,
+    },
+];
+
+/// Progress toward an achievement, capped at its target
+pub fn progress(achievement: &Achievement, stats: &StatsManager) -> u32 {
+    let player = &stats.player;
+    let wins = || stats.history.iter().filter(|r| r.result == GameResult::Win);
+    let any = |found: bool| u32::from(found);
+    let count = match achievement.id {
+        "first_win" => player.total_wins as u32,
+        "beginner_master" => player.get_difficulty_stats(Difficulty::Beginner).wins as u32,
+        "no_mistakes" => any(wins().any(|r| r.mistakes == 0)),
+        "speed_demon" => any(wins().any(|r| r.time_secs < SPEED_DEMON_TIME)),
+        "expert_solver" => player.get_difficulty_stats(Difficulty::Expert).wins as u32,
+        "streak_5" | "streak_10" => player.best_streak.max(0) as u32,
+        "konami" => any(player.konami_entered),
+        _ => 0,
+    };
+    count.min(achievement.target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_unique() {
+        for (i, a) in ACHIEVEMENTS.iter().enumerate() {
+            assert!(a.target > 0);
+            assert!(ACHIEVEMENTS[i + 1..].iter().all(|b| b.id != a.id));
+        }
+    }
+}
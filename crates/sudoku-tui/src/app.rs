@@ -1,15 +1,45 @@
+use crate::a11y;
 use crate::animations::{CelebrationManager, LoseScreen, WinScreen};
+use crate::clipboard::{self, CopyTarget};
+use crate::config::Config;
+use crate::editor::{Check, Editor};
+use crate::export::ExportFormat;
 use crate::game::Game;
+use crate::hotseat::HotSeat;
+use crate::keymap::{Action, Keymap};
+use crate::leaderboard::{LeaderboardEntry, LeaderboardResult};
+use crate::packs::PackBrowser;
+use crate::palette::{self, Command};
+use crate::slots::{self, SlotInfo};
 use crate::stats::{GameResult, StatsManager};
 use crate::theme::Theme;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::timer::TimerMode;
+use crate::trainer::{self, Trainer};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::collections::VecDeque;
 use std::fs;
-use std::path::PathBuf;
-use std::time::Duration;
-use sudoku_core::{Difficulty, Hint, Position, PuzzleId};
-
-/// Maximum mistakes before game over
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+use sudoku_core::{canonical_puzzle_hash_str, Difficulty, Hint, Position, PuzzleId, Solver};
+use sudoku_daily as daily;
+use sudoku_glossary::Verbosity;
+use sudoku_session::{GameMode, GameSettings};
+use sudoku_telemetry::{Event, Outcome, Telemetry};
+
+/// Mistakes that knock a player out of a hot-seat game
 pub const MAX_MISTAKES: usize = 3;
+/// Ticks an achievement toast stays up (~4 seconds at 100ms)
+const TOAST_TICKS: u32 = 40;
+
+/// Global rankings fetched per difficulty
+const GLOBAL_RANKINGS_LIMIT: usize = 50;
+
+/// How often an unfinished game is saved while it is being played, so the
+/// clock and anything outside the move history stay current
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
 
 /// Result of handling a key press
 pub enum AppAction {
@@ -32,6 +62,74 @@ pub enum ScreenState {
     Leaderboard,
     /// Game history screen (for replay selection)
     History,
+    /// Technique trainer
+    Trainer,
+    /// Achievements list
+    Achievements,
+    /// Puzzle pack browser
+    Packs,
+    /// Hot-seat results
+    HotSeat,
+    /// Move history browser
+    Moves,
+    /// Custom puzzle entry
+    Editor,
+    /// Every key binding
+    Help,
+}
+
+/// A puzzle being generated on a background thread
+pub struct Generation {
+    rx: mpsc::Receiver<Game>,
+    /// Set when the player cancels, so the worker drops its game
+    cancelled: Arc<AtomicBool>,
+    pub difficulty: Difficulty,
+    pub started: Instant,
+    /// Start it as a hot-seat game
+    hotseat: bool,
+    /// Shown once the game starts
+    message: String,
+    /// Screen to go back to if it is cancelled
+    previous_screen: ScreenState,
+}
+
+impl Generation {
+    /// Run `make` on a background thread; its game arrives on `rx` unless
+    /// cancelled first
+    fn spawn(
+        difficulty: Difficulty,
+        hotseat: bool,
+        message: String,
+        previous_screen: ScreenState,
+        make: impl FnOnce() -> Game + Send + 'static,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_cancelled = Arc::clone(&cancelled);
+        thread::spawn(move || {
+            // Generation can't be interrupted; a cancelled game is dropped
+            // here instead of being sent
+            let game = make();
+            if !worker_cancelled.load(Ordering::Relaxed) {
+                let _ = tx.send(game);
+            }
+        });
+        Self {
+            rx,
+            cancelled,
+            difficulty,
+            started: Instant::now(),
+            hotseat,
+            message,
+            previous_screen,
+        }
+    }
+
+    /// Tell the worker to drop its game. Returns the screen to go back to.
+    fn cancel(&self) -> ScreenState {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.previous_screen
+    }
 }
 
 /// Input mode for the app
@@ -40,8 +138,10 @@ pub enum ScreenState {
 pub enum InputMode {
     /// Normal mode: numbers set values
     Normal,
-    /// Candidate mode: numbers toggle candidates
+    /// Candidate mode: numbers toggle candidates (center notes)
     Candidate,
+    /// Corner mode: numbers toggle corner marks
+    Corner,
     /// Menu mode: selecting options
     Menu,
 }
@@ -55,6 +155,23 @@ pub enum MenuState {
     Difficulty,
     Theme,
     Confirm,
+    Export,
+    /// Pick what to copy to the clipboard
+    Copy,
+    /// Offered at startup when an unfinished game was autosaved
+    Resume,
+    /// Pick a slot to save into
+    SaveSlots,
+    /// Pick a slot to load
+    LoadSlots,
+    /// Today's daily puzzle and the daily streak
+    Daily,
+    /// Pick a technique to practise
+    Trainer,
+    /// Type to find a command
+    Palette,
+    /// Pick a bookmark to go back to
+    Bookmarks,
 }
 
 /// The main application state
@@ -71,12 +188,24 @@ pub struct App {
     pub menu_selection: usize,
     /// Color theme
     pub theme: Theme,
+    /// Themes offered by the theme menu, with their labels
+    pub themes: Vec<(String, Theme)>,
     /// Current hint to display
     pub current_hint: Option<Hint>,
     /// Message to display
     pub message: Option<String>,
     /// Message timer
     message_timer: u32,
+    /// Achievement unlock notifications waiting to be shown (front is showing)
+    pub toasts: VecDeque<String>,
+    /// Ticks the front toast has been shown
+    toast_timer: u32,
+    /// Screen-reader line mode
+    pub a11y: bool,
+    /// How line mode writes the board
+    pub a11y_board: a11y::BoardText,
+    /// What line mode reads out after the last action
+    pub announcement: String,
     /// Current screen state
     pub screen_state: ScreenState,
     /// Win screen animation
@@ -89,20 +218,83 @@ pub struct App {
     pub show_suggestions: bool,
     /// Whether to show naked singles (cells with only one candidate) as hints
     pub show_naked_singles: bool,
+    /// Assists new games start with
+    pub settings: GameSettings,
+    /// Two-player hot-seat game in progress
+    pub hotseat: Option<HotSeat>,
+    /// Whether the new game menu starts a hot-seat game
+    pub hotseat_next: bool,
+    /// Puzzle pack browser state
+    pub packs: PackBrowser,
+    /// Where puzzle packs are loaded from
+    packs_dir: PathBuf,
+    /// Clock mode for new games
+    pub timer_mode: TimerMode,
+    /// Countdown length for the timer menu's countdown option
+    countdown_secs: u64,
+    /// Game mode for new games
+    pub game_mode: GameMode,
+    /// Whether to show the available-techniques panel
+    pub show_techniques: bool,
+    /// How much hint boxes explain
+    pub explanations: Verbosity,
+    /// Techniques that apply to the current position, with how many moves each
+    pub available_techniques: Vec<(String, usize)>,
+    /// Grid the techniques were found for, to refresh only after a move
+    techniques_grid: Option<String>,
+    /// Digit chosen with the scroll wheel; right-click enters it
+    pub selected_digit: u8,
     /// Statistics manager
     pub stats: StatsManager,
     /// Whether current game has been recorded (to avoid double recording)
     game_recorded: bool,
     /// Selected difficulty for leaderboard filter
     pub leaderboard_difficulty: Difficulty,
+    /// Game mode whose leaderboard category is shown
+    pub leaderboard_mode: GameMode,
+    /// Show global rankings instead of local scores on the leaderboard
+    pub leaderboard_global: bool,
+    /// Latest global rankings (None while a fetch is in flight)
+    pub global_rankings: Option<LeaderboardResult<Vec<LeaderboardEntry>>>,
+    /// Pending global rankings fetch
+    rankings_rx: Option<mpsc::Receiver<LeaderboardResult<Vec<LeaderboardEntry>>>>,
+    /// New puzzle being generated, if any
+    pub generating: Option<Generation>,
     /// Scroll offset for history view
     pub history_scroll: usize,
+    /// Text typed into the command palette
+    pub palette_query: String,
+    /// Selected row of the move history (the number of steps applied there)
+    pub moves_selection: usize,
+    /// Puzzle editor grid, kept between visits
+    pub editor: Editor,
     /// Konami code progress (for easter egg)
     konami_progress: usize,
     /// Reverse Konami code progress (lose screen easter egg)
     reverse_konami_progress: usize,
     /// "42" pattern progress (The Answer)
     the_answer_progress: usize,
+    /// Autosave file for resuming an unfinished game
+    save_path: PathBuf,
+    /// Bindings for remappable gameplay keys
+    pub keymap: Keymap,
+    /// Autosaved game offered by the Resume menu
+    pub resume_game: Option<Game>,
+    /// Slot summaries shown by the save/load menus
+    pub slot_infos: Vec<Option<SlotInfo>>,
+    /// Move and undo history lengths at the last autosave; input saves
+    /// again only when they change
+    autosaved_moves: Option<(usize, usize)>,
+    /// When the game was last autosaved
+    autosaved_at: Instant,
+    /// Open technique trainer session
+    pub trainer: Option<Trainer>,
+    /// Whether leaving the trainer should resume the paused game
+    unpause_after_trainer: bool,
+    /// Anonymous analytics; a no-op unless the config opts in
+    analytics: Box<dyn Telemetry>,
+    /// Grid whose next technique was last reported, to report once per move
+    encountered_grid: Option<String>,
 }
 
 impl Default for App {
@@ -145,49 +337,124 @@ impl App {
 
     /// Create a new app with a medium difficulty game
     pub fn new() -> Self {
-        Self::new_with_puzzle(None)
+        Self::new_with_puzzle(None, &Config::default())
     }
 
     /// Create a new app, optionally loading a puzzle from an 81-character string or 8-character short code
-    pub fn new_with_puzzle(puzzle: Option<&str>) -> Self {
-        let game = match puzzle {
+    pub fn new_with_puzzle(puzzle: Option<&str>, config: &Config) -> Self {
+        let difficulty = config.difficulty();
+        let mut game = match puzzle {
             Some(p) if p.len() == 81 => {
-                Game::from_string(p).unwrap_or_else(|| Game::new(Difficulty::Medium))
+                Game::from_string(p).unwrap_or_else(|| Game::new(difficulty))
             }
             Some(p) => match PuzzleId::from_short_code(p) {
                 Some(id) => Game::new_with_id(&id),
                 None => {
                     // Try as puzzle string anyway (fallback)
-                    Game::from_string(p).unwrap_or_else(|| Game::new(Difficulty::Medium))
+                    Game::from_string(p).unwrap_or_else(|| Game::new(difficulty))
                 }
             },
-            None => Game::new(Difficulty::Medium),
+            None => Game::new(difficulty),
         };
+        game.set_timer_mode(config.timer_mode());
+        game.set_settings(config.assist.settings());
+        game.set_mode(config.game_mode());
 
-        Self {
+        let mut app = Self {
             game,
             cursor: Position::new(4, 4),
             mode: InputMode::Normal,
             menu: MenuState::None,
             menu_selection: 0,
-            theme: Theme::dark(),
+            theme: config.theme(),
+            themes: config.themes(),
             current_hint: None,
             message: None,
             message_timer: 0,
+            toasts: VecDeque::new(),
+            toast_timer: 0,
+            a11y: config.a11y,
+            a11y_board: config.a11y_board(),
+            announcement: String::new(),
             screen_state: ScreenState::Playing,
             win_screen: WinScreen::new(),
             lose_screen: LoseScreen::new(),
             celebrations: CelebrationManager::new(),
-            show_suggestions: true,
-            show_naked_singles: false, // Off by default - it's basically cheating!
+            show_suggestions: config.assist.show_suggestions,
+            show_naked_singles: config.assist.ghost_hints, // Off by default - it's basically cheating!
+            settings: config.assist.settings(),
+            hotseat: None,
+            hotseat_next: false,
+            packs: PackBrowser::default(),
+            packs_dir: config.packs_dir(),
+            timer_mode: config.timer_mode(),
+            countdown_secs: config.countdown_secs(),
+            game_mode: config.game_mode(),
+            show_techniques: config.assist.techniques,
+            explanations: config.explanations(),
+            available_techniques: Vec::new(),
+            techniques_grid: None,
+            selected_digit: 1,
             stats: StatsManager::load(),
             game_recorded: false,
             leaderboard_difficulty: Difficulty::Medium,
+            leaderboard_mode: GameMode::Classic,
+            leaderboard_global: false,
+            global_rankings: None,
+            rankings_rx: None,
+            generating: None,
             history_scroll: 0,
+            palette_query: String::new(),
+            moves_selection: 0,
+            editor: Editor::new(),
             konami_progress: 0,
             reverse_konami_progress: 0,
             the_answer_progress: 0,
+            save_path: config.save_path(),
+            keymap: config.keymap(),
+            resume_game: None,
+            slot_infos: Vec::new(),
+            autosaved_moves: None,
+            autosaved_at: Instant::now(),
+            trainer: None,
+            unpause_after_trainer: false,
+            analytics: sudoku_telemetry::sink(
+                config.analytics,
+                crate::telemetry::EVENTS_ENDPOINT,
+                "tui",
+                env!("CARGO_PKG_VERSION"),
+            ),
+            encountered_grid: None,
+        };
+        app.report_started();
+
+        if let Some(remote) = config.remote_leaderboard() {
+            app.stats.use_remote_leaderboard(remote);
         }
+
+        // Progress made before achievements existed unlocks quietly
+        app.stats.check_achievements();
+
+        // Offer to pick up an unfinished game unless a puzzle was requested
+        if puzzle.is_none() {
+            let saved = fs::read_to_string(&app.save_path)
+                .ok()
+                .and_then(|json| Game::deserialize(&json));
+            if let Some(saved) = saved.filter(|g| !g.is_lost()) {
+                app.resume_game = Some(saved);
+                app.menu = MenuState::Resume;
+            }
+        }
+
+        // Show how an imported puzzle rates before the player starts
+        if puzzle.is_some() {
+            app.show_message(&format!(
+                "Imported {} puzzle (SE {:.1})",
+                app.game.difficulty(),
+                app.game.se_rating()
+            ));
+        }
+        app
     }
 
     /// Get the tick rate based on current screen
@@ -201,7 +468,16 @@ impl App {
                     Duration::from_millis(100) // 10 FPS for normal gameplay
                 }
             }
-            ScreenState::Stats | ScreenState::Leaderboard | ScreenState::History => {
+            ScreenState::Stats
+            | ScreenState::Leaderboard
+            | ScreenState::History
+            | ScreenState::Trainer
+            | ScreenState::Achievements
+            | ScreenState::Packs
+            | ScreenState::HotSeat
+            | ScreenState::Moves
+            | ScreenState::Editor
+            | ScreenState::Help => {
                 Duration::from_millis(100) // 10 FPS for menu screens
             }
         }
@@ -209,6 +485,36 @@ impl App {
 
     /// Update animations and timers (called every tick)
     pub fn tick(&mut self) {
+        if let Some(generation) = &self.generating {
+            match generation.rx.try_recv() {
+                Ok(game) => {
+                    let generation = self.generating.take().unwrap();
+                    self.start_generated(game, generation);
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.generating = None;
+                    self.show_message("Puzzle generation failed");
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+
+        if let Some(rx) = &self.rankings_rx {
+            if let Ok(rankings) = rx.try_recv() {
+                self.global_rankings = Some(rankings);
+                self.rankings_rx = None;
+            }
+        }
+
+        // Show queued achievement toasts one after another
+        if !self.toasts.is_empty() {
+            self.toast_timer += 1;
+            if self.toast_timer >= TOAST_TICKS {
+                self.toasts.pop_front();
+                self.toast_timer = 0;
+            }
+        }
+
         // Update message timer
         if self.message_timer > 0 {
             self.message_timer -= 1;
@@ -226,6 +532,15 @@ impl App {
                 self.lose_screen.update();
             }
             ScreenState::Playing => {
+                if self.show_techniques {
+                    self.refresh_techniques();
+                }
+                self.report_next_technique();
+
+                if self.autosaved_at.elapsed() >= AUTOSAVE_INTERVAL {
+                    self.autosave();
+                }
+
                 // Update celebrations for row/column/box completions
                 self.celebrations.update(
                     self.game.completed_rows(),
@@ -241,23 +556,56 @@ impl App {
                     }
                 }
 
+                // A hot-seat game ends when the grid is full or a player is out
+                if let Some(hotseat) = self.hotseat.as_mut() {
+                    if !self.game_recorded {
+                        hotseat.set_running(!self.game.is_paused());
+                        if self.game.is_completed() || hotseat.out(MAX_MISTAKES).is_some() {
+                            self.finish_hotseat();
+                        }
+                    }
+                    return;
+                }
+
                 // Check for win/lose conditions
                 if self.game.is_completed() {
                     self.record_game(GameResult::Win);
                     self.screen_state = ScreenState::Win;
                     self.win_screen.reset();
-                } else if self.game.mistakes() >= MAX_MISTAKES {
+                } else if self.game.is_lost() || self.game.is_time_up() {
                     self.record_game(GameResult::Loss);
                     self.screen_state = ScreenState::Lose;
                     self.lose_screen.reset();
                 }
             }
-            ScreenState::Stats | ScreenState::Leaderboard | ScreenState::History => {
+            ScreenState::Stats
+            | ScreenState::Leaderboard
+            | ScreenState::History
+            | ScreenState::Trainer
+            | ScreenState::Achievements
+            | ScreenState::Packs
+            | ScreenState::HotSeat
+            | ScreenState::Moves
+            | ScreenState::Editor
+            | ScreenState::Help => {
                 // No animations for these screens
             }
         }
     }
 
+    /// Reset per-game bookkeeping after switching to a new game
+    fn begin_game(&mut self) {
+        self.game_recorded = false;
+        self.report_started();
+    }
+
+    fn report_started(&mut self) {
+        self.analytics.record(Event::GameStarted {
+            puzzle_hash: canonical_puzzle_hash_str(self.game.original_puzzle()),
+            difficulty: self.game.difficulty().to_string(),
+        });
+    }
+
     /// Record the current game to stats
     fn record_game(&mut self, result: GameResult) {
         if self.game_recorded {
@@ -265,37 +613,130 @@ impl App {
         }
         self.game_recorded = true;
 
-        let record = self.stats.record_game(
-            self.game.original_puzzle(),
-            self.game.difficulty(),
-            result,
-            self.game.elapsed().as_secs(),
-            self.game.hints_used(),
-            self.game.mistakes(),
-            self.game.move_times_ms(),
-            self.game.notes_used(),
-            self.game.seed(),
-            self.game.short_code(),
-        );
+        self.analytics.record(Event::GameFinished {
+            puzzle_hash: canonical_puzzle_hash_str(self.game.original_puzzle()),
+            outcome: match result {
+                GameResult::Win => Outcome::Won,
+                GameResult::Loss => Outcome::Lost,
+                GameResult::Abandoned => Outcome::Abandoned,
+            },
+            time_secs: self.game.elapsed().as_secs(),
+            hints_used: self.game.hints_used(),
+            mistakes: self.game.mistakes(),
+        });
+
+        // Each day's daily puzzle counts once
+        if let Some(day) = self.game.daily() {
+            if self.stats.daily_result(day).is_some() {
+                return;
+            }
+        }
+
+        // A finished game has nothing left to resume
+        if result != GameResult::Abandoned {
+            let _ = fs::remove_file(&self.save_path);
+            self.autosaved_moves = None;
+        }
+
+        let record = self
+            .stats
+            .record_game(
+                self.game.original_puzzle(),
+                self.game.difficulty(),
+                result,
+                self.game.elapsed().as_secs(),
+                self.game.hints_used(),
+                self.game.mistakes(),
+                self.game.move_times_ms(),
+                self.game.notes_used(),
+                self.game.seed(),
+                self.game.short_code(),
+                self.game.daily(),
+                self.game.timer_mode(),
+                (self.game.timer_mode() == TimerMode::Speedrun).then(|| self.game.splits().clone()),
+                self.game.mode(),
+            )
+            .clone();
 
         // Submit to ukodus API (fire-and-forget background thread)
-        crate::telemetry::submit_result(record, self.game.se_rating());
+        crate::telemetry::submit_result(&record, self.game.se_rating());
+
+        if result == GameResult::Win && self.stats.online_enabled() {
+            self.stats.submit_to_remote(&record);
+        }
+
+        if result == GameResult::Win {
+            if let Some((pack, index)) = self.game.pack() {
+                let pack = pack.to_string();
+                if let Err(e) = self.packs.progress.mark_solved(&pack, index) {
+                    self.show_message(&format!("Pack progress not saved: {}", e));
+                }
+            }
+            let placements = self.game.placements();
+            if let Some(profile) =
+                sudoku_glossary::profile(self.game.original_puzzle(), &placements)
+            {
+                self.stats.record_play_style(&profile);
+            }
+        }
+
+        self.check_achievements();
+    }
+
+    /// Queue a toast for each achievement unlocked since the last check
+    fn check_achievements(&mut self) {
+        for achievement in self.stats.check_achievements() {
+            self.toasts.push_back(format!("🏆 {}", achievement.name));
+            if self.a11y {
+                self.announcement = format!("Achievement unlocked: {}", achievement.name);
+            }
+        }
     }
 
     /// Show a temporary message
     pub fn show_message(&mut self, msg: &str) {
         self.message = Some(msg.to_string());
         self.message_timer = 30; // ~3 seconds at 100ms poll
+        if self.a11y {
+            self.announcement = msg.to_string();
+        }
     }
 
     /// Handle a key press
     pub fn handle_key(&mut self, key: KeyEvent) -> AppAction {
+        let action = self.dispatch_key(key);
+        self.autosave_on_change();
+        action
+    }
+
+    fn dispatch_key(&mut self, key: KeyEvent) -> AppAction {
+        // Only Esc (cancel) while a puzzle is being generated
+        if let Some(generation) = &self.generating {
+            if key.code == KeyCode::Esc {
+                self.screen_state = generation.cancel();
+                self.generating = None;
+                self.show_message("New game cancelled");
+            }
+            return AppAction::Continue;
+        }
+
         // Handle based on screen state
         match self.screen_state {
             ScreenState::Win | ScreenState::Lose => self.handle_endgame_key(key),
             ScreenState::Stats => self.handle_stats_key(key),
             ScreenState::Leaderboard => self.handle_leaderboard_key(key),
             ScreenState::History => self.handle_history_key(key),
+            ScreenState::Trainer => self.handle_trainer_key(key),
+            ScreenState::Achievements => self.handle_achievements_key(key),
+            ScreenState::Packs => self.handle_packs_key(key),
+            ScreenState::HotSeat => self.handle_hotseat_key(key),
+            ScreenState::Moves => self.handle_moves_key(key),
+            ScreenState::Editor => self.handle_editor_key(key),
+            ScreenState::Help => {
+                // Any key closes the list
+                self.screen_state = ScreenState::Playing;
+                AppAction::Continue
+            }
             ScreenState::Playing => {
                 // Clear hint on any key
                 if self.current_hint.is_some() {
@@ -304,10 +745,19 @@ impl App {
 
                 match self.menu {
                     MenuState::None => self.handle_game_key(key),
+                    MenuState::Palette => self.handle_palette_key(key),
                     MenuState::NewGame
                     | MenuState::Difficulty
                     | MenuState::Theme
-                    | MenuState::Confirm => self.handle_menu_key(key),
+                    | MenuState::Confirm
+                    | MenuState::Export
+                    | MenuState::Copy
+                    | MenuState::Resume
+                    | MenuState::SaveSlots
+                    | MenuState::LoadSlots
+                    | MenuState::Daily
+                    | MenuState::Trainer
+                    | MenuState::Bookmarks => self.handle_menu_key(key),
                 }
             }
         }
@@ -321,12 +771,11 @@ impl App {
                 if self.konami_progress >= Self::KONAMI_CODE.len() {
                     // Konami code completed! Start new game directly
                     self.konami_progress = 0;
-                    self.game = Game::new(self.game.difficulty());
-                    self.cursor = Position::new(4, 4);
-                    self.game_recorded = false;
-                    self.celebrations.reset();
-                    self.screen_state = ScreenState::Playing;
-                    self.show_message("KONAMI! New game started!");
+                    self.generate(
+                        self.game.difficulty(),
+                        false,
+                        "KONAMI! New game started!".to_string(),
+                    );
                     return AppAction::Continue;
                 }
             } else if key.code == Self::KONAMI_CODE[0] {
@@ -381,12 +830,23 @@ impl App {
             }
             KeyCode::Enter | KeyCode::Char(' ') => {
                 // Quick restart with same difficulty
-                self.game = Game::new(self.game.difficulty());
+                let difficulty = self.game.difficulty();
+                self.generate(difficulty, false, format!("New {} game", difficulty));
+            }
+            KeyCode::Char('r') if self.screen_state == ScreenState::Lose => {
+                // Same puzzle from the start, counted as a new attempt
+                self.game = self.game.restart();
                 self.cursor = Position::new(4, 4);
-                self.game_recorded = false;
+                self.begin_game();
                 self.celebrations.reset();
                 self.screen_state = ScreenState::Playing;
-                self.show_message(&format!("New {} game", self.game.difficulty()));
+                self.show_message("Retrying the same puzzle");
+            }
+            KeyCode::Char('z') if self.screen_state == ScreenState::Lose => {
+                // Keep going without a mistake limit; the loss stays recorded
+                self.game.continue_in_zen();
+                self.screen_state = ScreenState::Playing;
+                self.show_message("Zen mode: no mistake limit, this game won't count");
             }
             KeyCode::Esc => {
                 // Go back to the (finished) game view
@@ -398,62 +858,23 @@ impl App {
     }
 
     fn handle_game_key(&mut self, key: KeyEvent) -> AppAction {
-        match key.code {
-            // Quit - record abandoned game if in progress
-            KeyCode::Char('q') => {
-                if !self.game.is_completed() && self.game.moves_count() > 0 {
-                    self.record_game(GameResult::Abandoned);
-                }
-                return AppAction::Quit;
-            }
-
-            // Navigation
-            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1, 0),
-            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1, 0),
-            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(0, -1),
-            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(0, 1),
-
-            // Jump to box
-            KeyCode::Char('w') => self.jump_box(-1, 0),
-            KeyCode::Char('s') => self.jump_box(1, 0),
-            KeyCode::Char('a') => self.jump_box(0, -1),
-            KeyCode::Char('d') => self.jump_box(0, 1),
+        // Remappable keys take precedence over the fixed ones below
+        if let Some(action) = self.keymap.action(&key) {
+            self.perform(action, key.modifiers.contains(KeyModifiers::SHIFT));
+            return AppAction::Continue;
+        }
 
-            // Number input
-            KeyCode::Char(c @ '1'..='9') => {
-                let value = c.to_digit(10).unwrap() as u8;
-                if key.modifiers.contains(KeyModifiers::SHIFT) || self.mode == InputMode::Candidate
-                {
-                    self.game.toggle_candidate(self.cursor, value);
-                } else {
-                    let correct = self.game.set_value(self.cursor, value);
-                    if !correct {
-                        let remaining = MAX_MISTAKES.saturating_sub(self.game.mistakes());
-                        if remaining > 0 {
-                            self.show_message(&format!(
-                                "Incorrect! {} {} left",
-                                remaining,
-                                if remaining == 1 { "chance" } else { "chances" }
-                            ));
-                        }
-                    }
-                }
-            }
+        match key.code {
+            // Quit - an autosaved game can be resumed, so only record it as
+            // abandoned if the autosave failed
+            KeyCode::Char('q') => return self.quit(),
 
-            // Clear cell (value and notes)
-            KeyCode::Char('0') | KeyCode::Delete | KeyCode::Backspace => {
-                if self.mode == InputMode::Candidate {
-                    // In candidate mode, clear all candidates
-                    if self.game.clear_candidates(self.cursor) {
-                        self.show_message("Cleared notes");
-                    }
-                } else {
-                    // In normal mode, clear the cell value
-                    self.game.clear_cell(self.cursor);
+            // Clear notes from cell (x) or all cells (Shift+X)
+            KeyCode::Char('x') if self.mode == InputMode::Corner => {
+                if self.game.clear_corner_marks(self.cursor) {
+                    self.show_message("Cleared corner marks");
                 }
             }
-
-            // Clear notes from cell (x) or all cells (Shift+X)
             KeyCode::Char('x') => {
                 if self.game.clear_candidates(self.cursor) {
                     self.show_message("Cleared notes");
@@ -477,50 +898,6 @@ impl App {
                 }
             }
 
-            // Mode toggle
-            KeyCode::Char('c') => {
-                self.mode = match self.mode {
-                    InputMode::Normal => InputMode::Candidate,
-                    InputMode::Candidate => InputMode::Normal,
-                    InputMode::Menu => InputMode::Normal,
-                };
-                let mode_name = match self.mode {
-                    InputMode::Normal => "Normal",
-                    InputMode::Candidate => "Candidate",
-                    InputMode::Menu => "Menu",
-                };
-                self.show_message(&format!("{} mode", mode_name));
-            }
-
-            // Undo/Redo
-            KeyCode::Char('u') => {
-                if self.game.undo() {
-                    self.show_message("Undo");
-                }
-            }
-            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                if self.game.redo() {
-                    self.show_message("Redo");
-                }
-            }
-
-            // Hint
-            KeyCode::Char('?') => {
-                if let Some(hint) = self.game.get_hint() {
-                    self.current_hint = Some(hint);
-                } else {
-                    self.show_message("No hint available");
-                }
-            }
-
-            // Apply hint
-            KeyCode::Char('!') => {
-                if let Some(pos) = self.game.apply_hint() {
-                    self.cursor = pos;
-                    self.show_message("Hint applied");
-                }
-            }
-
             // New game menu
             KeyCode::Char('n') => {
                 self.menu = MenuState::NewGame;
@@ -528,13 +905,18 @@ impl App {
             }
 
             // Pause
-            KeyCode::Char('p') => {
-                self.game.toggle_pause();
-                if self.game.is_paused() {
-                    self.show_message("Paused");
-                } else {
-                    self.show_message("Resumed");
-                }
+            KeyCode::Char('p') => self.toggle_pause(),
+
+            // Export menu
+            KeyCode::Char('e') => {
+                self.menu = MenuState::Export;
+                self.menu_selection = 0;
+            }
+
+            // Copy menu
+            KeyCode::Char('y') => {
+                self.menu = MenuState::Copy;
+                self.menu_selection = 0;
             }
 
             // Theme toggle
@@ -557,15 +939,26 @@ impl App {
                 self.show_message(&format!("Auto-fill hints {}", state));
             }
 
-            // Save + Share
+            // Toggle auto notes for this game and the next ones
+            KeyCode::Char('N') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.toggle_auto_notes();
+            }
+
+            // Toggle the available-techniques panel
+            KeyCode::Char('A') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.show_techniques = !self.show_techniques;
+                let state = if self.show_techniques { "on" } else { "off" };
+                self.show_message(&format!("Techniques panel {}", state));
+            }
+
+            // Save to a slot (+ share)
             KeyCode::Char('S') if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                self.save_game();
-                self.share_puzzle();
+                self.open_slots_menu(MenuState::SaveSlots);
             }
 
-            // Load
+            // Load from a slot
             KeyCode::Char('L') if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                self.load_game();
+                self.open_slots_menu(MenuState::LoadSlots);
             }
 
             // Stats screen
@@ -573,9 +966,26 @@ impl App {
                 self.screen_state = ScreenState::Stats;
             }
 
+            // Puzzle packs
+            KeyCode::Char('P') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.open_packs();
+            }
+
+            // Puzzle editor
+            KeyCode::Char('E') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.screen_state = ScreenState::Editor;
+            }
+
+            // Technique trainer
+            KeyCode::Char('T') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.menu = MenuState::Trainer;
+                self.menu_selection = 0;
+            }
+
             // Leaderboard
             KeyCode::Char('b') => {
                 self.screen_state = ScreenState::Leaderboard;
+                self.refresh_global_rankings();
             }
 
             // History/Replay
@@ -590,6 +1000,338 @@ impl App {
         AppAction::Continue
     }
 
+    /// Quit, counting an unfinished game that could not be autosaved as
+    /// abandoned
+    fn quit(&mut self) -> AppAction {
+        if !self.game.is_completed() && self.game.moves_count() > 0 && !self.autosave() {
+            self.record_game(GameResult::Abandoned);
+        }
+        AppAction::Quit
+    }
+
+    fn toggle_pause(&mut self) {
+        self.game.toggle_pause();
+        if self.game.is_paused() {
+            self.show_message("Paused");
+        } else {
+            self.show_message("Resumed");
+        }
+    }
+
+    /// Toggle auto notes for this game and the next ones
+    fn toggle_auto_notes(&mut self) {
+        self.settings.auto_candidates = !self.settings.auto_candidates;
+        self.game.set_auto_notes(self.settings.auto_candidates);
+        let state = if self.settings.auto_candidates {
+            "on"
+        } else {
+            "off"
+        };
+        self.show_message(&format!("Auto notes {}", state));
+    }
+
+    fn handle_palette_key(&mut self, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Esc => self.menu = MenuState::None,
+            KeyCode::Up => self.menu_selection = self.menu_selection.saturating_sub(1),
+            KeyCode::Down => {
+                let found = palette::search(&self.palette_query).len();
+                if self.menu_selection + 1 < found {
+                    self.menu_selection += 1;
+                }
+            }
+            KeyCode::Backspace => {
+                self.palette_query.pop();
+                self.menu_selection = 0;
+            }
+            KeyCode::Enter => {
+                let found = palette::search(&self.palette_query);
+                if let Some(&(command, _)) = found.get(self.menu_selection) {
+                    self.menu = MenuState::None;
+                    return self.run_command(command);
+                }
+            }
+            KeyCode::Char(c) => {
+                self.palette_query.push(c);
+                self.menu_selection = 0;
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Run a command picked in the palette
+    fn run_command(&mut self, command: Command) -> AppAction {
+        let menu = match command {
+            Command::NewGame => Some(MenuState::NewGame),
+            Command::Theme => Some(MenuState::Theme),
+            Command::Export => Some(MenuState::Export),
+            Command::Copy => Some(MenuState::Copy),
+            Command::Trainer => Some(MenuState::Trainer),
+            _ => None,
+        };
+        if let Some(menu) = menu {
+            self.menu = menu;
+            self.menu_selection = 0;
+            return AppAction::Continue;
+        }
+
+        match command {
+            Command::SaveSlot => self.open_slots_menu(MenuState::SaveSlots),
+            Command::LoadSlot => self.open_slots_menu(MenuState::LoadSlots),
+            Command::Pause => self.toggle_pause(),
+            Command::Moves => self.perform(Action::History, false),
+            Command::Bookmark => self.bookmark_position(),
+            Command::Bookmarks if self.game.bookmarks().is_empty() => {
+                self.show_message("No bookmarks yet")
+            }
+            Command::Bookmarks => {
+                self.menu = MenuState::Bookmarks;
+                self.menu_selection = self.game.bookmarks().len() - 1;
+            }
+            Command::AutoNotes => self.toggle_auto_notes(),
+            Command::Packs => self.open_packs(),
+            Command::Editor => self.screen_state = ScreenState::Editor,
+            Command::Stats => self.screen_state = ScreenState::Stats,
+            Command::ExportStats => self.export_stats(),
+            Command::Leaderboard => {
+                self.screen_state = ScreenState::Leaderboard;
+                self.refresh_global_rankings();
+            }
+            Command::History => {
+                self.history_scroll = 0;
+                self.screen_state = ScreenState::History;
+            }
+            Command::Achievements => self.screen_state = ScreenState::Achievements,
+            Command::Keys => self.screen_state = ScreenState::Help,
+            Command::Quit => return self.quit(),
+            Command::NewGame
+            | Command::Theme
+            | Command::Export
+            | Command::Copy
+            | Command::Trainer => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Run a remappable gameplay action. `shift` turns digits into notes.
+    fn perform(&mut self, action: Action, shift: bool) {
+        match action {
+            Action::Up => self.move_cursor(-1, 0),
+            Action::Down => self.move_cursor(1, 0),
+            Action::Left => self.move_cursor(0, -1),
+            Action::Right => self.move_cursor(0, 1),
+            Action::BoxUp => self.jump_box(-1, 0),
+            Action::BoxDown => self.jump_box(1, 0),
+            Action::BoxLeft => self.jump_box(0, -1),
+            Action::BoxRight => self.jump_box(0, 1),
+            Action::Digit(value) => self.enter_digit(value, shift),
+
+            // Clear cell (value and notes)
+            Action::Clear => {
+                if self.mode == InputMode::Candidate {
+                    // In candidate mode, clear all candidates
+                    if self.game.clear_candidates(self.cursor) {
+                        self.show_message("Cleared notes");
+                    }
+                } else if self.mode == InputMode::Corner {
+                    if self.game.clear_corner_marks(self.cursor) {
+                        self.show_message("Cleared corner marks");
+                    }
+                } else {
+                    // In normal mode, clear the cell value
+                    self.game.clear_cell(self.cursor);
+                    self.announce_cell();
+                }
+            }
+
+            Action::ToggleNotes => {
+                self.mode = match self.mode {
+                    InputMode::Normal | InputMode::Corner => InputMode::Candidate,
+                    InputMode::Candidate => InputMode::Normal,
+                    InputMode::Menu => InputMode::Normal,
+                };
+                self.show_mode();
+            }
+
+            Action::ToggleCorner => {
+                self.mode = match self.mode {
+                    InputMode::Corner => InputMode::Normal,
+                    _ => InputMode::Corner,
+                };
+                self.show_mode();
+            }
+
+            Action::Undo | Action::Redo | Action::History if self.hotseat.is_some() => {
+                self.show_message("No undo in hot-seat games");
+            }
+            Action::Undo => {
+                if self.game.undo() {
+                    self.show_message("Undo");
+                }
+            }
+            Action::Redo => {
+                if self.game.redo() {
+                    self.show_message("Redo");
+                }
+            }
+            Action::History => {
+                self.moves_selection = self.game.history().0.len();
+                self.screen_state = ScreenState::Moves;
+            }
+            Action::Help => self.screen_state = ScreenState::Help,
+            Action::Palette => {
+                self.palette_query.clear();
+                self.menu = MenuState::Palette;
+                self.menu_selection = 0;
+            }
+
+            Action::Hint if !self.game.can_hint() => self.show_message("No hints left"),
+            Action::ApplyHint if !self.game.can_hint() => self.show_message("No hints left"),
+            Action::Hint => {
+                if let Some(hint) = self.game.get_hint() {
+                    if let Some(hotseat) = self.hotseat.as_mut() {
+                        hotseat.record_hint();
+                    }
+                    self.analytics.record(Event::HintUsed {
+                        technique: hint.technique.to_string(),
+                    });
+                    self.current_hint = Some(hint);
+                } else {
+                    self.show_message("No hint available");
+                }
+            }
+            Action::ApplyHint => {
+                if let Some(pos) = self.game.apply_hint() {
+                    if let Some(hotseat) = self.hotseat.as_mut() {
+                        hotseat.record_hint();
+                        hotseat.record_placement(true);
+                    }
+                    self.cursor = pos;
+                    self.show_message("Hint applied");
+                }
+            }
+        }
+    }
+
+    /// Flash the current input mode
+    fn show_mode(&mut self) {
+        let mode_name = match self.mode {
+            InputMode::Normal => "Normal",
+            InputMode::Candidate => "Candidate",
+            InputMode::Corner => "Corner",
+            InputMode::Menu => "Menu",
+        };
+        self.show_message(&format!("{} mode", mode_name));
+    }
+
+    /// Enter a digit at the cursor: a note in candidate mode (or when `as_note`
+    /// is set), a corner mark in corner mode, otherwise a value
+    fn enter_digit(&mut self, value: u8, as_note: bool) {
+        let marking = as_note || matches!(self.mode, InputMode::Candidate | InputMode::Corner);
+        if marking && !self.game.mode().allows_notes() {
+            self.show_message("No notes in Hardcore");
+            return;
+        }
+        if as_note || self.mode == InputMode::Candidate {
+            self.game.toggle_candidate(self.cursor, value);
+            self.announce_cell();
+            return;
+        }
+        if self.mode == InputMode::Corner {
+            self.game.toggle_corner_mark(self.cursor, value);
+            self.announce_cell();
+            return;
+        }
+        let moves = self.game.moves_count();
+        let correct = self.game.set_value(self.cursor, value);
+        if let Some(hotseat) = self.hotseat.as_mut() {
+            if self.game.moves_count() > moves {
+                hotseat.record_placement(correct);
+                let player = hotseat.current + 1;
+                if correct {
+                    self.show_message(&format!("Player {}'s turn", player));
+                } else {
+                    self.show_message(&format!("Incorrect! Player {}'s turn", player));
+                }
+            }
+            return;
+        }
+        let remaining = self.game.settings().mistakes_left(self.game.mistakes());
+        if let Some(remaining) = remaining.filter(|&left| !correct && left > 0) {
+            self.show_message(&format!(
+                "Incorrect! {} {} left",
+                remaining,
+                if remaining == 1 { "chance" } else { "chances" }
+            ));
+        }
+        if self.a11y && self.game.grid().cell(self.cursor).value() == Some(value) {
+            let placed = a11y::describe_placement(&self.game, self.cursor, value);
+            let settings = self.game.settings();
+            self.announcement = if correct || !settings.checks_mistakes() {
+                placed
+            } else if let Some(limit) = settings.mistake_limit() {
+                format!(
+                    "{}. Incorrect, {} of {} mistakes",
+                    placed,
+                    self.game.mistakes(),
+                    limit
+                )
+            } else {
+                format!("{}. Incorrect", placed)
+            };
+        }
+    }
+
+    /// In line mode, read out the cell under the cursor
+    fn announce_cell(&mut self) {
+        if self.a11y {
+            self.announcement = a11y::describe_cell(&self.game, self.cursor);
+        }
+    }
+
+    /// Handle a mouse event on the game screen. `term_size` is the terminal's
+    /// (width, height), used to locate the grid.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, term_size: (u16, u16)) {
+        self.dispatch_mouse(mouse, term_size);
+        self.autosave_on_change();
+    }
+
+    fn dispatch_mouse(&mut self, mouse: MouseEvent, term_size: (u16, u16)) {
+        if self.screen_state != ScreenState::Playing
+            || self.menu != MenuState::None
+            || self.game.is_paused()
+            || self.generating.is_some()
+        {
+            return;
+        }
+
+        let layout = crate::render::game_layout(term_size.0, term_size.1, self.show_techniques);
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(button) => {
+                self.current_hint = None;
+                if let Some(pos) = layout.cell_at(column, row) {
+                    self.cursor = pos;
+                    // Right-click enters the scroll-selected digit
+                    if button == MouseButton::Right {
+                        self.enter_digit(self.selected_digit, false);
+                    }
+                } else if let Some(digit) = layout.digit_at(column, row) {
+                    self.selected_digit = digit;
+                    self.enter_digit(digit, button == MouseButton::Right);
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                self.selected_digit = (self.selected_digit + 7) % 9 + 1;
+            }
+            MouseEventKind::ScrollDown => {
+                self.selected_digit = self.selected_digit % 9 + 1;
+            }
+            _ => {}
+        }
+    }
+
     fn handle_menu_key(&mut self, key: KeyEvent) -> AppAction {
         // Check for Konami code on new game menu to unlock secrets
         if self.menu == MenuState::NewGame || self.menu == MenuState::Difficulty {
@@ -599,6 +1341,7 @@ impl App {
                     self.konami_progress = 0;
                     self.stats.unlock_via_konami();
                     self.show_message("SECRET LEVELS UNLOCKED!");
+                    self.check_achievements();
                     return AppAction::Continue;
                 }
             } else if key.code == Self::KONAMI_CODE[0] {
@@ -611,6 +1354,7 @@ impl App {
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.menu = MenuState::None;
+                self.resume_game = None;
                 self.konami_progress = 0;
             }
 
@@ -620,18 +1364,45 @@ impl App {
                 }
             }
 
+            // Toggle hot-seat for the next game
+            KeyCode::Char('v')
+                if matches!(self.menu, MenuState::NewGame | MenuState::Difficulty) =>
+            {
+                self.hotseat_next = !self.hotseat_next;
+            }
+
+            // Cycle the timer mode for the next game
+            KeyCode::Char('m')
+                if matches!(self.menu, MenuState::NewGame | MenuState::Difficulty) =>
+            {
+                self.timer_mode = self.timer_mode.next(self.countdown_secs);
+            }
+
+            // Cycle the game mode for the next game
+            KeyCode::Char('g')
+                if matches!(self.menu, MenuState::NewGame | MenuState::Difficulty) =>
+            {
+                self.game_mode = self.game_mode.next();
+            }
+
             KeyCode::Down | KeyCode::Char('j') => {
                 let max = match self.menu {
                     MenuState::NewGame | MenuState::Difficulty => {
                         if self.stats.secrets_unlocked() {
-                            7 // All 8 difficulties (0-7)
+                            8 // All 8 difficulties (0-7) + Daily
                         } else {
-                            5 // Standard 6 difficulties (0-5)
+                            6 // Standard 6 difficulties (0-5) + Daily
                         }
                     }
-                    MenuState::Theme => 2,
-                    MenuState::Confirm => 1,
-                    MenuState::None => 0,
+                    MenuState::Theme => self.themes.len().saturating_sub(1),
+                    MenuState::Confirm | MenuState::Resume => 1,
+                    MenuState::Daily => 0,
+                    MenuState::Trainer => trainer::TECHNIQUES.len() - 1,
+                    MenuState::SaveSlots | MenuState::LoadSlots => slots::SLOT_COUNT - 1,
+                    MenuState::Export => ExportFormat::ALL.len() - 1,
+                    MenuState::Copy => CopyTarget::ALL.len() - 1,
+                    MenuState::Bookmarks => self.game.bookmarks().len().saturating_sub(1),
+                    MenuState::None | MenuState::Palette => 0,
                 };
                 if self.menu_selection < max {
                     self.menu_selection += 1;
@@ -640,23 +1411,66 @@ impl App {
 
             KeyCode::Enter | KeyCode::Char(' ') => {
                 match self.menu {
+                    MenuState::NewGame | MenuState::Difficulty
+                        if self.menu_selection == self.daily_menu_index() =>
+                    {
+                        self.menu = MenuState::Daily;
+                        self.menu_selection = 0;
+                    }
+                    MenuState::NewGame | MenuState::Difficulty if self.hotseat_next => {
+                        self.menu = MenuState::None;
+                        self.start_hotseat(self.get_difficulty_from_selection());
+                    }
                     MenuState::NewGame | MenuState::Difficulty => {
                         let difficulty = self.get_difficulty_from_selection();
-                        self.game = Game::new(difficulty);
-                        self.cursor = Position::new(4, 4);
-                        self.screen_state = ScreenState::Playing;
-                        self.game_recorded = false;
-                        self.celebrations.reset();
-                        self.show_message(&format!("New {} game", difficulty));
                         self.menu = MenuState::None;
+                        self.generate(difficulty, false, format!("New {} game", difficulty));
                     }
                     MenuState::Theme => {
-                        self.theme = match self.menu_selection {
-                            0 => Theme::dark(),
-                            1 => Theme::light(),
-                            _ => Theme::high_contrast(),
-                        };
+                        if let Some((_, theme)) = self.themes.get(self.menu_selection) {
+                            self.theme = theme.clone();
+                        }
+                        self.menu = MenuState::None;
+                    }
+                    MenuState::Resume => {
+                        let saved = self.resume_game.take();
+                        if let (0, Some(mut game)) = (self.menu_selection, saved) {
+                            if game.is_paused() {
+                                game.toggle_pause();
+                            }
+                            self.game = game;
+                            self.show_message("Resumed last game");
+                        }
+                        self.menu = MenuState::None;
+                    }
+                    MenuState::SaveSlots => {
+                        self.menu = MenuState::None;
+                        self.save_to_slot(self.menu_selection);
+                    }
+                    MenuState::LoadSlots => {
+                        self.menu = MenuState::None;
+                        self.load_slot(self.menu_selection);
+                    }
+                    MenuState::Export => {
+                        if let Some(&format) = ExportFormat::ALL.get(self.menu_selection) {
+                            self.export_game(format);
+                        }
+                        self.menu = MenuState::None;
+                    }
+                    MenuState::Copy => {
+                        if let Some(&target) = CopyTarget::ALL.get(self.menu_selection) {
+                            self.copy_to_clipboard(target);
+                        }
+                        self.menu = MenuState::None;
+                    }
+                    MenuState::Daily => self.start_daily(),
+                    MenuState::Trainer => {
                         self.menu = MenuState::None;
+                        self.start_trainer(self.menu_selection);
+                    }
+                    MenuState::Bookmarks => {
+                        self.menu = MenuState::None;
+                        self.restore_bookmark(self.menu_selection);
                     }
                     MenuState::Confirm => {
                         if self.menu_selection == 0 {
@@ -664,7 +1478,7 @@ impl App {
                         }
                         self.menu = MenuState::None;
                     }
-                    MenuState::None => {}
+                    MenuState::None | MenuState::Palette => {}
                 }
             }
 
@@ -674,6 +1488,196 @@ impl App {
         AppAction::Continue
     }
 
+    /// Bookmark the board, named after its number and the game time
+    fn bookmark_position(&mut self) {
+        let name = format!(
+            "Bookmark {} at {}",
+            self.game.bookmarks().len() + 1,
+            self.game.elapsed_string()
+        );
+        self.game.save_bookmark(&name);
+        self.show_message(&format!("Saved {}", name));
+    }
+
+    /// Put the board back as bookmark `index` left it (undoable)
+    fn restore_bookmark(&mut self, index: usize) {
+        let Some(name) = self.game.bookmarks().get(index).map(|b| b.name.clone()) else {
+            return;
+        };
+        if self.game.restore_bookmark(index) {
+            self.show_message(&format!("Back to {}", name));
+        }
+    }
+
+    /// Open the trainer for `TECHNIQUES[technique]`, pausing the game meanwhile
+    fn start_trainer(&mut self, technique: usize) {
+        let Some(trainer) = Trainer::new(technique) else {
+            self.show_message(&format!(
+                "No {} position found, try again",
+                trainer::TECHNIQUES[technique].0
+            ));
+            return;
+        };
+        self.trainer = Some(trainer);
+        self.screen_state = ScreenState::Trainer;
+        self.unpause_after_trainer = !self.game.is_paused() && !self.game.is_completed();
+        if self.unpause_after_trainer {
+            self.game.toggle_pause();
+        }
+    }
+
+    fn handle_trainer_key(&mut self, key: KeyEvent) -> AppAction {
+        let Some(trainer) = self.trainer.as_mut() else {
+            self.screen_state = ScreenState::Playing;
+            return AppAction::Continue;
+        };
+        let (row, col) = (trainer.cursor.row, trainer.cursor.col);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.trainer = None;
+                self.screen_state = ScreenState::Playing;
+                if self.unpause_after_trainer && self.game.is_paused() {
+                    self.game.toggle_pause();
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => trainer.cursor = Position::new((row + 8) % 9, col),
+            KeyCode::Down | KeyCode::Char('j') => {
+                trainer.cursor = Position::new((row + 1) % 9, col)
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                trainer.cursor = Position::new(row, (col + 8) % 9)
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                trainer.cursor = Position::new(row, (col + 1) % 9)
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                if let Some(correct) = trainer.answer(c as u8 - b'0') {
+                    let technique = trainer.technique();
+                    self.stats.record_trainer_answer(technique, correct);
+                    self.show_message(if correct { "Correct!" } else { "Not quite" });
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('n') => {
+                if !trainer.next_position() {
+                    self.show_message("No new position found, try again");
+                }
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Generate a new puzzle in the background; it starts when ready, and
+    /// the current screen stays up until then
+    fn generate(&mut self, difficulty: Difficulty, hotseat: bool, message: String) {
+        self.generating = Some(Generation::spawn(
+            difficulty,
+            hotseat,
+            message,
+            self.screen_state,
+            move || Game::new(difficulty),
+        ));
+        if self.a11y {
+            self.announcement = format!("Generating {} puzzle, Escape to cancel", difficulty);
+        }
+    }
+
+    /// Start playing a game from the background generator
+    fn start_generated(&mut self, mut game: Game, generation: Generation) {
+        // Don't count the time spent generating
+        game.reset_clock();
+        if !generation.hotseat {
+            game.set_timer_mode(self.timer_mode);
+            game.set_mode(self.game_mode);
+        }
+        game.set_settings(self.settings);
+
+        self.game = game;
+        self.cursor = Position::new(4, 4);
+        self.mode = InputMode::Normal;
+        self.screen_state = ScreenState::Playing;
+        self.begin_game();
+        self.hotseat = generation.hotseat.then(HotSeat::new);
+        self.celebrations.reset();
+        self.show_message(&generation.message);
+    }
+
+    /// Start a two-player game, player 1 to move
+    fn start_hotseat(&mut self, difficulty: Difficulty) {
+        let message = format!("Hot-seat {} game - Player 1 first", difficulty);
+        self.generate(difficulty, true, message);
+    }
+
+    /// Stop the clocks and show the results. Hot-seat games stay out of the
+    /// single-player stats.
+    fn finish_hotseat(&mut self) {
+        if let Some(hotseat) = self.hotseat.as_mut() {
+            hotseat.set_running(false);
+        }
+        self.game_recorded = true;
+        let _ = fs::remove_file(&self.save_path);
+        self.autosaved_moves = None;
+        self.screen_state = ScreenState::HotSeat;
+    }
+
+    fn handle_hotseat_key(&mut self, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Char('q') => return AppAction::Quit,
+            KeyCode::Enter | KeyCode::Char(' ') => self.start_hotseat(self.game.difficulty()),
+            KeyCode::Char('n') => {
+                self.screen_state = ScreenState::Playing;
+                self.menu = MenuState::NewGame;
+                self.menu_selection = 0;
+            }
+            KeyCode::Esc => self.screen_state = ScreenState::Playing,
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Position of the Daily entry in the new game menu (after the difficulties)
+    pub fn daily_menu_index(&self) -> usize {
+        if self.stats.secrets_unlocked() {
+            8
+        } else {
+            6
+        }
+    }
+
+    /// Start (or go back to) today's daily puzzle, unless it's already solved
+    fn start_daily(&mut self) {
+        let today = daily::today();
+        self.menu = MenuState::None;
+        if let Some(record) = self.stats.daily_result(today) {
+            let time = record.time_secs;
+            self.show_message(&format!(
+                "Today's daily is solved ({}:{:02}) - come back tomorrow",
+                time / 60,
+                time % 60
+            ));
+            return;
+        }
+        if self.game.daily() == Some(today) && !self.game.is_completed() {
+            return;
+        }
+
+        self.game = Game::new_daily(today);
+
+        self.game.set_timer_mode(self.timer_mode);
+        self.game.set_mode(self.game_mode);
+        self.game.set_settings(self.settings);
+        self.cursor = Position::new(4, 4);
+        self.screen_state = ScreenState::Playing;
+        self.begin_game();
+        self.hotseat = None;
+        self.celebrations.reset();
+        self.show_message(&format!(
+            "Daily {} ({})",
+            daily::format_date(today),
+            self.game.difficulty()
+        ));
+    }
+
     /// Get difficulty from current menu selection
     fn get_difficulty_from_selection(&self) -> Difficulty {
         if self.stats.secrets_unlocked() {
@@ -706,11 +1710,146 @@ impl App {
             }
             KeyCode::Char('b') => {
                 self.screen_state = ScreenState::Leaderboard;
+                self.refresh_global_rankings();
             }
             KeyCode::Char('H') if key.modifiers.contains(KeyModifiers::SHIFT) => {
                 self.history_scroll = 0;
                 self.screen_state = ScreenState::History;
             }
+            KeyCode::Char('a') => {
+                self.screen_state = ScreenState::Achievements;
+            }
+            KeyCode::Char('e') => self.export_stats(),
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    pub fn packs_dir(&self) -> &Path {
+        &self.packs_dir
+    }
+
+    /// (Re)load the packs directory and show the browser
+    fn open_packs(&mut self) {
+        let (packs, errors) = PackBrowser::load(&self.packs_dir);
+        self.packs = packs;
+        self.screen_state = ScreenState::Packs;
+        if let Some(error) = errors.first() {
+            self.show_message(error);
+        }
+    }
+
+    fn handle_packs_key(&mut self, key: KeyEvent) -> AppAction {
+        let browser = &mut self.packs;
+        match key.code {
+            KeyCode::Char('q') => {
+                self.screen_state = ScreenState::Playing;
+            }
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => {
+                match browser.open.take() {
+                    Some(pack) => browser.selection = pack,
+                    None => self.screen_state = ScreenState::Playing,
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                browser.selection = browser.selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if browser.selection + 1 < browser.row_count() {
+                    browser.selection += 1;
+                }
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => match browser.open {
+                Some(pack) => {
+                    let index = browser.selection;
+                    self.start_pack_puzzle(pack, index);
+                }
+                None if browser.selection < browser.packs.len() => {
+                    browser.open = Some(browser.selection);
+                    browser.selection = 0;
+                }
+                None => {}
+            },
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    fn handle_editor_key(&mut self, key: KeyEvent) -> AppAction {
+        let editor = &mut self.editor;
+        let cursor = &mut editor.cursor;
+        match key.code {
+            KeyCode::Esc => self.screen_state = ScreenState::Playing,
+            KeyCode::Up | KeyCode::Char('k') => cursor.row = cursor.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => cursor.row = (cursor.row + 1).min(8),
+            KeyCode::Left | KeyCode::Char('h') => cursor.col = cursor.col.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => cursor.col = (cursor.col + 1).min(8),
+            KeyCode::Char(c @ '1'..='9') => editor.set(c.to_digit(10).map(|d| d as u8)),
+            KeyCode::Char('0' | '.') | KeyCode::Backspace | KeyCode::Delete => editor.set(None),
+            KeyCode::Char('X') => editor.clear(),
+            KeyCode::Enter => self.play_edited_puzzle(),
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Lock the editor's grid as givens and start playing it
+    fn play_edited_puzzle(&mut self) {
+        if !matches!(self.editor.check, Check::Unique { .. }) {
+            let problem = self.editor.check.describe();
+            self.show_message(&format!("Can't play yet: {}", problem));
+            return;
+        }
+        let Some(mut game) = Game::from_string(&self.editor.puzzle_string()) else {
+            self.show_message("Puzzle is invalid or has no solution");
+            return;
+        };
+        game.set_timer_mode(self.timer_mode);
+        game.set_mode(self.game_mode);
+        game.set_settings(self.settings);
+
+        self.game = game;
+        self.cursor = Position::new(4, 4);
+        self.begin_game();
+        self.hotseat = None;
+        self.celebrations.reset();
+        self.screen_state = ScreenState::Playing;
+        self.show_message(&format!("Custom {} puzzle", self.game.difficulty()));
+    }
+
+    /// Start puzzle `index` of pack `pack` from the browser
+    fn start_pack_puzzle(&mut self, pack: usize, index: usize) {
+        let pack = &self.packs.packs[pack];
+        let Some(puzzle) = pack.puzzles.get(index) else {
+            return;
+        };
+        let Some(mut game) = Game::from_string(&puzzle.puzzle) else {
+            self.show_message("Puzzle is invalid or has no solution");
+            return;
+        };
+        game.set_pack(&pack.id, index);
+        game.set_timer_mode(self.timer_mode);
+        game.set_mode(self.game_mode);
+        game.set_settings(self.settings);
+        let label = format!("{} #{}", pack.name, index + 1);
+
+        self.game = game;
+        self.cursor = Position::new(4, 4);
+        self.begin_game();
+        self.hotseat = None;
+        self.celebrations.reset();
+        self.screen_state = ScreenState::Playing;
+        self.show_message(&label);
+    }
+
+    fn handle_achievements_key(&mut self, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.screen_state = ScreenState::Playing;
+            }
+            KeyCode::Char('i') => {
+                self.screen_state = ScreenState::Stats;
+            }
             _ => {}
         }
         AppAction::Continue
@@ -727,15 +1866,62 @@ impl App {
             // Change difficulty filter
             KeyCode::Left | KeyCode::Char('h') => {
                 self.leaderboard_difficulty = self.prev_difficulty(self.leaderboard_difficulty);
+                self.refresh_global_rankings();
             }
             KeyCode::Right | KeyCode::Char('l') => {
                 self.leaderboard_difficulty = self.next_difficulty(self.leaderboard_difficulty);
+                self.refresh_global_rankings();
+            }
+            // Cycle the mode category
+            KeyCode::Char('m') => {
+                self.leaderboard_mode = self.leaderboard_mode.next();
+            }
+            // Toggle local scores / global rankings
+            KeyCode::Char('g') if self.stats.online_enabled() => {
+                self.leaderboard_global = !self.leaderboard_global;
+                self.refresh_global_rankings();
             }
             _ => {}
         }
         AppAction::Continue
     }
 
+    /// Re-run the technique search if the grid changed since the last one
+    fn refresh_techniques(&mut self) {
+        let values = self.game.grid().to_string_compact();
+        if self.techniques_grid.as_ref() == Some(&values) {
+            return;
+        }
+        self.available_techniques = trainer::available_techniques(self.game.grid());
+        self.techniques_grid = Some(values);
+    }
+
+    /// Report the technique the position calls for next, once per grid
+    fn report_next_technique(&mut self) {
+        let values = self.game.grid().to_string_compact();
+        if self.encountered_grid.as_ref() == Some(&values) {
+            return;
+        }
+        if let Some(hint) = Solver::new().get_hint(self.game.grid()) {
+            self.analytics.record(Event::TechniqueEncountered {
+                technique: hint.technique.to_string(),
+            });
+        }
+        self.encountered_grid = Some(values);
+    }
+
+    /// Start fetching global rankings for the selected difficulty
+    fn refresh_global_rankings(&mut self) {
+        if !self.leaderboard_global {
+            return;
+        }
+        self.global_rankings = None;
+        self.rankings_rx = Some(
+            self.stats
+                .fetch_rankings(self.leaderboard_difficulty, GLOBAL_RANKINGS_LIMIT),
+        );
+    }
+
     fn prev_difficulty(&self, d: Difficulty) -> Difficulty {
         let levels = if self.stats.secrets_unlocked() {
             Difficulty::all_levels()
@@ -764,6 +1950,38 @@ impl App {
         }
     }
 
+    /// Move history keys: pick a point and jump there
+    fn handle_moves_key(&mut self, key: KeyEvent) -> AppAction {
+        let (applied, undone) = self.game.history();
+        let rows = applied.len() + undone.len() + 1;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.screen_state = ScreenState::Playing;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.moves_selection = self.moves_selection.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.moves_selection = (self.moves_selection + 1).min(rows - 1);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.moves_selection = 0,
+            KeyCode::End | KeyCode::Char('G') => self.moves_selection = rows - 1,
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let current = applied.len();
+                if self.game.jump_to(self.moves_selection) {
+                    self.screen_state = ScreenState::Playing;
+                    if self.moves_selection != current {
+                        self.show_message(&format!("Jumped to move {}", self.moves_selection));
+                    }
+                } else {
+                    self.show_message("Can't jump in a paused or finished game");
+                }
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
     fn handle_history_key(&mut self, key: KeyEvent) -> AppAction {
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc => {
@@ -779,10 +1997,14 @@ impl App {
             KeyCode::Enter | KeyCode::Char(' ') => {
                 // Replay selected game
                 if let Some(record) = self.stats.history.get(self.history_scroll) {
-                    if let Some(game) = Game::from_string(&record.puzzle) {
+                    if let Some(mut game) = Game::from_string(&record.puzzle) {
+                        game.set_timer_mode(self.timer_mode);
+                        game.set_mode(self.game_mode);
+                        game.set_settings(self.settings);
                         self.game = game;
                         self.cursor = Position::new(4, 4);
-                        self.game_recorded = false;
+                        self.begin_game();
+                        self.hotseat = None;
                         self.screen_state = ScreenState::Playing;
                         self.show_message("Replaying game");
                     }
@@ -800,6 +2022,7 @@ impl App {
         let new_row = (self.cursor.row as i32 + row_delta).clamp(0, 8) as usize;
         let new_col = (self.cursor.col as i32 + col_delta).clamp(0, 8) as usize;
         self.cursor = Position::new(new_row, new_col);
+        self.announce_cell();
     }
 
     fn jump_box(&mut self, row_delta: i32, col_delta: i32) {
@@ -811,20 +2034,23 @@ impl App {
 
         // Move to center of new box
         self.cursor = Position::new(new_box_row * 3 + 1, new_box_col * 3 + 1);
+        self.announce_cell();
     }
 
-    /// Get the save file path
-    fn save_path() -> PathBuf {
-        dirs::data_local_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("sudoku_save.json")
+    /// Open the save or load menu with fresh slot summaries
+    fn open_slots_menu(&mut self, menu: MenuState) {
+        self.slot_infos = slots::list();
+        self.menu = menu;
+        self.menu_selection = 0;
     }
 
-    /// Save the current game
-    fn save_game(&mut self) {
-        let json = self.game.serialize();
-        match fs::write(Self::save_path(), json) {
-            Ok(_) => self.show_message("Game saved"),
+    /// Save the current game to a slot, then share its code
+    fn save_to_slot(&mut self, index: usize) {
+        match slots::save(index, &self.game) {
+            Ok(_) => {
+                self.show_message(&format!("Saved to slot {}", index + 1));
+                self.share_puzzle();
+            }
             Err(_) => self.show_message("Failed to save"),
         }
     }
@@ -849,20 +2075,87 @@ impl App {
         }
     }
 
-    /// Load a saved game
-    fn load_game(&mut self) {
-        match fs::read_to_string(Self::save_path()) {
-            Ok(json) => {
-                if let Some(game) = Game::deserialize(&json) {
-                    self.game = game;
-                    self.cursor = Position::new(4, 4);
-                    self.screen_state = ScreenState::Playing;
-                    self.show_message("Game loaded");
-                } else {
-                    self.show_message("Invalid save file");
-                }
+    /// Save an unfinished game so it can be resumed after quitting or a
+    /// crash; returns whether it was saved. Input saves through
+    /// `autosave_on_change`, and the tick every `AUTOSAVE_INTERVAL`.
+    pub fn autosave(&mut self) -> bool {
+        let unfinished = self.screen_state == ScreenState::Playing
+            && !self.game.is_completed()
+            && !self.game.is_lost()
+            && self.game.moves_count() > 0;
+        // Don't overwrite the save while it is still on offer
+        if !unfinished || self.resume_game.is_some() {
+            return false;
+        }
+
+        let json = self.game.serialize();
+        if crate::persistence::atomic_write(&self.save_path, json.as_bytes()).is_err() {
+            return false;
+        }
+        let (applied, undone) = self.game.history();
+        self.autosaved_moves = Some((applied.len(), undone.len()));
+        self.autosaved_at = Instant::now();
+        true
+    }
+
+    /// Autosave after input, if it changed the move history since the last
+    /// save
+    fn autosave_on_change(&mut self) {
+        let (applied, undone) = self.game.history();
+        if self.autosaved_moves != Some((applied.len(), undone.len())) {
+            self.autosave();
+        }
+    }
+
+    /// Write the game in `format` to a file in the app data directory
+    fn export_game(&mut self, format: ExportFormat) {
+        let path = crate::persistence::app_data_dir().join(format.file_name());
+        let data = format.render(&self.game);
+        match crate::persistence::atomic_write(&path, data.as_bytes()) {
+            Ok(_) => self.show_message(&format!("Exported to {}", path.display())),
+            Err(_) => self.show_message("Failed to export"),
+        }
+    }
+
+    /// Copy part of the game to the system clipboard
+    fn copy_to_clipboard(&mut self, target: CopyTarget) {
+        let Some(text) = target.text(&self.game) else {
+            self.show_message("No short code for this puzzle");
+            return;
+        };
+        match clipboard::copy(&text) {
+            Ok(()) => self.show_message(&format!("Copied {}", target.label().to_lowercase())),
+            Err(e) => self.show_message(&format!("Copy failed: {}", e)),
+        }
+    }
+
+    /// Write all stats to a JSON file in the app data directory
+    fn export_stats(&mut self) {
+        let path = crate::persistence::app_data_dir().join("sudoku_stats_export.json");
+        let json = self.stats.export_json();
+        match crate::persistence::atomic_write(&path, json.as_bytes()) {
+            Ok(_) => self.show_message(&format!("Stats exported to {}", path.display())),
+            Err(_) => self.show_message("Failed to export stats"),
+        }
+    }
+
+    /// Load the game in a slot
+    fn load_slot(&mut self, index: usize) {
+        if self.slot_infos.get(index).is_some_and(Option::is_none) {
+            self.show_message("Slot is empty");
+            return;
+        }
+        match slots::load(index) {
+            Some(game) => {
+                self.game = game;
+                self.cursor = Position::new(4, 4);
+                self.screen_state = ScreenState::Playing;
+                self.begin_game();
+                self.hotseat = None;
+                self.celebrations.reset();
+                self.show_message(&format!("Loaded slot {} (p to resume)", index + 1));
             }
-            Err(_) => self.show_message("No save file found"),
+            None => self.show_message("Invalid save file"),
         }
     }
 
@@ -882,3 +2175,37 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_generation() {
+        let (go, wait) = mpsc::channel::<()>();
+        let generation = Generation::spawn(
+            Difficulty::Easy,
+            false,
+            String::new(),
+            ScreenState::Win,
+            move || {
+                let _ = wait.recv();
+                Game::new(Difficulty::Easy)
+            },
+        );
+        // Cancelled while the worker is still busy: back to the end screen,
+        // and the game it finishes is never delivered
+        assert_eq!(generation.cancel(), ScreenState::Win);
+        go.send(()).unwrap();
+        assert!(generation.rx.recv().is_err());
+
+        let generation = Generation::spawn(
+            Difficulty::Easy,
+            false,
+            String::new(),
+            ScreenState::Playing,
+            || Game::new(Difficulty::Easy),
+        );
+        assert!(generation.rx.recv().is_ok());
+    }
+}
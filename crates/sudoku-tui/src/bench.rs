@@ -0,0 +1,135 @@
+//! Headless benchmark (`--bench [n] [difficulty]`): generate, rate and hint
+//! puzzles and print how long each step took, without touching the screen.
+
+use crate::config::parse_difficulty;
+use std::time::{Duration, Instant};
+use sudoku_core::{Difficulty, PuzzleId, Solver};
+
+/// Puzzles to time when no count is given
+const DEFAULT_COUNT: usize = 20;
+
+/// Read the optional count and difficulty, in either order
+pub fn parse_args(args: &[String]) -> Result<(usize, Difficulty), String> {
+    let mut count = DEFAULT_COUNT;
+    let mut difficulty = Difficulty::Medium;
+    for arg in args {
+        if let Ok(n) = arg.parse::<usize>() {
+            if n == 0 {
+                return Err("benchmark needs at least one puzzle".to_string());
+            }
+            count = n;
+        } else {
+            difficulty =
+                parse_difficulty(arg).ok_or_else(|| format!("unknown difficulty \"{}\"", arg))?;
+        }
+    }
+    Ok((count, difficulty))
+}
+
+/// Spread of a set of timings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    /// Summarize timings; None if there are none
+    pub fn of(times: &[Duration]) -> Option<Self> {
+        let mut sorted = times.to_vec();
+        sorted.sort();
+        let last = sorted.len().checked_sub(1)?;
+        let total: Duration = sorted.iter().sum();
+        Some(Self {
+            min: sorted[0],
+            median: sorted[last / 2],
+            mean: total / sorted.len() as u32,
+            p95: sorted[last * 95 / 100],
+            max: sorted[last],
+        })
+    }
+
+    fn row(&self, label: &str) -> String {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        format!(
+            "{:<10} {:>9.2} {:>9.2} {:>9.2} {:>9.2} {:>9.2}",
+            label,
+            ms(self.min),
+            ms(self.median),
+            ms(self.mean),
+            ms(self.p95),
+            ms(self.max)
+        )
+    }
+}
+
+/// Time `count` puzzles of `difficulty` and print the results to stdout,
+/// with progress on stderr
+pub fn run(count: usize, difficulty: Difficulty) {
+    let solver = Solver::new();
+    let mut generate = Vec::with_capacity(count);
+    let mut rate = Vec::with_capacity(count);
+    let mut hint = Vec::with_capacity(count);
+    let started = Instant::now();
+
+    for i in 0..count {
+        eprint!("\r{}/{}", i + 1, count);
+
+        let t = Instant::now();
+        let mut grid = PuzzleId::random(difficulty).generate();
+        generate.push(t.elapsed());
+
+        // Same work as starting a game: difficulty and SE rating
+        let t = Instant::now();
+        solver.rate_difficulty(&grid);
+        solver.rate_se(&grid);
+        rate.push(t.elapsed());
+
+        // First hint on a fresh puzzle, as the player would ask for it
+        grid.recalculate_candidates();
+        let t = Instant::now();
+        solver.get_hint(&grid);
+        hint.push(t.elapsed());
+    }
+    eprintln!();
+
+    println!(
+        "{} {} puzzles in {:.2}s",
+        count,
+        difficulty,
+        started.elapsed().as_secs_f64()
+    );
+    println!(
+        "{:<10} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "ms", "min", "median", "mean", "p95", "max"
+    );
+    for (label, times) in [("generate", &generate), ("rate", &rate), ("hint", &hint)] {
+        if let Some(summary) = Summary::of(times) {
+            println!("{}", summary.row(label));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_args_and_summary() {
+        assert_eq!(parse_args(&[]), Ok((DEFAULT_COUNT, Difficulty::Medium)));
+        let args = ["hard".to_string(), "5".to_string()];
+        assert_eq!(parse_args(&args), Ok((5, Difficulty::Hard)));
+        assert!(parse_args(&["0".to_string()]).is_err());
+        assert!(parse_args(&["nope".to_string()]).is_err());
+
+        let times: Vec<_> = (1..=10).map(Duration::from_millis).collect();
+        let summary = Summary::of(&times).unwrap();
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.median, Duration::from_millis(5));
+        assert_eq!(summary.max, Duration::from_millis(10));
+        assert!(Summary::of(&[]).is_none());
+    }
+}
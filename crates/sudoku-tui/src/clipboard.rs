@@ -0,0 +1,76 @@
+//! Copying the puzzle to the system clipboard (needs the `clipboard` feature).
+
+use crate::export::ExportFormat;
+use crate::game::Game;
+
+/// What to copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// The original puzzle as an 81-character string
+    Puzzle,
+    /// The current values as an 81-character string
+    Position,
+    /// The current position as a pencilmark grid
+    Pencilmarks,
+    /// The 8-character short code of a generated puzzle
+    ShortCode,
+}
+
+impl CopyTarget {
+    /// All targets, in menu order
+    pub const ALL: [CopyTarget; 4] = [
+        CopyTarget::Puzzle,
+        CopyTarget::Position,
+        CopyTarget::Pencilmarks,
+        CopyTarget::ShortCode,
+    ];
+
+    /// Menu label
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyTarget::Puzzle => "Puzzle string",
+            CopyTarget::Position => "Current position",
+            CopyTarget::Pencilmarks => "Pencilmarks",
+            CopyTarget::ShortCode => "Short code",
+        }
+    }
+
+    /// Text to copy, or None if the game has no such thing (imported puzzles
+    /// have no short code)
+    pub fn text(self, game: &Game) -> Option<String> {
+        match self {
+            CopyTarget::Puzzle => Some(ExportFormat::Puzzle.render(game)),
+            CopyTarget::Position => Some(game.grid().to_string_compact()),
+            CopyTarget::Pencilmarks => Some(ExportFormat::Pencilmarks.render(game)),
+            CopyTarget::ShortCode => game.short_code(),
+        }
+    }
+}
+
+/// Put `text` on the system clipboard
+#[cfg(feature = "clipboard")]
+pub fn copy(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| e.to_string())
+}
+
+/// Put `text` on the system clipboard
+#[cfg(not(feature = "clipboard"))]
+pub fn copy(_text: &str) -> Result<(), String> {
+    Err("built without the `clipboard` feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_text() {
+        let puzzle =
+            "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+        let game = Game::from_string(puzzle).unwrap();
+        assert_eq!(CopyTarget::Position.text(&game).as_deref(), Some(puzzle));
+        assert_eq!(CopyTarget::ShortCode.text(&game), None);
+    }
+}
@@ -0,0 +1,480 @@
+//! User configuration loaded from `~/.config/sudoku-tui/config.toml`.
+//!
+//! Every setting is optional; anything missing keeps the built-in default.
+//!
+//! ```toml
+//! theme = "light"            # dark | light | high-contrast | solarized |
+//!                            # gruvbox | monochrome, or a [themes] entry
+//! difficulty = "hard"        # difficulty of the first game
+//! mode = "classic"           # classic | time-attack | hints | hardcore
+//! autosave_path = "/tmp/sudoku_save.json"
+//! packs_dir = "~/sudoku-packs"  # puzzle packs (default: packs/ next to this file)
+//! a11y = true                # screen-reader line mode (same as --a11y)
+//! a11y_board = "braille"     # board in that mode: lines | braille | large
+//! analytics = true           # share anonymous gameplay events (off by default)
+//!
+//! [assist]
+//! show_suggestions = true    # "Valid:" candidates in the info panel
+//! ghost_hints = false        # show naked singles in empty cells
+//! techniques = false         # panel listing the techniques that apply now
+//! auto_notes = false         # keep notes filled in and pruned (toggle: N)
+//! mistakes = "3"             # mistakes that lose a game, or count | off
+//! highlight_same_digit = true  # shade cells holding the digit under the cursor
+//! show_timer = true
+//! explanations = "brief"     # hint text: brief | standard | step-by-step
+//!
+//! [themes.dusk]             # custom theme: a base plus any element colors
+//! base = "gruvbox"
+//! given = "#ffd75f"          # "#rrggbb" or a name like "dark_cyan"
+//! selected_bg = "dark_blue"
+//!
+//! [timer]
+//! mode = "countdown"         # standard | countdown | speedrun
+//! countdown_minutes = 10
+//!
+//! [keys]
+//! preset = "vim"             # default | vim
+//! undo = "z"                 # one key or a list: hint = ["?", "F1"]
+//! corner = "C"               # corner-mark mode (notes = center notes)
+//! history = "U"              # move history, to jump back to any point
+//! help = "F1"                # list of every key
+//! palette = ":"              # command palette
+//!
+//! [online]                   # needs a build with the `online` feature
+//! endpoint = "https://scores.example.com"
+//! api_key = "..."
+//! ```
+
+use crate::a11y::BoardText;
+use crate::keymap::{Action, KeyBinding, Keymap};
+use crate::leaderboard::RemoteConfig;
+use crate::theme::{Theme, PRESETS};
+use crate::timer::{TimerMode, DEFAULT_COUNTDOWN_SECS};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sudoku_core::Difficulty;
+use sudoku_glossary::Verbosity;
+use sudoku_session::{GameMode, GameSettings, MistakePolicy};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Theme name
+    pub theme: Option<String>,
+    /// User-defined themes by name
+    pub themes: HashMap<String, ThemeConfig>,
+    /// Difficulty name for the first game
+    pub difficulty: Option<String>,
+    /// Game mode for new games, by name
+    pub mode: Option<String>,
+    /// Assist toggles
+    pub assist: AssistConfig,
+    /// Where the unfinished game is autosaved for resuming
+    pub autosave_path: Option<PathBuf>,
+    /// Directory of puzzle packs
+    pub packs_dir: Option<PathBuf>,
+    /// Plain-text line mode for screen readers
+    pub a11y: bool,
+    /// How line mode writes the board, by name
+    pub a11y_board: Option<String>,
+    /// Clock mode for new games
+    pub timer: TimerConfig,
+    /// Key bindings
+    pub keys: KeysConfig,
+    /// Remote leaderboard
+    pub online: OnlineConfig,
+    /// Opt in to anonymous gameplay analytics
+    pub analytics: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct AssistConfig {
+    pub show_suggestions: bool,
+    pub ghost_hints: bool,
+    pub techniques: bool,
+    pub auto_notes: bool,
+    /// Mistake policy, by name
+    pub mistakes: Option<String>,
+    pub highlight_same_digit: bool,
+    pub show_timer: bool,
+    /// Hint text length, by name
+    pub explanations: Option<String>,
+}
+
+impl Default for AssistConfig {
+    fn default() -> Self {
+        Self {
+            show_suggestions: true,
+            ghost_hints: false,
+            techniques: false,
+            auto_notes: false,
+            mistakes: None,
+            highlight_same_digit: true,
+            show_timer: true,
+            explanations: None,
+        }
+    }
+}
+
+impl AssistConfig {
+    /// Assists for new games
+    pub fn settings(&self) -> GameSettings {
+        let defaults = GameSettings::default();
+        GameSettings {
+            auto_candidates: self.auto_notes,
+            mistakes: self
+                .mistakes
+                .as_deref()
+                .and_then(MistakePolicy::from_name)
+                .unwrap_or(defaults.mistakes),
+            highlight_same_digit: self.highlight_same_digit,
+            show_timer: self.show_timer,
+            ..defaults
+        }
+    }
+}
+
+/// A `[themes.<name>]` table
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Built-in theme to start from (default "dark")
+    pub base: Option<String>,
+    /// Element name to color
+    #[serde(flatten)]
+    pub colors: HashMap<String, String>,
+}
+
+/// `[timer]` table
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TimerConfig {
+    pub mode: Option<String>,
+    pub countdown_minutes: Option<u64>,
+}
+
+/// `[online]` table: where wins are submitted and rankings fetched from
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OnlineConfig {
+    pub endpoint: Option<String>,
+    pub api_key: Option<String>,
+    pub timeout_secs: Option<u64>,
+}
+
+/// `[keys]` table: a preset plus per-action overrides
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub preset: Option<String>,
+    #[serde(flatten)]
+    pub bindings: HashMap<String, KeyList>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeysConfig {
+    /// Build the keymap, failing on unknown presets, actions or keys
+    fn build(&self) -> Result<Keymap, String> {
+        let mut keymap = match &self.preset {
+            Some(name) => {
+                Keymap::preset(name).ok_or_else(|| format!("unknown key preset \"{}\"", name))?
+            }
+            None => Keymap::default(),
+        };
+        for (name, list) in &self.bindings {
+            let action =
+                Action::from_name(name).ok_or_else(|| format!("unknown action \"{}\"", name))?;
+            let names = match list {
+                KeyList::One(key) => std::slice::from_ref(key),
+                KeyList::Many(keys) => keys.as_slice(),
+            };
+            let keys = names
+                .iter()
+                .map(|k| KeyBinding::parse(k).ok_or_else(|| format!("unknown key \"{}\"", k)))
+                .collect::<Result<Vec<_>, _>>()?;
+            keymap.rebind(action, &keys);
+        }
+        Ok(keymap)
+    }
+}
+
+impl Config {
+    /// Config directory (`~/.config/sudoku-tui`), also home to save slots
+    pub fn dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("sudoku-tui"))
+    }
+
+    /// Default config file location
+    pub fn default_path() -> Option<PathBuf> {
+        Self::dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Load the config. An explicit `path` must exist; the default file is
+    /// optional. Unknown theme or difficulty names are errors.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let (path, required) = match path {
+            Some(p) => (p.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(p) => (p, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !required && !path.exists() => return Ok(Self::default()),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let config = Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    /// Parse and validate config text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        let themes = config.build_themes()?;
+        if let Some(name) = &config.theme {
+            if Theme::by_name(name).is_none() && !themes.iter().any(|(n, _)| n == name) {
+                return Err(format!("unknown theme \"{}\"", name));
+            }
+        }
+        if let Some(name) = &config.difficulty {
+            parse_difficulty(name).ok_or_else(|| format!("unknown difficulty \"{}\"", name))?;
+        }
+        if let Some(name) = &config.mode {
+            GameMode::from_name(name).ok_or_else(|| format!("unknown game mode \"{}\"", name))?;
+        }
+        if let Some(name) = &config.timer.mode {
+            TimerMode::from_name(name, 0)
+                .ok_or_else(|| format!("unknown timer mode \"{}\"", name))?;
+        }
+        if let Some(name) = &config.assist.mistakes {
+            MistakePolicy::from_name(name)
+                .ok_or_else(|| format!("unknown mistake policy \"{}\"", name))?;
+        }
+        if config.timer.countdown_minutes == Some(0) {
+            return Err("countdown_minutes must be at least 1".to_string());
+        }
+        config.keys.build()?;
+        if let Some(endpoint) = &config.online.endpoint {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                return Err(format!(
+                    "online endpoint \"{}\" is not an http(s) URL",
+                    endpoint
+                ));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Configured theme, or the default
+    pub fn theme(&self) -> Theme {
+        let Some(name) = self.theme.as_deref() else {
+            return Theme::default();
+        };
+        Theme::by_name(name)
+            .or_else(|| {
+                self.themes()
+                    .into_iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, theme)| theme)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Themes for the theme menu: the presets, then custom themes by name
+    pub fn themes(&self) -> Vec<(String, Theme)> {
+        let mut themes: Vec<(String, Theme)> = PRESETS
+            .iter()
+            .filter_map(|&(name, label)| Some((label.to_string(), Theme::by_name(name)?)))
+            .collect();
+        themes.extend(self.build_themes().unwrap_or_default());
+        themes
+    }
+
+    /// Build the custom themes, failing on unknown bases, elements or colors
+    fn build_themes(&self) -> Result<Vec<(String, Theme)>, String> {
+        let mut names: Vec<&String> = self.themes.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| {
+                let custom = &self.themes[name];
+                let theme = Theme::custom(custom.base.as_deref(), &custom.colors)
+                    .map_err(|e| format!("theme \"{}\": {}", name, e))?;
+                Ok((name.clone(), theme))
+            })
+            .collect()
+    }
+
+    /// Configured starting difficulty, or Medium
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+            .as_deref()
+            .and_then(parse_difficulty)
+            .unwrap_or(Difficulty::Medium)
+    }
+
+    /// Configured game mode for new games, or classic
+    pub fn game_mode(&self) -> GameMode {
+        self.mode
+            .as_deref()
+            .and_then(GameMode::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Countdown length in seconds
+    pub fn countdown_secs(&self) -> u64 {
+        self.timer
+            .countdown_minutes
+            .map_or(DEFAULT_COUNTDOWN_SECS, |m| m * 60)
+    }
+
+    /// Configured timer mode for new games, or the standard clock
+    pub fn timer_mode(&self) -> TimerMode {
+        self.timer
+            .mode
+            .as_deref()
+            .and_then(|name| TimerMode::from_name(name, self.countdown_secs()))
+            .unwrap_or_default()
+    }
+
+    /// Configured board style for line mode, or spoken-style lines
+    pub fn a11y_board(&self) -> BoardText {
+        self.a11y_board
+            .as_deref()
+            .and_then(BoardText::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Configured hint text length, or the solver's own explanation
+    pub fn explanations(&self) -> Verbosity {
+        self.assist
+            .explanations
+            .as_deref()
+            .and_then(Verbosity::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Configured key bindings, or the default preset
+    pub fn keymap(&self) -> Keymap {
+        self.keys.build().unwrap_or_default()
+    }
+
+    /// Remote leaderboard settings, if an endpoint is configured
+    pub fn remote_leaderboard(&self) -> Option<RemoteConfig> {
+        let endpoint = self.online.endpoint.as_ref()?;
+        let defaults = RemoteConfig::default();
+        Some(RemoteConfig {
+            base_url: endpoint.clone(),
+            api_key: self.online.api_key.clone(),
+            timeout_secs: self.online.timeout_secs.unwrap_or(defaults.timeout_secs),
+        })
+    }
+
+    /// Configured pack directory, or `packs` in the config directory
+    pub fn packs_dir(&self) -> PathBuf {
+        self.packs_dir
+            .clone()
+            .or_else(|| Some(Self::dir()?.join("packs")))
+            .unwrap_or_else(|| PathBuf::from("packs"))
+    }
+
+    /// Configured save file, or the default in the local data directory
+    pub fn save_path(&self) -> PathBuf {
+        self.autosave_path.clone().unwrap_or_else(|| {
+            dirs::data_local_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("sudoku_save.json")
+        })
+    }
+}
+
+/// Match a difficulty by name, ignoring case
+pub fn parse_difficulty(name: &str) -> Option<Difficulty> {
+    Difficulty::all_levels()
+        .iter()
+        .copied()
+        .find(|d| d.to_string().eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            "theme = \"light\"\ndifficulty = \"HARD\"\n\n[assist]\nghost_hints = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.difficulty(), Difficulty::Hard);
+        assert!(config.assist.show_suggestions);
+        assert!(config.assist.ghost_hints);
+
+        let empty = Config::parse("").unwrap();
+        assert_eq!(empty.difficulty(), Difficulty::Medium);
+        assert_eq!(empty.game_mode(), GameMode::Classic);
+        assert!(Config::parse("mode = \"sprint\"\n").is_err());
+        assert!(empty.remote_leaderboard().is_none());
+
+        let online =
+            Config::parse("[online]\nendpoint = \"https://scores.example.com\"\n").unwrap();
+        let remote = online.remote_leaderboard().unwrap();
+        assert_eq!(remote.base_url, "https://scores.example.com");
+        assert_eq!(remote.timeout_secs, 10);
+
+        let timer =
+            Config::parse("[timer]\nmode = \"countdown\"\ncountdown_minutes = 5\n").unwrap();
+        assert_eq!(timer.timer_mode(), TimerMode::Countdown { limit_secs: 300 });
+
+        let assist = Config::parse("[assist]\nmistakes = \"count\"\nshow_timer = false\n").unwrap();
+        let settings = assist.assist.settings();
+        assert_eq!(settings.mistakes, MistakePolicy::Count);
+        assert!(!settings.show_timer && settings.highlight_same_digit);
+    }
+
+    #[test]
+    fn test_rejects_unknown_names() {
+        assert!(Config::parse("theme = \"neon\"").is_err());
+        assert!(Config::parse("difficulty = \"impossible\"").is_err());
+        assert!(Config::parse("theme = 3").is_err());
+        assert!(Config::parse("[themes.x]\nbase = \"neon\"").is_err());
+        assert!(Config::parse("[themes.x]\ngiven = \"sparkly\"").is_err());
+        assert!(Config::parse("[keys]\npreset = \"emacs\"").is_err());
+        assert!(Config::parse("[keys]\nfly = \"f\"").is_err());
+        assert!(Config::parse("[keys]\nundo = \"nope\"").is_err());
+        assert!(Config::parse("[online]\nendpoint = \"scores.example.com\"").is_err());
+        assert!(Config::parse("[timer]\nmode = \"sundial\"").is_err());
+        assert!(Config::parse("[assist]\nmistakes = \"lots\"").is_err());
+    }
+
+    #[test]
+    fn test_custom_theme_selected() {
+        let config =
+            Config::parse("theme = \"dusk\"\n\n[themes.dusk]\nbase = \"light\"\nkey = \"red\"\n")
+                .unwrap();
+        let theme = config.theme();
+        assert_eq!(theme.key, crossterm::style::Color::Red);
+        assert_eq!(theme.bg, Theme::light().bg);
+        assert_eq!(
+            config.themes().last().map(|(n, _)| n.as_str()),
+            Some("dusk")
+        );
+    }
+
+    #[test]
+    fn test_key_overrides() {
+        let config = Config::parse("[keys]\npreset = \"vim\"\nhint = [\"F1\", \"?\"]\n").unwrap();
+        assert_eq!(config.keymap().label(crate::keymap::Action::Hint), "F1");
+    }
+}
@@ -0,0 +1,138 @@
+//! Puzzle editor: type givens into an empty grid, checked live for clashes,
+//! solution count and rating before playing it.
+
+use crate::a11y;
+use sudoku_core::{Difficulty, Grid, Position, Solver};
+
+/// Result of checking the givens entered so far
+#[derive(Debug, Clone, PartialEq)]
+pub enum Check {
+    /// Two givens share a unit; the first clashing cell
+    Clash(Position),
+    NoSolution,
+    MultipleSolutions,
+    Unique {
+        difficulty: Difficulty,
+        se_rating: f32,
+    },
+}
+
+impl Check {
+    /// One-line summary for the status line
+    pub fn describe(&self) -> String {
+        match self {
+            Check::Clash(pos) => format!("{} clashes with another given", a11y::cell_name(*pos)),
+            Check::NoSolution => "No solution".to_string(),
+            Check::MultipleSolutions => "More than one solution".to_string(),
+            Check::Unique {
+                difficulty,
+                se_rating,
+            } => format!("Unique solution - {} (SE {:.1})", difficulty, se_rating),
+        }
+    }
+}
+
+/// The grid being edited
+#[derive(Debug, Clone)]
+pub struct Editor {
+    pub cells: [[Option<u8>; 9]; 9],
+    pub cursor: Position,
+    pub check: Check,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Editor {
+    /// An empty grid
+    pub fn new() -> Self {
+        Self {
+            cells: [[None; 9]; 9],
+            cursor: Position::new(4, 4),
+            check: Check::MultipleSolutions,
+        }
+    }
+
+    /// Set or clear the cell under the cursor and re-check the grid
+    pub fn set(&mut self, value: Option<u8>) {
+        let Position { row, col } = self.cursor;
+        if self.cells[row][col] != value {
+            self.cells[row][col] = value;
+            self.check = self.run_check();
+        }
+    }
+
+    /// Clear every cell
+    pub fn clear(&mut self) {
+        *self = Self {
+            cursor: self.cursor,
+            ..Self::new()
+        };
+    }
+
+    /// Number of givens entered
+    pub fn clues(&self) -> usize {
+        self.cells.iter().flatten().filter(|v| v.is_some()).count()
+    }
+
+    /// The givens as an 81-character puzzle string
+    pub fn puzzle_string(&self) -> String {
+        self.cells
+            .iter()
+            .flatten()
+            .map(|v| v.map_or('.', |v| (b'0' + v) as char))
+            .collect()
+    }
+
+    fn run_check(&self) -> Check {
+        for row in 0..9 {
+            for col in 0..9 {
+                let pos = Position::new(row, col);
+                if let Some(value) = self.cells[row][col] {
+                    if !a11y::conflicts(&self.cells, pos, value).is_empty() {
+                        return Check::Clash(pos);
+                    }
+                }
+            }
+        }
+        let Some(grid) = Grid::from_string(&self.puzzle_string()) else {
+            return Check::NoSolution;
+        };
+        let solver = Solver::new();
+        match solver.count_solutions(&grid, 2) {
+            0 => Check::NoSolution,
+            1 => Check::Unique {
+                difficulty: solver.rate_difficulty(&grid),
+                se_rating: solver.rate_se(&grid),
+            },
+            _ => Check::MultipleSolutions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_check() {
+        let mut editor = Editor::new();
+        editor.cursor = Position::new(0, 0);
+        editor.set(Some(5));
+        assert_eq!(editor.check, Check::MultipleSolutions);
+        assert_eq!(editor.clues(), 1);
+        assert!(editor.puzzle_string().starts_with("5."));
+
+        editor.cursor = Position::new(0, 8);
+        editor.set(Some(5));
+        assert_eq!(editor.check, Check::Clash(Position::new(0, 0)));
+
+        editor.set(None);
+        assert_eq!(editor.check, Check::MultipleSolutions);
+        editor.clear();
+        assert_eq!(editor.clues(), 0);
+    }
+}
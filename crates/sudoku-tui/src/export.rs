@@ -0,0 +1,135 @@
+//! Exporting the current game in formats the web and mobile apps can load.
+
+use crate::game::Game;
+use clap::ValueEnum;
+use sudoku_core::Position;
+
+/// What to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// The original puzzle as an 81-character string
+    Puzzle,
+    /// The current position as a pencilmark grid
+    Pencilmarks,
+    /// The full save file (JSON)
+    Save,
+}
+
+impl ExportFormat {
+    /// All formats, in menu order
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Puzzle,
+        ExportFormat::Pencilmarks,
+        ExportFormat::Save,
+    ];
+
+    /// Menu label
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Puzzle => "Puzzle string",
+            ExportFormat::Pencilmarks => "Pencilmarks",
+            ExportFormat::Save => "Full save",
+        }
+    }
+
+    /// File name used when exporting from the menu
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ExportFormat::Puzzle => "export_puzzle.txt",
+            ExportFormat::Pencilmarks => "export_pencilmarks.txt",
+            ExportFormat::Save => "export_save.json",
+        }
+    }
+
+    /// Render the game in this format
+    pub fn render(self, game: &Game) -> String {
+        match self {
+            ExportFormat::Puzzle => game.original_puzzle().to_string(),
+            ExportFormat::Pencilmarks => pencilmarks(game),
+            ExportFormat::Save => game.serialize(),
+        }
+    }
+}
+
+/// Pencilmark grid: each cell lists its value, or the player's notes (all
+/// valid candidates if the cell has none), padded so columns line up
+fn pencilmarks(game: &Game) -> String {
+    let grid = game.grid();
+    let cells: Vec<String> = (0..81)
+        .map(|i| {
+            let pos = Position::new(i / 9, i % 9);
+            let cell = grid.cell(pos);
+            if let Some(value) = cell.value() {
+                return value.to_string();
+            }
+            let notes = cell.candidates();
+            let digits = if notes.is_empty() {
+                grid.get_candidates(pos)
+            } else {
+                notes
+            };
+            digits.iter().map(|d| d.to_string()).collect()
+        })
+        .collect();
+
+    let width = cells.iter().map(String::len).max().unwrap_or(1);
+    let separator = vec!["-".repeat(width * 3 + 4); 3].join("+");
+    let mut out = String::new();
+    for row in 0..9 {
+        if row > 0 && row % 3 == 0 {
+            out.push_str(&separator);
+            out.push('\n');
+        }
+        let line: Vec<String> = (0..3)
+            .map(|band| {
+                let start = row * 9 + band * 3;
+                let group: Vec<String> = cells[start..start + 3]
+                    .iter()
+                    .map(|c| format!("{:<width$}", c, width = width))
+                    .collect();
+                format!(" {} ", group.join(" "))
+            })
+            .collect();
+        out.push_str(line.join("|").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_export_formats() {
+        let mut game = Game::from_string(PUZZLE).unwrap();
+        game.clear_all_candidates();
+        game.toggle_candidate(Position::new(0, 2), 1);
+        game.toggle_candidate(Position::new(0, 2), 2);
+        assert_eq!(ExportFormat::Puzzle.render(&game), PUZZLE);
+
+        // Three bands of three rows, split by two separator lines
+        let marks = ExportFormat::Pencilmarks.render(&game);
+        let lines: Vec<&str> = marks.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert!(lines[3].starts_with('-') && lines[7].starts_with('-'));
+        let cells: Vec<&str> = lines
+            .iter()
+            .filter(|line| !line.starts_with('-'))
+            .flat_map(|line| line.split(|c: char| c == '|' || c.is_whitespace()))
+            .filter(|cell| !cell.is_empty())
+            .collect();
+        assert_eq!(cells.len(), 81);
+        assert_eq!(&cells[..2], ["5", "3"]);
+        // The player's notes, then every valid candidate where there are none
+        assert_eq!(cells[2], "12");
+        assert_eq!(cells[3], "26");
+
+        let save = ExportFormat::Save.render(&game);
+        let restored = Game::deserialize(&save).unwrap();
+        assert_eq!(restored.original_puzzle(), PUZZLE);
+    }
+}
@@ -1,6 +1,8 @@
+use crate::timer::{Splits, TimerMode};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 use sudoku_core::{Difficulty, Grid, Hint, Position, PuzzleId, Solver};
+use sudoku_session::{Bookmark, ConflictKind, GameMode, GameSettings, Score};
 
 /// A single move in the game (for undo/redo)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +29,93 @@ pub enum GameMove {
         old_candidates: u16,
         new_candidates: u16,
     },
+    ToggleCorner {
+        pos: Position,
+        value: u8,
+    },
+    SetCornerMarks {
+        pos: Position,
+        old_marks: u16,
+        new_marks: u16,
+    },
+    /// A cell's value and notes together, as going back to a bookmark sets
+    /// them; unlike `SetValue` it leaves other cells' notes alone
+    SetCell {
+        pos: Position,
+        old_value: Option<u8>,
+        new_value: Option<u8>,
+        old_candidates: u16,
+        new_candidates: u16,
+    },
+}
+
+impl GameMove {
+    fn pos(&self) -> Position {
+        match *self {
+            GameMove::SetValue { pos, .. }
+            | GameMove::ToggleCandidate { pos, .. }
+            | GameMove::AddCandidate { pos, .. }
+            | GameMove::RemoveCandidate { pos, .. }
+            | GameMove::SetCandidates { pos, .. }
+            | GameMove::ToggleCorner { pos, .. }
+            | GameMove::SetCornerMarks { pos, .. }
+            | GameMove::SetCell { pos, .. } => pos,
+        }
+    }
+
+    /// Short description for the move history, e.g. "r3c5 = 7"
+    pub fn describe(&self) -> String {
+        let cell = format!("r{}c{}", self.pos().row + 1, self.pos().col + 1);
+        match self {
+            GameMove::SetValue {
+                new_value: Some(v), ..
+            } => format!("{} = {}", cell, v),
+            GameMove::SetValue { .. } => format!("{} cleared", cell),
+            GameMove::ToggleCandidate { value, .. } => format!("{} note {}", cell, value),
+            GameMove::AddCandidate { value, .. } => format!("{} note +{}", cell, value),
+            GameMove::RemoveCandidate { value, .. } => format!("{} note -{}", cell, value),
+            GameMove::SetCandidates {
+                new_candidates: 0, ..
+            } => format!("{} notes cleared", cell),
+            GameMove::SetCandidates { .. } => format!("{} notes filled", cell),
+            GameMove::ToggleCorner { value, .. } => format!("{} corner {}", cell, value),
+            GameMove::SetCornerMarks { .. } => format!("{} corners cleared", cell),
+            GameMove::SetCell { .. } => format!("{} restored", cell),
+        }
+    }
+}
+
+/// One undo step: the moves made by a single action (filling every cell's
+/// notes is one entry), and when it happened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub moves: Vec<GameMove>,
+    /// Game time of the action in milliseconds
+    pub at_ms: u64,
+    /// For a placement, the technique the solver could justify it with
+    #[serde(default)]
+    pub technique: Option<String>,
+}
+
+impl UndoEntry {
+    /// Short description for the move history
+    pub fn describe(&self) -> String {
+        match self.moves.as_slice() {
+            [single] => match &self.technique {
+                Some(technique) => format!("{} ({})", single.describe(), technique),
+                None => single.describe(),
+            },
+            moves => match moves.first() {
+                Some(GameMove::SetCandidates {
+                    new_candidates: 0, ..
+                }) => format!("notes cleared in {} cells", moves.len()),
+                Some(GameMove::SetCell { .. }) => {
+                    format!("back to a bookmark ({} cells)", moves.len())
+                }
+                _ => format!("notes filled in {} cells", moves.len()),
+            },
+        }
+    }
 }
 
 /// The game state
@@ -40,10 +129,10 @@ pub struct Game {
     original_puzzle: String,
     /// Difficulty level
     difficulty: Difficulty,
-    /// Undo stack
-    undo_stack: Vec<GameMove>,
-    /// Redo stack
-    redo_stack: Vec<GameMove>,
+    /// Undo stack, oldest first
+    undo_stack: Vec<UndoEntry>,
+    /// Redo stack, next redo last
+    redo_stack: Vec<UndoEntry>,
     /// Start time
     start_time: Instant,
     /// Elapsed time (for pause/resume)
@@ -66,6 +155,30 @@ pub struct Game {
     seed: Option<u64>,
     /// Cached SE (Sudoku Explainer) rating
     se_rating: f32,
+    /// Day number if this is a daily puzzle
+    daily: Option<u64>,
+    /// Corner (Snyder) marks per cell as digit bits, row-major. The grid's
+    /// candidates are the center notes.
+    corner_marks: [u16; 81],
+    /// Clock behaviour
+    timer_mode: TimerMode,
+    /// Speedrun split times
+    splits: Splits,
+    /// Pack id and puzzle index if launched from a puzzle pack
+    pack: Option<(String, usize)>,
+    /// Assists and mistake rules; auto-candidates keeps center notes
+    /// computed and pruned as values change
+    settings: GameSettings,
+    /// Notes the player added by hand in auto-notes mode, as candidate bits
+    manual_notes: [u16; 81],
+    /// Notes the player removed by hand in auto-notes mode, kept off
+    struck_notes: [u16; 81],
+    /// Points earned and lost, scored as on the other frontends
+    score: Score,
+    /// How the game is won and which leaderboard it counts for
+    mode: GameMode,
+    /// Named snapshots of the values and center notes to come back to
+    bookmarks: Vec<Bookmark>,
 }
 
 impl Game {
@@ -100,14 +213,32 @@ impl Game {
             completed: false,
             hints_used: 0,
             mistakes: 0,
+            score: Score::default(),
             last_move_time: now,
             move_times_ms: Vec::new(),
             notes_used: false,
             seed: Some(puzzle_id.seed),
             se_rating,
+            daily: None,
+            corner_marks: [0; 81],
+            timer_mode: TimerMode::Standard,
+            splits: Splits::default(),
+            pack: None,
+            settings: GameSettings::default(),
+            manual_notes: [0; 81],
+            struck_notes: [0; 81],
+            mode: GameMode::Classic,
+            bookmarks: Vec::new(),
         }
     }
 
+    /// Create the daily puzzle for a day number
+    pub fn new_daily(day: u64) -> Self {
+        let mut game = Self::new_with_id(&sudoku_daily::puzzle_id(day));
+        game.daily = Some(day);
+        game
+    }
+
     /// Create a game from a PuzzleId
     pub fn new_with_id(id: &PuzzleId) -> Self {
         let mut grid = id.generate();
@@ -138,11 +269,22 @@ impl Game {
             completed: false,
             hints_used: 0,
             mistakes: 0,
+            score: Score::default(),
             last_move_time: now,
             move_times_ms: Vec::new(),
             notes_used: false,
             seed: Some(id.seed),
             se_rating,
+            daily: None,
+            corner_marks: [0; 81],
+            timer_mode: TimerMode::Standard,
+            splits: Splits::default(),
+            pack: None,
+            settings: GameSettings::default(),
+            manual_notes: [0; 81],
+            struck_notes: [0; 81],
+            mode: GameMode::Classic,
+            bookmarks: Vec::new(),
         }
     }
 
@@ -168,11 +310,22 @@ impl Game {
             completed: false,
             hints_used: 0,
             mistakes: 0,
+            score: Score::default(),
             last_move_time: now,
             move_times_ms: Vec::new(),
             notes_used: false,
             seed: None,
             se_rating,
+            daily: None,
+            corner_marks: [0; 81],
+            timer_mode: TimerMode::Standard,
+            splits: Splits::default(),
+            pack: None,
+            settings: GameSettings::default(),
+            manual_notes: [0; 81],
+            struck_notes: [0; 81],
+            mode: GameMode::Classic,
+            bookmarks: Vec::new(),
         })
     }
 
@@ -203,6 +356,83 @@ impl Game {
         format!("{:02}:{:02}", mins, secs)
     }
 
+    /// The clock as shown in play: time left for a countdown, else elapsed
+    pub fn clock_string(&self) -> String {
+        match self.remaining() {
+            Some(left) => {
+                let secs = left.as_secs();
+                format!("-{:02}:{:02}", secs / 60, secs % 60)
+            }
+            None => self.elapsed_string(),
+        }
+    }
+
+    /// Set the clock behaviour (before the first move)
+    pub fn set_timer_mode(&mut self, mode: TimerMode) {
+        self.timer_mode = mode;
+    }
+
+    pub fn timer_mode(&self) -> TimerMode {
+        self.timer_mode
+    }
+
+    /// Play under `mode` (before the first move, after the timer mode).
+    /// Time attack counts down from its target; Hardcore wipes every note
+    /// and turns auto-notes off.
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.mode = mode;
+        if let GameMode::TimeAttack { target_secs } = mode {
+            self.timer_mode = TimerMode::Countdown {
+                limit_secs: target_secs,
+            };
+        }
+        if !mode.allows_notes() {
+            self.set_auto_notes(false);
+            self.grid.clear_all_candidates();
+            self.corner_marks = [0; 81];
+        }
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Whether the mode leaves a hint to use
+    pub fn can_hint(&self) -> bool {
+        self.mode
+            .hint_limit()
+            .is_none_or(|limit| self.hints_used < limit as usize)
+    }
+
+    /// Time left on a countdown
+    pub fn remaining(&self) -> Option<Duration> {
+        match self.timer_mode {
+            TimerMode::Countdown { limit_secs } => {
+                Some(Duration::from_secs(limit_secs).saturating_sub(self.elapsed()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a countdown has run out
+    pub fn is_time_up(&self) -> bool {
+        !self.completed && self.remaining() == Some(Duration::ZERO)
+    }
+
+    /// Note that this game is puzzle `index` of pack `id`
+    pub fn set_pack(&mut self, id: &str, index: usize) {
+        self.pack = Some((id.to_string(), index));
+    }
+
+    pub fn pack(&self) -> Option<(&str, usize)> {
+        self.pack.as_ref().map(|(id, i)| (id.as_str(), *i))
+    }
+
+    /// Speedrun splits so far
+    pub fn splits(&self) -> &Splits {
+        &self.splits
+    }
+
     /// Check if the game is paused
     pub fn is_paused(&self) -> bool {
         self.paused
@@ -218,6 +448,16 @@ impl Game {
         self.hints_used
     }
 
+    /// Points so far; a finished puzzle adds its time bonus
+    pub fn current_score(&self) -> u32 {
+        let bonus = if self.completed {
+            sudoku_session::time_bonus(self.difficulty, self.elapsed().as_secs())
+        } else {
+            0
+        };
+        self.score.total(bonus)
+    }
+
     /// Get mistakes count
     pub fn mistakes(&self) -> usize {
         self.mistakes
@@ -228,6 +468,23 @@ impl Game {
         &self.original_puzzle
     }
 
+    /// Percentage of the puzzle's empty cells that are now filled
+    pub fn completion_percent(&self) -> u8 {
+        let empty = self
+            .original_puzzle
+            .chars()
+            .filter(|c| !('1'..='9').contains(c))
+            .count();
+        if empty == 0 {
+            return 100;
+        }
+        let filled = (0..81)
+            .map(|i| self.grid.cell(Position::new(i / 9, i % 9)))
+            .filter(|cell| cell.is_filled() && !cell.is_given())
+            .count();
+        (filled * 100 / empty).min(100) as u8
+    }
+
     /// Get move times for anti-bot analysis
     pub fn move_times_ms(&self) -> &[u64] {
         &self.move_times_ms
@@ -248,6 +505,11 @@ impl Game {
         self.seed
     }
 
+    /// Day number if this is a daily puzzle
+    pub fn daily(&self) -> Option<u64> {
+        self.daily
+    }
+
     /// Get the SE (Sudoku Explainer) rating
     pub fn se_rating(&self) -> f32 {
         self.se_rating
@@ -302,10 +564,18 @@ impl Game {
         // Check if this is correct
         let is_correct = self.solution.get(pos) == Some(value);
 
-        if !is_correct {
+        if is_correct {
+            self.score
+                .record_correct(pos.row * 9 + pos.col, self.difficulty);
+        } else if self.settings.checks_mistakes() {
             self.mistakes += 1;
+            self.score.record_mistake();
         }
 
+        let technique = is_correct
+            .then(|| sudoku_glossary::justifying_technique(&self.grid, pos, value))
+            .flatten();
+
         // Make the move
         let game_move = GameMove::SetValue {
             pos,
@@ -315,9 +585,18 @@ impl Game {
 
         self.grid.set_cell_unchecked(pos, Some(value));
         self.grid.recalculate_candidates();
+        self.refresh_auto_notes();
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
+        if let Some(entry) = self.undo_stack.last_mut() {
+            entry.technique = technique;
+        }
+
+        if is_correct && self.timer_mode == TimerMode::Speedrun {
+            let (boxes, digits) = (self.completed_boxes(), self.completed_numbers());
+            let elapsed = self.elapsed();
+            self.splits.update(boxes, digits, elapsed);
+        }
 
         // Check for completion
         if self.grid.is_complete() && self.grid.validate().is_valid {
@@ -352,16 +631,16 @@ impl Game {
 
         self.grid.set_cell_unchecked(pos, None);
         self.grid.recalculate_candidates();
+        self.refresh_auto_notes();
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
 
         true
     }
 
     /// Toggle a candidate
     pub fn toggle_candidate(&mut self, pos: Position, value: u8) -> bool {
-        if self.completed || self.paused {
+        if self.completed || self.paused || !self.mode.allows_notes() {
             return false;
         }
 
@@ -372,10 +651,11 @@ impl Game {
 
         let game_move = GameMove::ToggleCandidate { pos, value };
 
+        let before = cell.candidates().as_raw();
         self.grid.cell_mut(pos).toggle_candidate(value);
+        self.track_note_change(pos, before);
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
 
         // Mark that notes were used
         self.notes_used = true;
@@ -386,7 +666,7 @@ impl Game {
     /// Add a candidate (Create)
     #[allow(dead_code)]
     pub fn add_candidate(&mut self, pos: Position, value: u8) -> bool {
-        if self.completed || self.paused {
+        if self.completed || self.paused || !self.mode.allows_notes() {
             return false;
         }
 
@@ -402,10 +682,11 @@ impl Game {
 
         let game_move = GameMove::AddCandidate { pos, value };
 
+        let before = cell.candidates().as_raw();
         self.grid.cell_mut(pos).add_candidate(value);
+        self.track_note_change(pos, before);
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
 
         // Mark that notes were used
         self.notes_used = true;
@@ -432,10 +713,11 @@ impl Game {
 
         let game_move = GameMove::RemoveCandidate { pos, value };
 
+        let before = cell.candidates().as_raw();
         self.grid.cell_mut(pos).remove_candidate(value);
+        self.track_note_change(pos, before);
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
 
         true
     }
@@ -465,16 +747,64 @@ impl Game {
         self.grid
             .cell_mut(pos)
             .set_candidates(sudoku_core::BitSet::empty());
+        self.track_note_change(pos, old_candidates);
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
 
         true
     }
 
+    /// Toggle a corner mark
+    pub fn toggle_corner_mark(&mut self, pos: Position, value: u8) -> bool {
+        if self.completed || self.paused || !(1..=9).contains(&value) || !self.mode.allows_notes() {
+            return false;
+        }
+
+        let cell = self.grid.cell(pos);
+        if cell.is_given() || cell.is_filled() {
+            return false;
+        }
+
+        self.corner_marks[pos.row * 9 + pos.col] ^= 1 << value;
+
+        self.record(vec![GameMove::ToggleCorner { pos, value }]);
+
+        self.notes_used = true;
+
+        true
+    }
+
+    /// Clear all corner marks from a cell
+    pub fn clear_corner_marks(&mut self, pos: Position) -> bool {
+        if self.completed || self.paused {
+            return false;
+        }
+
+        let idx = pos.row * 9 + pos.col;
+        let old_marks = self.corner_marks[idx];
+        if old_marks == 0 {
+            return false;
+        }
+        self.corner_marks[idx] = 0;
+
+        self.record(vec![GameMove::SetCornerMarks {
+            pos,
+            old_marks,
+            new_marks: 0,
+        }]);
+
+        true
+    }
+
+    /// Corner marks of a cell, ascending
+    pub fn corner_marks(&self, pos: Position) -> Vec<u8> {
+        let marks = self.corner_marks[pos.row * 9 + pos.col];
+        (1..=9).filter(|v| marks & (1 << v) != 0).collect()
+    }
+
     /// Set all valid candidates for a cell (based on constraints)
     pub fn fill_candidates(&mut self, pos: Position) -> bool {
-        if self.completed || self.paused {
+        if self.completed || self.paused || !self.mode.allows_notes() {
             return false;
         }
 
@@ -501,8 +831,7 @@ impl Game {
 
         self.grid.cell_mut(pos).set_candidates(valid);
 
-        self.undo_stack.push(game_move);
-        self.redo_stack.clear();
+        self.record(vec![game_move]);
 
         // Mark that notes were used
         self.notes_used = true;
@@ -512,14 +841,14 @@ impl Game {
 
     /// Fill all empty cells with valid candidates
     pub fn fill_all_candidates(&mut self) -> bool {
-        if self.completed || self.paused {
+        if self.completed || self.paused || !self.mode.allows_notes() {
             return false;
         }
 
-        // Recalculate all candidates based on constraints
+        // Recalculate all candidates based on constraints, as one undo step
+        let before = self.raw_candidates();
         self.grid.recalculate_candidates();
-        // Note: This is not undoable as a single action (would need complex undo)
-        self.redo_stack.clear();
+        self.record_candidate_changes(&before);
 
         // Mark that notes were used
         self.notes_used = true;
@@ -533,12 +862,192 @@ impl Game {
             return false;
         }
 
+        let before = self.raw_candidates();
         self.grid.clear_all_candidates();
-        self.redo_stack.clear();
+        self.record_candidate_changes(&before);
 
         true
     }
 
+    /// The same puzzle from the start: same seed, daily or pack and clock
+    /// mode, with no moves, mistakes or hints
+    pub fn restart(&self) -> Self {
+        let mut grid =
+            Grid::from_string(&self.original_puzzle).unwrap_or_else(|| self.grid.clone());
+        grid.clear_all_candidates();
+        let now = Instant::now();
+        let mut game = Self {
+            grid,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            start_time: now,
+            elapsed: Duration::ZERO,
+            paused: false,
+            completed: false,
+            hints_used: 0,
+            mistakes: 0,
+            score: Score::default(),
+            last_move_time: now,
+            move_times_ms: Vec::new(),
+            notes_used: false,
+            corner_marks: [0; 81],
+            splits: Splits::default(),
+            settings: GameSettings {
+                auto_candidates: false,
+                zen: false,
+                ..self.settings
+            },
+            manual_notes: [0; 81],
+            struck_notes: [0; 81],
+            bookmarks: Vec::new(),
+            ..self.clone()
+        };
+        game.set_auto_notes(self.settings.auto_candidates);
+        game
+    }
+
+    /// Keep playing after a loss with no mistake limit or countdown. A
+    /// time attack carries on as a classic game.
+    pub fn continue_in_zen(&mut self) {
+        self.settings.zen = true;
+        if matches!(self.timer_mode, TimerMode::Countdown { .. }) {
+            self.timer_mode = TimerMode::Standard;
+        }
+        if self.mode.time_limit_ms().is_some() {
+            self.mode = GameMode::Classic;
+        }
+    }
+
+    /// Whether this game was continued in zen mode
+    pub fn is_zen(&self) -> bool {
+        self.settings.zen
+    }
+
+    pub fn settings(&self) -> &GameSettings {
+        &self.settings
+    }
+
+    /// Change the assists mid-game. Auto-candidates goes through
+    /// `set_auto_notes` so notes are filled or left as they are.
+    pub fn set_settings(&mut self, settings: GameSettings) {
+        let auto_candidates = settings.auto_candidates;
+        self.settings = GameSettings {
+            auto_candidates: self.settings.auto_candidates,
+            ..settings
+        };
+        self.set_auto_notes(auto_candidates);
+    }
+
+    /// Whether the mistake limit has been reached
+    pub fn is_lost(&self) -> bool {
+        self.settings.is_lost(self.mistakes)
+    }
+
+    /// Mistakes for display: against the limit ("1/3"), in zen ("1 zen"),
+    /// or on their own
+    pub fn mistakes_label(&self) -> String {
+        match self.settings.mistake_limit() {
+            Some(limit) => format!("{}/{}", self.mistakes, limit),
+            None if self.is_zen() => format!("{} zen", self.mistakes),
+            None => self.mistakes.to_string(),
+        }
+    }
+
+    /// Whether one more mistake loses the game
+    pub fn on_last_chance(&self) -> bool {
+        self.settings
+            .mistakes_left(self.mistakes)
+            .is_some_and(|left| left <= 1)
+    }
+
+    /// Whether the clock is shown: always for a countdown, otherwise as set
+    pub fn timer_shown(&self) -> bool {
+        self.settings.show_timer || self.remaining().is_some()
+    }
+
+    /// Restart the clock from zero, for a game generated ahead of play
+    pub fn reset_clock(&mut self) {
+        let now = Instant::now();
+        self.start_time = now;
+        self.last_move_time = now;
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Whether auto-notes mode is on
+    pub fn auto_notes(&self) -> bool {
+        self.settings.auto_candidates
+    }
+
+    /// Turn auto-notes mode on or off. Turning it on fills every empty cell
+    /// with its valid candidates; cells that already have notes keep the
+    /// player's eliminations, and those notes count as manual. Like the
+    /// pruning after each move, the fill is not an undo step of its own.
+    pub fn set_auto_notes(&mut self, on: bool) {
+        if self.completed || on == self.settings.auto_candidates {
+            return;
+        }
+        if on && !self.mode.allows_notes() {
+            return;
+        }
+        self.settings.auto_candidates = on;
+        self.manual_notes = [0; 81];
+        self.struck_notes = [0; 81];
+        if !on {
+            return;
+        }
+
+        for (i, notes) in self.raw_candidates().into_iter().enumerate() {
+            let pos = Position::new(i / 9, i % 9);
+            if notes != 0 && !self.grid.cell(pos).is_filled() {
+                self.manual_notes[i] = notes;
+                self.struck_notes[i] = self.grid.get_candidates(pos).as_raw() & !notes;
+            }
+        }
+        self.refresh_auto_notes();
+        self.notes_used = true;
+    }
+
+    /// Center notes of a cell the player entered by hand in auto-notes mode
+    pub fn manual_notes(&self, pos: Position) -> Vec<u8> {
+        let manual = self.manual_notes[pos.row * 9 + pos.col];
+        let notes = self.grid.cell(pos).candidates();
+        notes
+            .iter()
+            .filter(|&v| sudoku_core::BitSet::single(v).as_raw() & manual != 0)
+            .collect()
+    }
+
+    /// In auto-notes mode, set every empty cell's notes to its valid
+    /// candidates minus the ones the player struck out
+    fn refresh_auto_notes(&mut self) {
+        if !self.settings.auto_candidates {
+            return;
+        }
+        for i in 0..81 {
+            let pos = Position::new(i / 9, i % 9);
+            if self.grid.cell(pos).is_filled() {
+                continue;
+            }
+            let valid = self.grid.get_candidates(pos).as_raw();
+            self.grid
+                .cell_mut(pos)
+                .set_candidates(sudoku_core::BitSet::from_raw(valid & !self.struck_notes[i]));
+        }
+    }
+
+    /// In auto-notes mode, remember a hand-made change to a cell's notes
+    /// (from `before`) so refreshing keeps it
+    fn track_note_change(&mut self, pos: Position, before: u16) {
+        if !self.settings.auto_candidates {
+            return;
+        }
+        let i = pos.row * 9 + pos.col;
+        let after = self.grid.cell(pos).candidates().as_raw();
+        let (added, removed) = (after & !before, before & !after);
+        self.manual_notes[i] = (self.manual_notes[i] | added) & !removed;
+        self.struck_notes[i] = (self.struck_notes[i] | removed) & !added;
+    }
+
     /// Get candidates for a cell (Read)
     #[allow(dead_code)]
     pub fn get_cell_candidates(&self, pos: Position) -> Vec<u8> {
@@ -551,44 +1060,186 @@ impl Game {
         self.grid.cell(pos).has_candidate(value)
     }
 
-    /// Undo the last move
-    pub fn undo(&mut self) -> bool {
-        if self.completed || self.paused {
-            return false;
+    /// Push an undo step made of `moves`, dropping the redo branch
+    fn record(&mut self, moves: Vec<GameMove>) {
+        self.undo_stack.push(UndoEntry {
+            moves,
+            at_ms: self.elapsed().as_millis() as u64,
+            technique: None,
+        });
+        self.redo_stack.clear();
+    }
+
+    /// Raw candidate bits of every cell, row-major
+    fn raw_candidates(&self) -> Vec<u16> {
+        (0..81)
+            .map(|i| {
+                self.grid
+                    .cell(Position::new(i / 9, i % 9))
+                    .candidates()
+                    .as_raw()
+            })
+            .collect()
+    }
+
+    /// Record the cells whose candidates differ from `before` as one undo step
+    fn record_candidate_changes(&mut self, before: &[u16]) {
+        let moves: Vec<GameMove> = self
+            .raw_candidates()
+            .into_iter()
+            .zip(before)
+            .enumerate()
+            .filter(|(_, (new, old))| new != *old)
+            .map(
+                |(i, (new_candidates, &old_candidates))| GameMove::SetCandidates {
+                    pos: Position::new(i / 9, i % 9),
+                    old_candidates,
+                    new_candidates,
+                },
+            )
+            .collect();
+        if !moves.is_empty() {
+            self.record(moves);
         }
+    }
 
-        if let Some(game_move) = self.undo_stack.pop() {
-            match &game_move {
-                GameMove::SetValue { pos, old_value, .. } => {
-                    self.grid.set_cell_unchecked(*pos, *old_value);
-                    self.grid.recalculate_candidates();
-                }
-                GameMove::ToggleCandidate { pos, value } => {
-                    self.grid.cell_mut(*pos).toggle_candidate(*value);
-                }
-                GameMove::AddCandidate { pos, value } => {
-                    // Undo add = remove
-                    self.grid.cell_mut(*pos).remove_candidate(*value);
-                }
-                GameMove::RemoveCandidate { pos, value } => {
-                    // Undo remove = add
+    /// Apply one move, forwards or backwards
+    fn apply_move(&mut self, game_move: &GameMove, forward: bool) {
+        match game_move {
+            GameMove::SetValue {
+                pos,
+                old_value,
+                new_value,
+            } => {
+                let value = if forward { *new_value } else { *old_value };
+                self.grid.set_cell_unchecked(*pos, value);
+                self.grid.recalculate_candidates();
+                self.refresh_auto_notes();
+            }
+            GameMove::ToggleCandidate { pos, value } => {
+                let before = self.grid.cell(*pos).candidates().as_raw();
+                self.grid.cell_mut(*pos).toggle_candidate(*value);
+                self.track_note_change(*pos, before);
+            }
+            GameMove::AddCandidate { pos, value } | GameMove::RemoveCandidate { pos, value } => {
+                // Undoing an add is a remove and vice versa
+                let add = forward == matches!(game_move, GameMove::AddCandidate { .. });
+                let before = self.grid.cell(*pos).candidates().as_raw();
+                if add {
                     self.grid.cell_mut(*pos).add_candidate(*value);
+                } else {
+                    self.grid.cell_mut(*pos).remove_candidate(*value);
                 }
-                GameMove::SetCandidates {
+                self.track_note_change(*pos, before);
+            }
+            GameMove::SetCandidates {
+                pos,
+                old_candidates,
+                new_candidates,
+            } => {
+                let raw = if forward {
+                    *new_candidates
+                } else {
+                    *old_candidates
+                };
+                self.grid
+                    .cell_mut(*pos)
+                    .set_candidates(sudoku_core::BitSet::from_raw(raw));
+            }
+            GameMove::ToggleCorner { pos, value } => {
+                self.corner_marks[pos.row * 9 + pos.col] ^= 1 << value;
+            }
+            GameMove::SetCornerMarks {
+                pos,
+                old_marks,
+                new_marks,
+            } => {
+                let marks = if forward { *new_marks } else { *old_marks };
+                self.corner_marks[pos.row * 9 + pos.col] = marks;
+            }
+            GameMove::SetCell {
+                pos,
+                old_value,
+                new_value,
+                old_candidates,
+                new_candidates,
+            } => {
+                let (value, raw) = if forward {
+                    (*new_value, *new_candidates)
+                } else {
+                    (*old_value, *old_candidates)
+                };
+                self.grid.set_cell_unchecked(*pos, value);
+                self.grid
+                    .cell_mut(*pos)
+                    .set_candidates(sudoku_core::BitSet::from_raw(raw));
+            }
+        }
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Save the values and center notes as `name`, replacing a bookmark of
+    /// that name
+    pub fn save_bookmark(&mut self, name: &str) {
+        let bookmark = Bookmark::capture(name, &self.grid);
+        match self.bookmarks.iter_mut().find(|b| b.name == name) {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+    }
+
+    /// Go back to bookmark `index` as one undo step. Digits it brings back
+    /// aren't counted as mistakes again.
+    pub fn restore_bookmark(&mut self, index: usize) -> bool {
+        if self.completed || self.paused {
+            return false;
+        }
+        let Some(bookmark) = self.bookmarks.get(index) else {
+            return false;
+        };
+
+        let mut moves = Vec::new();
+        for pos in (0..81).map(|i| Position::new(i / 9, i % 9)) {
+            let cell = self.grid.cell(pos);
+            let (old_value, new_value) = (cell.value(), bookmark.value(pos));
+            let old_candidates = cell.candidates().as_raw();
+            let new_candidates = bookmark.candidates(pos).as_raw();
+            if !cell.is_given() && (old_value != new_value || old_candidates != new_candidates) {
+                moves.push(GameMove::SetCell {
                     pos,
+                    old_value,
+                    new_value,
                     old_candidates,
-                    ..
-                } => {
-                    self.grid
-                        .cell_mut(*pos)
-                        .set_candidates(sudoku_core::BitSet::from_raw(*old_candidates));
-                }
+                    new_candidates,
+                });
             }
-            self.redo_stack.push(game_move);
-            true
-        } else {
-            false
         }
+        for game_move in &moves {
+            self.apply_move(game_move, true);
+        }
+        if !moves.is_empty() {
+            self.record(moves);
+        }
+        true
+    }
+
+    /// Undo the last move
+    pub fn undo(&mut self) -> bool {
+        if self.completed || self.paused {
+            return false;
+        }
+
+        let Some(entry) = self.undo_stack.pop() else {
+            return false;
+        };
+        for game_move in entry.moves.iter().rev() {
+            self.apply_move(game_move, false);
+        }
+        self.redo_stack.push(entry);
+        true
     }
 
     /// Redo the last undone move
@@ -597,41 +1248,61 @@ impl Game {
             return false;
         }
 
-        if let Some(game_move) = self.redo_stack.pop() {
-            match &game_move {
-                GameMove::SetValue { pos, new_value, .. } => {
-                    self.grid.set_cell_unchecked(*pos, *new_value);
-                    self.grid.recalculate_candidates();
-                }
-                GameMove::ToggleCandidate { pos, value } => {
-                    self.grid.cell_mut(*pos).toggle_candidate(*value);
-                }
-                GameMove::AddCandidate { pos, value } => {
-                    self.grid.cell_mut(*pos).add_candidate(*value);
-                }
-                GameMove::RemoveCandidate { pos, value } => {
-                    self.grid.cell_mut(*pos).remove_candidate(*value);
-                }
-                GameMove::SetCandidates {
+        let Some(entry) = self.redo_stack.pop() else {
+            return false;
+        };
+        for game_move in &entry.moves {
+            self.apply_move(game_move, true);
+        }
+        self.undo_stack.push(entry);
+        true
+    }
+
+    /// Move history: applied steps (oldest first) and undone steps that can
+    /// still be redone (next redo last)
+    pub fn history(&self) -> (&[UndoEntry], &[UndoEntry]) {
+        (&self.undo_stack, &self.redo_stack)
+    }
+
+    /// Digits placed, in order, for the technique profile. Hint placements
+    /// are included, since they go through the same move.
+    pub fn placements(&self) -> Vec<sudoku_glossary::Placement> {
+        self.undo_stack
+            .iter()
+            .filter_map(|entry| match entry.moves.as_slice() {
+                [GameMove::SetValue {
                     pos,
-                    new_candidates,
+                    new_value: Some(value),
                     ..
-                } => {
-                    self.grid
-                        .cell_mut(*pos)
-                        .set_candidates(sudoku_core::BitSet::from_raw(*new_candidates));
-                }
-            }
-            self.undo_stack.push(game_move);
-            true
-        } else {
-            false
+                }] => Some(sudoku_glossary::Placement {
+                    at_ms: entry.at_ms,
+                    cell: pos.row * 9 + pos.col,
+                    value: *value,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Undo or redo until exactly `applied` steps are applied. Steps after
+    /// that point stay on the redo stack until the next move replaces them.
+    pub fn jump_to(&mut self, applied: usize) -> bool {
+        let total = self.undo_stack.len() + self.redo_stack.len();
+        if self.completed || self.paused || applied > total {
+            return false;
+        }
+        while self.undo_stack.len() > applied {
+            self.undo();
+        }
+        while self.undo_stack.len() < applied {
+            self.redo();
         }
+        true
     }
 
     /// Get a hint
     pub fn get_hint(&mut self) -> Option<Hint> {
-        if self.completed || self.paused {
+        if self.completed || self.paused || !self.can_hint() {
             return None;
         }
 
@@ -640,6 +1311,7 @@ impl Game {
 
         if hint.is_some() {
             self.hints_used += 1;
+            self.score.record_hint();
         }
 
         hint
@@ -647,9 +1319,13 @@ impl Game {
 
     /// Apply a hint directly (verified against backtracking solution)
     pub fn apply_hint(&mut self) -> Option<Position> {
+        if !self.can_hint() {
+            return None;
+        }
         let solver = Solver::new();
         let hint = solver.get_next_placement(&self.grid)?;
         self.hints_used += 1;
+        self.score.record_hint();
 
         match hint.hint_type {
             sudoku_core::HintType::SetValue { pos, value } => {
@@ -778,39 +1454,10 @@ impl Game {
         result
     }
 
-    /// Check if a position has a conflict
-    #[allow(clippy::needless_range_loop)]
-    pub fn has_conflict(&self, pos: Position) -> bool {
-        if let Some(value) = self.grid.get(pos) {
-            // Check if this value appears elsewhere in same row/col/box
-            let values = self.grid.values();
-
-            // Row
-            for col in 0..9 {
-                if col != pos.col && values[pos.row][col] == Some(value) {
-                    return true;
-                }
-            }
-
-            // Column
-            for row in 0..9 {
-                if row != pos.row && values[row][pos.col] == Some(value) {
-                    return true;
-                }
-            }
-
-            // Box
-            let box_row = (pos.row / 3) * 3;
-            let box_col = (pos.col / 3) * 3;
-            for row in box_row..box_row + 3 {
-                for col in box_col..box_col + 3 {
-                    if (row != pos.row || col != pos.col) && values[row][col] == Some(value) {
-                        return true;
-                    }
-                }
-            }
-        }
-        false
+    /// What's wrong with a cell, if the settings show it
+    pub fn conflict(&self, pos: Position) -> Option<ConflictKind> {
+        sudoku_session::conflict_at(&self.grid, Some(&self.solution), pos)
+            .filter(|&kind| self.settings.shows(kind))
     }
 
     /// Serialize the game state for saving
@@ -822,6 +1469,29 @@ impl Game {
             elapsed_secs: self.elapsed().as_secs(),
             hints_used: self.hints_used,
             mistakes: self.mistakes,
+            original_puzzle: Some(self.original_puzzle.clone()),
+            candidates: self.raw_candidates(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            undo_history: self.undo_stack.clone(),
+            redo_history: self.redo_stack.clone(),
+            move_times_ms: self.move_times_ms.clone(),
+            notes_used: self.notes_used,
+            seed: self.seed,
+            se_rating: self.se_rating,
+            daily: self.daily,
+            corner_marks: self.corner_marks.to_vec(),
+            timer_mode: self.timer_mode,
+            splits: self.splits.clone(),
+            pack: self.pack.clone(),
+            auto_notes: false,
+            manual_notes: self.manual_notes.to_vec(),
+            struck_notes: self.struck_notes.to_vec(),
+            zen: false,
+            settings: Some(self.settings),
+            score: self.score,
+            mode: self.mode,
+            bookmarks: self.bookmarks.clone(),
         };
         serde_json::to_string(&state).unwrap_or_default()
     }
@@ -830,38 +1500,310 @@ impl Game {
     pub fn deserialize(json: &str) -> Option<Self> {
         let state: SaveState = serde_json::from_str(json).ok()?;
 
-        let grid = Grid::from_string(&state.puzzle)?;
         let solution = Grid::from_string(&state.solution)?;
+        let current = Grid::from_string(&state.puzzle)?;
+
+        // Older saves only have the current grid, so its values become givens
+        let (mut grid, original_puzzle) = match &state.original_puzzle {
+            Some(original) => {
+                let mut grid = Grid::from_string(original)?;
+                grid.clear_all_candidates();
+                for i in 0..81 {
+                    let pos = Position::new(i / 9, i % 9);
+                    if !grid.cell(pos).is_given() {
+                        grid.set_cell_unchecked(pos, current.get(pos));
+                    }
+                }
+                (grid, original.clone())
+            }
+            None => (current, state.puzzle.clone()),
+        };
+        if state.candidates.len() == 81 {
+            for (i, &raw) in state.candidates.iter().enumerate() {
+                let pos = Position::new(i / 9, i % 9);
+                if !grid.cell(pos).is_filled() {
+                    grid.cell_mut(pos)
+                        .set_candidates(sudoku_core::BitSet::from_raw(raw));
+                }
+            }
+        }
+
+        let mut corner_marks = [0; 81];
+        if state.corner_marks.len() == 81 {
+            corner_marks.copy_from_slice(&state.corner_marks);
+        }
+        let (mut manual_notes, mut struck_notes) = ([0; 81], [0; 81]);
+        if state.manual_notes.len() == 81 && state.struck_notes.len() == 81 {
+            manual_notes.copy_from_slice(&state.manual_notes);
+            struck_notes.copy_from_slice(&state.struck_notes);
+        }
+
+        // Older saves have one undo step per move, without times
+        let entries = |history: Vec<UndoEntry>, moves: Vec<GameMove>| {
+            if history.is_empty() {
+                moves
+                    .into_iter()
+                    .map(|m| UndoEntry {
+                        moves: vec![m],
+                        at_ms: 0,
+                        technique: None,
+                    })
+                    .collect()
+            } else {
+                history
+            }
+        };
 
         let now = Instant::now();
         Some(Self {
             grid,
             solution,
-            original_puzzle: state.puzzle.clone(),
+            original_puzzle,
             difficulty: state.difficulty,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
+            undo_stack: entries(state.undo_history, state.undo_stack),
+            redo_stack: entries(state.redo_history, state.redo_stack),
             start_time: now,
             elapsed: Duration::from_secs(state.elapsed_secs),
             paused: true, // Start paused when loading
             completed: false,
             hints_used: state.hints_used,
             mistakes: state.mistakes,
+            score: state.score,
             last_move_time: now,
-            move_times_ms: Vec::new(), // Can't restore move times from save
-            notes_used: false,         // Reset for loaded game
-            seed: None,                // Can't restore seed from save
-            se_rating: 0.0,            // Can't restore SE rating from save
+            move_times_ms: state.move_times_ms,
+            notes_used: state.notes_used,
+            seed: state.seed,
+            se_rating: state.se_rating,
+            daily: state.daily,
+            corner_marks,
+            timer_mode: state.timer_mode,
+            splits: state.splits,
+            pack: state.pack,
+            settings: state.settings.unwrap_or(GameSettings {
+                auto_candidates: state.auto_notes,
+                zen: state.zen,
+                ..GameSettings::default()
+            }),
+            manual_notes,
+            struck_notes,
+            mode: state.mode,
+            bookmarks: state.bookmarks,
         })
     }
 }
 
 #[derive(Serialize, Deserialize)]
 struct SaveState {
+    /// Current grid (values only)
     puzzle: String,
     solution: String,
     difficulty: Difficulty,
     elapsed_secs: u64,
     hints_used: usize,
     mistakes: usize,
+    // Fields below were added for resuming; older saves lack them
+    /// Puzzle as generated, to tell givens from entered values
+    #[serde(default)]
+    original_puzzle: Option<String>,
+    /// Raw candidate bits per cell, row-major
+    #[serde(default)]
+    candidates: Vec<u16>,
+    /// Ungrouped undo moves from older saves
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    undo_stack: Vec<GameMove>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    redo_stack: Vec<GameMove>,
+    #[serde(default)]
+    move_times_ms: Vec<u64>,
+    #[serde(default)]
+    notes_used: bool,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    se_rating: f32,
+    #[serde(default)]
+    daily: Option<u64>,
+    /// Corner mark bits per cell, row-major
+    #[serde(default)]
+    corner_marks: Vec<u16>,
+    #[serde(default)]
+    timer_mode: TimerMode,
+    #[serde(default)]
+    splits: Splits,
+    #[serde(default)]
+    pack: Option<(String, usize)>,
+    #[serde(default)]
+    undo_history: Vec<UndoEntry>,
+    #[serde(default)]
+    redo_history: Vec<UndoEntry>,
+    /// Superseded by `settings`; read from older saves
+    #[serde(default, skip_serializing)]
+    auto_notes: bool,
+    /// Hand-made note bits per cell in auto-notes mode, row-major
+    #[serde(default)]
+    manual_notes: Vec<u16>,
+    #[serde(default)]
+    struck_notes: Vec<u16>,
+    #[serde(default, skip_serializing)]
+    zen: bool,
+    #[serde(default)]
+    settings: Option<GameSettings>,
+    #[serde(default)]
+    score: Score,
+    #[serde(default)]
+    mode: GameMode,
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_save_round_trip() {
+        let mut game = Game::from_string(PUZZLE).unwrap();
+        assert!(game.set_value(Position::new(0, 2), 4));
+        game.clear_all_candidates();
+        assert!(game.toggle_candidate(Position::new(0, 3), 2));
+
+        // Givens, entered values, notes and history all come back
+        let mut restored = Game::deserialize(&game.serialize()).unwrap();
+        assert!(restored.is_paused());
+        assert_eq!(restored.original_puzzle(), PUZZLE);
+        assert!(restored.grid().cell(Position::new(0, 0)).is_given());
+        let entered = restored.grid().cell(Position::new(0, 2));
+        assert!(!entered.is_given() && entered.value() == Some(4));
+        assert!(restored.has_candidate(Position::new(0, 3), 2));
+        assert!(!restored.has_candidate(Position::new(0, 3), 6));
+        assert_eq!(restored.history().0.len(), game.history().0.len());
+        assert_eq!(restored.move_times_ms(), game.move_times_ms());
+        // Resuming unpauses it, and the moves can be undone
+        restored.toggle_pause();
+        while restored.undo() {}
+        assert_eq!(restored.grid().get(Position::new(0, 2)), None);
+
+        // Older saves have only the current grid, whose values become givens
+        let mut save: serde_json::Value = serde_json::from_str(&game.serialize()).unwrap();
+        let old_fields = [
+            "puzzle",
+            "solution",
+            "difficulty",
+            "elapsed_secs",
+            "hints_used",
+            "mistakes",
+        ];
+        save.as_object_mut()
+            .unwrap()
+            .retain(|key, _| old_fields.contains(&key.as_str()));
+        let old = Game::deserialize(&save.to_string()).unwrap();
+        assert!(old.grid().cell(Position::new(0, 2)).is_given());
+        assert_eq!(old.grid().get(Position::new(0, 2)), Some(4));
+        assert!(Game::deserialize("{}").is_none());
+    }
+
+    #[test]
+    fn test_history_jumps() {
+        let mut game = Game::from_string(PUZZLE).unwrap();
+        let start = game.history().0.len();
+        for (col, value) in [(2, 4), (3, 6), (5, 8)] {
+            assert!(game.set_value(Position::new(0, col), value));
+        }
+        game.clear_all_candidates();
+        let cleared = game.history().0.len();
+
+        // Filling every note is one step, and undoes as one
+        assert!(game.fill_all_candidates());
+        let (applied, undone) = game.history();
+        assert_eq!((applied.len(), undone.len()), (cleared + 1, 0));
+        assert!(applied
+            .last()
+            .unwrap()
+            .describe()
+            .starts_with("notes filled"));
+        assert!(game.undo());
+        assert!(game.get_cell_candidates(Position::new(1, 1)).is_empty());
+
+        // Jumping back keeps the later steps redoable
+        assert!(game.jump_to(start + 1));
+        assert_eq!(game.grid().get(Position::new(0, 2)), Some(4));
+        assert_eq!(game.grid().get(Position::new(0, 3)), None);
+        assert_eq!(game.history().1.len(), cleared - start);
+        assert!(!game.jump_to(cleared + 2));
+        assert!(game.jump_to(cleared + 1));
+        assert!(!game.get_cell_candidates(Position::new(1, 1)).is_empty());
+
+        // Until a new move replaces them
+        assert!(game.jump_to(start));
+        assert!(game.set_value(Position::new(0, 5), 8));
+        assert_eq!(game.history().0.len(), start + 1);
+        assert!(game.history().1.is_empty());
+    }
+
+    #[test]
+    fn test_auto_notes() {
+        let mut game = Game::from_string(PUZZLE).unwrap();
+        game.clear_all_candidates();
+        assert!(game.toggle_candidate(Position::new(0, 2), 1));
+
+        // Empty cells fill in; a cell with notes keeps the player's choice
+        game.set_auto_notes(true);
+        assert!(game.auto_notes());
+        assert_eq!(game.get_cell_candidates(Position::new(0, 3)), [2, 6]);
+        assert_eq!(game.get_cell_candidates(Position::new(0, 2)), [1]);
+        assert_eq!(game.manual_notes(Position::new(0, 2)), [1]);
+        assert!(game.manual_notes(Position::new(0, 3)).is_empty());
+
+        // Placements prune the notes, undo restores them, and a struck
+        // note stays off
+        assert!(game.toggle_candidate(Position::new(0, 3), 6));
+        assert!(game.set_value(Position::new(0, 6), 9));
+        assert_eq!(game.get_cell_candidates(Position::new(0, 7)), [1, 2, 4]);
+        assert!(game.undo());
+        assert_eq!(game.get_cell_candidates(Position::new(0, 7)), [1, 2, 4, 9]);
+        assert_eq!(game.get_cell_candidates(Position::new(0, 3)), [2]);
+
+        let restored = Game::deserialize(&game.serialize()).unwrap();
+        assert!(restored.auto_notes());
+        assert_eq!(restored.manual_notes(Position::new(0, 2)), [1]);
+
+        game.set_auto_notes(false);
+        assert!(game.manual_notes(Position::new(0, 2)).is_empty());
+        assert_eq!(game.get_cell_candidates(Position::new(0, 3)), [2]);
+    }
+
+    #[test]
+    fn test_retry_and_zen_after_loss() {
+        let mut game = Game::from_string(PUZZLE).unwrap();
+        game.set_settings(GameSettings {
+            mistakes: sudoku_session::MistakePolicy::Limit(3),
+            ..GameSettings::default()
+        });
+        game.set_timer_mode(TimerMode::Countdown { limit_secs: 300 });
+        // 3 belongs nowhere else in the top row
+        for col in [2, 3, 5] {
+            assert!(!game.set_value(Position::new(0, col), 3));
+        }
+        assert!(game.is_lost());
+        assert_eq!(game.mistakes_label(), "3/3");
+
+        // Retrying starts the same puzzle over with the same clock
+        let retry = game.restart();
+        assert_eq!(retry.original_puzzle(), PUZZLE);
+        assert_eq!((retry.mistakes(), retry.moves_count()), (0, 0));
+        assert_eq!(retry.grid().get(Position::new(0, 2)), None);
+        assert_eq!(retry.timer_mode(), game.timer_mode());
+        assert!(!retry.is_lost() && !retry.is_zen());
+
+        // Zen drops the limit and the countdown, and survives a save
+        game.continue_in_zen();
+        assert!(game.is_zen() && !game.is_lost());
+        assert_eq!(game.timer_mode(), TimerMode::Standard);
+        assert!(!game.set_value(Position::new(0, 6), 3));
+        assert_eq!(game.mistakes_label(), "4 zen");
+        assert!(Game::deserialize(&game.serialize()).unwrap().is_zen());
+    }
 }
@@ -0,0 +1,24 @@
+//! Colors for the cell roles of a hint's reasoning. The roles themselves
+//! come from [`sudoku_render::hint_roles`], shared with the SVG and PNG
+//! boards.
+
+use crossterm::style::Color;
+use sudoku_render::HintRole;
+
+/// Background color for cells with this role
+pub fn role_color(role: HintRole) -> Option<Color> {
+    let rgb = |r, g, b| Some(Color::Rgb { r, g, b });
+    match role {
+        HintRole::None => None,
+        HintRole::Target => rgb(40, 120, 50),
+        HintRole::Involved => rgb(55, 65, 100),
+        HintRole::ChainOn => rgb(30, 90, 150),
+        HintRole::ChainOff => rgb(140, 70, 40),
+        HintRole::FishBase => rgb(95, 55, 130),
+        HintRole::FishCover => rgb(40, 105, 110),
+        HintRole::FishFin => rgb(150, 120, 30),
+        HintRole::UrFloor => rgb(70, 100, 60),
+        HintRole::UrRoof => rgb(120, 60, 95),
+        HintRole::AlsGroup => rgb(75, 75, 130),
+    }
+}
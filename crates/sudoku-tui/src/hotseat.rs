@@ -0,0 +1,134 @@
+//! Hot-seat play: two players take turns on one puzzle in the same terminal.
+//!
+//! Placing a value (right or wrong) hands the turn over. Each player has
+//! their own clock that only runs on their turn, and their own placements,
+//! mistakes and hints.
+
+use std::time::{Duration, Instant};
+
+/// One player's tally
+#[derive(Debug, Clone, Default)]
+pub struct PlayerTally {
+    /// Correct values placed
+    pub placed: usize,
+    pub mistakes: usize,
+    pub hints: usize,
+    /// Time on this player's clock from finished turns
+    time: Duration,
+}
+
+/// A two-player game in progress
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    pub players: [PlayerTally; 2],
+    /// Whose turn it is (0 or 1)
+    pub current: usize,
+    /// When the current turn's clock started (None while stopped)
+    turn_started: Option<Instant>,
+}
+
+impl Default for HotSeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HotSeat {
+    /// Player 1 to move, clock running
+    pub fn new() -> Self {
+        Self {
+            players: Default::default(),
+            current: 0,
+            turn_started: Some(Instant::now()),
+        }
+    }
+
+    /// Time on a player's clock, including the running turn
+    pub fn clock(&self, player: usize) -> Duration {
+        let running = match self.turn_started {
+            Some(start) if player == self.current => start.elapsed(),
+            _ => Duration::ZERO,
+        };
+        self.players[player].time + running
+    }
+
+    /// Stop or restart the current player's clock (for pausing and game end)
+    pub fn set_running(&mut self, running: bool) {
+        match (running, self.turn_started) {
+            (true, None) => self.turn_started = Some(Instant::now()),
+            (false, Some(start)) => {
+                self.players[self.current].time += start.elapsed();
+                self.turn_started = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Credit a placement to the current player and hand the turn over
+    pub fn record_placement(&mut self, correct: bool) {
+        let player = &mut self.players[self.current];
+        if correct {
+            player.placed += 1;
+        } else {
+            player.mistakes += 1;
+        }
+        let running = self.turn_started.is_some();
+        self.set_running(false);
+        self.current = 1 - self.current;
+        self.set_running(running);
+    }
+
+    /// Count a hint against the current player
+    pub fn record_hint(&mut self) {
+        self.players[self.current].hints += 1;
+    }
+
+    /// A player who has made `max_mistakes` mistakes and is out
+    pub fn out(&self, max_mistakes: usize) -> Option<usize> {
+        self.players.iter().position(|p| p.mistakes >= max_mistakes)
+    }
+
+    /// The winner: the other player if one is out, otherwise most placements,
+    /// then fewest mistakes, then least time. None for a tie.
+    pub fn winner(&self, max_mistakes: usize) -> Option<usize> {
+        if let Some(out) = self.out(max_mistakes) {
+            return Some(1 - out);
+        }
+        let [a, b] = &self.players;
+        let order = b
+            .placed
+            .cmp(&a.placed)
+            .then(a.mistakes.cmp(&b.mistakes))
+            .then(self.clock(0).cmp(&self.clock(1)));
+        match order {
+            std::cmp::Ordering::Less => Some(0),
+            std::cmp::Ordering::Greater => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns_and_winner() {
+        let mut seat = HotSeat::new();
+        seat.set_running(false);
+        seat.record_placement(true);
+        assert_eq!(seat.current, 1);
+        seat.record_hint();
+        seat.record_placement(false);
+        assert_eq!(seat.current, 0);
+        seat.record_placement(true);
+
+        assert_eq!(seat.players[0].placed, 2);
+        assert_eq!(seat.players[1].hints, 1);
+        assert_eq!(seat.winner(3), Some(0));
+
+        seat.players[0].mistakes = 3;
+        assert_eq!(seat.out(3), Some(0));
+        assert_eq!(seat.winner(3), Some(1));
+    }
+}
@@ -0,0 +1,100 @@
+//! Reading puzzles from the command line, files and stdin.
+
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+use sudoku_core::{Grid, Solver};
+use sudoku_session::parse_puzzle_text;
+
+/// Resolve the `--puzzle` argument (or piped stdin when it is absent) to
+/// either an 81-character puzzle string or a short code. `-` reads stdin.
+pub fn read_puzzle(arg: Option<&str>) -> Result<Option<String>, String> {
+    let text = match arg {
+        Some("-") => read_stdin()?,
+        Some(arg) if Path::new(arg).is_file() => {
+            fs::read_to_string(arg).map_err(|e| format!("{}: {}", arg, e))?
+        }
+        Some(arg) => arg.to_string(),
+        None if !io::stdin().is_terminal() => {
+            // Nothing piped (e.g. stdin is /dev/null): start normally
+            let text = read_stdin()?;
+            if text.trim().is_empty() {
+                return Ok(None);
+            }
+            text
+        }
+        None => return Ok(None),
+    };
+
+    let trimmed = text.trim();
+    if trimmed.len() == 8 && trimmed.chars().all(|c| c.is_ascii_alphanumeric()) {
+        // Short code; PuzzleId decoding happens when the game is created
+        return Ok(Some(trimmed.to_string()));
+    }
+
+    let puzzle = parse_puzzle_text(&text).ok_or("expected 81 cells (digits, '.' or '0')")?;
+    validate(&puzzle)?;
+    Ok(Some(puzzle))
+}
+
+/// Check that a puzzle is readable and has exactly one solution
+pub fn validate(puzzle: &str) -> Result<(), String> {
+    let grid = Grid::from_string(puzzle).ok_or("puzzle could not be read")?;
+    match Solver::new().count_solutions(&grid, 2) {
+        0 => Err("puzzle has no solution".to_string()),
+        1 => Ok(()),
+        _ => Err("puzzle has more than one solution".to_string()),
+    }
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|e| format!("stdin: {}", e))?;
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_parse_and_validate() {
+        // Drawn grids: borders, separators and spaces are skipped
+        let drawn = "\
+            +-------+-------+-------+\n\
+            | 5 3 . | . 7 . | . . . |\n\
+            | 6 . . | 1 9 5 | . . . |\n\
+            | . 9 8 | . . . | . 6 . |\n\
+            +-------+-------+-------+\n\
+            | 8 . . | . 6 . | . . 3 |\n\
+            | 4 . . | 8 . 3 | . . 1 |\n\
+            | 7 . . | . 2 . | . . 6 |\n\
+            +-------+-------+-------+\n\
+            | . 6 . | . . . | 2 8 . |\n\
+            | . . . | 4 1 9 | . . 5 |\n\
+            | . . . | . 8 . | . 7 9 |\n\
+            +-------+-------+-------+\n";
+        assert_eq!(parse_puzzle_text(drawn).as_deref(), Some(PUZZLE));
+        assert_eq!(
+            parse_puzzle_text(&PUZZLE.replace('.', "0")).as_deref(),
+            Some(PUZZLE)
+        );
+
+        // Too short, and letters that aren't digits leave it short
+        assert_eq!(parse_puzzle_text(&PUZZLE[..80]), None);
+        assert_eq!(parse_puzzle_text(&PUZZLE.replacen('5', "x", 1)), None);
+
+        assert_eq!(validate(PUZZLE), Ok(()));
+        assert_eq!(
+            validate(&".".repeat(81)),
+            Err("puzzle has more than one solution".to_string())
+        );
+        // Two 5s in the first row
+        assert!(validate(&format!("55{}", &PUZZLE[2..])).is_err());
+    }
+}
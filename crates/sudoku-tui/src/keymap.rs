@@ -0,0 +1,361 @@
+//! Remappable key bindings for gameplay actions.
+//!
+//! Two presets are built in: "default" (arrows, hjkl and wasd box jumps) and
+//! "vim" (hjkl only, `w`/`b` and `{`/`}` jump boxes, `x` clears a cell). Keys
+//! not bound to an action fall through to the fixed game-screen keys, so a
+//! binding can shadow one of those (the vim preset's `b` hides the leaderboard
+//! shortcut; it is still reachable from the stats screen).
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+/// A gameplay action that can be bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    BoxUp,
+    BoxDown,
+    BoxLeft,
+    BoxRight,
+    /// Enter a digit (1-9)
+    Digit(u8),
+    /// Clear the cell (or its notes in notes mode)
+    Clear,
+    /// Toggle notes mode (center candidates)
+    ToggleNotes,
+    /// Toggle corner-mark mode
+    ToggleCorner,
+    Hint,
+    ApplyHint,
+    Undo,
+    Redo,
+    /// Open the move history
+    History,
+    /// Show every key binding
+    Help,
+    /// Open the command palette
+    Palette,
+}
+
+impl Action {
+    /// Parse an action name as used in the config file
+    pub fn from_name(name: &str) -> Option<Self> {
+        let action = match name {
+            "up" => Action::Up,
+            "down" => Action::Down,
+            "left" => Action::Left,
+            "right" => Action::Right,
+            "box_up" => Action::BoxUp,
+            "box_down" => Action::BoxDown,
+            "box_left" => Action::BoxLeft,
+            "box_right" => Action::BoxRight,
+            "clear" => Action::Clear,
+            "notes" => Action::ToggleNotes,
+            "corner" => Action::ToggleCorner,
+            "hint" => Action::Hint,
+            "apply_hint" => Action::ApplyHint,
+            "undo" => Action::Undo,
+            "redo" => Action::Redo,
+            "history" => Action::History,
+            "help" => Action::Help,
+            "palette" => Action::Palette,
+            _ => {
+                let digit = name.strip_prefix("digit_")?.parse::<u8>().ok()?;
+                return (1..=9).contains(&digit).then_some(Action::Digit(digit));
+            }
+        };
+        Some(action)
+    }
+}
+
+/// A key with the modifiers that matter for matching. Shift is folded into
+/// the character for printable keys, so "X" means Shift+x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    ctrl: bool,
+    alt: bool,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            ctrl: false,
+            alt: false,
+        }
+    }
+
+    fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            ctrl: true,
+            alt: false,
+        }
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        Self {
+            code: key.code,
+            ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+            alt: key.modifiers.contains(KeyModifiers::ALT),
+        }
+    }
+
+    /// Parse a key description such as "k", "X", "ctrl+r", "up", "del" or "f1"
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut binding = Self::new(KeyCode::Null);
+        let mut rest = text.trim();
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if let Some(r) = lower.strip_prefix("ctrl+") {
+                binding.ctrl = true;
+                rest = &rest[rest.len() - r.len()..];
+            } else if let Some(r) = lower.strip_prefix("alt+") {
+                binding.alt = true;
+                rest = &rest[rest.len() - r.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        binding.code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "del" | "delete" => KeyCode::Delete,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                other => {
+                    let n: u8 = other.strip_prefix('f')?.parse().ok()?;
+                    if !(1..=12).contains(&n) {
+                        return None;
+                    }
+                    KeyCode::F(n)
+                }
+            },
+        };
+        Some(binding)
+    }
+
+    /// Short label for the controls line
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Backspace => "Bksp".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        match (self.ctrl, self.alt) {
+            (true, _) => format!("^{}", key),
+            (false, true) => format!("M-{}", key),
+            _ => key,
+        }
+    }
+}
+
+/// Maps keys to gameplay actions
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyBinding, Action>,
+    /// Keys per action in preference order, for labels
+    keys: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::default_preset()
+    }
+}
+
+impl Keymap {
+    fn from_pairs(pairs: &[(Action, KeyBinding)]) -> Self {
+        let mut keymap = Self {
+            bindings: HashMap::new(),
+            keys: HashMap::new(),
+        };
+        for &(action, key) in pairs {
+            keymap.bind(action, key);
+        }
+        keymap
+    }
+
+    fn bind(&mut self, action: Action, key: KeyBinding) {
+        if let Some(previous) = self.bindings.insert(key, action) {
+            if let Some(keys) = self.keys.get_mut(&previous) {
+                keys.retain(|k| *k != key);
+            }
+        }
+        self.keys.entry(action).or_default().push(key);
+    }
+
+    /// The standard bindings
+    pub fn default_preset() -> Self {
+        use Action::*;
+        let c = |ch| KeyBinding::new(KeyCode::Char(ch));
+        let k = KeyBinding::new;
+        let mut pairs = vec![
+            (Left, c('h')),
+            (Down, c('j')),
+            (Up, c('k')),
+            (Right, c('l')),
+            (Left, k(KeyCode::Left)),
+            (Down, k(KeyCode::Down)),
+            (Up, k(KeyCode::Up)),
+            (Right, k(KeyCode::Right)),
+            (BoxUp, c('w')),
+            (BoxDown, c('s')),
+            (BoxLeft, c('a')),
+            (BoxRight, c('d')),
+            (Clear, c('0')),
+            (Clear, k(KeyCode::Delete)),
+            (Clear, k(KeyCode::Backspace)),
+            (ToggleNotes, c('c')),
+            (ToggleCorner, c('C')),
+            (Hint, c('?')),
+            (ApplyHint, c('!')),
+            (Undo, c('u')),
+            (Redo, KeyBinding::ctrl('r')),
+            (History, c('U')),
+            (Help, KeyBinding::new(KeyCode::F(1))),
+            (Palette, c(':')),
+        ];
+        pairs.extend((1..=9).map(|d| (Digit(d), c((b'0' + d) as char))));
+        Self::from_pairs(&pairs)
+    }
+
+    /// Vim-flavoured bindings
+    pub fn vim_preset() -> Self {
+        use Action::*;
+        let c = |ch| KeyBinding::new(KeyCode::Char(ch));
+        let mut pairs = vec![
+            (Left, c('h')),
+            (Down, c('j')),
+            (Up, c('k')),
+            (Right, c('l')),
+            (BoxLeft, c('b')),
+            (BoxRight, c('w')),
+            (BoxUp, c('{')),
+            (BoxDown, c('}')),
+            (Clear, c('x')),
+            (Clear, c('0')),
+            (ToggleNotes, c('c')),
+            (ToggleCorner, c('C')),
+            (Hint, c('?')),
+            (ApplyHint, c('!')),
+            (Undo, c('u')),
+            (Redo, KeyBinding::ctrl('r')),
+            (History, c('U')),
+            (Help, KeyBinding::new(KeyCode::F(1))),
+            (Palette, c(':')),
+        ];
+        pairs.extend((1..=9).map(|d| (Digit(d), c((b'0' + d) as char))));
+        Self::from_pairs(&pairs)
+    }
+
+    /// Look up a preset by name
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "default" => Some(Self::default_preset()),
+            "vim" => Some(Self::vim_preset()),
+            _ => None,
+        }
+    }
+
+    /// Replace the keys for `action` with `keys`
+    pub fn rebind(&mut self, action: Action, keys: &[KeyBinding]) {
+        for key in self.keys.remove(&action).unwrap_or_default() {
+            self.bindings.remove(&key);
+        }
+        for &key in keys {
+            self.bind(action, key);
+        }
+    }
+
+    /// Action bound to a key event, if any
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&KeyBinding::from_event(key)).copied()
+    }
+
+    /// Labels of every key bound to an action, preferred first
+    pub fn labels(&self, action: Action) -> Vec<String> {
+        self.keys
+            .get(&action)
+            .map(|keys| keys.iter().map(KeyBinding::label).collect())
+            .unwrap_or_default()
+    }
+
+    /// Label of the preferred key for an action ("-" when unbound)
+    pub fn label(&self, action: Action) -> String {
+        self.keys
+            .get(&action)
+            .and_then(|keys| keys.first())
+            .map_or_else(|| "-".to_string(), KeyBinding::label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(
+            KeyBinding::parse("k"),
+            Some(KeyBinding::new(KeyCode::Char('k')))
+        );
+        assert_eq!(KeyBinding::parse("Ctrl+r"), Some(KeyBinding::ctrl('r')));
+        assert_eq!(
+            KeyBinding::parse("del"),
+            Some(KeyBinding::new(KeyCode::Delete))
+        );
+        assert_eq!(
+            KeyBinding::parse("F5"),
+            Some(KeyBinding::new(KeyCode::F(5)))
+        );
+        assert_eq!(KeyBinding::parse("bogus"), None);
+        assert_eq!(KeyBinding::parse("f13"), None);
+    }
+
+    #[test]
+    fn test_rebind_moves_key() {
+        let mut keymap = Keymap::vim_preset();
+        let z = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE);
+        let u = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(&u), Some(Action::Undo));
+
+        keymap.rebind(Action::Undo, &[KeyBinding::parse("z").unwrap()]);
+        assert_eq!(keymap.action(&z), Some(Action::Undo));
+        assert_eq!(keymap.action(&u), None);
+        assert_eq!(keymap.label(Action::Undo), "z");
+
+        // Binding a key already in use takes it from the other action
+        keymap.rebind(Action::Hint, &[KeyBinding::parse("x").unwrap()]);
+        assert_eq!(keymap.label(Action::Clear), "0");
+        assert_eq!(Action::from_name("digit_5"), Some(Action::Digit(5)));
+        assert_eq!(Action::from_name("digit_0"), None);
+        assert_eq!(Action::from_name("corner"), Some(Action::ToggleCorner));
+        assert_eq!(keymap.label(Action::History), "U");
+        assert_eq!(keymap.labels(Action::Help), vec!["F1"]);
+    }
+}
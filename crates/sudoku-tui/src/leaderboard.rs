@@ -7,10 +7,12 @@
 
 #![allow(dead_code)]
 
+use crate::timer::TimerMode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use sudoku_core::Difficulty;
+use sudoku_session::GameMode;
 
 /// Environment configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,6 +53,12 @@ pub struct LeaderboardEntry {
     /// Whether this score was verified by anti-bot checks
     #[serde(default)]
     pub verified: bool,
+    /// Clock mode the game was played with
+    #[serde(default)]
+    pub timer: TimerMode,
+    /// Game mode, whose leaderboard category the score ranks in
+    #[serde(default)]
+    pub mode: GameMode,
 }
 
 /// Result type for leaderboard operations
@@ -393,10 +401,15 @@ impl LeaderboardBackend for MockLeaderboard {
 // ==================== Remote HTTP Backend ====================
 
 /// Remote HTTP API leaderboard for production
+///
+/// Requests are only made when built with the `online` feature; otherwise
+/// every call fails and the manager falls back to local storage.
 pub struct RemoteLeaderboard {
     base_url: String,
     api_key: Option<String>,
-    client: Mutex<Option<()>>, // Placeholder for HTTP client
+    timeout: std::time::Duration,
+    /// Cleared when the server can't be reached, set again on success
+    reachable: Mutex<bool>,
 }
 
 /// Configuration for remote backend
@@ -422,38 +435,82 @@ impl RemoteLeaderboard {
         Self {
             base_url: config.base_url,
             api_key: config.api_key,
-            client: Mutex::new(None),
+            timeout: std::time::Duration::from_secs(config.timeout_secs),
+            reachable: Mutex::new(true),
         }
     }
 
     pub fn from_env() -> Self {
-        let base_url = std::env::var("SUDOKU_API_URL")
-            .unwrap_or_else(|_| "https://api.sudoku-game.example.com".to_string());
-        let api_key = std::env::var("SUDOKU_API_KEY").ok();
+        let defaults = RemoteConfig::default();
+        Self::new(RemoteConfig {
+            base_url: std::env::var("SUDOKU_API_URL").unwrap_or(defaults.base_url),
+            api_key: std::env::var("SUDOKU_API_KEY").ok(),
+            ..defaults
+        })
+    }
 
-        Self {
-            base_url,
-            api_key,
-            client: Mutex::new(None),
+    /// Make a JSON request against the configured endpoint
+    #[cfg(feature = "online")]
+    fn request<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<&impl Serialize>,
+    ) -> LeaderboardResult<T> {
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), endpoint);
+        let mut req = ureq::request(method, &url)
+            .timeout(self.timeout)
+            .set("Accept", "application/json");
+        if let Some(ref key) = self.api_key {
+            req = req.set("Authorization", &format!("Bearer {}", key));
         }
+
+        let result = match body {
+            Some(body) => {
+                let json = serde_json::to_string(body)
+                    .map_err(|e| LeaderboardError::InvalidResponse(e.to_string()))?;
+                req.set("Content-Type", "application/json")
+                    .send_string(&json)
+            }
+            None => req.call(),
+        };
+
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(401 | 403, _)) => {
+                return Err(LeaderboardError::NotAuthenticated)
+            }
+            Err(ureq::Error::Status(429, _)) => return Err(LeaderboardError::RateLimited),
+            Err(ureq::Error::Status(code @ (400 | 422), resp)) => {
+                let reason = resp.into_string().unwrap_or_else(|_| code.to_string());
+                return Err(LeaderboardError::ScoreRejected(reason));
+            }
+            Err(ureq::Error::Status(code, _)) => {
+                return Err(LeaderboardError::ServerError(format!("HTTP {}", code)))
+            }
+            Err(ureq::Error::Transport(e)) => {
+                *self.reachable.lock().unwrap() = false;
+                return Err(LeaderboardError::NetworkError(e.to_string()));
+            }
+        };
+        *self.reachable.lock().unwrap() = true;
+
+        let text = response
+            .into_string()
+            .map_err(|e| LeaderboardError::InvalidResponse(e.to_string()))?;
+        serde_json::from_str(&text).map_err(|e| LeaderboardError::InvalidResponse(e.to_string()))
     }
 
-    /// Make an HTTP request (placeholder - would use reqwest/ureq in real implementation)
+    /// Without the `online` feature there is no HTTP client
+    #[cfg(not(feature = "online"))]
     fn request<T: for<'de> Deserialize<'de>>(
         &self,
         _method: &str,
         _endpoint: &str,
         _body: Option<&impl Serialize>,
     ) -> LeaderboardResult<T> {
-        // In a real implementation, this would:
-        // 1. Build the full URL
-        // 2. Add authentication headers
-        // 3. Make the HTTP request
-        // 4. Parse the response
-        //
-        // For now, return an error indicating remote is not implemented
         Err(LeaderboardError::NetworkError(
-            "Remote backend not fully implemented - add HTTP client dependency".to_string(),
+            "built without the `online` feature".to_string(),
         ))
     }
 }
@@ -471,13 +528,19 @@ impl LeaderboardBackend for RemoteLeaderboard {
             message: Option<String>,
         }
 
-        let _response: SubmitResponse = self.request(
+        let response: SubmitResponse = self.request(
             "POST",
             "/api/v1/leaderboard/submit",
             Some(&SubmitRequest { entry }),
         )?;
 
-        Ok(())
+        if response.success {
+            Ok(())
+        } else {
+            Err(LeaderboardError::ScoreRejected(
+                response.message.unwrap_or_default(),
+            ))
+        }
     }
 
     fn get_leaderboard(
@@ -486,27 +549,16 @@ impl LeaderboardBackend for RemoteLeaderboard {
         limit: usize,
         offset: usize,
     ) -> LeaderboardResult<Vec<LeaderboardEntry>> {
-        #[derive(Serialize)]
-        struct ListRequest {
-            difficulty: Option<String>,
-            limit: usize,
-            offset: usize,
-        }
-
         #[derive(Deserialize)]
         struct ListResponse {
             entries: Vec<LeaderboardEntry>,
         }
 
-        let response: ListResponse = self.request(
-            "GET",
-            "/api/v1/leaderboard",
-            Some(&ListRequest {
-                difficulty: difficulty.map(|d| format!("{:?}", d)),
-                limit,
-                offset,
-            }),
-        )?;
+        let mut endpoint = format!("/api/v1/leaderboard?limit={}&offset={}", limit, offset);
+        if let Some(d) = difficulty {
+            endpoint.push_str(&format!("&difficulty={:?}", d));
+        }
+        let response: ListResponse = self.request("GET", &endpoint, None::<&()>)?;
 
         Ok(response.entries)
     }
@@ -558,9 +610,7 @@ impl LeaderboardBackend for RemoteLeaderboard {
     }
 
     fn is_available(&self) -> bool {
-        // In real implementation, would ping the server
-        // For now, always return false since not implemented
-        false
+        cfg!(feature = "online") && *self.reachable.lock().unwrap()
     }
 
     fn backend_name(&self) -> &'static str {
@@ -777,6 +827,8 @@ mod tests {
             puzzle_hash: "abc123".to_string(),
             rank: None,
             verified: true,
+            timer: TimerMode::Standard,
+            mode: GameMode::Classic,
         };
 
         backend.submit_score(entry).unwrap();
@@ -811,4 +863,20 @@ mod tests {
         assert!(backend.is_available());
         assert_eq!(backend.backend_name(), "Local");
     }
+
+    #[test]
+    fn test_remote_unreachable_falls_back() {
+        let remote = Arc::new(RemoteLeaderboard::new(RemoteConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            api_key: None,
+            timeout_secs: 1,
+        }));
+        let manager =
+            LeaderboardManager::new(remote.clone()).with_fallback(Arc::new(MockLeaderboard::new()));
+
+        assert!(remote.get_leaderboard(None, 10, 0).is_err());
+        assert!(!remote.is_available());
+        assert_eq!(manager.status().backend_name, "Mock");
+        assert!(manager.get_leaderboard(None, 10).unwrap().is_empty());
+    }
 }
@@ -1,78 +1,208 @@
+mod a11y;
+mod achievements;
 mod animations;
 mod app;
+mod bench;
+mod clipboard;
+mod config;
+mod editor;
+mod export;
 mod game;
+mod hint_roles;
+mod hotseat;
+mod import;
+mod keymap;
 mod leaderboard;
+mod packs;
+mod palette;
 mod persistence;
 mod render;
+mod slots;
 mod stats;
 mod telemetry;
 mod theme;
+mod timer;
+mod trainer;
+mod ui;
 
 use app::App;
 use clap::Parser;
+use config::Config;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    },
 };
-use std::io::{self, Write};
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::thread;
 use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "ukodus", about = "Terminal Sudoku game")]
 struct Cli {
-    /// Load a puzzle from an 81-character string or 8-character short code
+    /// Load a puzzle from an 81-character string, 8-character short code, or
+    /// file (e.g. .sdk); "-" or piping a puzzle reads it from stdin
     #[arg(long)]
     puzzle: Option<String>,
+
+    /// Config file to use instead of ~/.config/sudoku-tui/config.toml
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Print the game to stdout in this format on quit
+    #[arg(long, value_enum)]
+    dump: Option<export::ExportFormat>,
+
+    /// Screen-reader line mode: the board as plain text rows and every
+    /// action described in words
+    #[arg(long)]
+    a11y: bool,
+
+    /// Write all stats, game history and the local leaderboard as JSON to
+    /// this file ("-" for stdout) and exit
+    #[arg(long, value_name = "FILE")]
+    export_stats: Option<PathBuf>,
+
+    /// Time generating, rating and hinting N puzzles (default 20, Medium)
+    /// and print the results without starting the game
+    #[arg(long, num_args = 0..=2, value_names = ["N", "DIFFICULTY"])]
+    bench: Option<Vec<String>>,
 }
 
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
 
+    // Read config before taking over the terminal so errors are visible
+    let mut config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Config error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    config.a11y |= cli.a11y;
+
+    if let Some(path) = &cli.export_stats {
+        let json = stats::StatsManager::load().export_json();
+        if path.as_os_str() == "-" {
+            println!("{}", json);
+        } else if let Err(e) = persistence::atomic_write(path, json.as_bytes()) {
+            eprintln!("{}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(args) = &cli.bench {
+        match bench::parse_args(args) {
+            Ok((count, difficulty)) => bench::run(count, difficulty),
+            Err(e) => {
+                eprintln!("Bench error: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
+
+    // Likewise for an imported puzzle
+    let puzzle = match import::read_puzzle(cli.puzzle.as_deref()) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("Puzzle error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    restore_terminal_on_panic();
 
     // Run the app
-    let result = run_app(&mut stdout, cli.puzzle.as_deref());
+    let result = run_app(&mut stdout, puzzle.as_deref(), &config);
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
 
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
+    match result {
+        Ok(app) => {
+            if let Some(format) = cli.dump {
+                println!("{}", format.render(&app.game).trim_end());
+            }
+        }
+        Err(e) => eprintln!("Error: {}", e),
     }
 
     Ok(())
 }
 
-fn run_app(stdout: &mut io::Stdout, puzzle: Option<&str>) -> io::Result<()> {
-    let mut app = App::new_with_puzzle(puzzle);
+/// Leave raw mode and the alternate screen before the panic message is
+/// printed, so it shows on the normal screen and the shell still works.
+/// Panics on worker threads leave the game running and the terminal alone.
+fn restore_terminal_on_panic() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            let _ = disable_raw_mode();
+            let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+        }
+        default(info);
+    }));
+}
+
+/// Run the event loop until the player quits, returning the final app state.
+/// If the loop panics, the game is autosaved before the panic carries on.
+fn run_app(stdout: &mut io::Stdout, puzzle: Option<&str>, config: &Config) -> io::Result<App> {
+    let mut app = App::new_with_puzzle(puzzle, config);
+    match panic::catch_unwind(AssertUnwindSafe(|| event_loop(stdout, &mut app))) {
+        Ok(result) => result.map(|()| app),
+        Err(payload) => {
+            app.autosave();
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+fn event_loop(stdout: &mut io::Stdout, app: &mut App) -> io::Result<()> {
+    let mut screen = ui::Terminal::new();
     let mut last_tick = Instant::now();
 
     loop {
         // Determine tick rate based on screen mode
         let tick_rate = app.get_tick_rate();
 
-        // Render
-        render::render(stdout, &mut app)?;
-        stdout.flush()?;
+        // Render, writing only what changed since the last frame
+        let (width, height) = terminal::size()?;
+        screen.draw(stdout, width, height, |frame| render::render(frame, app))?;
 
         // Handle input with timeout for animation updates
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
         if event::poll(timeout.min(Duration::from_millis(33)))? {
-            if let Event::Key(key) = event::read()? {
-                // Handle Ctrl+C
-                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-                    break;
-                }
+            match event::read()? {
+                Event::Key(key) => {
+                    // Handle Ctrl+C
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('c')
+                    {
+                        app.autosave();
+                        break;
+                    }
 
-                match app.handle_key(key) {
-                    app::AppAction::Continue => {}
-                    app::AppAction::Quit => break,
+                    match app.handle_key(key) {
+                        app::AppAction::Continue => {}
+                        app::AppAction::Quit => break,
+                    }
                 }
+                Event::Mouse(mouse) => app.handle_mouse(mouse, terminal::size()?),
+                Event::Resize(..) => screen.invalidate(),
+                _ => {}
             }
         }
 
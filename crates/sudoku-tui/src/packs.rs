@@ -0,0 +1,220 @@
+//! Puzzle packs: collections of puzzles loaded from a directory.
+//!
+//! A pack is either a JSON file
+//!
+//! ```json
+//! { "name": "Tough Ones", "author": "...",
+//!   "puzzles": [{ "puzzle": "53..7....", "title": "No. 1", "rating": 7.2 }] }
+//! ```
+//!
+//! or a text file with one puzzle per line (`.txt`, `.sdm`), named after the
+//! file. Which puzzles of each pack were solved is kept in
+//! `pack_progress.json` next to the config file, keyed by file name.
+
+use crate::config::Config;
+use crate::persistence::atomic_write;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sudoku_session::parse_puzzle_text;
+
+/// One puzzle in a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackPuzzle {
+    pub puzzle: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// SE rating, if the pack provides one
+    #[serde(default)]
+    pub rating: Option<f32>,
+}
+
+/// A loaded pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzlePack {
+    pub name: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    pub puzzles: Vec<PackPuzzle>,
+    /// File name, used as the pack's id for progress
+    #[serde(skip)]
+    pub id: String,
+}
+
+/// Solved puzzle indices per pack id
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PackProgress(HashMap<String, BTreeSet<usize>>);
+
+impl PackProgress {
+    fn path() -> Option<PathBuf> {
+        Some(Config::dir()?.join("pack_progress.json"))
+    }
+
+    /// Load saved progress (empty if there is none)
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        atomic_write(&path, json.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    pub fn is_solved(&self, pack: &str, index: usize) -> bool {
+        self.0
+            .get(pack)
+            .is_some_and(|solved| solved.contains(&index))
+    }
+
+    pub fn solved_count(&self, pack: &str) -> usize {
+        self.0.get(pack).map_or(0, BTreeSet::len)
+    }
+
+    /// Mark a puzzle solved and save
+    pub fn mark_solved(&mut self, pack: &str, index: usize) -> Result<(), String> {
+        if self.0.entry(pack.to_string()).or_default().insert(index) {
+            self.save()?;
+        }
+        Ok(())
+    }
+}
+
+/// Read a pack file. Text packs skip lines that aren't a puzzle.
+pub fn read_pack(path: &Path) -> Result<PuzzlePack, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let id = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut pack = if path.extension().is_some_and(|e| e == "json") {
+        serde_json::from_str::<PuzzlePack>(&text)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+    } else {
+        let puzzles = text
+            .lines()
+            .filter_map(parse_puzzle_text)
+            .map(|puzzle| PackPuzzle {
+                puzzle,
+                title: None,
+                rating: None,
+            })
+            .collect();
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        PuzzlePack {
+            name,
+            author: None,
+            puzzles,
+            id: String::new(),
+        }
+    };
+    pack.id = id;
+
+    if pack.puzzles.is_empty() {
+        return Err(format!("{}: no puzzles", path.display()));
+    }
+    Ok(pack)
+}
+
+/// Load every pack in `dir`, sorted by name, with one message per file that
+/// couldn't be read
+pub fn load_packs(dir: &Path) -> (Vec<PuzzlePack>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|e| e == "json" || e == "txt" || e == "sdm")
+        })
+        .collect();
+    paths.sort();
+
+    let mut packs = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        match read_pack(&path) {
+            Ok(pack) => packs.push(pack),
+            Err(e) => errors.push(e),
+        }
+    }
+    (packs, errors)
+}
+
+/// Browser state: the pack list, or one pack's puzzles when `open` is set
+#[derive(Debug, Default)]
+pub struct PackBrowser {
+    pub packs: Vec<PuzzlePack>,
+    pub progress: PackProgress,
+    /// Pack whose puzzles are listed
+    pub open: Option<usize>,
+    /// Selected row in the current list
+    pub selection: usize,
+}
+
+impl PackBrowser {
+    /// Load packs and progress
+    pub fn load(dir: &Path) -> (Self, Vec<String>) {
+        let (packs, errors) = load_packs(dir);
+        let browser = Self {
+            packs,
+            progress: PackProgress::load(),
+            open: None,
+            selection: 0,
+        };
+        (browser, errors)
+    }
+
+    /// Rows in the current list
+    pub fn row_count(&self) -> usize {
+        match self.open {
+            Some(pack) => self.packs[pack].puzzles.len(),
+            None => self.packs.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_read_text_and_json_packs() {
+        let dir = tempfile::tempdir().unwrap();
+        let puzzle = Tier::Singles.puzzle();
+
+        let text_path = dir.path().join("classics.txt");
+        fs::write(&text_path, format!("# comment\n{}\nnot a puzzle\n", puzzle)).unwrap();
+        let json_path = dir.path().join("rated.json");
+        fs::write(
+            &json_path,
+            format!(
+                r#"{{"name": "Rated", "puzzles": [{{"puzzle": "{}", "rating": 2.3}}]}}"#,
+                puzzle
+            ),
+        )
+        .unwrap();
+        fs::write(dir.path().join("empty.sdm"), "").unwrap();
+
+        let (packs, errors) = load_packs(dir.path());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(packs.len(), 2);
+        assert_eq!(packs[0].name, "classics");
+        assert_eq!(packs[0].puzzles.len(), 1);
+        assert_eq!(packs[1].id, "rated.json");
+        assert_eq!(packs[1].puzzles[0].rating, Some(2.3));
+    }
+}
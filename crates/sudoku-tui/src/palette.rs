@@ -0,0 +1,87 @@
+//! Command palette: type part of a command's name to find and run it.
+
+/// A command the palette can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    NewGame,
+    Theme,
+    Export,
+    Copy,
+    SaveSlot,
+    LoadSlot,
+    Pause,
+    Moves,
+    Bookmark,
+    Bookmarks,
+    AutoNotes,
+    Trainer,
+    Packs,
+    Editor,
+    Stats,
+    ExportStats,
+    Leaderboard,
+    History,
+    Achievements,
+    Keys,
+    Quit,
+}
+
+/// Every command with its name, in the order listed when nothing is typed
+pub const COMMANDS: [(Command, &str); 21] = [
+    (Command::NewGame, "new game"),
+    (Command::Theme, "theme"),
+    (Command::Export, "export"),
+    (Command::Copy, "copy to clipboard"),
+    (Command::SaveSlot, "save to slot"),
+    (Command::LoadSlot, "load slot"),
+    (Command::Pause, "pause"),
+    (Command::Moves, "move history"),
+    (Command::Bookmark, "bookmark position"),
+    (Command::Bookmarks, "restore bookmark"),
+    (Command::AutoNotes, "auto notes"),
+    (Command::Trainer, "technique trainer"),
+    (Command::Packs, "puzzle packs"),
+    (Command::Editor, "puzzle editor"),
+    (Command::Stats, "statistics"),
+    (Command::ExportStats, "export statistics"),
+    (Command::Leaderboard, "leaderboard"),
+    (Command::History, "game history"),
+    (Command::Achievements, "achievements"),
+    (Command::Keys, "keys"),
+    (Command::Quit, "quit"),
+];
+
+/// Commands whose name contains the letters of `query` in order (so "ntr"
+/// finds "technique trainer"), names starting with the query first
+pub fn search(query: &str) -> Vec<(Command, &'static str)> {
+    let query = query.trim().to_lowercase();
+    let mut found: Vec<_> = COMMANDS
+        .iter()
+        .copied()
+        .filter(|(_, name)| {
+            let mut letters = name.chars();
+            query
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .all(|q| letters.any(|c| c == q))
+        })
+        .collect();
+    // Stable, so ties keep the listing order
+    found.sort_by_key(|(_, name)| !name.starts_with(&query));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        assert_eq!(search("").len(), COMMANDS.len());
+        assert_eq!(search("exp")[0].0, Command::Export);
+        assert_eq!(search("ntr")[0].0, Command::Trainer);
+        // Prefix matches come first
+        assert_eq!(search("p")[0].0, Command::Pause);
+        assert!(search("zzz").is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+//! The puzzle editor.
+
+use super::{footer, screen_sections, title, MARGIN};
+use crate::app::App;
+use crate::editor::Check;
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+use sudoku_core::Position;
+
+/// Puzzle editor: the grid being typed in and the live check
+pub fn render_editor_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let editor = &app.editor;
+
+    let (header, body, nav) = screen_sections(area);
+    title(stdout, app, header, "═══ PUZZLE EDITOR ═══")?;
+
+    let check_color = match editor.check {
+        Check::Unique { .. } => theme.success,
+        Check::Clash(_) | Check::NoSolution => theme.error,
+        Check::MultipleSolutions => theme.info,
+    };
+    execute!(
+        stdout,
+        body.at(MARGIN, 0),
+        SetForegroundColor(theme.info),
+        Print(format!("{} givens - ", editor.clues())),
+        SetForegroundColor(check_color),
+        Print(editor.check.describe())
+    )?;
+
+    // 9 rows plus 2 box separators, each cell 2 columns wide
+    let parts = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .split(body);
+    let (board, message) = (parts[1], parts[2]);
+    let grid = board.centered(23, 11);
+    let clash = match editor.check {
+        Check::Clash(pos) => Some(pos),
+        _ => None,
+    };
+    let mut y = 0;
+    for row in 0..9 {
+        if row == 3 || row == 6 {
+            execute!(
+                stdout,
+                grid.at(0, y),
+                SetForegroundColor(theme.box_border),
+                Print("───────┼───────┼───────")
+            )?;
+            y += 1;
+        }
+        execute!(stdout, grid.at(0, y))?;
+        for col in 0..9 {
+            if col == 3 || col == 6 {
+                execute!(stdout, SetForegroundColor(theme.box_border), Print(" │"))?;
+            }
+            let pos = Position::new(row, col);
+            let bg = if pos == editor.cursor {
+                theme.selected_bg
+            } else {
+                theme.bg
+            };
+            let (text, fg) = match editor.cells[row][col] {
+                Some(v) if clash == Some(pos) => (v.to_string(), theme.error),
+                Some(v) => (v.to_string(), theme.given),
+                None => ("·".to_string(), theme.border),
+            };
+            execute!(
+                stdout,
+                Print(" "),
+                SetBackgroundColor(bg),
+                SetForegroundColor(fg),
+                Print(text),
+                SetBackgroundColor(theme.bg)
+            )?;
+        }
+        y += 1;
+    }
+
+    if let Some(ref msg) = app.message {
+        execute!(
+            stdout,
+            message.at(MARGIN, 0),
+            SetForegroundColor(theme.info),
+            Print(msg)
+        )?;
+    }
+
+    footer(
+        stdout,
+        app,
+        nav,
+        &[
+            ("1-9", "Set"),
+            ("0/Del", "Clear"),
+            ("X", "Clear all"),
+            ("Enter", "Play"),
+            ("Esc", "Back"),
+        ],
+    )
+}
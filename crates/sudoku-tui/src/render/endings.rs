@@ -0,0 +1,250 @@
+//! The animated win and lose screens.
+
+use super::centered;
+use crate::animations::particles::hue_to_rgb;
+use crate::app::App;
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Color, Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+
+pub fn render_win_screen(stdout: &mut Buffer, app: &mut App, area: Rect) -> io::Result<()> {
+    app.win_screen.resize(area.width, area.height);
+
+    // Consistent dark background base
+    let bg_base = Color::Rgb { r: 8, g: 12, b: 20 };
+
+    // Background - draw entire screen
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (ch, color) = app.win_screen.background.render_at(
+                x as usize,
+                y as usize,
+                area.width as usize,
+                area.height as usize,
+                app.win_screen.frame_count() as f32,
+            );
+            execute!(
+                stdout,
+                area.at(x, y),
+                SetForegroundColor(color),
+                SetBackgroundColor(bg_base),
+                Print(ch)
+            )?;
+        }
+    }
+
+    // Particles - draw on top of background
+    for particle in app.win_screen.particles() {
+        if particle.is_visible(area.width, area.height) {
+            execute!(
+                stdout,
+                area.at(particle.x as u16, particle.y as u16),
+                SetForegroundColor(particle.color),
+                SetBackgroundColor(bg_base),
+                Print(particle.char)
+            )?;
+        }
+    }
+
+    // Banner
+    let banner = app.win_screen.current_banner();
+    let lines: Vec<&str> = banner.lines().filter(|l| !l.is_empty()).collect();
+    let banner_width = lines.iter().map(|l| l.len()).max().unwrap_or(40) as u16;
+    // Banner, message, stats and instructions down the middle
+    let rows = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(lines.len() as u16),
+        Constraint::Length(2),
+        Constraint::Length(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+    let banner_area = rows[1].centered(banner_width, rows[1].height);
+
+    for (i, line) in lines.iter().enumerate() {
+        let hue = (app.win_screen.rainbow_offset() + i as f32 * 0.1) % 1.0;
+        execute!(
+            stdout,
+            banner_area.at(0, i as u16),
+            SetForegroundColor(hue_to_rgb(hue)),
+            SetBackgroundColor(bg_base),
+            Print(line)
+        )?;
+    }
+
+    // Message
+    let msg = app.win_screen.current_message();
+    let hue = (app.win_screen.rainbow_offset() * 2.0) % 1.0;
+
+    execute!(
+        stdout,
+        centered(rows[3], msg),
+        SetForegroundColor(hue_to_rgb(hue)),
+        SetBackgroundColor(bg_base),
+        Print(msg)
+    )?;
+
+    // Stats box
+    let stats = format!(
+        "Time: {} | Hints: {} | Score: {} | Difficulty: {}",
+        app.game.elapsed_string(),
+        app.game.hints_used(),
+        app.game.current_score(),
+        app.game.difficulty()
+    );
+    let stats = format!(" {} ", stats);
+    execute!(
+        stdout,
+        centered(rows[4], &stats),
+        SetForegroundColor(Color::White),
+        SetBackgroundColor(Color::Rgb {
+            r: 30,
+            g: 50,
+            b: 30
+        }),
+        Print(&stats)
+    )?;
+
+    // Instructions
+    let instr = "Press 'n' for new game or 'q' to quit";
+    execute!(
+        stdout,
+        centered(rows[5], instr),
+        SetForegroundColor(Color::Yellow),
+        SetBackgroundColor(bg_base),
+        Print(instr)
+    )?;
+
+    Ok(())
+}
+
+pub fn render_lose_screen(stdout: &mut Buffer, app: &mut App, area: Rect) -> io::Result<()> {
+    app.lose_screen.resize(area.width, area.height);
+
+    // Consistent dark background base
+    let bg_base = Color::Rgb {
+        r: 15,
+        g: 10,
+        b: 12,
+    };
+
+    // Background - draw entire screen
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (ch, color) = app.lose_screen.background.render_at(
+                x as usize,
+                y as usize,
+                area.width as usize,
+                area.height as usize,
+                app.lose_screen.frame_count() as f32,
+            );
+            execute!(
+                stdout,
+                area.at(x, y),
+                SetForegroundColor(color),
+                SetBackgroundColor(bg_base),
+                Print(ch)
+            )?;
+        }
+    }
+
+    // Particles - draw on top of background
+    for particle in app.lose_screen.particles() {
+        if particle.is_visible(area.width, area.height) {
+            execute!(
+                stdout,
+                area.at(particle.x as u16, particle.y as u16),
+                SetForegroundColor(particle.color),
+                SetBackgroundColor(bg_base),
+                Print(particle.char)
+            )?;
+        }
+    }
+
+    // Banner
+    let banner = app.lose_screen.current_banner();
+    let lines: Vec<&str> = banner.lines().filter(|l| !l.is_empty()).collect();
+    let banner_width = lines.iter().map(|l| l.len()).max().unwrap_or(40) as u16;
+    // Banner, message, stats and instructions down the middle
+    let rows = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(lines.len() as u16),
+        Constraint::Length(2),
+        Constraint::Length(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+    let banner_area = rows[1].centered(banner_width, rows[1].height);
+
+    for (i, line) in lines.iter().enumerate() {
+        let intensity = 150u8.saturating_sub((i * 10).min(100) as u8);
+        execute!(
+            stdout,
+            banner_area.at(0, i as u16),
+            SetForegroundColor(Color::Rgb {
+                r: intensity,
+                g: 30,
+                b: 30
+            }),
+            SetBackgroundColor(bg_base),
+            Print(line)
+        )?;
+    }
+
+    // Message with pulse
+    let msg = if app.game.is_time_up() {
+        "Out of time!"
+    } else {
+        app.lose_screen.current_message()
+    };
+    let pulse = ((app.lose_screen.frame_count() as f32 * 0.1).sin() * 0.3 + 0.7) * 255.0;
+
+    execute!(
+        stdout,
+        centered(rows[3], msg),
+        SetForegroundColor(Color::Rgb {
+            r: pulse as u8,
+            g: 50,
+            b: 50
+        }),
+        SetBackgroundColor(bg_base),
+        Print(msg)
+    )?;
+
+    // Stats box
+    let stats = format!(
+        "Mistakes: {} | Time: {} | Difficulty: {}",
+        app.game.mistakes_label(),
+        app.game.elapsed_string(),
+        app.game.difficulty()
+    );
+    let stats = format!(" {} ", stats);
+    execute!(
+        stdout,
+        centered(rows[4], &stats),
+        SetForegroundColor(Color::Grey),
+        SetBackgroundColor(Color::Rgb {
+            r: 30,
+            g: 20,
+            b: 20
+        }),
+        Print(&stats)
+    )?;
+
+    // Instructions
+    let instr = "Press 'r' to retry, 'z' for zen mode, 'n' for new game or 'q' to quit";
+    execute!(
+        stdout,
+        centered(rows[5], instr),
+        SetForegroundColor(Color::DarkYellow),
+        SetBackgroundColor(bg_base),
+        Print(instr)
+    )?;
+
+    Ok(())
+}
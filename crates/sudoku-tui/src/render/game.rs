@@ -0,0 +1,1115 @@
+//! The game screen: the grid, its side panels and the key help, with the
+//! menus and hints drawn over it.
+
+use super::menu::render_menu;
+use super::{centered, popup, wrap_text};
+use crate::animations::CelebrationManager;
+use crate::app::{App, InputMode, MenuState, MAX_MISTAKES};
+use crate::hint_roles::role_color;
+use crate::keymap::Action;
+use crate::stats::format_time;
+use crate::timer::{format_split, TimerMode};
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Color, Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+use sudoku_core::{Hint, Position};
+use sudoku_glossary::Verbosity;
+use sudoku_render::{hint_roles, HintRole};
+use sudoku_session::ConflictKind;
+
+/// How much of the game screen fits in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// Bordered grid, info panel and (if there's room) the controls
+    Full,
+    /// Bordered grid with a one-line status below it
+    NoPanel,
+    /// Grid without cell borders and a one-line status
+    Compact,
+    /// Not even the compact grid fits
+    TooSmall,
+}
+
+/// Screen positions of the game screen's interactive parts, shared by the
+/// renderer and mouse hit-testing
+#[derive(Debug, Clone, Copy)]
+pub struct GameLayout {
+    pub mode: LayoutMode,
+    pub grid_x: u16,
+    pub grid_y: u16,
+    pub info_x: u16,
+    /// Left edge of the techniques panel, when it is on and fits
+    pub techniques_x: Option<u16>,
+}
+
+/// Bordered grid size
+const GRID_WIDTH: u16 = 37;
+const GRID_HEIGHT: u16 = 19;
+/// Gap plus info panel to the right of the grid
+const PANEL_WIDTH: u16 = 25;
+/// Borderless grid size: 3-char cells, lines only between boxes
+const COMPACT_WIDTH: u16 = 29;
+const COMPACT_HEIGHT: u16 = 11;
+/// Techniques panel to the right of the info panel
+const TECHNIQUES_WIDTH: u16 = 26;
+/// Rows of key help under the grid in the full layout
+const CONTROLS_HEIGHT: u16 = 4;
+
+/// Row offset of the "Numbers: 123456789" line within the info panel
+const NUMBERS_ROW: u16 = 14;
+/// Column offset of the first digit on that line (after "Numbers: ")
+const NUMBERS_COL: u16 = 9;
+
+impl GameLayout {
+    /// Cell under a terminal position, if any (borders don't count)
+    pub fn cell_at(&self, column: u16, row: u16) -> Option<Position> {
+        let (col, row) = match self.mode {
+            LayoutMode::Full | LayoutMode::NoPanel => {
+                let dx = column.checked_sub(self.grid_x + 1)?;
+                let dy = row.checked_sub(self.grid_y + 1)?;
+                // Each cell is 3 chars plus a border, each row is 1 line plus a separator
+                if dx % 4 == 3 || dy % 2 == 1 {
+                    return None;
+                }
+                ((dx / 4) as usize, (dy / 2) as usize)
+            }
+            LayoutMode::Compact => {
+                let dx = column.checked_sub(self.grid_x)?;
+                let dy = row.checked_sub(self.grid_y)?;
+                // Boxes are 9 chars plus a separator column, 3 rows plus a separator line
+                if dx % 10 == 9 || dy % 4 == 3 {
+                    return None;
+                }
+                (
+                    (dx / 10 * 3 + dx % 10 / 3) as usize,
+                    (dy / 4 * 3 + dy % 4) as usize,
+                )
+            }
+            LayoutMode::TooSmall => return None,
+        };
+        (col < 9 && row < 9).then(|| Position::new(row, col))
+    }
+
+    /// Digit under a terminal position in the info panel's number list
+    pub fn digit_at(&self, column: u16, row: u16) -> Option<u8> {
+        if self.mode != LayoutMode::Full || row != self.grid_y + NUMBERS_ROW {
+            return None;
+        }
+        let offset = column.checked_sub(self.info_x + NUMBERS_COL)?;
+        (offset < 9).then(|| offset as u8 + 1)
+    }
+
+    /// Area the grid is drawn in
+    fn grid(&self) -> Rect {
+        let (width, height) = match self.mode {
+            LayoutMode::Compact => (COMPACT_WIDTH, COMPACT_HEIGHT),
+            _ => (GRID_WIDTH, GRID_HEIGHT),
+        };
+        Rect::new(self.grid_x, self.grid_y, width, height)
+    }
+
+    /// Area of the info panel beside the grid
+    fn info(&self) -> Rect {
+        Rect::new(self.info_x, self.grid_y, PANEL_WIDTH - 3, GRID_HEIGHT)
+    }
+
+    /// Area of the techniques panel, when it is shown
+    fn techniques(&self) -> Option<Rect> {
+        self.techniques_x
+            .map(|x| Rect::new(x, self.grid_y, TECHNIQUES_WIDTH, GRID_HEIGHT))
+    }
+
+    /// Row just below the grid
+    fn below_grid(&self) -> u16 {
+        let grid = self.grid();
+        grid.y + grid.height
+    }
+}
+
+/// Compute where the game screen is drawn for a terminal size, making room
+/// for the techniques panel if `techniques` is set
+pub fn game_layout(term_width: u16, term_height: u16, techniques: bool) -> GameLayout {
+    // Row 0 is kept for messages, one row under the grid for status
+    let mode = if term_width >= GRID_WIDTH + PANEL_WIDTH && term_height > GRID_HEIGHT + 1 {
+        LayoutMode::Full
+    } else if term_width >= GRID_WIDTH && term_height > GRID_HEIGHT + 1 {
+        LayoutMode::NoPanel
+    } else if term_width >= COMPACT_WIDTH && term_height > COMPACT_HEIGHT + 1 {
+        LayoutMode::Compact
+    } else {
+        LayoutMode::TooSmall
+    };
+
+    let with_techniques = mode == LayoutMode::Full
+        && techniques
+        && term_width >= GRID_WIDTH + PANEL_WIDTH + TECHNIQUES_WIDTH;
+
+    let screen = Rect::new(0, 0, term_width, term_height);
+    match mode {
+        LayoutMode::Full => {
+            // Grid, info panel and techniques panel side by side, centered
+            let mut columns = vec![
+                Constraint::Length(GRID_WIDTH),
+                Constraint::Length(PANEL_WIDTH),
+            ];
+            if with_techniques {
+                columns.push(Constraint::Length(TECHNIQUES_WIDTH));
+            }
+            let width =
+                GRID_WIDTH + PANEL_WIDTH + if with_techniques { TECHNIQUES_WIDTH } else { 0 };
+            let mut area = screen.centered(width, GRID_HEIGHT);
+            area.x = area.x.max(1);
+            area.y = if term_height > GRID_HEIGHT + 12 { 2 } else { 1 };
+            let parts = Layout::horizontal(columns).split(area);
+            GameLayout {
+                mode,
+                grid_x: parts[0].x,
+                grid_y: parts[0].y,
+                info_x: parts[1].x + 3,
+                techniques_x: parts.get(2).map(|panel| panel.x),
+            }
+        }
+        LayoutMode::NoPanel | LayoutMode::Compact | LayoutMode::TooSmall => {
+            let grid = match mode {
+                LayoutMode::NoPanel => screen.centered(GRID_WIDTH, GRID_HEIGHT),
+                LayoutMode::Compact => screen.centered(COMPACT_WIDTH, COMPACT_HEIGHT),
+                _ => Rect::default(),
+            };
+            let grid_y = if mode == LayoutMode::TooSmall { 0 } else { 1 };
+            GameLayout {
+                mode,
+                grid_x: grid.x,
+                grid_y,
+                info_x: grid.x + GRID_WIDTH + 3,
+                techniques_x: None,
+            }
+        }
+    }
+}
+
+/// The board with its panels, and the menu, hint or message over it
+pub fn render_game_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let layout = game_layout(area.width, area.height, app.show_techniques);
+    let grid = layout.grid();
+    // The controls and the hint keep a blank row between them and the grid
+    let below = Rect::new(
+        grid.x,
+        layout.below_grid() + 1,
+        area.width.saturating_sub(grid.x),
+        area.height.saturating_sub(layout.below_grid() + 1),
+    );
+
+    match layout.mode {
+        LayoutMode::Full => {
+            render_grid(stdout, app, grid)?;
+            render_info_panel(stdout, app, layout.info())?;
+            if let Some(panel) = layout.techniques() {
+                render_techniques_panel(stdout, app, panel)?;
+            }
+            if below.height >= CONTROLS_HEIGHT {
+                if app.game.timer_mode() == TimerMode::Speedrun {
+                    render_splits(stdout, app, below)?;
+                } else {
+                    render_controls(stdout, app, below)?;
+                }
+            }
+        }
+        LayoutMode::NoPanel => {
+            render_grid(stdout, app, grid)?;
+            render_status_line(stdout, app, grid.row(grid.height))?;
+        }
+        LayoutMode::Compact => {
+            render_compact_grid(stdout, app, grid)?;
+            render_status_line(stdout, app, grid.row(grid.height))?;
+        }
+        LayoutMode::TooSmall => {
+            let lines = [
+                "Terminal too small".to_string(),
+                format!("need {}x{}", COMPACT_WIDTH, COMPACT_HEIGHT + 2),
+            ];
+            let middle = area.centered(area.width, 2);
+            for (i, line) in lines.iter().enumerate() {
+                execute!(
+                    stdout,
+                    centered(middle.row(i as u16), line),
+                    SetBackgroundColor(app.theme.bg),
+                    SetForegroundColor(app.theme.info),
+                    Print(line)
+                )?;
+            }
+            return Ok(());
+        }
+    }
+
+    if let Some(ref msg) = app.message {
+        render_message(stdout, app, msg, area)?;
+    }
+
+    if app.menu != MenuState::None {
+        render_menu(stdout, app, area)?;
+    }
+
+    if let Some(ref hint) = app.current_hint {
+        // Below the grid, so the highlighted cells stay visible
+        render_hint(stdout, app, hint, area, below)?;
+    }
+
+    Ok(())
+}
+
+/// A message centered on the top row
+fn render_message(stdout: &mut Buffer, app: &App, msg: &str, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let padded = format!("  {}  ", msg);
+
+    execute!(
+        stdout,
+        centered(area.row(0), &padded),
+        SetForegroundColor(theme.fg),
+        SetBackgroundColor(theme.selected_bg),
+        Print(&padded)
+    )?;
+
+    Ok(())
+}
+
+/// Which techniques apply right now, without saying where
+fn render_techniques_panel(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let width = area.width.saturating_sub(2) as usize;
+
+    execute!(
+        stdout,
+        SetBackgroundColor(theme.bg),
+        area.at(0, 0),
+        SetForegroundColor(theme.key),
+        Print("Techniques available"),
+        area.at(0, 1),
+        SetForegroundColor(theme.border),
+        Print("─".repeat(width))
+    )?;
+
+    if app.available_techniques.is_empty() {
+        execute!(
+            stdout,
+            area.at(0, 2),
+            SetForegroundColor(theme.info),
+            Print("none")
+        )?;
+        return Ok(());
+    }
+
+    let rows = area.height.saturating_sub(2) as usize;
+    for (i, (name, count)) in app.available_techniques.iter().take(rows).enumerate() {
+        let name: String = name.chars().take(width - 4).collect();
+        execute!(
+            stdout,
+            area.at(0, 2 + i as u16),
+            SetForegroundColor(theme.fg),
+            Print(format!("{:<1$}", name, width - 4)),
+            SetForegroundColor(theme.info),
+            Print(format!("{:>4}", format!("×{}", count)))
+        )?;
+    }
+    Ok(())
+}
+
+/// Borderless grid for small terminals (29 x 11)
+fn render_compact_grid(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let roles = app.current_hint.as_ref().map(hint_roles);
+
+    for row in 0..9 {
+        let y = (row + row / 3) as u16;
+        execute!(stdout, area.at(0, y))?;
+        for col in 0..9 {
+            if col > 0 && col % 3 == 0 {
+                execute!(
+                    stdout,
+                    SetBackgroundColor(theme.bg),
+                    SetForegroundColor(theme.box_border),
+                    Print("│")
+                )?;
+            }
+            let role = roles.map_or(HintRole::None, |r| r[row * 9 + col]);
+            render_cell(stdout, app, Position::new(row, col), role)?;
+        }
+        if row == 2 || row == 5 {
+            execute!(
+                stdout,
+                area.at(0, y + 1),
+                SetBackgroundColor(theme.bg),
+                SetForegroundColor(theme.box_border),
+                Print("─────────┼─────────┼─────────")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// One-line summary of the info panel: difficulty, time, mistakes, progress
+fn render_status_line(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let game = &app.game;
+    let mode = match app.mode {
+        InputMode::Candidate => " NOTES",
+        InputMode::Corner => " CORNER",
+        _ => "",
+    };
+    let status = match &app.hotseat {
+        Some(hotseat) => format!(
+            "P{} to move {}-{} {}%{}",
+            hotseat.current + 1,
+            hotseat.players[0].placed,
+            hotseat.players[1].placed,
+            game.completion_percent(),
+            mode
+        ),
+        None => format!(
+            "{}{} ✗{} {}%{}",
+            game.difficulty(),
+            if game.timer_shown() {
+                format!(" {}", game.clock_string())
+            } else {
+                String::new()
+            },
+            game.mistakes_label(),
+            game.completion_percent(),
+            mode
+        ),
+    };
+    let color = if game.on_last_chance() {
+        theme.error
+    } else {
+        theme.info
+    };
+    let width = area.width as usize;
+    let status: String = status.chars().take(width).collect();
+    execute!(
+        stdout,
+        area.at(0, 0),
+        SetBackgroundColor(theme.bg),
+        SetForegroundColor(color),
+        Print(format!("{:^1$}", status, width))
+    )?;
+    Ok(())
+}
+
+fn render_grid(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let roles = app.current_hint.as_ref().map(hint_roles);
+
+    // Grid design:
+    // +---+---+---+---+---+---+---+---+---+
+    // | 5 | 3 | . | . | 7 | . | . | . | . |
+    // +---+---+---+---+---+---+---+---+---+
+    // Each cell is 3 chars: " X "
+    // Border chars: + and - and |
+
+    execute!(stdout, SetBackgroundColor(theme.bg))?;
+
+    // Top border (thick - uses box_border for visibility)
+    // Check if row 0 is celebrating
+    let row0_intensity = app.celebrations.row_intensity(0);
+    let top_border_color = CelebrationManager::throb_color(theme.box_border, row0_intensity);
+    execute!(
+        stdout,
+        area.at(0, 0),
+        SetForegroundColor(top_border_color),
+        Print("+===+===+===+===+===+===+===+===+===+")
+    )?;
+
+    for row in 0..9 {
+        let cell_y = 1 + row as u16 * 2;
+
+        // Get row celebration intensity
+        let row_intensity = app.celebrations.row_intensity(row);
+
+        // Cell row
+        execute!(stdout, area.at(0, cell_y))?;
+
+        for col in 0..9 {
+            // Get column celebration intensity
+            let col_intensity = app.celebrations.column_intensity(col);
+            let border_intensity = row_intensity.max(col_intensity);
+
+            // Left border - thick borders at 3x3 boundaries
+            let base_border_color = if col % 3 == 0 {
+                theme.box_border
+            } else {
+                theme.border
+            };
+            let border_color = CelebrationManager::throb_color(base_border_color, border_intensity);
+
+            if col % 3 == 0 {
+                execute!(stdout, SetForegroundColor(border_color), Print("║"))?;
+            } else {
+                execute!(stdout, SetForegroundColor(border_color), Print("│"))?;
+            }
+
+            let pos = Position::new(row, col);
+            let role = roles.map_or(HintRole::None, |r| r[row * 9 + col]);
+            render_cell(stdout, app, pos, role)?;
+        }
+        // Right border (thick) - use row intensity for right border
+        let right_border_color = CelebrationManager::throb_color(theme.box_border, row_intensity);
+        execute!(stdout, SetForegroundColor(right_border_color), Print("║"))?;
+
+        // Horizontal separator
+        execute!(stdout, area.at(0, cell_y + 1))?;
+
+        // Get intensities for the row below this separator
+        let next_row = row + 1;
+        let row_below_intensity = if next_row < 9 {
+            app.celebrations.row_intensity(next_row)
+        } else {
+            0.0
+        };
+        let sep_intensity = row_intensity.max(row_below_intensity);
+
+        if row == 8 {
+            // Bottom border (thick - highlighted)
+            let bottom_color = CelebrationManager::throb_color(theme.box_border, row_intensity);
+            execute!(
+                stdout,
+                SetForegroundColor(bottom_color),
+                Print("+===+===+===+===+===+===+===+===+===+")
+            )?;
+        } else if (row + 1) % 3 == 0 {
+            // Box separator (thick - highlighted)
+            let box_sep_color = CelebrationManager::throb_color(theme.box_border, sep_intensity);
+            execute!(
+                stdout,
+                SetForegroundColor(box_sep_color),
+                Print("+===+===+===+===+===+===+===+===+===+")
+            )?;
+        } else {
+            // Regular separator (thinner color)
+            let sep_color = CelebrationManager::throb_color(theme.border, sep_intensity);
+            execute!(
+                stdout,
+                SetForegroundColor(sep_color),
+                Print("+---+---+---+---+---+---+---+---+---+")
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn render_cell(stdout: &mut Buffer, app: &App, pos: Position, role: HintRole) -> io::Result<()> {
+    let theme = &app.theme;
+    let game = &app.game;
+    let cell = game.grid().cell(pos);
+    let is_cursor = pos == app.cursor;
+    let is_highlighted = app.is_highlighted(pos);
+    let has_same_value = game.settings().highlight_same_digit && app.has_same_value(pos);
+    let conflict = game.conflict(pos);
+    let has_conflict = conflict.is_some();
+
+    // Calculate celebration intensity for this cell
+    let row_intensity = app.celebrations.row_intensity(pos.row);
+    let col_intensity = app.celebrations.column_intensity(pos.col);
+    let box_idx = (pos.row / 3) * 3 + (pos.col / 3);
+    let box_intensity = app.celebrations.box_intensity(box_idx);
+    // Take the maximum intensity from any active celebration affecting this cell
+    let celebration_intensity = row_intensity.max(col_intensity).max(box_intensity);
+
+    // Background color
+    let mut bg = if let Some(color) = role_color(role) {
+        color
+    } else if is_cursor {
+        theme.selected_bg
+    } else if conflict == Some(ConflictKind::RuleConflict) {
+        // Rule breaks stand out from digits that are merely wrong
+        Color::Rgb {
+            r: 90,
+            g: 30,
+            b: 30,
+        }
+    } else if has_same_value && !cell.is_empty() {
+        Color::Rgb {
+            r: 60,
+            g: 60,
+            b: 100,
+        }
+    } else if is_highlighted {
+        theme.highlight_bg
+    } else {
+        theme.bg
+    };
+
+    // Apply celebration throbbing to background
+    if celebration_intensity > 0.0 {
+        bg = CelebrationManager::throb_color(bg, celebration_intensity);
+    }
+
+    // Foreground color
+    let mut fg = if has_conflict {
+        theme.error
+    } else if cell.is_given() {
+        theme.given
+    } else if cell.is_filled() {
+        theme.filled
+    } else {
+        theme.candidate
+    };
+
+    // Apply celebration throbbing to foreground
+    if celebration_intensity > 0.0 && !has_conflict {
+        fg = CelebrationManager::throb_color(fg, celebration_intensity * 0.5);
+    }
+
+    execute!(stdout, SetBackgroundColor(bg), SetForegroundColor(fg))?;
+
+    // Cell content: 3 chars " X "
+    if let Some(value) = cell.value() {
+        execute!(stdout, Print(format!(" {} ", value)))?;
+        return Ok(());
+    }
+
+    // Notes: corner marks hug the left edge, center notes sit in the middle
+    let corner = game.corner_marks(pos);
+    let candidates = cell.candidates();
+    let center = match candidates.count() {
+        0 => None,
+        1 => Some(char::from(b'0' + candidates.single_value().unwrap())),
+        _ => Some('*'),
+    };
+
+    match (corner.first(), center) {
+        (Some(&first), Some(center)) => {
+            let more = if corner.len() > 1 { '+' } else { ' ' };
+            execute!(
+                stdout,
+                SetForegroundColor(theme.key),
+                Print(first),
+                SetForegroundColor(fg),
+                Print(center),
+                SetForegroundColor(theme.key),
+                Print(more)
+            )?;
+        }
+        (Some(_), None) => {
+            // Up to three marks; more than that ends in '+'
+            let mut text: String = corner.iter().take(3).map(|v| v.to_string()).collect();
+            if corner.len() > 3 {
+                text.truncate(2);
+                text.push('+');
+            }
+            execute!(
+                stdout,
+                SetForegroundColor(theme.key),
+                Print(format!("{:<3}", text))
+            )?;
+        }
+        (None, Some(center)) => {
+            // The player's own notes, always shown; auto notes are dimmed
+            // unless the player added one by hand
+            if game.auto_notes() && game.manual_notes(pos).is_empty() {
+                execute!(stdout, SetForegroundColor(theme.border))?;
+            }
+            execute!(stdout, Print(format!(" {} ", center)))?;
+        }
+        (None, None) => {
+            // No notes - check if we should show the valid candidate as a hint
+            let valid = app.game.grid().get_candidates(pos);
+            if app.show_naked_singles && valid.count() == 1 {
+                let val = valid.single_value().unwrap();
+                execute!(
+                    stdout,
+                    SetForegroundColor(Color::DarkGrey),
+                    Print(format!(" {} ", val))
+                )?;
+            } else {
+                execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(" · "))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn render_info_panel(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let game = &app.game;
+
+    execute!(stdout, SetBackgroundColor(theme.bg))?;
+
+    // Title
+    execute!(
+        stdout,
+        area.at(0, 0),
+        SetForegroundColor(theme.key),
+        Print("═══ SUDOKU ═══")
+    )?;
+
+    // Time (red for the last minute of a countdown)
+    let time_color = match game.remaining() {
+        Some(left) if left.as_secs() < 60 => theme.error,
+        _ => theme.info,
+    };
+    if game.timer_shown() {
+        execute!(
+            stdout,
+            area.at(0, 2),
+            SetForegroundColor(time_color),
+            Print(format!("Time: {:>10}", game.clock_string()))
+        )?;
+    }
+
+    // Difficulty
+    execute!(
+        stdout,
+        area.at(0, 4),
+        SetForegroundColor(theme.info),
+        Print(format!(
+            "{} (SE {:.1})",
+            game.difficulty(),
+            game.se_rating()
+        ))
+    )?;
+
+    // Mode
+    let mode_str = match app.mode {
+        InputMode::Normal => "Normal",
+        InputMode::Candidate => "Notes ",
+        InputMode::Corner => "Corner",
+        InputMode::Menu => "Menu  ",
+    };
+    let mode_color = if matches!(app.mode, InputMode::Candidate | InputMode::Corner) {
+        Color::Cyan
+    } else {
+        theme.fg
+    };
+    execute!(
+        stdout,
+        area.at(0, 6),
+        SetForegroundColor(theme.info),
+        Print("Mode: "),
+        SetForegroundColor(mode_color),
+        Print(format!("{:>10}", mode_str))
+    )?;
+
+    if let Some(hotseat) = &app.hotseat {
+        // One line per player in place of the mistakes and hints
+        for (i, player) in hotseat.players.iter().enumerate() {
+            let turn = i == hotseat.current;
+            let color = if player.mistakes >= MAX_MISTAKES - 1 {
+                theme.error
+            } else if turn {
+                theme.key
+            } else {
+                theme.info
+            };
+            execute!(
+                stdout,
+                area.at(0, 8 + 2 * i as u16),
+                SetForegroundColor(color),
+                Print(format!(
+                    "{}P{} {:>2} ✗{} {:>5}",
+                    if turn { "▶" } else { " " },
+                    i + 1,
+                    player.placed,
+                    player.mistakes,
+                    format_time(hotseat.clock(i).as_secs())
+                ))
+            )?;
+        }
+    } else {
+        // Mistakes
+        let mistakes_color = if game.settings().mistake_limit().is_none() {
+            theme.info
+        } else if game.on_last_chance() {
+            theme.error
+        } else if game.mistakes() > 0 {
+            Color::Yellow
+        } else {
+            theme.info
+        };
+        let mistakes = game.mistakes_label();
+        execute!(
+            stdout,
+            area.at(0, 8),
+            SetForegroundColor(mistakes_color),
+            Print(format!("Mistakes: {:>6}", mistakes))
+        )?;
+
+        // Hints
+        execute!(
+            stdout,
+            area.at(0, 10),
+            SetForegroundColor(theme.info),
+            Print(format!("Hints used: {:>4}", game.hints_used()))
+        )?;
+    }
+
+    // Separator
+    execute!(
+        stdout,
+        area.at(0, 12),
+        SetForegroundColor(theme.border),
+        Print("────────────────")
+    )?;
+
+    // Number completion indicator
+    let completed = game.completed_numbers();
+    execute!(
+        stdout,
+        area.at(0, NUMBERS_ROW),
+        SetForegroundColor(theme.info),
+        Print("Numbers: ")
+    )?;
+    for (i, &is_completed) in completed.iter().enumerate() {
+        let num = (i + 1) as u8;
+        // The digit chosen with the scroll wheel
+        let bg = if num == app.selected_digit {
+            theme.selected_bg
+        } else {
+            theme.bg
+        };
+        execute!(stdout, SetBackgroundColor(bg))?;
+        if is_completed {
+            execute!(
+                stdout,
+                SetForegroundColor(theme.success),
+                Print(format!("{}", num))
+            )?;
+        } else {
+            execute!(
+                stdout,
+                SetForegroundColor(theme.border),
+                Print(format!("{}", num))
+            )?;
+        }
+    }
+    execute!(stdout, SetBackgroundColor(theme.bg))?;
+
+    // Current cell
+    let pos = app.cursor;
+    let cell = game.grid().cell(pos);
+    execute!(
+        stdout,
+        area.at(0, 16),
+        SetForegroundColor(theme.info),
+        Print(format!("Cell: Row {} Col {}", pos.row + 1, pos.col + 1))
+    )?;
+
+    if cell.is_empty() {
+        if app.show_suggestions {
+            let valid = game.grid().get_candidates(pos);
+            let valid_str: String = valid
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            execute!(
+                stdout,
+                area.at(0, 17),
+                SetForegroundColor(Color::Green),
+                Print(format!("Valid: {:>9}", valid_str))
+            )?;
+        } else {
+            execute!(
+                stdout,
+                area.at(0, 17),
+                SetForegroundColor(theme.border),
+                Print("Valid:   (v=show)")
+            )?;
+        }
+
+        // Center notes, or the corner marks when there are none. With auto
+        // notes on, the ones entered by hand stand out.
+        let notes = cell.candidates();
+        let corner = game.corner_marks(pos);
+        let (label, color, digits, manual) = if notes.count() == 0 && !corner.is_empty() {
+            ("Corner:", theme.key, corner, Vec::new())
+        } else if game.auto_notes() {
+            let manual = game.manual_notes(pos);
+            ("Auto: ", theme.candidate, notes.iter().collect(), manual)
+        } else {
+            (
+                "Notes:",
+                theme.candidate,
+                notes.iter().collect(),
+                Vec::new(),
+            )
+        };
+        let width = (digits.len() * 2).saturating_sub(1);
+        execute!(
+            stdout,
+            area.at(0, 18),
+            SetForegroundColor(color),
+            Print(format!(
+                "{} {:>pad$}",
+                label,
+                "",
+                pad = 9usize.saturating_sub(width)
+            ))
+        )?;
+        for (i, digit) in digits.iter().enumerate() {
+            let digit_color = if manual.contains(digit) {
+                theme.key
+            } else {
+                color
+            };
+            let sep = if i + 1 < digits.len() { " " } else { "" };
+            execute!(
+                stdout,
+                SetForegroundColor(digit_color),
+                Print(format!("{}{}", digit, sep))
+            )?;
+        }
+    } else {
+        execute!(
+            stdout,
+            area.at(0, 17),
+            SetForegroundColor(theme.bg),
+            Print("                 ")
+        )?;
+        execute!(stdout, area.at(0, 18), Print("                 "))?;
+    }
+
+    Ok(())
+}
+
+/// Speedrun splits in place of the controls: boxes laid out like the grid,
+/// digits 1-9 beside them
+fn render_splits(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let splits = app.game.splits();
+    let latest = splits.latest().map(|(_, ms)| ms);
+
+    execute!(
+        stdout,
+        SetBackgroundColor(theme.bg),
+        area.at(0, 0),
+        SetForegroundColor(theme.key),
+        Print(format!("{:<28}{}", "Box splits", "Digit splits"))
+    )?;
+    for row in 0..3 {
+        execute!(stdout, area.at(0, 1 + row as u16))?;
+        for (table, times) in [&splits.boxes, &splits.digits].into_iter().enumerate() {
+            if table == 1 {
+                execute!(stdout, Print("    "))?;
+            }
+            for col in 0..3 {
+                let time = times[row * 3 + col];
+                let (text, color) = match time {
+                    Some(ms) if Some(ms) == latest => (format_split(ms), theme.success),
+                    Some(ms) => (format_split(ms), theme.fg),
+                    None => ("-:--.-".to_string(), theme.border),
+                };
+                execute!(
+                    stdout,
+                    SetForegroundColor(color),
+                    Print(format!("{:>8}", text))
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Key help under the grid
+fn render_controls(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+
+    execute!(stdout, SetBackgroundColor(theme.bg))?;
+
+    // Remappable keys show whatever the keymap binds first
+    let keys = &app.keymap;
+    let move_keys = [Action::Left, Action::Down, Action::Up, Action::Right]
+        .map(|a| keys.label(a))
+        .concat();
+    let controls = [
+        (move_keys, "Move"),
+        (
+            format!(
+                "{}-{}",
+                keys.label(Action::Digit(1)),
+                keys.label(Action::Digit(9))
+            ),
+            "Set/Note",
+        ),
+        (keys.label(Action::Clear), "Clear"),
+        (keys.label(Action::ToggleNotes), "Notes mode"),
+        (keys.label(Action::ToggleCorner), "Corner mode"),
+        ("f/F".to_string(), "Fill notes"),
+        ("x/X".to_string(), "Clear notes"),
+        ("v".to_string(), "Valid hints"),
+        ("g".to_string(), "Ghost hints"),
+        ("n".to_string(), "New game"),
+        (
+            format!(
+                "{}/{}",
+                keys.label(Action::Hint),
+                keys.label(Action::ApplyHint)
+            ),
+            "Hint",
+        ),
+        (keys.label(Action::Undo), "Undo"),
+        (keys.label(Action::History), "Moves"),
+        ("i".to_string(), "Stats"),
+        ("b".to_string(), "Leaderboard"),
+        ("H".to_string(), "History"),
+        ("t".to_string(), "Theme"),
+        ("q".to_string(), "Quit"),
+        ("T".to_string(), "Trainer"),
+        ("A".to_string(), "Techniques"),
+        ("P".to_string(), "Packs"),
+        ("N".to_string(), "Auto notes"),
+        (keys.label(Action::Palette), "Commands"),
+        (keys.label(Action::Help), "All keys"),
+        ("y".to_string(), "Copy"),
+        ("E".to_string(), "Editor"),
+    ];
+
+    // Display in columns of 4 items each
+    for (i, (key, desc)) in controls.iter().enumerate() {
+        let col = (i / 4) as u16;
+        let row = (i % 4) as u16;
+
+        execute!(
+            stdout,
+            area.at(col * 17, row),
+            SetForegroundColor(theme.key),
+            Print(format!("{:>8}", key)),
+            SetForegroundColor(theme.info),
+            Print(format!(" {}", desc))
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The hint's explanation in a box at the top left of `anchor`, moved up or
+/// left as needed to stay inside `area`
+fn render_hint(
+    stdout: &mut Buffer,
+    app: &App,
+    hint: &Hint,
+    area: Rect,
+    anchor: Rect,
+) -> io::Result<()> {
+    let theme = &app.theme;
+
+    let max_width = 45.min(area.width.saturating_sub(4) as usize).max(10);
+    let wrapped: Vec<String> = sudoku_glossary::explain(hint, app.explanations)
+        .lines()
+        .flat_map(|line| wrap_text(line, max_width))
+        .collect();
+    // What the technique is, from the glossary, below the explanation; the
+    // step-by-step text already starts with it
+    let about = sudoku_glossary::lookup(&hint.technique.to_string())
+        .filter(|_| app.explanations != Verbosity::StepByStep)
+        .map(|entry| wrap_text(entry.summary, max_width))
+        .unwrap_or_default();
+    let text_lines = wrapped.len() + if about.is_empty() { 0 } else { about.len() + 1 };
+
+    // Roles shown on the grid, in legend order
+    let roles = hint_roles(hint);
+    let mut legend: Vec<HintRole> = Vec::new();
+    for role in roles {
+        if role != HintRole::None && !legend.contains(&role) {
+            legend.push(role);
+        }
+    }
+    legend.sort_by_key(|&r| r as u8);
+
+    let box_width = (max_width + 4) as u16;
+    let box_height = (text_lines + 6) as u16;
+    let frame = Rect::new(
+        anchor.x.min(area.width.saturating_sub(box_width)),
+        anchor.y.min(area.height.saturating_sub(box_height)),
+        box_width,
+        box_height,
+    );
+
+    let bg = Color::Rgb {
+        r: 25,
+        g: 45,
+        b: 25,
+    };
+    popup(stdout, frame, bg, None)?;
+
+    // Title
+    execute!(
+        stdout,
+        frame.at(2, 1),
+        SetForegroundColor(theme.success),
+        SetBackgroundColor(bg),
+        Print(format!("💡 Hint: {}", hint.technique))
+    )?;
+
+    // Text
+    for (i, line) in wrapped.iter().enumerate() {
+        execute!(
+            stdout,
+            frame.at(2, 3 + i as u16),
+            SetForegroundColor(theme.fg),
+            SetBackgroundColor(bg),
+            Print(line)
+        )?;
+    }
+    for (i, line) in about.iter().enumerate() {
+        execute!(
+            stdout,
+            frame.at(2, 4 + (wrapped.len() + i) as u16),
+            SetForegroundColor(theme.info),
+            SetBackgroundColor(bg),
+            Print(line)
+        )?;
+    }
+
+    // Legend for the cell colors
+    execute!(stdout, frame.at(2, 4 + text_lines as u16))?;
+    for role in legend {
+        if let Some(color) = role_color(role) {
+            execute!(
+                stdout,
+                SetForegroundColor(color),
+                Print("██"),
+                SetForegroundColor(theme.info),
+                Print(format!(" {}  ", role.label()))
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_modes() {
+        assert_eq!(game_layout(80, 24, false).mode, LayoutMode::Full);
+        assert_eq!(game_layout(40, 24, false).mode, LayoutMode::NoPanel);
+        assert_eq!(game_layout(40, 15, false).mode, LayoutMode::Compact);
+        assert_eq!(game_layout(20, 10, false).mode, LayoutMode::TooSmall);
+        assert_eq!(game_layout(80, 24, true).techniques_x, None);
+        assert!(game_layout(100, 24, true).techniques_x.is_some());
+
+        // Compact grid: 3-char cells, separators between boxes only
+        let layout = game_layout(29, 13, false);
+        assert_eq!(layout.cell_at(0, 1), Some(Position::new(0, 0)));
+        assert_eq!(layout.cell_at(10, 5), Some(Position::new(3, 3)));
+        assert_eq!(layout.cell_at(9, 1), None);
+        assert_eq!(layout.cell_at(0, 4), None);
+        assert_eq!(layout.cell_at(28, 11), Some(Position::new(8, 8)));
+        assert_eq!(
+            layout.digit_at(layout.info_x + NUMBERS_COL, 1 + NUMBERS_ROW),
+            None
+        );
+        assert_eq!(
+            layout.grid(),
+            Rect::new(0, 1, COMPACT_WIDTH, COMPACT_HEIGHT)
+        );
+        assert_eq!(layout.below_grid(), 12);
+    }
+}
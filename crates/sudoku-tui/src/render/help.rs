@@ -0,0 +1,101 @@
+//! The key reference.
+
+use super::{footer, screen_sections, subtitle, title, MARGIN};
+use crate::app::App;
+use crate::keymap::Action;
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Print, SetForegroundColor},
+};
+use std::io;
+
+/// Keys that work on the board and cannot be remapped
+const FIXED_KEYS: [(&str, &str); 22] = [
+    ("Shift+digit", "Note"),
+    ("f / F", "Fill notes / all"),
+    ("x / X", "Clear notes / all"),
+    ("N", "Auto notes"),
+    ("v", "Valid hints"),
+    ("g", "Ghost hints"),
+    ("A", "Techniques panel"),
+    ("n", "New game"),
+    ("p", "Pause"),
+    ("e", "Export"),
+    ("y", "Copy"),
+    ("S / L", "Save / load slot"),
+    ("t", "Theme"),
+    ("m", "Timer (new game menu)"),
+    ("i", "Stats"),
+    ("b", "Leaderboard"),
+    ("H", "Game history"),
+    ("T", "Trainer"),
+    ("P", "Puzzle packs"),
+    ("E", "Puzzle editor"),
+    ("Esc", "Close menu"),
+    ("q", "Quit"),
+];
+
+/// Every key: the remappable actions with their current keys, then the
+/// fixed ones
+pub fn render_help_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let keys = &app.keymap;
+
+    let (header, body, nav) = screen_sections(area);
+    title(stdout, app, header, "═══ KEYS ═══")?;
+    let body = subtitle(
+        stdout,
+        app,
+        body,
+        "Game keys can be changed under [keys] in the config file",
+    )?;
+
+    let join = |action| keys.labels(action).join(" ");
+    let digits = format!(
+        "{}…{}",
+        keys.label(Action::Digit(1)),
+        keys.label(Action::Digit(9))
+    );
+    let remappable: Vec<(String, &str)> = vec![
+        (join(Action::Up), "Up"),
+        (join(Action::Down), "Down"),
+        (join(Action::Left), "Left"),
+        (join(Action::Right), "Right"),
+        (join(Action::BoxUp), "Box up"),
+        (join(Action::BoxDown), "Box down"),
+        (join(Action::BoxLeft), "Box left"),
+        (join(Action::BoxRight), "Box right"),
+        (digits, "Digit"),
+        (join(Action::Clear), "Clear"),
+        (join(Action::ToggleNotes), "Notes mode"),
+        (join(Action::ToggleCorner), "Corner mode"),
+        (join(Action::Hint), "Hint"),
+        (join(Action::ApplyHint), "Apply hint"),
+        (join(Action::Undo), "Undo"),
+        (join(Action::Redo), "Redo"),
+        (join(Action::History), "Move history"),
+        (join(Action::Help), "Keys"),
+        (join(Action::Palette), "Commands"),
+    ];
+
+    let columns = Layout::horizontal([Constraint::Length(40), Constraint::Min(1)]).split(body);
+    let fixed = FIXED_KEYS
+        .iter()
+        .map(|&(key, what)| (key.to_string(), what));
+    for (column, rows) in [(columns[0], remappable), (columns[1], fixed.collect())] {
+        for (i, (key, what)) in rows.iter().take(column.height as usize).enumerate() {
+            let key = if key.is_empty() { "-" } else { key.as_str() };
+            execute!(
+                stdout,
+                column.at(MARGIN, i as u16),
+                SetForegroundColor(theme.key),
+                Print(format!("{:<14.14}", key)),
+                SetForegroundColor(theme.fg),
+                Print(what)
+            )?;
+        }
+    }
+
+    footer(stdout, app, nav, &[("", "Any key to go back")])
+}
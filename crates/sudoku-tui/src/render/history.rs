@@ -0,0 +1,148 @@
+//! Game history.
+
+use super::{footer, screen_sections, subtitle, title, MARGIN};
+use crate::app::App;
+use crate::stats::{format_time, GameResult};
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+
+/// Recent games, newest first, for replaying
+pub fn render_history_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let stats = &app.stats;
+
+    let (header, body, nav) = screen_sections(area);
+    title(stdout, app, header, "═══ GAME HISTORY ═══")?;
+    let list = subtitle(stdout, app, body, "Select a game and press Enter to replay")?;
+
+    // Column headings
+    let parts = Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).split(list);
+    let (headings, rows) = (parts[0], parts[1]);
+    execute!(
+        stdout,
+        headings.at(MARGIN, 0),
+        SetForegroundColor(theme.fg),
+        Print(format!(
+            "{:>4} {:>8} {:>8} {:>8} {:>6} {:>8} {:>10}",
+            "#", "Result", "Diff", "Time", "Hints", "Errors", "Verified"
+        ))
+    )?;
+    execute!(
+        stdout,
+        headings.at(MARGIN, 1),
+        SetForegroundColor(theme.border),
+        Print("─".repeat(65))
+    )?;
+
+    // History entries, with a blank line before the footer
+    let visible_rows = rows.height.saturating_sub(1) as usize;
+    let history = stats.recent_games(100);
+
+    for (i, record) in history
+        .iter()
+        .skip(app.history_scroll)
+        .take(visible_rows)
+        .enumerate()
+    {
+        let y = i as u16;
+        let is_selected = i + app.history_scroll == app.history_scroll;
+        let actual_idx = i + app.history_scroll;
+
+        let bg = if is_selected && actual_idx == app.history_scroll {
+            theme.selected_bg
+        } else {
+            theme.bg
+        };
+
+        execute!(stdout, SetBackgroundColor(bg))?;
+
+        let result_str = match record.result {
+            GameResult::Win => "WIN",
+            GameResult::Loss => "LOSS",
+            GameResult::Abandoned => "QUIT",
+        };
+        let result_color = match record.result {
+            GameResult::Win => theme.success,
+            GameResult::Loss => theme.error,
+            GameResult::Abandoned => theme.border,
+        };
+
+        let diff_str = format!("{:?}", record.difficulty);
+        let verified_str = if record.verified { "✓" } else { "✗" };
+        let verified_color = if record.verified {
+            theme.success
+        } else {
+            theme.error
+        };
+
+        // Highlight selected row
+        let prefix = if actual_idx == app.history_scroll {
+            "▶"
+        } else {
+            " "
+        };
+
+        execute!(
+            stdout,
+            rows.at(2, y),
+            SetForegroundColor(theme.key),
+            Print(prefix),
+            rows.at(MARGIN, y),
+            SetForegroundColor(theme.info),
+            Print(format!("{:>4}", record.id)),
+            SetForegroundColor(result_color),
+            Print(format!(" {:>8}", result_str)),
+            SetForegroundColor(theme.info),
+            Print(format!(" {:>8}", diff_str)),
+            Print(format!(" {:>8}", format_time(record.time_secs))),
+            Print(format!(" {:>6}", record.hints_used)),
+            Print(format!(" {:>8}", record.mistakes)),
+            SetForegroundColor(verified_color),
+            Print(format!(" {:>10}", verified_str))
+        )?;
+
+        execute!(stdout, SetBackgroundColor(theme.bg))?;
+    }
+
+    if history.is_empty() {
+        execute!(
+            stdout,
+            rows.at(MARGIN, 1),
+            SetForegroundColor(theme.border),
+            Print("No games played yet!")
+        )?;
+    }
+
+    // Scroll indicator
+    if history.len() > visible_rows {
+        let scroll_height = visible_rows as u16;
+        let scroll_pos =
+            (app.history_scroll as f32 / history.len() as f32 * scroll_height as f32) as u16;
+
+        for i in 0..scroll_height {
+            let ch = if i == scroll_pos { '█' } else { '░' };
+            execute!(
+                stdout,
+                rows.at(rows.width.saturating_sub(3), i),
+                SetForegroundColor(theme.border),
+                Print(ch)
+            )?;
+        }
+    }
+
+    footer(
+        stdout,
+        app,
+        nav,
+        &[
+            ("↑/↓", "Select"),
+            ("Enter", "Replay"),
+            ("i", "Stats"),
+            ("Esc", "Back"),
+        ],
+    )
+}
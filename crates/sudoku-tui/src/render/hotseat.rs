@@ -0,0 +1,90 @@
+//! Results of a hot-seat game.
+
+use super::{footer, screen_sections, title};
+use crate::app::{App, MAX_MISTAKES};
+use crate::stats::format_time;
+use crate::ui::{Buffer, Rect};
+use crossterm::{
+    execute,
+    style::{Print, SetForegroundColor},
+};
+use std::io;
+
+/// Hot-seat results: each player's tally and the winner
+pub fn render_hotseat_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let Some(hotseat) = &app.hotseat else {
+        return Ok(());
+    };
+
+    let (header, body, nav) = screen_sections(area);
+    title(stdout, app, header, "═══ HOT-SEAT RESULTS ═══")?;
+
+    let table = body.centered(44, body.height);
+    let ending = if app.game.is_completed() {
+        format!("{} puzzle solved", app.game.difficulty())
+    } else {
+        format!(
+            "{} puzzle - a player reached {} mistakes",
+            app.game.difficulty(),
+            MAX_MISTAKES
+        )
+    };
+    execute!(
+        stdout,
+        table.at(0, 0),
+        SetForegroundColor(theme.info),
+        Print(ending),
+        table.at(0, 2),
+        SetForegroundColor(theme.border),
+        Print(format!(
+            "{:<10}{:>8}{:>10}{:>7}{:>9}",
+            "", "Placed", "Mistakes", "Hints", "Time"
+        ))
+    )?;
+
+    let winner = hotseat.winner(MAX_MISTAKES);
+    for (i, player) in hotseat.players.iter().enumerate() {
+        let color = if winner == Some(i) {
+            theme.success
+        } else {
+            theme.fg
+        };
+        execute!(
+            stdout,
+            table.at(0, 3 + i as u16),
+            SetForegroundColor(color),
+            Print(format!(
+                "{:<10}{:>8}{:>10}{:>7}{:>9}",
+                format!("Player {}", i + 1),
+                player.placed,
+                player.mistakes,
+                player.hints,
+                format_time(hotseat.clock(i).as_secs())
+            ))
+        )?;
+    }
+
+    let verdict = match winner {
+        Some(i) => format!("🏆 Player {} wins!", i + 1),
+        None => "It's a tie!".to_string(),
+    };
+    execute!(
+        stdout,
+        table.at(0, 6),
+        SetForegroundColor(theme.key),
+        Print(verdict)
+    )?;
+
+    footer(
+        stdout,
+        app,
+        nav,
+        &[
+            ("Enter", "Rematch"),
+            ("n", "New game"),
+            ("Esc", "Board"),
+            ("q", "Quit"),
+        ],
+    )
+}
@@ -0,0 +1,223 @@
+//! Local and global leaderboards.
+
+use super::{footer, screen_sections, title, MARGIN};
+use crate::app::App;
+use crate::stats::format_time;
+use crate::timer::TimerMode;
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Color, Print, SetForegroundColor},
+};
+use std::io;
+use sudoku_session::GameMode;
+
+/// Best scores at a difficulty, local or global
+pub fn render_leaderboard_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let stats = &app.stats;
+
+    let (header, body, nav) = screen_sections(area);
+    let heading = if app.leaderboard_global {
+        "═══ GLOBAL LEADERBOARD ═══"
+    } else {
+        "═══ LEADERBOARD ═══"
+    };
+    title(stdout, app, header, heading)?;
+
+    // Difficulty filter, column headings, then the entries
+    let parts = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(2),
+        Constraint::Min(1),
+    ])
+    .split(body);
+    let (filter, headings, list) = (parts[0], parts[1], parts[2]);
+    let difficulties: Vec<&str> = if app.stats.secrets_unlocked() {
+        vec![
+            "Beginner", "Easy", "Medium", "Inter", "Hard", "Expert", "Master", "Extreme",
+        ]
+    } else {
+        vec!["Beginner", "Easy", "Medium", "Inter", "Hard", "Expert"]
+    };
+    let current_idx = match app.leaderboard_difficulty {
+        sudoku_core::Difficulty::Beginner => 0,
+        sudoku_core::Difficulty::Easy => 1,
+        sudoku_core::Difficulty::Medium => 2,
+        sudoku_core::Difficulty::Intermediate => 3,
+        sudoku_core::Difficulty::Hard => 4,
+        sudoku_core::Difficulty::Expert => 5,
+        sudoku_core::Difficulty::Master => 6,
+        sudoku_core::Difficulty::Extreme => 7,
+    };
+
+    execute!(
+        stdout,
+        filter.at(MARGIN, 0),
+        SetForegroundColor(theme.info),
+        Print("◀ ")
+    )?;
+
+    for (i, name) in difficulties.iter().enumerate() {
+        let color = if i == current_idx {
+            theme.key
+        } else {
+            theme.border
+        };
+        execute!(
+            stdout,
+            SetForegroundColor(color),
+            Print(format!(" {} ", name))
+        )?;
+    }
+    execute!(stdout, SetForegroundColor(theme.info), Print(" ▶"))?;
+
+    execute!(
+        stdout,
+        headings.at(MARGIN, 0),
+        SetForegroundColor(theme.fg),
+        Print(format!(
+            "{:>4} {:>12} {:>8} {:>6} {:>8} {:>10} {:>6}",
+            "Rank", "Player", "Score", "Time", "Hints", "Verified", "Mode"
+        ))
+    )?;
+    execute!(
+        stdout,
+        headings.at(MARGIN, 1),
+        SetForegroundColor(theme.border),
+        Print("─".repeat(60))
+    )?;
+
+    // Leaderboard entries: (rank, player, score, time, hints, verified,
+    // mode label), in the selected mode's category only
+    type Row<'a> = (usize, &'a str, u64, u64, usize, bool, &'static str);
+    let category = app.leaderboard_mode.leaderboard_category();
+    let in_category = |mode: GameMode| mode.leaderboard_category() == category;
+    let mode_label = |mode: GameMode, timer: TimerMode| match mode.short_label() {
+        "" => timer.short_label(),
+        label => label,
+    };
+    let rows: Vec<Row> = if app.leaderboard_global {
+        match &app.global_rankings {
+            Some(Ok(entries)) => entries
+                .iter()
+                .filter(|e| in_category(e.mode))
+                .enumerate()
+                .map(|(i, e)| {
+                    let rank = e.rank.unwrap_or(i + 1);
+                    (
+                        rank,
+                        e.player_name.as_str(),
+                        e.score,
+                        e.time_secs,
+                        e.hints_used,
+                        e.verified,
+                        mode_label(e.mode, e.timer),
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    } else {
+        stats
+            .leaderboard_by_difficulty(app.leaderboard_difficulty)
+            .into_iter()
+            .filter(|e| in_category(e.mode))
+            .enumerate()
+            .map(|(i, e)| {
+                (
+                    i + 1,
+                    e.player_name.as_str(),
+                    e.score,
+                    e.time_secs,
+                    e.hints_used,
+                    true,
+                    mode_label(e.mode, e.timer),
+                )
+            })
+            .collect()
+    };
+    let max_entries = list.height as usize;
+
+    for (i, &(rank, player_name, score, time_secs, hints_used, verified, mode)) in
+        rows.iter().take(max_entries).enumerate()
+    {
+        let y = i as u16;
+        let rank_color = match rank {
+            1 => Color::Yellow, // Gold
+            2 => Color::Grey,   // Silver
+            3 => Color::Rgb {
+                r: 205,
+                g: 127,
+                b: 50,
+            }, // Bronze
+            _ => theme.info,
+        };
+        let name: String = player_name.chars().take(12).collect();
+
+        execute!(
+            stdout,
+            list.at(MARGIN, y),
+            SetForegroundColor(rank_color),
+            Print(format!("{:>4}", rank)),
+            SetForegroundColor(theme.fg),
+            Print(format!(" {:>12}", name)),
+            SetForegroundColor(theme.key),
+            Print(format!(" {:>8}", score)),
+            SetForegroundColor(theme.info),
+            Print(format!(" {:>8}", format_time(time_secs))),
+            Print(format!(" {:>6}", hints_used)),
+            SetForegroundColor(theme.success),
+            Print(format!(" {:>10}", if verified { "✓" } else { "" })),
+            SetForegroundColor(theme.info),
+            Print(format!(" {:>6}", mode))
+        )?;
+    }
+
+    if rows.is_empty() {
+        let empty = if !app.leaderboard_global {
+            "No entries yet. Win some games!".to_string()
+        } else {
+            match &app.global_rankings {
+                None => "Loading global rankings...".to_string(),
+                Some(Err(e)) => format!("Couldn't load global rankings: {}", e),
+                Some(Ok(_)) => "No global entries for this difficulty yet.".to_string(),
+            }
+        };
+        execute!(
+            stdout,
+            list.at(MARGIN, 1),
+            SetForegroundColor(theme.border),
+            Print(empty)
+        )?;
+    } else if app.leaderboard_global && stats.leaderboard_status().using_fallback {
+        execute!(
+            stdout,
+            list.at(MARGIN, rows.len().min(max_entries) as u16 + 1),
+            SetForegroundColor(theme.border),
+            Print("Server unreachable - showing scores kept locally")
+        )?;
+    }
+
+    let mode = match app.leaderboard_mode.short_label() {
+        "" => "Classic",
+        label => label,
+    };
+    let mut keys = vec![
+        ("←/→", "Change difficulty"),
+        ("i", "Stats"),
+        ("m", mode),
+        ("Esc", "Back"),
+    ];
+    if stats.online_enabled() {
+        keys.push((
+            "g",
+            if app.leaderboard_global {
+                "Local"
+            } else {
+                "Global"
+            },
+        ));
+    }
+    footer(stdout, app, nav, &keys)
+}
@@ -0,0 +1,203 @@
+//! Screen-reader line mode in place of the game, its menus and the end
+//! screens.
+
+use super::menu::{level_record, menu_title};
+use super::wrap_text;
+use crate::a11y;
+use crate::app::{App, InputMode, MenuState, ScreenState};
+use crate::clipboard::CopyTarget;
+use crate::export::ExportFormat;
+use crate::keymap::Action;
+use crate::palette;
+use crate::stats::format_time;
+use crate::trainer::TECHNIQUES;
+use crate::ui::{Buffer, Rect};
+use crossterm::{cursor::Show, execute, style::Print};
+use std::io;
+use sudoku_core::Difficulty;
+
+/// Plain-text lines for a menu in line mode, or None to draw it as usual
+fn a11y_menu_lines(app: &App) -> Option<Vec<String>> {
+    let options: Vec<String> = match app.menu {
+        MenuState::NewGame | MenuState::Difficulty => {
+            let levels = if app.stats.secrets_unlocked() {
+                Difficulty::all_levels()
+            } else {
+                Difficulty::standard_levels()
+            };
+            levels
+                .iter()
+                .map(|&d| {
+                    let (se_min, se_max) = d.se_range();
+                    let mut line = format!("{}, SE {:.1} to {:.1}", d, se_min, se_max);
+                    let (clues, best) = level_record(app, d);
+                    if let Some(clues) = clues {
+                        line += &format!(", about {} clues", clues);
+                    }
+                    if let Some(best) = best {
+                        line += &format!(", best {}", format_time(best));
+                    }
+                    line
+                })
+                .chain(["Daily puzzle".to_string()])
+                .collect()
+        }
+        MenuState::Theme => app.themes.iter().map(|(name, _)| name.clone()).collect(),
+        MenuState::Confirm => vec!["Yes".to_string(), "No".to_string()],
+        MenuState::Export => ExportFormat::ALL
+            .iter()
+            .map(|f| f.label().to_string())
+            .collect(),
+        MenuState::Copy => CopyTarget::ALL
+            .iter()
+            .map(|t| t.label().to_string())
+            .collect(),
+        MenuState::Resume => vec!["Resume last game".to_string(), "New game".to_string()],
+        MenuState::SaveSlots | MenuState::LoadSlots => app
+            .slot_infos
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| match slot {
+                Some(info) => format!("Slot {}: {}, {}", i + 1, info.name, info.difficulty),
+                None => format!("Slot {}: empty", i + 1),
+            })
+            .collect(),
+        MenuState::Trainer => TECHNIQUES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        MenuState::Bookmarks => app
+            .game
+            .bookmarks()
+            .iter()
+            .map(|b| b.name.clone())
+            .collect(),
+        MenuState::Palette => {
+            let mut lines = vec![format!(
+                "Command: {}. Type to search, up and down to choose, Enter to run, Esc to close.",
+                app.palette_query
+            )];
+            for (i, (_, name)) in palette::search(&app.palette_query).iter().enumerate() {
+                let marker = if i == app.menu_selection { ">" } else { " " };
+                lines.push(format!("{} {}", marker, name));
+            }
+            return Some(lines);
+        }
+        MenuState::Daily | MenuState::None => return None,
+    };
+
+    let mut lines = vec![format!(
+        "Menu: {}. Up and down to choose, Enter to select, Esc to close.",
+        menu_title(app.menu)
+    )];
+    for (i, option) in options.iter().enumerate() {
+        let marker = if i == app.menu_selection { ">" } else { " " };
+        lines.push(format!("{} {}", marker, option));
+    }
+    if matches!(app.menu, MenuState::NewGame | MenuState::Difficulty) {
+        lines.push(format!(
+            "Timer {} (m to change), {} (v to change), {} (g to change)",
+            app.timer_mode.label(),
+            if app.hotseat_next {
+                "two players"
+            } else {
+                "one player"
+            },
+            app.game_mode.label()
+        ));
+    }
+    Some(lines)
+}
+
+/// Line mode: the board as plain text rows, the cell under the cursor, and
+/// the latest announcement last with the terminal cursor on it so screen
+/// readers pick it up
+pub fn render_a11y_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let game = &app.game;
+    let mut lines = Vec::new();
+
+    match app.screen_state {
+        ScreenState::Win => lines.push(format!(
+            "Solved! {} puzzle in {}, {} hints. Enter for another game, n to choose difficulty, q to quit.",
+            game.difficulty(),
+            game.elapsed_string(),
+            game.hints_used()
+        )),
+        ScreenState::Lose => lines.push(format!(
+            "Game over: {}. r to retry this puzzle, z to keep playing in zen mode, Enter for another game, n to choose difficulty, q to quit.",
+            if game.is_time_up() {
+                "out of time".to_string()
+            } else {
+                format!("{} mistakes", game.mistakes())
+            }
+        )),
+        _ => lines.push(format!(
+            "Sudoku, {}{}, mistakes {}, {}% done{}",
+            game.difficulty(),
+            if game.timer_shown() {
+                format!(", time {}", game.clock_string())
+            } else {
+                String::new()
+            },
+            game.mistakes_label(),
+            game.completion_percent(),
+            if game.is_paused() { ", paused" } else { "" }
+        )),
+    }
+    lines.push(String::new());
+    lines.extend(a11y::board_text(game, app.a11y_board));
+    lines.push(String::new());
+
+    let menu = if app.screen_state == ScreenState::Playing {
+        a11y_menu_lines(app)
+    } else {
+        None
+    };
+    match menu {
+        Some(menu) => lines.extend(menu),
+        None if app.screen_state == ScreenState::Playing => {
+            let mode = match app.mode {
+                InputMode::Candidate => "notes",
+                InputMode::Corner => "corner marks",
+                InputMode::Normal | InputMode::Menu => "values",
+            };
+            lines.push(format!(
+                "Cursor {}. Digits enter {}.",
+                a11y::describe_cell(game, app.cursor),
+                mode
+            ));
+            let keys = &app.keymap;
+            lines.push(format!(
+                "Keys: arrows move, 1 to 9 enter, {} notes, {} undo, {} hint, n new game, q quit.",
+                keys.label(Action::ToggleNotes),
+                keys.label(Action::Undo),
+                keys.label(Action::Hint)
+            ));
+        }
+        None => {}
+    }
+    if let Some(hint) = &app.current_hint {
+        lines.push(format!("Hint: {}", hint.explanation));
+    }
+    lines.push(String::new());
+
+    let width = area.width.max(1) as usize;
+    let mut y = 0;
+    for line in &lines {
+        // Board rows keep their spacing; long sentences wrap at words
+        let chunks = if line.chars().count() <= width {
+            vec![line.clone()]
+        } else {
+            wrap_text(line, width)
+        };
+        for chunk in chunks {
+            execute!(stdout, area.at(0, y), Print(chunk))?;
+            y += 1;
+        }
+    }
+
+    // The announcement goes last and keeps the cursor
+    execute!(stdout, area.at(0, y), Print(&app.announcement))?;
+    execute!(stdout, Show)?;
+    Ok(())
+}
@@ -0,0 +1,456 @@
+//! Menu popups over the game screen: new game, daily puzzle, command
+//! palette and the simple option lists.
+
+use super::{centered, popup};
+use crate::app::{App, MenuState};
+use crate::clipboard::CopyTarget;
+use crate::export::ExportFormat;
+use crate::palette;
+use crate::slots::SLOT_COUNT;
+use crate::stats::format_time;
+use crate::trainer::TECHNIQUES;
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Color, Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+use sudoku_core::Difficulty;
+use sudoku_daily as daily;
+use sudoku_streak::Day;
+
+/// Heading of a menu popup
+pub fn menu_title(menu: MenuState) -> &'static str {
+    match menu {
+        MenuState::NewGame | MenuState::Difficulty => "Select Difficulty",
+        MenuState::Theme => "Select Theme",
+        MenuState::Confirm => "Confirm",
+        MenuState::Export => "Export",
+        MenuState::Copy => "Copy to Clipboard",
+        MenuState::Resume => "Unfinished Game",
+        MenuState::SaveSlots => "Save to Slot",
+        MenuState::LoadSlots => "Load Slot",
+        MenuState::Daily => "Daily Puzzle",
+        MenuState::Trainer => "Technique Trainer",
+        MenuState::Palette => "Command",
+        MenuState::Bookmarks => "Restore Bookmark",
+        MenuState::None => "",
+    }
+}
+
+/// Typical clue count and best time at a level, from this player's games
+pub fn level_record(app: &App, level: Difficulty) -> (Option<usize>, Option<u64>) {
+    let best = app.stats.player.get_difficulty_stats(level).best_time_secs;
+    (app.stats.typical_clues(level), best)
+}
+
+/// The open menu as a popup centered in `area`
+pub fn render_menu(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+
+    // Calculate menu size based on content
+    let is_difficulty_menu = matches!(app.menu, MenuState::NewGame | MenuState::Difficulty);
+    let num_options = if is_difficulty_menu {
+        app.daily_menu_index() + 4 // difficulties + Daily + timer + players + mode
+    } else {
+        match app.menu {
+            MenuState::Theme => app.themes.len(),
+            MenuState::Confirm => 2,
+            MenuState::Export => ExportFormat::ALL.len(),
+            MenuState::Copy => CopyTarget::ALL.len(),
+            MenuState::Resume => 3, // two options + saved game details
+            MenuState::SaveSlots | MenuState::LoadSlots => SLOT_COUNT + 1, // + slot name
+            MenuState::Daily => DAILY_MENU_LINES,
+            MenuState::Trainer => TECHNIQUES.len(),
+            MenuState::Bookmarks => app.game.bookmarks().len(),
+            MenuState::Palette => PALETTE_ROWS + 1, // + the query line
+            _ => 0,
+        }
+    };
+
+    // The difficulty menu has room for each level's SE range, clues and best time
+    let menu_width: u16 = if is_difficulty_menu { 44 } else { 30 };
+    let inner = menu_width as usize - 6;
+    let menu_height: u16 = (num_options + 5) as u16; // title + options + padding
+    let frame = area.centered(menu_width, menu_height);
+
+    let bg = Color::Rgb {
+        r: 30,
+        g: 30,
+        b: 40,
+    };
+    popup(stdout, frame, bg, Some(theme.border))?;
+
+    // Title, a row for column headings, then the options
+    let parts = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .split(frame.inner(2, 0));
+    let (heading, rows) = (parts[1], parts[2]);
+    let title = menu_title(app.menu);
+    execute!(
+        stdout,
+        centered(frame.row(1), title),
+        SetForegroundColor(theme.fg),
+        Print(title)
+    )?;
+
+    // Options
+    if is_difficulty_menu {
+        let difficulties: Vec<(&str, Color, bool)> = if app.stats.secrets_unlocked() {
+            vec![
+                ("Beginner", Color::Cyan, true),
+                ("Easy", Color::Green, true),
+                ("Medium", Color::Yellow, true),
+                (
+                    "Intermediate",
+                    Color::Rgb {
+                        r: 255,
+                        g: 200,
+                        b: 100,
+                    },
+                    true,
+                ),
+                (
+                    "Hard",
+                    Color::Rgb {
+                        r: 255,
+                        g: 165,
+                        b: 0,
+                    },
+                    true,
+                ),
+                ("Expert", Color::Red, true),
+                ("★ Master", Color::Magenta, true),
+                (
+                    "★ Extreme",
+                    Color::Rgb {
+                        r: 255,
+                        g: 50,
+                        b: 255,
+                    },
+                    true,
+                ),
+            ]
+        } else {
+            vec![
+                ("Beginner", Color::Cyan, true),
+                ("Easy", Color::Green, true),
+                ("Medium", Color::Yellow, true),
+                (
+                    "Intermediate",
+                    Color::Rgb {
+                        r: 255,
+                        g: 200,
+                        b: 100,
+                    },
+                    true,
+                ),
+                (
+                    "Hard",
+                    Color::Rgb {
+                        r: 255,
+                        g: 165,
+                        b: 0,
+                    },
+                    true,
+                ),
+                ("Expert", Color::Red, true),
+            ]
+        };
+
+        execute!(
+            stdout,
+            heading.at(0, 0),
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!(
+                " {:<15} {:^9} {:>5} {:>6} ",
+                "", "SE", "clues", "best"
+            ))
+        )?;
+
+        let levels = if app.stats.secrets_unlocked() {
+            Difficulty::all_levels()
+        } else {
+            Difficulty::standard_levels()
+        };
+        for (i, ((name, color, _), level)) in difficulties.iter().zip(levels.iter()).enumerate() {
+            let selected = i == app.menu_selection;
+            let (fg, item_bg) = if selected {
+                (Color::Black, *color)
+            } else {
+                (*color, bg)
+            };
+
+            let (se_min, se_max) = level.se_range();
+            let (clues, best) = level_record(app, *level);
+            let clues = clues.map_or("-".to_string(), |n| format!("~{}", n));
+            let best = best.map_or("-".to_string(), format_time);
+            execute!(
+                stdout,
+                rows.at(0, i as u16),
+                SetForegroundColor(fg),
+                SetBackgroundColor(item_bg),
+                Print(format!(
+                    " {:<15} {:>4.1}-{:<4.1} {:>5} {:>6} ",
+                    name, se_min, se_max, clues, best
+                ))
+            )?;
+        }
+
+        let daily_y = difficulties.len() as u16;
+        let (fg, item_bg) = if app.menu_selection == difficulties.len() {
+            (Color::Black, theme.key)
+        } else {
+            (theme.key, bg)
+        };
+        execute!(
+            stdout,
+            rows.at(0, daily_y),
+            SetForegroundColor(fg),
+            SetBackgroundColor(item_bg),
+            Print(format!(" {:^inner$} ", "☀ Daily"))
+        )?;
+
+        // Show unlock hint if not unlocked
+        if !app.stats.secrets_unlocked() {
+            let (wins, needed) = app.stats.expert_wins_progress();
+            let hint = format!("🔒 {}/{} Expert wins", wins, needed);
+            execute!(
+                stdout,
+                rows.at(0, daily_y + 1),
+                SetForegroundColor(Color::DarkGrey),
+                SetBackgroundColor(bg),
+                Print(format!(" {:^inner$} ", hint))
+            )?;
+        }
+
+        let timer = format!("⏱ {} (m)", app.timer_mode.label());
+        let players = if app.hotseat_next {
+            "2 players, hot-seat (v)"
+        } else {
+            "1 player (v)"
+        };
+        execute!(
+            stdout,
+            rows.at(0, daily_y + 2),
+            SetForegroundColor(theme.info),
+            SetBackgroundColor(bg),
+            Print(format!(" {:^inner$} ", timer)),
+            rows.at(0, daily_y + 3),
+            Print(format!(" {:^inner$} ", players)),
+            rows.at(0, daily_y + 4),
+            Print(format!(
+                " {:^inner$} ",
+                format!("{} (g)", app.game_mode.label())
+            ))
+        )?;
+    } else if app.menu == MenuState::Daily {
+        render_daily_menu(stdout, app, rows, bg)?;
+    } else if app.menu == MenuState::Palette {
+        render_palette(stdout, app, rows, bg)?;
+    } else {
+        let export_labels = ExportFormat::ALL.map(ExportFormat::label);
+        let copy_labels = CopyTarget::ALL.map(CopyTarget::label);
+        let slot_labels: Vec<String> = app
+            .slot_infos
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| match slot {
+                Some(info) => format!(
+                    "{} {:<8.8} {:>6} {:>3}%",
+                    i + 1,
+                    info.difficulty.to_string(),
+                    info.elapsed,
+                    info.completion
+                ),
+                None => format!("{} (empty)", i + 1),
+            })
+            .collect();
+        let slot_refs: Vec<&str> = slot_labels.iter().map(String::as_str).collect();
+        let technique_labels = TECHNIQUES.map(|(name, _)| name);
+        let theme_labels: Vec<&str> = app.themes.iter().map(|(name, _)| name.as_str()).collect();
+        let bookmark_labels: Vec<&str> = app
+            .game
+            .bookmarks()
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        let options: &[&str] = match app.menu {
+            MenuState::Theme => &theme_labels,
+            MenuState::Confirm => &["Yes", "No"],
+            MenuState::Export => &export_labels,
+            MenuState::Copy => &copy_labels,
+            MenuState::Resume => &["Resume last game", "New game"],
+            MenuState::SaveSlots | MenuState::LoadSlots => &slot_refs,
+            MenuState::Trainer => &technique_labels,
+            MenuState::Bookmarks => &bookmark_labels,
+            _ => &[],
+        };
+
+        for (i, option) in options.iter().enumerate() {
+            let selected = i == app.menu_selection;
+            let (fg, item_bg) = if selected {
+                (Color::Black, theme.key)
+            } else {
+                (theme.fg, bg)
+            };
+
+            execute!(
+                stdout,
+                rows.at(0, i as u16),
+                SetForegroundColor(fg),
+                SetBackgroundColor(item_bg),
+                Print(format!(" {:^24} ", option))
+            )?;
+        }
+
+        // Name of the highlighted slot
+        if matches!(app.menu, MenuState::SaveSlots | MenuState::LoadSlots) {
+            if let Some(Some(info)) = app.slot_infos.get(app.menu_selection) {
+                execute!(
+                    stdout,
+                    rows.at(0, options.len() as u16),
+                    SetForegroundColor(Color::DarkGrey),
+                    SetBackgroundColor(bg),
+                    Print(format!(" {:^24.24} ", info.name))
+                )?;
+            }
+        }
+
+        if let (MenuState::Resume, Some(saved)) = (app.menu, &app.resume_game) {
+            let details = format!("{} · {}", saved.difficulty(), saved.elapsed_string());
+            execute!(
+                stdout,
+                rows.at(0, options.len() as u16),
+                SetForegroundColor(Color::DarkGrey),
+                SetBackgroundColor(bg),
+                Print(format!(" {:^24} ", details))
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lines inside the Daily popup: summary, streak, calendar, action
+const DAILY_MENU_LINES: usize = 10;
+
+/// Matching commands shown in the palette
+const PALETTE_ROWS: usize = 8;
+
+/// Command palette: the query, then the best matches
+fn render_palette(stdout: &mut Buffer, app: &App, area: Rect, bg: Color) -> io::Result<()> {
+    let theme = &app.theme;
+    let found = palette::search(&app.palette_query);
+    execute!(
+        stdout,
+        area.at(0, 0),
+        SetBackgroundColor(theme.selected_bg),
+        SetForegroundColor(theme.fg),
+        Print(format!(" :{:<23}", app.palette_query))
+    )?;
+
+    // Keep the selection in view
+    let scroll = app.menu_selection.saturating_sub(PALETTE_ROWS - 1);
+    for (i, (_, name)) in found.iter().enumerate().skip(scroll).take(PALETTE_ROWS) {
+        let (fg, item_bg) = if i == app.menu_selection {
+            (Color::Black, theme.key)
+        } else {
+            (theme.fg, bg)
+        };
+        execute!(
+            stdout,
+            area.at(0, 1 + (i - scroll) as u16),
+            SetForegroundColor(fg),
+            SetBackgroundColor(item_bg),
+            Print(format!(" {:<24} ", name))
+        )?;
+    }
+    if found.is_empty() {
+        execute!(
+            stdout,
+            area.at(0, 1),
+            SetForegroundColor(theme.border),
+            SetBackgroundColor(bg),
+            Print(format!(" {:<24} ", "No matching command"))
+        )?;
+    }
+    Ok(())
+}
+
+/// Weeks shown in the Daily popup calendar
+const DAILY_CALENDAR_WEEKS: u64 = 4;
+
+/// Today's daily puzzle, the streak and a calendar of recent weeks
+fn render_daily_menu(stdout: &mut Buffer, app: &App, area: Rect, bg: Color) -> io::Result<()> {
+    let theme = &app.theme;
+    let today = daily::today();
+    let tracker = &app.stats.player.daily_streak;
+    let (streak, best) = app.stats.daily_puzzle_streak();
+
+    let summary = format!(
+        "{} {} · {}",
+        daily::weekday_name(today),
+        daily::format_date(today),
+        daily::difficulty(today)
+    );
+    let mut streak_line = format!("Streak {} (best {})", streak, best);
+    if tracker.freezes() > 0 {
+        streak_line.push_str(&format!(" ❄{}", tracker.freezes()));
+    }
+    execute!(
+        stdout,
+        SetBackgroundColor(bg),
+        area.at(0, 0),
+        SetForegroundColor(theme.fg),
+        Print(format!(" {:^24} ", summary)),
+        area.at(0, 1),
+        SetForegroundColor(theme.info),
+        Print(format!(" {:^24} ", streak_line)),
+        area.at(0, 3),
+        SetForegroundColor(theme.border),
+        Print(format!(" {:^24} ", "Mo Tu We Th Fr Sa Su"))
+    )?;
+
+    // Weeks run Monday to Sunday, ending with the current one
+    let first = today - daily::weekday(today) as u64 - 7 * (DAILY_CALENDAR_WEEKS - 1);
+    for week in 0..DAILY_CALENDAR_WEEKS {
+        execute!(stdout, area.at(2, 4 + week as u16))?;
+        for weekday in 0..7 {
+            let day = first + week * 7 + weekday;
+            let (mark, color) = if tracker.is_completed(day) {
+                ("■", theme.success)
+            } else if tracker.day(day) == Some(Day::Frozen) {
+                ("❄", theme.info)
+            } else if day == today {
+                ("□", theme.key)
+            } else if day < today {
+                ("·", theme.border)
+            } else {
+                (" ", theme.border)
+            };
+            execute!(
+                stdout,
+                SetForegroundColor(color),
+                Print(format!(" {} ", mark))
+            )?;
+        }
+    }
+
+    let action = match app.stats.daily_result(today) {
+        Some(record) => format!("Solved in {}", format_time(record.time_secs)),
+        None if app.game.daily() == Some(today) => "Continue".to_string(),
+        None => "Play".to_string(),
+    };
+    execute!(
+        stdout,
+        area.at(0, 5 + DAILY_CALENDAR_WEEKS as u16),
+        SetForegroundColor(Color::Black),
+        SetBackgroundColor(theme.key),
+        Print(format!(" {:^24} ", action))
+    )?;
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! Drawing every screen into a [`Buffer`]. Each screen lives in its own
+//! module and lays itself out inside the area it is given; the pieces they
+//! share (title, footer, popup boxes) are here.
+
+mod editor;
+mod endings;
+mod game;
+mod help;
+mod history;
+mod hotseat;
+mod leaderboard;
+mod line_mode;
+mod menu;
+mod moves;
+mod packs;
+mod stats;
+mod trainer;
+
+pub use game::game_layout;
+
+use crate::app::{App, ScreenState};
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    execute,
+    style::{Color, Print, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+use std::io;
+
+/// Left margin of a full screen's text
+const MARGIN: u16 = 4;
+/// Longest rule drawn above a screen's footer
+const RULE_WIDTH: u16 = 60;
+
+/// Draw the current screen into `stdout`, a frame sized to the terminal
+pub fn render(stdout: &mut Buffer, app: &mut App) -> io::Result<()> {
+    let area = stdout.area();
+
+    execute!(stdout, Hide)?;
+
+    match app.screen_state {
+        // Line mode replaces the game, menus and end screens
+        ScreenState::Playing | ScreenState::Win | ScreenState::Lose if app.a11y => {
+            execute!(stdout, Clear(ClearType::All))?;
+            line_mode::render_a11y_screen(stdout, app, area)?;
+        }
+        // For animation screens, don't clear - we redraw everything anyway
+        ScreenState::Win => endings::render_win_screen(stdout, app, area)?,
+        ScreenState::Lose => endings::render_lose_screen(stdout, app, area)?,
+        state => {
+            execute!(stdout, Clear(ClearType::All))?;
+            match state {
+                ScreenState::Stats => stats::render_stats_screen(stdout, app, area)?,
+                ScreenState::Leaderboard => {
+                    leaderboard::render_leaderboard_screen(stdout, app, area)?
+                }
+                ScreenState::History => history::render_history_screen(stdout, app, area)?,
+                ScreenState::Trainer => trainer::render_trainer_screen(stdout, app, area)?,
+                ScreenState::Achievements => stats::render_achievements_screen(stdout, app, area)?,
+                ScreenState::Packs => packs::render_packs_screen(stdout, app, area)?,
+                ScreenState::HotSeat => hotseat::render_hotseat_screen(stdout, app, area)?,
+                ScreenState::Moves => moves::render_moves_screen(stdout, app, area)?,
+                ScreenState::Editor => editor::render_editor_screen(stdout, app, area)?,
+                ScreenState::Help => help::render_help_screen(stdout, app, area)?,
+                _ => game::render_game_screen(stdout, app, area)?,
+            }
+        }
+    }
+
+    if app.generating.is_some() {
+        render_generating(stdout, app, area)?;
+    }
+
+    if let Some(toast) = app.toasts.front() {
+        render_toast(stdout, app, toast, area)?;
+    }
+
+    execute!(stdout, Show)?;
+    Ok(())
+}
+
+/// Achievement unlock notice in the top-right corner
+fn render_toast(stdout: &mut Buffer, app: &App, text: &str, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let text = format!(" Achievement unlocked: {} ", text);
+    let x = area.width.saturating_sub(text.chars().count() as u16 + 2);
+    execute!(
+        stdout,
+        area.at(x, 1),
+        SetBackgroundColor(theme.success),
+        SetForegroundColor(Color::Black),
+        Print(&text),
+        SetBackgroundColor(theme.bg)
+    )?;
+    Ok(())
+}
+
+/// Spinner box shown while a new puzzle is generated in the background
+fn render_generating(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    let Some(generation) = &app.generating else {
+        return Ok(());
+    };
+    let theme = &app.theme;
+    let elapsed = generation.started.elapsed();
+    let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+    let line = format!(
+        "{} Generating {} puzzle {:.1}s",
+        frame,
+        generation.difficulty,
+        elapsed.as_secs_f32()
+    );
+
+    let area = area.centered(36, 4);
+    popup(stdout, area, theme.selected_bg, None)?;
+    for (row, text) in [(1, line.as_str()), (2, "Esc to cancel")] {
+        execute!(
+            stdout,
+            area.at(0, row),
+            SetForegroundColor(if row == 1 { theme.fg } else { theme.info }),
+            Print(format!("{:^width$}", text, width = area.width as usize))
+        )?;
+    }
+    execute!(stdout, SetBackgroundColor(theme.bg))?;
+    Ok(())
+}
+
+/// Split a full screen into the title rows, the body and the navigation
+/// footer
+fn screen_sections(area: Rect) -> (Rect, Rect, Rect) {
+    let parts = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(1),
+        Constraint::Length(3),
+    ])
+    .split(area);
+    (parts[0], parts[1], parts[2])
+}
+
+/// Cursor position that centers `text` on the first row of `area`
+fn centered(area: Rect, text: &str) -> MoveTo {
+    area.at(
+        area.width.saturating_sub(text.chars().count() as u16) / 2,
+        0,
+    )
+}
+
+/// A screen's title, centered in the middle row of its header
+fn title(stdout: &mut Buffer, app: &App, header: Rect, text: &str) -> io::Result<()> {
+    execute!(
+        stdout,
+        SetBackgroundColor(app.theme.bg),
+        centered(header.row(1), text),
+        SetForegroundColor(app.theme.key),
+        Print(text)
+    )
+}
+
+/// A line of explanation at the top of the body; returns the body below it
+/// and a blank line
+fn subtitle(stdout: &mut Buffer, app: &App, body: Rect, text: &str) -> io::Result<Rect> {
+    execute!(
+        stdout,
+        body.at(MARGIN, 0),
+        SetForegroundColor(app.theme.info),
+        Print(text)
+    )?;
+    Ok(Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).split(body)[1])
+}
+
+/// A rule across the footer with the screen's keys below it. A pair with an
+/// empty key is printed as plain text.
+fn footer(stdout: &mut Buffer, app: &App, nav: Rect, keys: &[(&str, &str)]) -> io::Result<()> {
+    let theme = &app.theme;
+    let rule = nav.width.saturating_sub(MARGIN * 2).min(RULE_WIDTH);
+    execute!(
+        stdout,
+        nav.at(MARGIN, 0),
+        SetForegroundColor(theme.border),
+        Print("─".repeat(rule as usize)),
+        nav.at(MARGIN, 1)
+    )?;
+    for (key, label) in keys {
+        if !key.is_empty() {
+            execute!(
+                stdout,
+                SetForegroundColor(theme.key),
+                Print(key),
+                Print(" ")
+            )?;
+        }
+        execute!(
+            stdout,
+            SetForegroundColor(theme.info),
+            Print(label),
+            Print("  ")
+        )?;
+    }
+    Ok(())
+}
+
+/// Fill `area` with `bg`, framed in `border` when there is one. Leaves the
+/// background set to `bg` for the box's contents.
+fn popup(stdout: &mut Buffer, area: Rect, bg: Color, border: Option<Color>) -> io::Result<()> {
+    execute!(stdout, SetBackgroundColor(bg))?;
+    for row in 0..area.height {
+        execute!(
+            stdout,
+            area.at(0, row),
+            Print(" ".repeat(area.width as usize))
+        )?;
+    }
+    let Some(border) = border else {
+        return Ok(());
+    };
+    if area.width < 2 || area.height < 2 {
+        return Ok(());
+    }
+    let inner = "─".repeat(area.width as usize - 2);
+    execute!(
+        stdout,
+        SetForegroundColor(border),
+        area.at(0, 0),
+        Print(format!("┌{}┐", inner)),
+        area.at(0, area.height - 1),
+        Print(format!("└{}┘", inner))
+    )?;
+    for row in 1..area.height - 1 {
+        execute!(
+            stdout,
+            area.at(0, row),
+            Print("│"),
+            area.at(area.width - 1, row),
+            Print("│")
+        )?;
+    }
+    Ok(())
+}
+
+fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        if current.len() + word.len() + 1 > max_width && !current.is_empty() {
+            lines.push(current);
+            current = String::new();
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
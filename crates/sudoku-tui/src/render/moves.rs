@@ -0,0 +1,82 @@
+//! Move history of the current game, for jumping back to any point.
+
+use super::{footer, screen_sections, subtitle, title, MARGIN};
+use crate::app::App;
+use crate::timer::format_split;
+use crate::ui::{Buffer, Rect};
+use crossterm::{
+    execute,
+    style::{Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+
+/// Move history: the start, every applied step, then the undone steps that
+/// can still be redone
+pub fn render_moves_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let (applied, undone) = app.game.history();
+
+    let (header, body, nav) = screen_sections(area);
+    title(stdout, app, header, "═══ MOVE HISTORY ═══")?;
+    let list = subtitle(
+        stdout,
+        app,
+        body,
+        &format!(
+            "{} moves, {} undone - pick a point to go back to",
+            applied.len(),
+            undone.len()
+        ),
+    )?;
+
+    // Row i shows the position after i steps
+    let steps: Vec<_> = applied.iter().chain(undone.iter().rev()).collect();
+    let rows = steps.len() + 1;
+
+    // A blank line between the list and the footer
+    let visible_rows = list.height.saturating_sub(1).max(1) as usize;
+    let scroll = app
+        .moves_selection
+        .saturating_sub(visible_rows - 1)
+        .min(rows.saturating_sub(visible_rows));
+    for i in (scroll..rows).take(visible_rows) {
+        let y = (i - scroll) as u16;
+        let selected = i == app.moves_selection;
+        let (time, text) = match i.checked_sub(1).map(|step| steps[step]) {
+            Some(entry) => (format_split(entry.at_ms), entry.describe()),
+            None => (String::new(), "Start".to_string()),
+        };
+        let color = if i > applied.len() {
+            theme.border
+        } else {
+            theme.fg
+        };
+        let now = if i == applied.len() { "◀ now" } else { "" };
+        execute!(
+            stdout,
+            list.at(2, y),
+            SetForegroundColor(theme.key),
+            Print(if selected { "▶" } else { " " }),
+            list.at(MARGIN, y),
+            SetBackgroundColor(if selected {
+                theme.selected_bg
+            } else {
+                theme.bg
+            }),
+            SetForegroundColor(theme.info),
+            Print(format!("{:>4} {:>8}  ", i, time)),
+            SetForegroundColor(color),
+            Print(format!("{:<28}", text)),
+            SetBackgroundColor(theme.bg),
+            SetForegroundColor(theme.key),
+            Print(format!(" {}", now))
+        )?;
+    }
+
+    footer(
+        stdout,
+        app,
+        nav,
+        &[("↑/↓", "Select"), ("Enter", "Jump here"), ("Esc", "Back")],
+    )
+}
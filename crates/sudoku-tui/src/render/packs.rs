@@ -0,0 +1,118 @@
+//! The puzzle pack browser.
+
+use super::{footer, screen_sections, subtitle, title, MARGIN};
+use crate::app::App;
+use crate::ui::{Buffer, Rect};
+use crossterm::{
+    execute,
+    style::{Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+
+/// Puzzle packs, or the puzzles of the open pack
+pub fn render_packs_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let browser = &app.packs;
+
+    let (header, body, nav) = screen_sections(area);
+    let heading = match browser.open {
+        Some(pack) => format!("═══ {} ═══", browser.packs[pack].name.to_uppercase()),
+        None => "═══ PUZZLE PACKS ═══".to_string(),
+    };
+    title(stdout, app, header, &heading)?;
+
+    let explanation = match browser.open {
+        Some(pack) => match &browser.packs[pack].author {
+            Some(author) => format!("by {}", author),
+            None => String::new(),
+        },
+        None => "Select a pack and press Enter".to_string(),
+    };
+    let list = subtitle(stdout, app, body, &explanation)?;
+
+    // Rows: (label, detail, solved)
+    let rows: Vec<(String, String, bool)> = match browser.open {
+        None => browser
+            .packs
+            .iter()
+            .map(|pack| {
+                let solved = browser.progress.solved_count(&pack.id);
+                let total = pack.puzzles.len();
+                (
+                    pack.name.clone(),
+                    format!("{}/{} solved", solved, total),
+                    solved == total,
+                )
+            })
+            .collect(),
+        Some(pack) => {
+            let pack = &browser.packs[pack];
+            pack.puzzles
+                .iter()
+                .enumerate()
+                .map(|(i, puzzle)| {
+                    let label = match &puzzle.title {
+                        Some(title) => format!("{:>3}. {}", i + 1, title),
+                        None => format!("{:>3}.", i + 1),
+                    };
+                    let rating = puzzle
+                        .rating
+                        .map_or_else(|| "SE -".to_string(), |r| format!("SE {:.1}", r));
+                    (label, rating, browser.progress.is_solved(&pack.id, i))
+                })
+                .collect()
+        }
+    };
+
+    // A blank line between the list and the footer
+    let visible_rows = list.height.saturating_sub(1).max(1) as usize;
+    let scroll = browser.selection.saturating_sub(visible_rows - 1);
+    for (i, (label, detail, solved)) in rows.iter().enumerate().skip(scroll).take(visible_rows) {
+        let y = (i - scroll) as u16;
+        let selected = i == browser.selection;
+        let label: String = label.chars().take(36).collect();
+        execute!(
+            stdout,
+            list.at(2, y),
+            SetForegroundColor(theme.key),
+            Print(if selected { "▶" } else { " " }),
+            list.at(MARGIN, y),
+            SetBackgroundColor(if selected {
+                theme.selected_bg
+            } else {
+                theme.bg
+            }),
+            SetForegroundColor(theme.fg),
+            Print(format!("{:<36}", label)),
+            SetForegroundColor(theme.info),
+            Print(format!(" {:>14}", detail)),
+            SetForegroundColor(theme.success),
+            Print(format!(" {:>2}", if *solved { "✓" } else { "" })),
+            SetBackgroundColor(theme.bg)
+        )?;
+    }
+
+    if rows.is_empty() {
+        execute!(
+            stdout,
+            list.at(MARGIN, 0),
+            SetForegroundColor(theme.border),
+            Print(format!(
+                "No packs in {} (.json, .txt or .sdm)",
+                app.packs_dir().display()
+            ))
+        )?;
+    }
+
+    let enter = if browser.open.is_some() {
+        "Play"
+    } else {
+        "Open"
+    };
+    footer(
+        stdout,
+        app,
+        nav,
+        &[("↑/↓", "Select"), ("Enter", enter), ("Esc", "Back")],
+    )
+}
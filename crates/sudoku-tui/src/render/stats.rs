@@ -0,0 +1,457 @@
+//! Statistics and achievements.
+
+use super::{footer, screen_sections, title, MARGIN};
+use crate::achievements::{self, ACHIEVEMENTS};
+use crate::app::App;
+use crate::stats::{format_time, histogram};
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Color, Print, SetForegroundColor},
+};
+use std::io;
+
+/// Overall and per-difficulty statistics, and some fun with big numbers
+pub fn render_stats_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let stats = &app.stats;
+    let player = &stats.player;
+
+    let (header, body, nav) = screen_sections(area);
+    title(stdout, app, header, "═══ STATISTICS ═══")?;
+
+    let columns =
+        Layout::horizontal([Constraint::Length(body.width / 2), Constraint::Min(1)]).split(body);
+    let (left, right) = (columns[0].inner(MARGIN, 0), columns[1]);
+
+    // Overall stats (left column)
+    execute!(
+        stdout,
+        left.at(0, 0),
+        SetForegroundColor(theme.fg),
+        Print(format!("Player: {}", player.player_name))
+    )?;
+
+    execute!(
+        stdout,
+        left.at(0, 2),
+        SetForegroundColor(theme.info),
+        Print(format!("Total Games: {}", player.total_games))
+    )?;
+    execute!(
+        stdout,
+        left.at(0, 3),
+        SetForegroundColor(theme.success),
+        Print(format!("Wins: {}", player.total_wins))
+    )?;
+    execute!(
+        stdout,
+        left.at(0, 4),
+        SetForegroundColor(theme.error),
+        Print(format!("Losses: {}", player.total_losses))
+    )?;
+    execute!(
+        stdout,
+        left.at(0, 5),
+        SetForegroundColor(theme.border),
+        Print(format!("Abandoned: {}", player.total_abandoned))
+    )?;
+    execute!(
+        stdout,
+        left.at(0, 7),
+        SetForegroundColor(theme.fg),
+        Print(format!("Win Rate: {:.1}%", player.overall_win_rate()))
+    )?;
+
+    // Streak info
+    let streak_color = if player.current_streak > 0 {
+        theme.success
+    } else if player.current_streak < 0 {
+        theme.error
+    } else {
+        theme.info
+    };
+    execute!(
+        stdout,
+        left.at(0, 8),
+        SetForegroundColor(streak_color),
+        Print(format!("Current Streak: {}", player.current_streak.abs())),
+        SetForegroundColor(theme.info),
+        Print(if player.current_streak > 0 {
+            " wins"
+        } else if player.current_streak < 0 {
+            " losses"
+        } else {
+            ""
+        })
+    )?;
+    execute!(
+        stdout,
+        left.at(0, 9),
+        SetForegroundColor(theme.key),
+        Print(format!("Best Streak: {} wins", player.best_streak))
+    )?;
+
+    let (daily, best_daily) = stats.daily_streak();
+    execute!(
+        stdout,
+        left.at(0, 11),
+        SetForegroundColor(theme.info),
+        Print(format!(
+            "Daily Streak: {} days (best {})",
+            daily, best_daily
+        ))
+    )?;
+
+    // Techniques the player has had to use in won games
+    let techniques = player.top_techniques(4);
+    if !techniques.is_empty() {
+        execute!(
+            stdout,
+            left.at(0, 13),
+            SetForegroundColor(theme.fg),
+            Print("Techniques Seen:")
+        )?;
+        for (i, (name, count)) in techniques.iter().enumerate() {
+            execute!(
+                stdout,
+                left.at(2, 14 + i as u16),
+                SetForegroundColor(theme.info),
+                Print(format!("{:<20.20} {:>4}", name, count))
+            )?;
+        }
+    }
+
+    // Per-difficulty stats (right column)
+    execute!(
+        stdout,
+        right.at(0, 0),
+        SetForegroundColor(theme.fg),
+        Print("By Difficulty:")
+    )?;
+
+    let difficulties = [
+        sudoku_core::Difficulty::Easy,
+        sudoku_core::Difficulty::Medium,
+        sudoku_core::Difficulty::Hard,
+        sudoku_core::Difficulty::Expert,
+    ];
+
+    for (i, diff) in difficulties.iter().enumerate() {
+        let ds = player.get_difficulty_stats(*diff);
+        let y = 2 + i as u16 * 4;
+
+        let diff_color = match diff {
+            sudoku_core::Difficulty::Beginner => Color::Cyan,
+            sudoku_core::Difficulty::Easy => Color::Green,
+            sudoku_core::Difficulty::Medium => Color::Yellow,
+            sudoku_core::Difficulty::Intermediate => Color::Rgb {
+                r: 255,
+                g: 200,
+                b: 100,
+            },
+            sudoku_core::Difficulty::Hard => Color::Rgb {
+                r: 255,
+                g: 165,
+                b: 0,
+            },
+            sudoku_core::Difficulty::Expert => Color::Red,
+            sudoku_core::Difficulty::Master => Color::Magenta,
+            sudoku_core::Difficulty::Extreme => Color::Rgb {
+                r: 255,
+                g: 50,
+                b: 255,
+            },
+        };
+
+        execute!(
+            stdout,
+            right.at(0, y),
+            SetForegroundColor(diff_color),
+            Print(format!("{:?}", diff))
+        )?;
+        execute!(
+            stdout,
+            right.at(2, y + 1),
+            SetForegroundColor(theme.info),
+            Print(format!(
+                "Games: {} | Wins: {} ({:.0}%)",
+                ds.total_games,
+                ds.wins,
+                ds.win_rate()
+            ))
+        )?;
+
+        let best_str = ds
+            .best_time_secs
+            .map(format_time)
+            .unwrap_or_else(|| "--:--".to_string());
+        let avg_str = ds
+            .avg_time_secs()
+            .map(format_time)
+            .unwrap_or_else(|| "--:--".to_string());
+        execute!(
+            stdout,
+            right.at(2, y + 2),
+            SetForegroundColor(theme.info),
+            Print(format!("Best: {} | Avg: {}", best_str, avg_str))
+        )?;
+
+        // Solve-time distribution (fastest left) and per-game assist averages
+        let times = stats.solve_times(*diff);
+        let chart = if times.is_empty() {
+            "·".repeat(HISTOGRAM_BUCKETS)
+        } else {
+            sparkline(&histogram(&times, HISTOGRAM_BUCKETS))
+        };
+        execute!(
+            stdout,
+            right.at(2, y + 3),
+            SetForegroundColor(diff_color),
+            Print(chart),
+            SetForegroundColor(theme.border),
+            Print(format!(
+                " hints {:.1} | mistakes {:.1}",
+                ds.avg_hints(),
+                ds.avg_mistakes()
+            ))
+        )?;
+    }
+
+    // Puzzle Universe section (fun stats!)
+    let universe_y = 18;
+    if body.height >= universe_y + 6 {
+        execute!(
+            stdout,
+            left.at(0, universe_y),
+            SetForegroundColor(theme.key),
+            Print("═══ PUZZLE UNIVERSE ═══")
+        )?;
+
+        // Total puzzles in universe (10^30 estimate)
+        let total_universe: f64 = 1e30;
+        let total_wins = player.total_wins as f64;
+
+        // Universe explored
+        let (explored_text, percentage_text) = if total_wins > 0.0 {
+            let percentage = total_wins / total_universe * 100.0;
+            let exponent = percentage.log10().floor() as i32;
+            (
+                format!("{} / 10³⁰ puzzles", player.total_wins),
+                format!("(10^{}%)", exponent),
+            )
+        } else {
+            ("0 / 10³⁰ puzzles".to_string(), "(0%)".to_string())
+        };
+
+        execute!(
+            stdout,
+            left.at(0, universe_y + 1),
+            SetForegroundColor(Color::Blue),
+            Print("◉ "),
+            SetForegroundColor(theme.fg),
+            Print("Explored: "),
+            SetForegroundColor(theme.info),
+            Print(&explored_text),
+            Print(" "),
+            SetForegroundColor(theme.border),
+            Print(&percentage_text)
+        )?;
+
+        // Cheeky note about progress
+        let progress_note = match player.total_wins {
+            0 => "The universe awaits your first victory!",
+            1 => "One small step for puzzlekind...",
+            2..=9 => "A tiny dent in infinity!",
+            10..=99 => "At this rate: approximately... never.",
+            100..=999 => "Impressive! The universe remains unimpressed.",
+            _ => "A true warrior! The cosmos trembles (microscopically).",
+        };
+
+        execute!(
+            stdout,
+            left.at(2, universe_y + 2),
+            SetForegroundColor(theme.border),
+            Print("└─ "),
+            SetForegroundColor(Color::DarkGrey),
+            Print(progress_note)
+        )?;
+
+        // Time to complete all puzzles
+        if player.total_wins > 0 {
+            let avg_time = player
+                .total_play_time_secs
+                .checked_div(player.total_wins as u64)
+                .unwrap_or(300);
+            let total_seconds = avg_time as f64 * total_universe;
+            let years = total_seconds / (365.25 * 24.0 * 3600.0);
+            let exponent = years.log10().floor() as i32;
+            let mantissa = years / 10_f64.powi(exponent);
+
+            execute!(
+                stdout,
+                left.at(0, universe_y + 4),
+                SetForegroundColor(Color::Rgb {
+                    r: 255,
+                    g: 165,
+                    b: 0
+                }),
+                Print("⏱ "),
+                SetForegroundColor(theme.fg),
+                Print("Time to finish all: "),
+                SetForegroundColor(theme.info),
+                Print(format!("≈ {:.1} × 10^{} years", mantissa, exponent))
+            )?;
+
+            // Cheeky time note based on average solve time
+            let avg_mins = avg_time / 60;
+            let time_note = match avg_mins {
+                0..=1 => "Speed demon! Still need multiple universe lifetimes.",
+                2..=4 => "Quick! The heat death of the universe will wait.",
+                5..=9 => "Solid pace! Only 10²² generations needed to help.",
+                10..=19 => "Taking your time? You'll need immortality.",
+                20..=29 => "Thoughtful! The Sun burns out first, no pressure.",
+                _ => "Savoring it! New universes will form and die. Repeatedly.",
+            };
+
+            execute!(
+                stdout,
+                left.at(2, universe_y + 5),
+                SetForegroundColor(theme.border),
+                Print("└─ "),
+                SetForegroundColor(Color::DarkGrey),
+                Print(time_note)
+            )?;
+        }
+    }
+
+    // What won games' placements say about how the player solves
+    let style = &player.play_style;
+    let style_y = universe_y + 7;
+    if style.placements > 0 && body.height >= style_y + 3 {
+        execute!(
+            stdout,
+            left.at(0, style_y),
+            SetForegroundColor(theme.fg),
+            Print("Play Style: "),
+            SetForegroundColor(theme.info),
+            Print(format!(
+                "guessed {:.0}% of {} digits",
+                style.guess_rate(),
+                style.placements
+            ))
+        )?;
+        let tiers: Vec<String> = style
+            .tier_times()
+            .iter()
+            .map(|(d, secs)| format!("{:?} {:.0}s", d, secs))
+            .collect();
+        execute!(
+            stdout,
+            left.at(2, style_y + 1),
+            SetForegroundColor(theme.info),
+            Print(format!("Avg per digit: {}", tiers.join("  ")))
+        )?;
+        execute!(
+            stdout,
+            left.at(2, style_y + 2),
+            SetForegroundColor(theme.info),
+            Print(format!("Most used: {}", style.top_techniques(3).join(", ")))
+        )?;
+    }
+
+    footer(
+        stdout,
+        app,
+        nav,
+        &[
+            ("b", "Leaderboard"),
+            ("H", "History"),
+            ("a", "Achievements"),
+            ("e", "Export"),
+            ("Esc", "Back to game"),
+        ],
+    )?;
+    if let Some(ref msg) = app.message {
+        execute!(
+            stdout,
+            nav.at(MARGIN, 2),
+            SetForegroundColor(theme.info),
+            Print(msg)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Width of the progress bars on the achievements screen
+const ACHIEVEMENT_BAR_WIDTH: usize = 20;
+
+/// Every achievement with its progress bar
+pub fn render_achievements_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let stats = &app.stats;
+
+    let unlocked = ACHIEVEMENTS
+        .iter()
+        .filter(|a| stats.has_achievement(a.id))
+        .count();
+    let (header, body, nav) = screen_sections(area);
+    title(
+        stdout,
+        app,
+        header,
+        &format!("═══ ACHIEVEMENTS {}/{} ═══", unlocked, ACHIEVEMENTS.len()),
+    )?;
+
+    for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+        let y = i as u16 * 2;
+        if y >= body.height {
+            break;
+        }
+        let done = stats.has_achievement(achievement.id);
+        let progress = achievements::progress(achievement, stats);
+        let filled = progress as usize * ACHIEVEMENT_BAR_WIDTH / achievement.target as usize;
+        let (mark, name_color) = if done {
+            ("🏆", theme.success)
+        } else {
+            ("🔒", theme.border)
+        };
+        execute!(
+            stdout,
+            body.at(MARGIN, y),
+            Print(mark),
+            SetForegroundColor(name_color),
+            Print(format!(" {:<18}", achievement.name)),
+            SetForegroundColor(theme.info),
+            Print(format!("{:<26}", achievement.description)),
+            SetForegroundColor(if done { theme.success } else { theme.key }),
+            Print("█".repeat(filled)),
+            SetForegroundColor(theme.border),
+            Print("░".repeat(ACHIEVEMENT_BAR_WIDTH - filled)),
+            SetForegroundColor(theme.info),
+            Print(format!(" {}/{}", progress, achievement.target))
+        )?;
+    }
+
+    footer(stdout, app, nav, &[("i", "Stats"), ("Esc", "Back to game")])
+}
+
+/// Bars in the solve-time histograms on the stats screen
+const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Draw counts as a one-line bar chart, scaled to the largest count
+fn sparkline(counts: &[usize]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|&c| {
+            if c == 0 {
+                ' '
+            } else {
+                BARS[(c * (BARS.len() - 1)).div_ceil(max)]
+            }
+        })
+        .collect()
+}
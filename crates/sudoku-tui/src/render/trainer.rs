@@ -0,0 +1,175 @@
+//! The technique trainer.
+
+use super::{footer, screen_sections, title, wrap_text, MARGIN};
+use crate::app::App;
+use crate::ui::{Buffer, Constraint, Layout, Rect};
+use crossterm::{
+    execute,
+    style::{Color, Print, SetBackgroundColor, SetForegroundColor},
+};
+use std::io;
+use sudoku_core::Position;
+
+/// Technique trainer: a compact grid, the question and how it went
+pub fn render_trainer_screen(stdout: &mut Buffer, app: &App, area: Rect) -> io::Result<()> {
+    let theme = &app.theme;
+    let Some(trainer) = &app.trainer else {
+        return Ok(());
+    };
+
+    let (header, body, nav) = screen_sections(area);
+    title(
+        stdout,
+        app,
+        header,
+        &format!("═══ TRAINER: {} ═══", trainer.technique()),
+    )?;
+
+    // Compact grid: 3-char cells, lines only between boxes (29 x 11), then
+    // the side panel
+    let columns = Layout::horizontal([
+        Constraint::Length(MARGIN),
+        Constraint::Length(33),
+        Constraint::Min(20),
+    ])
+    .split(body);
+    let (board, panel) = (columns[1], columns[2]);
+    let answered = trainer.answered();
+    let grid = trainer.grid();
+    for row in 0..9 {
+        let y = (row + row / 3) as u16;
+        execute!(stdout, board.at(0, y))?;
+        for col in 0..9 {
+            if col > 0 && col % 3 == 0 {
+                execute!(
+                    stdout,
+                    SetBackgroundColor(theme.bg),
+                    SetForegroundColor(theme.box_border),
+                    Print("│")
+                )?;
+            }
+            let pos = Position::new(row, col);
+            let cell = grid.cell(pos);
+            let bg = if answered.is_some() && pos == trainer.target() {
+                theme.success
+            } else if pos == trainer.cursor {
+                theme.selected_bg
+            } else {
+                theme.bg
+            };
+            let (fg, text) = match cell.value() {
+                Some(v) if cell.is_given() => (theme.given, format!(" {} ", v)),
+                Some(v) => (theme.filled, format!(" {} ", v)),
+                None => (Color::DarkGrey, " · ".to_string()),
+            };
+            execute!(
+                stdout,
+                SetBackgroundColor(bg),
+                SetForegroundColor(fg),
+                Print(text)
+            )?;
+        }
+        if row == 2 || row == 5 {
+            execute!(
+                stdout,
+                board.at(0, y + 1),
+                SetBackgroundColor(theme.bg),
+                SetForegroundColor(theme.box_border),
+                Print("─────────┼─────────┼─────────")
+            )?;
+        }
+    }
+    execute!(stdout, SetBackgroundColor(theme.bg))?;
+
+    // Side panel
+    let mut y = 0;
+    let cursor = trainer.cursor;
+    let candidates: String = grid
+        .cell(cursor)
+        .candidates()
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let record = app
+        .stats
+        .player
+        .trainer
+        .get(trainer.technique())
+        .copied()
+        .unwrap_or_default();
+    let goal = if trainer.is_placement() {
+        "the technique places."
+    } else {
+        "the technique eliminates."
+    };
+    let lines = [
+        (theme.fg, "Find the cell and the digit".to_string()),
+        (theme.fg, goal.to_string()),
+        (theme.fg, String::new()),
+        (
+            theme.info,
+            format!("r{}c{}: {}", cursor.row + 1, cursor.col + 1, candidates),
+        ),
+        (theme.fg, String::new()),
+        (
+            theme.info,
+            format!(
+                "Session: {}/{} correct",
+                trainer.solved(),
+                trainer.attempts()
+            ),
+        ),
+        (
+            theme.info,
+            format!("All time: {:.0}% of {}", record.accuracy(), record.attempts),
+        ),
+    ];
+    for (color, line) in &lines {
+        execute!(
+            stdout,
+            panel.at(0, y),
+            SetForegroundColor(*color),
+            Print(line)
+        )?;
+        y += 1;
+    }
+
+    // After answering, show the finding
+    if let Some((pos, digit)) = answered {
+        y += 1;
+        let (color, verdict) = if trainer.is_correct(pos, digit) {
+            (theme.success, "Correct!")
+        } else {
+            (theme.error, "Not quite.")
+        };
+        execute!(
+            stdout,
+            panel.at(0, y),
+            SetForegroundColor(color),
+            Print(verdict)
+        )?;
+        let max_width = panel.width.saturating_sub(2).max(20) as usize;
+        for line in wrap_text(&trainer.hint().explanation, max_width) {
+            y += 1;
+            execute!(
+                stdout,
+                panel.at(0, y),
+                SetForegroundColor(theme.fg),
+                Print(line)
+            )?;
+        }
+    }
+
+    footer(
+        stdout,
+        app,
+        nav,
+        &[
+            ("hjkl/←↓↑→", "Move"),
+            ("1-9", "Answer"),
+            ("n", "Next"),
+            ("Esc", "Back"),
+        ],
+    )
+}
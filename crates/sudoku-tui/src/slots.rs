@@ -0,0 +1,121 @@
+//! Named save slots under the config directory.
+
+use crate::config::Config;
+use crate::game::Game;
+use crate::persistence::atomic_write;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sudoku_core::Difficulty;
+
+/// Number of save slots
+pub const SLOT_COUNT: usize = 5;
+
+/// A slot file: the save plus a name to recognise it by
+#[derive(Serialize, Deserialize)]
+struct SlotFile {
+    name: String,
+    /// `Game::serialize` output
+    game: String,
+}
+
+/// Summary of a filled slot for the save/load menu
+#[derive(Debug, Clone)]
+pub struct SlotInfo {
+    pub name: String,
+    pub difficulty: Difficulty,
+    pub elapsed: String,
+    pub completion: u8,
+}
+
+/// Where the slot files live
+fn slots_dir() -> Option<PathBuf> {
+    Config::dir().map(|dir| dir.join("saves"))
+}
+
+fn slot_path(dir: &Path, index: usize) -> PathBuf {
+    dir.join(format!("slot{}.json", index + 1))
+}
+
+fn read_slot(dir: &Path, index: usize) -> Option<(String, Game)> {
+    let json = fs::read_to_string(slot_path(dir, index)).ok()?;
+    let file: SlotFile = serde_json::from_str(&json).ok()?;
+    Some((file.name, Game::deserialize(&file.game)?))
+}
+
+/// Summaries of every slot, `None` for empty (or unreadable) ones
+pub fn list() -> Vec<Option<SlotInfo>> {
+    match slots_dir() {
+        Some(dir) => list_in(&dir),
+        None => vec![None; SLOT_COUNT],
+    }
+}
+
+fn list_in(dir: &Path) -> Vec<Option<SlotInfo>> {
+    (0..SLOT_COUNT)
+        .map(|i| {
+            let (name, game) = read_slot(dir, i)?;
+            Some(SlotInfo {
+                name,
+                difficulty: game.difficulty(),
+                elapsed: game.elapsed_string(),
+                completion: game.completion_percent(),
+            })
+        })
+        .collect()
+}
+
+/// Save a game to a slot, named after its short code when it has one
+pub fn save(index: usize, game: &Game) -> Result<(), String> {
+    save_in(&slots_dir().ok_or("no config directory")?, index, game)
+}
+
+fn save_in(dir: &Path, index: usize, game: &Game) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let name = match game.short_code() {
+        Some(code) => format!("{} #{}", game.difficulty(), code),
+        None => format!("{} (imported)", game.difficulty()),
+    };
+    let file = SlotFile {
+        name,
+        game: game.serialize(),
+    };
+    let json = serde_json::to_string(&file).map_err(|e| e.to_string())?;
+    atomic_write(&slot_path(dir, index), json.as_bytes()).map_err(|e| e.to_string())
+}
+
+/// Load the game in a slot
+pub fn load(index: usize) -> Option<Game> {
+    read_slot(&slots_dir()?, index).map(|(_, game)| game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    #[test]
+    fn test_save_and_load_slots() {
+        let dir = tempfile::tempdir().unwrap();
+        let saves = dir.path().join("saves");
+        let puzzle = Tier::Singles.puzzle();
+        let game = Game::from_string(puzzle).unwrap();
+
+        // The directory is created on first save
+        save_in(&saves, 2, &game).unwrap();
+        let infos = list_in(&saves);
+        assert_eq!(infos.len(), SLOT_COUNT);
+        assert!(infos[0].is_none() && infos[1].is_none());
+        let info = infos[2].as_ref().unwrap();
+        assert!(info.name.ends_with("(imported)"));
+        assert_eq!(info.difficulty, game.difficulty());
+        assert_eq!(info.completion, game.completion_percent());
+
+        let (_, loaded) = read_slot(&saves, 2).unwrap();
+        assert_eq!(loaded.original_puzzle(), puzzle);
+
+        // A damaged file reads as an empty slot
+        fs::write(slot_path(&saves, 0), "not json").unwrap();
+        assert!(list_in(&saves)[0].is_none());
+    }
+}
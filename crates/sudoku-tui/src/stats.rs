@@ -1,13 +1,19 @@
 #![allow(dead_code)]
 
-use crate::leaderboard::{self, LeaderboardManager};
+use crate::achievements::{self, Achievement, ACHIEVEMENTS};
+use crate::leaderboard::{
+    self, LeaderboardManager, LeaderboardResult, LocalLeaderboard, RemoteConfig, RemoteLeaderboard,
+};
+use crate::timer::{Splits, TimerMode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
 use std::time::{SystemTime, UNIX_EPOCH};
-use sudoku_core::Difficulty;
+use sudoku_core::{Difficulty, Grid, Solver};
+use sudoku_session::GameMode;
+use sudoku_streak::StreakTracker;
 
 /// Result of a completed game
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,6 +60,18 @@ pub struct GameRecord {
     /// Short code (if generated via PuzzleId)
     #[serde(default)]
     pub short_code: Option<String>,
+    /// Day number if this was a daily puzzle
+    #[serde(default)]
+    pub daily: Option<u64>,
+    /// Clock mode the game was played with
+    #[serde(default)]
+    pub timer: TimerMode,
+    /// Split times of a speedrun
+    #[serde(default)]
+    pub splits: Option<Splits>,
+    /// Game mode, which picks the leaderboard
+    #[serde(default)]
+    pub mode: GameMode,
 }
 
 impl GameRecord {
@@ -98,6 +116,22 @@ impl DifficultyStats {
             0.0
         }
     }
+
+    pub fn avg_hints(&self) -> f32 {
+        if self.total_games > 0 {
+            self.total_hints as f32 / self.total_games as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn avg_mistakes(&self) -> f32 {
+        if self.total_games > 0 {
+            self.total_mistakes as f32 / self.total_games as f32
+        } else {
+            0.0
+        }
+    }
 }
 
 /// Overall player statistics
@@ -127,6 +161,101 @@ pub struct PlayerStats {
     pub no_notes_master_achieved: bool,
     /// Unlock reason (for display)
     pub unlock_reason: Option<String>,
+    /// Won games in which each solving technique was needed
+    #[serde(default)]
+    pub technique_exposure: HashMap<String, u32>,
+    /// Trainer answers per technique
+    #[serde(default)]
+    pub trainer: HashMap<String, TrainerStats>,
+    /// Whether the Konami code was ever entered
+    #[serde(default)]
+    pub konami_entered: bool,
+    /// Ids of unlocked achievements
+    #[serde(default)]
+    pub achievements: Vec<String>,
+    /// Techniques and timing inferred from won games' placements
+    #[serde(default)]
+    pub play_style: PlayStyle,
+    /// Solved daily puzzles by day, with streak freezes
+    #[serde(default)]
+    pub daily_streak: StreakTracker,
+}
+
+/// Technique profiles of won games, summed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayStyle {
+    /// Placements per technique
+    pub techniques: HashMap<String, u32>,
+    /// Placements and total milliseconds per difficulty level
+    pub tiers: HashMap<String, (u32, u64)>,
+    pub guesses: u32,
+    pub placements: u32,
+}
+
+impl PlayStyle {
+    pub fn add(&mut self, profile: &sudoku_glossary::Profile) {
+        for (technique, count) in &profile.techniques {
+            *self.techniques.entry(technique.clone()).or_default() += count;
+        }
+        for tier in &profile.tiers {
+            let entry = self
+                .tiers
+                .entry(format!("{:?}", tier.difficulty))
+                .or_default();
+            entry.0 += tier.moves;
+            entry.1 += tier.total_ms;
+        }
+        self.guesses += profile.guesses;
+        self.placements += profile.placements;
+    }
+
+    /// Percentage of placements that were guesses
+    pub fn guess_rate(&self) -> f32 {
+        if self.placements > 0 {
+            self.guesses as f32 / self.placements as f32 * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Techniques used most, most first
+    pub fn top_techniques(&self, limit: usize) -> Vec<&str> {
+        let mut techniques: Vec<(&String, &u32)> = self.techniques.iter().collect();
+        techniques.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        techniques
+            .into_iter()
+            .take(limit)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Average seconds per placement at each level, easiest first
+    pub fn tier_times(&self) -> Vec<(Difficulty, f32)> {
+        Difficulty::all_levels()
+            .iter()
+            .filter_map(|&d| {
+                let &(moves, ms) = self.tiers.get(&format!("{:?}", d))?;
+                (moves > 0).then(|| (d, ms as f32 / moves as f32 / 1000.0))
+            })
+            .collect()
+    }
+}
+
+/// Trainer accuracy for one technique
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TrainerStats {
+    pub attempts: u32,
+    pub correct: u32,
+}
+
+impl TrainerStats {
+    pub fn accuracy(&self) -> f32 {
+        if self.attempts > 0 {
+            self.correct as f32 / self.attempts as f32 * 100.0
+        } else {
+            0.0
+        }
+    }
 }
 
 /// Number of expert wins required to unlock secret difficulties
@@ -162,6 +291,18 @@ impl PlayerStats {
             .cloned()
             .unwrap_or_default()
     }
+
+    /// Most frequently needed techniques, most common first
+    pub fn top_techniques(&self, limit: usize) -> Vec<(&str, u32)> {
+        let mut techniques: Vec<(&str, u32)> = self
+            .technique_exposure
+            .iter()
+            .map(|(name, &count)| (name.as_str(), count))
+            .collect();
+        techniques.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        techniques.truncate(limit);
+        techniques
+    }
 }
 
 /// Leaderboard entry
@@ -175,6 +316,10 @@ pub struct LeaderboardEntry {
     pub mistakes: usize,
     pub timestamp: u64,
     pub puzzle_hash: String,
+    #[serde(default)]
+    pub timer: TimerMode,
+    #[serde(default)]
+    pub mode: GameMode,
 }
 
 /// Anti-bot verification thresholds
@@ -274,6 +419,24 @@ pub struct VerificationResult {
     pub issues: Vec<String>,
 }
 
+/// Version of the stats export layout (docs/stats-export.md). Bumped when
+/// a field is removed or changes meaning; new fields may appear any time.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Everything `--export-stats` writes
+#[derive(Serialize)]
+struct StatsExport<'a> {
+    /// Always "sudoku-stats"
+    format: &'static str,
+    version: u32,
+    /// Unix time of the export
+    exported_at: u64,
+    player: &'a PlayerStats,
+    /// Most recent first
+    history: &'a [GameRecord],
+    leaderboard: &'a [LeaderboardEntry],
+}
+
 /// The main statistics manager
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatsManager {
@@ -320,6 +483,19 @@ impl StatsManager {
         };
         // Initialize remote leaderboard after deserialization
         stats.remote_leaderboard = Some(Arc::new(LeaderboardManager::auto()));
+        // Stats from before the streak tracker: rebuild it from the history
+        if stats.player.daily_streak.is_empty() {
+            let mut solved: Vec<(u64, u64)> = stats
+                .history
+                .iter()
+                .filter(|r| r.result == GameResult::Win)
+                .filter_map(|r| Some((r.daily?, r.time_secs)))
+                .collect();
+            solved.sort_unstable();
+            for (day, time_secs) in solved {
+                stats.player.daily_streak.complete(day, time_secs);
+            }
+        }
         stats
     }
 
@@ -330,6 +506,23 @@ impl StatsManager {
         }
     }
 
+    /// Player stats, game history and the local leaderboard as JSON in the
+    /// documented export layout
+    pub fn export_json(&self) -> String {
+        let export = StatsExport {
+            format: "sudoku-stats",
+            version: EXPORT_VERSION,
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            player: &self.player,
+            history: &self.history,
+            leaderboard: &self.leaderboard,
+        };
+        serde_json::to_string_pretty(&export).unwrap_or_default()
+    }
+
     /// Record a completed game
     #[allow(clippy::too_many_arguments)]
     pub fn record_game(
@@ -344,6 +537,10 @@ impl StatsManager {
         notes_used: bool,
         seed: Option<u64>,
         short_code: Option<String>,
+        daily: Option<u64>,
+        timer: TimerMode,
+        splits: Option<Splits>,
+        mode: GameMode,
     ) -> &GameRecord {
         let puzzle_hash = sudoku_core::canonical_puzzle_hash_str(puzzle);
 
@@ -392,6 +589,10 @@ impl StatsManager {
             verified: false,
             seed,
             short_code,
+            daily,
+            timer,
+            splits,
+            mode,
         };
 
         // Run anti-bot verification
@@ -440,6 +641,14 @@ impl StatsManager {
                     self.player.no_notes_master_achieved = true;
                 }
 
+                for technique in techniques_needed(puzzle) {
+                    *self.player.technique_exposure.entry(technique).or_default() += 1;
+                }
+
+                if let Some(day) = daily {
+                    self.player.daily_streak.complete(day, time_secs);
+                }
+
                 // Check all unlock conditions
                 self.check_unlocks();
             }
@@ -502,6 +711,8 @@ impl StatsManager {
                 mistakes,
                 timestamp,
                 puzzle_hash: self.history[0].puzzle_hash.clone(),
+                timer,
+                mode,
             };
             self.add_to_leaderboard(entry);
         }
@@ -537,6 +748,62 @@ impl StatsManager {
             .collect()
     }
 
+    /// Winning times for a difficulty, oldest first
+    pub fn solve_times(&self, difficulty: Difficulty) -> Vec<u64> {
+        self.history
+            .iter()
+            .rev()
+            .filter(|r| r.result == GameResult::Win && r.difficulty == difficulty)
+            .map(|r| r.time_secs)
+            .collect()
+    }
+
+    /// Median number of givens in the puzzles played at a difficulty
+    pub fn typical_clues(&self, difficulty: Difficulty) -> Option<usize> {
+        let mut clues: Vec<usize> = self
+            .history
+            .iter()
+            .filter(|r| r.difficulty == difficulty)
+            .map(|r| r.puzzle.chars().filter(|c| ('1'..='9').contains(c)).count())
+            .collect();
+        clues.sort_unstable();
+        clues.get(clues.len() / 2).copied()
+    }
+
+    /// Consecutive days (UTC) with at least one win: (current, best). The
+    /// current streak survives until a full day passes without a win.
+    pub fn daily_streak(&self) -> (u32, u32) {
+        streak(
+            self.history
+                .iter()
+                .filter(|r| r.result == GameResult::Win)
+                .map(|r| sudoku_daily::day_of(r.timestamp))
+                .collect(),
+        )
+    }
+
+    /// Consecutive daily puzzles solved, bridged by freezes: (current, best)
+    pub fn daily_puzzle_streak(&self) -> (u32, u32) {
+        let tracker = &self.player.daily_streak;
+        (tracker.current(sudoku_daily::today()), tracker.best())
+    }
+
+    /// Day numbers of solved daily puzzles
+    pub fn completed_dailies(&self) -> Vec<u64> {
+        self.history
+            .iter()
+            .filter(|r| r.result == GameResult::Win)
+            .filter_map(|r| r.daily)
+            .collect()
+    }
+
+    /// The winning record for a day's daily puzzle, if it was solved
+    pub fn daily_result(&self, day: u64) -> Option<&GameRecord> {
+        self.history
+            .iter()
+            .find(|r| r.result == GameResult::Win && r.daily == Some(day))
+    }
+
     /// Get recent games
     pub fn recent_games(&self, limit: usize) -> &[GameRecord] {
         let end = limit.min(self.history.len());
@@ -712,8 +979,29 @@ impl StatsManager {
         false
     }
 
+    /// Record a trainer answer for a technique
+    pub fn record_trainer_answer(&mut self, technique: &str, correct: bool) {
+        let entry = self
+            .player
+            .trainer
+            .entry(technique.to_string())
+            .or_default();
+        entry.attempts += 1;
+        if correct {
+            entry.correct += 1;
+        }
+        self.save();
+    }
+
+    /// Add a won game's technique profile
+    pub fn record_play_style(&mut self, profile: &sudoku_glossary::Profile) {
+        self.player.play_style.add(profile);
+        self.save();
+    }
+
     /// Unlock via Konami code (called from app)
     pub fn unlock_via_konami(&mut self) {
+        self.player.konami_entered = true;
         if !self.player.secret_unlocked {
             self.player.secret_unlocked = true;
             self.player.unlock_reason = Some("Konami Code: ↑↑↓↓←→←→BA".to_string());
@@ -739,11 +1027,69 @@ impl StatsManager {
         }
     }
 
+    /// Unlock achievements whose progress reached the target, returning the
+    /// ones unlocked just now
+    pub fn check_achievements(&mut self) -> Vec<&'static Achievement> {
+        let unlocked: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|a| !self.player.achievements.iter().any(|id| id == a.id))
+            .filter(|a| achievements::progress(a, self) >= a.target)
+            .collect();
+        if !unlocked.is_empty() {
+            self.player
+                .achievements
+                .extend(unlocked.iter().map(|a| a.id.to_string()));
+            self.save();
+        }
+        unlocked
+    }
+
+    /// Whether an achievement has been unlocked
+    pub fn has_achievement(&self, id: &str) -> bool {
+        self.player.achievements.iter().any(|a| a == id)
+    }
+
     // ==================== Remote Leaderboard Methods ====================
 
-    /// Submit a score to the remote leaderboard
+    /// Use the remote leaderboard at `config`, keeping local storage as fallback
+    pub fn use_remote_leaderboard(&mut self, config: RemoteConfig) {
+        let manager = LeaderboardManager::new(Arc::new(RemoteLeaderboard::new(config)))
+            .with_fallback(Arc::new(LocalLeaderboard::new()));
+        self.remote_leaderboard = Some(Arc::new(manager));
+    }
+
+    /// Whether wins go to (and rankings come from) a remote server
+    pub fn online_enabled(&self) -> bool {
+        let status = self.leaderboard_status();
+        cfg!(feature = "online") && (status.backend_name != "Local" || status.using_fallback)
+    }
+
+    /// Fetch global rankings on a background thread; the result arrives on
+    /// the returned channel
+    pub fn fetch_rankings(
+        &self,
+        difficulty: Difficulty,
+        limit: usize,
+    ) -> mpsc::Receiver<LeaderboardResult<Vec<leaderboard::LeaderboardEntry>>> {
+        let (tx, rx) = mpsc::channel();
+        match self.remote_leaderboard.clone() {
+            Some(manager) => {
+                std::thread::spawn(move || {
+                    let _ = tx.send(manager.get_leaderboard(Some(difficulty), limit));
+                });
+            }
+            None => {
+                let _ = tx.send(Err(leaderboard::LeaderboardError::NetworkError(
+                    "no leaderboard configured".to_string(),
+                )));
+            }
+        }
+        rx
+    }
+
+    /// Submit a score to the remote leaderboard on a background thread
     pub fn submit_to_remote(&self, record: &GameRecord) {
-        if let Some(ref manager) = self.remote_leaderboard {
+        if let Some(manager) = self.remote_leaderboard.clone() {
             if let Some(score) = record.score() {
                 let entry = leaderboard::LeaderboardEntry {
                     player_name: self.player.player_name.clone(),
@@ -756,10 +1102,14 @@ impl StatsManager {
                     puzzle_hash: record.puzzle_hash.clone(),
                     rank: None,
                     verified: record.verified,
+                    timer: record.timer,
+                    mode: record.mode,
                 };
 
-                // Submit in background (ignore errors for now)
-                let _ = manager.submit_score(entry);
+                // Failures land in the local fallback
+                std::thread::spawn(move || {
+                    let _ = manager.submit_score(entry);
+                });
             }
         }
     }
@@ -793,6 +1143,8 @@ impl StatsManager {
                 puzzle_hash: e.puzzle_hash.clone(),
                 rank: Some(i + 1),
                 verified: true,
+                timer: e.timer,
+                mode: e.mode,
             })
             .collect()
     }
@@ -830,6 +1182,75 @@ impl StatsManager {
     }
 }
 
+/// Current and best run of consecutive day numbers. The current run
+/// survives until a full day passes without an entry.
+fn streak(mut days: Vec<u64>) -> (u32, u32) {
+    days.sort_unstable();
+    days.dedup();
+
+    let mut best = 0;
+    let mut run = 0;
+    let mut prev: Option<u64> = None;
+    for &day in &days {
+        run = if prev.is_some_and(|p| p + 1 == day) {
+            run + 1
+        } else {
+            1
+        };
+        best = best.max(run);
+        prev = Some(day);
+    }
+
+    let current = match prev {
+        Some(last) if last + 1 >= sudoku_daily::today() => run,
+        _ => 0,
+    };
+    (current, best)
+}
+
+/// Count values into `buckets` equal-width bins between their min and max
+pub fn histogram(values: &[u64], buckets: usize) -> Vec<usize> {
+    let mut counts = vec![0; buckets];
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return counts;
+    };
+    if buckets == 0 {
+        return counts;
+    }
+    let span = (max - min).max(1);
+    for &v in values {
+        let bucket = ((v - min) as usize * buckets / span as usize).min(buckets - 1);
+        counts[bucket] += 1;
+    }
+    counts
+}
+
+/// Distinct techniques the hint engine needs to solve a puzzle
+fn techniques_needed(puzzle: &str) -> Vec<String> {
+    const MAX_STEPS: usize = 400;
+
+    let Some(mut grid) = Grid::from_string(puzzle) else {
+        return Vec::new();
+    };
+    grid.recalculate_candidates();
+    let solver = Solver::new();
+    let mut names: Vec<String> = Vec::new();
+    for _ in 0..MAX_STEPS {
+        let Some(hint) = solver.get_hint(&grid) else {
+            break;
+        };
+        let name = hint.technique.to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+        // No progress; stop rather than loop on the same finding
+        if !sudoku_session::apply_hint(&mut grid, &hint) {
+            break;
+        }
+    }
+    names
+}
+
 /// Format seconds as MM:SS or HH:MM:SS
 pub fn format_time(secs: u64) -> String {
     if secs >= 3600 {
@@ -843,3 +1264,92 @@ pub fn format_time(secs: u64) -> String {
         format!("{:02}:{:02}", mins, secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    #[test]
+    fn test_solve_time_histogram() {
+        assert_eq!(histogram(&[10, 20, 30, 40], 2), [2, 2]);
+        assert_eq!(histogram(&[5, 5], 3), [2, 0, 0]);
+        assert_eq!(histogram(&[], 3), [0, 0, 0]);
+        assert!(histogram(&[1, 2], 0).is_empty());
+    }
+
+    #[test]
+    fn test_streak() {
+        let today = sudoku_daily::today();
+        assert_eq!(
+            streak(vec![today - 10, today - 9, today - 1, today]),
+            (2, 2)
+        );
+        // Yesterday still counts; a full day without a win ends the run
+        assert_eq!(streak(vec![today - 1, today - 2, today - 2]), (2, 2));
+        assert_eq!(streak(vec![today - 5, today - 4, today - 3]), (0, 3));
+        assert_eq!(streak(Vec::new()), (0, 0));
+    }
+
+    #[test]
+    fn test_per_difficulty_averages() {
+        let stats = DifficultyStats {
+            total_games: 4,
+            wins: 2,
+            total_time_secs: 300,
+            total_hints: 3,
+            total_mistakes: 2,
+            ..Default::default()
+        };
+        assert_eq!(stats.avg_time_secs(), Some(150));
+        assert_eq!(stats.avg_hints(), 0.75);
+        assert_eq!(stats.avg_mistakes(), 0.5);
+        assert_eq!(DifficultyStats::default().avg_hints(), 0.0);
+    }
+
+    #[test]
+    fn test_techniques_needed() {
+        let solution = solution(Tier::Singles);
+        let names = techniques_needed(&format!("...{}", &solution[3..]));
+        assert!(!names.is_empty());
+        let mut distinct = names.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), names.len());
+        assert!(techniques_needed("not a puzzle").is_empty());
+    }
+
+    #[test]
+    fn test_export_json() {
+        let mut stats = StatsManager::new("Ada");
+        // Set directly, since the record_* methods save to the stats file
+        stats.player.trainer.insert(
+            "X-Wing".to_string(),
+            TrainerStats {
+                attempts: 2,
+                correct: 1,
+            },
+        );
+        let export: serde_json::Value = serde_json::from_str(&stats.export_json()).unwrap();
+        assert_eq!(export["format"], "sudoku-stats");
+        assert_eq!(export["version"], EXPORT_VERSION);
+        assert!(export["exported_at"].as_u64().unwrap() > 0);
+        assert!(export["history"].as_array().unwrap().is_empty());
+        assert!(export["leaderboard"].as_array().unwrap().is_empty());
+
+        // Fields docs/stats-export.md promises for the player
+        let player = &export["player"];
+        assert_eq!(player["player_name"], "Ada");
+        for field in [
+            "total_games",
+            "current_streak",
+            "by_difficulty",
+            "technique_exposure",
+            "achievements",
+        ] {
+            assert!(!player[field].is_null(), "missing {}", field);
+        }
+        assert_eq!(player["trainer"]["X-Wing"]["attempts"], 2);
+        assert_eq!(player["trainer"]["X-Wing"]["correct"], 1);
+    }
+}
@@ -9,6 +9,8 @@ use sudoku_core::canonical_puzzle_hash_str;
 
 const API_ENDPOINT: &str = "https://ukodus.now/api/v1/results";
 const TOKEN_ENDPOINT: &str = "https://ukodus.now/api/v1/token";
+/// Anonymous gameplay events, sent only with `analytics = true` in the config
+pub const EVENTS_ENDPOINT: &str = "https://ukodus.now/api/v1/events";
 
 struct CachedToken {
     token: String,
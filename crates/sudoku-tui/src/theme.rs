@@ -1,4 +1,15 @@
 use crossterm::style::Color;
+use std::collections::HashMap;
+
+/// Built-in themes as (config name, menu label)
+pub const PRESETS: [(&str, &str); 6] = [
+    ("dark", "Dark"),
+    ("light", "Light"),
+    ("high-contrast", "High Contrast"),
+    ("solarized", "Solarized"),
+    ("gruvbox", "Gruvbox"),
+    ("monochrome", "Monochrome"),
+];
 
 /// Color theme for the TUI
 #[derive(Debug, Clone)]
@@ -38,6 +49,48 @@ impl Default for Theme {
 }
 
 impl Theme {
+    /// Look up a built-in theme by name (see `PRESETS`)
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "highcontrast" => Some(Self::high_contrast()),
+            "solarized" => Some(Self::solarized()),
+            "gruvbox" => Some(Self::gruvbox()),
+            "monochrome" | "mono" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Build a user-defined theme: start from `base` (default "dark") and
+    /// override elements by field name, e.g. `given = "#ffcc00"`
+    pub fn custom(base: Option<&str>, colors: &HashMap<String, String>) -> Result<Self, String> {
+        let base = base.unwrap_or("dark");
+        let mut theme =
+            Self::by_name(base).ok_or_else(|| format!("unknown base theme \"{}\"", base))?;
+        for (element, value) in colors {
+            let color = parse_color(value).ok_or_else(|| format!("unknown color \"{}\"", value))?;
+            let slot = match element.as_str() {
+                "bg" => &mut theme.bg,
+                "fg" => &mut theme.fg,
+                "border" => &mut theme.border,
+                "box_border" => &mut theme.box_border,
+                "given" => &mut theme.given,
+                "filled" => &mut theme.filled,
+                "candidate" => &mut theme.candidate,
+                "selected_bg" => &mut theme.selected_bg,
+                "highlight_bg" => &mut theme.highlight_bg,
+                "error" => &mut theme.error,
+                "success" => &mut theme.success,
+                "info" => &mut theme.info,
+                "key" => &mut theme.key,
+                _ => return Err(format!("unknown theme element \"{}\"", element)),
+            };
+            *slot = color;
+        }
+        Ok(theme)
+    }
+
     /// Dark theme (default) - improved contrast
     pub fn dark() -> Self {
         Self {
@@ -202,4 +255,122 @@ impl Theme {
             key: Color::Yellow,
         }
     }
+
+    /// Solarized dark
+    pub fn solarized() -> Self {
+        Self {
+            bg: hex(0x002b36),
+            fg: hex(0x839496),
+            border: hex(0x586e75),
+            box_border: hex(0x93a1a1),
+            given: hex(0xfdf6e3),
+            filled: hex(0x268bd2),
+            candidate: hex(0x657b83),
+            selected_bg: hex(0x145060),
+            highlight_bg: hex(0x073642),
+            error: hex(0xdc322f),
+            success: hex(0x859900),
+            info: hex(0x93a1a1),
+            key: hex(0xb58900),
+        }
+    }
+
+    /// Gruvbox dark
+    pub fn gruvbox() -> Self {
+        Self {
+            bg: hex(0x282828),
+            fg: hex(0xebdbb2),
+            border: hex(0x504945),
+            box_border: hex(0xa89984),
+            given: hex(0xfbf1c7),
+            filled: hex(0x83a598),
+            candidate: hex(0x928374),
+            selected_bg: hex(0x665c54),
+            highlight_bg: hex(0x3c3836),
+            error: hex(0xfb4934),
+            success: hex(0xb8bb26),
+            info: hex(0xbdae93),
+            key: hex(0xfabd2f),
+        }
+    }
+
+    /// Greys only, for terminals without color
+    pub fn monochrome() -> Self {
+        Self {
+            bg: Color::Black,
+            fg: Color::White,
+            border: Color::DarkGrey,
+            box_border: Color::Grey,
+            given: Color::White,
+            filled: hex(0xb0b0b0),
+            candidate: hex(0x707070),
+            selected_bg: hex(0x505050),
+            highlight_bg: hex(0x1c1c1c),
+            error: Color::White,
+            success: Color::White,
+            info: Color::Grey,
+            key: Color::White,
+        }
+    }
+}
+
+fn hex(rgb: u32) -> Color {
+    Color::Rgb {
+        r: (rgb >> 16) as u8,
+        g: (rgb >> 8) as u8,
+        b: rgb as u8,
+    }
+}
+
+/// Parse "#rrggbb" or a terminal color name ("red", "dark_grey", ...)
+pub fn parse_color(text: &str) -> Option<Color> {
+    let text = text.trim();
+    if let Some(digits) = text.strip_prefix('#') {
+        if digits.len() != 6 {
+            return None;
+        }
+        return u32::from_str_radix(digits, 16).ok().map(hex);
+    }
+    let color = match text.to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+        "black" => Color::Black,
+        "white" => Color::White,
+        "grey" | "gray" => Color::Grey,
+        "dark_grey" | "dark_gray" => Color::DarkGrey,
+        "red" => Color::Red,
+        "dark_red" => Color::DarkRed,
+        "green" => Color::Green,
+        "dark_green" => Color::DarkGreen,
+        "yellow" => Color::Yellow,
+        "dark_yellow" => Color::DarkYellow,
+        "blue" => Color::Blue,
+        "dark_blue" => Color::DarkBlue,
+        "magenta" => Color::Magenta,
+        "dark_magenta" => Color::DarkMagenta,
+        "cyan" => Color::Cyan,
+        "dark_cyan" => Color::DarkCyan,
+        "reset" | "default" => Color::Reset,
+        _ => return None,
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_theme() {
+        assert_eq!(parse_color("#ff8000"), Some(hex(0xff8000)));
+        assert_eq!(parse_color("Dark Grey"), Some(Color::DarkGrey));
+        assert_eq!(parse_color("#fff"), None);
+
+        let colors = HashMap::from([("given".to_string(), "yellow".to_string())]);
+        let theme = Theme::custom(Some("gruvbox"), &colors).unwrap();
+        assert_eq!(theme.given, Color::Yellow);
+        assert_eq!(theme.bg, Theme::gruvbox().bg);
+
+        let bad = HashMap::from([("grid".to_string(), "red".to_string())]);
+        assert!(Theme::custom(None, &bad).is_err());
+        assert!(Theme::custom(Some("neon"), &HashMap::new()).is_err());
+    }
 }
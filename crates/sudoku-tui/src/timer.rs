@@ -0,0 +1,133 @@
+//! Timer modes: the standard clock, a countdown that ends the game when it
+//! runs out, and a speedrun clock that records split times as boxes and
+//! digits are completed.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Default countdown length
+pub const DEFAULT_COUNTDOWN_SECS: u64 = 15 * 60;
+
+/// How the game clock behaves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimerMode {
+    /// Count up, no limit
+    #[default]
+    Standard,
+    /// Count down from `limit_secs`; the game is lost at zero
+    Countdown { limit_secs: u64 },
+    /// Count up and record splits
+    Speedrun,
+}
+
+impl TimerMode {
+    /// Parse a mode name from the config ("standard", "countdown", "speedrun")
+    pub fn from_name(name: &str, countdown_secs: u64) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "standard" => Some(Self::Standard),
+            "countdown" => Some(Self::Countdown {
+                limit_secs: countdown_secs,
+            }),
+            "speedrun" => Some(Self::Speedrun),
+            _ => None,
+        }
+    }
+
+    /// The next mode in the menu's cycle
+    pub fn next(self, countdown_secs: u64) -> Self {
+        match self {
+            Self::Standard => Self::Countdown {
+                limit_secs: countdown_secs,
+            },
+            Self::Countdown { .. } => Self::Speedrun,
+            Self::Speedrun => Self::Standard,
+        }
+    }
+
+    /// Menu label
+    pub fn label(self) -> String {
+        match self {
+            Self::Standard => "Standard".to_string(),
+            Self::Countdown { limit_secs } => {
+                format!("Countdown {}:{:02}", limit_secs / 60, limit_secs % 60)
+            }
+            Self::Speedrun => "Speedrun".to_string(),
+        }
+    }
+
+    /// Short label for leaderboard rows
+    pub fn short_label(self) -> &'static str {
+        match self {
+            Self::Standard => "",
+            Self::Countdown { .. } => "Count",
+            Self::Speedrun => "Speed",
+        }
+    }
+}
+
+/// Speedrun split times, in milliseconds of game time
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Splits {
+    /// When each box (row-major) was first completed
+    pub boxes: [Option<u64>; 9],
+    /// When the ninth copy of each digit was first placed
+    pub digits: [Option<u64>; 9],
+}
+
+impl Splits {
+    /// Record a split for every newly completed box or digit
+    pub fn update(&mut self, boxes: [bool; 9], digits: [bool; 9], elapsed: Duration) {
+        let ms = elapsed.as_millis() as u64;
+        for (split, done) in self
+            .boxes
+            .iter_mut()
+            .zip(boxes)
+            .chain(self.digits.iter_mut().zip(digits))
+        {
+            if done && split.is_none() {
+                *split = Some(ms);
+            }
+        }
+    }
+
+    /// Most recent split as (label, time), e.g. ("Box 5", 83_000)
+    pub fn latest(&self) -> Option<(String, u64)> {
+        let boxes = self
+            .boxes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| t.map(|t| (format!("Box {}", i + 1), t)));
+        let digits = self
+            .digits
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| t.map(|t| (format!("All {}s", i + 1), t)));
+        boxes.chain(digits).max_by_key(|&(_, t)| t)
+    }
+}
+
+/// Format a split as m:ss.t
+pub fn format_split(ms: u64) -> String {
+    format!("{}:{:02}.{}", ms / 60_000, ms / 1000 % 60, ms / 100 % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits_keep_first_time() {
+        let mut splits = Splits::default();
+        let mut boxes = [false; 9];
+        boxes[4] = true;
+        splits.update(boxes, [false; 9], Duration::from_millis(83_400));
+        assert_eq!(splits.boxes[4], Some(83_400));
+
+        let mut digits = [false; 9];
+        digits[0] = true;
+        splits.update(boxes, digits, Duration::from_millis(90_000));
+        assert_eq!(splits.boxes[4], Some(83_400));
+        assert_eq!(splits.latest(), Some(("All 1s".to_string(), 90_000)));
+        assert_eq!(format_split(83_400), "1:23.4");
+    }
+}
@@ -0,0 +1,142 @@
+//! Technique trainer: find the move a chosen technique makes in real positions
+
+use sudoku_core::{Difficulty, Grid, Hint, HintType, Position, Solver};
+use sudoku_session::find_technique;
+
+/// Techniques offered by the trainer, with the difficulty used to find them
+pub const TECHNIQUES: [(&str, Difficulty); 10] = [
+    ("Naked Single", Difficulty::Easy),
+    ("Hidden Single", Difficulty::Easy),
+    ("Naked Pair", Difficulty::Medium),
+    ("Pointing Pair", Difficulty::Medium),
+    ("Hidden Pair", Difficulty::Intermediate),
+    ("Box/Line Reduction", Difficulty::Intermediate),
+    ("Naked Triple", Difficulty::Hard),
+    ("X-Wing", Difficulty::Hard),
+    ("XY-Wing", Difficulty::Expert),
+    ("Swordfish", Difficulty::Expert),
+];
+
+/// A trainer session for one technique
+pub struct Trainer {
+    /// Index into `TECHNIQUES`
+    technique: usize,
+    /// Position (with full candidates) where the technique applies
+    grid: Grid,
+    /// The solver's finding for that position
+    hint: Hint,
+    /// Cell the player is looking at
+    pub cursor: Position,
+    /// The player's answer: a cell and the digit placed or eliminated there
+    answer: Option<(Position, u8)>,
+    /// Positions answered correctly this session
+    solved: u32,
+    /// Positions answered this session
+    attempts: u32,
+}
+
+impl Trainer {
+    /// Start a session, or None if no position using the technique turned up
+    pub fn new(technique: usize) -> Option<Self> {
+        let (grid, hint) = find_position(technique)?;
+        Some(Self {
+            technique,
+            grid,
+            hint,
+            cursor: Position::new(4, 4),
+            answer: None,
+            solved: 0,
+            attempts: 0,
+        })
+    }
+
+    /// Move on to a fresh position. Returns false if none was found.
+    pub fn next_position(&mut self) -> bool {
+        let Some((grid, hint)) = find_position(self.technique) else {
+            return false;
+        };
+        self.grid = grid;
+        self.hint = hint;
+        self.answer = None;
+        true
+    }
+
+    /// Answer with `digit` at the cursor. Returns whether it matches the
+    /// finding, or None if this position was already answered.
+    pub fn answer(&mut self, digit: u8) -> Option<bool> {
+        if self.answer.is_some() {
+            return None;
+        }
+        self.answer = Some((self.cursor, digit));
+        let correct = self.is_correct(self.cursor, digit);
+        self.attempts += 1;
+        if correct {
+            self.solved += 1;
+        }
+        Some(correct)
+    }
+
+    /// Whether placing/eliminating `digit` at `pos` is the finding's move
+    pub fn is_correct(&self, pos: Position, digit: u8) -> bool {
+        match &self.hint.hint_type {
+            HintType::SetValue { pos: p, value } => *p == pos && *value == digit,
+            HintType::EliminateCandidates { pos: p, values } => {
+                *p == pos && values.contains(&digit)
+            }
+        }
+    }
+
+    /// Cell the finding acts on
+    pub fn target(&self) -> Position {
+        match self.hint.hint_type {
+            HintType::SetValue { pos, .. } | HintType::EliminateCandidates { pos, .. } => pos,
+        }
+    }
+
+    /// Whether the finding places a digit (rather than eliminating candidates)
+    pub fn is_placement(&self) -> bool {
+        matches!(self.hint.hint_type, HintType::SetValue { .. })
+    }
+
+    pub fn technique(&self) -> &'static str {
+        TECHNIQUES[self.technique].0
+    }
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+    pub fn hint(&self) -> &Hint {
+        &self.hint
+    }
+    pub fn answered(&self) -> Option<(Position, u8)> {
+        self.answer
+    }
+    pub fn solved(&self) -> u32 {
+        self.solved
+    }
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+/// Techniques the solver can apply to `grid` right now, easiest first, with
+/// how many moves each one finds. Candidates are recomputed so the player's
+/// notes don't matter.
+pub fn available_techniques(grid: &Grid) -> Vec<(String, usize)> {
+    let mut grid = grid.deep_clone();
+    grid.recalculate_candidates();
+    let mut found: Vec<(String, usize)> = Vec::new();
+    for hint in Solver::new().find_all(&grid) {
+        let name = hint.technique.to_string();
+        match found.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => *count += 1,
+            None => found.push((name, 1)),
+        }
+    }
+    found
+}
+
+/// A position where the solver's next move is the technique
+fn find_position(technique: usize) -> Option<(Grid, Hint)> {
+    let (name, difficulty) = TECHNIQUES[technique];
+    find_technique(name, difficulty)
+}
@@ -0,0 +1,592 @@
+//! Layout and double-buffered drawing.
+//!
+//! Screens draw with crossterm commands as before, but into a [`Buffer`]
+//! instead of the terminal. The buffer keeps the cells those commands would
+//! produce, and [`Terminal::draw`] compares each frame with the last one and
+//! writes only the cells that changed. Clearing and redrawing the whole screen
+//! every tick therefore no longer flickers, and an idle screen writes nothing.
+//!
+//! Cells keep their colors and the bold, dim, italic, underline and reverse
+//! attributes. Other SGR attributes (blink, hidden, strikethrough) are
+//! dropped, so screens shouldn't rely on them.
+//!
+//! [`Layout`] splits an area into panels by [`Constraint`]s, so screens can
+//! describe where things go instead of computing cursor positions by hand.
+
+use crossterm::cursor::MoveTo;
+use std::io::{self, Write};
+
+/// A rectangle of terminal cells
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// This area less `horizontal` cells on the left and right and
+    /// `vertical` rows at the top and bottom
+    pub fn inner(self, horizontal: u16, vertical: u16) -> Self {
+        Self {
+            x: self.x + horizontal.min(self.width),
+            y: self.y + vertical.min(self.height),
+            width: self.width.saturating_sub(horizontal * 2),
+            height: self.height.saturating_sub(vertical * 2),
+        }
+    }
+
+    /// Row `dy` of this area, one line high
+    pub fn row(self, dy: u16) -> Self {
+        Self {
+            y: self.y + dy,
+            height: 1,
+            ..self
+        }
+    }
+
+    /// Command moving the cursor to column `dx` of row `dy` in this area
+    pub fn at(self, dx: u16, dy: u16) -> MoveTo {
+        MoveTo(self.x + dx, self.y + dy)
+    }
+
+    /// A `width` x `height` area centered in this one (clipped to it)
+    pub fn centered(self, width: u16, height: u16) -> Self {
+        let width = width.min(self.width);
+        let height = height.min(self.height);
+        Self {
+            x: self.x + (self.width - width) / 2,
+            y: self.y + (self.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+/// Size of one part of a layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// Exactly this many cells (less if the area runs out)
+    Length(u16),
+    /// At least this many cells, sharing whatever is left over
+    Min(u16),
+}
+
+/// Splits an area into consecutive parts along one direction
+#[derive(Debug, Clone)]
+pub struct Layout {
+    vertical: bool,
+    constraints: Vec<Constraint>,
+}
+
+impl Layout {
+    /// Parts side by side, left to right
+    pub fn horizontal(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self {
+            vertical: false,
+            constraints: constraints.into(),
+        }
+    }
+
+    /// Parts stacked top to bottom
+    pub fn vertical(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self {
+            vertical: true,
+            constraints: constraints.into(),
+        }
+    }
+
+    /// One rectangle per constraint. Fixed sizes are given out first; space
+    /// left over is shared evenly by the `Min` parts.
+    pub fn split(&self, area: Rect) -> Vec<Rect> {
+        let total = if self.vertical {
+            area.height
+        } else {
+            area.width
+        };
+
+        let mut sizes: Vec<u16> = self
+            .constraints
+            .iter()
+            .map(|c| match *c {
+                Constraint::Length(n) | Constraint::Min(n) => n,
+            })
+            .collect();
+
+        let used: u16 = sizes.iter().fold(0, |a, &b| a.saturating_add(b));
+        let flexible: Vec<usize> = (0..sizes.len())
+            .filter(|&i| matches!(self.constraints[i], Constraint::Min(_)))
+            .collect();
+        if used < total && !flexible.is_empty() {
+            let spare = total - used;
+            let share = spare / flexible.len() as u16;
+            for (n, &i) in flexible.iter().enumerate() {
+                // The first parts take the remainder
+                let extra = u16::from((n as u16) < spare % flexible.len() as u16);
+                sizes[i] += share + extra;
+            }
+        }
+
+        let mut offset = 0u16;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let size = size.min(total.saturating_sub(offset));
+                let rect = if self.vertical {
+                    Rect::new(area.x, area.y + offset, area.width, size)
+                } else {
+                    Rect::new(area.x + offset, area.y, size, area.height)
+                };
+                offset += size;
+                rect
+            })
+            .collect()
+    }
+}
+
+/// SGR parameters that turn each kept attribute on and off, by bit in
+/// [`Cell::attributes`]: bold, dim, italic, underline, reverse
+const ATTRIBUTES: [(&str, &str); 5] = [
+    ("1", "22"),
+    ("2", "22"),
+    ("3", "23"),
+    ("4", "24"),
+    ("7", "27"),
+];
+
+/// One terminal cell: its text, the SGR parameters of its colors and its
+/// attributes
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cell {
+    /// Empty for the right half of a wide character
+    symbol: String,
+    fg: String,
+    bg: String,
+    /// Bits of [`ATTRIBUTES`]
+    attributes: u8,
+}
+
+impl Cell {
+    fn blank(bg: &str) -> Self {
+        Self {
+            symbol: " ".to_string(),
+            fg: String::new(),
+            bg: bg.to_string(),
+            attributes: 0,
+        }
+    }
+}
+
+/// An off-screen frame. Writing crossterm commands to it updates its cells
+/// the way the terminal would.
+pub struct Buffer {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+    cursor: (u16, u16),
+    fg: String,
+    bg: String,
+    attributes: u8,
+    cursor_visible: bool,
+    /// Bytes of an escape sequence or character split across writes
+    pending: Vec<u8>,
+}
+
+impl Buffer {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::blank(""); width as usize * height as usize],
+            cursor: (0, 0),
+            fg: String::new(),
+            bg: String::new(),
+            attributes: 0,
+            cursor_visible: true,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The whole frame
+    pub fn area(&self) -> Rect {
+        Rect::new(0, 0, self.width, self.height)
+    }
+
+    fn cell_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell> {
+        if x < self.width && y < self.height {
+            self.cells
+                .get_mut(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn put(&mut self, c: char) {
+        if c == '\n' {
+            self.cursor = (0, self.cursor.1 + 1);
+            return;
+        }
+        let width = char_width(c);
+        if width == 0 {
+            return;
+        }
+        let (x, y) = self.cursor;
+        let (fg, bg, attributes) = (self.fg.clone(), self.bg.clone(), self.attributes);
+        if let Some(cell) = self.cell_mut(x, y) {
+            *cell = Cell {
+                symbol: c.to_string(),
+                fg: fg.clone(),
+                bg: bg.clone(),
+                attributes,
+            };
+        }
+        if width == 2 {
+            if let Some(cell) = self.cell_mut(x + 1, y) {
+                *cell = Cell {
+                    symbol: String::new(),
+                    fg,
+                    bg,
+                    attributes,
+                };
+            }
+        }
+        self.cursor.0 = x.saturating_add(width);
+    }
+
+    /// Apply one CSI sequence (without the leading ESC [)
+    fn control(&mut self, params: &str, command: char) {
+        let numbers = || params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+        match command {
+            'H' => {
+                let mut n = numbers();
+                let row = n.next().unwrap_or(1).max(1);
+                let col = n.next().unwrap_or(1).max(1);
+                self.cursor = (col - 1, row - 1);
+            }
+            'J' if params == "2" || params == "3" => {
+                let blank = Cell::blank(&self.bg);
+                self.cells.fill(blank);
+            }
+            'K' => {
+                let blank = Cell::blank(&self.bg);
+                let (x, y) = self.cursor;
+                let from = if params == "2" { 0 } else { x };
+                for col in from..self.width {
+                    if let Some(cell) = self.cell_mut(col, y) {
+                        *cell = blank.clone();
+                    }
+                }
+            }
+            'm' => self.style(params),
+            'l' if params == "?25" => self.cursor_visible = false,
+            'h' if params == "?25" => self.cursor_visible = true,
+            _ => {}
+        }
+    }
+
+    /// Apply SGR color and attribute parameters
+    fn style(&mut self, params: &str) {
+        let parts: Vec<&str> = params.split(';').collect();
+        let mut i = 0;
+        while i < parts.len() {
+            match parts[i] {
+                "" | "0" => {
+                    self.fg.clear();
+                    self.bg.clear();
+                    self.attributes = 0;
+                }
+                "39" => self.fg.clear(),
+                "49" => self.bg.clear(),
+                target @ ("38" | "48") => {
+                    let len = match parts.get(i + 1) {
+                        Some(&"5") => 2,
+                        Some(&"2") => 4,
+                        _ => 0,
+                    };
+                    let end = (i + 1 + len).min(parts.len());
+                    let color = parts[i..end].join(";");
+                    if target == "38" {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                    i = end;
+                    continue;
+                }
+                param => {
+                    for (bit, (on, off)) in ATTRIBUTES.iter().enumerate() {
+                        if param == *on {
+                            self.attributes |= 1 << bit;
+                        } else if param == *off {
+                            self.attributes &= !(1 << bit);
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    /// Interpret `pending`, keeping an incomplete sequence at the end for the
+    /// next write
+    fn process(&mut self) {
+        let bytes = std::mem::take(&mut self.pending);
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == 0x1b {
+                if i + 1 >= bytes.len() {
+                    break;
+                }
+                if bytes[i + 1] != b'[' {
+                    i += 2;
+                    continue;
+                }
+                let Some(end) = bytes[i + 2..]
+                    .iter()
+                    .position(|b| (0x40..=0x7e).contains(b))
+                else {
+                    break;
+                };
+                let params = String::from_utf8_lossy(&bytes[i + 2..i + 2 + end]).into_owned();
+                self.control(&params, bytes[i + 2 + end] as char);
+                i += end + 3;
+                continue;
+            }
+
+            let len = match bytes[i] {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            if i + len > bytes.len() {
+                break;
+            }
+            let text = String::from_utf8_lossy(&bytes[i..i + len]).into_owned();
+            for c in text.chars() {
+                self.put(c);
+            }
+            i += len;
+        }
+        self.pending = bytes[i..].to_vec();
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        self.process();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Columns a character takes up in the terminal. Covers the wide ranges the
+/// screens use (CJK, emoji); everything else is one column.
+fn char_width(c: char) -> u16 {
+    match c as u32 {
+        0x200b..=0x200f | 0xfe00..=0xfe0f | 0x0300..=0x036f => 0,
+        0x1100..=0x115f
+        | 0x231a..=0x231b
+        | 0x23e9..=0x23f3
+        | 0x2e80..=0xa4cf
+        | 0xac00..=0xd7a3
+        | 0xf900..=0xfaff
+        | 0xfe30..=0xfe4f
+        | 0xff00..=0xff60
+        | 0xffe0..=0xffe6
+        | 0x1f300..=0x1f64f
+        | 0x1f680..=0x1f6ff
+        | 0x1f900..=0x1f9ff => 2,
+        _ => 1,
+    }
+}
+
+/// Draws frames to the real terminal, writing only what changed
+#[derive(Default)]
+pub struct Terminal {
+    previous: Option<Buffer>,
+}
+
+impl Terminal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the last frame so the next draw repaints everything (after a
+    /// resize, or when something else wrote to the screen)
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Draw a `width` x `height` frame with `draw`, then write the cells that
+    /// differ from the previous frame to `out`
+    pub fn draw(
+        &mut self,
+        out: &mut impl Write,
+        width: u16,
+        height: u16,
+        draw: impl FnOnce(&mut Buffer) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut frame = Buffer::new(width, height);
+        draw(&mut frame)?;
+
+        let previous = self
+            .previous
+            .take()
+            .filter(|p| p.width == width && p.height == height);
+
+        let mut bytes = Vec::new();
+        // Hide the cursor while updating, whatever the frame wants at the end
+        bytes.extend_from_slice(b"\x1b[?25l");
+        if previous.is_none() {
+            bytes.extend_from_slice(b"\x1b[0m\x1b[2J");
+        }
+
+        let mut style: Option<(&str, &str, u8)> = None;
+        let mut at: Option<(u16, u16)> = None;
+        for y in 0..height {
+            for x in 0..width {
+                let index = y as usize * width as usize + x as usize;
+                let cell = &frame.cells[index];
+                let unchanged = previous.as_ref().is_some_and(|p| p.cells[index] == *cell);
+                if unchanged || cell.symbol.is_empty() {
+                    continue;
+                }
+                if at != Some((x, y)) {
+                    bytes.extend_from_slice(format!("\x1b[{};{}H", y + 1, x + 1).as_bytes());
+                }
+                if style != Some((&cell.fg, &cell.bg, cell.attributes)) {
+                    bytes.extend_from_slice(b"\x1b[0m");
+                    for (bit, (on, _)) in ATTRIBUTES.iter().enumerate() {
+                        if cell.attributes & (1 << bit) != 0 {
+                            bytes.extend_from_slice(format!("\x1b[{}m", on).as_bytes());
+                        }
+                    }
+                    for color in [&cell.fg, &cell.bg] {
+                        if !color.is_empty() {
+                            bytes.extend_from_slice(format!("\x1b[{}m", color).as_bytes());
+                        }
+                    }
+                    style = Some((&cell.fg, &cell.bg, cell.attributes));
+                }
+                bytes.extend_from_slice(cell.symbol.as_bytes());
+                let advance = cell.symbol.chars().map(char_width).sum::<u16>();
+                at = Some((x + advance, y));
+            }
+        }
+
+        let (x, y) = frame.cursor;
+        bytes.extend_from_slice(format!("\x1b[{};{}H", y + 1, x + 1).as_bytes());
+        if frame.cursor_visible {
+            bytes.extend_from_slice(b"\x1b[?25h");
+        }
+
+        out.write_all(&bytes)?;
+        out.flush()?;
+        self.previous = Some(frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::{
+        queue,
+        style::{Attribute, Color, Print, SetAttribute, SetForegroundColor},
+    };
+
+    #[test]
+    fn test_layout_split() {
+        let area = Rect::new(0, 0, 100, 20);
+        let parts = Layout::horizontal([
+            Constraint::Length(37),
+            Constraint::Min(10),
+            Constraint::Min(10),
+            Constraint::Length(25),
+        ])
+        .split(area);
+        assert_eq!(parts[0], Rect::new(0, 0, 37, 20));
+        assert_eq!(parts[1], Rect::new(37, 0, 19, 20));
+        assert_eq!(parts[2], Rect::new(56, 0, 19, 20));
+        assert_eq!(parts[3], Rect::new(75, 0, 25, 20));
+
+        // Fixed parts are cut short when the area is too small
+        let rows = Layout::vertical([Constraint::Length(15), Constraint::Length(10)])
+            .split(Rect::new(0, 5, 80, 20));
+        assert_eq!(rows[1], Rect::new(0, 20, 80, 5));
+
+        assert_eq!(area.centered(30, 10), Rect::new(35, 5, 30, 10));
+        assert_eq!(area.inner(4, 1), Rect::new(4, 1, 92, 18));
+        assert_eq!(area.inner(4, 1).row(2), Rect::new(4, 3, 92, 1));
+        assert_eq!(Rect::new(2, 3, 1, 1).inner(4, 4), Rect::new(3, 4, 0, 0));
+    }
+
+    #[test]
+    fn test_only_changed_cells_are_written() {
+        let mut terminal = Terminal::new();
+        let draw = |text: &'static str| {
+            move |frame: &mut Buffer| -> io::Result<()> {
+                queue!(
+                    frame,
+                    MoveTo(2, 1),
+                    SetForegroundColor(Color::Red),
+                    Print(text)
+                )
+            }
+        };
+
+        let mut out = Vec::new();
+        terminal.draw(&mut out, 10, 3, draw("abc")).unwrap();
+        assert!(String::from_utf8_lossy(&out).contains("abc"));
+
+        out.clear();
+        terminal.draw(&mut out, 10, 3, draw("abd")).unwrap();
+        let written = String::from_utf8_lossy(&out).into_owned();
+        assert!(written.contains("\x1b[2;5H"), "{:?}", written);
+        assert!(written.contains("md") && !written.contains('a') && !written.contains('b'));
+    }
+
+    #[test]
+    fn test_attributes_are_kept() {
+        let mut frame = Buffer::new(6, 1);
+        queue!(
+            frame,
+            SetAttribute(Attribute::Bold),
+            SetAttribute(Attribute::Reverse),
+            Print("ab"),
+            SetAttribute(Attribute::NormalIntensity),
+            Print("c"),
+            SetAttribute(Attribute::Underlined),
+            Print("d"),
+            SetAttribute(Attribute::Reset),
+            Print("e")
+        )
+        .unwrap();
+        let attributes: Vec<u8> = frame.cells.iter().map(|c| c.attributes).collect();
+        assert_eq!(attributes, [0b10001, 0b10001, 0b10000, 0b11000, 0, 0]);
+
+        let mut out = Vec::new();
+        Terminal::new()
+            .draw(&mut out, 6, 1, |f| {
+                queue!(f, SetAttribute(Attribute::Bold), Print("x"))
+            })
+            .unwrap();
+        let written = String::from_utf8_lossy(&out).into_owned();
+        assert!(written.contains("\x1b[0m\x1b[1mx"), "{:?}", written);
+    }
+}
@@ -0,0 +1,88 @@
+//! Rules beyond "each digit once per sector". Placing a digit under an
+//! [`ExtraConstraint`] takes candidates out of other cells, so candidates,
+//! the solution search, generation and every technique follow it.
+
+use std::fmt::Debug;
+
+/// A rule relating a placed digit to other cells
+pub trait ExtraConstraint: Debug + Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Candidates ruled out by `value` in `cell`, as (cell, digit), on a
+    /// board `size` cells to a side. The rule must be symmetric: if a in
+    /// one cell rules out b in another, b there rules out a in the first.
+    fn eliminations(&self, size: usize, cell: usize, value: u8) -> Vec<(usize, u8)>;
+}
+
+/// No two cells a chess knight's move apart hold the same digit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AntiKnight;
+
+impl ExtraConstraint for AntiKnight {
+    fn name(&self) -> &str {
+        "Anti-Knight"
+    }
+
+    fn eliminations(&self, size: usize, cell: usize, value: u8) -> Vec<(usize, u8)> {
+        const MOVES: [(isize, isize); 8] = [
+            (-2, -1),
+            (-2, 1),
+            (-1, -2),
+            (-1, 2),
+            (1, -2),
+            (1, 2),
+            (2, -1),
+            (2, 1),
+        ];
+        MOVES
+            .iter()
+            .filter_map(|&(dr, dc)| offset(size, cell, dr, dc))
+            .map(|other| (other, value))
+            .collect()
+    }
+}
+
+/// Orthogonally adjacent cells never hold consecutive digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonConsecutive;
+
+impl ExtraConstraint for NonConsecutive {
+    fn name(&self) -> &str {
+        "Non-Consecutive"
+    }
+
+    fn eliminations(&self, size: usize, cell: usize, value: u8) -> Vec<(usize, u8)> {
+        let neighbours = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(|(dr, dc)| offset(size, cell, dr, dc));
+        let digits = [value - 1, value + 1]
+            .into_iter()
+            .filter(|&d| d >= 1 && d as usize <= size);
+        neighbours
+            .flat_map(|other| digits.clone().map(move |d| (other, d)))
+            .collect()
+    }
+}
+
+/// The cell `dr` rows and `dc` columns from `cell`, if on the board
+fn offset(size: usize, cell: usize, dr: isize, dc: isize) -> Option<usize> {
+    let row = (cell / size).checked_add_signed(dr)?;
+    let col = (cell % size).checked_add_signed(dc)?;
+    (row < size && col < size).then_some(row * size + col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraints() {
+        let mut knight = AntiKnight.eliminations(9, 0, 5);
+        knight.sort_unstable();
+        assert_eq!(knight, [(11, 5), (19, 5)]);
+        assert_eq!(AntiKnight.eliminations(9, 40, 1).len(), 8);
+
+        assert_eq!(NonConsecutive.eliminations(9, 0, 1), [(9, 2), (1, 2)]);
+        assert_eq!(NonConsecutive.eliminations(6, 7, 3).len(), 8);
+    }
+}
@@ -0,0 +1,232 @@
+//! Dancing links: the board as an exact-cover problem, so counting and
+//! finding solutions unlinks and relinks nodes instead of cloning the
+//! board at every guess. Every cell takes one digit and every sector
+//! takes each digit once; extra constraints aren't exact covers, so
+//! boards with any use the plain search instead.
+
+use crate::puzzle::{digits, Puzzle};
+
+/// A row of the cover: (cell, digit)
+type Choice = (usize, u8);
+
+/// The links of one puzzle's open constraints and candidates
+pub(crate) struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Column header of each node; headers are their own
+    column: Vec<usize>,
+    /// Nodes left in each column, indexed by header
+    size: Vec<usize>,
+    /// Row of each node
+    choice: Vec<Choice>,
+    /// Rows chosen so far, as one node of each
+    chosen: Vec<usize>,
+}
+
+/// The root header, linked to the open columns
+const ROOT: usize = 0;
+
+impl Dlx {
+    /// Columns for the cells still empty and the (sector, digit) pairs
+    /// still missing, and a row per candidate of each empty cell
+    pub(crate) fn new(puzzle: &Puzzle) -> Self {
+        let sectors = puzzle.sectors();
+        let size = sectors.size();
+        let cells = sectors.cell_count();
+        let mut dlx = Self {
+            left: vec![0],
+            right: vec![0],
+            up: vec![0],
+            down: vec![0],
+            column: vec![0],
+            size: vec![0],
+            choice: vec![(0, 0)],
+            chosen: Vec::new(),
+        };
+
+        // Header per open constraint: cells first, then sector x digit
+        let mut header = vec![None; cells + sectors.len() * size];
+        for cell in (0..cells).filter(|&cell| puzzle.value(cell).is_none()) {
+            header[cell] = Some(dlx.add_header());
+        }
+        for (s, sector) in sectors.iter().enumerate() {
+            for digit in 1..=size as u8 {
+                if !sector
+                    .cells
+                    .iter()
+                    .any(|&cell| puzzle.value(cell) == Some(digit))
+                {
+                    header[cells + s * size + digit as usize - 1] = Some(dlx.add_header());
+                }
+            }
+        }
+
+        for cell in (0..cells).filter(|&cell| puzzle.value(cell).is_none()) {
+            for digit in digits(puzzle.candidates(cell)) {
+                let columns = std::iter::once(cell).chain(
+                    sectors
+                        .of_cell(cell)
+                        .iter()
+                        .map(|&s| cells + s * size + digit as usize - 1),
+                );
+                let columns: Vec<usize> = columns.filter_map(|c| header[c]).collect();
+                dlx.add_row(&columns, (cell, digit));
+            }
+        }
+        dlx
+    }
+
+    fn add_header(&mut self) -> usize {
+        let node = self.left.len();
+        let last = self.left[ROOT];
+        self.left.push(last);
+        self.right.push(ROOT);
+        self.right[last] = node;
+        self.left[ROOT] = node;
+        self.up.push(node);
+        self.down.push(node);
+        self.column.push(node);
+        self.size.push(0);
+        self.choice.push((0, 0));
+        node
+    }
+
+    fn add_row(&mut self, columns: &[usize], choice: Choice) {
+        let first = self.left.len();
+        for (i, &header) in columns.iter().enumerate() {
+            let node = first + i;
+            let above = self.up[header];
+            self.up.push(above);
+            self.down.push(header);
+            self.down[above] = node;
+            self.up[header] = node;
+            self.left.push(if i == 0 {
+                first + columns.len() - 1
+            } else {
+                node - 1
+            });
+            self.right.push(if i + 1 == columns.len() {
+                first
+            } else {
+                node + 1
+            });
+            self.column.push(header);
+            self.size.push(0);
+            self.size[header] += 1;
+            self.choice.push(choice);
+        }
+    }
+
+    fn cover(&mut self, header: usize) {
+        let (l, r) = (self.left[header], self.right[header]);
+        self.right[l] = r;
+        self.left[r] = l;
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                let (u, d) = (self.up[node], self.down[node]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                let (u, d) = (self.up[node], self.down[node]);
+                self.down[u] = node;
+                self.up[d] = node;
+                self.size[self.column[node]] += 1;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        let (l, r) = (self.left[header], self.right[header]);
+        self.right[l] = header;
+        self.left[r] = header;
+    }
+
+    /// Find every exact cover, handing each to `visit` as the (cell,
+    /// digit) choices to add; `visit` returns whether to go on. Returns
+    /// false once it has said stop.
+    pub(crate) fn search(&mut self, visit: &mut dyn FnMut(&[Choice]) -> bool) -> bool {
+        if self.right[ROOT] == ROOT {
+            let choices: Vec<Choice> = self.chosen.iter().map(|&n| self.choice[n]).collect();
+            return visit(&choices);
+        }
+        // The column with the fewest rows left
+        let mut header = self.right[ROOT];
+        let mut c = self.right[header];
+        while c != ROOT {
+            if self.size[c] < self.size[header] {
+                header = c;
+            }
+            c = self.right[c];
+        }
+        if self.size[header] == 0 {
+            return true;
+        }
+
+        self.cover(header);
+        let mut go_on = true;
+        let mut row = self.down[header];
+        while row != header && go_on {
+            self.chosen.push(row);
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+            go_on = self.search(visit);
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            self.chosen.pop();
+            row = self.down[row];
+        }
+        self.uncover(header);
+        go_on
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sectors::Sectors;
+    use std::sync::Arc;
+    use sudoku_test_utils::{solution, Tier};
+
+    #[test]
+    fn test_exact_cover() {
+        let classic = Arc::new(Sectors::classic());
+        let solution = solution(Tier::Singles);
+        // Two cells left open have one cover, as (cell, digit) choices
+        let puzzle = format!("..{}", &solution[2..]);
+        let mut dlx = Dlx::new(&Puzzle::parse(classic.clone(), &puzzle).unwrap());
+        let mut found = Vec::new();
+        dlx.search(&mut |choices| {
+            found.push(choices.to_vec());
+            true
+        });
+        assert_eq!(found, [vec![(0, 5), (1, 3)]]);
+
+        let empty = Puzzle::parse(classic, &".".repeat(81)).unwrap();
+        let mut count = 0;
+        Dlx::new(&empty).search(&mut |_| {
+            count += 1;
+            count < 50
+        });
+        assert_eq!(count, 50);
+    }
+}
@@ -0,0 +1,92 @@
+//! New puzzles for any layout: a random full board, then givens removed in
+//! random order while the techniques in [`crate::solver`] still solve it.
+
+use crate::puzzle::{digits, Puzzle};
+use crate::sectors::Sectors;
+use crate::solver::solve_logically;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::sync::Arc;
+
+/// A puzzle the techniques solve without guessing, so its solution is
+/// unique, and from which no given can be removed without losing that.
+/// None if the layout's constraints leave no way to fill the board.
+pub fn generate(sectors: Arc<Sectors>, rng: &mut impl Rng) -> Option<Puzzle> {
+    let empty = vec![0; sectors.cell_count()];
+    let solution = fill(&Puzzle::new(Arc::clone(&sectors), &empty)?, rng)?;
+    let mut values = solution.values().to_vec();
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.shuffle(rng);
+    for cell in order {
+        let value = std::mem::replace(&mut values[cell], 0);
+        if !solves(&sectors, &values) {
+            values[cell] = value;
+        }
+    }
+    Puzzle::new(sectors, &values)
+}
+
+/// A random solution, trying the cell with the fewest candidates first
+fn fill(puzzle: &Puzzle, rng: &mut impl Rng) -> Option<Puzzle> {
+    let values = puzzle.values();
+    let empty = (0..values.len())
+        .filter(|&cell| values[cell] == 0)
+        .min_by_key(|&cell| puzzle.candidates(cell).count_ones());
+    let Some(cell) = empty else {
+        return Some(puzzle.clone());
+    };
+    let mut choices: Vec<u8> = digits(puzzle.candidates(cell)).collect();
+    choices.shuffle(rng);
+    choices.into_iter().find_map(|value| {
+        let mut next = puzzle.clone();
+        next.place(cell, value);
+        fill(&next, rng)
+    })
+}
+
+fn solves(sectors: &Arc<Sectors>, values: &[u8]) -> bool {
+    Puzzle::new(Arc::clone(sectors), values).is_some_and(|mut puzzle| {
+        solve_logically(&mut puzzle);
+        puzzle.is_solved()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint::{AntiKnight, ExtraConstraint};
+    use crate::solver::rate;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generate_six_by_six() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let puzzle = generate(Arc::new(Sectors::boxed(2, 3)), &mut rng).unwrap();
+        assert!(puzzle.is_unique());
+        assert!(rate(&puzzle).is_some());
+
+        // Every given is needed
+        let values = puzzle.values();
+        for cell in (0..36).filter(|&cell| values[cell] != 0) {
+            let mut fewer = values.to_vec();
+            fewer[cell] = 0;
+            assert!(!solves(&Arc::new(Sectors::boxed(2, 3)), &fewer));
+        }
+    }
+
+    #[test]
+    fn test_generate_anti_knight() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let anti_knight = Arc::new(Sectors::classic().with_constraint(AntiKnight));
+        let puzzle = generate(anti_knight, &mut rng).unwrap();
+        assert!(puzzle.is_unique());
+        let solution = puzzle.solve().unwrap();
+        let values = solution.values();
+        for cell in 0..81 {
+            for (other, digit) in AntiKnight.eliminations(9, cell, values[cell]) {
+                assert_ne!(values[other], digit);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Sudoku variants with more units than rows, columns and boxes, such as
+//! Windoku's four shaded windows. A variant is a set of [`Sectors`]; a
+//! [`Puzzle`] keeps its candidates in step with them, and the techniques
+//! in [`solver`] work over any sector, so a window joins in singles,
+//! subsets and fish like a box does. Boards can also be 6x6 or 16x16 (any
+//! box shape up to 16 digits), and [`generate`] makes new puzzles for any
+//! layout. Extra constraints such as anti-knight and non-consecutive ride
+//! along with the sectors and are followed everywhere.
+
+mod constraint;
+mod dlx;
+mod generate;
+mod puzzle;
+mod sectors;
+pub mod solver;
+
+pub use constraint::{AntiKnight, ExtraConstraint, NonConsecutive};
+pub use generate::generate;
+pub use puzzle::{digits, Mask, Puzzle, Solutions};
+pub use sectors::{Sector, SectorKind, Sectors, MAX_SIZE, WINDOWS};
+pub use solver::{
+    next_step, next_step_with, rate, rate_with, solve_logically, solve_logically_with, Step,
+    Technique, TechniqueSet, TECHNIQUES,
+};
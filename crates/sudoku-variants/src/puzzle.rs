@@ -0,0 +1,260 @@
+//! A board under a set of sectors: values, candidates kept in step with
+//! them, and a search for solutions: dancing links when only the sectors
+//! apply, backtracking when extra constraints do.
+
+use crate::dlx::Dlx;
+use crate::sectors::{Sectors, MAX_SIZE};
+use std::sync::Arc;
+
+/// Candidate digits of a cell as bits 1 up to the board's size
+pub type Mask = u32;
+
+/// Digits set in `mask`, lowest first
+pub fn digits(mask: Mask) -> impl Iterator<Item = u8> {
+    (1..=MAX_SIZE as u8).filter(move |&d| mask & (1 << d) != 0)
+}
+
+/// A board whose digits must be unique in every sector
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    sectors: Arc<Sectors>,
+    /// Digit per cell in reading order, 0 for empty
+    values: Vec<u8>,
+    /// Candidates per cell, empty for a filled cell
+    candidates: Vec<Mask>,
+}
+
+impl Puzzle {
+    /// Read a character per cell: digits for givens, then 'A' to 'G' for
+    /// 10 to 16, and '.' or '0' for empty cells. None if the string is
+    /// malformed or two givens clash.
+    pub fn parse(sectors: Arc<Sectors>, puzzle: &str) -> Option<Self> {
+        let values: Vec<u8> = puzzle
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                '.' => Some(0),
+                _ => c.to_digit(MAX_SIZE as u32 + 1).map(|d| d as u8),
+            })
+            .collect::<Option<_>>()?;
+        Self::new(sectors, &values)
+    }
+
+    /// A board from one digit per cell, 0 for empty
+    pub fn new(sectors: Arc<Sectors>, values: &[u8]) -> Option<Self> {
+        let size = sectors.size();
+        let cells = sectors.cell_count();
+        if values.len() != cells || values.iter().any(|&v| v as usize > size) {
+            return None;
+        }
+        let all: Mask = (1 << (size + 1)) - 2;
+        let mut puzzle = Self {
+            sectors,
+            values: vec![0; cells],
+            candidates: vec![all; cells],
+        };
+        for (cell, &value) in values.iter().enumerate() {
+            if value != 0 {
+                if puzzle.candidates[cell] & (1 << value) == 0 {
+                    return None;
+                }
+                puzzle.place(cell, value);
+            }
+        }
+        Some(puzzle)
+    }
+
+    pub fn sectors(&self) -> &Sectors {
+        &self.sectors
+    }
+
+    pub fn values(&self) -> &[u8] {
+        &self.values
+    }
+
+    pub fn value(&self, cell: usize) -> Option<u8> {
+        Some(self.values[cell]).filter(|&v| v != 0)
+    }
+
+    pub fn candidates(&self, cell: usize) -> Mask {
+        self.candidates[cell]
+    }
+
+    pub fn is_solved(&self) -> bool {
+        !self.values.contains(&0)
+    }
+
+    /// Put `value` in `cell` and take it out of the peers' candidates, and
+    /// whatever the extra constraints rule out
+    pub fn place(&mut self, cell: usize, value: u8) {
+        self.values[cell] = value;
+        self.candidates[cell] = 0;
+        for &peer in self.sectors.peers(cell) {
+            self.candidates[peer] &= !(1 << value);
+        }
+        let size = self.sectors.size();
+        for constraint in self.sectors.constraints() {
+            for (other, digit) in constraint.eliminations(size, cell, value) {
+                self.candidates[other] &= !(1 << digit);
+            }
+        }
+    }
+
+    pub fn eliminate(&mut self, cell: usize, value: u8) {
+        self.candidates[cell] &= !(1 << value);
+    }
+
+    /// The board as a character per cell, as [`Puzzle::parse`] reads it
+    pub fn to_line(&self) -> String {
+        self.values
+            .iter()
+            .map(|&v| match v {
+                0 => '.',
+                1..=9 => (b'0' + v) as char,
+                _ => (b'A' + v - 10) as char,
+            })
+            .collect()
+    }
+
+    /// Count solutions, stopping at `limit`
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut count = 0;
+        self.each_solution(&mut |_| {
+            count += 1;
+            count < limit
+        });
+        count
+    }
+
+    /// The first solution found, if any
+    pub fn solve(&self) -> Option<Puzzle> {
+        let mut found = None;
+        self.each_solution(&mut |solved| {
+            found = Some(solved.clone());
+            false
+        });
+        found
+    }
+
+    /// Every solution, found one at a time as the iterator is advanced, so
+    /// `.take(n)` caps the search. Handy for showing what makes a puzzle
+    /// ambiguous rather than only counting.
+    pub fn solutions(&self) -> Solutions {
+        Solutions {
+            stack: vec![self.clone()],
+        }
+    }
+
+    /// Whether the board has exactly one solution
+    pub fn is_unique(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// Hand each solution to `visit` until it says stop: as an exact cover
+    /// when only the sectors apply, which doesn't clone the board per guess
+    fn each_solution(&self, visit: &mut dyn FnMut(&Puzzle) -> bool) {
+        if !self.sectors.constraints().is_empty() {
+            self.search(visit);
+            return;
+        }
+        Dlx::new(self).search(&mut |choices| {
+            let mut solved = self.clone();
+            for &(cell, digit) in choices {
+                solved.place(cell, digit);
+            }
+            visit(&solved)
+        });
+    }
+
+    /// Depth-first search, trying the cell with the fewest candidates
+    /// first. `visit` gets each solution and returns whether to go on;
+    /// returns false once it has said stop.
+    fn search(&self, visit: &mut dyn FnMut(&Puzzle) -> bool) -> bool {
+        let Some(cell) = self.fewest_candidates() else {
+            return visit(self);
+        };
+        for value in digits(self.candidates[cell]) {
+            let mut next = self.clone();
+            next.place(cell, value);
+            if !next.search(visit) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The empty cell with the fewest candidates, None if the board is full
+    fn fewest_candidates(&self) -> Option<usize> {
+        (0..self.values.len())
+            .filter(|&cell| self.values[cell] == 0)
+            .min_by_key(|&cell| self.candidates[cell].count_ones())
+    }
+}
+
+/// A board's solutions, from [`Puzzle::solutions`]
+#[derive(Debug, Clone)]
+pub struct Solutions {
+    /// Boards still to search, the next on top
+    stack: Vec<Puzzle>,
+}
+
+impl Iterator for Solutions {
+    type Item = Puzzle;
+
+    fn next(&mut self) -> Option<Puzzle> {
+        while let Some(puzzle) = self.stack.pop() {
+            let Some(cell) = puzzle.fewest_candidates() else {
+                return Some(puzzle);
+            };
+            for value in digits(puzzle.candidates[cell]) {
+                let mut next = puzzle.clone();
+                next.place(cell, value);
+                self.stack.push(next);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::{solution, Tier};
+
+    #[test]
+    fn test_puzzle() {
+        let classic = Arc::new(Sectors::classic());
+        let puzzle = Puzzle::parse(classic.clone(), Tier::Singles.puzzle()).unwrap();
+        assert_eq!(digits(puzzle.candidates(2)).collect::<Vec<_>>(), [1, 2, 4]);
+        assert!(puzzle.is_unique());
+        // Agrees with sudoku-core's solver
+        assert_eq!(puzzle.solve().unwrap().to_line(), solution(Tier::Singles));
+
+        assert!(Puzzle::parse(classic.clone(), &"1".repeat(2)).is_none());
+        assert!(Puzzle::parse(classic, &format!("11{}", ".".repeat(79))).is_none());
+
+        // Letters for digits past 9 on a 16x16 board
+        let sixteen = Arc::new(Sectors::boxed(4, 4));
+        let puzzle = Puzzle::parse(sixteen, &format!("G{}A", ".".repeat(254))).unwrap();
+        assert_eq!(puzzle.value(0), Some(16));
+        assert_eq!(digits(puzzle.candidates(1)).count(), 15);
+        assert!(puzzle.to_line().ends_with(".A"));
+    }
+
+    #[test]
+    fn test_solutions() {
+        let classic = Arc::new(Sectors::classic());
+        let solution = solution(Tier::Singles);
+        // A rectangle of two digits over two boxes can be swapped
+        let mut line = solution.as_bytes().to_vec();
+        for cell in [32, 35, 41, 44] {
+            line[cell] = b'.';
+        }
+        let puzzle = Puzzle::parse(classic, std::str::from_utf8(&line).unwrap()).unwrap();
+        let found: Vec<String> = puzzle.solutions().map(|s| s.to_line()).collect();
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&solution));
+        assert_eq!(puzzle.solutions().take(1).count(), 1);
+        assert_eq!(puzzle.count_solutions(10), 2);
+    }
+}
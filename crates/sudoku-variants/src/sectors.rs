@@ -0,0 +1,236 @@
+//! Sectors: every set of cells that must hold each digit once. The classic
+//! rules have 27 (rows, columns, boxes); Windoku adds four shaded windows.
+//! Boards of other sizes have a row, column and box per digit. A layout
+//! can also carry extra constraints, such as anti-knight.
+
+use crate::constraint::ExtraConstraint;
+use std::sync::Arc;
+
+/// Largest side a board can have, so candidates fit in a `Mask`
+pub const MAX_SIZE: usize = 16;
+
+/// Top-left cells of the four Windoku windows, as (row, col)
+pub const WINDOWS: [(usize, usize); 4] = [(1, 1), (1, 5), (5, 1), (5, 5)];
+
+/// What kind of unit a sector is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectorKind {
+    Row,
+    Column,
+    Box,
+    /// One of the extra Windoku windows
+    Window,
+}
+
+/// One unit: its kind, its number among units of that kind, and its cells
+/// in reading order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sector {
+    pub kind: SectorKind,
+    pub index: usize,
+    pub cells: Vec<usize>,
+}
+
+impl Sector {
+    /// e.g. "row 3" or "window 2", counting from 1
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            SectorKind::Row => "row",
+            SectorKind::Column => "column",
+            SectorKind::Box => "box",
+            SectorKind::Window => "window",
+        };
+        format!("{} {}", kind, self.index + 1)
+    }
+
+    pub fn contains(&self, cell: usize) -> bool {
+        self.cells.contains(&cell)
+    }
+}
+
+/// The sectors of a layout, with each cell's sectors and peers, and any
+/// extra constraints
+#[derive(Debug, Clone)]
+pub struct Sectors {
+    /// Cells per side, and digits per sector
+    size: usize,
+    sectors: Vec<Sector>,
+    /// Per cell, the indices of the sectors holding it
+    of_cell: Vec<Vec<usize>>,
+    /// Per cell, every other cell sharing a sector with it
+    peers: Vec<Vec<usize>>,
+    constraints: Vec<Arc<dyn ExtraConstraint>>,
+}
+
+impl Sectors {
+    /// Rows, then columns, then boxes: sectors 0-8, 9-17 and 18-26
+    pub fn classic() -> Self {
+        Self::boxed(3, 3)
+    }
+
+    /// A board of `box_rows` x `box_cols` boxes, as many to a side as a box
+    /// has cells: 2x3 for 6x6, 4x4 for 16x16. Rows, then columns, then
+    /// boxes, as for [`Sectors::classic`].
+    ///
+    /// Panics if the side would be over [`MAX_SIZE`].
+    pub fn boxed(box_rows: usize, box_cols: usize) -> Self {
+        let size = box_rows * box_cols;
+        assert!(
+            size <= MAX_SIZE,
+            "boards can be at most {MAX_SIZE} to a side"
+        );
+        Self::build(size, boxed_sectors(box_rows, box_cols))
+    }
+
+    /// The classic sectors plus the four windows as sectors 27-30
+    pub fn windoku() -> Self {
+        let mut sectors = boxed_sectors(3, 3);
+        for (index, &(top, left)) in WINDOWS.iter().enumerate() {
+            sectors.push(Sector {
+                kind: SectorKind::Window,
+                index,
+                cells: rectangle(9, (top, left), (3, 3)),
+            });
+        }
+        Self::build(9, sectors)
+    }
+
+    fn build(size: usize, sectors: Vec<Sector>) -> Self {
+        let mut of_cell = vec![Vec::new(); size * size];
+        for (i, sector) in sectors.iter().enumerate() {
+            for &cell in &sector.cells {
+                of_cell[cell].push(i);
+            }
+        }
+        let peers = (0..size * size)
+            .map(|cell| {
+                let mut peers: Vec<usize> = of_cell[cell]
+                    .iter()
+                    .flat_map(|&s| sectors[s].cells.iter().copied())
+                    .filter(|&other| other != cell)
+                    .collect();
+                peers.sort_unstable();
+                peers.dedup();
+                peers
+            })
+            .collect();
+        Self {
+            size,
+            sectors,
+            of_cell,
+            peers,
+            constraints: Vec::new(),
+        }
+    }
+
+    /// The same layout with `constraint` enforced as well
+    pub fn with_constraint(mut self, constraint: impl ExtraConstraint + 'static) -> Self {
+        self.constraints.push(Arc::new(constraint));
+        self
+    }
+
+    pub fn constraints(&self) -> &[Arc<dyn ExtraConstraint>] {
+        &self.constraints
+    }
+
+    /// Cells per side, and the highest digit
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.size * self.size
+    }
+
+    pub fn len(&self) -> usize {
+        self.sectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sectors.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> &Sector {
+        &self.sectors[index]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Sector> {
+        self.sectors.iter()
+    }
+
+    /// Indices of the sectors holding `cell`
+    pub fn of_cell(&self, cell: usize) -> &[usize] {
+        &self.of_cell[cell]
+    }
+
+    /// Cells that can't share a digit with `cell`
+    pub fn peers(&self, cell: usize) -> &[usize] {
+        &self.peers[cell]
+    }
+}
+
+fn boxed_sectors(box_rows: usize, box_cols: usize) -> Vec<Sector> {
+    let size = box_rows * box_cols;
+    let rows = (0..size).map(|r| Sector {
+        kind: SectorKind::Row,
+        index: r,
+        cells: (0..size).map(|c| r * size + c).collect(),
+    });
+    let cols = (0..size).map(|c| Sector {
+        kind: SectorKind::Column,
+        index: c,
+        cells: (0..size).map(|r| r * size + c).collect(),
+    });
+    // Boxes across a band
+    let across = size / box_cols;
+    let boxes = (0..size).map(|b| Sector {
+        kind: SectorKind::Box,
+        index: b,
+        cells: rectangle(
+            size,
+            (b / across * box_rows, b % across * box_cols),
+            (box_rows, box_cols),
+        ),
+    });
+    rows.chain(cols).chain(boxes).collect()
+}
+
+/// Cells of the `(rows, cols)` rectangle whose top-left cell is
+/// `(top, left)`, on a board `size` cells wide
+fn rectangle(size: usize, (top, left): (usize, usize), (rows, cols): (usize, usize)) -> Vec<usize> {
+    (0..rows * cols)
+        .map(|i| (top + i / cols) * size + left + i % cols)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windoku_sectors() {
+        let sectors = Sectors::windoku();
+        assert_eq!(sectors.len(), 31);
+        // r2c2 is in row 2, column 2, box 1 and window 1
+        assert_eq!(sectors.of_cell(10), &[1, 10, 18, 27]);
+        assert_eq!(sectors.get(30).label(), "window 4");
+        assert!(sectors.get(30).contains(80 - 10));
+
+        // A window adds peers outside the cell's row, column and box
+        assert_eq!(Sectors::classic().peers(10).len(), 20);
+        assert!(sectors.peers(10).contains(&30));
+    }
+
+    #[test]
+    fn test_boxed_sectors() {
+        // 6x6: boxes two rows tall and three columns wide
+        let six = Sectors::boxed(2, 3);
+        assert_eq!((six.size(), six.len()), (6, 18));
+        assert_eq!(six.get(13).cells, [3, 4, 5, 9, 10, 11]);
+        assert_eq!(six.peers(0).len(), 5 + 5 + 2);
+
+        let sixteen = Sectors::boxed(4, 4);
+        assert_eq!(sixteen.cell_count(), 256);
+        assert_eq!(sixteen.of_cell(255), &[15, 31, 47]);
+    }
+}
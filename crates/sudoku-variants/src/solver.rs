@@ -0,0 +1,519 @@
+//! Logical steps over any set of sectors. Each technique looks at sectors
+//! rather than rows, columns and boxes by name, so a Windoku window takes
+//! part in singles, intersections, subsets and fish like any other unit,
+//! and the same techniques work on boards of any size. A [`TechniqueSet`]
+//! turns individual techniques off for hints and ratings.
+
+use crate::puzzle::{digits, Mask, Puzzle};
+
+/// The kind of deduction a step makes, ordered from easiest to hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Technique {
+    /// The cell has one candidate left
+    NakedSingle,
+    /// The digit fits one cell of a sector
+    HiddenSingle,
+    /// A digit confined to where two sectors overlap leaves the rest of
+    /// the second (pointing and claiming, and their window forms)
+    Intersection,
+    /// `n` cells of a sector share `n` candidates
+    NakedSubset(usize),
+    /// `n` digits of a sector fit only `n` cells
+    HiddenSubset(usize),
+    /// A digit in `n` disjoint base sectors lies within `n` disjoint cover
+    /// sectors (X-Wing, Swordfish)
+    Fish(usize),
+}
+
+impl Technique {
+    pub fn name(self) -> String {
+        let size = |n| match n {
+            2 => "Pair",
+            3 => "Triple",
+            _ => "Quad",
+        };
+        match self {
+            Technique::NakedSingle => "Naked Single".to_string(),
+            Technique::HiddenSingle => "Hidden Single".to_string(),
+            Technique::Intersection => "Intersection".to_string(),
+            Technique::NakedSubset(n) => format!("Naked {}", size(n)),
+            Technique::HiddenSubset(n) => format!("Hidden {}", size(n)),
+            Technique::Fish(2) => "X-Wing".to_string(),
+            Technique::Fish(_) => "Swordfish".to_string(),
+        }
+    }
+}
+
+/// Every technique, in the order [`next_step`] tries them
+pub const TECHNIQUES: [Technique; 11] = [
+    Technique::NakedSingle,
+    Technique::HiddenSingle,
+    Technique::Intersection,
+    Technique::NakedSubset(2),
+    Technique::NakedSubset(3),
+    Technique::NakedSubset(4),
+    Technique::HiddenSubset(2),
+    Technique::HiddenSubset(3),
+    Technique::HiddenSubset(4),
+    Technique::Fish(2),
+    Technique::Fish(3),
+];
+
+/// Techniques the solver may use, a bit per entry of [`TECHNIQUES`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TechniqueSet(u16);
+
+impl TechniqueSet {
+    pub const ALL: Self = Self((1 << TECHNIQUES.len()) - 1);
+    pub const EMPTY: Self = Self(0);
+
+    pub fn contains(self, technique: Technique) -> bool {
+        Self::bit(technique).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn with(self, technique: Technique) -> Self {
+        Self(self.0 | Self::bit(technique).unwrap_or(0))
+    }
+
+    pub fn without(self, technique: Technique) -> Self {
+        Self(self.0 & !Self::bit(technique).unwrap_or(0))
+    }
+
+    /// None for a technique not in [`TECHNIQUES`], such as a 5-cell subset
+    fn bit(technique: Technique) -> Option<u16> {
+        let index = TECHNIQUES.iter().position(|&t| t == technique)?;
+        Some(1 << index)
+    }
+}
+
+impl Default for TechniqueSet {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl FromIterator<Technique> for TechniqueSet {
+    fn from_iter<I: IntoIterator<Item = Technique>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::EMPTY, Self::with)
+    }
+}
+
+/// One deduction: a digit to place or candidates to remove, and the
+/// sectors it rests on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    /// Cell and digit, for singles
+    pub placement: Option<(usize, u8)>,
+    /// Cells and digits to remove
+    pub eliminations: Vec<(usize, u8)>,
+    /// Indices of the sectors involved
+    pub sectors: Vec<usize>,
+}
+
+impl Step {
+    pub fn apply(&self, puzzle: &mut Puzzle) {
+        if let Some((cell, value)) = self.placement {
+            puzzle.place(cell, value);
+        }
+        for &(cell, value) in &self.eliminations {
+            puzzle.eliminate(cell, value);
+        }
+    }
+}
+
+/// The simplest step available, or None when these techniques are stuck
+pub fn next_step(puzzle: &Puzzle) -> Option<Step> {
+    next_step_with(puzzle, TechniqueSet::ALL)
+}
+
+/// The simplest step using only `techniques`
+pub fn next_step_with(puzzle: &Puzzle, techniques: TechniqueSet) -> Option<Step> {
+    TECHNIQUES
+        .iter()
+        .filter(|&&technique| techniques.contains(technique))
+        .find_map(|&technique| find(puzzle, technique))
+}
+
+/// Apply steps until the board is solved or no technique applies,
+/// returning the steps taken
+pub fn solve_logically(puzzle: &mut Puzzle) -> Vec<Step> {
+    solve_logically_with(puzzle, TechniqueSet::ALL)
+}
+
+pub fn solve_logically_with(puzzle: &mut Puzzle, techniques: TechniqueSet) -> Vec<Step> {
+    let mut steps = Vec::new();
+    while !puzzle.is_solved() {
+        let Some(step) = next_step_with(puzzle, techniques) else {
+            break;
+        };
+        step.apply(puzzle);
+        steps.push(step);
+    }
+    steps
+}
+
+/// The hardest technique needed to solve the board, or None if these
+/// techniques get stuck (or there is nothing left to solve)
+pub fn rate(puzzle: &Puzzle) -> Option<Technique> {
+    rate_with(puzzle, TechniqueSet::ALL)
+}
+
+/// The hardest technique needed using only `techniques`
+pub fn rate_with(puzzle: &Puzzle, techniques: TechniqueSet) -> Option<Technique> {
+    let mut puzzle = puzzle.clone();
+    let steps = solve_logically_with(&mut puzzle, techniques);
+    if !puzzle.is_solved() {
+        return None;
+    }
+    steps.iter().map(|step| step.technique).max()
+}
+
+fn find(puzzle: &Puzzle, technique: Technique) -> Option<Step> {
+    match technique {
+        Technique::NakedSingle => naked_single(puzzle),
+        Technique::HiddenSingle => hidden_single(puzzle),
+        Technique::Intersection => intersection(puzzle),
+        Technique::NakedSubset(n) => naked_subset(puzzle, n),
+        Technique::HiddenSubset(n) => hidden_subset(puzzle, n),
+        Technique::Fish(n) => fish(puzzle, n),
+    }
+}
+
+/// Digits of the board, 1 up to its size
+fn all_digits(puzzle: &Puzzle) -> std::ops::RangeInclusive<u8> {
+    1..=puzzle.sectors().size() as u8
+}
+
+fn naked_single(puzzle: &Puzzle) -> Option<Step> {
+    let cell = (0..puzzle.sectors().cell_count())
+        .find(|&cell| puzzle.candidates(cell).count_ones() == 1)?;
+    let value = digits(puzzle.candidates(cell)).next()?;
+    Some(Step {
+        technique: Technique::NakedSingle,
+        placement: Some((cell, value)),
+        eliminations: Vec::new(),
+        sectors: puzzle.sectors().of_cell(cell).to_vec(),
+    })
+}
+
+fn hidden_single(puzzle: &Puzzle) -> Option<Step> {
+    for (s, sector) in puzzle.sectors().iter().enumerate() {
+        for value in all_digits(puzzle) {
+            let mut cells = holding(puzzle, &sector.cells, value);
+            if let (Some(cell), None) = (cells.next(), cells.next()) {
+                return Some(Step {
+                    technique: Technique::HiddenSingle,
+                    placement: Some((cell, value)),
+                    eliminations: Vec::new(),
+                    sectors: vec![s],
+                });
+            }
+        }
+    }
+    None
+}
+
+fn intersection(puzzle: &Puzzle) -> Option<Step> {
+    let sectors = puzzle.sectors();
+    for (a, base) in sectors.iter().enumerate() {
+        for value in all_digits(puzzle) {
+            let cells: Vec<usize> = holding(puzzle, &base.cells, value).collect();
+            if cells.len() < 2 {
+                continue;
+            }
+            // Every sector holding all of them, other than the base
+            for &b in sectors.of_cell(cells[0]) {
+                let other = sectors.get(b);
+                if b == a || !cells.iter().all(|&cell| other.contains(cell)) {
+                    continue;
+                }
+                let eliminations: Vec<(usize, u8)> = holding(puzzle, &other.cells, value)
+                    .filter(|cell| !base.contains(*cell))
+                    .map(|cell| (cell, value))
+                    .collect();
+                if !eliminations.is_empty() {
+                    return Some(Step {
+                        technique: Technique::Intersection,
+                        placement: None,
+                        eliminations,
+                        sectors: vec![a, b],
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+fn naked_subset(puzzle: &Puzzle, n: usize) -> Option<Step> {
+    for (s, sector) in puzzle.sectors().iter().enumerate() {
+        let open: Vec<usize> = sector
+            .cells
+            .iter()
+            .copied()
+            .filter(|&cell| puzzle.candidates(cell) != 0)
+            .collect();
+        if open.len() <= n {
+            continue;
+        }
+        for subset in combinations(&open, n) {
+            let union = subset
+                .iter()
+                .fold(0, |mask: Mask, &cell| mask | puzzle.candidates(cell));
+            if union.count_ones() as usize != n {
+                continue;
+            }
+            let eliminations: Vec<(usize, u8)> = open
+                .iter()
+                .filter(|cell| !subset.contains(cell))
+                .flat_map(|&cell| digits(puzzle.candidates(cell) & union).map(move |d| (cell, d)))
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: Technique::NakedSubset(n),
+                    placement: None,
+                    eliminations,
+                    sectors: vec![s],
+                });
+            }
+        }
+    }
+    None
+}
+
+fn hidden_subset(puzzle: &Puzzle, n: usize) -> Option<Step> {
+    for (s, sector) in puzzle.sectors().iter().enumerate() {
+        let open: Vec<u8> = all_digits(puzzle)
+            .filter(|&d| holding(puzzle, &sector.cells, d).next().is_some())
+            .collect();
+        if open.len() <= n {
+            continue;
+        }
+        for subset in combinations(&open, n) {
+            let mut cells: Vec<usize> = subset
+                .iter()
+                .flat_map(|&d| holding(puzzle, &sector.cells, d))
+                .collect();
+            cells.sort_unstable();
+            cells.dedup();
+            if cells.len() != n {
+                continue;
+            }
+            let keep = subset.iter().fold(0, |mask: Mask, &d| mask | 1 << d);
+            let eliminations: Vec<(usize, u8)> = cells
+                .iter()
+                .flat_map(|&cell| digits(puzzle.candidates(cell) & !keep).map(move |d| (cell, d)))
+                .collect();
+            if !eliminations.is_empty() {
+                return Some(Step {
+                    technique: Technique::HiddenSubset(n),
+                    placement: None,
+                    eliminations,
+                    sectors: vec![s],
+                });
+            }
+        }
+    }
+    None
+}
+
+fn fish(puzzle: &Puzzle, n: usize) -> Option<Step> {
+    let sectors = puzzle.sectors();
+    for value in all_digits(puzzle) {
+        // Bases hold the digit in 2 to n cells
+        let bases: Vec<usize> = (0..sectors.len())
+            .filter(|&s| (2..=n).contains(&holding(puzzle, &sectors.get(s).cells, value).count()))
+            .collect();
+        for base in combinations(&bases, n) {
+            if !disjoint(puzzle, &base) {
+                continue;
+            }
+            let mut cells: Vec<usize> = base
+                .iter()
+                .flat_map(|&s| holding(puzzle, &sectors.get(s).cells, value))
+                .collect();
+            cells.sort_unstable();
+            let fish = Fish {
+                value,
+                size: n,
+                base: &base,
+                cells: &cells,
+            };
+            if let Some(step) = fish.cover(puzzle, &mut Vec::new()) {
+                return Some(step);
+            }
+        }
+    }
+    None
+}
+
+/// Base sectors of a fish and their candidate cells, looking for covers
+struct Fish<'a> {
+    value: u8,
+    size: usize,
+    base: &'a [usize],
+    /// Sorted
+    cells: &'a [usize],
+}
+
+impl Fish<'_> {
+    /// Grow `cover` one sector at a time, each holding the first cell not
+    /// yet covered, until every cell is; the first cover that eliminates
+    /// something makes the step
+    fn cover(&self, puzzle: &Puzzle, cover: &mut Vec<usize>) -> Option<Step> {
+        let sectors = puzzle.sectors();
+        let uncovered = self
+            .cells
+            .iter()
+            .find(|&&cell| !cover.iter().any(|&s| sectors.get(s).contains(cell)));
+        let Some(&cell) = uncovered else {
+            return self.eliminate(puzzle, cover);
+        };
+        if cover.len() == self.size {
+            return None;
+        }
+        for &s in sectors.of_cell(cell) {
+            if self.base.contains(&s) || cover.contains(&s) {
+                continue;
+            }
+            cover.push(s);
+            if disjoint(puzzle, cover) {
+                if let Some(step) = self.cover(puzzle, cover) {
+                    return Some(step);
+                }
+            }
+            cover.pop();
+        }
+        None
+    }
+
+    fn eliminate(&self, puzzle: &Puzzle, cover: &[usize]) -> Option<Step> {
+        if cover.len() != self.size {
+            return None;
+        }
+        let mut eliminations: Vec<(usize, u8)> = cover
+            .iter()
+            .flat_map(|&s| holding(puzzle, &puzzle.sectors().get(s).cells, self.value))
+            .filter(|cell| self.cells.binary_search(cell).is_err())
+            .map(|cell| (cell, self.value))
+            .collect();
+        eliminations.sort_unstable();
+        eliminations.dedup();
+        (!eliminations.is_empty()).then(|| Step {
+            technique: Technique::Fish(self.size),
+            placement: None,
+            eliminations,
+            sectors: self.base.iter().chain(cover).copied().collect(),
+        })
+    }
+}
+
+/// Cells of `cells` with `value` as a candidate
+fn holding<'a>(
+    puzzle: &'a Puzzle,
+    cells: &'a [usize],
+    value: u8,
+) -> impl Iterator<Item = usize> + 'a {
+    cells
+        .iter()
+        .copied()
+        .filter(move |&cell| puzzle.candidates(cell) & (1 << value) != 0)
+}
+
+/// Whether no two of `sectors` share a cell
+fn disjoint(puzzle: &Puzzle, sectors: &[usize]) -> bool {
+    let mut seen = vec![false; puzzle.sectors().cell_count()];
+    for &s in sectors {
+        for &cell in &puzzle.sectors().get(s).cells {
+            if std::mem::replace(&mut seen[cell], true) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Every way to choose `k` of `items`, in order
+fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    let mut all = Vec::new();
+    for (i, &first) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], k - 1) {
+            rest.insert(0, first);
+            all.push(rest);
+        }
+    }
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sectors::Sectors;
+    use std::sync::Arc;
+
+    /// A 17-clue Windoku; under classic rules it has many solutions
+    const WINDOKU: &str =
+        ".38..9..7.....1..4......9......75.....9........5..4.2.....8.....6..........7..6..";
+
+    #[test]
+    fn test_windoku_solves_logically() {
+        let windoku = Arc::new(Sectors::windoku());
+        let mut puzzle = Puzzle::parse(windoku, WINDOKU).unwrap();
+        assert!(puzzle.is_unique());
+        let solution = puzzle.solve().unwrap();
+
+        let steps = solve_logically(&mut puzzle);
+        assert!(puzzle.is_solved());
+        assert_eq!(puzzle.values(), solution.values());
+        assert!(steps
+            .iter()
+            .any(|step| step.sectors.iter().any(|&s| s >= 27)));
+
+        let classic = Puzzle::parse(Arc::new(Sectors::classic()), WINDOKU).unwrap();
+        assert_eq!(classic.count_solutions(2), 2);
+    }
+
+    #[test]
+    fn test_sixteen_by_sixteen() {
+        let puzzle = concat!(
+            "D.....F...6.EG.1..4.5.1.3..C.A..5...D....A.......C...6E..F9.D...",
+            "....BA6.9.E...D7FD..7.23.8.....G..7.18.....6.923.1......AB...F.8",
+            "7G..2D..B..A4.696....B5....4.1....24..3.......B5..5D.9.EC.....G.",
+            "2..AG58.....9..6.EC..2A..1......1B...E......C..4G.F......5.B.2..",
+        );
+        let mut puzzle = Puzzle::parse(Arc::new(Sectors::boxed(4, 4)), puzzle).unwrap();
+        assert_eq!(rate(&puzzle), Some(Technique::NakedSubset(2)));
+        solve_logically(&mut puzzle);
+        assert!(puzzle.is_solved());
+        assert!(Puzzle::new(Arc::new(Sectors::boxed(4, 4)), puzzle.values()).is_some());
+    }
+
+    #[test]
+    fn test_technique_set() {
+        let singles: TechniqueSet = [Technique::NakedSingle, Technique::HiddenSingle]
+            .into_iter()
+            .collect();
+        assert!(singles.contains(Technique::HiddenSingle));
+        assert!(!singles.contains(Technique::Fish(2)));
+        assert!(!TechniqueSet::ALL.contains(Technique::NakedSubset(5)));
+        assert_eq!(
+            TechniqueSet::ALL
+                .without(Technique::Fish(3))
+                .with(Technique::Fish(3)),
+            TechniqueSet::ALL
+        );
+
+        let windoku = Arc::new(Sectors::windoku());
+        let puzzle = Puzzle::parse(windoku, WINDOKU).unwrap();
+        let hardest = rate(&puzzle).unwrap();
+        let without = TechniqueSet::ALL.without(hardest);
+        let mut limited = puzzle.clone();
+        let steps = solve_logically_with(&mut limited, without);
+        assert!(steps.iter().all(|step| step.technique != hardest));
+        assert_eq!(rate_with(&puzzle, without).is_some(), limited.is_solved());
+    }
+}
@@ -0,0 +1,101 @@
+//! The engine as a WebAssembly component, for edge runtimes that host the
+//! daily puzzle and rating service without native binaries.
+//!
+//! Build with `cargo build -p sudoku-wasi --target wasm32-wasip2 --release`;
+//! the interface is `wit/world.wit`.
+
+use sudoku_core::{Difficulty, Grid, PuzzleId, Solver};
+
+wit_bindgen::generate!("sudoku");
+
+use exports::ukodus::sudoku::engine::{Guest, Puzzle, Rating};
+
+fn parse_unique(puzzle: &str) -> Result<Grid, String> {
+    let grid =
+        Grid::from_string(puzzle.trim()).ok_or("puzzle must be 81 cells (digits, '.' or '0')")?;
+    match Solver::new().count_solutions(&grid, 2) {
+        1 => Ok(grid),
+        0 => Err("puzzle has no solution".into()),
+        _ => Err("puzzle has more than one solution".into()),
+    }
+}
+
+fn parse_difficulty(name: &str) -> Result<Difficulty, String> {
+    Difficulty::all_levels()
+        .iter()
+        .copied()
+        .find(|d| d.to_string().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("unknown difficulty \"{}\"", name))
+}
+
+fn describe(id: PuzzleId) -> Result<Puzzle, String> {
+    let grid = id.generate();
+    let solver = Solver::new();
+    let solution = solver.solve(&grid).ok_or("unsolvable")?;
+    Ok(Puzzle {
+        puzzle: grid.to_string_compact(),
+        solution: solution.to_string_compact(),
+        difficulty: solver.rate_difficulty(&grid).to_string(),
+        se_rating: solver.rate_se(&grid),
+        short_code: Some(id.to_short_code()),
+    })
+}
+
+struct Engine;
+
+impl Guest for Engine {
+    fn generate(difficulty: String) -> Result<Puzzle, String> {
+        describe(PuzzleId::random(parse_difficulty(&difficulty)?))
+    }
+
+    fn from_short_code(code: String) -> Result<Puzzle, String> {
+        let id = PuzzleId::from_short_code(code.trim())
+            .ok_or_else(|| format!("invalid short code \"{}\"", code))?;
+        describe(id)
+    }
+
+    fn daily(date: String) -> Result<Puzzle, String> {
+        let day =
+            sudoku_daily::parse_date(&date).ok_or("date must be YYYY-MM-DD, from 1970-01-01")?;
+        describe(sudoku_daily::puzzle_id(day))
+    }
+
+    fn solve(puzzle: String) -> Result<String, String> {
+        let grid = parse_unique(&puzzle)?;
+        let solution = Solver::new().solve(&grid).ok_or("puzzle has no solution")?;
+        Ok(solution.to_string_compact())
+    }
+
+    fn rate(puzzle: String) -> Result<Rating, String> {
+        let grid = parse_unique(&puzzle)?;
+        let solver = Solver::new();
+        Ok(Rating {
+            difficulty: solver.rate_difficulty(&grid).to_string(),
+            se_rating: solver.rate_se(&grid),
+            clues: grid.values().iter().flatten().flatten().count() as u32,
+        })
+    }
+}
+
+export!(Engine);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sudoku_test_utils::Tier;
+
+    const PUZZLE: &str = Tier::Singles.puzzle();
+
+    #[test]
+    fn test_engine() {
+        let solution = Engine::solve(PUZZLE.into()).unwrap();
+        assert!(solution.starts_with("534678912"));
+        assert_eq!(Engine::rate(PUZZLE.into()).unwrap().clues, 30);
+        assert!(Engine::solve("123".into()).is_err());
+        assert!(Engine::generate("impossible".into()).is_err());
+
+        let daily = Engine::daily("2024-01-01".into()).unwrap();
+        let again = Engine::from_short_code(daily.short_code.unwrap()).unwrap();
+        assert_eq!(daily.puzzle, again.puzzle);
+    }
+}
@@ -0,0 +1,168 @@
+//! Natural-language descriptions of the board for screen readers
+//!
+//! The canvas is invisible to assistive tech, so the host page mirrors these
+//! strings into an ARIA live region.
+
+use crate::game::{GameState, ScreenState};
+use sudoku_core::Position;
+
+/// State captured before an action so the change can be announced afterwards
+pub struct Snapshot {
+    cursor: Position,
+    screen: ScreenState,
+    value: Option<u8>,
+    candidates: Vec<u8>,
+}
+
+impl Snapshot {
+    pub fn capture(state: &GameState) -> Self {
+        let cursor = state.cursor();
+        Self {
+            cursor,
+            screen: state.screen(),
+            value: state.grid().get(cursor),
+            candidates: state.grid().cell(cursor).candidates().iter().collect(),
+        }
+    }
+}
+
+/// "Row 4, column 7"
+fn cell_name(pos: Position) -> String {
+    format!("Row {}, column {}", pos.row + 1, pos.col + 1)
+}
+
+/// Join digits as "1, 3, 7"
+fn digit_list(digits: &[u8]) -> String {
+    digits
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Units a placed value conflicts in, e.g. "conflict in row and box"
+fn conflict_phrase(state: &GameState, pos: Position) -> Option<String> {
+    let [row, col, bx] = state.conflict_units(pos);
+    let units: Vec<&str> = [(row, "row"), (col, "column"), (bx, "box")]
+        .into_iter()
+        .filter(|(hit, _)| *hit)
+        .map(|(_, name)| name)
+        .collect();
+    if units.is_empty() {
+        None
+    } else {
+        Some(format!("conflict in {}", units.join(" and ")))
+    }
+}
+
+/// Describe a single cell: its value (given or entered), conflicts, or notes
+pub fn describe_cell(state: &GameState, pos: Position) -> String {
+    let cell = state.grid().cell(pos);
+    let name = cell_name(pos);
+    match cell.value() {
+        Some(v) if cell.is_given() => format!("{}: given {}", name, v),
+        Some(v) => match conflict_phrase(state, pos) {
+            Some(conflict) => format!("{}: {}, {}", name, v, conflict),
+            None => format!("{}: {}", name, v),
+        },
+        None => {
+            let notes: Vec<u8> = cell.candidates().iter().collect();
+            if notes.is_empty() {
+                format!("{}: empty", name)
+            } else {
+                format!("{}: empty, notes {}", name, digit_list(&notes))
+            }
+        }
+    }
+}
+
+/// Describe the whole board: a summary line followed by one line per row
+pub fn describe_board(state: &GameState) -> String {
+    let filled = (0..81)
+        .filter(|&i| state.grid().get(Position::new(i / 9, i % 9)).is_some())
+        .count();
+    let mut lines = vec![format!(
+        "{} puzzle, {} of 81 cells filled, {}, time {}",
+        state.difficulty(),
+        filled,
+        match state.settings().mistake_limit() {
+            Some(limit) => format!("{} of {} mistakes", state.mistakes(), limit),
+            None => format!("{} mistakes", state.mistakes()),
+        },
+        state.elapsed_string()
+    )];
+    for row in 0..9 {
+        let cells: Vec<String> = (0..9)
+            .map(|col| match state.grid().get(Position::new(row, col)) {
+                Some(v) => v.to_string(),
+                None => "blank".to_string(),
+            })
+            .collect();
+        lines.push(format!("Row {}: {}", row + 1, cells.join(", ")));
+    }
+    lines.join(".\n")
+}
+
+/// Describe the current screen when it changes
+fn describe_screen(state: &GameState) -> String {
+    match state.screen() {
+        ScreenState::Playing => format!("Playing. {}", describe_cell(state, state.cursor())),
+        ScreenState::Paused => "Paused. Press P to resume".to_string(),
+        ScreenState::Win => format!(
+            "Puzzle solved in {} with {} hints and {} mistakes",
+            state.elapsed_string(),
+            state.hints_used(),
+            state.mistakes()
+        ),
+        ScreenState::Lose => "Game over, too many mistakes".to_string(),
+        ScreenState::Menu => "New game menu. Press 1 to 6 to choose a difficulty".to_string(),
+        ScreenState::Stats => "Statistics".to_string(),
+        ScreenState::Loading => "Generating puzzle".to_string(),
+        ScreenState::Replay => "Replay. Press space to play or pause".to_string(),
+        ScreenState::Trainer => match state.trainer() {
+            Some(trainer) => format!(
+                "{} trainer. Mark the cells the technique acts on with space, then press enter",
+                trainer.technique()
+            ),
+            None => "Trainer".to_string(),
+        },
+    }
+}
+
+/// Build the announcement for what changed since `before`, if anything did
+pub fn announce_change(state: &GameState, before: &Snapshot) -> Option<String> {
+    if state.screen() != before.screen {
+        return Some(describe_screen(state));
+    }
+    if state.screen() != ScreenState::Playing {
+        return None;
+    }
+
+    let cursor = state.cursor();
+    if cursor != before.cursor {
+        return Some(describe_cell(state, cursor));
+    }
+
+    let name = cell_name(cursor);
+    let value = state.grid().get(cursor);
+    if value != before.value {
+        return Some(match value {
+            Some(v) => match conflict_phrase(state, cursor) {
+                Some(conflict) => format!("{} set to {}, {}", name, v, conflict),
+                None => format!("{} set to {}", name, v),
+            },
+            None => format!("{} cleared", name),
+        });
+    }
+
+    let candidates: Vec<u8> = state.grid().cell(cursor).candidates().iter().collect();
+    if candidates != before.candidates {
+        return Some(if candidates.is_empty() {
+            format!("{} notes cleared", name)
+        } else {
+            format!("{} notes {}", name, digit_list(&candidates))
+        });
+    }
+
+    None
+}
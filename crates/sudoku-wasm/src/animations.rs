@@ -53,6 +53,11 @@ pub struct Particle {
 }
 
 impl Particle {
+    /// Position `alpha` (0..1) of the way towards the next logic step
+    pub fn position_at(&self, alpha: f32) -> (f32, f32) {
+        (self.x + self.vx * alpha, self.y + self.vy * alpha)
+    }
+
     pub fn is_visible(&self, width: f32, height: f32) -> bool {
         self.x >= -10.0
             && self.x < width + 10.0
@@ -257,6 +262,8 @@ pub struct WinScreen {
     pub width: f32,
     pub height: f32,
     rng: AnimRng,
+    /// Hold a still frame: no particles, color cycling or effect switching
+    reduced_motion: bool,
 }
 
 impl WinScreen {
@@ -279,6 +286,7 @@ impl WinScreen {
             width: 800.0,
             height: 600.0,
             rng,
+            reduced_motion: false,
         }
     }
 
@@ -297,7 +305,17 @@ impl WinScreen {
         self.height = height;
     }
 
+    pub fn set_reduced_motion(&mut self, reduced: bool) {
+        self.reduced_motion = reduced;
+        if reduced {
+            self.particles.clear();
+        }
+    }
+
     pub fn update(&mut self) {
+        if self.reduced_motion {
+            return;
+        }
         self.frame_count += 1;
         self.rainbow_offset += 0.02;
 
@@ -434,6 +452,8 @@ pub struct LoseScreen {
     pub width: f32,
     pub height: f32,
     rng: AnimRng,
+    /// Skip the rain and debris
+    reduced_motion: bool,
 }
 
 const RAIN_CHARS: &[char] = &['│', '╎', '┊', '┆', '|', '.'];
@@ -447,6 +467,7 @@ impl LoseScreen {
             width: 800.0,
             height: 600.0,
             rng: AnimRng::new(seed),
+            reduced_motion: false,
         }
     }
 
@@ -455,7 +476,17 @@ impl LoseScreen {
         self.height = height;
     }
 
+    pub fn set_reduced_motion(&mut self, reduced: bool) {
+        self.reduced_motion = reduced;
+        if reduced {
+            self.particles.clear();
+        }
+    }
+
     pub fn update(&mut self) {
+        if self.reduced_motion {
+            return;
+        }
         self.frame_count += 1;
 
         // Update particles
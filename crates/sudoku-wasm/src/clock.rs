@@ -0,0 +1,49 @@
+//! Fixed-step frame clock
+//!
+//! Game logic (message timers, particle physics) advances in fixed steps of
+//! `1000 / LOGIC_HZ` ms whatever the display refresh rate; rendering uses
+//! `alpha()` to interpolate between steps.
+
+/// Logic steps per second
+pub const LOGIC_HZ: f64 = 60.0;
+const STEP_MS: f64 = 1000.0 / LOGIC_HZ;
+/// Most steps to run in one frame; a larger backlog (e.g. after the tab was
+/// throttled) is dropped rather than fast-forwarded
+const MAX_STEPS_PER_FRAME: u32 = 8;
+
+#[derive(Debug, Default)]
+pub struct FrameClock {
+    /// Time not yet consumed by a logic step
+    accumulator_ms: f64,
+    /// Timestamp of the previous frame when the host doesn't pass a delta
+    last_frame_ms: Option<f64>,
+}
+
+impl FrameClock {
+    /// Add `dt_ms` of elapsed time and return how many logic steps to run
+    pub fn advance(&mut self, dt_ms: f64) -> u32 {
+        if dt_ms.is_finite() {
+            self.accumulator_ms += dt_ms.max(0.0);
+        }
+        let steps = (self.accumulator_ms / STEP_MS).floor() as u32;
+        if steps > MAX_STEPS_PER_FRAME {
+            self.accumulator_ms = 0.0;
+            MAX_STEPS_PER_FRAME
+        } else {
+            self.accumulator_ms -= steps as f64 * STEP_MS;
+            steps
+        }
+    }
+
+    /// Advance to wall-clock time `now_ms`, measuring the delta ourselves
+    pub fn advance_to(&mut self, now_ms: f64) -> u32 {
+        let dt = self.last_frame_ms.map_or(STEP_MS, |last| now_ms - last);
+        self.last_frame_ms = Some(now_ms);
+        self.advance(dt)
+    }
+
+    /// Progress from the last logic step towards the next (0..1)
+    pub fn alpha(&self) -> f64 {
+        (self.accumulator_ms / STEP_MS).clamp(0.0, 1.0)
+    }
+}
@@ -1,17 +1,32 @@
 //! Game state management for WASM Sudoku
 
+use crate::a11y;
 use crate::animations::{LoseScreen, WinScreen};
+use crate::rating::{self, TechniqueCount};
+use crate::replay::Replay;
+use crate::trainer::Trainer;
 use serde::{Deserialize, Serialize};
+use std::cell::{OnceCell, RefCell};
 use sudoku_core::{
     BitSet, Difficulty, Generator, Grid, Hint, HintType, Position, PuzzleId, Solver,
 };
+use sudoku_session::{
+    Bookmark, ConflictKind, GameMode, GameSession, GameSettings, MinuteMetrics, Score,
+    DEFAULT_MISTAKE_LIMIT,
+};
 
-/// Maximum mistakes before game over
-pub const MAX_MISTAKES: usize = 3;
+/// Maximum mistakes before game over under the default settings
+pub const MAX_MISTAKES: usize = DEFAULT_MISTAKE_LIMIT as usize;
 
 /// Estimated total puzzles in the puzzle universe (~10^30)
 pub const TOTAL_PUZZLE_UNIVERSE: f64 = 1e30;
 
+/// Number of colors available for cell coloring
+pub const CELL_COLOR_COUNT: u8 = 8;
+
+/// Default seconds without input before auto-pause kicks in
+pub const DEFAULT_IDLE_TIMEOUT_SECS: u32 = 120;
+
 /// Level of hint detail shown to the player
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HintDetailLevel {
@@ -32,6 +47,9 @@ pub struct MoveLogEntry {
     pub cell: u8,
     /// What the player did
     pub action: MoveAction,
+    /// For a placement, the technique the solver could justify it with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub technique: Option<String>,
 }
 
 /// The action taken on a cell
@@ -47,6 +65,59 @@ pub enum MoveAction {
     Undo(Option<u8>),
     /// Redo restored cell to this value (None = cleared)
     Redo(Option<u8>),
+    /// Going back to a bookmark set cell to this value (None = cleared)
+    Bookmark(Option<u8>),
+}
+
+/// Gameplay milestone reported to the host page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// A digit was placed (by the player or a hint)
+    MoveMade {
+        row: usize,
+        col: usize,
+        value: u8,
+        correct: bool,
+    },
+    /// A placed digit doesn't match the solution
+    Mistake {
+        row: usize,
+        col: usize,
+        value: u8,
+        mistakes: usize,
+        /// None when there is no mistake limit
+        remaining: Option<usize>,
+    },
+    /// A placed digit clashes with a peer in the given units
+    Conflict {
+        row: usize,
+        col: usize,
+        value: u8,
+        in_row: bool,
+        in_col: bool,
+        in_box: bool,
+    },
+    RowCompleted { row: usize },
+    ColumnCompleted { col: usize },
+    BoxCompleted { box_index: usize },
+    PuzzleComplete {
+        elapsed_secs: u32,
+        mistakes: usize,
+        hints_used: usize,
+        score: u32,
+        /// Leaderboard the win counts for, e.g. "time_attack"
+        category: &'static str,
+    },
+    /// Too many mistakes, or a timed mode's clock ran out
+    GameOver { elapsed_secs: u32, mistakes: usize },
+    /// A correct placement made a technique available (with move feedback
+    /// on); `cells` as [row, col] pairs
+    TechniqueCreated {
+        technique: String,
+        message: String,
+        cells: Vec<(usize, usize)>,
+    },
 }
 
 /// Input mode
@@ -54,6 +125,8 @@ pub enum MoveAction {
 pub enum InputMode {
     Normal,
     Candidate,
+    /// Digits 1-8 paint the selected cells
+    Color,
 }
 
 /// Screen state
@@ -66,6 +139,8 @@ pub enum ScreenState {
     Menu,
     Stats,
     Loading,
+    Replay,
+    Trainer,
 }
 
 /// Player statistics for lifetime tracking
@@ -216,16 +291,30 @@ pub struct SerializableState {
     /// Whether secret difficulties are unlocked (backwards-compatible default)
     #[serde(default)]
     pub secrets_unlocked: bool,
+    /// Player cell colors, 81 palette indices (0 = none)
+    #[serde(default)]
+    pub cell_colors: Vec<u8>,
+    /// Assists and mistake rules the game was started with
+    #[serde(default)]
+    pub settings: GameSettings,
+    #[serde(default)]
+    pub score: Score,
+    #[serde(default)]
+    pub game_mode: GameMode,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    /// Activity per minute, for the post-game timeline
+    #[serde(default)]
+    pub timeline: Vec<MinuteMetrics>,
 }
 
 /// The game state
 pub struct GameState {
-    /// Current grid (player's progress)
-    grid: Grid,
+    /// Player's board with its solution, undo history, counts, clock and
+    /// assists
+    session: GameSession,
     /// Original puzzle (for reference)
     puzzle: Grid,
-    /// Solution
-    solution: Grid,
     /// Difficulty level
     difficulty: Difficulty,
     /// Cursor position
@@ -234,14 +323,6 @@ pub struct GameState {
     mode: InputMode,
     /// Screen state
     screen: ScreenState,
-    /// Start timestamp (ms since epoch)
-    start_time: f64,
-    /// Elapsed time when paused
-    paused_elapsed: f64,
-    /// Number of mistakes
-    mistakes: usize,
-    /// Number of hints used
-    hints_used: usize,
     /// Current message to display
     message: Option<String>,
     /// Message timer (ticks remaining)
@@ -250,10 +331,6 @@ pub struct GameState {
     current_hint: Option<Hint>,
     /// Hint detail level (Summary vs ProofDetail)
     hint_detail: HintDetailLevel,
-    /// Undo stack (position, old value, old candidates)
-    undo_stack: Vec<(Position, Option<u8>, BitSet)>,
-    /// Redo stack (position, old value, old candidates)
-    redo_stack: Vec<(Position, Option<u8>, BitSet)>,
     /// Animation frame counter
     frame: u32,
     /// Win screen animation
@@ -282,6 +359,36 @@ pub struct GameState {
     move_seq: u32,
     /// Deferred new-game request (difficulty the host should generate asynchronously)
     pending_new_game: Option<Difficulty>,
+    /// Active solve replay (when screen is Replay)
+    replay: Option<Replay>,
+    /// Pending screen-reader announcement for the last action
+    announcement: Option<String>,
+    /// Pause automatically when the page is hidden or the player goes idle
+    autopause: bool,
+    /// Seconds without input before auto-pausing (0 disables idle detection)
+    idle_timeout_secs: u32,
+    /// Timestamp of the last key press (ms)
+    last_input: f64,
+    /// Milestones not yet delivered to the host
+    events: Vec<GameEvent>,
+    /// Extra cells selected alongside the cursor (empty = cursor only)
+    selection: Vec<Position>,
+    /// Player cell colors as palette indices (0 = none, 1-8 = color)
+    cell_colors: [u8; 81],
+    /// Active technique trainer (when screen is Trainer)
+    trainer: Option<Trainer>,
+    /// Show the technique breakdown in the info panel
+    show_rating: bool,
+    /// Techniques on the puzzle's solve path (computed on first use)
+    technique_breakdown: OnceCell<Vec<TechniqueCount>>,
+    /// Difficulty of what's left, with the board it was rated for
+    remaining_difficulty: RefCell<Option<(String, Difficulty)>>,
+    /// Locale for technique feedback after correct moves; None when off
+    move_feedback: Option<String>,
+    /// Disable particles and animated effects
+    reduced_motion: bool,
+    /// Interpolation between logic steps for the frame being drawn (0..1)
+    frame_alpha: f64,
 }
 
 /// Konami code sequence: Up Up Down Down Left Right Left Right B A
@@ -313,23 +420,16 @@ impl GameState {
         let (_, se_rating) = solver.analyze(&puzzle);
 
         Self {
-            grid,
+            session: Self::start_session(grid, solution, difficulty),
             puzzle,
-            solution,
             difficulty,
             cursor: Position::new(4, 4),
             mode: InputMode::Normal,
             screen: ScreenState::Playing,
-            start_time: Self::now(),
-            paused_elapsed: 0.0,
-            mistakes: 0,
-            hints_used: 0,
             message: None,
             message_timer: 0,
             current_hint: None,
             hint_detail: HintDetailLevel::Summary,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
             frame: 0,
             win_screen: None,
             lose_screen: None,
@@ -344,6 +444,21 @@ impl GameState {
             move_log: Vec::new(),
             move_seq: 0,
             pending_new_game: None,
+            replay: None,
+            announcement: None,
+            autopause: false,
+            idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            last_input: Self::now(),
+            events: Vec::new(),
+            selection: Vec::new(),
+            cell_colors: [0; 81],
+            trainer: None,
+            show_rating: false,
+            technique_breakdown: OnceCell::new(),
+            remaining_difficulty: RefCell::new(None),
+            move_feedback: None,
+            reduced_motion: false,
+            frame_alpha: 0.0,
         }
     }
 
@@ -366,6 +481,10 @@ impl GameState {
     pub fn from_puzzle_string(puzzle: &str) -> Option<Self> {
         let puzzle_grid = Grid::from_string(puzzle)?;
         let solver = Solver::new();
+        // Only start puzzles with exactly one solution
+        if solver.count_solutions(&puzzle_grid, 2) != 1 {
+            return None;
+        }
         let solution = solver.solve(&puzzle_grid)?;
         let (difficulty, se_rating) = solver.analyze(&puzzle_grid);
 
@@ -373,23 +492,16 @@ impl GameState {
         grid.clear_all_candidates();
 
         Some(Self {
-            grid,
+            session: Self::start_session(grid, solution, difficulty),
             puzzle: puzzle_grid,
-            solution,
             difficulty,
             cursor: Position::new(4, 4),
             mode: InputMode::Normal,
             screen: ScreenState::Playing,
-            start_time: Self::now(),
-            paused_elapsed: 0.0,
-            mistakes: 0,
-            hints_used: 0,
             message: None,
             message_timer: 0,
             current_hint: None,
             hint_detail: HintDetailLevel::Summary,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
             frame: 0,
             win_screen: None,
             lose_screen: None,
@@ -404,9 +516,52 @@ impl GameState {
             move_log: Vec::new(),
             move_seq: 0,
             pending_new_game: None,
+            replay: None,
+            announcement: None,
+            autopause: false,
+            idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            last_input: Self::now(),
+            events: Vec::new(),
+            selection: Vec::new(),
+            cell_colors: [0; 81],
+            trainer: None,
+            show_rating: false,
+            technique_breakdown: OnceCell::new(),
+            remaining_difficulty: RefCell::new(None),
+            move_feedback: None,
+            reduced_motion: false,
+            frame_alpha: 0.0,
         })
     }
 
+    /// Create a game from a pencilmark grid (see [`parse_pencilmarks`]).
+    /// Single-digit cells become givens; the rest keep their listed notes.
+    pub fn from_pencilmarks(text: &str) -> Option<Self> {
+        let cells = parse_pencilmarks(text)?;
+        let puzzle: String = cells
+            .iter()
+            .map(|digits| match digits.as_slice() {
+                [d] => (b'0' + d) as char,
+                _ => '.',
+            })
+            .collect();
+        let mut state = Self::from_puzzle_string(&puzzle)?;
+
+        for (i, digits) in cells.iter().enumerate() {
+            if digits.len() > 1 {
+                let cell = state
+                    .session
+                    .grid_mut()
+                    .cell_mut(Position::new(i / 9, i % 9));
+                cell.set_candidates(BitSet::empty());
+                for &d in digits {
+                    cell.toggle_candidate(d);
+                }
+            }
+        }
+        Some(state)
+    }
+
     /// Create a game from a short code (e.g., "M1A2B3C4")
     pub fn from_short_code(code: &str) -> Option<Self> {
         let puzzle_id = PuzzleId::from_short_code(code)?;
@@ -422,23 +577,16 @@ impl GameState {
         grid.clear_all_candidates();
 
         Some(Self {
-            grid,
+            session: Self::start_session(grid, solution, difficulty),
             puzzle,
-            solution,
             difficulty,
             cursor: Position::new(4, 4),
             mode: InputMode::Normal,
             screen: ScreenState::Playing,
-            start_time: Self::now(),
-            paused_elapsed: 0.0,
-            mistakes: 0,
-            hints_used: 0,
             message: None,
             message_timer: 0,
             current_hint: None,
             hint_detail: HintDetailLevel::Summary,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
             frame: 0,
             win_screen: None,
             lose_screen: None,
@@ -453,6 +601,21 @@ impl GameState {
             move_log: Vec::new(),
             move_seq: 0,
             pending_new_game: None,
+            replay: None,
+            announcement: None,
+            autopause: false,
+            idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            last_input: Self::now(),
+            events: Vec::new(),
+            selection: Vec::new(),
+            cell_colors: [0; 81],
+            trainer: None,
+            show_rating: false,
+            technique_breakdown: OnceCell::new(),
+            remaining_difficulty: RefCell::new(None),
+            move_feedback: None,
+            reduced_motion: false,
+            frame_alpha: 0.0,
         })
     }
 
@@ -471,23 +634,16 @@ impl GameState {
         grid.clear_all_candidates();
 
         Some(Self {
-            grid,
+            session: Self::start_session(grid, solution, difficulty),
             puzzle,
-            solution,
             difficulty,
             cursor: Position::new(4, 4),
             mode: InputMode::Normal,
             screen: ScreenState::Playing,
-            start_time: Self::now(),
-            paused_elapsed: 0.0,
-            mistakes: 0,
-            hints_used: 0,
             message: None,
             message_timer: 0,
             current_hint: None,
             hint_detail: HintDetailLevel::Summary,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
             frame: 0,
             win_screen: None,
             lose_screen: None,
@@ -502,6 +658,21 @@ impl GameState {
             move_log: Vec::new(),
             move_seq: 0,
             pending_new_game: None,
+            replay: None,
+            announcement: None,
+            autopause: false,
+            idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            last_input: Self::now(),
+            events: Vec::new(),
+            selection: Vec::new(),
+            cell_colors: [0; 81],
+            trainer: None,
+            show_rating: false,
+            technique_breakdown: OnceCell::new(),
+            remaining_difficulty: RefCell::new(None),
+            move_feedback: None,
+            reduced_motion: false,
+            frame_alpha: 0.0,
         })
     }
 
@@ -511,37 +682,34 @@ impl GameState {
     }
 
     /// Get current timestamp in milliseconds
-    fn now() -> f64 {
+    pub(crate) fn now() -> f64 {
         web_sys::window()
             .and_then(|w| w.performance())
             .map(|p| p.now())
             .unwrap_or(0.0)
     }
 
+    /// Current timestamp in whole milliseconds, for the session clock
+    fn clock_ms() -> u64 {
+        Self::now().max(0.0) as u64
+    }
+
+    /// A session on a new board with its clock running
+    fn start_session(grid: Grid, solution: Grid, difficulty: Difficulty) -> GameSession {
+        let mut session = GameSession::new(grid, solution);
+        session.set_difficulty(difficulty);
+        session.start_clock(Self::clock_ms());
+        session
+    }
+
     /// Get elapsed time in seconds
     pub fn elapsed_secs(&self) -> u32 {
-        if self.screen == ScreenState::Paused
-            || self.screen == ScreenState::Win
-            || self.screen == ScreenState::Lose
-        {
-            (self.paused_elapsed / 1000.0) as u32
-        } else {
-            let elapsed = Self::now() - self.start_time + self.paused_elapsed;
-            (elapsed / 1000.0) as u32
-        }
+        (self.session.elapsed_ms(Self::clock_ms()) / 1000) as u32
     }
 
     /// Get elapsed time in milliseconds (for move log timestamps)
     fn elapsed_ms(&self) -> u32 {
-        let ms = if self.screen == ScreenState::Paused
-            || self.screen == ScreenState::Win
-            || self.screen == ScreenState::Lose
-        {
-            self.paused_elapsed
-        } else {
-            Self::now() - self.start_time + self.paused_elapsed
-        };
-        ms.max(0.0) as u32
+        self.session.elapsed_ms(Self::clock_ms()) as u32
     }
 
     /// Append a move to the log
@@ -551,6 +719,7 @@ impl GameState {
             ms: self.elapsed_ms(),
             cell: (pos.row * 9 + pos.col) as u8,
             action,
+            technique: None,
         };
         self.move_seq += 1;
         self.move_log.push(entry);
@@ -569,9 +738,20 @@ impl GameState {
         format!("{:02}:{:02}", mins, secs)
     }
 
-    /// Update game state (called each frame)
+    /// Set how far the frame being drawn is between logic steps (0..1)
+    pub fn set_frame_alpha(&mut self, alpha: f64) {
+        self.frame_alpha = alpha.clamp(0.0, 1.0);
+    }
+
+    pub fn frame_alpha(&self) -> f64 {
+        self.frame_alpha
+    }
+
+    /// Advance game logic by one fixed step (see `clock::LOGIC_HZ`)
     pub fn tick(&mut self) {
         self.frame = self.frame.wrapping_add(1);
+        // Keeps the activity timeline's minutes in step with the clock
+        self.session.sync_clock(Self::clock_ms());
 
         // Update message timer
         if self.message_timer > 0 {
@@ -581,11 +761,27 @@ impl GameState {
             }
         }
 
+        // Auto-pause after a stretch without input
+        if self.autopause
+            && self.idle_timeout_secs > 0
+            && self.screen == ScreenState::Playing
+            && Self::now() - self.last_input >= self.idle_timeout_secs as f64 * 1000.0
+        {
+            self.auto_pause("Paused (idle)");
+        }
+
         // Check win/lose conditions
         if self.screen == ScreenState::Playing {
             if self.is_complete() {
-                self.paused_elapsed += Self::now() - self.start_time;
+                self.session.pause_clock(Self::clock_ms());
                 self.screen = ScreenState::Win;
+                self.events.push(GameEvent::PuzzleComplete {
+                    elapsed_secs: self.elapsed_secs(),
+                    mistakes: self.session.mistakes(),
+                    hints_used: self.session.hints_used(),
+                    score: self.current_score(),
+                    category: self.game_mode().leaderboard_category(),
+                });
                 // Record the win
                 if !self.game_recorded {
                     self.player_stats
@@ -595,10 +791,16 @@ impl GameState {
                 }
                 // Create win screen animation
                 let seed = (Self::now() * 1000.0) as u64;
-                self.win_screen = Some(WinScreen::new(seed));
-            } else if self.mistakes >= MAX_MISTAKES {
-                self.paused_elapsed += Self::now() - self.start_time;
+                let mut win_screen = WinScreen::new(seed);
+                win_screen.set_reduced_motion(self.reduced_motion);
+                self.win_screen = Some(win_screen);
+            } else if self.is_game_over() {
+                self.session.pause_clock(Self::clock_ms());
                 self.screen = ScreenState::Lose;
+                self.events.push(GameEvent::GameOver {
+                    elapsed_secs: self.elapsed_secs(),
+                    mistakes: self.session.mistakes(),
+                });
                 // Record the loss
                 if !self.game_recorded {
                     self.player_stats
@@ -607,7 +809,16 @@ impl GameState {
                 }
                 // Create lose screen animation
                 let seed = (Self::now() * 1000.0) as u64;
-                self.lose_screen = Some(LoseScreen::new(seed));
+                let mut lose_screen = LoseScreen::new(seed);
+                lose_screen.set_reduced_motion(self.reduced_motion);
+                self.lose_screen = Some(lose_screen);
+            }
+        }
+
+        // Advance replay playback
+        if self.screen == ScreenState::Replay {
+            if let Some(ref mut replay) = self.replay {
+                replay.tick(Self::now());
             }
         }
 
@@ -628,14 +839,39 @@ impl GameState {
             self.hint_detail = HintDetailLevel::Summary;
         }
 
-        match self.screen {
+        self.announcement = None;
+        self.last_input = Self::now();
+        let before = a11y::Snapshot::capture(self);
+
+        let keep_going = match self.screen {
             ScreenState::Win | ScreenState::Lose => self.handle_endgame_key(key),
             ScreenState::Paused => self.handle_paused_key(key),
             ScreenState::Menu => self.handle_menu_key(key),
             ScreenState::Stats => self.handle_stats_key(key),
             ScreenState::Playing => self.handle_playing_key(key, shift, ctrl),
             ScreenState::Loading => true, // ignore input while loading
+            ScreenState::Replay => self.handle_replay_key(key),
+            ScreenState::Trainer => self.handle_trainer_key(key),
+        };
+
+        // Lead with what changed, followed by any message the action showed
+        if let Some(change) = a11y::announce_change(self, &before) {
+            self.announcement = Some(match self.announcement.take() {
+                Some(msg) => format!("{}. {}", change, msg),
+                None => change,
+            });
         }
+        keep_going
+    }
+
+    /// Take the screen-reader announcement produced by the last action, if any
+    pub fn take_announcement(&mut self) -> Option<String> {
+        self.announcement.take()
+    }
+
+    /// Drain milestones produced since the last call
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
     }
 
     fn handle_endgame_key(&mut self, key: &str) -> bool {
@@ -647,6 +883,9 @@ impl GameState {
         match key {
             "q" | "Q" | "Escape" => return false,
             "s" | "S" => self.screen = ScreenState::Stats,
+            "r" | "R" => {
+                self.start_replay(None);
+            }
             "n" | "N" | "Enter" | " " => self.request_new_game(self.difficulty),
             "1" => self.request_new_game(Difficulty::Beginner),
             "2" => self.request_new_game(Difficulty::Easy),
@@ -667,7 +906,7 @@ impl GameState {
             "s" => self.screen = ScreenState::Stats,
             "p" | " " | "Enter" => {
                 self.screen = ScreenState::Playing;
-                self.start_time = Self::now();
+                self.session.start_clock(Self::clock_ms());
             }
             _ => {}
         }
@@ -705,6 +944,59 @@ impl GameState {
         true
     }
 
+    fn handle_replay_key(&mut self, key: &str) -> bool {
+        let Some(replay) = self.replay.as_mut() else {
+            self.screen = ScreenState::Menu;
+            return true;
+        };
+        match key {
+            "ArrowRight" | "l" => {
+                replay.step();
+            }
+            "ArrowLeft" | "h" => {
+                replay.step_back();
+            }
+            " " | "Enter" => replay.toggle_playing(),
+            "Home" | "0" => replay.seek(0.0),
+            "End" => replay.seek(replay.duration_ms() as f64),
+            "+" | "=" => replay.set_speed(replay.speed() * 2.0),
+            "-" => replay.set_speed(replay.speed() / 2.0),
+            "Escape" | "q" | "r" => self.stop_replay(),
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_trainer_key(&mut self, key: &str) -> bool {
+        if self.trainer.is_none() {
+            self.screen = ScreenState::Menu;
+            return true;
+        }
+        match key {
+            "ArrowUp" | "k" => self.move_cursor(-1, 0),
+            "ArrowDown" | "j" => self.move_cursor(1, 0),
+            "ArrowLeft" | "h" => self.move_cursor(0, -1),
+            "ArrowRight" | "l" => self.move_cursor(0, 1),
+            " " => {
+                let cursor = self.cursor;
+                if let Some(ref mut trainer) = self.trainer {
+                    trainer.toggle_pick(cursor);
+                }
+            }
+            "Enter" => {
+                self.trainer_check();
+            }
+            "n" => {
+                if !self.trainer_next() {
+                    self.show_message("No more positions found");
+                }
+            }
+            "Escape" | "q" => self.stop_trainer(),
+            _ => {}
+        }
+        true
+    }
+
     /// Check Konami code progress. Returns true if the key was consumed.
     fn check_konami(&mut self, key: &str) -> bool {
         if self.secrets_unlocked {
@@ -731,6 +1023,13 @@ impl GameState {
             // Quit
             "q" if !shift && !ctrl => return false,
 
+            // Extend the selection (shift+arrows)
+            "ArrowUp" if shift => self.extend_selection(-1, 0),
+            "ArrowDown" if shift => self.extend_selection(1, 0),
+            "ArrowLeft" if shift => self.extend_selection(0, -1),
+            "ArrowRight" if shift => self.extend_selection(0, 1),
+            "Escape" if !self.selection.is_empty() => self.selection.clear(),
+
             // Navigation
             "ArrowUp" | "k" => self.move_cursor(-1, 0),
             "ArrowDown" | "j" => self.move_cursor(1, 0),
@@ -743,10 +1042,25 @@ impl GameState {
             "a" => self.jump_box(0, -1),
             "d" => self.jump_box(0, 1),
 
+            // Coloring mode: 1-8 paint, 0/Del erase
+            "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" if self.mode == InputMode::Color => {
+                self.paint_selected(key.parse::<u8>().unwrap());
+            }
+            "0" | "Delete" | "Backspace" if self.mode == InputMode::Color => {
+                self.paint_selected(0);
+            }
+
             // Number input
             "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" => {
                 let value = key.parse::<u8>().unwrap();
-                if shift || self.mode == InputMode::Candidate {
+                let candidate = shift || self.mode == InputMode::Candidate;
+                if !self.selection.is_empty() {
+                    if candidate {
+                        self.toggle_candidate_selected(value);
+                    } else {
+                        self.for_each_selected(|game| game.set_value(value));
+                    }
+                } else if candidate {
                     self.toggle_candidate(value);
                 } else {
                     self.set_value(value);
@@ -755,7 +1069,13 @@ impl GameState {
 
             // Clear cell
             "0" | "Delete" | "Backspace" => {
-                if self.mode == InputMode::Candidate {
+                if !self.selection.is_empty() {
+                    if self.mode == InputMode::Candidate {
+                        self.for_each_selected(Self::clear_candidates_undoable);
+                    } else {
+                        self.for_each_selected(Self::clear_cell);
+                    }
+                } else if self.mode == InputMode::Candidate {
                     self.clear_candidates();
                 } else {
                     self.clear_cell();
@@ -774,15 +1094,33 @@ impl GameState {
             "c" => {
                 self.mode = match self.mode {
                     InputMode::Normal => InputMode::Candidate,
-                    InputMode::Candidate => InputMode::Normal,
+                    InputMode::Candidate | InputMode::Color => InputMode::Normal,
                 };
                 let mode_name = match self.mode {
                     InputMode::Normal => "Normal",
                     InputMode::Candidate => "Candidate",
+                    InputMode::Color => "Color",
                 };
                 self.show_message(&format!("{} mode", mode_name));
             }
 
+            // Coloring mode toggle (o) / clear all colors (O)
+            "o" if !shift => {
+                self.mode = match self.mode {
+                    InputMode::Color => InputMode::Normal,
+                    _ => InputMode::Color,
+                };
+                if self.mode == InputMode::Color {
+                    self.show_message("Color mode: 1-8 paint, 0 erase");
+                } else {
+                    self.show_message("Normal mode");
+                }
+            }
+            "O" | "o" if shift => {
+                self.cell_colors = [0; 81];
+                self.show_message("Cleared colors");
+            }
+
             // Undo/Redo
             "u" => {
                 if self.undo() {
@@ -800,12 +1138,14 @@ impl GameState {
                 if self.current_hint.is_some() {
                     // Already showing a hint — upgrade to proof detail
                     self.hint_detail = HintDetailLevel::ProofDetail;
-                } else if let Some(hint) = self.get_hint() {
-                    self.current_hint = Some(hint);
-                    self.hint_detail = HintDetailLevel::Summary;
-                    self.hints_used += 1;
-                } else {
-                    self.show_message("No hint available");
+                } else if self.hint_allowed() {
+                    if let Some(hint) = self.get_hint() {
+                        self.current_hint = Some(hint);
+                        self.hint_detail = HintDetailLevel::Summary;
+                        self.session.record_hint();
+                    } else {
+                        self.show_message("No hint available");
+                    }
                 }
             }
 
@@ -822,13 +1162,13 @@ impl GameState {
 
             // Pause
             "p" => {
-                self.paused_elapsed += Self::now() - self.start_time;
+                self.session.pause_clock(Self::clock_ms());
                 self.screen = ScreenState::Paused;
             }
 
             // Stats
             "S" | "s" if shift => {
-                self.paused_elapsed += Self::now() - self.start_time;
+                self.session.pause_clock(Self::clock_ms());
                 self.screen = ScreenState::Stats;
             }
 
@@ -839,6 +1179,13 @@ impl GameState {
                 self.show_message(&format!("Ghost hints: {}", status));
             }
 
+            // Technique breakdown toggle
+            "t" => {
+                self.show_rating = !self.show_rating;
+                let status = if self.show_rating { "ON" } else { "OFF" };
+                self.show_message(&format!("Technique breakdown: {}", status));
+            }
+
             // Valid cells toggle
             "v" => {
                 self.show_valid_cells = !self.show_valid_cells;
@@ -852,12 +1199,14 @@ impl GameState {
     }
 
     fn move_cursor(&mut self, row_delta: i32, col_delta: i32) {
+        self.selection.clear();
         let new_row = (self.cursor.row as i32 + row_delta).clamp(0, 8) as usize;
         let new_col = (self.cursor.col as i32 + col_delta).clamp(0, 8) as usize;
         self.cursor = Position::new(new_row, new_col);
     }
 
     fn jump_box(&mut self, row_delta: i32, col_delta: i32) {
+        self.selection.clear();
         let box_row = (self.cursor.row / 3) as i32;
         let box_col = (self.cursor.col / 3) as i32;
 
@@ -867,18 +1216,154 @@ impl GameState {
         self.cursor = Position::new(new_box_row * 3 + 1, new_box_col * 3 + 1);
     }
 
+    /// Move the cursor while keeping the cells it passes selected
+    fn extend_selection(&mut self, row_delta: i32, col_delta: i32) {
+        let anchor = self.cursor;
+        let selection = std::mem::take(&mut self.selection);
+        self.move_cursor(row_delta, col_delta);
+        self.selection = selection;
+        for pos in [anchor, self.cursor] {
+            if !self.selection.contains(&pos) {
+                self.selection.push(pos);
+            }
+        }
+    }
+
+    /// Move the cursor to a cell, optionally adding it to the selection (pointer drag)
+    pub fn select_cell(&mut self, pos: Position, extend: bool) {
+        if self.screen == ScreenState::Trainer {
+            self.cursor = pos;
+            if let Some(ref mut trainer) = self.trainer {
+                trainer.toggle_pick(pos);
+            }
+            return;
+        }
+        if !matches!(self.screen, ScreenState::Playing) {
+            return;
+        }
+        if extend {
+            for p in [self.cursor, pos] {
+                if !self.selection.contains(&p) {
+                    self.selection.push(p);
+                }
+            }
+        } else {
+            self.selection.clear();
+        }
+        self.cursor = pos;
+    }
+
+    /// Paint every selected cell with a palette color (0 erases). Painting cells
+    /// that already all have the color erases them instead.
+    pub fn paint_selected(&mut self, color: u8) {
+        if color > CELL_COLOR_COUNT {
+            return;
+        }
+        let cells = self.selected_cells();
+        let idx = |p: Position| p.row * 9 + p.col;
+        let color = if color != 0 && cells.iter().all(|&p| self.cell_colors[idx(p)] == color) {
+            0
+        } else {
+            color
+        };
+        for pos in cells {
+            self.cell_colors[idx(pos)] = color;
+        }
+    }
+
+    /// Palette index painted on a cell (0 = none)
+    pub fn cell_color(&self, pos: Position) -> u8 {
+        self.cell_colors[pos.row * 9 + pos.col]
+    }
+
+    /// Cells covered by the selection (just the cursor when nothing extra is selected)
+    pub fn selected_cells(&self) -> Vec<Position> {
+        if self.selection.is_empty() {
+            vec![self.cursor]
+        } else {
+            self.selection.clone()
+        }
+    }
+
+    pub fn is_selected(&self, pos: Position) -> bool {
+        self.selection.contains(&pos)
+    }
+
+    /// Run a single-cell edit on every selected cell as one undo step
+    fn for_each_selected(&mut self, mut edit: impl FnMut(&mut Self)) {
+        let cursor = self.cursor;
+        let depth = self.session.undo_depth();
+        for pos in self.selected_cells() {
+            self.cursor = pos;
+            edit(self);
+        }
+        self.cursor = cursor;
+        self.session.merge_steps_since(depth);
+    }
+
+    /// Toggle a note across the selection: added to every empty cell unless
+    /// all of them already have it, in which case it's removed from all
+    fn toggle_candidate_selected(&mut self, value: u8) {
+        if !self.notes_allowed() {
+            return;
+        }
+        let cells: Vec<Position> = self
+            .selected_cells()
+            .into_iter()
+            .filter(|&p| !self.grid().cell(p).is_filled())
+            .collect();
+        if cells.is_empty() {
+            return;
+        }
+        let add = !cells
+            .iter()
+            .all(|&p| self.grid().cell(p).has_candidate(value));
+
+        self.session.edit(|grid| {
+            for pos in cells {
+                if grid.cell(pos).has_candidate(value) != add {
+                    grid.cell_mut(pos).toggle_candidate(value);
+                }
+            }
+        });
+    }
+
+    /// Clear a cell's notes, recording the change for undo
+    fn clear_candidates_undoable(&mut self) {
+        let cell = self.grid().cell(self.cursor);
+        if cell.is_given() || cell.is_filled() {
+            return;
+        }
+        let pos = self.cursor;
+        self.session
+            .edit(|grid| grid.cell_mut(pos).set_candidates(BitSet::empty()));
+    }
+
     fn set_value(&mut self, value: u8) {
-        let cell = self.grid.cell(self.cursor);
-        if cell.is_given() {
+        let grid = self.session.grid();
+        let cell = grid.cell(self.cursor);
+        if cell.is_given() || cell.value() == Some(value) {
             return;
         }
 
-        // Check if correct
-        let is_correct = self.solution.get(self.cursor) == Some(value);
+        // Explain the move against the board as it was before it
+        let is_correct = self.session.solution().get(self.cursor) == Some(value);
+        let feedback = match &self.move_feedback {
+            Some(locale) if is_correct => {
+                sudoku_glossary::after_placement(grid, self.cursor, value, locale)
+            }
+            _ => Vec::new(),
+        };
+        let technique = is_correct
+            .then(|| sudoku_glossary::justifying_technique(grid, self.cursor, value))
+            .flatten();
+
+        // The session records the undo step, counts the mistake and updates
+        // notes as the settings ask
+        self.session.place(self.cursor, value);
         if !is_correct {
-            self.mistakes += 1;
-            let remaining = MAX_MISTAKES.saturating_sub(self.mistakes);
-            if remaining > 0 {
+            let remaining = self.session.mistakes_left();
+            if let Some(remaining) = remaining.filter(|&left| left > 0) {
                 self.show_message(&format!(
                     "Incorrect! {} {} left",
                     remaining,
@@ -887,149 +1372,200 @@ impl GameState {
             }
         }
 
-        // Save for undo (including current candidates so they can be restored)
-        let old_value = self.grid.get(self.cursor);
-        let old_candidates = self.grid.cell(self.cursor).candidates();
-        self.undo_stack
-            .push((self.cursor, old_value, old_candidates));
-        self.redo_stack.clear();
-
-        // Set the value and remove it from peer candidates
-        self.grid.set_cell_unchecked(self.cursor, Some(value));
-        self.grid.update_candidates_after_move(self.cursor, value);
-
         // Log the move
         self.log_move(self.cursor, MoveAction::Place(value));
-    }
+        if let Some(entry) = self.move_log.last_mut() {
+            entry.technique = technique;
+        }
+        self.push_move_events(self.cursor, value, is_correct);
+        self.events
+            .extend(feedback.into_iter().map(|f| GameEvent::TechniqueCreated {
+                technique: f.technique,
+                message: f.message,
+                cells: f.cells.iter().map(|p| (p.row, p.col)).collect(),
+            }));
+    }
+
+    /// Queue the events a placement produces
+    fn push_move_events(&mut self, pos: Position, value: u8, correct: bool) {
+        let (row, col) = (pos.row, pos.col);
+        self.events.push(GameEvent::MoveMade {
+            row,
+            col,
+            value,
+            correct,
+        });
+        if !correct && self.session.settings().checks_mistakes() {
+            self.events.push(GameEvent::Mistake {
+                row,
+                col,
+                value,
+                mistakes: self.session.mistakes(),
+                remaining: self.session.mistakes_left(),
+            });
+        }
 
-    fn clear_cell(&mut self) {
-        let cell = self.grid.cell(self.cursor);
-        if cell.is_given() || cell.value().is_none() {
-            return;
+        let [in_row, in_col, in_box] = self.conflict_units(pos);
+        if in_row || in_col || in_box {
+            self.events.push(GameEvent::Conflict {
+                row,
+                col,
+                value,
+                in_row,
+                in_col,
+                in_box,
+            });
         }
 
-        let old_value = self.grid.get(self.cursor);
-        let old_candidates = self.grid.cell(self.cursor).candidates();
-        self.undo_stack
-            .push((self.cursor, old_value, old_candidates));
-        self.redo_stack.clear();
+        if correct {
+            let (br, bc) = (row / 3 * 3, col / 3 * 3);
+            let row_done = self.unit_solved((0..9).map(|c| Position::new(row, c)));
+            let col_done = self.unit_solved((0..9).map(|r| Position::new(r, col)));
+            let box_done =
+                self.unit_solved((0..9).map(|i| Position::new(br + i / 3, bc + i % 3)));
+            if row_done {
+                self.events.push(GameEvent::RowCompleted { row });
+            }
+            if col_done {
+                self.events.push(GameEvent::ColumnCompleted { col });
+            }
+            if box_done {
+                self.events.push(GameEvent::BoxCompleted {
+                    box_index: br + bc / 3,
+                });
+            }
+        }
+    }
 
-        self.grid.set_cell_unchecked(self.cursor, None);
+    /// Whether every cell in a unit holds its solution value
+    fn unit_solved(&self, mut cells: impl Iterator<Item = Position>) -> bool {
+        cells.all(|p| self.grid().get(p).is_some() && self.grid().get(p) == self.solution().get(p))
+    }
 
-        // Log the clear (old_value is always Some here due to guard above)
-        if let Some(v) = old_value {
+    fn clear_cell(&mut self) {
+        if let Some(v) = self.session.clear(self.cursor) {
             self.log_move(self.cursor, MoveAction::Clear(v));
         }
     }
 
     fn toggle_candidate(&mut self, value: u8) {
-        let cell = self.grid.cell(self.cursor);
-        if cell.is_given() || cell.is_filled() {
+        let cell = self.grid().cell(self.cursor);
+        if cell.is_given() || cell.is_filled() || !self.notes_allowed() {
             return;
         }
-        self.grid.cell_mut(self.cursor).toggle_candidate(value);
+        self.session
+            .grid_mut()
+            .cell_mut(self.cursor)
+            .toggle_candidate(value);
+        self.session.record_candidate_edit();
     }
 
     fn clear_candidates(&mut self) {
-        let cell = self.grid.cell(self.cursor);
+        let cell = self.grid().cell(self.cursor);
         if cell.is_given() || cell.is_filled() {
             return;
         }
-        self.grid
+        self.session
+            .grid_mut()
             .cell_mut(self.cursor)
             .set_candidates(BitSet::empty());
+        self.session.record_candidate_edit();
 
         self.show_message("Cleared notes");
     }
 
     fn fill_candidates(&mut self) {
-        let cell = self.grid.cell(self.cursor);
-        if cell.is_given() || cell.is_filled() {
+        let cell = self.grid().cell(self.cursor);
+        if cell.is_given() || cell.is_filled() || !self.notes_allowed() {
             return;
         }
-        let valid = self.grid.compute_candidates(self.cursor);
-        self.grid.cell_mut(self.cursor).set_candidates(valid);
+        let valid = self.grid().compute_candidates(self.cursor);
+        self.session
+            .grid_mut()
+            .cell_mut(self.cursor)
+            .set_candidates(valid);
+        self.session.record_candidate_edit();
 
         self.show_message("Filled valid notes");
     }
 
     fn fill_all_candidates(&mut self) {
-        self.grid.recalculate_candidates();
+        if !self.notes_allowed() {
+            return;
+        }
+        self.session.grid_mut().recalculate_candidates();
+        self.session.record_candidate_edit();
 
         self.show_message("Filled all notes");
     }
 
     fn clear_all_candidates(&mut self) {
-        self.grid.clear_all_candidates();
+        self.session.grid_mut().clear_all_candidates();
+        self.session.record_candidate_edit();
 
         self.show_message("Cleared all notes");
     }
 
     fn undo(&mut self) -> bool {
-        if let Some((pos, old_value, old_candidates)) = self.undo_stack.pop() {
-            let current_value = self.grid.get(pos);
-            let current_candidates = self.grid.cell(pos).candidates();
-            self.redo_stack
-                .push((pos, current_value, current_candidates));
-
-            self.grid.set_cell_unchecked(pos, old_value);
-            // Restore the cell's own candidates from before the move
-            if old_value.is_none() {
-                self.grid.cell_mut(pos).set_candidates(old_candidates);
-            }
-            // If restoring a value, remove it from peer candidates
-            if let Some(v) = old_value {
-                self.grid.update_candidates_after_move(pos, v);
-            }
-
-            self.log_move(pos, MoveAction::Undo(old_value));
-            true
-        } else {
-            false
+        let Some(changes) = self.session.undo() else {
+            return false;
+        };
+        // Notes-only steps change no values and aren't part of the move log
+        for change in changes {
+            self.log_move(change.pos, MoveAction::Undo(change.to));
         }
+        true
     }
 
     fn redo(&mut self) -> bool {
-        if let Some((pos, value, saved_candidates)) = self.redo_stack.pop() {
-            let current_value = self.grid.get(pos);
-            let current_candidates = self.grid.cell(pos).candidates();
-            self.undo_stack
-                .push((pos, current_value, current_candidates));
-
-            self.grid.set_cell_unchecked(pos, value);
-            // Restore the cell's candidates from the redo snapshot
-            if value.is_none() {
-                self.grid.cell_mut(pos).set_candidates(saved_candidates);
-            }
-            // If placing a value, remove it from peer candidates
-            if let Some(v) = value {
-                self.grid.update_candidates_after_move(pos, v);
-            }
+        let Some(changes) = self.session.redo() else {
+            return false;
+        };
+        for change in changes {
+            self.log_move(change.pos, MoveAction::Redo(change.to));
+        }
+        true
+    }
 
-            self.log_move(pos, MoveAction::Redo(value));
-            true
-        } else {
-            false
+    /// Whether the mode allows notes, saying so if it doesn't
+    fn notes_allowed(&mut self) -> bool {
+        let allowed = self.game_mode().allows_notes();
+        if !allowed {
+            self.show_message("No notes in Hardcore");
         }
+        allowed
+    }
+
+    /// Whether the mode leaves a hint to use, saying so if it doesn't
+    fn hint_allowed(&mut self) -> bool {
+        let allowed = self.session.can_hint();
+        if !allowed {
+            self.show_message("No hints left");
+        }
+        allowed
     }
 
     fn get_hint(&self) -> Option<Hint> {
         let solver = Solver::new();
-        solver.get_hint(&self.grid)
+        solver.get_hint(self.grid())
     }
 
     fn apply_hint(&mut self) -> Option<Position> {
+        if !self.hint_allowed() {
+            return None;
+        }
         let solver = Solver::new();
-        let hint = solver.get_next_placement(&self.grid)?;
-        self.hints_used += 1;
+        let hint = solver.get_next_placement(self.grid())?;
+        self.session.record_hint();
 
         match hint.hint_type {
             HintType::SetValue { pos, value } => {
                 // Use the original puzzle solution to determine the correct value.
                 // get_next_placement() solves the *current* grid (which may contain
                 // player mistakes), so its placement can disagree with the original
-                // solution. Always trust self.solution to avoid false "mistake" counts.
-                let correct_value = self.solution.get(pos).unwrap_or(value);
+                // solution. Always trust the session's solution to avoid false
+                // "mistake" counts.
+                let correct_value = self.solution().get(pos).unwrap_or(value);
                 self.cursor = pos;
                 self.set_value(correct_value);
 
@@ -1052,18 +1588,22 @@ impl GameState {
 
     fn show_message(&mut self, msg: &str) {
         self.message = Some(msg.to_string());
-        self.message_timer = 90; // ~3 seconds at 30fps
+        self.message_timer = 90; // 1.5 seconds of logic steps
+        self.announcement = Some(match self.announcement.take() {
+            Some(prev) => format!("{}. {}", prev, msg),
+            None => msg.to_string(),
+        });
     }
 
     // Getters
     pub fn grid(&self) -> &Grid {
-        &self.grid
+        self.session.grid()
     }
     pub fn puzzle(&self) -> &Grid {
         &self.puzzle
     }
     pub fn solution(&self) -> &Grid {
-        &self.solution
+        self.session.solution()
     }
     pub fn cursor(&self) -> Position {
         self.cursor
@@ -1081,6 +1621,50 @@ impl GameState {
         self.se_rating
     }
 
+    pub fn show_rating(&self) -> bool {
+        self.show_rating
+    }
+
+    /// Techniques needed to solve the puzzle, hardest first
+    pub fn technique_breakdown(&self) -> &[TechniqueCount] {
+        self.technique_breakdown
+            .get_or_init(|| rating::technique_breakdown(&self.puzzle))
+    }
+
+    /// Difficulty of what's left to solve, rated from the correct digits on
+    /// the board. Falls as the solve progresses; rated again only after the
+    /// board changes.
+    pub fn remaining_difficulty(&self) -> Difficulty {
+        let current: String = (0..81)
+            .map(|i| {
+                let pos = Position::new(i / 9, i % 9);
+                match self.grid().cell(pos).value() {
+                    Some(v) if self.solution().cell(pos).value() == Some(v) => char::from(b'0' + v),
+                    _ => '.',
+                }
+            })
+            .collect();
+        if let Some((board, difficulty)) = &*self.remaining_difficulty.borrow() {
+            if *board == current {
+                return *difficulty;
+            }
+        }
+        let difficulty = Grid::from_string(&current)
+            .map_or(self.difficulty, |grid| Solver::new().rate_difficulty(&grid));
+        *self.remaining_difficulty.borrow_mut() = Some((current, difficulty));
+        difficulty
+    }
+
+    /// SE rating, difficulty and technique breakdown as JSON
+    pub fn rating_json(&self) -> String {
+        serde_json::json!({
+            "se_rating": self.se_rating,
+            "difficulty": self.difficulty.to_string(),
+            "techniques": self.technique_breakdown(),
+        })
+        .to_string()
+    }
+
     /// Set a deferred new-game request and show the loading screen.
     /// The host (JS/Swift) should poll `take_pending_new_game()` and provide puzzle data.
     fn request_new_game(&mut self, difficulty: Difficulty) {
@@ -1094,10 +1678,13 @@ impl GameState {
     }
 
     pub fn mistakes(&self) -> usize {
-        self.mistakes
+        self.session.mistakes()
     }
     pub fn hints_used(&self) -> usize {
-        self.hints_used
+        self.session.hints_used()
+    }
+    pub fn current_score(&self) -> u32 {
+        self.session.current_score(Self::clock_ms())
     }
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
@@ -1111,6 +1698,21 @@ impl GameState {
     pub fn frame(&self) -> u32 {
         self.frame
     }
+    /// Turn off particles and animated effects (accessibility / low power)
+    pub fn set_reduced_motion(&mut self, reduced: bool) {
+        self.reduced_motion = reduced;
+        if let Some(ref mut win_screen) = self.win_screen {
+            win_screen.set_reduced_motion(reduced);
+        }
+        if let Some(ref mut lose_screen) = self.lose_screen {
+            lose_screen.set_reduced_motion(reduced);
+        }
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
     pub fn win_screen(&self) -> Option<&WinScreen> {
         self.win_screen.as_ref()
     }
@@ -1162,26 +1764,74 @@ impl GameState {
 
     /// Get ghost candidates for a cell (valid candidates computed from grid state)
     pub fn get_ghost_candidates(&self, pos: Position) -> Vec<u8> {
-        if self.grid.cell(pos).is_filled() || self.grid.cell(pos).is_given() {
+        if self.grid().cell(pos).is_filled() || self.grid().cell(pos).is_given() {
             return Vec::new();
         }
-        self.grid.compute_candidates(pos).iter().collect()
+        self.grid().compute_candidates(pos).iter().collect()
     }
 
     /// Check if a cell has only one valid candidate (naked single)
     pub fn is_naked_single(&self, pos: Position) -> bool {
-        if self.grid.cell(pos).is_filled() || self.grid.cell(pos).is_given() {
+        if self.grid().cell(pos).is_filled() || self.grid().cell(pos).is_given() {
             return false;
         }
-        self.grid.compute_candidates(pos).count() == 1
+        self.grid().compute_candidates(pos).count() == 1
     }
 
     pub fn is_complete(&self) -> bool {
-        self.grid.is_complete() && self.grid.validate().is_valid
+        self.session.is_complete()
     }
 
     pub fn is_game_over(&self) -> bool {
-        self.mistakes >= MAX_MISTAKES
+        self.session.is_lost() || self.is_out_of_time()
+    }
+
+    /// Whether a timed mode's clock has run out
+    pub fn is_out_of_time(&self) -> bool {
+        self.session.is_out_of_time(Self::clock_ms())
+    }
+
+    pub fn game_mode(&self) -> GameMode {
+        self.session.mode()
+    }
+
+    /// Play this game under `mode`; Hardcore wipes any notes
+    pub fn set_game_mode(&mut self, mode: GameMode) {
+        self.session.set_mode(mode);
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        self.session.bookmarks()
+    }
+
+    /// Save the board and notes as `name`, replacing a bookmark of that name
+    pub fn save_bookmark(&mut self, name: &str) {
+        self.session.save_bookmark(name);
+        self.show_message(&format!("Bookmarked \"{}\"", name));
+    }
+
+    /// Go back to bookmark `name` as one undo step; only while playing
+    pub fn restore_bookmark(&mut self, name: &str) -> bool {
+        if self.screen != ScreenState::Playing {
+            return false;
+        }
+        let Some(changes) = self.session.restore_bookmark(name) else {
+            return false;
+        };
+        for change in changes {
+            self.log_move(change.pos, MoveAction::Bookmark(change.to));
+        }
+        self.show_message(&format!("Back to \"{}\"", name));
+        true
+    }
+
+    pub fn delete_bookmark(&mut self, name: &str) -> bool {
+        self.session.remove_bookmark(name)
+    }
+
+    /// Placements, note edits and mistakes per minute of play
+    pub fn metrics_timeline(&self) -> Vec<MinuteMetrics> {
+        self.session.metrics_timeline()
     }
 
     pub fn is_paused(&self) -> bool {
@@ -1191,34 +1841,199 @@ impl GameState {
     pub fn toggle_pause(&mut self) {
         match self.screen {
             ScreenState::Playing => {
-                self.paused_elapsed += Self::now() - self.start_time;
+                self.session.pause_clock(Self::clock_ms());
                 self.screen = ScreenState::Paused;
             }
             ScreenState::Paused => {
-                self.start_time = Self::now();
+                self.session.start_clock(Self::clock_ms());
                 self.screen = ScreenState::Playing;
             }
             _ => {}
         }
     }
 
+    pub fn settings(&self) -> &GameSettings {
+        self.session.settings()
+    }
+
+    /// Change the assists and mistake rules, mid-game or for a new game
+    pub fn set_settings(&mut self, settings: GameSettings) {
+        self.session.set_settings(settings);
+    }
+
+    /// Report techniques each correct move makes available, worded in
+    /// `locale`; None turns it off
+    pub fn set_move_feedback(&mut self, locale: Option<String>) {
+        self.move_feedback = locale;
+    }
+
+    /// Enable or disable auto-pause on page hide and idle
+    pub fn set_autopause(&mut self, enabled: bool) {
+        self.autopause = enabled;
+        self.last_input = Self::now();
+    }
+
+    pub fn autopause(&self) -> bool {
+        self.autopause
+    }
+
+    /// Set the idle threshold in seconds (0 = only pause on page hide)
+    pub fn set_idle_timeout_secs(&mut self, secs: u32) {
+        self.idle_timeout_secs = secs;
+    }
+
+    pub fn idle_timeout_secs(&self) -> u32 {
+        self.idle_timeout_secs
+    }
+
+    /// Host notification that the page became visible or hidden
+    pub fn notify_visibility(&mut self, visible: bool) {
+        if visible {
+            // Don't count time spent away towards idle detection
+            self.last_input = Self::now();
+        } else if self.autopause && self.screen == ScreenState::Playing {
+            self.auto_pause("Paused (tab hidden)");
+        }
+    }
+
+    /// Pause a running game and tell the player why
+    fn auto_pause(&mut self, reason: &str) {
+        self.session.pause_clock(Self::clock_ms());
+        self.screen = ScreenState::Paused;
+        self.show_message(reason);
+    }
+
+    /// Start watching a recorded solve. Uses this game's move log when `log` is
+    /// None; a shared log must belong to the currently loaded puzzle.
+    pub fn start_replay(&mut self, log: Option<Vec<MoveLogEntry>>) -> bool {
+        if matches!(self.screen, ScreenState::Replay | ScreenState::Loading) {
+            return false;
+        }
+        let log = log.unwrap_or_else(|| self.move_log.clone());
+        if log.is_empty() {
+            self.show_message("Nothing to replay");
+            return false;
+        }
+        // Freeze the game timer while watching
+        if self.screen == ScreenState::Playing {
+            self.session.pause_clock(Self::clock_ms());
+        }
+        self.replay = Some(Replay::new(&self.puzzle, log, self.screen));
+        self.screen = ScreenState::Replay;
+        true
+    }
+
+    /// Close the replay and return to the screen it was opened from
+    pub fn stop_replay(&mut self) {
+        if let Some(replay) = self.replay.take() {
+            self.screen = replay.return_screen();
+            if self.screen == ScreenState::Playing {
+                self.session.start_clock(Self::clock_ms());
+            }
+        }
+    }
+
+    /// Apply the next recorded move. Returns false when there is nothing left.
+    pub fn replay_step(&mut self) -> bool {
+        self.replay.as_mut().is_some_and(|r| r.step())
+    }
+
+    /// Jump the replay to `t_ms` milliseconds into the solve
+    pub fn replay_seek(&mut self, t_ms: f64) {
+        if let Some(ref mut replay) = self.replay {
+            replay.seek(t_ms);
+        }
+    }
+
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    /// Open the technique trainer on positions where `technique` (e.g. "X-Wing")
+    /// is the next logical step. Returns false if no such position was found.
+    pub fn start_trainer(&mut self, technique: &str, difficulty: Difficulty) -> bool {
+        if matches!(
+            self.screen,
+            ScreenState::Trainer | ScreenState::Replay | ScreenState::Loading
+        ) {
+            return false;
+        }
+        let Some(trainer) = Trainer::new(technique, difficulty, self.screen) else {
+            self.show_message(&format!("No {} position found", technique));
+            return false;
+        };
+        // Freeze the game timer while training
+        if self.screen == ScreenState::Playing {
+            self.session.pause_clock(Self::clock_ms());
+        }
+        self.trainer = Some(trainer);
+        self.screen = ScreenState::Trainer;
+        self.selection.clear();
+        true
+    }
+
+    /// Close the trainer and return to the screen it was opened from
+    pub fn stop_trainer(&mut self) {
+        if let Some(trainer) = self.trainer.take() {
+            self.screen = trainer.return_screen();
+            if self.screen == ScreenState::Playing {
+                self.session.start_clock(Self::clock_ms());
+            }
+        }
+    }
+
+    /// Check the marked cells against the finding
+    pub fn trainer_check(&mut self) -> bool {
+        let Some(ref mut trainer) = self.trainer else {
+            return false;
+        };
+        let correct = trainer.check();
+        self.show_message(if correct { "Correct!" } else { "Not quite" });
+        correct
+    }
+
+    /// Load the next trainer position
+    pub fn trainer_next(&mut self) -> bool {
+        self.trainer.as_mut().is_some_and(|t| t.next_position())
+    }
+
+    pub fn trainer(&self) -> Option<&Trainer> {
+        self.trainer.as_ref()
+    }
+
     /// Check if a cell has a conflict
-    #[allow(clippy::needless_range_loop)]
     pub fn has_conflict(&self, pos: Position) -> bool {
-        if let Some(value) = self.grid.get(pos) {
-            let values = self.grid.values();
+        self.conflict_units(pos).contains(&true)
+    }
+
+    /// What's wrong with a cell, if the settings show it
+    pub fn conflict(&self, pos: Position) -> Option<ConflictKind> {
+        self.session.conflict(pos)
+    }
+
+    /// Every conflict the settings show, in reading order
+    pub fn conflicts(&self) -> Vec<(Position, ConflictKind)> {
+        self.session.conflicts()
+    }
+
+    /// Which units (row, column, box) repeat the value at `pos`
+    #[allow(clippy::needless_range_loop)]
+    pub fn conflict_units(&self, pos: Position) -> [bool; 3] {
+        let mut units = [false; 3];
+        if let Some(value) = self.grid().get(pos) {
+            let values = self.grid().values();
 
             // Row
             for col in 0..9 {
                 if col != pos.col && values[pos.row][col] == Some(value) {
-                    return true;
+                    units[0] = true;
                 }
             }
 
             // Column
             for row in 0..9 {
                 if row != pos.row && values[row][pos.col] == Some(value) {
-                    return true;
+                    units[1] = true;
                 }
             }
 
@@ -1228,12 +2043,12 @@ impl GameState {
             for row in box_row..box_row + 3 {
                 for col in box_col..box_col + 3 {
                     if (row != pos.row || col != pos.col) && values[row][col] == Some(value) {
-                        return true;
+                        units[2] = true;
                     }
                 }
             }
         }
-        false
+        units
     }
 
     /// Check if position is highlighted (same row/col/box as cursor)
@@ -1243,10 +2058,13 @@ impl GameState {
             || pos.box_index() == self.cursor.box_index()
     }
 
-    /// Check if position has same value as cursor
+    /// Check if position has same value as cursor (when that is highlighted)
     pub fn has_same_value(&self, pos: Position) -> bool {
-        if let Some(cursor_value) = self.grid.get(self.cursor) {
-            self.grid.get(pos) == Some(cursor_value)
+        if !self.settings().highlight_same_digit {
+            return false;
+        }
+        if let Some(cursor_value) = self.grid().get(self.cursor) {
+            self.grid().get(pos) == Some(cursor_value)
         } else {
             false
         }
@@ -1255,7 +2073,7 @@ impl GameState {
     /// Get completed numbers (all 9 placed)
     pub fn completed_numbers(&self) -> [bool; 9] {
         let mut counts = [0u8; 9];
-        let values = self.grid.values();
+        let values = self.grid().values();
 
         for row in &values {
             for v in row.iter().flatten() {
@@ -1272,8 +2090,8 @@ impl GameState {
     pub fn to_serializable(&self) -> SerializableState {
         SerializableState {
             puzzle: self.puzzle.to_string_compact(),
-            current: self.grid.to_string_compact(),
-            solution: self.solution.to_string_compact(),
+            current: self.grid().to_string_compact(),
+            solution: self.solution().to_string_compact(),
             difficulty: format!("{:?}", self.difficulty),
             cursor_row: self.cursor.row,
             cursor_col: self.cursor.col,
@@ -1281,13 +2099,27 @@ impl GameState {
             // Don't persist terminal states — on reload, go to menu instead
             screen: match self.screen {
                 ScreenState::Win | ScreenState::Lose => ScreenState::Menu,
+                ScreenState::Replay => match self.replay.as_ref().map(|r| r.return_screen()) {
+                    Some(ScreenState::Playing) => ScreenState::Playing,
+                    _ => ScreenState::Menu,
+                },
+                ScreenState::Trainer => match self.trainer.as_ref().map(|t| t.return_screen()) {
+                    Some(ScreenState::Playing) => ScreenState::Playing,
+                    _ => ScreenState::Menu,
+                },
                 other => other,
             },
             elapsed_secs: self.elapsed_secs(),
-            mistakes: self.mistakes,
-            hints_used: self.hints_used,
+            mistakes: self.mistakes(),
+            hints_used: self.hints_used(),
             message: self.message.clone(),
             secrets_unlocked: self.secrets_unlocked,
+            cell_colors: self.cell_colors.to_vec(),
+            settings: *self.settings(),
+            score: *self.session.score(),
+            game_mode: self.game_mode(),
+            bookmarks: self.bookmarks().to_vec(),
+            timeline: self.metrics_timeline(),
         }
     }
 
@@ -1303,6 +2135,11 @@ impl GameState {
             solver.solve(&puzzle).unwrap_or_else(|| puzzle.deep_clone())
         });
 
+        let mut cell_colors = [0u8; 81];
+        for (slot, &color) in cell_colors.iter_mut().zip(&state.cell_colors) {
+            *slot = if color <= CELL_COLOR_COUNT { color } else { 0 };
+        }
+
         let difficulty = match state.difficulty.as_str() {
             "Beginner" => Difficulty::Beginner,
             "Easy" => Difficulty::Easy,
@@ -1315,24 +2152,30 @@ impl GameState {
             _ => Difficulty::Medium,
         };
 
+        let mut session = GameSession::new(grid, solution);
+        session.set_mode(state.game_mode);
+        session.set_settings(state.settings);
+        session.set_bookmarks(state.bookmarks);
+        session.restore_timeline(state.timeline);
+        session.set_difficulty(difficulty);
+        session.restore_counts(state.mistakes, state.hints_used);
+        session.restore_score(state.score);
+        session.set_elapsed_ms(u64::from(state.elapsed_secs) * 1000, Self::clock_ms());
+        if state.screen != ScreenState::Paused {
+            session.start_clock(Self::clock_ms());
+        }
+
         Self {
-            grid,
+            session,
             puzzle,
-            solution,
             difficulty,
             cursor: Position::new(state.cursor_row.min(8), state.cursor_col.min(8)),
             mode: state.mode,
             screen: state.screen,
-            start_time: Self::now() - (state.elapsed_secs as f64 * 1000.0),
-            paused_elapsed: 0.0,
-            mistakes: state.mistakes,
-            hints_used: state.hints_used,
             message: state.message,
             message_timer: 0,
             current_hint: None,
             hint_detail: HintDetailLevel::Summary,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
             frame: 0,
             win_screen: None,
             lose_screen: None,
@@ -1347,6 +2190,21 @@ impl GameState {
             move_log: Vec::new(),
             move_seq: 0,
             pending_new_game: None,
+            replay: None,
+            announcement: None,
+            autopause: false,
+            idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            last_input: Self::now(),
+            events: Vec::new(),
+            selection: Vec::new(),
+            cell_colors,
+            trainer: None,
+            show_rating: false,
+            technique_breakdown: OnceCell::new(),
+            remaining_difficulty: RefCell::new(None),
+            move_feedback: None,
+            reduced_motion: false,
+            frame_alpha: 0.0,
         }
     }
 
@@ -1369,3 +2227,20 @@ impl GameState {
         self.secrets_unlocked = unlocked;
     }
 }
+
+/// Parse a pencilmark grid: 81 cells separated by whitespace, each listing the
+/// digits still possible there (a single digit is a solved cell). Box borders
+/// such as `|`, `+` and `---` lines are ignored.
+pub fn parse_pencilmarks(text: &str) -> Option<Vec<Vec<u8>>> {
+    let cells: Vec<Vec<u8>> = text
+        .split(|c: char| c.is_whitespace() || c == '|')
+        .filter(|token| !token.is_empty() && token.bytes().all(|b| (b'1'..=b'9').contains(&b)))
+        .map(|token| {
+            let mut digits: Vec<u8> = token.bytes().map(|b| b - b'0').collect();
+            digits.sort_unstable();
+            digits.dedup();
+            digits
+        })
+        .collect();
+    (cells.len() == 81).then_some(cells)
+}
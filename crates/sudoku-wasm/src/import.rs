@@ -0,0 +1,75 @@
+//! Forgiving reading of pasted puzzles. Text copied from web pages comes
+//! with grid borders, stray spaces and odd empty-cell markers; instead of a
+//! bare yes/no, `parse_lossy` reads what it can and says what it skipped.
+
+use serde::Serialize;
+
+/// Something `parse_lossy` had to work around
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ParseIssue {
+    /// A character that is neither a cell nor part of a drawn grid; skipped.
+    /// Lines and columns count from 1.
+    UnexpectedChar {
+        line: usize,
+        column: usize,
+        ch: char,
+    },
+    /// The text held this many cells instead of 81; missing cells were
+    /// left empty and extra ones dropped
+    WrongCellCount { found: usize },
+}
+
+/// An 81-character puzzle ("." for empty) and the issues met reading it
+#[derive(Debug, Clone, Serialize)]
+pub struct Lossy {
+    pub puzzle: String,
+    pub issues: Vec<ParseIssue>,
+}
+
+/// Characters used to draw grid borders, ignored anywhere
+fn is_border(c: char) -> bool {
+    c.is_whitespace() || "|+-=:".contains(c) || ('\u{2500}'..='\u{257f}').contains(&c)
+}
+
+/// Read a puzzle from messy text. Digits 1-9 are givens; `.`, `0`, `_` and
+/// `*` are empty cells; lines starting with `#` are comments.
+pub fn parse_lossy(text: &str) -> Lossy {
+    let mut cells = String::with_capacity(81);
+    let mut found = 0;
+    let mut issues = Vec::new();
+    for (l, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        for (c, ch) in line.chars().enumerate() {
+            let cell = match ch {
+                '1'..='9' => ch,
+                '.' | '0' | '_' | '*' => '.',
+                _ if is_border(ch) => continue,
+                _ => {
+                    issues.push(ParseIssue::UnexpectedChar {
+                        line: l + 1,
+                        column: c + 1,
+                        ch,
+                    });
+                    continue;
+                }
+            };
+            found += 1;
+            if cells.len() < 81 {
+                cells.push(cell);
+            }
+        }
+    }
+    if found != 81 {
+        issues.push(ParseIssue::WrongCellCount { found });
+        while cells.len() < 81 {
+            cells.push('.');
+        }
+    }
+    Lossy {
+        puzzle: cells,
+        issues,
+    }
+}
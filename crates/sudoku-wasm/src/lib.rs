@@ -3,21 +3,36 @@
 //! This crate provides a browser-based Sudoku game that looks and feels
 //! like the terminal UI version.
 
-use sudoku_core::{canonical_puzzle_hash_str, Difficulty, PuzzleId, Solver};
+use sudoku_core::{canonical_puzzle_hash_str, Difficulty, HintType, Position, PuzzleId, Solver};
+use sudoku_glossary::{i18n::DEFAULT_LOCALE, Verbosity};
+use sudoku_render::{Board, Style};
+use sudoku_session::{GameMode, GameSettings, RatingResult, SeSearch};
+use sudoku_streak::{Day, StreakTracker};
 use wasm_bindgen::prelude::*;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement, KeyboardEvent};
+use web_sys::{
+    CanvasRenderingContext2d, CustomEvent, CustomEventInit, HtmlCanvasElement, HtmlElement,
+    KeyboardEvent,
+};
 
+mod a11y;
 mod animations;
+mod clock;
 mod game;
+mod import;
+mod rating;
 mod render;
+mod replay;
+mod save;
 mod theme;
+mod trainer;
 
 // WASM tests require wasm-pack test to run
 #[cfg(all(test, target_arch = "wasm32"))]
 mod tests;
 
-pub use game::GameState;
-pub use theme::Theme;
+use clock::FrameClock;
+pub use game::{parse_pencilmarks, GameState};
+pub use theme::{FontConfig, Theme};
 
 // Initialize panic hook for better error messages
 #[wasm_bindgen(start)]
@@ -26,6 +41,12 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
+/// A4 page size in CSS pixels (210mm x 297mm at 96 dpi)
+const PRINT_WIDTH: u32 = 794;
+const PRINT_HEIGHT: u32 = 1123;
+/// Resolution multiplier for the print canvas
+const PRINT_SCALE: f64 = 2.0;
+
 /// The main WASM game controller
 #[wasm_bindgen]
 pub struct SudokuGame {
@@ -38,6 +59,17 @@ pub struct SudokuGame {
     width: u32,
     height: u32,
     dpr: f64, // Device pixel ratio for crisp rendering
+    /// Host preferences carried over when a new game replaces the state
+    autopause: bool,
+    idle_timeout_secs: u32,
+    reduced_motion: bool,
+    move_feedback: Option<String>,
+    settings: GameSettings,
+    game_mode: GameMode,
+    /// Host callback receiving gameplay events
+    event_callback: Option<js_sys::Function>,
+    /// Fixed-step clock driving `GameState::tick`
+    clock: FrameClock,
 }
 
 #[wasm_bindgen]
@@ -95,6 +127,14 @@ impl SudokuGame {
             width,
             height,
             dpr,
+            autopause: false,
+            idle_timeout_secs: game::DEFAULT_IDLE_TIMEOUT_SECS,
+            reduced_motion: false,
+            move_feedback: None,
+            settings: GameSettings::default(),
+            game_mode: GameMode::default(),
+            event_callback: None,
+            clock: FrameClock::default(),
         };
 
         game.render();
@@ -111,20 +151,75 @@ impl SudokuGame {
         let action = self.state.handle_key(&key, shift, ctrl);
 
         self.render();
+        self.dispatch_announcement();
+        self.dispatch_events();
         action
     }
 
-    /// Update game state (call from requestAnimationFrame)
+    /// Cell index (row * 9 + col) under canvas coordinates in CSS pixels, or -1
+    #[wasm_bindgen]
+    pub fn cell_at(&self, x: f64, y: f64) -> i32 {
+        let (grid_x, grid_y) = render::grid_origin(self.height, self.cell_size);
+        let col = ((x - grid_x) / self.cell_size).floor();
+        let row = ((y - grid_y) / self.cell_size).floor();
+        if (0.0..9.0).contains(&col) && (0.0..9.0).contains(&row) {
+            row as i32 * 9 + col as i32
+        } else {
+            -1
+        }
+    }
+
+    /// Move the cursor to a cell; with `extend` the cell joins the selection
+    /// (used for pointer drags). Digits and notes then apply to every selected cell.
+    #[wasm_bindgen]
+    pub fn select_cell(&mut self, row: usize, col: usize, extend: bool) {
+        self.state
+            .select_cell(Position::new(row.min(8), col.min(8)), extend);
+        self.render();
+    }
+
+    /// Paint the selected cells with palette color 1-8 (0 erases), e.g. from a palette click
     #[wasm_bindgen]
-    pub fn tick(&mut self) {
-        self.state.tick();
+    pub fn paint_cells(&mut self, color: u8) {
+        self.state.paint_selected(color);
         self.render();
     }
 
+    /// Describe one cell for screen readers (e.g. "Row 4, column 7: empty, notes 1, 3")
+    #[wasm_bindgen]
+    pub fn describe_cell(&self, row: usize, col: usize) -> String {
+        a11y::describe_cell(&self.state, Position::new(row.min(8), col.min(8)))
+    }
+
+    /// Describe the whole board for screen readers, one line per row
+    #[wasm_bindgen]
+    pub fn describe_board(&self) -> String {
+        a11y::describe_board(&self.state)
+    }
+
+    /// Advance and draw one frame (call from requestAnimationFrame). Pass the
+    /// milliseconds since the previous frame; when omitted the delta is measured.
+    /// Logic runs in fixed steps so pace is the same at any refresh rate.
+    #[wasm_bindgen]
+    pub fn tick(&mut self, dt_ms: Option<f64>) {
+        let steps = match dt_ms {
+            Some(dt) => self.clock.advance(dt),
+            None => self.clock.advance_to(GameState::now()),
+        };
+        for _ in 0..steps {
+            self.state.tick();
+        }
+        self.state.set_frame_alpha(self.clock.alpha());
+        self.render();
+        self.dispatch_announcement();
+        self.dispatch_events();
+    }
+
     /// Start a new game with specified difficulty
     #[wasm_bindgen]
     pub fn new_game(&mut self, difficulty: &str) {
         self.state = GameState::new(parse_difficulty(difficulty));
+        self.apply_host_settings();
         self.render();
     }
 
@@ -135,6 +230,7 @@ impl SudokuGame {
             // Preserve player stats
             new_state.load_stats_json(&self.state.stats_json());
             self.state = new_state;
+            self.apply_host_settings();
             self.render();
             true
         } else {
@@ -142,6 +238,35 @@ impl SudokuGame {
         }
     }
 
+    /// Load a puzzle as an 81-character string, a pencilmark grid (81
+    /// whitespace-separated candidate lists) or a drawn grid with borders.
+    /// Rejects puzzles without exactly one solution. Returns true on
+    /// success; `diagnose_puzzle` explains a failure.
+    #[wasm_bindgen]
+    pub fn load_puzzle(&mut self, puzzle: &str) -> bool {
+        let compact: String = puzzle.chars().filter(|c| !c.is_whitespace()).collect();
+        let new_state = if compact.len() == 81 {
+            GameState::from_puzzle_string(&compact)
+        } else {
+            GameState::from_pencilmarks(puzzle).or_else(|| {
+                let lossy = import::parse_lossy(puzzle);
+                lossy
+                    .issues
+                    .is_empty()
+                    .then(|| GameState::from_puzzle_string(&lossy.puzzle))
+                    .flatten()
+            })
+        };
+        let Some(mut new_state) = new_state else {
+            return false;
+        };
+        new_state.load_stats_json(&self.state.stats_json());
+        self.state = new_state;
+        self.apply_host_settings();
+        self.render();
+        true
+    }
+
     /// Get the current puzzle as an 81-character string
     #[wasm_bindgen]
     pub fn get_puzzle_string(&self) -> String {
@@ -154,6 +279,7 @@ impl SudokuGame {
         if let Some(mut new_state) = GameState::from_short_code(code) {
             new_state.load_stats_json(&self.state.stats_json());
             self.state = new_state;
+            self.apply_host_settings();
             self.render();
             true
         } else {
@@ -167,35 +293,136 @@ impl SudokuGame {
         self.state.short_code().unwrap_or_default()
     }
 
+    /// The current board as an SVG image, with pencil marks, the cursor and
+    /// any hint on screen, for sharing or saving
+    #[wasm_bindgen]
+    pub fn export_svg(&self) -> String {
+        let mut board = Board::from_grid(self.state.grid());
+        let cursor = self.state.cursor();
+        board.select(cursor.row * 9 + cursor.col);
+        if let Some(hint) = self.state.current_hint() {
+            board.show_hint(hint);
+        }
+        sudoku_render::to_svg(&sudoku_render::layout(&board, &Style::default()))
+    }
+
+    /// Text of the hint on screen at a verbosity: "brief", "standard" or
+    /// "step-by-step" (numbered lines), with the technique and step wording
+    /// in `locale` where registered. Empty if no hint is showing.
+    #[wasm_bindgen]
+    pub fn hint_text(&self, verbosity: &str, locale: Option<String>) -> String {
+        let verbosity = Verbosity::from_name(verbosity).unwrap_or_default();
+        let locale = locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        self.state.current_hint().map_or_else(String::new, |hint| {
+            sudoku_glossary::explain_in(hint, verbosity, locale)
+        })
+    }
+
     /// Set the color theme
     #[wasm_bindgen]
     pub fn set_theme(&mut self, theme_name: &str) {
+        let font = self.theme.font.clone();
         self.theme = match theme_name {
             "light" | "ukodus" => Theme::light(),
             "high_contrast" => Theme::high_contrast(),
             _ => Theme::dark(),
         };
+        self.theme.font = font;
         self.render();
     }
 
-    /// Get current game state as JSON
+    /// Set the board typography: CSS font family, digit weight ("normal",
+    /// "bold", "100"-"900") and note size as a fraction of the digit size.
+    /// Invalid parts fall back to the defaults.
+    #[wasm_bindgen]
+    pub fn set_font(&mut self, family: &str, digit_weight: &str, note_scale: f64) {
+        self.theme.font = FontConfig::new(family, digit_weight, note_scale);
+        self.render();
+    }
+
+    /// Get current game state as JSON (versioned save envelope)
     #[wasm_bindgen]
     pub fn get_state_json(&self) -> String {
-        serde_json::to_string(&self.state.to_serializable()).unwrap_or_default()
+        save::encode(&self.state.to_serializable())
     }
 
-    /// Load game state from JSON
+    /// Load game state from JSON. Older save versions are migrated and damaged
+    /// saves repaired; returns false if the save can't be used.
     #[wasm_bindgen]
     pub fn load_state_json(&mut self, json: &str) -> bool {
-        if let Ok(state) = serde_json::from_str(json) {
-            self.state = GameState::from_serializable(state);
-            self.render();
-            true
-        } else {
-            false
+        match save::decode(json) {
+            Ok(state) => {
+                // A resumed game keeps the mode and assists it was saved with
+                self.state = GameState::from_serializable(state);
+                self.apply_host_preferences();
+                self.render();
+                true
+            }
+            Err(reason) => {
+                web_sys::console::warn_1(&format!("Ignoring saved game: {}", reason).into());
+                false
+            }
         }
     }
 
+    /// Get the puzzle's SE rating, difficulty and technique histogram as JSON:
+    /// `{ se_rating, difficulty, techniques: [{ name, se_rating, count }] }`
+    #[wasm_bindgen]
+    pub fn get_rating_json(&self) -> String {
+        self.state.rating_json()
+    }
+
+    /// Every step from the board as it stands to the solution, for a
+    /// walkthrough, as JSON: `{ solved, steps: [{ technique, se_rating,
+    /// explanation, row, col, value, eliminate }] }`. `value` is null for
+    /// eliminations; `solved` is false if the techniques get stuck.
+    #[wasm_bindgen]
+    pub fn solve_path_json(&self) -> String {
+        let path = sudoku_session::solve_path(self.state.grid());
+        let steps: Vec<_> = path
+            .steps
+            .iter()
+            .map(|hint| {
+                let (pos, value, eliminate) = match &hint.hint_type {
+                    HintType::SetValue { pos, value } => (pos, Some(*value), Vec::new()),
+                    HintType::EliminateCandidates { pos, values } => (pos, None, values.clone()),
+                };
+                serde_json::json!({
+                    "technique": hint.technique.to_string(),
+                    "se_rating": hint.technique.se_rating(),
+                    "explanation": hint.explanation,
+                    "row": pos.row,
+                    "col": pos.col,
+                    "value": value,
+                    "eliminate": eliminate,
+                })
+            })
+            .collect();
+        serde_json::json!({ "solved": path.solved, "steps": steps }).to_string()
+    }
+
+    /// SE rating of the board as it stands from at most `max_steps` solver
+    /// steps, as JSON: `{ complete, se_rating }`. When `complete` is false,
+    /// `se_rating` is the hardest step before the cap. This caps the number
+    /// of steps; one step on a very hard board can still take a while.
+    #[wasm_bindgen]
+    pub fn rate_se_by_path_json(&self, max_steps: usize) -> String {
+        let (complete, se_rating) =
+            match sudoku_session::rate_se_by_path(self.state.grid(), max_steps) {
+                RatingResult::Rated(se) => (true, se),
+                RatingResult::Incomplete { hardest_se, .. } => (false, hardest_se),
+            };
+        serde_json::json!({ "complete": complete, "se_rating": se_rating }).to_string()
+    }
+
+    /// Whether the board as it stands can be finished with techniques no
+    /// harder than `difficulty` (a level name such as "Intermediate")
+    #[wasm_bindgen]
+    pub fn solvable_within(&self, difficulty: &str) -> bool {
+        let level = parse_difficulty(difficulty);
+        sudoku_session::solve_path_within(self.state.grid(), level).solved
+    }
+
     /// Get player statistics as JSON for persistence
     #[wasm_bindgen]
     pub fn get_stats_json(&self) -> String {
@@ -256,6 +483,13 @@ impl SudokuGame {
         self.state.se_rating()
     }
 
+    /// Difficulty of what's left to solve, e.g. "Expert" falling to "Easy"
+    /// as the board fills in
+    #[wasm_bindgen]
+    pub fn remaining_difficulty(&self) -> String {
+        self.state.remaining_difficulty().to_string()
+    }
+
     /// Get number of mistakes
     #[wasm_bindgen]
     pub fn mistakes(&self) -> usize {
@@ -268,12 +502,112 @@ impl SudokuGame {
         self.state.hints_used()
     }
 
+    /// Points so far, scored the same way as on mobile
+    #[wasm_bindgen]
+    pub fn current_score(&self) -> u32 {
+        self.state.current_score()
+    }
+
+    /// Conflicts the settings show, as JSON: `[{"row":0,"col":2,"kind":"rule_conflict"}]`.
+    /// Kinds are rule_conflict, incorrect and candidate_contradiction.
+    #[wasm_bindgen]
+    pub fn conflicts_json(&self) -> String {
+        let conflicts: Vec<_> = self
+            .state
+            .conflicts()
+            .into_iter()
+            .map(|(pos, kind)| serde_json::json!({ "row": pos.row, "col": pos.col, "kind": kind }))
+            .collect();
+        serde_json::Value::from(conflicts).to_string()
+    }
+
     /// Get the move log as JSON for anti-cheat replay
     #[wasm_bindgen]
     pub fn get_move_log(&self) -> String {
         self.state.move_log_json()
     }
 
+    /// Start replaying this game's recorded solve. Returns false if nothing was recorded.
+    #[wasm_bindgen]
+    pub fn start_replay(&mut self) -> bool {
+        let started = self.state.start_replay(None);
+        self.render();
+        started
+    }
+
+    /// Start replaying a shared move log (JSON from `get_move_log()`).
+    /// Load the matching puzzle first with `load_puzzle_string` or `load_short_code`.
+    #[wasm_bindgen]
+    pub fn start_replay_json(&mut self, log_json: &str) -> bool {
+        let Ok(log) = serde_json::from_str(log_json) else {
+            return false;
+        };
+        let started = self.state.start_replay(Some(log));
+        self.render();
+        started
+    }
+
+    /// Advance the replay by one recorded move. Returns false at the end.
+    #[wasm_bindgen]
+    pub fn replay_step(&mut self) -> bool {
+        let stepped = self.state.replay_step();
+        self.render();
+        stepped
+    }
+
+    /// Seek the replay to `t` milliseconds into the solve
+    #[wasm_bindgen]
+    pub fn replay_seek(&mut self, t: f64) {
+        self.state.replay_seek(t);
+        self.render();
+    }
+
+    /// Leave the replay and return to the previous screen
+    #[wasm_bindgen]
+    pub fn stop_replay(&mut self) {
+        self.state.stop_replay();
+        self.render();
+    }
+
+    /// Total length of the active replay in milliseconds (0 if none)
+    #[wasm_bindgen]
+    pub fn replay_duration_ms(&self) -> u32 {
+        self.state.replay().map(|r| r.duration_ms()).unwrap_or(0)
+    }
+
+    /// Current replay position in milliseconds (0 if none)
+    #[wasm_bindgen]
+    pub fn replay_position_ms(&self) -> f64 {
+        self.state.replay().map(|r| r.playhead_ms()).unwrap_or(0.0)
+    }
+
+    /// Open the technique trainer for e.g. "X-Wing" using puzzles of the given
+    /// difficulty. Returns false if no position needing the technique was found.
+    #[wasm_bindgen]
+    pub fn start_trainer(&mut self, technique: &str, difficulty: &str) -> bool {
+        let started = self
+            .state
+            .start_trainer(technique, parse_difficulty(difficulty));
+        self.render();
+        self.dispatch_announcement();
+        started
+    }
+
+    /// Check the cells marked in the trainer, returns true if they match the finding
+    #[wasm_bindgen]
+    pub fn trainer_check(&mut self) -> bool {
+        let correct = self.state.trainer_check();
+        self.render();
+        correct
+    }
+
+    /// Close the technique trainer
+    #[wasm_bindgen]
+    pub fn stop_trainer(&mut self) {
+        self.state.stop_trainer();
+        self.render();
+    }
+
     /// Check if secret difficulties (Master/Extreme) are unlocked
     #[wasm_bindgen]
     pub fn is_secrets_unlocked(&self) -> bool {
@@ -286,7 +620,7 @@ impl SudokuGame {
         self.state.set_secrets_unlocked(unlocked);
     }
 
-    /// Get the current screen state (Playing, Paused, Win, Lose, Menu, Stats, Loading)
+    /// Get the current screen state (Playing, Paused, Win, Lose, Menu, Stats, Loading, Replay, Trainer)
     #[wasm_bindgen]
     pub fn screen_state(&self) -> String {
         format!("{:?}", self.state.screen())
@@ -327,6 +661,7 @@ impl SudokuGame {
                 new_state.set_secrets_unlocked(true);
             }
             self.state = new_state;
+            self.apply_host_settings();
             self.render();
             true
         } else {
@@ -341,6 +676,145 @@ impl SudokuGame {
         self.render();
     }
 
+    /// Register a function called with each gameplay event, e.g.
+    /// `{ type: "mistake", row, col, value, mistakes, remaining }`.
+    /// Event types: move_made, mistake, conflict, row_completed, column_completed,
+    /// box_completed, puzzle_complete, game_over, technique_created. Pass null
+    /// to unregister.
+    #[wasm_bindgen]
+    pub fn set_event_callback(&mut self, callback: Option<js_sys::Function>) {
+        self.event_callback = callback;
+    }
+
+    /// Pause automatically when the page is hidden or the player goes idle
+    #[wasm_bindgen]
+    pub fn set_autopause(&mut self, enabled: bool) {
+        self.autopause = enabled;
+        self.state.set_autopause(enabled);
+    }
+
+    /// Seconds without input before auto-pausing (0 = only pause on page hide)
+    #[wasm_bindgen]
+    pub fn set_autopause_idle_secs(&mut self, secs: u32) {
+        self.idle_timeout_secs = secs;
+        self.state.set_idle_timeout_secs(secs);
+    }
+
+    /// After each correct move, send a `technique_created` event for every
+    /// technique it made available ("New after this move: Naked Pair in box
+    /// 7."), worded in `locale`. Pass null to turn it off, the default.
+    #[wasm_bindgen]
+    pub fn set_move_feedback(&mut self, locale: Option<String>) {
+        self.move_feedback = locale.clone();
+        self.state.set_move_feedback(locale);
+    }
+
+    /// Assists and mistake rules for this game and new ones, as JSON:
+    /// `{ auto_candidates, mistakes: "ignore" | "count" | { limit: n },
+    /// smart_notes, highlight_same_digit, show_timer, zen }`. Missing fields
+    /// take their defaults. Returns false if the JSON can't be read.
+    #[wasm_bindgen]
+    pub fn set_settings_json(&mut self, json: &str) -> bool {
+        let Ok(settings) = serde_json::from_str::<GameSettings>(json) else {
+            return false;
+        };
+        self.settings = settings;
+        self.state.set_settings(settings);
+        self.render();
+        true
+    }
+
+    /// The current game's assists and mistake rules, as JSON
+    #[wasm_bindgen]
+    pub fn settings_json(&self) -> String {
+        serde_json::to_string(self.state.settings()).unwrap_or_default()
+    }
+
+    /// Game mode for new games, as JSON: `"classic"`, `"hardcore"`,
+    /// `{ "time_attack": { "target_secs": n } }` or
+    /// `{ "hint_challenge": { "max_hints": n } }`. A plain name such as
+    /// "time-attack" takes the mode's defaults. Returns false if it can't be
+    /// read.
+    #[wasm_bindgen]
+    pub fn set_game_mode(&mut self, mode: &str) -> bool {
+        let Some(mode) = serde_json::from_str(mode)
+            .ok()
+            .or_else(|| GameMode::from_name(mode))
+        else {
+            return false;
+        };
+        self.game_mode = mode;
+        true
+    }
+
+    /// The current game's mode, as JSON
+    #[wasm_bindgen]
+    pub fn game_mode_json(&self) -> String {
+        serde_json::to_string(&self.state.game_mode()).unwrap_or_default()
+    }
+
+    /// Leaderboard the current game counts for, e.g. "hint_challenge"
+    #[wasm_bindgen]
+    pub fn leaderboard_category(&self) -> String {
+        self.state.game_mode().leaderboard_category().to_string()
+    }
+
+    /// Save the board and notes under `name`, replacing a bookmark of that name
+    #[wasm_bindgen]
+    pub fn save_bookmark(&mut self, name: &str) {
+        self.state.save_bookmark(name);
+        self.render();
+    }
+
+    /// Go back to a saved bookmark (undoable); false if there's none by that name
+    #[wasm_bindgen]
+    pub fn restore_bookmark(&mut self, name: &str) -> bool {
+        let restored = self.state.restore_bookmark(name);
+        self.render();
+        restored
+    }
+
+    #[wasm_bindgen]
+    pub fn delete_bookmark(&mut self, name: &str) -> bool {
+        self.state.delete_bookmark(name)
+    }
+
+    /// Activity per minute of play for a post-game sparkline, as JSON:
+    /// `[{"minute":0,"placements":4,"candidate_edits":9,"mistakes":1}]`
+    #[wasm_bindgen]
+    pub fn metrics_timeline_json(&self) -> String {
+        serde_json::to_string(&self.state.metrics_timeline()).unwrap_or_default()
+    }
+
+    /// Names of the saved bookmarks, oldest first, as a JSON array
+    #[wasm_bindgen]
+    pub fn bookmark_names_json(&self) -> String {
+        let names: Vec<&str> = self
+            .state
+            .bookmarks()
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        serde_json::to_string(&names).unwrap_or_default()
+    }
+
+    /// Disable particle effects and animated transitions (e.g. for prefers-reduced-motion)
+    #[wasm_bindgen]
+    pub fn set_reduced_motion(&mut self, reduced: bool) {
+        self.reduced_motion = reduced;
+        self.state.set_reduced_motion(reduced);
+        self.render();
+    }
+
+    /// Tell the game the page visibility changed (call from `visibilitychange`)
+    #[wasm_bindgen]
+    pub fn notify_visibility(&mut self, visible: bool) {
+        self.state.notify_visibility(visible);
+        self.render();
+        self.dispatch_announcement();
+        self.dispatch_events();
+    }
+
     /// Check if paused
     #[wasm_bindgen]
     pub fn is_paused(&self) -> bool {
@@ -404,6 +878,74 @@ impl SudokuGame {
         self.height
     }
 
+    /// Render a print-friendly copy of the board onto another (usually hidden)
+    /// canvas, sized to A4 proportions. The host page can then print that canvas.
+    #[wasm_bindgen]
+    pub fn render_for_print(&self, canvas_id: &str) -> Result<(), JsValue> {
+        let canvas = web_sys::window()
+            .ok_or("No window")?
+            .document()
+            .ok_or("No document")?
+            .get_element_by_id(canvas_id)
+            .ok_or("Canvas not found")?
+            .dyn_into::<HtmlCanvasElement>()?;
+
+        let ctx = canvas
+            .get_context("2d")?
+            .ok_or("Failed to get 2d context")?
+            .dyn_into::<CanvasRenderingContext2d>()?;
+
+        // Render at a higher resolution than the screen so print output stays sharp
+        canvas.set_width((PRINT_WIDTH as f64 * PRINT_SCALE) as u32);
+        canvas.set_height((PRINT_HEIGHT as f64 * PRINT_SCALE) as u32);
+        let _ = ctx.reset_transform();
+        let _ = ctx.scale(PRINT_SCALE, PRINT_SCALE);
+
+        render::render_print(&ctx, &self.state, PRINT_WIDTH, PRINT_HEIGHT);
+        Ok(())
+    }
+
+    /// Fire a `sudoku-announce` CustomEvent on the canvas with the last action's
+    /// announcement in `detail`, for the host page's ARIA live region.
+    /// Set up a new game with the host's preferences, mode and assists
+    fn apply_host_settings(&mut self) {
+        self.apply_host_preferences();
+        self.state.set_game_mode(self.game_mode);
+        self.state.set_settings(self.settings);
+    }
+
+    /// Re-apply host preferences after the game state is replaced
+    fn apply_host_preferences(&mut self) {
+        self.state.set_autopause(self.autopause);
+        self.state.set_idle_timeout_secs(self.idle_timeout_secs);
+        self.state.set_reduced_motion(self.reduced_motion);
+        self.state.set_move_feedback(self.move_feedback.clone());
+    }
+
+    fn dispatch_announcement(&mut self) {
+        let Some(text) = self.state.take_announcement() else {
+            return;
+        };
+        let init = CustomEventInit::new();
+        init.set_detail(&JsValue::from_str(&text));
+        if let Ok(event) = CustomEvent::new_with_event_init_dict("sudoku-announce", &init) {
+            let _ = self.canvas.dispatch_event(&event);
+        }
+    }
+
+    /// Deliver queued gameplay events to the host callback
+    fn dispatch_events(&mut self) {
+        let events = self.state.take_events();
+        let Some(callback) = &self.event_callback else {
+            return;
+        };
+        for event in events {
+            if let Ok(value) = serde_wasm_bindgen::to_value(&event) {
+                let _ = callback.call1(&JsValue::NULL, &value);
+            }
+        }
+    }
+
     /// Render the game to canvas
     fn render(&self) {
         render::render_game(
@@ -432,6 +974,167 @@ fn parse_difficulty(s: &str) -> Difficulty {
     }
 }
 
+/// Draw an 81-character puzzle as an SVG image (no canvas required).
+/// Returns an empty string if the puzzle can't be read.
+#[wasm_bindgen]
+pub fn puzzle_svg(puzzle: &str) -> String {
+    Board::from_puzzle(puzzle.trim()).map_or_else(String::new, |board| {
+        sudoku_render::to_svg(&sudoku_render::layout(&board, &Style::default()))
+    })
+}
+
+/// Glossary entry for a technique name or key as JSON:
+/// `{ name, key, difficulty, summary, description }`, or an empty string if
+/// there is none. Text is in `locale` where registered, otherwise English.
+#[wasm_bindgen]
+pub fn technique_info_json(name: &str, locale: Option<String>) -> String {
+    let locale = locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+    sudoku_glossary::lookup(name).map_or_else(String::new, |entry| {
+        serde_json::json!({
+            "name": entry.name_in(locale),
+            "key": entry.key,
+            "difficulty": entry.difficulty.to_string(),
+            "summary": entry.summary_in(locale),
+            "description": entry.description_in(locale),
+        })
+        .to_string()
+    })
+}
+
+/// Register translations for a locale from `id = text` lines (see
+/// `sudoku_glossary::i18n`). Returns the numbers of lines that couldn't be
+/// read.
+#[wasm_bindgen]
+pub fn register_locale(tag: &str, source: &str) -> Vec<u32> {
+    sudoku_glossary::i18n::register_locale(tag, source)
+        .into_iter()
+        .map(|line| line as u32)
+        .collect()
+}
+
+/// A streak tracker saved as JSON; empty for "" or anything unreadable
+fn streak_tracker(json: &str) -> StreakTracker {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+/// Record the daily puzzle of `day` (days since 1970-01-01 UTC) as solved in
+/// a streak tracker saved as JSON, "" for a new one. Returns the updated
+/// JSON to save with the player's stats.
+#[wasm_bindgen]
+pub fn streak_complete(tracker: &str, day: u32, time_secs: u32) -> String {
+    let mut tracker = streak_tracker(tracker);
+    tracker.complete(day.into(), time_secs.into());
+    serde_json::to_string(&tracker).unwrap_or_default()
+}
+
+/// A saved streak tracker as of `today`, as JSON: `{ current, best,
+/// freezes, completed: [day], frozen: [day] }`
+#[wasm_bindgen]
+pub fn streak_status_json(tracker: &str, today: u32) -> String {
+    let tracker = streak_tracker(tracker);
+    let days_where = |frozen: bool| -> Vec<u64> {
+        tracker
+            .days()
+            .filter(|(_, d)| (*d == Day::Frozen) == frozen)
+            .map(|(day, _)| day)
+            .collect()
+    };
+    serde_json::json!({
+        "current": tracker.current(today.into()),
+        "best": tracker.best(),
+        "freezes": tracker.freezes(),
+        "completed": days_where(false),
+        "frozen": days_where(true),
+    })
+    .to_string()
+}
+
+/// The daily puzzle of a "YYYY-MM-DD" date (UTC), the same as every other
+/// frontend's, as JSON: `{ day, weekday, difficulty, short_code }`; "" for
+/// a date that can't be read. Load it with `load_short_code`; `day` is what
+/// `streak_complete` takes.
+#[wasm_bindgen]
+pub fn daily_puzzle_json(date: &str) -> String {
+    let Some(day) = sudoku_daily::parse_date(date) else {
+        return String::new();
+    };
+    let id = sudoku_daily::puzzle_id(day);
+    serde_json::json!({
+        "day": day,
+        "weekday": sudoku_daily::weekday_name(day),
+        "difficulty": format!("{}", id.difficulty),
+        "short_code": id.to_short_code(),
+    })
+    .to_string()
+}
+
+/// Read pasted text as leniently as possible and report what was skipped.
+/// Returns JSON: `{ puzzle, issues: [{ kind, line, column, ch } |
+/// { kind: "wrong_cell_count", found }] }`, where `puzzle` is always 81
+/// characters with "." for empty cells.
+#[wasm_bindgen]
+pub fn diagnose_puzzle(text: &str) -> String {
+    serde_json::to_string(&import::parse_lossy(text)).unwrap_or_default()
+}
+
+/// A search for a puzzle near a target SE rating, run a few attempts at a
+/// time (say, once per animation frame) so the page can show progress.
+/// Dropping it cancels, but only between calls to `step`: one attempt
+/// generates and rates a whole puzzle and can't be stopped part way, and at
+/// high targets that can take seconds. Pass a small `attempts` (1 for
+/// targets above about 7) to keep the page responsive.
+#[wasm_bindgen]
+pub struct PuzzleSearch {
+    search: SeSearch,
+}
+
+#[wasm_bindgen]
+impl PuzzleSearch {
+    #[wasm_bindgen(constructor)]
+    pub fn new(target_se: f32) -> PuzzleSearch {
+        PuzzleSearch {
+            search: SeSearch::new(target_se),
+        }
+    }
+
+    /// Try up to `attempts` more puzzles; returns whether one is close
+    /// enough to the target
+    pub fn step(&mut self, attempts: u32) -> bool {
+        (0..attempts).any(|_| self.search.step()) || self.search.is_done()
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.search.progress().attempts
+    }
+
+    /// SE rating of the closest puzzle so far
+    pub fn best_se(&self) -> Option<f32> {
+        self.search.progress().best_se
+    }
+
+    /// The closest puzzle so far, as JSON for `load_pregenerated`:
+    /// {puzzle_hash, puzzle_string, solution_string, difficulty, se_rating};
+    /// empty before the first attempt
+    pub fn result_json(&self) -> String {
+        let Some((puzzle, se_rating)) = self.search.best() else {
+            return String::new();
+        };
+        let solver = Solver::new();
+        let Some(solution) = solver.solve(puzzle) else {
+            return String::new();
+        };
+        let puzzle_string = puzzle.to_string_compact();
+        serde_json::json!({
+            "puzzle_hash": canonical_puzzle_hash_str(&puzzle_string),
+            "puzzle_string": puzzle_string,
+            "solution_string": solution.to_string_compact(),
+            "difficulty": format!("{}", solver.rate_difficulty(puzzle)),
+            "se_rating": se_rating,
+        })
+        .to_string()
+    }
+}
+
 /// Generate a puzzle in the background (no canvas required).
 /// Returns JSON: {puzzle_hash, puzzle_string, solution_string, difficulty, se_rating, short_code}
 #[wasm_bindgen]
@@ -0,0 +1,31 @@
+//! Technique breakdown of a puzzle's logical solve path
+
+use serde::Serialize;
+use sudoku_core::Grid;
+
+/// How often one technique was needed along the solve path
+#[derive(Debug, Clone, Serialize)]
+pub struct TechniqueCount {
+    pub name: String,
+    pub se_rating: f32,
+    pub count: u32,
+}
+
+/// Count the techniques along `puzzle`'s solve path, hardest first
+pub fn technique_breakdown(puzzle: &Grid) -> Vec<TechniqueCount> {
+    let mut counts: Vec<TechniqueCount> = Vec::new();
+    for hint in sudoku_session::solve_path(puzzle).steps {
+        let name = hint.technique.to_string();
+        match counts.iter_mut().find(|c| c.name == name) {
+            Some(entry) => entry.count += 1,
+            None => counts.push(TechniqueCount {
+                name,
+                se_rating: hint.technique.se_rating(),
+                count: 1,
+            }),
+        }
+    }
+
+    counts.sort_by(|a, b| b.se_rating.total_cmp(&a.se_rating));
+    counts
+}
@@ -1,8 +1,9 @@
 //! Canvas rendering for terminal-like Sudoku UI
 
-use crate::game::{GameState, HintDetailLevel, InputMode, ScreenState, MAX_MISTAKES};
-use crate::theme::{Color, Theme};
+use crate::game::{GameState, HintDetailLevel, InputMode, ScreenState};
+use crate::theme::{Color, Theme, CELL_PALETTE};
 use sudoku_core::{Hint, Polarity, Position, ProofCertificate};
+use sudoku_session::ConflictKind;
 use web_sys::CanvasRenderingContext2d;
 
 /// Role of a cell in the current hint visualization.
@@ -197,6 +198,12 @@ mod box_chars {
     pub const T_LEFT_THICK: &str = "┫";
 }
 
+/// Top-left corner of the board in the game layout
+pub fn grid_origin(height: u32, cell_size: f64) -> (f64, f64) {
+    let grid_height = cell_size * 9.0 + 4.0; // 9 cells + borders
+    (40.0, (height as f64 - grid_height) / 2.0)
+}
+
 /// Render the complete game to canvas
 pub fn render_game(
     ctx: &CanvasRenderingContext2d,
@@ -214,8 +221,7 @@ pub fn render_game(
     // Calculate grid position (centered with room for info panel)
     let grid_width = cell_size * 9.0 + 4.0; // 9 cells + borders
     let grid_height = cell_size * 9.0 + 4.0;
-    let grid_x = 40.0;
-    let grid_y = (height as f64 - grid_height) / 2.0;
+    let (grid_x, grid_y) = grid_origin(height, cell_size);
 
     match state.screen() {
         ScreenState::Playing | ScreenState::Paused => {
@@ -258,6 +264,28 @@ pub fn render_game(
         ScreenState::Loading => {
             render_loading_screen(ctx, state, theme, width, height, font_size);
         }
+        ScreenState::Replay => {
+            render_replay(ctx, state, theme, grid_x, grid_y, cell_size, font_size);
+            render_replay_panel(
+                ctx,
+                state,
+                theme,
+                grid_x + grid_width + 30.0,
+                grid_y,
+                font_size,
+            );
+        }
+        ScreenState::Trainer => {
+            render_trainer(ctx, state, theme, grid_x, grid_y, cell_size, font_size);
+            render_trainer_panel(
+                ctx,
+                state,
+                theme,
+                grid_x + grid_width + 30.0,
+                grid_y,
+                font_size,
+            );
+        }
     }
 
     // Render message if present
@@ -266,6 +294,106 @@ pub fn render_game(
     }
 }
 
+/// Render a print-friendly page: white background, black thin/thick lines,
+/// a one-line header, and no info panel, hint overlay, or cursor.
+pub fn render_print(ctx: &CanvasRenderingContext2d, state: &GameState, width: u32, height: u32) {
+    let theme = Theme::print();
+    let w = width as f64;
+    let h = height as f64;
+
+    ctx.set_fill_style_str(&theme.background.as_css());
+    ctx.fill_rect(0.0, 0.0, w, h);
+
+    // Grid spans most of the page width, leaving a margin on each side
+    let margin = w * 0.1;
+    let cell_size = (w - margin * 2.0) / 9.0;
+    let grid_size = cell_size * 9.0;
+    let x = (w - grid_size) / 2.0;
+    let y = margin * 1.5;
+
+    // Header
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("bottom");
+    ctx.set_font(&format!(
+        "bold {}px 'JetBrains Mono', monospace",
+        cell_size * 0.35
+    ));
+    ctx.set_fill_style_str(&theme.given_text.as_css());
+    let _ = ctx.fill_text(
+        &format!("Sudoku — {} (SE {:.1})", state.difficulty(), state.se_rating()),
+        x,
+        y - cell_size * 0.25,
+    );
+    if let Some(code) = state.short_code() {
+        ctx.set_text_align("right");
+        ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", cell_size * 0.3));
+        let _ = ctx.fill_text(&code, x + grid_size, y - cell_size * 0.25);
+    }
+
+    // Values and pencil marks
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+    for row in 0..9 {
+        for col in 0..9 {
+            let cell = state.grid().cell(Position::new(row, col));
+            let cell_x = x + col as f64 * cell_size;
+            let cell_y = y + row as f64 * cell_size;
+
+            if let Some(value) = cell.value() {
+                let (weight, color) = if cell.is_given() {
+                    ("bold ", &theme.given_text)
+                } else {
+                    ("", &theme.player_text)
+                };
+                ctx.set_font(&format!(
+                    "{}{}px 'JetBrains Mono', monospace",
+                    weight,
+                    cell_size * 0.55
+                ));
+                ctx.set_fill_style_str(&color.as_css());
+                let _ = ctx.fill_text(
+                    &value.to_string(),
+                    cell_x + cell_size / 2.0,
+                    cell_y + cell_size / 2.0,
+                );
+            } else if !cell.candidates().is_empty() {
+                ctx.set_font(&format!(
+                    "{}px 'JetBrains Mono', monospace",
+                    cell_size * 0.22
+                ));
+                ctx.set_fill_style_str(&theme.candidate_text.as_css());
+                for v in cell.candidates().iter() {
+                    let (dx, dy) = candidate_offset(v);
+                    let _ = ctx.fill_text(
+                        &v.to_string(),
+                        cell_x + cell_size * dx,
+                        cell_y + cell_size * dy,
+                    );
+                }
+            }
+        }
+    }
+
+    // Thin cell lines, then thick box borders on top
+    ctx.set_stroke_style_str(&theme.grid_lines.as_css());
+    for (step, line_width) in [(1, 1.0), (3, 3.0)] {
+        ctx.set_line_width(line_width);
+        for i in (0..=9).step_by(step) {
+            let offset = i as f64 * cell_size;
+
+            ctx.begin_path();
+            ctx.move_to(x + offset, y);
+            ctx.line_to(x + offset, y + grid_size);
+            ctx.stroke();
+
+            ctx.begin_path();
+            ctx.move_to(x, y + offset);
+            ctx.line_to(x + grid_size, y + offset);
+            ctx.stroke();
+        }
+    }
+}
+
 /// Render the Sudoku grid
 fn render_grid(
     ctx: &CanvasRenderingContext2d,
@@ -285,10 +413,7 @@ fn render_grid(
         .map(|hint| compute_hint_roles(hint, state.hint_detail()));
 
     // Set font for numbers
-    ctx.set_font(&format!(
-        "{}px 'JetBrains Mono', 'Fira Code', 'Consolas', monospace",
-        font_size
-    ));
+    ctx.set_font(&theme.font.digit_font(font_size));
     ctx.set_text_align("center");
     ctx.set_text_baseline("middle");
 
@@ -301,12 +426,14 @@ fn render_grid(
             let cell_y = y + row as f64 * cell_size;
 
             // Determine cell background
-            // Priority: cursor > hint_target > proof_role > hint_involved > same_value > highlight > cell_bg
+            // Priority: cursor > selection > hint_target > proof_role > hint_involved > same_value > highlight > cell_bg
             let idx = row * 9 + col;
             let hint_role = hint_roles.map(|r| r[idx]).unwrap_or(HintCellRole::None);
 
             let bg_color = if pos == cursor {
                 &theme.cursor_bg
+            } else if state.is_selected(pos) {
+                &theme.selection_bg
             } else if let Some(color) = role_color(hint_role, theme) {
                 color
             } else if state.has_same_value(pos) && state.grid().get(cursor).is_some() {
@@ -321,6 +448,16 @@ fn render_grid(
             ctx.set_fill_style_str(&bg_color.as_css());
             ctx.fill_rect(cell_x, cell_y, cell_size, cell_size);
 
+            // Player coloring on top of the background
+            if let Some(color) = state
+                .cell_color(pos)
+                .checked_sub(1)
+                .and_then(|i| CELL_PALETTE.get(i as usize))
+            {
+                ctx.set_fill_style_str(&color.as_css_alpha(0.45));
+                ctx.fill_rect(cell_x, cell_y, cell_size, cell_size);
+            }
+
             // Highlight naked singles if valid cells mode is on
             if state.show_valid_cells() && state.is_naked_single(pos) {
                 ctx.set_stroke_style_str(&theme.win_color.as_css_alpha(0.6));
@@ -329,21 +466,19 @@ fn render_grid(
             }
 
             // Draw cell content
+            let conflict = state.conflict(pos);
             if let Some(value) = cell.value() {
-                // Check for conflict
-                let has_conflict = state.has_conflict(pos);
-
-                // Determine text color
-                let text_color = if has_conflict {
-                    &theme.error_text
-                } else if cell.is_given() {
-                    &theme.given_text
-                } else {
-                    &theme.player_text
+                // Rule conflicts in full error color, wrong digits fainter
+                let text_color = match conflict {
+                    Some(ConflictKind::RuleConflict) => theme.error_text.as_css(),
+                    Some(ConflictKind::Incorrect) => theme.error_text.as_css_alpha(0.6),
+                    _ if cell.is_given() => theme.given_text.as_css(),
+                    _ => theme.player_text.as_css(),
                 };
 
-                ctx.set_fill_style_str(&text_color.as_css());
-                let _ = ctx.fill_text(
+                ctx.set_fill_style_str(&text_color);
+                fill_centered(
+                    ctx,
                     &value.to_string(),
                     cell_x + cell_size / 2.0,
                     cell_y + cell_size / 2.0,
@@ -357,15 +492,16 @@ fn render_grid(
                     Vec::new()
                 };
 
-                let small_font = font_size * 0.45;
-                ctx.set_font(&format!(
-                    "bold {}px 'JetBrains Mono', monospace",
-                    small_font
-                ));
+                ctx.set_font(&theme.font.note_font(font_size));
 
-                // Draw user's candidates
+                // Draw user's candidates, in error color if none can fit
                 if !candidates.is_empty() {
-                    ctx.set_fill_style_str(&theme.candidate_text.as_css());
+                    let note_color = if conflict == Some(ConflictKind::CandidateContradiction) {
+                        &theme.error_text
+                    } else {
+                        &theme.candidate_text
+                    };
+                    ctx.set_fill_style_str(&note_color.as_css());
                     for v in candidates.iter() {
                         let (dx, dy) = candidate_offset(v);
                         let cx = cell_x + cell_size * dx;
@@ -386,11 +522,43 @@ fn render_grid(
                 }
 
                 // Reset font
-                ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", font_size));
+                ctx.set_font(&theme.font.digit_font(font_size));
             }
         }
     }
 
+    draw_grid_lines(ctx, theme, x, y, cell_size);
+
+    // Draw cursor outline
+    ctx.set_stroke_style_str(&theme.cursor_bg.as_css());
+    ctx.set_line_width(3.0);
+    let cursor_x = x + cursor.col as f64 * cell_size;
+    let cursor_y = y + cursor.row as f64 * cell_size;
+    ctx.stroke_rect(cursor_x, cursor_y, cell_size, cell_size);
+
+    // Draw number completion indicator at bottom
+    let indicator_y = y + 9.0 * cell_size + 20.0;
+    ctx.set_font(&format!("{}px monospace", font_size * 0.6));
+    ctx.set_text_align("center");
+
+    for (i, &is_completed) in completed.iter().enumerate() {
+        let num = (i + 1) as u8;
+        let indicator_x = x + (i as f64 + 0.5) * cell_size;
+
+        if is_completed {
+            ctx.set_fill_style_str(&theme.completed_bg.as_css());
+            ctx.fill_rect(indicator_x - 10.0, indicator_y - 10.0, 20.0, 20.0);
+            ctx.set_fill_style_str(&theme.given_text.as_css());
+        } else {
+            ctx.set_fill_style_str(&theme.candidate_text.as_css());
+        }
+
+        let _ = ctx.fill_text(&num.to_string(), indicator_x, indicator_y);
+    }
+}
+
+/// Draw thin cell lines and thick box borders
+fn draw_grid_lines(ctx: &CanvasRenderingContext2d, theme: &Theme, x: f64, y: f64, cell_size: f64) {
     // Draw grid lines
     ctx.set_stroke_style_str(&theme.grid_lines.as_css());
     ctx.set_line_width(1.0);
@@ -430,32 +598,324 @@ fn render_grid(
         ctx.line_to(x + 9.0 * cell_size, y + offset);
         ctx.stroke();
     }
+}
 
-    // Draw cursor outline
-    ctx.set_stroke_style_str(&theme.cursor_bg.as_css());
-    ctx.set_line_width(3.0);
-    let cursor_x = x + cursor.col as f64 * cell_size;
-    let cursor_y = y + cursor.row as f64 * cell_size;
-    ctx.stroke_rect(cursor_x, cursor_y, cell_size, cell_size);
+/// Render the board as it stood at the replay playhead
+fn render_replay(
+    ctx: &CanvasRenderingContext2d,
+    state: &GameState,
+    theme: &Theme,
+    x: f64,
+    y: f64,
+    cell_size: f64,
+    font_size: f64,
+) {
+    let Some(replay) = state.replay() else {
+        return;
+    };
+    let last_cell = replay.last_cell();
 
-    // Draw number completion indicator at bottom
-    let indicator_y = y + 9.0 * cell_size + 20.0;
-    ctx.set_font(&format!("{}px monospace", font_size * 0.6));
+    ctx.set_font(&theme.font.digit_font(font_size));
     ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
 
-    for (i, &is_completed) in completed.iter().enumerate() {
-        let num = (i + 1) as u8;
-        let indicator_x = x + (i as f64 + 0.5) * cell_size;
+    for row in 0..9 {
+        for col in 0..9 {
+            let pos = Position::new(row, col);
+            let cell = replay.grid().cell(pos);
+            let cell_x = x + col as f64 * cell_size;
+            let cell_y = y + row as f64 * cell_size;
 
-        if is_completed {
-            ctx.set_fill_style_str(&theme.completed_bg.as_css());
-            ctx.fill_rect(indicator_x - 10.0, indicator_y - 10.0, 20.0, 20.0);
-            ctx.set_fill_style_str(&theme.given_text.as_css());
-        } else {
-            ctx.set_fill_style_str(&theme.candidate_text.as_css());
+            let bg_color = if Some(pos) == last_cell {
+                &theme.cursor_bg
+            } else {
+                &theme.cell_bg
+            };
+            ctx.set_fill_style_str(&bg_color.as_css());
+            ctx.fill_rect(cell_x, cell_y, cell_size, cell_size);
+
+            if let Some(value) = cell.value() {
+                // Wrong placements are shown as they were made, in the error color
+                let text_color = if cell.is_given() {
+                    &theme.given_text
+                } else if state.solution().get(pos) != Some(value) {
+                    &theme.error_text
+                } else {
+                    &theme.player_text
+                };
+                ctx.set_fill_style_str(&text_color.as_css());
+                fill_centered(
+                    ctx,
+                    &value.to_string(),
+                    cell_x + cell_size / 2.0,
+                    cell_y + cell_size / 2.0,
+                );
+            }
         }
+    }
 
-        let _ = ctx.fill_text(&num.to_string(), indicator_x, indicator_y);
+    draw_grid_lines(ctx, theme, x, y, cell_size);
+}
+
+/// Render replay progress and controls beside the grid
+fn render_replay_panel(
+    ctx: &CanvasRenderingContext2d,
+    state: &GameState,
+    theme: &Theme,
+    x: f64,
+    y: f64,
+    font_size: f64,
+) {
+    let Some(replay) = state.replay() else {
+        return;
+    };
+    let info_font = font_size * 0.65;
+    let small_font = font_size * 0.5;
+    let line_height = font_size * 0.9;
+    let small_line = font_size * 0.55;
+
+    let fmt_ms = |ms: f64| {
+        let secs = (ms / 1000.0) as u32;
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    };
+
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("top");
+
+    let mut cy = y;
+    ctx.set_font(&format!("bold {}px 'JetBrains Mono', monospace", info_font));
+    ctx.set_fill_style_str(&theme.given_text.as_css());
+    let _ = ctx.fill_text("Replay", x, cy);
+    cy += line_height;
+
+    ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", info_font));
+    ctx.set_fill_style_str(&theme.info_text.as_css());
+    let _ = ctx.fill_text(
+        &format!("Move {} / {}", replay.applied(), replay.total_moves()),
+        x,
+        cy,
+    );
+    cy += line_height;
+
+    if let Some(technique) = replay.last_technique() {
+        let _ = ctx.fill_text(technique, x, cy);
+        cy += line_height;
+    }
+
+    let _ = ctx.fill_text(
+        &format!(
+            "Time {} / {}",
+            fmt_ms(replay.playhead_ms()),
+            fmt_ms(replay.duration_ms() as f64)
+        ),
+        x,
+        cy,
+    );
+    cy += line_height;
+
+    let status = if replay.is_playing() { "▶" } else { "❚❚" };
+    let _ = ctx.fill_text(&format!("{} │ {}x", status, replay.speed()), x, cy);
+    cy += line_height;
+
+    // Progress bar
+    let bar_width = font_size * 7.0;
+    let progress = if replay.duration_ms() > 0 {
+        (replay.playhead_ms() / replay.duration_ms() as f64).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ctx.set_fill_style_str(&theme.cell_bg.as_css());
+    ctx.fill_rect(x, cy, bar_width, 6.0);
+    ctx.set_fill_style_str(&theme.player_text.as_css());
+    ctx.fill_rect(x, cy, bar_width * progress, 6.0);
+    cy += line_height * 1.3;
+
+    ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", small_font));
+    ctx.set_fill_style_str(&theme.candidate_text.as_css());
+    let controls = [
+        "←/→ h/l     Step",
+        "Space       Play/Pause",
+        "Home/End    Start/End",
+        "+/-         Speed",
+        "Esc         Close replay",
+    ];
+    for line in controls {
+        let _ = ctx.fill_text(line, x, cy);
+        cy += small_line;
+    }
+}
+
+/// Render the trainer position: full candidates, marked cells, and the
+/// finding's cells once the answer has been checked
+fn render_trainer(
+    ctx: &CanvasRenderingContext2d,
+    state: &GameState,
+    theme: &Theme,
+    x: f64,
+    y: f64,
+    cell_size: f64,
+    font_size: f64,
+) {
+    let Some(trainer) = state.trainer() else {
+        return;
+    };
+    let cursor = state.cursor();
+    let revealed = trainer.verdict().is_some();
+    let targets = trainer.targets();
+
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+
+    for row in 0..9 {
+        for col in 0..9 {
+            let pos = Position::new(row, col);
+            let cell = trainer.grid().cell(pos);
+            let cell_x = x + col as f64 * cell_size;
+            let cell_y = y + row as f64 * cell_size;
+
+            let bg_color = if pos == cursor {
+                &theme.cursor_bg
+            } else if revealed && targets.contains(&pos) {
+                &theme.hint_target_bg
+            } else if trainer.is_picked(pos) {
+                &theme.selection_bg
+            } else if revealed && trainer.hint().involved_cells.contains(&pos) {
+                &theme.hint_involved_bg
+            } else {
+                &theme.cell_bg
+            };
+            ctx.set_fill_style_str(&bg_color.as_css());
+            ctx.fill_rect(cell_x, cell_y, cell_size, cell_size);
+
+            // Outline marked cells so they stay visible under the cursor and reveal colors
+            if trainer.is_picked(pos) {
+                let color = match trainer.verdict() {
+                    Some(_) if !targets.contains(&pos) => &theme.error_text,
+                    _ => &theme.player_text,
+                };
+                ctx.set_stroke_style_str(&color.as_css());
+                ctx.set_line_width(2.0);
+                ctx.stroke_rect(cell_x + 2.0, cell_y + 2.0, cell_size - 4.0, cell_size - 4.0);
+            }
+
+            if let Some(value) = cell.value() {
+                ctx.set_font(&theme.font.digit_font(font_size));
+                ctx.set_fill_style_str(&theme.given_text.as_css());
+                fill_centered(
+                    ctx,
+                    &value.to_string(),
+                    cell_x + cell_size / 2.0,
+                    cell_y + cell_size / 2.0,
+                );
+            } else {
+                ctx.set_font(&theme.font.note_font(font_size));
+                ctx.set_fill_style_str(&theme.candidate_text.as_css());
+                for v in cell.candidates().iter() {
+                    let (dx, dy) = candidate_offset(v);
+                    let _ = ctx.fill_text(
+                        &v.to_string(),
+                        cell_x + cell_size * dx,
+                        cell_y + cell_size * dy,
+                    );
+                }
+            }
+        }
+    }
+
+    draw_grid_lines(ctx, theme, x, y, cell_size);
+}
+
+/// Render trainer score, result and controls beside the grid
+fn render_trainer_panel(
+    ctx: &CanvasRenderingContext2d,
+    state: &GameState,
+    theme: &Theme,
+    x: f64,
+    y: f64,
+    font_size: f64,
+) {
+    let Some(trainer) = state.trainer() else {
+        return;
+    };
+    let info_font = font_size * 0.65;
+    let small_font = font_size * 0.5;
+    let line_height = font_size * 0.9;
+    let small_line = font_size * 0.55;
+
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("top");
+
+    let mut cy = y;
+    ctx.set_font(&format!("bold {}px 'JetBrains Mono', monospace", info_font));
+    ctx.set_fill_style_str(&theme.hint_technique_text.as_css());
+    let _ = ctx.fill_text(&format!("Trainer: {}", trainer.technique()), x, cy);
+    cy += line_height;
+
+    ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", info_font));
+    ctx.set_fill_style_str(&theme.info_text.as_css());
+    let _ = ctx.fill_text(
+        &format!("Score {} / {}", trainer.solved(), trainer.attempts()),
+        x,
+        cy,
+    );
+    cy += line_height;
+
+    match trainer.verdict() {
+        None => {
+            let _ = ctx.fill_text("Mark the cells it acts on", x, cy);
+            cy += line_height;
+        }
+        Some(correct) => {
+            let (text, color) = if correct {
+                ("Correct!", &theme.win_color)
+            } else {
+                ("Not quite", &theme.lose_color)
+            };
+            ctx.set_fill_style_str(&color.as_css());
+            let _ = ctx.fill_text(text, x, cy);
+            cy += line_height;
+
+            ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", small_font));
+            ctx.set_fill_style_str(&theme.hint_explain_text.as_css());
+            let _ = ctx.fill_text(&trainer.hint().explanation, x, cy);
+            cy += small_line;
+        }
+    }
+    cy += line_height * 0.5;
+
+    ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", small_font));
+    ctx.set_fill_style_str(&theme.candidate_text.as_css());
+    let controls = [
+        "↑↓←→ hjkl   Move",
+        "Space/Click Mark cell",
+        "Enter       Check",
+        "n           Next position",
+        "Esc         Close trainer",
+    ];
+    for line in controls {
+        let _ = ctx.fill_text(line, x, cy);
+        cy += small_line;
+    }
+}
+
+/// Draw text centered on (cx, cy) by its measured ink box, so fonts whose
+/// digits sit high or low in the em box still look centered. Falls back to
+/// the "middle" baseline when the browser reports no glyph metrics.
+fn fill_centered(ctx: &CanvasRenderingContext2d, text: &str, cx: f64, cy: f64) {
+    let metrics = ctx.measure_text(text).ok().filter(|m| {
+        m.actual_bounding_box_ascent() + m.actual_bounding_box_descent() > 0.0
+    });
+    match metrics {
+        Some(m) => {
+            let x = cx + (m.actual_bounding_box_left() - m.actual_bounding_box_right()) / 2.0;
+            let y = cy + (m.actual_bounding_box_ascent() - m.actual_bounding_box_descent()) / 2.0;
+            ctx.set_text_baseline("alphabetic");
+            let _ = ctx.fill_text(text, x, y);
+            ctx.set_text_baseline("middle");
+        }
+        None => {
+            let _ = ctx.fill_text(text, cx, cy);
+        }
     }
 }
 
@@ -496,7 +956,21 @@ fn render_info_panel(
     ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", info_font));
     ctx.set_fill_style_str(&theme.info_text.as_css());
 
-    let _ = ctx.fill_text(&format!("Time: {}", state.elapsed_string()), x, cy);
+    if state.settings().show_timer {
+        let time = match state.game_mode().time_limit_ms() {
+            Some(limit) => {
+                let secs = limit / 1000;
+                format!(
+                    "{} / {:02}:{:02}",
+                    state.elapsed_string(),
+                    secs / 60,
+                    secs % 60
+                )
+            }
+            None => state.elapsed_string(),
+        };
+        let _ = ctx.fill_text(&format!("Time: {}", time), x, cy);
+    }
     cy += line_height;
 
     let _ = ctx.fill_text(
@@ -506,8 +980,10 @@ fn render_info_panel(
     );
     cy += line_height;
 
-    let remaining = MAX_MISTAKES.saturating_sub(state.mistakes());
-    let hearts: String = "♥".repeat(remaining) + &"♡".repeat(state.mistakes());
+    let hearts = match state.settings().mistakes_left(state.mistakes()) {
+        Some(remaining) => "♥".repeat(remaining) + &"♡".repeat(state.mistakes()),
+        None => format!("✗{}", state.mistakes()),
+    };
     let _ = ctx.fill_text(
         &format!("{} │ Hints: {}", hearts, state.hints_used()),
         x,
@@ -518,6 +994,7 @@ fn render_info_panel(
     let mode_str = match state.mode() {
         InputMode::Normal => "Normal",
         InputMode::Candidate => "Notes",
+        InputMode::Color => "Color",
     };
     let ghost = if state.show_ghost_hints() { "G" } else { "-" };
     let valid = if state.show_valid_cells() { "V" } else { "-" };
@@ -547,6 +1024,26 @@ fn render_info_panel(
     let _ = ctx.fill_text(&num_line, x, cy);
     cy += line_height * 1.3;
 
+    // Technique breakdown (hardest techniques on the solve path)
+    if state.show_rating() {
+        ctx.set_font(&format!("bold {}px 'JetBrains Mono', monospace", info_font));
+        ctx.set_fill_style_str(&theme.given_text.as_css());
+        let _ = ctx.fill_text("Techniques", x, cy);
+        cy += line_height;
+
+        ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", small_font));
+        ctx.set_fill_style_str(&theme.hint_technique_text.as_css());
+        for tech in state.technique_breakdown().iter().take(4) {
+            let _ = ctx.fill_text(
+                &format!("{:.1} {} ×{}", tech.se_rating, tech.name, tech.count),
+                x,
+                cy,
+            );
+            cy += small_line;
+        }
+        cy += line_height * 0.5;
+    }
+
     // Controls - single column, compact
     ctx.set_font(&format!("bold {}px 'JetBrains Mono', monospace", info_font));
     ctx.set_fill_style_str(&theme.given_text.as_css());
@@ -563,12 +1060,14 @@ fn render_info_panel(
         "Shift+1-9   Toggle note",
         "0/Del       Clear cell",
         "c           Mode",
+        "o / O       Color/Clear",
         "f           Fill notes",
         "F           Fill ALL notes",
         "x           Clear notes",
         "X           Clear ALL notes",
         "g           Ghost hints",
         "v           Valid cells",
+        "t           Techniques",
         "? / !       Hint/Apply",
         "u           Undo",
         "p  n  S     Pause/New/Stats",
@@ -679,11 +1178,8 @@ fn render_win_screen(
             ctx.set_text_baseline("middle");
             let alpha = (particle.lifetime.min(1.0) * 255.0) as u8;
             if alpha > 50 {
-                let _ = ctx.fill_text(
-                    &particle.char.to_string(),
-                    particle.x as f64,
-                    particle.y as f64,
-                );
+                let (px, py) = particle.position_at(state.frame_alpha() as f32);
+                let _ = ctx.fill_text(&particle.char.to_string(), px as f64, py as f64);
             }
         }
 
@@ -768,9 +1264,9 @@ fn render_win_screen(
     ));
     ctx.set_fill_style_str(&theme.info_text.as_css_alpha(0.8));
     let diff_hint = if state.secrets_unlocked() {
-        "N: New game  1-8: Difficulty  S: Full stats"
+        "N: New game  1-8: Difficulty  S: Full stats  R: Replay"
     } else {
-        "N: New game  1-6: Difficulty  S: Full stats"
+        "N: New game  1-6: Difficulty  S: Full stats  R: Replay"
     };
     let _ = ctx.fill_text(diff_hint, w / 2.0, h / 2.0 + 160.0);
 }
@@ -802,11 +1298,8 @@ fn render_lose_screen(
             ctx.set_text_baseline("middle");
             let alpha = (particle.lifetime.min(1.0) * 255.0) as u8;
             if alpha > 30 {
-                let _ = ctx.fill_text(
-                    &particle.char.to_string(),
-                    particle.x as f64,
-                    particle.y as f64,
-                );
+                let (px, py) = particle.position_at(state.frame_alpha() as f32);
+                let _ = ctx.fill_text(&particle.char.to_string(), px as f64, py as f64);
             }
         }
     }
@@ -845,7 +1338,11 @@ fn render_lose_screen(
 
     // Flicker effect
     let frame = state.frame();
-    let flicker = if frame % 60 < 5 { 0.6 } else { 1.0 };
+    let flicker = if !state.reduced_motion() && frame % 60 < 5 {
+        0.6
+    } else {
+        1.0
+    };
 
     for (i, line) in lines.iter().enumerate() {
         // Red gradient
@@ -858,7 +1355,12 @@ fn render_lose_screen(
     ctx.set_font(&format!("{}px 'JetBrains Mono', monospace", font_size));
     ctx.set_fill_style_str(&theme.info_text.as_css());
     ctx.set_text_baseline("middle");
-    let _ = ctx.fill_text("Too many mistakes!", w / 2.0, h / 2.0 + 50.0);
+    let reason = if state.is_out_of_time() {
+        "Out of time!"
+    } else {
+        "Too many mistakes!"
+    };
+    let _ = ctx.fill_text(reason, w / 2.0, h / 2.0 + 50.0);
 
     ctx.set_font(&format!(
         "{}px 'JetBrains Mono', monospace",
@@ -866,9 +1368,9 @@ fn render_lose_screen(
     ));
     ctx.set_fill_style_str(&theme.info_text.as_css_alpha(0.8));
     let diff_hint = if state.secrets_unlocked() {
-        "Press N for new game, 1-8 for difficulty"
+        "Press N for new game, 1-8 for difficulty, R to replay"
     } else {
-        "Press N for new game, 1-6 for difficulty"
+        "Press N for new game, 1-6 for difficulty, R to replay"
     };
     let _ = ctx.fill_text(diff_hint, w / 2.0, h / 2.0 + 90.0);
 }
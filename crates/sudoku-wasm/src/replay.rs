@@ -0,0 +1,172 @@
+//! Replay of a recorded solve (from the anti-cheat move log)
+
+use crate::game::{MoveAction, MoveLogEntry, ScreenState};
+use sudoku_core::{Grid, Position};
+
+/// Playback state for watching a recorded solve
+pub struct Replay {
+    /// Original puzzle the log was recorded against
+    puzzle: Grid,
+    /// Recorded moves, in sequence order
+    log: Vec<MoveLogEntry>,
+    /// Board after applying the first `applied` moves
+    grid: Grid,
+    /// Number of log entries applied to `grid`
+    applied: usize,
+    /// Current playback time in milliseconds
+    playhead_ms: f64,
+    /// Whether playback advances automatically on tick
+    playing: bool,
+    /// Playback speed multiplier (1.0 = real time)
+    speed: f64,
+    /// Wall-clock time of the previous tick (ms)
+    last_tick_ms: Option<f64>,
+    /// Screen to return to when the replay is closed
+    return_screen: ScreenState,
+}
+
+impl Replay {
+    /// Create a replay positioned at the start of the solve
+    pub fn new(puzzle: &Grid, mut log: Vec<MoveLogEntry>, return_screen: ScreenState) -> Self {
+        log.sort_by_key(|e| e.seq);
+        let mut grid = puzzle.deep_clone();
+        grid.clear_all_candidates();
+        Self {
+            puzzle: puzzle.deep_clone(),
+            log,
+            grid,
+            applied: 0,
+            playhead_ms: 0.0,
+            playing: false,
+            speed: 1.0,
+            last_tick_ms: None,
+            return_screen,
+        }
+    }
+
+    /// Apply the next recorded move. Returns false at the end of the log.
+    pub fn step(&mut self) -> bool {
+        let Some(entry) = self.log.get(self.applied) else {
+            self.playing = false;
+            return false;
+        };
+        let (pos, value) = Self::effect(entry);
+        self.grid.set_cell_unchecked(pos, value);
+        self.playhead_ms = entry.ms as f64;
+        self.applied += 1;
+        true
+    }
+
+    /// Rewind by one move. Returns false at the start of the log.
+    pub fn step_back(&mut self) -> bool {
+        if self.applied == 0 {
+            return false;
+        }
+        let target = self.applied - 1;
+        self.rebuild(target);
+        self.playhead_ms = match target {
+            0 => 0.0,
+            n => self.log[n - 1].ms as f64,
+        };
+        true
+    }
+
+    /// Jump to the board state at `t_ms` milliseconds into the solve
+    pub fn seek(&mut self, t_ms: f64) {
+        let t_ms = t_ms.clamp(0.0, self.duration_ms() as f64);
+        let target = self.log.iter().take_while(|e| e.ms as f64 <= t_ms).count();
+        if target != self.applied {
+            self.rebuild(target);
+        }
+        self.playhead_ms = t_ms;
+    }
+
+    /// Advance automatic playback to wall-clock time `now_ms`
+    pub fn tick(&mut self, now_ms: f64) {
+        let dt = self.last_tick_ms.map(|t| now_ms - t).unwrap_or(0.0);
+        self.last_tick_ms = Some(now_ms);
+        if !self.playing {
+            return;
+        }
+        self.seek(self.playhead_ms + dt.max(0.0) * self.speed);
+        if self.applied >= self.log.len() {
+            self.playing = false;
+        }
+    }
+
+    /// Replay the first `count` moves from the original puzzle
+    fn rebuild(&mut self, count: usize) {
+        self.grid = self.puzzle.deep_clone();
+        self.grid.clear_all_candidates();
+        for entry in &self.log[..count] {
+            let (pos, value) = Self::effect(entry);
+            self.grid.set_cell_unchecked(pos, value);
+        }
+        self.applied = count;
+    }
+
+    /// The cell and resulting value a log entry produces
+    fn effect(entry: &MoveLogEntry) -> (Position, Option<u8>) {
+        let cell = (entry.cell as usize).min(80);
+        let pos = Position::new(cell / 9, cell % 9);
+        let value = match entry.action {
+            MoveAction::Place(v) | MoveAction::Hint(v) => Some(v),
+            MoveAction::Clear(_) => None,
+            MoveAction::Undo(v) | MoveAction::Redo(v) | MoveAction::Bookmark(v) => v,
+        };
+        (pos, value)
+    }
+
+    pub fn toggle_playing(&mut self) {
+        if self.applied >= self.log.len() {
+            self.rebuild(0);
+            self.playhead_ms = 0.0;
+        }
+        self.playing = !self.playing;
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.clamp(0.25, 32.0);
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+    pub fn total_moves(&self) -> usize {
+        self.log.len()
+    }
+    pub fn playhead_ms(&self) -> f64 {
+        self.playhead_ms
+    }
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+    pub fn return_screen(&self) -> ScreenState {
+        self.return_screen
+    }
+
+    /// Cell touched by the most recently applied move
+    pub fn last_cell(&self) -> Option<Position> {
+        self.applied
+            .checked_sub(1)
+            .map(|i| Self::effect(&self.log[i]).0)
+    }
+
+    /// Technique tagged on the most recently applied move
+    pub fn last_technique(&self) -> Option<&str> {
+        self.applied
+            .checked_sub(1)
+            .and_then(|i| self.log[i].technique.as_deref())
+    }
+
+    /// Timestamp of the final recorded move
+    pub fn duration_ms(&self) -> u32 {
+        self.log.last().map(|e| e.ms).unwrap_or(0)
+    }
+}
@@ -0,0 +1,114 @@
+//! Versioned save format for `get_state_json` / `load_state_json`
+//!
+//! Saves are wrapped as `{ "version": N, "state": { ... } }`. Older versions
+//! are migrated forward on load, and damaged saves are repaired where the
+//! puzzle itself is still readable.
+
+use crate::game::SerializableState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sudoku_core::{Grid, Solver};
+
+/// Current save format version
+pub const SAVE_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct SaveEnvelope {
+    version: u32,
+    state: Value,
+}
+
+/// Wrap a state in the current envelope
+pub fn encode(state: &SerializableState) -> String {
+    let envelope = SaveEnvelope {
+        version: SAVE_VERSION,
+        state: serde_json::to_value(state).unwrap_or(Value::Null),
+    };
+    serde_json::to_string(&envelope).unwrap_or_default()
+}
+
+/// Read a save of any known version, migrating and repairing as needed
+pub fn decode(json: &str) -> Result<SerializableState, String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("not JSON: {}", e))?;
+
+    // Version 1 saves were the bare state object
+    let (version, state) = match serde_json::from_value::<SaveEnvelope>(value.clone()) {
+        Ok(envelope) => (envelope.version, envelope.state),
+        Err(_) => (1, value),
+    };
+    if version > SAVE_VERSION {
+        return Err(format!("save version {} is newer than {}", version, SAVE_VERSION));
+    }
+
+    let state = migrate(version, state)?;
+    let mut state: SerializableState =
+        serde_json::from_value(state).map_err(|e| format!("unreadable state: {}", e))?;
+    repair(&mut state)?;
+    Ok(state)
+}
+
+/// Bring a state object from `version` up to the current format
+fn migrate(version: u32, state: Value) -> Result<Value, String> {
+    let mut state = state;
+    let mut version = version;
+    while version < SAVE_VERSION {
+        state = match version {
+            1 => migrate_v1(state),
+            _ => return Err(format!("no migration from version {}", version)),
+        };
+        version += 1;
+    }
+    Ok(state)
+}
+
+/// v1 → v2: v1 had no cell colors; anything else missing gets its serde default
+fn migrate_v1(mut state: Value) -> Value {
+    if let Some(obj) = state.as_object_mut() {
+        obj.entry("cell_colors").or_insert_with(|| Value::Array(Vec::new()));
+    }
+    state
+}
+
+/// Fix inconsistencies in a decoded state. Fails only if the puzzle itself
+/// is unreadable or unsolvable.
+fn repair(state: &mut SerializableState) -> Result<(), String> {
+    let puzzle = Grid::from_string(&state.puzzle).ok_or("unreadable puzzle")?;
+    let solver = Solver::new();
+    let solved = solver.solve(&puzzle).ok_or("puzzle has no solution")?;
+
+    let is_given = |c: char| ('1'..='9').contains(&c);
+    let puzzle_chars: Vec<char> = state.puzzle.chars().collect();
+
+    // Progress must keep every given; otherwise restart from the puzzle
+    let current_ok = state.current.chars().count() == 81
+        && Grid::from_string(&state.current).is_some()
+        && state
+            .current
+            .chars()
+            .zip(&puzzle_chars)
+            .all(|(c, &p)| !is_given(p) || c == p);
+    if !current_ok {
+        state.current = state.puzzle.clone();
+    }
+
+    // The stored solution must be complete and agree with the givens
+    let solution_ok = Grid::from_string(&state.solution).is_some_and(|g| g.is_complete())
+        && state
+            .solution
+            .chars()
+            .zip(&puzzle_chars)
+            .all(|(c, &p)| !is_given(p) || c == p);
+    if !solution_ok {
+        state.solution = solved.to_string_compact();
+    }
+
+    if let Some(limit) = state.settings.mistake_limit() {
+        state.mistakes = state.mistakes.min(limit as usize);
+    }
+    state.cursor_row = state.cursor_row.min(8);
+    state.cursor_col = state.cursor_col.min(8);
+    if state.cell_colors.len() != 81 {
+        state.cell_colors.clear();
+    }
+    Ok(())
+}
@@ -2,8 +2,25 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::game::{GameState, InputMode, ScreenState, MAX_MISTAKES};
+    use crate::game::{GameEvent, GameState, InputMode, ScreenState, MAX_MISTAKES};
     use sudoku_core::{Difficulty, Position};
+    use sudoku_session::{GameMode, GameSettings, MistakePolicy};
+
+    /// Walk the cursor to `pos` with vim keys
+    fn move_cursor_to(state: &mut GameState, pos: Position) {
+        while state.cursor() != pos {
+            let key = if state.cursor().row < pos.row {
+                "j"
+            } else if state.cursor().row > pos.row {
+                "k"
+            } else if state.cursor().col < pos.col {
+                "l"
+            } else {
+                "h"
+            };
+            state.handle_key(key, false, false);
+        }
+    }
 
     #[test]
     fn test_game_state_new() {
@@ -337,4 +354,463 @@ mod tests {
             assert!(cell.candidates().count() > 0);
         }
     }
+
+    #[test]
+    fn test_remaining_difficulty_falls() {
+        let mut state = GameState::new(Difficulty::Hard);
+        let level = |d: Difficulty| Difficulty::all_levels().iter().position(|&l| l == d);
+        let start = state.remaining_difficulty();
+
+        // Fill all but one empty cell
+        let empty: Vec<Position> = (0..81)
+            .map(|i| Position::new(i / 9, i % 9))
+            .filter(|&p| state.grid().get(p).is_none())
+            .collect();
+        for &pos in &empty[1..] {
+            let value = state.solution().get(pos).unwrap();
+            move_cursor_to(&mut state, pos);
+            state.handle_key(&value.to_string(), false, false);
+        }
+        let end = state.remaining_difficulty();
+        assert!(level(end) <= level(start));
+        assert_eq!(state.remaining_difficulty(), end);
+    }
+
+    #[test]
+    fn test_placement_tagged_with_technique() {
+        let mut state = GameState::new(Difficulty::Easy);
+        let mut grid = state.grid().deep_clone();
+        grid.recalculate_candidates();
+        let hint = sudoku_core::Solver::new().get_hint(&grid).unwrap();
+        let sudoku_core::HintType::SetValue { pos, value } = hint.hint_type else {
+            panic!("an easy puzzle starts with a single");
+        };
+        move_cursor_to(&mut state, pos);
+        state.handle_key(&value.to_string(), false, false);
+
+        let log: serde_json::Value = serde_json::from_str(&state.move_log_json()).unwrap();
+        assert!(log[0]["technique"].is_string());
+    }
+
+    #[test]
+    fn test_replay_rebuilds_recorded_moves() {
+        let mut state = GameState::new(Difficulty::Easy);
+
+        // Nothing recorded yet
+        assert!(!state.start_replay(None));
+
+        // Find an empty cell and place its solution value
+        let pos = (0..81)
+            .map(|i| Position::new(i / 9, i % 9))
+            .find(|&p| state.grid().get(p).is_none())
+            .expect("puzzle should have empty cells");
+        let value = state.solution().get(pos).unwrap();
+        move_cursor_to(&mut state, pos);
+        state.handle_key(&value.to_string(), false, false);
+
+        assert!(state.start_replay(None));
+        assert_eq!(state.screen(), ScreenState::Replay);
+        assert_eq!(state.replay().unwrap().grid().get(pos), None);
+
+        assert!(state.replay_step());
+        assert_eq!(state.replay().unwrap().grid().get(pos), Some(value));
+        assert!(!state.replay_step());
+
+        state.handle_key("Escape", false, false);
+        assert_eq!(state.screen(), ScreenState::Playing);
+        assert!(state.replay().is_none());
+    }
+
+    #[test]
+    fn test_describe_board_and_announcements() {
+        let mut state = GameState::new(Difficulty::Easy);
+
+        let board = crate::a11y::describe_board(&state);
+        assert_eq!(board.lines().count(), 10); // summary + 9 rows
+        assert!(board.contains("Row 9:"));
+
+        let cell = crate::a11y::describe_cell(&state, Position::new(4, 4));
+        assert!(cell.starts_with("Row 5, column 5:"));
+
+        // Moving the cursor announces the new cell
+        state.handle_key("ArrowUp", false, false);
+        let announcement = state.take_announcement().unwrap();
+        assert!(announcement.starts_with("Row 4, column 5:"));
+        assert!(state.take_announcement().is_none());
+
+        // Screen changes are announced
+        state.handle_key("p", false, false);
+        assert!(state.take_announcement().unwrap().starts_with("Paused"));
+    }
+
+    #[test]
+    fn test_autopause_on_hidden_page() {
+        let mut state = GameState::new(Difficulty::Easy);
+
+        // Disabled by default
+        state.notify_visibility(false);
+        assert_eq!(state.screen(), ScreenState::Playing);
+
+        state.set_autopause(true);
+        state.notify_visibility(false);
+        assert_eq!(state.screen(), ScreenState::Paused);
+
+        // Becoming visible again doesn't resume on its own
+        state.notify_visibility(true);
+        assert_eq!(state.screen(), ScreenState::Paused);
+    }
+
+    #[test]
+    fn test_wrong_placement_emits_mistake_events() {
+        let mut state = GameState::new(Difficulty::Easy);
+        let pos = (0..81)
+            .map(|i| Position::new(i / 9, i % 9))
+            .find(|&p| state.grid().get(p).is_none())
+            .expect("puzzle should have empty cells");
+        let correct = state.solution().get(pos).unwrap();
+        let wrong = correct % 9 + 1;
+
+        move_cursor_to(&mut state, pos);
+        state.take_events();
+        state.handle_key(&wrong.to_string(), false, false);
+
+        let events = state.take_events();
+        assert_eq!(
+            events[0],
+            GameEvent::MoveMade {
+                row: pos.row,
+                col: pos.col,
+                value: wrong,
+                correct: false,
+            }
+        );
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GameEvent::Mistake { mistakes: 1, .. })));
+        assert!(state.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_settings_without_mistake_limit() {
+        let mut state = GameState::new(Difficulty::Easy);
+        state.set_settings(GameSettings {
+            mistakes: MistakePolicy::Count,
+            ..GameSettings::default()
+        });
+        let pos = (0..81)
+            .map(|i| Position::new(i / 9, i % 9))
+            .find(|&p| state.grid().get(p).is_none())
+            .expect("puzzle should have empty cells");
+        let wrong = state.solution().get(pos).unwrap() % 9 + 1;
+
+        move_cursor_to(&mut state, pos);
+        for _ in 0..MAX_MISTAKES + 1 {
+            state.handle_key(&wrong.to_string(), false, false);
+            state.handle_key("Backspace", false, false);
+        }
+        assert_eq!(state.mistakes(), MAX_MISTAKES + 1);
+        assert!(!state.is_game_over());
+
+        // The settings travel with the save
+        let restored = GameState::from_serializable(state.to_serializable());
+        assert_eq!(restored.settings().mistakes, MistakePolicy::Count);
+    }
+
+    #[test]
+    fn test_batch_notes_undo_as_one_step() {
+        let mut state = GameState::new(Difficulty::Easy);
+        let empty: Vec<Position> = (0..81)
+            .map(|i| Position::new(i / 9, i % 9))
+            .filter(|&p| state.grid().get(p).is_none())
+            .collect();
+        let (a, b) = (empty[0], empty[1]);
+        for &p in &[a, b] {
+            state.select_cell(p, false);
+            state.handle_key("x", false, false);
+        }
+
+        state.select_cell(a, false);
+        state.select_cell(b, true);
+        assert!(state.is_selected(a) && state.is_selected(b));
+
+        state.handle_key("c", false, false);
+        state.handle_key("4", false, false);
+        assert!(state.grid().cell(a).has_candidate(4));
+        assert!(state.grid().cell(b).has_candidate(4));
+
+        // A single undo removes the note from both cells
+        state.handle_key("u", false, false);
+        assert!(!state.grid().cell(a).has_candidate(4));
+        assert!(!state.grid().cell(b).has_candidate(4));
+
+        // Plain navigation drops the selection
+        state.handle_key("ArrowDown", false, false);
+        assert!(!state.is_selected(a));
+    }
+
+    #[test]
+    fn test_hardcore_and_hint_challenge_modes() {
+        let mut state = GameState::new(Difficulty::Easy);
+        state.set_game_mode(GameMode::Hardcore);
+        state.handle_key("F", true, false);
+        for pos in (0..81).map(|i| Position::new(i / 9, i % 9)) {
+            assert!(state.grid().cell(pos).candidates().is_empty());
+        }
+
+        state.set_game_mode(GameMode::HintChallenge { max_hints: 1 });
+        state.handle_key("!", false, false);
+        state.handle_key("!", false, false);
+        assert_eq!(state.hints_used(), 1);
+
+        // The mode travels with the save
+        let restored = GameState::from_serializable(state.to_serializable());
+        assert_eq!(
+            restored.game_mode(),
+            GameMode::HintChallenge { max_hints: 1 }
+        );
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        let mut state = GameState::new(Difficulty::Easy);
+        let pos = (0..81)
+            .map(|i| Position::new(i / 9, i % 9))
+            .find(|&p| state.grid().get(p).is_none())
+            .expect("puzzle should have empty cells");
+        state.save_bookmark("start");
+
+        let value = state.solution().get(pos).unwrap();
+        move_cursor_to(&mut state, pos);
+        state.handle_key(&value.to_string(), false, false);
+        assert!(!state.restore_bookmark("missing"));
+        assert!(state.restore_bookmark("start"));
+        assert_eq!(state.grid().get(pos), None);
+
+        // Going back is one undo step, and the bookmark travels with the save
+        state.handle_key("u", false, false);
+        assert_eq!(state.grid().get(pos), Some(value));
+        let mut restored = GameState::from_serializable(state.to_serializable());
+        assert_eq!(restored.bookmarks()[0].name, "start");
+        assert!(restored.delete_bookmark("start"));
+        assert!(restored.bookmarks().is_empty());
+    }
+
+    #[test]
+    fn test_cell_colors_survive_save() {
+        let mut state = GameState::new(Difficulty::Easy);
+        state.handle_key("o", false, false);
+        assert_eq!(state.mode(), InputMode::Color);
+
+        state.select_cell(Position::new(0, 0), false);
+        state.select_cell(Position::new(0, 1), true);
+        state.handle_key("3", false, false);
+        assert_eq!(state.cell_color(Position::new(0, 1)), 3);
+
+        let restored = GameState::from_serializable(state.to_serializable());
+        assert_eq!(restored.cell_color(Position::new(0, 0)), 3);
+        assert_eq!(restored.cell_color(Position::new(0, 1)), 3);
+        assert_eq!(restored.cell_color(Position::new(0, 2)), 0);
+
+        // Painting the same color again erases it
+        state.handle_key("3", false, false);
+        assert_eq!(state.cell_color(Position::new(0, 0)), 0);
+    }
+
+    #[test]
+    fn test_load_from_pencilmarks() {
+        let original = GameState::new(Difficulty::Easy);
+        let puzzle = original.puzzle_string();
+
+        // Givens as single digits, empty cells with every candidate, boxed like a pencilmark grid
+        let mut text = String::new();
+        for (i, c) in puzzle.chars().enumerate() {
+            match c.to_digit(10) {
+                Some(d) if d > 0 => text.push_str(&d.to_string()),
+                _ => text.push_str("123456789"),
+            }
+            text.push_str(if i % 9 == 8 { "\n" } else if i % 3 == 2 { " | " } else { " " });
+            if i % 27 == 26 {
+                text.push_str("------+------+------\n");
+            }
+        }
+
+        let cells = crate::game::parse_pencilmarks(&text).unwrap();
+        assert_eq!(cells.len(), 81);
+
+        let loaded = GameState::from_pencilmarks(&text).unwrap();
+        assert_eq!(loaded.puzzle_string(), puzzle);
+
+        // Too few cells is rejected
+        assert!(crate::game::parse_pencilmarks("1 2 3").is_none());
+    }
+
+    #[test]
+    fn test_font_config_falls_back() {
+        use crate::theme::FontConfig;
+
+        let font = FontConfig::new("Inter", "600", 0.4);
+        assert_eq!(font.digit_font(20.0), "600 20px Inter, monospace");
+
+        // Bad weight and out-of-range note size are repaired
+        let font = FontConfig::new("", "heavy", 5.0);
+        assert_eq!(font.digit_weight, "normal");
+        assert_eq!(font.note_scale, 0.6);
+        assert!(font.family.contains("monospace"));
+    }
+
+    #[test]
+    fn test_save_envelope_migrates_and_repairs() {
+        use crate::save;
+
+        let state = GameState::new(Difficulty::Easy);
+        let saved = state.to_serializable();
+
+        // Current format round-trips
+        let json = save::encode(&saved);
+        assert!(json.contains("\"version\":2"));
+        assert_eq!(save::decode(&json).unwrap().puzzle, saved.puzzle);
+
+        // A bare v1 state still loads
+        let v1 = serde_json::to_string(&saved).unwrap();
+        assert_eq!(save::decode(&v1).unwrap().current, saved.current);
+
+        // Progress that overwrote a given is reset to the puzzle
+        let mut damaged = saved.clone();
+        let given = damaged.puzzle.find(|c: char| ('1'..='9').contains(&c)).unwrap();
+        let bad = if &damaged.current[given..given + 1] == "1" { "2" } else { "1" };
+        damaged.current.replace_range(given..given + 1, bad);
+        damaged.mistakes = 99;
+        let repaired = save::decode(&save::encode(&damaged)).unwrap();
+        assert_eq!(repaired.current, saved.puzzle);
+        assert_eq!(repaired.mistakes, MAX_MISTAKES);
+
+        // Unreadable saves are rejected
+        assert!(save::decode("{\"version\":1,\"state\":{}}").is_err());
+        assert!(save::decode("not json").is_err());
+        assert!(save::decode("{\"version\":99,\"state\":{}}").is_err());
+    }
+
+    #[test]
+    fn test_frame_clock_steps_at_fixed_rate() {
+        use crate::clock::FrameClock;
+
+        // 120 Hz frames run a logic step every other frame
+        let mut clock = FrameClock::default();
+        let steps: u32 = (0..120).map(|_| clock.advance(1000.0 / 120.0)).sum();
+        assert!((59..=60).contains(&steps));
+
+        // A throttled frame catches up, but only up to the cap
+        let mut clock = FrameClock::default();
+        assert_eq!(clock.advance(55.0), 3);
+        assert_eq!(clock.advance(5000.0), 8);
+        assert_eq!(clock.alpha(), 0.0);
+    }
+
+    #[test]
+    fn test_parse_lossy_reports_issues() {
+        use crate::import::{parse_lossy, ParseIssue};
+
+        let puzzle = GameState::new(Difficulty::Easy).puzzle_string();
+        let drawn: String = puzzle
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let c = if c == '.' { '0' } else { c };
+                if i % 9 == 8 {
+                    format!("{}\n", c)
+                } else {
+                    format!("{} | ", c)
+                }
+            })
+            .collect();
+        let lossy = parse_lossy(&format!("# pasted\n{}", drawn));
+        assert!(lossy.issues.is_empty());
+        assert_eq!(lossy.puzzle, puzzle);
+
+        let lossy = parse_lossy("12x4");
+        assert_eq!(lossy.puzzle.len(), 81);
+        assert_eq!(
+            lossy.issues,
+            vec![
+                ParseIssue::UnexpectedChar {
+                    line: 1,
+                    column: 3,
+                    ch: 'x'
+                },
+                ParseIssue::WrongCellCount { found: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streak_json() {
+        use crate::{streak_complete, streak_status_json};
+
+        let mut tracker = String::new();
+        for day in [20_000, 20_001, 20_002] {
+            tracker = streak_complete(&tracker, day, 240);
+        }
+        let status: serde_json::Value =
+            serde_json::from_str(&streak_status_json(&tracker, 20_003)).unwrap();
+        assert_eq!(status["current"], 3);
+        assert_eq!(status["completed"].as_array().unwrap().len(), 3);
+        let broken: serde_json::Value =
+            serde_json::from_str(&streak_status_json(&tracker, 20_010)).unwrap();
+        assert_eq!(broken["current"], 0);
+        assert_eq!(broken["best"], 3);
+    }
+
+    #[test]
+    fn test_puzzle_search() {
+        use crate::PuzzleSearch;
+
+        let mut search = PuzzleSearch::new(2.0);
+        assert!(search.best_se().is_none());
+        assert_eq!(search.result_json(), "");
+        search.step(2);
+        assert!((1..=2).contains(&search.attempts()));
+        let result: serde_json::Value = serde_json::from_str(&search.result_json()).unwrap();
+        assert_eq!(result["puzzle_string"].as_str().unwrap().len(), 81);
+        assert_eq!(
+            result["se_rating"].as_f64(),
+            search.best_se().map(f64::from)
+        );
+    }
+
+    #[test]
+    fn test_trainer() {
+        let mut state = GameState::new(Difficulty::Easy);
+        assert!(!state.start_trainer("Not A Technique", Difficulty::Easy));
+        assert_eq!(state.screen(), ScreenState::Playing);
+
+        assert!(state.start_trainer("Naked Single", Difficulty::Easy));
+        assert_eq!(state.screen(), ScreenState::Trainer);
+        assert!(!state.start_trainer("Naked Single", Difficulty::Easy));
+        let target = state.trainer().unwrap().targets()[0];
+
+        // Clicking a cell picks it and clicking again drops it
+        state.select_cell(target, false);
+        assert!(state.trainer().unwrap().is_picked(target));
+        state.select_cell(target, false);
+        assert!(!state.trainer().unwrap().is_picked(target));
+
+        move_cursor_to(&mut state, target);
+        state.handle_key(" ", false, false);
+        state.handle_key("Enter", false, false);
+        let trainer = state.trainer().unwrap();
+        assert_eq!(trainer.verdict(), Some(true));
+        assert_eq!((trainer.solved(), trainer.attempts()), (1, 1));
+        assert_eq!(state.message(), Some("Correct!"));
+
+        // A fresh position, checked with nothing picked
+        state.handle_key("n", false, false);
+        assert_eq!(state.trainer().unwrap().verdict(), None);
+        assert!(!state.trainer_check());
+        let trainer = state.trainer().unwrap();
+        assert_eq!((trainer.solved(), trainer.attempts()), (1, 2));
+
+        state.handle_key("Escape", false, false);
+        assert!(state.trainer().is_none());
+        assert_eq!(state.screen(), ScreenState::Playing);
+    }
 }
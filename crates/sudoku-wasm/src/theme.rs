@@ -41,6 +41,8 @@ pub struct Theme {
     pub cursor_bg: Color,
     /// Same value highlight
     pub same_value_bg: Color,
+    /// Multi-cell selection background
+    pub selection_bg: Color,
     /// Given number color
     pub given_text: Color,
     /// Player-entered number color
@@ -86,8 +88,98 @@ pub struct Theme {
     pub hint_technique_text: Color,
     /// Explanation body text color
     pub hint_explain_text: Color,
+    /// Typography for digits and notes on the board
+    #[serde(default)]
+    pub font: FontConfig,
 }
 
+/// Board typography. Panels keep the monospace font since their text is
+/// laid out in columns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontConfig {
+    /// CSS font-family list
+    pub family: String,
+    /// CSS font-weight for placed digits ("normal", "bold", "100"-"900")
+    pub digit_weight: String,
+    /// Note size as a fraction of the digit size
+    pub note_scale: f64,
+}
+
+impl Default for FontConfig {
+    fn default() -> Self {
+        Self {
+            family: "'JetBrains Mono', 'Fira Code', 'Consolas', monospace".to_string(),
+            digit_weight: "normal".to_string(),
+            note_scale: 0.45,
+        }
+    }
+}
+
+impl FontConfig {
+    /// Build a config from host input, falling back to defaults for invalid parts
+    pub fn new(family: &str, digit_weight: &str, note_scale: f64) -> Self {
+        let default = Self::default();
+        let family = family.trim();
+        let weight = digit_weight.trim();
+        let weight_ok = matches!(weight, "normal" | "bold" | "lighter" | "bolder")
+            || weight
+                .parse::<u16>()
+                .is_ok_and(|w| (100..=900).contains(&w) && w % 100 == 0);
+        Self {
+            family: if family.is_empty() {
+                default.family
+            } else {
+                family.to_string()
+            },
+            digit_weight: if weight_ok {
+                weight.to_string()
+            } else {
+                default.digit_weight
+            },
+            note_scale: if note_scale.is_finite() {
+                note_scale.clamp(0.25, 0.6)
+            } else {
+                default.note_scale
+            },
+        }
+    }
+
+    /// Font-family list ending in a generic family, so a missing web font
+    /// still falls back to something with digits
+    fn family_css(&self) -> String {
+        let generic = ["monospace", "sans-serif", "serif", "system-ui", "cursive"];
+        let last = self.family.rsplit(',').next().unwrap_or("").trim();
+        if generic.contains(&last) {
+            self.family.clone()
+        } else {
+            format!("{}, monospace", self.family)
+        }
+    }
+
+    /// CSS font for placed digits at `size` px
+    pub fn digit_font(&self, size: f64) -> String {
+        format!("{} {}px {}", self.digit_weight, size, self.family_css())
+    }
+
+    /// CSS font for notes, scaled from the digit size
+    pub fn note_font(&self, digit_size: f64) -> String {
+        format!("bold {}px {}", digit_size * self.note_scale, self.family_css())
+    }
+}
+
+/// Palette for player cell coloring, indexed by color number - 1.
+/// Drawn translucently over the cell background so it works with every theme.
+pub const CELL_PALETTE: [Color; 8] = [
+    Color::new(230, 80, 80),   // red
+    Color::new(240, 150, 50),  // orange
+    Color::new(230, 210, 60),  // yellow
+    Color::new(80, 190, 90),   // green
+    Color::new(70, 190, 200),  // cyan
+    Color::new(70, 120, 230),  // blue
+    Color::new(160, 90, 210),  // purple
+    Color::new(230, 110, 180), // pink
+];
+
 impl Theme {
     /// Dark theme (default)
     pub fn dark() -> Self {
@@ -99,6 +191,7 @@ impl Theme {
             highlight_bg: Color::new(48, 48, 64),
             cursor_bg: Color::new(70, 100, 150),
             same_value_bg: Color::new(60, 80, 100),
+            selection_bg: Color::new(90, 80, 150),
             given_text: Color::new(200, 200, 220),
             player_text: Color::new(100, 180, 255),
             candidate_text: Color::new(120, 120, 140),
@@ -121,6 +214,7 @@ impl Theme {
             hint_panel_bg: Color::new(20, 30, 20),
             hint_technique_text: Color::new(100, 220, 140),
             hint_explain_text: Color::new(200, 200, 200),
+            font: FontConfig::default(),
         }
     }
 
@@ -134,6 +228,7 @@ impl Theme {
             highlight_bg: Color::new(237, 228, 210),
             cursor_bg: Color::new(200, 185, 155),
             same_value_bg: Color::new(220, 210, 188),
+            selection_bg: Color::new(214, 200, 228),
             given_text: Color::new(20, 20, 20),    // --ink
             player_text: Color::new(10, 132, 255), // --accent2
             candidate_text: Color::new(150, 144, 130),
@@ -156,6 +251,7 @@ impl Theme {
             hint_panel_bg: Color::new(240, 235, 225),
             hint_technique_text: Color::new(20, 120, 60),
             hint_explain_text: Color::new(40, 40, 40),
+            font: FontConfig::default(),
         }
     }
 
@@ -169,6 +265,7 @@ impl Theme {
             highlight_bg: Color::new(40, 40, 60),
             cursor_bg: Color::new(0, 80, 160),
             same_value_bg: Color::new(60, 60, 0),
+            selection_bg: Color::new(90, 0, 120),
             given_text: Color::new(255, 255, 255),
             player_text: Color::new(0, 255, 255),
             candidate_text: Color::new(150, 150, 150),
@@ -191,6 +288,44 @@ impl Theme {
             hint_panel_bg: Color::new(0, 20, 0),
             hint_technique_text: Color::new(0, 255, 100),
             hint_explain_text: Color::new(255, 255, 255),
+            font: FontConfig::default(),
+        }
+    }
+
+    /// Print theme — white paper, black ink, no tinted highlights
+    pub fn print() -> Self {
+        Self {
+            background: Color::new(255, 255, 255),
+            grid_lines: Color::new(0, 0, 0),
+            box_border: Color::new(0, 0, 0),
+            cell_bg: Color::new(255, 255, 255),
+            highlight_bg: Color::new(255, 255, 255),
+            cursor_bg: Color::new(255, 255, 255),
+            same_value_bg: Color::new(255, 255, 255),
+            selection_bg: Color::new(255, 255, 255),
+            given_text: Color::new(0, 0, 0),
+            player_text: Color::new(70, 70, 70),
+            candidate_text: Color::new(110, 110, 110),
+            error_text: Color::new(0, 0, 0),
+            completed_bg: Color::new(255, 255, 255),
+            info_text: Color::new(0, 0, 0),
+            message_text: Color::new(0, 0, 0),
+            win_color: Color::new(0, 0, 0),
+            lose_color: Color::new(0, 0, 0),
+            hint_involved_bg: Color::new(255, 255, 255),
+            hint_target_bg: Color::new(255, 255, 255),
+            hint_chain_on: Color::new(255, 255, 255),
+            hint_chain_off: Color::new(255, 255, 255),
+            hint_fish_base: Color::new(255, 255, 255),
+            hint_fish_cover: Color::new(255, 255, 255),
+            hint_fish_fin: Color::new(255, 255, 255),
+            hint_ur_floor: Color::new(255, 255, 255),
+            hint_ur_roof: Color::new(255, 255, 255),
+            hint_als_group: Color::new(255, 255, 255),
+            hint_panel_bg: Color::new(255, 255, 255),
+            hint_technique_text: Color::new(0, 0, 0),
+            hint_explain_text: Color::new(0, 0, 0),
+            font: FontConfig::default(),
         }
     }
 }
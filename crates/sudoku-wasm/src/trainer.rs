@@ -0,0 +1,119 @@
+//! Technique trainer: practise spotting one technique in real positions
+
+use crate::game::ScreenState;
+use sudoku_core::{Difficulty, Grid, Hint, HintType, Position};
+use sudoku_session::find_technique;
+
+/// A trainer session for one technique
+pub struct Trainer {
+    /// Technique being practised, as typed by the player
+    technique: String,
+    /// Difficulty used to generate positions
+    difficulty: Difficulty,
+    /// Position (with full candidates) where the technique applies
+    grid: Grid,
+    /// The engine's finding for that position
+    hint: Hint,
+    /// Cells the player has marked
+    picked: Vec<Position>,
+    /// Result of the last check (None = not checked yet)
+    verdict: Option<bool>,
+    /// Positions answered correctly
+    solved: u32,
+    /// Positions checked
+    attempts: u32,
+    /// Screen to return to when the trainer is closed
+    return_screen: ScreenState,
+}
+
+impl Trainer {
+    /// Start a session, or None if no position using the technique turned up
+    pub fn new(technique: &str, difficulty: Difficulty, return_screen: ScreenState) -> Option<Self> {
+        let (grid, hint) = find_technique(technique, difficulty)?;
+        Some(Self {
+            technique: technique.to_string(),
+            difficulty,
+            grid,
+            hint,
+            picked: Vec::new(),
+            verdict: None,
+            solved: 0,
+            attempts: 0,
+            return_screen,
+        })
+    }
+
+    /// Move on to a fresh position. Returns false if none was found.
+    pub fn next_position(&mut self) -> bool {
+        let Some((grid, hint)) = find_technique(&self.technique, self.difficulty) else {
+            return false;
+        };
+        self.grid = grid;
+        self.hint = hint;
+        self.picked.clear();
+        self.verdict = None;
+        true
+    }
+
+    /// Mark or unmark a cell as part of the answer
+    pub fn toggle_pick(&mut self, pos: Position) {
+        if self.verdict.is_some() {
+            return;
+        }
+        if let Some(i) = self.picked.iter().position(|&p| p == pos) {
+            self.picked.remove(i);
+        } else {
+            self.picked.push(pos);
+        }
+    }
+
+    /// Compare the marked cells with the engine's finding
+    pub fn check(&mut self) -> bool {
+        if let Some(verdict) = self.verdict {
+            return verdict;
+        }
+        let targets = self.targets();
+        let correct = self.picked.len() == targets.len()
+            && targets.iter().all(|p| self.picked.contains(p));
+        self.attempts += 1;
+        if correct {
+            self.solved += 1;
+        }
+        self.verdict = Some(correct);
+        correct
+    }
+
+    /// Cells the finding acts on (placement or elimination)
+    pub fn targets(&self) -> Vec<Position> {
+        match self.hint.hint_type {
+            HintType::SetValue { pos, .. } | HintType::EliminateCandidates { pos, .. } => {
+                vec![pos]
+            }
+        }
+    }
+
+    pub fn technique(&self) -> &str {
+        &self.technique
+    }
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+    pub fn hint(&self) -> &Hint {
+        &self.hint
+    }
+    pub fn is_picked(&self, pos: Position) -> bool {
+        self.picked.contains(&pos)
+    }
+    pub fn verdict(&self) -> Option<bool> {
+        self.verdict
+    }
+    pub fn solved(&self) -> u32 {
+        self.solved
+    }
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+    pub fn return_screen(&self) -> ScreenState {
+        self.return_screen
+    }
+}
@@ -0,0 +1,16 @@
+//! `Grid::from_string` takes puzzle strings from URLs and the FFI. It must
+//! never panic, and a grid it accepts must survive a round trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sudoku_core::Grid;
+
+fuzz_target!(|text: &str| {
+    if let Some(grid) = Grid::from_string(text) {
+        let compact = grid.to_string_compact();
+        let again = Grid::from_string(&compact).expect("compact form should parse");
+        assert_eq!(again.to_string_compact(), compact);
+        let _ = grid.validate();
+    }
+});
@@ -0,0 +1,45 @@
+//! Strip candidates from a tier fixture at random, never the solution's
+//! digit, then follow the solver's hints. Whatever the pencilmarks look
+//! like, a hint must not place a wrong digit or eliminate a right one.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sudoku_core::{Grid, Position, Solver};
+use sudoku_test_utils::{apply_hint, assert_hint_sound, solution_digits, Tier};
+
+/// Hints followed per input, enough to reach the harder techniques
+const MAX_STEPS: usize = 30;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&pick, eliminations)) = data.split_first() else {
+        return;
+    };
+    let tier = Tier::ALL[usize::from(pick) % Tier::ALL.len()];
+    let mut grid = Grid::from_string(tier.puzzle()).unwrap();
+    let solution = solution_digits(&grid);
+    grid.recalculate_candidates();
+
+    for pair in eliminations.chunks_exact(2) {
+        let cell = usize::from(pair[0]) % 81;
+        let value = pair[1] % 9 + 1;
+        let pos = Position::new(cell / 9, cell % 9);
+        if grid.cell(pos).value().is_none() && value != solution[cell] {
+            grid.cell_mut(pos).remove_candidate(value);
+        }
+    }
+
+    let solver = Solver::new();
+    for _ in 0..MAX_STEPS {
+        if grid.is_complete() {
+            break;
+        }
+        let Some(hint) = solver.get_hint(&grid) else {
+            break;
+        };
+        assert_hint_sound(&solution, &hint);
+        if !apply_hint(&mut grid, &hint) {
+            break;
+        }
+    }
+});
@@ -0,0 +1,18 @@
+//! Pencilmark grids pasted into the web app: 81 cells of sorted, distinct
+//! digits 1-9, or nothing.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sudoku_wasm::parse_pencilmarks;
+
+fuzz_target!(|text: &str| {
+    if let Some(cells) = parse_pencilmarks(text) {
+        assert_eq!(cells.len(), 81);
+        for digits in &cells {
+            assert!(!digits.is_empty());
+            assert!(digits.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(digits.iter().all(|d| (1..=9).contains(d)));
+        }
+    }
+});
@@ -0,0 +1,13 @@
+//! Save files from the mobile apps: any JSON must load or be rejected
+//! without panicking, and a loaded game must save and load again.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sudoku_ffi::game_deserialize;
+
+fuzz_target!(|json: &str| {
+    if let Some(game) = game_deserialize(json.to_string()) {
+        assert!(game_deserialize(game.serialize()).is_some());
+    }
+});